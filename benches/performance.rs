@@ -13,6 +13,9 @@
 //! cargo bench --bench performance contact_detection
 //! cargo bench --bench performance kdtree
 //! cargo bench --bench performance pipeline
+//! cargo bench --bench performance component_labeling
+//! cargo bench --bench performance spatial_index
+//! cargo bench --bench performance detection_algorithm
 //! ```
 //!
 //! View HTML reports:
@@ -26,6 +29,9 @@
 //! - **contact_detection**: Tests contact pair detection at different scales
 //! - **kdtree**: Tests k-d tree construction and query performance
 //! - **pipeline**: Tests complete end-to-end pipeline
+//! - **component_labeling**: Tests union-find body labeling over shared faces
+//! - **spatial_index**: Compares the k-d tree and the grid hash over dense surfaces
+//! - **detection_algorithm**: Compares the built-in contact detection algorithms
 //!
 //! # Scale Targets
 //!
@@ -34,11 +40,14 @@
 //! - 100K elements: Large test case
 //! - 1M elements: Target scale (should complete in ≤30s)
 
+use contact_detector::contact::algorithm::DetectionAlgorithmKind;
 use contact_detector::contact::detection::detect_contact_pairs;
+use contact_detector::contact::spatial_index::{GridHash, SpatialIndexKind};
 use contact_detector::contact::types::ContactCriteria;
+use contact_detector::mesh::components::connected_components;
 use contact_detector::mesh::surface::extract_surface;
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
-use kiddo::KdTree;
+use kiddo::{ImmutableKdTree, KdTree};
 
 mod synthetic_mesh;
 use synthetic_mesh::{calculate_grid_dimensions, generate_hex_grid, generate_parallel_surfaces};
@@ -75,6 +84,31 @@ fn benchmark_surface_extraction(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark connected-component labeling of a whole block
+fn benchmark_component_labeling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("component_labeling");
+
+    let scales = vec![("1K", 1_000), ("10K", 10_000), ("100K", 100_000)];
+
+    for (name, target_elements) in scales {
+        let (nx, ny, nz) = calculate_grid_dimensions(target_elements);
+        let actual_elements = nx * ny * nz;
+
+        let mesh = generate_hex_grid(nx, ny, nz, 1.0);
+        let elements: Vec<usize> = (0..mesh.num_elements()).collect();
+
+        group.throughput(Throughput::Elements(actual_elements as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &mesh, |b, mesh| {
+            b.iter(|| {
+                let bodies = connected_components(black_box(mesh), black_box(&elements));
+                black_box(bodies);
+            });
+        });
+    }
+
+    group.finish();
+}
+
 /// Benchmark k-d tree construction and queries
 fn benchmark_kdtree(c: &mut Criterion) {
     let mut group = c.benchmark_group("kdtree");
@@ -200,6 +234,115 @@ fn benchmark_contact_detection(c: &mut Criterion) {
     group.finish();
 }
 
+/// Compare the k-d tree and the grid hash over the face centroids of uniform
+/// parallel surfaces: index construction, one radius query per face of the
+/// other surface, and full contact detection
+///
+/// "sparse" surfaces have faces much larger than the search radius (few
+/// candidates per query), "dense" ones faces the size of the search radius.
+fn benchmark_spatial_index(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spatial_index");
+    group.sample_size(10);
+
+    let scales = vec![
+        ("10K_faces_sparse", 100, 100, 1.0),
+        ("10K_faces_dense", 100, 100, 0.01),
+        ("100K_faces_dense", 316, 316, 0.01),
+    ];
+
+    for (name, nx, ny, element_size) in scales {
+        let (mesh_a, mesh_b) = generate_parallel_surfaces(nx, ny, 0.001, element_size);
+        let surfaces_a = extract_surface(&mesh_a).unwrap();
+        let surfaces_b = extract_surface(&mesh_b).unwrap();
+        let (surface_a, surface_b) = (&surfaces_a[0], &surfaces_b[0]);
+
+        let mut criteria = ContactCriteria::new(0.005, 0.001, 45.0);
+        let radius = criteria.search_radius();
+        let centroids_b = &surface_b.face_centroids;
+        let points_b: Vec<[f64; 3]> = centroids_b.iter().map(|c| [c.x, c.y, c.z]).collect();
+
+        group.throughput(Throughput::Elements(centroids_b.len() as u64));
+        group.bench_function(BenchmarkId::new("kd_tree_build", name), |b| {
+            b.iter(|| black_box(ImmutableKdTree::<f64, 3>::new_from_slice(black_box(&points_b))));
+        });
+        group.bench_function(BenchmarkId::new("grid_hash_build", name), |b| {
+            b.iter(|| black_box(GridHash::new(black_box(centroids_b), 2.0 * radius)));
+        });
+
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&points_b);
+        let grid = GridHash::new(centroids_b, 2.0 * radius);
+        group.throughput(Throughput::Elements(surface_a.face_centroids.len() as u64));
+        group.bench_function(BenchmarkId::new("kd_tree_queries", name), |b| {
+            b.iter(|| {
+                for c in &surface_a.face_centroids {
+                    black_box(
+                        tree.within::<kiddo::SquaredEuclidean>(&[c.x, c.y, c.z], radius * radius),
+                    );
+                }
+            });
+        });
+        group.bench_function(BenchmarkId::new("grid_hash_queries", name), |b| {
+            b.iter(|| {
+                for c in &surface_a.face_centroids {
+                    black_box(grid.within(c, radius));
+                }
+            });
+        });
+
+        for (label, kind) in [
+            ("kd_tree_detection", SpatialIndexKind::KdTree),
+            ("grid_hash_detection", SpatialIndexKind::GridHash),
+        ] {
+            criteria.spatial_index = kind;
+            group.bench_function(BenchmarkId::new(label, name), |b| {
+                b.iter(|| {
+                    black_box(
+                        detect_contact_pairs(
+                            black_box(surface_a),
+                            black_box(surface_b),
+                            black_box(&criteria),
+                        )
+                        .unwrap(),
+                    )
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+/// Compare the built-in detection algorithms on the same candidate pairs
+fn benchmark_detection_algorithm(c: &mut Criterion) {
+    let mut group = c.benchmark_group("detection_algorithm");
+    group.sample_size(10);
+
+    let (mesh_a, mesh_b) = generate_parallel_surfaces(100, 100, 0.001, 1.0);
+    let surfaces_a = extract_surface(&mesh_a).unwrap();
+    let surfaces_b = extract_surface(&mesh_b).unwrap();
+    let (surface_a, surface_b) = (&surfaces_a[0], &surfaces_b[0]);
+
+    let mut criteria = ContactCriteria::new(0.005, 0.001, 45.0);
+    group.throughput(Throughput::Elements(surface_a.faces.len() as u64));
+    for kind in DetectionAlgorithmKind::ALL {
+        criteria.algorithm = kind;
+        group.bench_function(BenchmarkId::new(kind.algorithm().name(), "10K_faces"), |b| {
+            b.iter(|| {
+                black_box(
+                    detect_contact_pairs(
+                        black_box(surface_a),
+                        black_box(surface_b),
+                        black_box(&criteria),
+                    )
+                    .unwrap(),
+                )
+            });
+        });
+    }
+
+    group.finish();
+}
+
 /// Benchmark complete pipeline (surface extraction + contact detection)
 fn benchmark_pipeline(c: &mut Criterion) {
     let mut group = c.benchmark_group("pipeline");
@@ -284,6 +427,14 @@ fn benchmark_1m_target(c: &mut Criterion) {
         });
     });
 
+    let elements: Vec<usize> = (0..mesh.num_elements()).collect();
+    group.bench_function("component_labeling_1M", |b| {
+        b.iter(|| {
+            let bodies = connected_components(black_box(&mesh), black_box(&elements));
+            black_box(bodies);
+        });
+    });
+
     group.finish();
 }
 
@@ -293,6 +444,9 @@ criterion_group!(
     benchmark_kdtree,
     benchmark_contact_detection,
     benchmark_pipeline,
+    benchmark_component_labeling,
+    benchmark_spatial_index,
+    benchmark_detection_algorithm,
 );
 
 // Separate group for 1M element benchmark (commented out by default)
@@ -76,8 +76,18 @@ pub fn generate_hex_grid(nx: usize, ny: usize, nz: usize, element_size: f64) ->
         nodes,
         elements,
         element_blocks,
+        material_ids: vec![1; total_elements],
         node_sets: HashMap::new(),
         side_sets: HashMap::new(),
+        side_set_dist_factors: HashMap::new(),
+        side_set_fields: HashMap::new(),
+        node_num_map: Vec::new(),
+        elem_num_map: Vec::new(),
+        node_fields: HashMap::new(),
+        element_fields: HashMap::new(),
+        assemblies: HashMap::new(),
+        units: None,
+        provenance: Default::default(),
     }
 }
 
@@ -160,8 +170,18 @@ fn generate_hex_grid_with_perturbation(
         nodes,
         elements,
         element_blocks,
+        material_ids: vec![1; total_elements],
         node_sets: HashMap::new(),
         side_sets: HashMap::new(),
+        side_set_dist_factors: HashMap::new(),
+        side_set_fields: HashMap::new(),
+        node_num_map: Vec::new(),
+        elem_num_map: Vec::new(),
+        node_fields: HashMap::new(),
+        element_fields: HashMap::new(),
+        assemblies: HashMap::new(),
+        units: None,
+        provenance: Default::default(),
     }
 }
 
@@ -224,25 +244,23 @@ pub fn calculate_grid_dimensions(target_elements: usize) -> (usize, usize, usize
 
 #[cfg(test)]
 mod tests {
-    use super::{calculate_grid_dimensions, generate_hex_grid, generate_parallel_surfaces};
-
     #[test]
     fn test_generate_small_grid() {
-        let mesh = generate_hex_grid(2, 2, 2, 1.0);
+        let mesh = super::generate_hex_grid(2, 2, 2, 1.0);
         assert_eq!(mesh.num_elements(), 8); // 2*2*2
         assert_eq!(mesh.num_nodes(), 27); // 3*3*3
     }
 
     #[test]
     fn test_parallel_surfaces() {
-        let (mesh_a, mesh_b) = generate_parallel_surfaces(10, 10, 0.001, 1.0);
+        let (mesh_a, mesh_b) = super::generate_parallel_surfaces(10, 10, 0.001, 1.0);
         assert_eq!(mesh_a.num_elements(), 100); // 10*10*1
         assert_eq!(mesh_b.num_elements(), 100); // 10*10*1
     }
 
     #[test]
     fn test_calculate_dimensions() {
-        let (nx, ny, nz) = calculate_grid_dimensions(1000);
+        let (nx, ny, nz) = super::calculate_grid_dimensions(1000);
         let actual = nx * ny * nz;
         // Should be close to target (within 10%)
         assert!((actual as f64 - 1000.0).abs() / 1000.0 < 0.1);
@@ -90,6 +90,7 @@ for mesh_file in "${test_meshes[@]}"; do
         # --max-gap: Maximum gap distance (0.01)
         # --max-penetration: Maximum penetration distance (0.01)
         # --max-angle: Maximum normal angle in degrees (30)
+        # --overwrite: Replace the results of a previous run
         "$BINARY" auto-contact "$mesh_file" \
             --max-gap 0.01 \
             --max-penetration 0.01 \
@@ -98,6 +99,7 @@ for mesh_file in "${test_meshes[@]}"; do
             --export-metadata \
             --export-volume \
             --export-sidesets \
+            --overwrite \
             -o "$output_dir"
 
         echo -e "${GREEN}✓ Complete${NC}"
@@ -3,27 +3,421 @@
 //! This module defines the command-line interface structure using clap.
 //! It provides commands for mesh inspection, surface extraction, and contact detection.
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use contact_detector::config::Settings;
+use contact_detector::contact::{
+    ContactCriteria, DetectionAlgorithmKind, FaceValueSource, GapTolerance, SpatialIndexKind,
+};
+use contact_detector::format::Notation;
+use contact_detector::io::VtkVersion;
+use contact_detector::mesh::{AssemblyScope, Mesh, RoiSpec, Triangulation};
+use serde::Serialize;
 use std::path::PathBuf;
 
-/// Parse a VTK version string (e.g., "2.2" or "4.2") into a tuple
-pub fn parse_vtk_version(version_str: &str) -> Result<(u8, u8), String> {
-    let parts: Vec<&str> = version_str.split('.').collect();
-    if parts.len() != 2 {
+/// Parse a point given as "x,y,z" into its coordinates
+pub fn parse_point(point_str: &str) -> Result<[f64; 3], String> {
+    parse_floats::<3>(point_str, "point")
+}
+
+/// Parse a comma-separated list of exactly `N` numbers
+fn parse_floats<const N: usize>(value: &str, what: &str) -> Result<[f64; N], String> {
+    let parts: Vec<&str> = value.split(',').map(str::trim).collect();
+    if parts.len() != N {
         return Err(format!(
-            "Invalid VTK version format '{}'. Expected format: 'X.Y' (e.g., '2.2' or '4.2')",
-            version_str
+            "Invalid {} '{}': expected {} comma-separated numbers",
+            what, value, N
         ));
     }
 
-    let major = parts[0]
-        .parse::<u8>()
-        .map_err(|_| format!("Invalid major version '{}'", parts[0]))?;
-    let minor = parts[1]
-        .parse::<u8>()
-        .map_err(|_| format!("Invalid minor version '{}'", parts[1]))?;
+    let mut out = [0.0; N];
+    for (v, part) in out.iter_mut().zip(&parts) {
+        *v = part
+            .parse::<f64>()
+            .map_err(|_| format!("Invalid number '{}' in {}", part, what))?;
+    }
+    Ok(out)
+}
+
+/// Parse an ROI box given as "xmin,ymin,zmin,xmax,ymax,zmax"
+pub fn parse_roi_box(value: &str) -> Result<RoiSpec, String> {
+    let v = parse_floats::<6>(value, "ROI box")?;
+    Ok(RoiSpec::Box {
+        min: [v[0], v[1], v[2]],
+        max: [v[3], v[4], v[5]],
+    })
+}
+
+/// Parse an ROI sphere given as "x,y,z,radius"
+pub fn parse_roi_sphere(value: &str) -> Result<RoiSpec, String> {
+    let v = parse_floats::<4>(value, "ROI sphere")?;
+    Ok(RoiSpec::Sphere {
+        center: [v[0], v[1], v[2]],
+        radius: v[3],
+    })
+}
+
+/// Parse a maximum Exodus name length (1 to 255 characters)
+pub fn parse_max_name_length(value: &str) -> Result<usize, String> {
+    let limit = contact_detector::io::EXODUS_NAME_LEN_LIMIT;
+    match value.parse::<usize>() {
+        Ok(len) if (1..=limit).contains(&len) => Ok(len),
+        _ => Err(format!(
+            "Invalid name length '{}': expected a number from 1 to {}",
+            value, limit
+        )),
+    }
+}
+
+/// Parse a pair of assembly names given as "A:B"
+pub fn parse_assembly_pair(value: &str) -> Result<(String, String), String> {
+    match value.split_once(':') {
+        Some((a, b)) if !a.trim().is_empty() && !b.trim().is_empty() => {
+            Ok((a.trim().to_string(), b.trim().to_string()))
+        }
+        _ => Err(format!(
+            "Invalid assembly pair '{}': expected \"AssemblyA:AssemblyB\"",
+            value
+        )),
+    }
+}
+
+/// Parse a positive time in seconds
+pub fn parse_seconds(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() && seconds > 0.0 => Ok(seconds),
+        _ => Err(format!(
+            "Invalid time '{}': expected a positive number of seconds",
+            value
+        )),
+    }
+}
+
+/// Validate a sideset name template
+pub fn parse_sideset_name_template(value: &str) -> Result<String, String> {
+    contact_detector::io::sideset_naming::validate_template(value)
+        .map(|_| value.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Region-of-interest options shared by the detection commands
+#[derive(Args, Debug, Clone, Default, Serialize)]
+pub struct RoiArgs {
+    /// Only consider faces inside this box (format: "xmin,ymin,zmin,xmax,ymax,zmax")
+    #[arg(long, value_parser = parse_roi_box, allow_hyphen_values = true,
+          conflicts_with_all = ["roi_sphere", "roi_stl"])]
+    pub roi_box: Option<RoiSpec>,
+
+    /// Only consider faces inside this sphere (format: "x,y,z,radius")
+    #[arg(long, value_parser = parse_roi_sphere, allow_hyphen_values = true,
+          conflicts_with = "roi_stl")]
+    pub roi_sphere: Option<RoiSpec>,
+
+    /// Only consider faces inside the closed volume of this STL file
+    #[arg(long, value_name = "FILE")]
+    pub roi_stl: Option<PathBuf>,
+
+    /// Also drop faces outside the region of interest from the output files
+    #[arg(long)]
+    pub roi_clip_output: bool,
+
+    /// Never pair the faces of these side sets (e.g. weld seams or
+    /// instrumentation pads); they are removed from the surfaces
+    #[arg(long, value_name = "SIDESET", value_delimiter = ',')]
+    pub exclude_sideset: Vec<String>,
+}
+
+impl RoiArgs {
+    /// The requested region, if any
+    pub fn spec(&self) -> Option<RoiSpec> {
+        self.roi_box
+            .clone()
+            .or_else(|| self.roi_sphere.clone())
+            .or_else(|| self.roi_stl.clone().map(|path| RoiSpec::Stl { path }))
+    }
+}
+
+/// Scoping of detection by the assemblies of blocks in the mesh (Exodus
+/// assembly records)
+#[derive(Args, Debug, Clone, Default, Serialize)]
+pub struct AssemblyScopeArgs {
+    /// Only detect contacts between blocks of this assembly, including those
+    /// of nested assemblies
+    #[arg(long, value_name = "NAME", conflicts_with = "between_assemblies")]
+    pub within_assembly: Option<String>,
+
+    /// Only detect contacts between a block of one assembly and a block of
+    /// the other (format: "AssemblyA:AssemblyB")
+    #[arg(long, value_name = "A:B", value_parser = parse_assembly_pair)]
+    pub between_assemblies: Option<(String, String)>,
+}
+
+impl AssemblyScopeArgs {
+    /// The requested scope, resolved to the blocks of `mesh`
+    pub fn resolve(&self, mesh: &Mesh) -> contact_detector::Result<Option<AssemblyScope>> {
+        if let Some(name) = &self.within_assembly {
+            return AssemblyScope::within(mesh, name).map(Some);
+        }
+        self.between_assemblies
+            .as_ref()
+            .map(|(a, b)| AssemblyScope::between(mesh, a, b))
+            .transpose()
+    }
+}
+
+/// Detection tolerances shared by the detection commands
+#[derive(Args, Debug, Clone, Default, Serialize)]
+pub struct CriteriaArgs {
+    /// Start from a named criteria preset: general-contact, tied-strict,
+    /// interference-check or one defined in presets.yaml of the user
+    /// configuration directory or in contact-detector.toml; the tolerance
+    /// flags override it
+    #[arg(long, value_name = "NAME")]
+    pub preset: Option<String>,
+
+    /// Maximum gap distance (tolerance), or a percentage of the smaller
+    /// part's size resolved per pair: "0.5%" of its bounding box diagonal,
+    /// "40%:edge" of its median element edge length [default: 0.005]
+    #[arg(long, value_name = "GAP")]
+    pub max_gap: Option<GapTolerance>,
+
+    /// Maximum penetration distance [default: 0.001]
+    #[arg(long)]
+    pub max_penetration: Option<f64>,
+
+    /// Maximum normal angle in degrees [default: 45.0]
+    #[arg(long)]
+    pub max_angle: Option<f64>,
+
+    /// Minimum facing of paired faces: |cosine| between A's normal and the
+    /// direction to the closest point of B's face (0 disables the test)
+    /// [default: 0.5]
+    #[arg(long)]
+    pub min_facing: Option<f64>,
+}
+
+impl CriteriaArgs {
+    /// Criteria of the preset (or the default preset of the settings, or
+    /// the defaults) with the given tolerances
+    pub fn resolve(&self, settings: &Settings) -> contact_detector::Result<ContactCriteria> {
+        let mut criteria = match self.preset.as_ref().or(settings.preset.as_ref()) {
+            Some(name) => settings.criteria_presets()?.get(name)?.criteria(),
+            None => ContactCriteria::default(),
+        };
+        if let Some(max_gap) = &self.max_gap {
+            max_gap.apply(&mut criteria);
+        }
+        if let Some(max_penetration) = self.max_penetration {
+            criteria.max_penetration = max_penetration;
+        }
+        if let Some(max_angle) = self.max_angle {
+            criteria.max_normal_angle = max_angle;
+        }
+        if let Some(min_facing) = self.min_facing {
+            criteria.min_facing = min_facing;
+        }
+        Ok(criteria)
+    }
+}
+
+/// Validate an output name template
+pub fn parse_output_name_template(value: &str) -> Result<String, String> {
+    contact_detector::io::output_naming::validate_output_template(value)
+        .map(|_| value.to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// Output file naming options shared by the detection commands
+#[derive(Args, Debug, Clone, Serialize)]
+pub struct OutputArgs {
+    /// Template for per-pair result file names (without extension);
+    /// placeholders: {pair}, {a}, {b} (surface names), {block_a},
+    /// {block_b}, {timestamp}
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        value_parser = parse_output_name_template,
+        default_value = contact_detector::io::DEFAULT_OUTPUT_NAME_TEMPLATE
+    )]
+    pub output_name: String,
+
+    /// Handling of existing output files
+    #[command(flatten)]
+    #[serde(flatten)]
+    pub existing: OverwriteArgs,
+}
+
+impl OutputArgs {
+    /// What to do with output files that already exist (stop by default)
+    pub fn policy(&self) -> contact_detector::io::OverwritePolicy {
+        self.existing.policy()
+    }
+}
+
+/// Handling of output files that already exist, shared by every command
+/// writing files
+#[derive(Args, Debug, Clone, Serialize)]
+pub struct OverwriteArgs {
+    /// Replace output files that already exist
+    #[arg(long, conflicts_with = "skip_existing")]
+    pub overwrite: bool,
+
+    /// Keep output files that already exist and don't write them again
+    #[arg(long)]
+    pub skip_existing: bool,
+}
+
+impl OverwriteArgs {
+    /// What to do with output files that already exist (stop by default)
+    pub fn policy(&self) -> contact_detector::io::OverwritePolicy {
+        use contact_detector::io::OverwritePolicy;
+        if self.overwrite {
+            OverwritePolicy::Overwrite
+        } else if self.skip_existing {
+            OverwritePolicy::Skip
+        } else {
+            OverwritePolicy::Error
+        }
+    }
+}
+
+/// Quantity written as side set distribution factors on export
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DistFactorArg {
+    /// Signed contact gap of each face
+    Gap,
+    /// Fraction of each face overlapped by its partner
+    OverlapFraction,
+}
+
+impl From<DistFactorArg> for FaceValueSource {
+    fn from(arg: DistFactorArg) -> Self {
+        match arg {
+            DistFactorArg::Gap => FaceValueSource::Gap,
+            DistFactorArg::OverlapFraction => FaceValueSource::OverlapFraction,
+        }
+    }
+}
+
+/// File format of the contact manifest written by `--export-manifest`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ManifestFormatArg {
+    Json,
+    Yaml,
+}
+
+impl ManifestFormatArg {
+    /// The format given on the command line, else the one of the settings,
+    /// else JSON
+    pub fn resolve(flag: Option<Self>, settings: &Settings) -> contact_detector::Result<Self> {
+        match (flag, &settings.manifest_format) {
+            (Some(format), _) => Ok(format),
+            (None, Some(name)) => Self::from_str(name, true).map_err(|_| {
+                contact_detector::ContactDetectorError::ConfigError(format!(
+                    "Invalid manifest_format '{}' in settings (expected json or yaml)",
+                    name
+                ))
+            }),
+            (None, None) => Ok(ManifestFormatArg::Json),
+        }
+    }
+
+    /// File extension selecting this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ManifestFormatArg::Json => "json",
+            ManifestFormatArg::Yaml => "yaml",
+        }
+    }
+}
+
+/// File format of the volume mesh written by `--export-nodal-status`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NodalStatusFormatArg {
+    Vtu,
+    Exodus,
+}
+
+impl NodalStatusFormatArg {
+    /// Name of the file written in the output directory
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            NodalStatusFormatArg::Vtu => "mesh_with_contact_status.vtu",
+            NodalStatusFormatArg::Exodus => "mesh_with_contact_status.exo",
+        }
+    }
+}
 
-    Ok((major, minor))
+/// How quads are split by `--triangulate`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TriangulateArg {
+    /// Split along the shorter diagonal of each quad
+    ShortestDiagonal,
+    /// Split along the diagonal through face nodes 0 and 2
+    #[value(name = "diagonal-0-2")]
+    #[serde(rename = "diagonal-0-2")]
+    Diagonal02,
+    /// Split along the diagonal through face nodes 1 and 3
+    #[value(name = "diagonal-1-3")]
+    #[serde(rename = "diagonal-1-3")]
+    Diagonal13,
+}
+
+impl From<TriangulateArg> for Triangulation {
+    fn from(arg: TriangulateArg) -> Self {
+        match arg {
+            TriangulateArg::ShortestDiagonal => Triangulation::ShortestDiagonal,
+            TriangulateArg::Diagonal02 => Triangulation::Diagonal02,
+            TriangulateArg::Diagonal13 => Triangulation::Diagonal13,
+        }
+    }
+}
+
+/// Spatial index used to find candidate faces (`--spatial-index`)
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpatialIndexArg {
+    /// k-d tree, suited to any mesh
+    KdTree,
+    /// Uniform grid hash, faster for dense, uniformly meshed surfaces
+    GridHash,
+}
+
+impl From<SpatialIndexArg> for SpatialIndexKind {
+    fn from(arg: SpatialIndexArg) -> Self {
+        match arg {
+            SpatialIndexArg::KdTree => SpatialIndexKind::KdTree,
+            SpatialIndexArg::GridHash => SpatialIndexKind::GridHash,
+        }
+    }
+}
+
+/// Algorithm measuring the gap between candidate faces (`--algorithm`)
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AlgorithmArg {
+    /// Centroid of A to the plane of B's centroid (fastest)
+    CentroidPlane,
+    /// Centroid of A to the closest point of B's face
+    ClosestPoint,
+    /// Ray from A's centroid along its normal onto B's face
+    RayCast,
+    /// Gap averaged over several integration points of A's face
+    Mortar,
+}
+
+impl From<AlgorithmArg> for DetectionAlgorithmKind {
+    fn from(arg: AlgorithmArg) -> Self {
+        match arg {
+            AlgorithmArg::CentroidPlane => DetectionAlgorithmKind::CentroidPlane,
+            AlgorithmArg::ClosestPoint => DetectionAlgorithmKind::ClosestPoint,
+            AlgorithmArg::RayCast => DetectionAlgorithmKind::RayCast,
+            AlgorithmArg::Mortar => DetectionAlgorithmKind::Mortar,
+        }
+    }
 }
 
 /// Command-line interface for the contact detector application
@@ -42,7 +436,7 @@ pub fn parse_vtk_version(version_str: &str) -> Result<(u8, u8), String> {
 /// # Detect contact pairs
 /// contact-detector contact mesh.exo --part-a Block1 --part-b Block2 -o result.vtu
 /// ```
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Serialize)]
 #[command(name = "contact-detector")]
 #[command(author, version, about, long_about = None)]
 #[command(propagate_version = true)]
@@ -55,26 +449,99 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub verbose: bool,
 
-    /// Enable debug logging
+    /// Enable debug logging (also prints per-stage timings)
     #[arg(short, long, global = true)]
     pub debug: bool,
 
-    /// VTK file format version (e.g., "2.2" for ParaView 6.0.1 compatibility, "4.2" for latest)
+    /// Only log errors to the console
+    #[arg(short, long, global = true, conflicts_with_all = ["verbose", "debug"])]
+    pub quiet: bool,
+
+    /// Write a detailed debug trace (including stage timings) to this file
+    #[arg(long, global = true, value_name = "FILE")]
+    pub log_file: Option<PathBuf>,
+
+    /// VTK file format version: 1.0 to 2.2 for XML files (default 2.2),
+    /// 2.0 to 4.2 for legacy .vtk files (default 4.2)
     #[arg(long, global = true, value_name = "VERSION")]
-    pub vtk_version: Option<String>,
+    pub vtk_version: Option<VtkVersion>,
+
+    /// Number of worker threads (default: one per CPU)
+    #[arg(long, global = true, value_name = "N")]
+    pub threads: Option<usize>,
+
+    /// Notation of numbers in reports, CSV files and JSON metadata: fixed,
+    /// scientific or shortest (default: six decimals in reports, every digit
+    /// in files)
+    #[arg(long, global = true, value_name = "NOTATION")]
+    pub notation: Option<Notation>,
+
+    /// Decimals of numbers in reports, CSV files and JSON metadata (in fixed
+    /// notation unless --notation is given)
+    #[arg(long, global = true, value_name = "N")]
+    pub precision: Option<usize>,
+
+    /// Write only ASCII to the console ("<->", "deg" and "x" instead of
+    /// "↔", "°" and "×"), for terminals that garble them
+    #[arg(long, global = true)]
+    pub ascii: bool,
+
+    /// Assembly definition (YAML) adding blocks, node sets and side sets to
+    /// the mesh by element ID ranges or regions
+    #[arg(long, global = true, value_name = "FILE")]
+    pub assembly: Option<PathBuf>,
+
+    /// Treat every connected body of a block as a separate part (for meshes
+    /// that put a whole assembly into one block)
+    #[arg(long, global = true)]
+    pub split_bodies: bool,
 }
 
 /// Available subcommands for the contact detector CLI
 ///
 /// Each command provides specific functionality for working with hexahedral meshes
 /// and detecting contact pairs between surfaces.
-#[derive(Subcommand, Debug)]
+// Parsed once per run, so the size of the largest variant does not matter
+#[allow(clippy::large_enum_variant)]
+#[derive(Subcommand, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum Commands {
     /// Display information about an Exodus mesh file
     Info {
         /// Path to the Exodus II file
         #[arg(value_name = "FILE")]
         input: PathBuf,
+
+        /// Print counts and the volume, centroid and inertia of each block as
+        /// JSON instead of the report
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Convert a mesh between formats (chosen from the output extension:
+    /// .json, .cdmesh, optionally .gz/.zst compressed, or Exodus)
+    Convert {
+        /// Path to the input mesh file
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
+        /// Output mesh file path
+        #[arg(short, long, value_name = "FILE")]
+        output: PathBuf,
+
+        /// Longest block/set name to store in an Exodus output (up to 255);
+        /// longer names are truncated and kept unique
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = contact_detector::io::EXODUS_MAX_NAME_LEN,
+            value_parser = parse_max_name_length
+        )]
+        max_name_length: usize,
+
+        /// Handling of an existing output file
+        #[command(flatten)]
+        existing: OverwriteArgs,
     },
 
     /// Extract surface mesh from hexahedral mesh
@@ -83,13 +550,49 @@ pub enum Commands {
         #[arg(value_name = "FILE")]
         input: PathBuf,
 
-        /// Output VTU file path
+        /// Output VTU file path (or directory for several surfaces); a .obj or
+        /// .ply path writes all surfaces to one file
         #[arg(short, long, value_name = "FILE")]
         output: PathBuf,
 
         /// Part/block name to extract (if not specified, extracts all)
         #[arg(short, long)]
         part: Option<String>,
+
+        /// Write all surfaces to one VTU file with SourceBlock, PatchId,
+        /// ElementId and ElementSide cell arrays instead of one file per
+        /// surface
+        #[arg(long)]
+        merged: bool,
+
+        /// Close holes in each block's skin with a perimeter up to this
+        /// length; filler faces are written as surfaces named
+        /// <block>:hole_<k>
+        #[arg(long, value_name = "MAX_PERIMETER")]
+        fill_holes: Option<f64>,
+
+        /// Leave the faces of these side sets out of the extracted surfaces
+        #[arg(long, value_name = "SIDESET", value_delimiter = ',')]
+        exclude_sideset: Vec<String>,
+
+        /// Report per block whether the skin is closed and how many holes
+        /// (loops of edges used by only one face) it has
+        #[arg(long)]
+        check_closure: bool,
+
+        /// Write the holes found by --check-closure as VTP polylines
+        #[arg(long, value_name = "FILE", requires = "check_closure")]
+        holes_output: Option<PathBuf>,
+
+        /// Split quads into triangles in the surface output (default split:
+        /// shortest-diagonal); cell data is repeated on both triangles
+        #[arg(long, value_enum, value_name = "SPLIT", num_args = 0..=1,
+              default_missing_value = "shortest-diagonal")]
+        triangulate: Option<TriangulateArg>,
+
+        /// Handling of existing output files
+        #[command(flatten)]
+        existing: OverwriteArgs,
     },
 
     /// Detect contact pairs between surfaces
@@ -103,24 +606,142 @@ pub enum Commands {
         part_a: String,
 
         /// Second part name
+        #[arg(long, required_unless_present = "surface_b")]
+        part_b: Option<String>,
+
+        /// Use an STL/OBJ surface (e.g. an as-built scan) as the second
+        /// surface instead of a mesh part
+        #[arg(long, value_name = "FILE", conflicts_with = "part_b")]
+        surface_b: Option<PathBuf>,
+
+        /// Detection tolerances
+        #[command(flatten)]
+        criteria: CriteriaArgs,
+
+        /// Spatial index used to find candidate faces
+        #[arg(long, value_enum, default_value = "kd-tree")]
+        spatial_index: SpatialIndexArg,
+
+        /// Algorithm measuring the gap between candidate faces
+        #[arg(long, value_enum, default_value = "centroid-plane")]
+        algorithm: AlgorithmArg,
+
+        /// Count why candidates were rejected (gap, penetration, angle,
+        /// facing, none found) and report the binding criterion
+        #[arg(long)]
+        rejection_stats: bool,
+
+        /// Keep pairs whose connecting segment passes through part A's own
+        /// elements (thin-wall check disabled)
+        #[arg(long)]
+        no_occlusion_check: bool,
+
+        /// Output file path (.vtu, .vtp or .csv)
+        #[arg(short, long, value_name = "FILE")]
+        output: PathBuf,
+
+        /// Split quads into triangles in the surface output (default split:
+        /// shortest-diagonal); cell data is repeated on both triangles
+        #[arg(long, value_enum, value_name = "SPLIT", num_args = 0..=1,
+              default_missing_value = "shortest-diagonal")]
+        triangulate: Option<TriangulateArg>,
+
+        /// Save the detection results (JSON, gzip/zstd compressed if the path
+        /// ends in .gz/.zst) for later reuse without rerunning detection
+        #[arg(long, value_name = "FILE")]
+        save_results: Option<PathBuf>,
+
+        /// Region-of-interest filtering
+        #[command(flatten)]
+        roi: RoiArgs,
+
+        /// Handling of existing output files
+        #[command(flatten)]
+        existing: OverwriteArgs,
+    },
+
+    /// Report the nearest skin face to a point and its contact pairing
+    Query {
+        /// Path to the Exodus II file
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
+        /// Point to query (format: "x,y,z")
+        #[arg(long, value_parser = parse_point, allow_hyphen_values = true)]
+        point: [f64; 3],
+
+        /// Detection tolerances
+        #[command(flatten)]
+        criteria: CriteriaArgs,
+
+        /// Print the nearest face and its partners as JSON instead of the
+        /// report
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Sample the gap between two parts along a path across their interface
+    /// and write gap vs. arclength as CSV
+    Probe {
+        /// Path to the Exodus II file
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
+        /// Polyline to sample (JSON list of [x, y, z] points)
+        #[arg(long, value_name = "FILE")]
+        path: PathBuf,
+
+        /// Part whose faces the gap is read from
+        #[arg(long)]
+        part_a: String,
+
+        /// Part across the interface
         #[arg(long)]
         part_b: String,
 
-        /// Maximum gap distance (tolerance)
-        #[arg(long, default_value = "0.005")]
-        max_gap: f64,
+        /// Number of samples, evenly spaced along the path
+        #[arg(long, default_value_t = 100)]
+        samples: usize,
+
+        /// Detection tolerances
+        #[command(flatten)]
+        criteria: CriteriaArgs,
+
+        /// Output CSV file path
+        #[arg(short, long, value_name = "FILE")]
+        output: PathBuf,
+
+        /// Handling of an existing output file
+        #[command(flatten)]
+        existing: OverwriteArgs,
+    },
+
+    /// Measure the signed deviation of a point cloud (e.g. a scan) from the
+    /// mesh skin
+    Deviation {
+        /// Path to the Exodus II file
+        #[arg(value_name = "FILE")]
+        input: PathBuf,
+
+        /// Point cloud file (.csv/.txt/.xyz with x,y,z columns, or .ply)
+        #[arg(long, value_name = "FILE")]
+        points: PathBuf,
 
-        /// Maximum penetration distance
-        #[arg(long, default_value = "0.001")]
-        max_penetration: f64,
+        /// Surface part name to compare against (if not specified, uses all)
+        #[arg(short, long)]
+        part: Option<String>,
 
-        /// Maximum normal angle in degrees
-        #[arg(long, default_value = "45.0")]
-        max_angle: f64,
+        /// Ignore points farther than this from the surface
+        #[arg(long)]
+        max_distance: Option<f64>,
 
-        /// Output VTU file path
+        /// Output file path (.csv for a table, otherwise a VTU point cloud)
         #[arg(short, long, value_name = "FILE")]
         output: PathBuf,
+
+        /// Handling of an existing output file
+        #[command(flatten)]
+        existing: OverwriteArgs,
     },
 
     /// Full analysis pipeline
@@ -140,6 +761,24 @@ pub enum Commands {
         /// Output directory
         #[arg(short, long, value_name = "DIR")]
         output: PathBuf,
+
+        /// Always re-run detection instead of reusing results cached in the
+        /// output directory for unchanged surfaces and criteria
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Extract the surfaces and list the pairs that would be analyzed and
+        /// the files that would be written, without detecting or writing
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output file naming
+        #[command(flatten)]
+        naming: OutputArgs,
+
+        /// Region-of-interest filtering
+        #[command(flatten)]
+        roi: RoiArgs,
     },
 
     /// Automatically detect contact surfaces based on geometry alone
@@ -148,22 +787,99 @@ pub enum Commands {
         #[arg(value_name = "FILE")]
         input: PathBuf,
 
-        /// Maximum gap distance (tolerance)
-        #[arg(long, default_value = "0.005")]
-        max_gap: f64,
+        /// Detection tolerances
+        #[command(flatten)]
+        criteria: CriteriaArgs,
 
-        /// Maximum penetration distance
-        #[arg(long, default_value = "0.001")]
-        max_penetration: f64,
+        /// Spatial index used to find candidate faces
+        #[arg(long, value_enum, default_value = "kd-tree")]
+        spatial_index: SpatialIndexArg,
 
-        /// Maximum normal angle in degrees
-        #[arg(long, default_value = "45.0")]
-        max_angle: f64,
+        /// Algorithm measuring the gap between candidate faces
+        #[arg(long, value_enum, default_value = "centroid-plane")]
+        algorithm: AlgorithmArg,
+
+        /// Count why candidates were rejected (gap, penetration, angle,
+        /// facing, none found) and report the binding criterion
+        #[arg(long)]
+        rejection_stats: bool,
+
+        /// Detect against a signed distance field of each second surface,
+        /// voxelized once with this grid spacing, instead of searching its
+        /// faces (faster for many small parts against one large skin; keep
+        /// the spacing well below --max-gap)
+        #[arg(long, value_name = "SPACING")]
+        sdf_spacing: Option<f64>,
+
+        /// Run a coarse pass on every Nth face first, ranking surface pairs
+        /// and skipping those without a hit (may miss contacts smaller than
+        /// about N faces)
+        #[arg(long, value_name = "N")]
+        coarse_stride: Option<usize>,
+
+        /// Give up on a surface pair whose face search takes longer than
+        /// this and report it as skipped (budget)
+        #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+        pair_time_limit: Option<f64>,
+
+        /// Skip surface pairs with more than N faces in total and report
+        /// them as skipped (budget)
+        #[arg(long, value_name = "N")]
+        pair_max_faces: Option<usize>,
+
+        /// Test pairs skipped for the budget again, without limits, after
+        /// all other pairs
+        #[arg(long)]
+        revisit_skipped: bool,
+
+        /// Preview run: detect on surfaces decimated to about FACES faces
+        /// each (500 if no value is given), list the parts that touch and
+        /// write no output files
+        #[arg(long, value_name = "FACES", num_args = 0..=1, default_missing_value = "500")]
+        preview: Option<usize>,
+
+        /// Run surface extraction and the broad phase only, then list the
+        /// surface pairs that would be tested and the files that would be
+        /// written
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Keep pairs whose connecting segment passes through part A's own
+        /// elements (thin-wall check disabled)
+        #[arg(long)]
+        no_occlusion_check: bool,
 
         /// Minimum number of contact pairs to consider surfaces in contact
         #[arg(long, default_value = "1")]
         min_pairs: usize,
 
+        /// Minimum paired area of the first surface to consider surfaces in
+        /// contact (independent of the mesh density, unlike --min-pairs)
+        #[arg(long, value_name = "AREA")]
+        min_paired_area: Option<f64>,
+
+        /// Minimum paired fraction (0 to 1) of either surface's area to
+        /// consider surfaces in contact
+        #[arg(long, value_name = "FRACTION")]
+        min_coverage_fraction: Option<f64>,
+
+        /// Merge overlapping pairs into consolidated interfaces in the report
+        #[arg(long)]
+        merge_pairs: bool,
+
+        /// File of allowed part pairs, one "PartA:PartB" per line ("*" for
+        /// any part); detected pairs not listed are flagged as unexpected
+        #[arg(long, value_name = "FILE")]
+        allowed_contacts: Option<PathBuf>,
+
+        /// Minimum number of faces for a surface patch to take part in detection
+        #[arg(long, default_value = "0")]
+        min_patch_faces: usize,
+
+        /// Minimum total area for a surface patch to take part in detection
+        #[arg(long, default_value = "0.0")]
+        min_patch_area: f64,
+
         /// Output directory for results
         #[arg(short, long, value_name = "DIR")]
         output: PathBuf,
@@ -172,10 +888,138 @@ pub enum Commands {
         #[arg(long)]
         export_metadata: bool,
 
-        /// Write detected contact sidesets back to Exodus file
+        /// Save the detection results of every pair to contact_results.json
+        #[arg(long)]
+        save_results: bool,
+
+        /// Export a solver-agnostic contact manifest (surfaces by element
+        /// side, roles, measured gaps, recommended tolerances)
+        #[arg(long)]
+        export_manifest: bool,
+
+        /// File format of the contact manifest [default: json]
+        #[arg(long, value_enum)]
+        manifest_format: Option<ManifestFormatArg>,
+
+        /// Export the outlines of each contact zone as VTP polylines
+        /// (contact_outlines.vtp)
+        #[arg(long)]
+        export_outlines: bool,
+
+        /// Export which parts touch which as a graph of the parts with their
+        /// interfaces, labelled with paired area and smallest gap
+        /// (interface_graph.dot for GraphViz, interface_graph.json as an
+        /// adjacency list)
+        #[arg(long)]
+        export_graph: bool,
+
+        /// Export the bounding box of every surface and the broad-phase
+        /// outcome of every surface pair (broad_phase.json and
+        /// broad_phase.vtp), to see why an expected pair was never tested
+        #[arg(long)]
+        export_broad_phase: bool,
+
+        /// Write the volume mesh with nodal fields contact_status (0 = no
+        /// contact, k = contact pair k) and contact_gap, to overlay the
+        /// results on the solid mesh (default format: vtu)
+        #[arg(long, value_enum, value_name = "FORMAT", num_args = 0..=1,
+              default_missing_value = "vtu")]
+        export_nodal_status: Option<NodalStatusFormatArg>,
+
+        /// Also write a decimated contact_<A>_<B>.vis.vtu of about N faces
+        /// for contact surfaces with more than N faces, keeping ParaView
+        /// interactive
+        #[arg(long, value_name = "N")]
+        vis_max_faces: Option<usize>,
+
+        /// Write contact_state.py, a ParaView Python script loading the
+        /// outputs colored by distance with the paired faces highlighted
+        /// (.vis.vtu copies are loaded in place of per-pair full files)
+        #[arg(long)]
+        paraview_script: bool,
+
+        /// Flag interfaces whose master/slave face size ratio exceeds this
+        #[arg(long, default_value = "4.0")]
+        max_size_ratio: f64,
+
+        /// Write the mesh density check to mesh_refinement_report.json
+        #[arg(long)]
+        refinement_report: bool,
+
+        /// Check whether interface nodes coincide within this distance and
+        /// export unmatched nodes as node sets
+        #[arg(long, value_name = "TOL")]
+        conformity_tolerance: Option<f64>,
+
+        /// Measure the contact patches and check those around bolt holes
+        /// against the footprint of a pressure cone of this half angle in
+        /// degrees (e.g. 30); writes bolt_footprint_report.json
+        #[arg(long, value_name = "DEGREES")]
+        pressure_cone: Option<f64>,
+
+        /// Bearing diameter of the bolt head or washer relative to the hole
+        /// diameter, for --pressure-cone
+        #[arg(long, default_value = "1.5", requires = "pressure_cone")]
+        head_diameter_ratio: f64,
+
+        /// Clamped thickness under the bolt head, for --pressure-cone
+        /// (default: the hole diameter)
+        #[arg(long, value_name = "LENGTH", requires = "pressure_cone")]
+        clamp_length: Option<f64>,
+
+        /// Find bolts and pins in the holes of other parts from their
+        /// cylindrical surfaces, and pair them whatever their clearance
+        /// compared to --max-gap
+        #[arg(long)]
+        detect_fasteners: bool,
+
+        /// Largest diametral clearance of a fastener relative to the hole
+        /// diameter, for --detect-fasteners
+        #[arg(long, default_value = "0.2", requires = "detect_fasteners")]
+        max_clearance_ratio: f64,
+
+        /// Thermal config (JSON) mapping parts to materials and choosing the
+        /// conductance correlation and its coefficients; writes the
+        /// estimated conductance of each interface to thermal_conductance.csv
+        #[arg(long, value_name = "FILE")]
+        thermal_config: Option<PathBuf>,
+
+        /// Write detected contact sidesets back to Exodus file, with the
+        /// per-face contact fields of the contact surface files as sideset
+        /// variables (pair_id, contact_gap, contact_angle, ...)
         #[arg(long)]
         export_sidesets: bool,
 
+        /// Populate exported sideset distribution factors from contact results
+        #[arg(long, value_enum, requires = "export_sidesets")]
+        sideset_dist_factors: Option<DistFactorArg>,
+
+        /// Restrict exported sidesets to the faces that actually paired
+        #[arg(long, requires = "export_sidesets")]
+        sideset_paired_only: bool,
+
+        /// Template for exported sideset names; placeholders: {pair}, {role}
+        /// (master/slave), {surface}, {block}
+        #[arg(
+            long,
+            value_name = "TEMPLATE",
+            value_parser = parse_sideset_name_template,
+            default_value = contact_detector::io::DEFAULT_SIDESET_NAME_TEMPLATE,
+            requires = "export_sidesets"
+        )]
+        sideset_name_template: String,
+
+        /// Longest sideset/block name to store in the exported Exodus file
+        /// (up to 255); longer names are truncated and kept unique
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = contact_detector::io::EXODUS_MAX_NAME_LEN,
+            value_parser = parse_max_name_length,
+            requires = "export_sidesets"
+        )]
+        max_name_length: usize,
+
         /// Visualize contact surfaces overlaid on full skinned mesh
         #[arg(long)]
         visualize_with_skin: bool,
@@ -195,5 +1039,179 @@ pub enum Commands {
         /// Include full volume mesh in multi-block output
         #[arg(long)]
         export_volume: bool,
+
+        /// Split quads into triangles in the surface output (default split:
+        /// shortest-diagonal); cell data is repeated on both triangles
+        #[arg(long, value_enum, value_name = "SPLIT", num_args = 0..=1,
+              default_missing_value = "shortest-diagonal")]
+        triangulate: Option<TriangulateArg>,
+
+        /// Output file naming
+        #[command(flatten)]
+        naming: OutputArgs,
+
+        /// Region-of-interest filtering
+        #[command(flatten)]
+        roi: RoiArgs,
+
+        /// Assembly scoping
+        #[command(flatten)]
+        scope: AssemblyScopeArgs,
+    },
+
+    /// Track contact pairs between two revisions of a design, matching them
+    /// by the overlap of their contact patches rather than by name
+    Compare {
+        /// Contact results of the old revision (from --save-results)
+        #[arg(value_name = "BEFORE")]
+        before: PathBuf,
+
+        /// Contact results of the new revision (from --save-results)
+        #[arg(value_name = "AFTER")]
+        after: PathBuf,
+
+        /// Distance within which contact points of both revisions overlap
+        /// (default: the contact point spacing of the patches)
+        #[arg(long)]
+        tolerance: Option<f64>,
+
+        /// Print the matched, removed and added pairs as JSON instead of the
+        /// report
+        #[arg(long)]
+        json: bool,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    use contact_detector::io::OverwritePolicy;
+
+    /// Parse a command line given without the program name
+    fn parse(args: &str) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("contact-detector").chain(args.split_whitespace()))
+    }
+
+    #[test]
+    fn test_cli_parsing() {
+        Cli::command().debug_assert();
+
+        // Global options are accepted after the subcommand too
+        let cli = parse("skin mesh.exo -o skin.vtu --vtk-version 2.1").unwrap();
+        assert_eq!(cli.vtk_version, Some(VtkVersion::V2_1));
+        assert!(parse("--vtk-version 5.1 info mesh.exo").is_err());
+        assert!(parse("--vtk-version 2 info mesh.exo").is_err());
+        let cli = parse("info mesh.exo --notation sci --precision 3").unwrap();
+        assert_eq!(cli.notation, Some(Notation::Scientific));
+        assert_eq!(cli.precision, Some(3));
+        assert!(parse("info mesh.exo --notation engineering").is_err());
+        assert!(parse("--ascii info mesh.exo").unwrap().ascii);
+
+        // Every command writing files takes the same overwrite options
+        let policy = |args: &str| match parse(args).unwrap().command {
+            Commands::Convert { existing, .. }
+            | Commands::Skin { existing, .. }
+            | Commands::Contact { existing, .. }
+            | Commands::Probe { existing, .. }
+            | Commands::Deviation { existing, .. } => existing.policy(),
+            Commands::Analyze { naming, .. } | Commands::AutoContact { naming, .. } => {
+                naming.policy()
+            }
+            command => panic!("{:?} writes no files", command),
+        };
+        for command in [
+            "convert mesh.exo -o mesh.json",
+            "skin mesh.exo -o skin.vtu",
+            "contact mesh.exo --part-a A --part-b B -o a.vtu",
+            "probe mesh.exo --path line.json --part-a A --part-b B -o gap.csv",
+            "deviation mesh.exo --points scan.csv -o deviation.vtu",
+            "analyze mesh.exo --pairs A:B -o out",
+            "auto-contact mesh.exo -o out",
+        ] {
+            assert_eq!(policy(command), OverwritePolicy::Error);
+            let overwrite = format!("{} --overwrite", command);
+            assert_eq!(policy(&overwrite), OverwritePolicy::Overwrite);
+            let skip = format!("{} --skip-existing", command);
+            assert_eq!(policy(&skip), OverwritePolicy::Skip);
+            assert!(parse(&format!("{} --overwrite --skip-existing", command)).is_err());
+        }
+
+        // Commands reporting to the console can print JSON instead
+        for command in [
+            "info mesh.exo --json",
+            "query mesh.exo --point 0,-1,2 --json",
+            "compare a.json b.json --json",
+        ] {
+            match parse(command).unwrap().command {
+                Commands::Info { json, .. }
+                | Commands::Query { json, .. }
+                | Commands::Compare { json, .. } => assert!(json),
+                command => panic!("{:?} has no report", command),
+            }
+        }
+
+        // Detection can be scoped to one assembly or two, not both
+        let scope = |args: &str| match parse(args).unwrap().command {
+            Commands::AutoContact { scope, .. } => scope,
+            command => panic!("{:?} is not auto-contact", command),
+        };
+        let within = scope("auto-contact mesh.exo -o out --within-assembly Engine");
+        assert_eq!(within.within_assembly.as_deref(), Some("Engine"));
+        let between = scope("auto-contact mesh.exo -o out --between-assemblies Engine:Mount");
+        assert_eq!(
+            between.between_assemblies,
+            Some(("Engine".to_string(), "Mount".to_string()))
+        );
+        assert!(parse("auto-contact mesh.exo -o out --between-assemblies Engine").is_err());
+        assert!(parse(
+            "auto-contact mesh.exo -o out --within-assembly Engine --between-assemblies A:B"
+        )
+        .is_err());
+
+        // The gap tolerance is a distance or a percentage of the part size
+        let criteria = |args: &str| match parse(args).unwrap().command {
+            Commands::AutoContact { criteria, .. } => criteria,
+            command => panic!("{:?} is not auto-contact", command),
+        };
+        let relative = criteria("auto-contact mesh.exo -o out --max-gap 0.5%:edge");
+        let resolved = relative.resolve(&Settings::default()).unwrap();
+        assert_eq!(resolved.relative_max_gap, Some("0.5%:edge".parse().unwrap()));
+        let absolute = criteria("auto-contact mesh.exo -o out --max-gap 0.002");
+        let resolved = absolute.resolve(&Settings::default()).unwrap();
+        assert_eq!(resolved.max_gap_distance, 0.002);
+        assert!(resolved.relative_max_gap.is_none());
+        assert!(parse("auto-contact mesh.exo -o out --max-gap 0.5%:area").is_err());
+    }
+
+    #[test]
+    fn test_dry_run() {
+        let dry_run = |args: &str| match parse(args).unwrap().command {
+            Commands::Analyze { dry_run, .. } | Commands::AutoContact { dry_run, .. } => dry_run,
+            command => panic!("{:?} has no dry run", command),
+        };
+        assert!(dry_run("analyze mesh.exo --pairs A:B -o out --dry-run"));
+        assert!(dry_run("auto-contact mesh.exo -o out --dry-run"));
+        assert!(!dry_run("auto-contact mesh.exo -o out"));
+        assert!(parse("skin mesh.exo -o skin.vtu --dry-run").is_err());
+    }
+
+    #[test]
+    fn test_logging_options() {
+        let cli = parse("info mesh.exo").unwrap();
+        assert!(!cli.quiet);
+        assert!(cli.log_file.is_none());
+
+        // Global, so accepted on either side of the subcommand
+        let cli = parse("--quiet info mesh.exo --log-file run.log").unwrap();
+        assert!(cli.quiet);
+        assert_eq!(cli.log_file, Some(PathBuf::from("run.log")));
+        assert!(parse("info mesh.exo -q").unwrap().quiet);
+
+        // --quiet cannot be combined with a more verbose console level, but
+        // the log file records debug events either way
+        assert!(parse("--quiet --verbose info mesh.exo").is_err());
+        assert!(parse("--quiet --debug info mesh.exo").is_err());
+        assert!(parse("--debug info mesh.exo --log-file run.log").is_ok());
+    }
+}
@@ -1,17 +1,21 @@
 //! Configuration file support for batch analysis
 
-use crate::contact::ContactCriteria;
+use crate::contact::{ContactCriteria, GapTolerance, PairMetrics};
 use crate::error::{ContactDetectorError, Result};
+use crate::format::Notation;
+use crate::io::VtkVersion;
+use crate::mesh::{combine_surfaces, extract_sideset_surface, Mesh, RoiSpec, SurfaceMesh};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 /// Configuration for a single contact pair analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContactPairConfig {
-    /// Name of the first surface/part
+    /// Name of the first surface/part (surface patch, element block, side set or group)
     pub surface_a: String,
 
-    /// Name of the second surface/part
+    /// Name of the second surface/part (surface patch, element block, side set or group)
     pub surface_b: String,
 
     /// Contact detection criteria
@@ -22,6 +26,122 @@ pub struct ContactPairConfig {
     pub output_file: Option<String>,
 }
 
+/// Interface that must be found, with limits on its coverage and gaps
+///
+/// E.g. `{"surface_a": "Block_A", "surface_b": "Block_B", "min_coverage":
+/// 0.9, "max_gap": 0.1}`: Block_A must pair over at least 90% of its area
+/// with no paired gap above 0.1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpectedContact {
+    /// Name of the first surface/part; coverage is measured on this side
+    pub surface_a: String,
+
+    /// Name of the second surface/part
+    pub surface_b: String,
+
+    /// Smallest paired fraction of surface A's area, in [0, 1]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_coverage: Option<f64>,
+
+    /// Smallest allowed paired gap (negative for an allowed penetration)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_gap: Option<f64>,
+
+    /// Largest allowed paired gap
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_gap: Option<f64>,
+}
+
+impl ExpectedContact {
+    /// Violated limits given the metrics of the pair from surface A to
+    /// surface B, empty if all hold
+    pub fn violations(&self, metrics: &PairMetrics) -> Vec<String> {
+        if metrics.num_pairs() == 0 {
+            return vec!["no contact detected".to_string()];
+        }
+
+        let metrics_a = &metrics.surface_a;
+        let mut violations = Vec::new();
+        let coverage = metrics.coverage_a();
+        if let Some(min_coverage) = self.min_coverage {
+            if coverage < min_coverage {
+                violations.push(format!(
+                    "coverage {:.1}% below {:.1}%",
+                    coverage * 100.0,
+                    min_coverage * 100.0
+                ));
+            }
+        }
+        if let Some(min_gap) = self.min_gap {
+            if metrics_a.min_distance < min_gap {
+                violations.push(format!(
+                    "min gap {:.6} below {:.6}",
+                    metrics_a.min_distance, min_gap
+                ));
+            }
+        }
+        if let Some(max_gap) = self.max_gap {
+            if metrics_a.max_distance > max_gap {
+                violations.push(format!(
+                    "max gap {:.6} above {:.6}",
+                    metrics_a.max_distance, max_gap
+                ));
+            }
+        }
+        violations
+    }
+}
+
+/// Part pairs allowed to be in contact, for flagging unexpected contacts
+///
+/// Read from a text file with one `PartA:PartB` pair per line (`#` starts a
+/// comment); `*` stands for any part. Pairs are unordered and refer to
+/// element blocks, so renumbered surface patches still match.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AllowedContacts {
+    /// Allowed (part, part) pairs
+    pub pairs: Vec<(String, String)>,
+}
+
+impl AllowedContacts {
+    /// Parse the allowed pairs from the text of a list file
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut pairs = Vec::new();
+        for (line_idx, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let parts: Vec<&str> = line.split(':').map(str::trim).collect();
+            if parts.len() != 2 || parts.iter().any(|p| p.is_empty()) {
+                return Err(ContactDetectorError::ConfigError(format!(
+                    "Invalid allowed contact on line {}: '{}'. Expected 'PartA:PartB'",
+                    line_idx + 1,
+                    line
+                )));
+            }
+            pairs.push((parts[0].to_string(), parts[1].to_string()));
+        }
+        Ok(Self { pairs })
+    }
+
+    /// Load the allowed pairs from a list file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to read allowed contacts: {}", e))
+        })?;
+        Self::parse(&content)
+    }
+
+    /// Whether parts `a` and `b` may be in contact
+    pub fn allows(&self, a: &str, b: &str) -> bool {
+        let matches = |pattern: &str, part: &str| pattern == "*" || pattern == part;
+        self.pairs.iter().any(|(p, q)| {
+            (matches(p, a) && matches(q, b)) || (matches(p, b) && matches(q, a))
+        })
+    }
+}
+
 /// Top-level configuration for analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisConfig {
@@ -37,6 +157,26 @@ pub struct AnalysisConfig {
     /// Global contact criteria (can be overridden per pair)
     #[serde(default)]
     pub default_criteria: ContactCriteria,
+
+    /// Named groups of element blocks, surface patches or side sets
+    ///
+    /// A group name can be used in place of a surface name in `contact_pairs`,
+    /// e.g. `"bolts": ["Bolt_1", "Bolt_2"]` to detect "bolts" vs "flange".
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub groups: HashMap<String, Vec<String>>,
+
+    /// Optional region of interest; faces outside it are skipped in pairing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roi: Option<RoiSpec>,
+
+    /// Also drop faces outside the region of interest from the written output
+    #[serde(default)]
+    pub roi_clip_output: bool,
+
+    /// Interfaces that must be found; analyze fails if any is missing or
+    /// outside its limits
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expected_contacts: Vec<ExpectedContact>,
 }
 
 impl AnalysisConfig {
@@ -64,6 +204,72 @@ impl AnalysisConfig {
         Ok(())
     }
 
+    /// Whether `name` refers to a configured group
+    pub fn is_group(&self, name: &str) -> bool {
+        self.groups.contains_key(name)
+    }
+
+    /// Resolve a contact pair side into a single surface
+    ///
+    /// `name` may be a group, a surface patch (e.g. "Block1:patch_0"), an
+    /// element block (all of its patches) or a side set. Groups expand to the
+    /// union of their members; the resulting surface is named `name`.
+    pub fn resolve_surface(
+        &self,
+        name: &str,
+        mesh: &Mesh,
+        surfaces: &[SurfaceMesh],
+    ) -> Result<SurfaceMesh> {
+        let members: Vec<&str> = match self.groups.get(name) {
+            Some(members) if members.is_empty() => {
+                return Err(ContactDetectorError::ConfigError(format!(
+                    "Group '{}' has no members",
+                    name
+                )));
+            }
+            Some(members) => members.iter().map(String::as_str).collect(),
+            None => vec![name],
+        };
+
+        let mut sideset_surfaces = Vec::new();
+        let mut patch_indices = Vec::new();
+        for member in &members {
+            if let Some(idx) = surfaces.iter().position(|s| s.part_name == *member) {
+                patch_indices.push(idx);
+                continue;
+            }
+
+            let block_patches: Vec<usize> = surfaces
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.block_name() == *member)
+                .map(|(i, _)| i)
+                .collect();
+            if !block_patches.is_empty() {
+                patch_indices.extend(block_patches);
+            } else if mesh.side_sets.contains_key(*member) {
+                sideset_surfaces.push(extract_sideset_surface(mesh, member)?);
+            } else {
+                return Err(ContactDetectorError::ElementBlockNotFound(member.to_string()));
+            }
+        }
+
+        // Single patch referenced by its own name: return it unchanged
+        if patch_indices.len() == 1 && sideset_surfaces.is_empty() && surfaces[patch_indices[0]].part_name == name {
+            return Ok(surfaces[patch_indices[0]].clone());
+        }
+
+        patch_indices.sort_unstable();
+        patch_indices.dedup();
+        let parts: Vec<&SurfaceMesh> = patch_indices
+            .iter()
+            .map(|&i| &surfaces[i])
+            .chain(sideset_surfaces.iter())
+            .collect();
+
+        Ok(combine_surfaces(name.to_string(), &parts))
+    }
+
     /// Parse contact pairs from command-line string
     /// Format: "PartA:PartB,PartC:PartD"
     pub fn from_pairs_string(
@@ -96,10 +302,346 @@ impl AnalysisConfig {
             output_dir,
             contact_pairs,
             default_criteria,
+            groups: HashMap::new(),
+            roi: None,
+            roi_clip_output: false,
+            expected_contacts: Vec::new(),
         })
     }
 }
 
+/// File in the user configuration directory defining criteria presets
+pub const PRESETS_FILE_NAME: &str = "presets.yaml";
+
+/// Named set of detection tolerances, e.g. a team standard for tied
+/// interfaces
+///
+/// Unset fields keep the [`ContactCriteria`] defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CriteriaPreset {
+    /// What the preset is meant for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Maximum gap distance, or a percentage of the part size ("0.5%")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_gap: Option<GapTolerance>,
+
+    /// Maximum penetration distance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_penetration: Option<f64>,
+
+    /// Maximum normal angle in degrees
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_angle: Option<f64>,
+
+    /// Minimum facing of paired faces (0 disables the test)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_facing: Option<f64>,
+
+    /// Candidate search radius as a multiple of the maximum gap
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_radius_multiplier: Option<f64>,
+}
+
+impl CriteriaPreset {
+    /// Default criteria with the preset's tolerances
+    pub fn criteria(&self) -> ContactCriteria {
+        let mut criteria = ContactCriteria::default();
+        if let Some(max_gap) = &self.max_gap {
+            max_gap.apply(&mut criteria);
+        }
+        if let Some(max_penetration) = self.max_penetration {
+            criteria.max_penetration = max_penetration;
+        }
+        if let Some(max_angle) = self.max_angle {
+            criteria.max_normal_angle = max_angle;
+        }
+        if let Some(min_facing) = self.min_facing {
+            criteria.min_facing = min_facing;
+        }
+        if let Some(multiplier) = self.search_radius_multiplier {
+            criteria.search_radius_multiplier = multiplier;
+        }
+        criteria
+    }
+}
+
+/// Criteria presets by name: the built-in ones and those defined by the user
+///
+/// User presets are read from a YAML map of name to preset, e.g.
+///
+/// ```yaml
+/// flange-seal:
+///   description: Gasketed flanges
+///   max_gap: 0.0002
+///   max_angle: 10
+/// ```
+///
+/// and replace built-in presets of the same name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CriteriaPresets {
+    presets: BTreeMap<String, CriteriaPreset>,
+}
+
+impl CriteriaPresets {
+    /// The built-in presets
+    pub fn builtin() -> Self {
+        let preset =
+            |description: &str, max_gap, max_penetration, max_angle, min_facing| CriteriaPreset {
+                description: Some(description.to_string()),
+                max_gap: Some(GapTolerance::Absolute(max_gap)),
+                max_penetration: Some(max_penetration),
+                max_angle: Some(max_angle),
+                min_facing: Some(min_facing),
+                search_radius_multiplier: None,
+            };
+        let mut presets = BTreeMap::new();
+        presets.insert(
+            "general-contact".to_string(),
+            preset(
+                "Default tolerances for touching parts",
+                0.005,
+                0.001,
+                45.0,
+                0.5,
+            ),
+        );
+        presets.insert(
+            "tied-strict".to_string(),
+            preset(
+                "Coincident, parallel faces of tied or bonded interfaces",
+                1e-4,
+                1e-4,
+                5.0,
+                0.9,
+            ),
+        );
+        presets.insert(
+            "interference-check".to_string(),
+            CriteriaPreset {
+                // Overlapping faces sit up to the penetration apart, so the
+                // search radius has to reach that far
+                search_radius_multiplier: Some(60.0),
+                ..preset(
+                    "Overlapping parts (press fits, modelling errors)",
+                    1e-4,
+                    0.005,
+                    30.0,
+                    0.5,
+                )
+            },
+        );
+        Self { presets }
+    }
+
+    /// Built-in presets plus those of the user configuration directory's
+    /// [`PRESETS_FILE_NAME`], if there is one
+    pub fn with_user_presets() -> Result<Self> {
+        let mut presets = Self::builtin();
+        if let Some(path) = user_config_dir().map(|dir| dir.join(PRESETS_FILE_NAME)) {
+            if path.is_file() {
+                presets.add_file(&path)?;
+            }
+        }
+        Ok(presets)
+    }
+
+    /// Add the presets of a YAML file
+    pub fn add_file(&mut self, path: &Path) -> Result<()> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            ContactDetectorError::ConfigError(format!(
+                "Failed to read presets file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let presets: BTreeMap<String, CriteriaPreset> =
+            serde_yaml::from_str(&content).map_err(|e| {
+                ContactDetectorError::ConfigError(format!(
+                    "Failed to parse presets file {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+        self.presets.extend(presets);
+        Ok(())
+    }
+
+    /// Add the presets of a YAML map of name to preset
+    pub fn add_yaml(&mut self, text: &str) -> Result<()> {
+        let presets: BTreeMap<String, CriteriaPreset> =
+            serde_yaml::from_str(text).map_err(|e| {
+                ContactDetectorError::ConfigError(format!("Failed to parse presets: {}", e))
+            })?;
+        self.presets.extend(presets);
+        Ok(())
+    }
+
+    /// The preset called `name`
+    pub fn get(&self, name: &str) -> Result<&CriteriaPreset> {
+        self.presets.get(name).ok_or_else(|| {
+            ContactDetectorError::ConfigError(format!(
+                "Unknown criteria preset '{}' (available: {})",
+                name,
+                self.names().collect::<Vec<_>>().join(", ")
+            ))
+        })
+    }
+
+    /// Preset names in alphabetical order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.presets.keys().map(String::as_str)
+    }
+}
+
+/// Directory holding the user's configuration files
+///
+/// `$CONTACT_DETECTOR_CONFIG_DIR` if set, otherwise `contact-detector` in
+/// `$XDG_CONFIG_HOME` or `~/.config`.
+pub fn user_config_dir() -> Option<PathBuf> {
+    let var = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty());
+    if let Some(dir) = var("CONTACT_DETECTOR_CONFIG_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| Path::new(&home).join(".config")))
+        .map(|dir| dir.join("contact-detector"))
+}
+
+/// Settings file looked up in the working directory and its parents and in
+/// the user configuration directory
+pub const SETTINGS_FILE_NAME: &str = "contact-detector.toml";
+
+/// Defaults for command-line options from `contact-detector.toml` files
+///
+/// ```toml
+/// vtk_version = "2.2"
+/// threads = 8
+/// manifest_format = "yaml"
+/// notation = "scientific"
+/// precision = 4
+/// ascii = true
+/// preset = "flange-seal"
+///
+/// [presets.flange-seal]
+/// max_gap = 0.0002
+/// max_angle = 10
+/// ```
+///
+/// The project file (the nearest one from the working directory upward)
+/// takes precedence over the user's file, and flags given on the command
+/// line over both.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    /// VTK file format version, e.g. "2.2"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vtk_version: Option<VtkVersion>,
+
+    /// Number of worker threads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<usize>,
+
+    /// File format of contact manifests ("json" or "yaml")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_format: Option<String>,
+
+    /// Notation of floating-point values in reports, CSV files and metadata
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notation: Option<Notation>,
+
+    /// Decimals of floating-point values in reports, CSV files and metadata
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub precision: Option<usize>,
+
+    /// Write only ASCII to the console
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ascii: Option<bool>,
+
+    /// Criteria preset used when no other is selected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+
+    /// Criteria presets, added to the built-in and user-defined ones
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub presets: BTreeMap<String, CriteriaPreset>,
+
+    /// Files the settings were read from, lowest precedence first
+    #[serde(skip)]
+    pub sources: Vec<PathBuf>,
+}
+
+impl Settings {
+    /// Parse settings from the text of a settings file
+    pub fn parse(text: &str) -> Result<Self> {
+        toml::from_str(text).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to parse settings: {}", e))
+        })
+    }
+
+    /// Load settings from a file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            ContactDetectorError::ConfigError(format!(
+                "Failed to read settings file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let mut settings: Self = toml::from_str(&content).map_err(|e| {
+            ContactDetectorError::ConfigError(format!(
+                "Failed to parse settings file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        settings.sources = vec![path.to_path_buf()];
+        Ok(settings)
+    }
+
+    /// Settings of the user's file overlaid with those of the nearest
+    /// project file from `dir` upward (either may be missing)
+    pub fn discover(dir: &Path) -> Result<Self> {
+        let user = user_config_dir().map(|dir| dir.join(SETTINGS_FILE_NAME));
+        let project = dir
+            .ancestors()
+            .map(|dir| dir.join(SETTINGS_FILE_NAME))
+            .find(|path| path.is_file());
+
+        let mut settings = Self::default();
+        for path in [user, project].into_iter().flatten() {
+            if path.is_file() && !settings.sources.contains(&path) {
+                settings.merge(Self::load(&path)?);
+            }
+        }
+        Ok(settings)
+    }
+
+    /// Overlay `other` onto these settings; its values take precedence
+    pub fn merge(&mut self, other: Settings) {
+        self.vtk_version = other.vtk_version.or(self.vtk_version.take());
+        self.threads = other.threads.or(self.threads);
+        self.manifest_format = other.manifest_format.or(self.manifest_format.take());
+        self.notation = other.notation.or(self.notation);
+        self.precision = other.precision.or(self.precision);
+        self.ascii = other.ascii.or(self.ascii);
+        self.preset = other.preset.or(self.preset.take());
+        self.presets.extend(other.presets);
+        self.sources.extend(other.sources);
+    }
+
+    /// Built-in and user-defined criteria presets with those of the
+    /// settings on top
+    pub fn criteria_presets(&self) -> Result<CriteriaPresets> {
+        let mut presets = CriteriaPresets::with_user_presets()?;
+        presets.presets.extend(self.presets.clone());
+        Ok(presets)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,6 +663,208 @@ mod tests {
         assert_eq!(config.contact_pairs[1].surface_b, "Block4");
     }
 
+    #[test]
+    fn test_roi_in_config() {
+        let config: AnalysisConfig = serde_json::from_str(
+            r#"{
+                "input_file": "test.exo",
+                "output_dir": "output",
+                "contact_pairs": [],
+                "roi": {"type": "box", "min": [0, 0, 0], "max": [1, 1, 1]},
+                "roi_clip_output": true
+            }"#,
+        )
+        .unwrap();
+
+        assert!(matches!(config.roi, Some(RoiSpec::Box { .. })));
+        assert!(config.roi_clip_output);
+    }
+
+    #[test]
+    fn test_resolve_group_surface() {
+        use crate::mesh::{HexElement, Point};
+
+        let mut mesh = Mesh::new();
+        mesh.nodes = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(0.0, 0.0, 1.0),
+            Point::new(1.0, 0.0, 1.0),
+            Point::new(1.0, 1.0, 1.0),
+            Point::new(0.0, 1.0, 1.0),
+        ];
+        mesh.elements = vec![HexElement::new([0, 1, 2, 3, 4, 5, 6, 7])];
+        mesh.element_blocks.insert("Bolt_1".to_string(), vec![0]);
+        mesh.side_sets.insert("top".to_string(), vec![(0, 6)]);
+        let surfaces = crate::mesh::extract_surface(&mesh).unwrap();
+
+        let mut config = AnalysisConfig::from_pairs_string(
+            "test.exo".to_string(),
+            "output".to_string(),
+            "bolts:top",
+            ContactCriteria::default(),
+        )
+        .unwrap();
+        config
+            .groups
+            .insert("bolts".to_string(), vec!["Bolt_1".to_string(), "top".to_string()]);
+
+        assert!(config.is_group("bolts"));
+
+        // Block expands to all 6 patches, side set adds one more face
+        let bolts = config.resolve_surface("bolts", &mesh, &surfaces).unwrap();
+        assert_eq!(bolts.part_name, "bolts");
+        assert_eq!(bolts.num_faces(), 7);
+
+        let top = config.resolve_surface("top", &mesh, &surfaces).unwrap();
+        assert_eq!(top.num_faces(), 1);
+
+        assert!(config.resolve_surface("missing", &mesh, &surfaces).is_err());
+    }
+
+    #[test]
+    fn test_expected_contact_violations() {
+        let config: AnalysisConfig = serde_json::from_str(
+            r#"{
+                "input_file": "test.exo",
+                "output_dir": "output",
+                "contact_pairs": [],
+                "expected_contacts": [
+                    {"surface_a": "Block_A", "surface_b": "Block_B", "min_coverage": 0.9, "max_gap": 0.1}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let expected = &config.expected_contacts[0];
+
+        let mut metrics_a = crate::contact::SurfaceMetrics {
+            total_area: 10.0,
+            paired_area: 9.5,
+            unpaired_area: 0.5,
+            avg_distance: 0.02,
+            std_dev_distance: 0.01,
+            min_distance: -0.001,
+            max_distance: 0.05,
+            avg_normal_angle: 179.0,
+            num_pairs: 95,
+            num_unpaired: 5,
+            opposition_deviation: Default::default(),
+        };
+        let mut metrics = PairMetrics {
+            surface_a: metrics_a.clone(),
+            surface_b: metrics_a.clone(),
+            size_ratio: 1.0,
+            interface_type: Default::default(),
+        };
+        assert!(expected.violations(&metrics).is_empty());
+
+        metrics_a.paired_area = 4.0;
+        metrics_a.max_distance = 0.2;
+        metrics.surface_a = metrics_a.clone();
+        let violations = expected.violations(&metrics);
+        assert_eq!(violations.len(), 2);
+        assert!(violations[0].starts_with("coverage 40.0%"));
+        assert!(violations[1].starts_with("max gap"));
+
+        metrics.surface_a.num_pairs = 0;
+        assert_eq!(expected.violations(&metrics), vec!["no contact detected"]);
+    }
+
+    #[test]
+    fn test_allowed_contacts() {
+        let allowed = AllowedContacts::parse(
+            "# bolted flange\nFlange:Bolt_1\n\nWasher : *  # washers touch anything\n",
+        )
+        .unwrap();
+        assert_eq!(allowed.pairs.len(), 2);
+        assert!(allowed.allows("Flange", "Bolt_1"));
+        assert!(allowed.allows("Bolt_1", "Flange"));
+        assert!(allowed.allows("Housing", "Washer"));
+        assert!(!allowed.allows("Flange", "Housing"));
+
+        assert!(AllowedContacts::parse("Flange:Bolt_1:Nut").is_err());
+        assert!(AllowedContacts::parse("Flange:").is_err());
+    }
+
+    #[test]
+    fn test_criteria_presets() {
+        let mut presets = CriteriaPresets::builtin();
+        let general = presets.get("general-contact").unwrap().criteria();
+        let default = ContactCriteria::default();
+        assert_eq!(general.max_gap_distance, default.max_gap_distance);
+        assert_eq!(general.min_facing, default.min_facing);
+
+        let interference = presets.get("interference-check").unwrap().criteria();
+        assert!(interference.search_radius() > interference.max_penetration);
+
+        let error = presets.get("tied").unwrap_err().to_string();
+        assert!(error.contains("interference-check, tied-strict"));
+
+        // User presets add to and replace the built-in ones; unset fields
+        // keep the defaults
+        presets
+            .add_yaml("flange-seal:\n  max_gap: 0.0002\n  max_angle: 10\ntied-strict:\n  max_gap: 0.001\n")
+            .unwrap();
+        let seal = presets.get("flange-seal").unwrap().criteria();
+        assert_eq!(seal.max_gap_distance, 0.0002);
+        assert_eq!(seal.max_normal_angle, 10.0);
+        assert_eq!(seal.max_penetration, default.max_penetration);
+        assert_eq!(
+            presets.get("tied-strict").unwrap().max_gap,
+            Some(GapTolerance::Absolute(0.001))
+        );
+        assert_eq!(presets.get("tied-strict").unwrap().max_angle, None);
+
+        // Relative gap tolerances scale with each pair of parts
+        presets.add_yaml("scaled:\n  max_gap: 0.5%\n").unwrap();
+        let scaled = presets.get("scaled").unwrap().criteria();
+        assert_eq!(scaled.relative_max_gap.map(|r| r.percent), Some(0.5));
+
+        assert!(presets.add_yaml("bad:\n  max_gapp: 0.1\n").is_err());
+    }
+
+    #[test]
+    fn test_settings_layering() {
+        let dir = std::env::temp_dir().join("test_settings_layering");
+        let project = dir.join("project");
+        let nested = project.join("meshes/rev_b");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(
+            project.join(SETTINGS_FILE_NAME),
+            "vtk_version = \"2.2\"\npreset = \"seal\"\n\n[presets.seal]\nmax_gap = 0.0002\n",
+        )
+        .unwrap();
+
+        // Found from a subdirectory of the project
+        let settings = Settings::discover(&nested).unwrap();
+        assert_eq!(settings.vtk_version, Some(VtkVersion::V2_2));
+        assert_eq!(
+            settings.sources.last(),
+            Some(&project.join(SETTINGS_FILE_NAME))
+        );
+        let presets = settings.criteria_presets().unwrap();
+        assert_eq!(
+            presets.get("seal").unwrap().max_gap,
+            Some(GapTolerance::Absolute(0.0002))
+        );
+        assert!(presets.get("tied-strict").is_ok());
+
+        // Later settings take precedence, unset values fall through
+        let mut merged =
+            Settings::parse("threads = 4\nvtk_version = \"4.2\"\nnotation = \"scientific\"")
+                .unwrap();
+        merged.merge(settings);
+        assert_eq!(merged.threads, Some(4));
+        assert_eq!(merged.notation, Some(Notation::Scientific));
+        assert_eq!(merged.vtk_version, Some(VtkVersion::V2_2));
+        assert_eq!(merged.preset.as_deref(), Some("seal"));
+
+        assert!(Settings::parse("thread = 4").is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_invalid_pairs_string() {
         let result = AnalysisConfig::from_pairs_string(
@@ -0,0 +1,325 @@
+//! Pluggable contact detection algorithms
+//!
+//! Detection runs in two phases. Candidate generation is shared: a spatial
+//! index over the face centroids of surface B yields the faces near each face
+//! of surface A (see [`CandidateGenerator`]). Each candidate is then measured
+//! by a [`ContactDetectionAlgorithm`], which decides how the gap between the
+//! two faces is defined; the gap, normal angle and facing criteria and the
+//! choice of the closest candidate are again common to all algorithms.
+//!
+//! The built-in algorithms are selected with [`DetectionAlgorithmKind`] in the
+//! detection criteria. Others are passed to
+//! [`crate::contact::detect_contact_pairs_with`] directly.
+
+use crate::contact::spatial_index::{CentroidIndex, SpatialIndexKind};
+use crate::mesh::geometry::{
+    closest_point_on_face, project_point_to_plane, signed_distance_to_plane,
+};
+use crate::mesh::types::{Point, SurfaceMesh, Vec3};
+use serde::{Deserialize, Serialize};
+
+/// Relative tolerance for a point to lie on a face
+const ON_FACE_TOLERANCE: f64 = 1e-6;
+
+/// A candidate pair: a face of surface A and a nearby face of surface B
+#[derive(Debug, Clone, Copy)]
+pub struct FacePair<'a> {
+    pub surface_a: &'a SurfaceMesh,
+    pub face_a: usize,
+    pub surface_b: &'a SurfaceMesh,
+    pub face_b: usize,
+}
+
+impl FacePair<'_> {
+    pub fn centroid_a(&self) -> &Point {
+        &self.surface_a.face_centroids[self.face_a]
+    }
+
+    pub fn normal_a(&self) -> &Vec3 {
+        &self.surface_a.face_normals[self.face_a]
+    }
+
+    pub fn centroid_b(&self) -> &Point {
+        &self.surface_b.face_centroids[self.face_b]
+    }
+
+    pub fn normal_b(&self) -> &Vec3 {
+        &self.surface_b.face_normals[self.face_b]
+    }
+
+    /// Point of face B closest to `point`
+    pub fn closest_point_on_b(&self, point: &Point) -> Point {
+        closest_point_on_face(
+            point,
+            &self.surface_b.faces[self.face_b],
+            &self.surface_b.nodes,
+        )
+    }
+
+    /// Hit of the line through `origin` along A's normal with face B, as the
+    /// signed distance along the normal and the hit point
+    ///
+    /// `None` if the line is parallel to B's plane or misses the face.
+    fn cast_along_normal_a(&self, origin: &Point) -> Option<(f64, Point)> {
+        let normal_a = self.normal_a();
+        let normal_b = self.normal_b();
+        let denominator = normal_a.dot(normal_b);
+        if denominator.abs() < 1e-12 {
+            return None;
+        }
+        let t = (self.centroid_b() - origin).dot(normal_b) / denominator;
+        let hit = origin + normal_a * t;
+        let tolerance = ON_FACE_TOLERANCE * self.surface_b.face_areas[self.face_b].sqrt();
+        ((self.closest_point_on_b(&hit) - hit).norm() <= tolerance).then_some((t, hit))
+    }
+}
+
+/// Gap between the faces of a candidate pair as measured by an algorithm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Measurement {
+    /// Signed distance (+ for gap, - for overlap)
+    pub distance: f64,
+
+    /// Contact point on surface B
+    pub contact_point: Point,
+}
+
+/// Narrow phase of contact detection: measures a candidate pair
+pub trait ContactDetectionAlgorithm: Send + Sync {
+    /// Short algorithm name, e.g. `centroid_plane`
+    fn name(&self) -> &str;
+
+    /// Gap between the faces, or `None` if the algorithm finds no contact
+    /// geometry between them (e.g. a ray that misses face B)
+    fn measure(&self, pair: &FacePair) -> Option<Measurement>;
+}
+
+/// Distance from A's centroid to the plane of B's centroid along A's normal;
+/// the contact point is A's centroid projected onto B's plane
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CentroidPlane;
+
+impl ContactDetectionAlgorithm for CentroidPlane {
+    fn name(&self) -> &str {
+        "centroid_plane"
+    }
+
+    fn measure(&self, pair: &FacePair) -> Option<Measurement> {
+        Some(Measurement {
+            distance: signed_distance_to_plane(
+                pair.centroid_b(),
+                pair.centroid_a(),
+                pair.normal_a(),
+            ),
+            contact_point: project_point_to_plane(
+                pair.centroid_a(),
+                pair.centroid_b(),
+                pair.normal_b(),
+            ),
+        })
+    }
+}
+
+/// Distance from A's centroid to the closest point of face B, signed by the
+/// side of A it lies on; robust for faces that only partly overlap
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClosestPoint;
+
+impl ContactDetectionAlgorithm for ClosestPoint {
+    fn name(&self) -> &str {
+        "closest_point"
+    }
+
+    fn measure(&self, pair: &FacePair) -> Option<Measurement> {
+        let closest = pair.closest_point_on_b(pair.centroid_a());
+        let offset = closest - pair.centroid_a();
+        let distance = if offset.dot(pair.normal_a()) < 0.0 {
+            -offset.norm()
+        } else {
+            offset.norm()
+        };
+        Some(Measurement {
+            distance,
+            contact_point: closest,
+        })
+    }
+}
+
+/// Ray from A's centroid along A's normal (both directions) intersected with
+/// face B; candidates the ray misses are not in contact
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RayCast;
+
+impl ContactDetectionAlgorithm for RayCast {
+    fn name(&self) -> &str {
+        "ray_cast"
+    }
+
+    fn measure(&self, pair: &FacePair) -> Option<Measurement> {
+        let (distance, contact_point) = pair.cast_along_normal_a(pair.centroid_a())?;
+        Some(Measurement {
+            distance,
+            contact_point,
+        })
+    }
+}
+
+/// Gap averaged over integration points of face A (its corners pulled halfway
+/// to the centroid) that project along A's normal onto face B, in the spirit
+/// of mortar methods; at least one point has to land on B
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Mortar;
+
+impl ContactDetectionAlgorithm for Mortar {
+    fn name(&self) -> &str {
+        "mortar"
+    }
+
+    fn measure(&self, pair: &FacePair) -> Option<Measurement> {
+        let centroid_a = pair.centroid_a();
+        let face_a = &pair.surface_a.faces[pair.face_a];
+        let hits: Vec<(f64, Point)> = face_a
+            .corners()
+            .iter()
+            .map(|&node| centroid_a + (pair.surface_a.nodes[node] - centroid_a) * 0.5)
+            .chain(std::iter::once(*centroid_a))
+            .filter_map(|point| pair.cast_along_normal_a(&point))
+            .collect();
+        if hits.is_empty() {
+            return None;
+        }
+
+        let n = hits.len() as f64;
+        let distance = hits.iter().map(|(d, _)| d).sum::<f64>() / n;
+        let sum = hits
+            .iter()
+            .fold(Vec3::zeros(), |sum, (_, p)| sum + p.coords);
+        Some(Measurement {
+            distance,
+            contact_point: Point::from(sum / n),
+        })
+    }
+}
+
+/// Built-in detection algorithm used by the criteria
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DetectionAlgorithmKind {
+    /// Centroid of A to the plane of B's centroid (fastest)
+    #[default]
+    CentroidPlane,
+
+    /// Centroid of A to the closest point of face B
+    ClosestPoint,
+
+    /// Ray from A's centroid along its normal onto face B
+    RayCast,
+
+    /// Average gap over several integration points of A
+    Mortar,
+}
+
+impl DetectionAlgorithmKind {
+    /// All built-in algorithms, e.g. for benchmarking them against each other
+    pub const ALL: [DetectionAlgorithmKind; 4] = [
+        DetectionAlgorithmKind::CentroidPlane,
+        DetectionAlgorithmKind::ClosestPoint,
+        DetectionAlgorithmKind::RayCast,
+        DetectionAlgorithmKind::Mortar,
+    ];
+
+    /// The algorithm implementation
+    pub fn algorithm(self) -> &'static dyn ContactDetectionAlgorithm {
+        match self {
+            DetectionAlgorithmKind::CentroidPlane => &CentroidPlane,
+            DetectionAlgorithmKind::ClosestPoint => &ClosestPoint,
+            DetectionAlgorithmKind::RayCast => &RayCast,
+            DetectionAlgorithmKind::Mortar => &Mortar,
+        }
+    }
+}
+
+/// Candidate generation shared by all algorithms: the faces of surface B
+/// whose centroids are within the search radius of a point
+pub struct CandidateGenerator {
+    index: CentroidIndex,
+    search_radius: f64,
+}
+
+impl CandidateGenerator {
+    /// Index the faces of `surface_b`
+    pub fn new(surface_b: &SurfaceMesh, kind: SpatialIndexKind, search_radius: f64) -> Self {
+        Self {
+            index: CentroidIndex::build(surface_b, kind, search_radius),
+            search_radius,
+        }
+    }
+
+    /// Faces of surface B near `point`, nearest first
+    pub fn candidates(&self, point: &Point) -> Vec<usize> {
+        self.index.within(point, self.search_radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::types::QuadFace;
+
+    fn square(z: f64, x0: f64, normal_z: f64) -> SurfaceMesh {
+        SurfaceMesh {
+            part_name: format!("z{}", z),
+            faces: vec![QuadFace::new([0, 1, 2, 3])],
+            face_normals: vec![Vec3::new(0.0, 0.0, normal_z)],
+            face_centroids: vec![Point::new(x0 + 0.5, 0.5, z)],
+            face_areas: vec![1.0],
+            nodes: vec![
+                Point::new(x0, 0.0, z),
+                Point::new(x0 + 1.0, 0.0, z),
+                Point::new(x0 + 1.0, 1.0, z),
+                Point::new(x0, 1.0, z),
+            ],
+            face_tags: Default::default(),
+        }
+    }
+
+    fn measure(kind: DetectionAlgorithmKind, a: &SurfaceMesh, b: &SurfaceMesh) -> Option<f64> {
+        let pair = FacePair {
+            surface_a: a,
+            face_a: 0,
+            surface_b: b,
+            face_b: 0,
+        };
+        kind.algorithm().measure(&pair).map(|m| m.distance)
+    }
+
+    #[test]
+    fn test_algorithms_agree_on_aligned_faces() {
+        let a = square(0.0, 0.0, 1.0);
+        let b = square(0.002, 0.0, -1.0);
+        for kind in DetectionAlgorithmKind::ALL {
+            let distance = measure(kind, &a, &b).unwrap();
+            assert!((distance - 0.002).abs() < 1e-12, "{:?}: {}", kind, distance);
+        }
+    }
+
+    #[test]
+    fn test_algorithms_differ_on_offset_faces() {
+        // B is shifted past A's centroid: only its edge region is across
+        let a = square(0.0, 0.0, 1.0);
+        let b = square(0.002, 0.7, -1.0);
+
+        assert!(
+            (measure(DetectionAlgorithmKind::CentroidPlane, &a, &b).unwrap() - 0.002).abs() < 1e-12
+        );
+        let closest = measure(DetectionAlgorithmKind::ClosestPoint, &a, &b).unwrap();
+        assert!((closest - (0.2f64.powi(2) + 0.002f64.powi(2)).sqrt()).abs() < 1e-12);
+        assert_eq!(measure(DetectionAlgorithmKind::RayCast, &a, &b), None);
+        // The two integration points at x = 0.75 land on B
+        let mortar = measure(DetectionAlgorithmKind::Mortar, &a, &b).unwrap();
+        assert!((mortar - 0.002).abs() < 1e-12);
+
+        let b = square(0.002, 1.5, -1.0);
+        assert_eq!(measure(DetectionAlgorithmKind::Mortar, &a, &b), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! Contact pairs as contiguous columns
+//!
+//! [`ContactResults::pairs`] stores one record per pair, which suits building
+//! and filtering results but makes numerics code gather each field pair by
+//! pair. [`PairArrays`] splits the pairs into one array per field, built in a
+//! single pass, whose slices (or `ndarray` views with the `ndarray` feature)
+//! can be handed to numpy bindings or optimization loops as they are. See
+//! [`crate::mesh::arrays`] for the face arrays of a surface.
+
+use crate::contact::types::ContactResults;
+use crate::mesh::arrays::point_coords;
+use crate::mesh::types::Point;
+
+/// The pairs of a [`ContactResults`], one array per field, in pair order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PairArrays {
+    /// Face of surface A of each pair
+    pub surface_a_faces: Vec<usize>,
+
+    /// Face of surface B of each pair
+    pub surface_b_faces: Vec<usize>,
+
+    /// Signed distances (+ for gap, - for overlap)
+    pub distances: Vec<f64>,
+
+    /// Angles between the face normals in degrees
+    pub normal_angles: Vec<f64>,
+
+    /// Contact points on surface B
+    pub contact_points: Vec<Point>,
+}
+
+impl PairArrays {
+    /// Number of pairs
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    /// Whether there are no pairs
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+
+    /// Contact points as `[x, y, z]` rows, sharing their memory
+    pub fn contact_point_coords(&self) -> &[[f64; 3]] {
+        point_coords(&self.contact_points)
+    }
+
+    /// Signed distances as an array
+    #[cfg(feature = "ndarray")]
+    pub fn distance_view(&self) -> ndarray::ArrayView1<'_, f64> {
+        self.distances.as_slice().into()
+    }
+
+    /// Normal angles as an array
+    #[cfg(feature = "ndarray")]
+    pub fn normal_angle_view(&self) -> ndarray::ArrayView1<'_, f64> {
+        self.normal_angles.as_slice().into()
+    }
+
+    /// Contact points as an n × 3 array
+    #[cfg(feature = "ndarray")]
+    pub fn contact_point_view(&self) -> ndarray::ArrayView2<'_, f64> {
+        self.contact_point_coords().into()
+    }
+}
+
+impl From<&ContactResults> for PairArrays {
+    fn from(results: &ContactResults) -> Self {
+        let n = results.pairs.len();
+        let mut arrays = PairArrays {
+            surface_a_faces: Vec::with_capacity(n),
+            surface_b_faces: Vec::with_capacity(n),
+            distances: Vec::with_capacity(n),
+            normal_angles: Vec::with_capacity(n),
+            contact_points: Vec::with_capacity(n),
+        };
+        for pair in &results.pairs {
+            arrays.surface_a_faces.push(pair.surface_a_face_id);
+            arrays.surface_b_faces.push(pair.surface_b_face_id);
+            arrays.distances.push(pair.distance);
+            arrays.normal_angles.push(pair.normal_angle);
+            arrays.contact_points.push(pair.contact_point);
+        }
+        arrays
+    }
+}
+
+impl ContactResults {
+    /// The pairs split into one array per field (see [`PairArrays`])
+    pub fn to_arrays(&self) -> PairArrays {
+        PairArrays::from(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::{ContactCriteria, ContactPair};
+
+    #[test]
+    fn test_pair_arrays() {
+        let mut results = ContactResults::new("A".into(), "B".into(), ContactCriteria::default());
+        for (face, distance) in [(3, 0.002), (7, -0.001)] {
+            results.pairs.push(ContactPair {
+                surface_a_face_id: face,
+                surface_b_face_id: face + 1,
+                distance,
+                normal_angle: 5.0,
+                contact_point: Point::new(face as f64, 0.0, 1.0),
+            });
+        }
+
+        let arrays = results.to_arrays();
+        assert_eq!(arrays.len(), 2);
+        assert_eq!(arrays.surface_a_faces, [3, 7]);
+        assert_eq!(arrays.surface_b_faces, [4, 8]);
+        assert_eq!(arrays.distances, [0.002, -0.001]);
+        assert_eq!(arrays.contact_point_coords()[1], [7.0, 0.0, 1.0]);
+        assert!(
+            ContactResults::new("A".into(), "B".into(), ContactCriteria::default())
+                .to_arrays()
+                .is_empty()
+        );
+
+        #[cfg(feature = "ndarray")]
+        {
+            assert_eq!(arrays.distance_view().sum(), 0.001);
+            assert_eq!(arrays.contact_point_view().column(0).to_vec(), [3.0, 7.0]);
+        }
+    }
+}
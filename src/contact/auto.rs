@@ -0,0 +1,1285 @@
+//! End-to-end automatic contact detection
+//!
+//! Runs the whole auto-contact workflow on a mesh: surface extraction, patch
+//! filtering, the bounding-volume broad phase, an optional coarse pass, and
+//! detection on every remaining pair of surfaces. [`detect_all`] does it in
+//! one call; [`plan_all`] stops after the broad phase so callers can inspect (or
+//! print) the planned pair tests before [`AutoContactPlan::run`] does the
+//! expensive part. The [`AutoContactReport`] then derives everything else a
+//! run reports about the interfaces it found: reports, checks, merged
+//! interfaces and nodal status.
+
+use crate::config::AllowedContacts;
+use crate::contact::broad_phase::{BroadPhaseGraph, BroadPhaseStatus};
+use crate::contact::coarse::{coarse_contact_estimate, CoarseEstimate};
+use crate::contact::conformity::InterfaceConformity;
+use crate::contact::detection::{detect_contact_pairs, region_pairs, search_pairs};
+use crate::contact::fasteners::{find_fastener_fits, FastenerFit, FastenerReport};
+use crate::contact::footprint::{FootprintReport, InterfaceFootprints, PressureCone};
+use crate::contact::interface::{ContactInterface, InterfaceSide};
+use crate::contact::interface_graph::InterfaceGraph;
+use crate::contact::merge::{merge_contact_pairs, DetectedPair, MergedInterface};
+use crate::contact::metrics::PairMetrics;
+use crate::contact::nodal::NodalContact;
+use crate::contact::occlusion::{remove_occluded_pairs, VolumeIndex};
+use crate::contact::refinement::{MeshDensityAssessment, RefinementReport};
+use crate::contact::sdf::{detect_contact_pairs_sdf, SignedDistanceField};
+use crate::contact::summary::RunSummary;
+use crate::contact::thermal::{InterfaceConductance, ThermalConfig};
+use crate::contact::types::{ContactCriteria, RejectionStats};
+use crate::error::Result;
+use crate::mesh::{
+    boundary_loops, extract_surface, filter_patches, restrict_surface, AssemblyScope, EdgeLoop,
+    ExclusionZones, FilteredPatch, Mesh, PatchFilter, RegionOfInterest, SurfaceBounds, SurfaceMesh,
+};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Settings of an automatic contact detection run
+#[derive(Debug, Clone)]
+pub struct AutoContactOptions {
+    /// Detection criteria
+    pub criteria: ContactCriteria,
+
+    /// Minimum number of face pairs for two surfaces to count as in contact
+    /// (see also [`ContactCriteria::min_paired_area`] and
+    /// [`ContactCriteria::min_coverage_fraction`])
+    pub min_pairs: usize,
+
+    /// Patches excluded from detection
+    pub patch_filter: PatchFilter,
+
+    /// Faces removed from the surfaces before detection
+    pub exclusion_zones: ExclusionZones,
+
+    /// Drop pairs whose connecting segment passes through part A's own material
+    pub occlusion_check: bool,
+
+    /// Rank surface pairs with a coarse pass over every Nth face first and
+    /// skip those without a hit
+    pub coarse_stride: Option<usize>,
+
+    /// Detect on surfaces decimated to about this many faces each
+    pub preview: Option<usize>,
+
+    /// Region of interest; faces outside it are skipped in pairing
+    pub region: Option<RegionOfInterest>,
+
+    /// Drop faces outside the region from the surfaces themselves
+    pub clip_to_region: bool,
+
+    /// Detect against a signed distance field of each surface B with this
+    /// voxel spacing instead of searching its faces; each field is built once
+    /// and reused for every surface tested against it (not combined with a
+    /// region of interest that only restricts pairing)
+    pub sdf_spacing: Option<f64>,
+
+    /// Keep the bounding volumes and the broad-phase outcome of every surface
+    /// pair in [`AutoContactPlan::broad_phase`]
+    pub record_broad_phase: bool,
+
+    /// Limits on the work spent on a single surface pair
+    pub budget: PairBudget,
+
+    /// Also pair shafts with the coaxial holes of other parts whose
+    /// diametral clearance is at most this fraction of the hole diameter,
+    /// whatever the gap tolerance (see [`find_fastener_fits`])
+    pub fastener_clearance: Option<f64>,
+
+    /// Only detect contacts within one assembly of blocks or between two
+    /// (see [`AssemblyScope`])
+    pub scope: Option<AssemblyScope>,
+}
+
+/// Limits on the work spent on a single surface pair, so that one huge or
+/// pathological pair cannot stall a run over many pairs
+#[derive(Debug, Clone, Default)]
+pub struct PairBudget {
+    /// Give up on a pair whose face search takes longer than this (not
+    /// applied to signed distance field lookups, which take constant time
+    /// per face)
+    pub time_limit: Option<Duration>,
+
+    /// Skip pairs whose two surfaces have more faces than this in total
+    pub max_faces: Option<usize>,
+
+    /// Test skipped pairs again without limits once all other pairs are done
+    pub revisit_skipped: bool,
+}
+
+/// Limit of a [`PairBudget`] that a surface pair exceeded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetLimit {
+    /// Detection did not finish within the time limit
+    Time,
+
+    /// The surfaces have more faces than the face limit
+    Faces,
+}
+
+impl std::fmt::Display for BudgetLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BudgetLimit::Time => write!(f, "time limit"),
+            BudgetLimit::Faces => write!(f, "face limit"),
+        }
+    }
+}
+
+/// Surface pair skipped because it exceeded the budget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetSkip {
+    /// Names of the surfaces
+    pub part_a: String,
+    pub part_b: String,
+
+    /// Indices of the surfaces in [`AutoContactReport::surfaces`]
+    pub surface_a: usize,
+    pub surface_b: usize,
+
+    /// Limit the pair exceeded
+    pub limit: BudgetLimit,
+
+    /// Whether the pair was tested again without limits after the others
+    /// (see [`PairBudget::revisit_skipped`])
+    pub revisited: bool,
+}
+
+impl Default for AutoContactOptions {
+    fn default() -> Self {
+        Self {
+            criteria: ContactCriteria::default(),
+            min_pairs: 1,
+            patch_filter: PatchFilter::default(),
+            exclusion_zones: ExclusionZones::default(),
+            occlusion_check: true,
+            coarse_stride: None,
+            preview: None,
+            region: None,
+            clip_to_region: false,
+            sdf_spacing: None,
+            record_broad_phase: false,
+            budget: PairBudget::default(),
+            fastener_clearance: None,
+            scope: None,
+        }
+    }
+}
+
+/// Surface pairs planned for detection, after the broad phase
+#[derive(Debug, Clone)]
+pub struct AutoContactPlan {
+    /// Surfaces taking part in detection (decimated in preview runs)
+    pub surfaces: Vec<SurfaceMesh>,
+
+    /// Patches excluded by the patch filter
+    pub filtered_patches: Vec<FilteredPatch>,
+
+    /// Criteria used for detection (the search radius is widened in preview
+    /// runs); a relative gap tolerance is resolved per pair (see
+    /// [`AutoContactPlan::pair_criteria`])
+    pub criteria: ContactCriteria,
+
+    /// Sizes of the parts by block, if the gap tolerance is relative
+    pub part_sizes: HashMap<String, f64>,
+
+    /// Largest decimated face size of a preview run, widening the search
+    /// radius of each pair
+    pub preview_face_size: Option<f64>,
+
+    /// Number of surface pairs, n choose 2 (only those in scope, see
+    /// [`AutoContactOptions::scope`])
+    pub total_tests: usize,
+
+    /// Surface pairs (i, j), i < j, to test, in test order
+    pub candidates: Vec<(usize, usize)>,
+
+    /// Coarse estimates of the candidates, in the same order, if a coarse
+    /// pass ran (candidates are then ranked by estimated pairs)
+    pub coarse_estimates: Option<Vec<CoarseEstimate>>,
+
+    /// Surface pairs skipped because their bounding volumes are too far apart
+    pub skipped_far_apart: usize,
+
+    /// Surface pairs skipped because the coarse pass found no hit
+    pub skipped_coarse: usize,
+
+    /// Bounding volumes and the outcome of every surface pair, if recorded
+    /// (see [`AutoContactOptions::record_broad_phase`])
+    pub broad_phase: Option<BroadPhaseGraph>,
+
+    /// Shafts in holes of other parts, tested with their own gap tolerance
+    /// after the candidates (see [`AutoContactOptions::fastener_clearance`])
+    pub fasteners: Vec<FastenerFit>,
+}
+
+/// Outcome of an automatic contact detection run
+#[derive(Debug, Clone)]
+pub struct AutoContactReport {
+    /// Surfaces detection ran on
+    pub surfaces: Vec<SurfaceMesh>,
+
+    /// Patches excluded by the patch filter
+    pub filtered_patches: Vec<FilteredPatch>,
+
+    /// Criteria used for detection
+    pub criteria: ContactCriteria,
+
+    /// Surface pairs in contact, in surface order and numbered in it
+    pub pairs: Vec<ContactInterface>,
+
+    /// Number of surface pairs, n choose 2 (only those in scope)
+    pub total_tests: usize,
+
+    /// Assemblies detection was restricted to, if any
+    pub scope: Option<AssemblyScope>,
+
+    /// Surface pairs skipped by the broad phase
+    pub skipped_far_apart: usize,
+
+    /// Surface pairs skipped by the coarse pass
+    pub skipped_coarse: usize,
+
+    /// Surface pairs skipped because they exceeded the per-pair budget, in
+    /// test order
+    pub skipped_budget: Vec<BudgetSkip>,
+
+    /// Surface pairs with face pairs that fell short of the minimum number
+    /// of pairs, paired area or coverage
+    pub below_acceptance: usize,
+
+    /// Candidate face pairs rejected because the faces don't face each other
+    pub rejected_facing: usize,
+
+    /// Face pairs rejected because they pass through a part's own material
+    pub rejected_occluded: usize,
+
+    /// Why candidates were rejected, over every tested surface pair (if
+    /// recorded, see [`ContactCriteria::record_rejections`])
+    pub rejections: Option<RejectionStats>,
+
+    /// Shafts in holes of other parts with the face pairs found between them
+    pub fasteners: Vec<FastenerFit>,
+}
+
+impl AutoContactReport {
+    /// Parts (element blocks) of `mesh` in the scope of the run
+    pub fn parts<'a>(&'a self, mesh: &'a Mesh) -> impl Iterator<Item = &'a String> + 'a {
+        mesh.element_blocks
+            .keys()
+            .filter(|block| self.scope.as_ref().is_none_or(|s| s.includes_block(block)))
+    }
+
+    /// Run-level totals over the parts (element blocks) of `mesh`
+    pub fn run_summary(&self, mesh: &Mesh) -> RunSummary {
+        let mut summary = RunSummary::new(self.parts(mesh));
+        for pair in &self.pairs {
+            summary.add_pair(
+                pair.id,
+                pair.surface(&self.surfaces, true),
+                pair.surface(&self.surfaces, false),
+                &pair.results,
+                &pair.metrics,
+            );
+        }
+        summary
+    }
+
+    /// Which parts (element blocks) of `mesh` touch which
+    pub fn interface_graph(&self, mesh: &Mesh) -> InterfaceGraph {
+        let mut graph = InterfaceGraph::new(self.parts(mesh));
+        for pair in &self.pairs {
+            graph.add_pair(
+                pair.surface(&self.surfaces, true),
+                pair.surface(&self.surfaces, false),
+                &pair.metrics,
+            );
+        }
+        graph
+    }
+
+    /// Pairs in contact whose blocks `allowed` does not list
+    pub fn unexpected_contacts<'a>(
+        &'a self,
+        allowed: &'a AllowedContacts,
+    ) -> impl Iterator<Item = &'a ContactInterface> + 'a {
+        self.pairs.iter().filter(|pair| {
+            let block_a = pair.surface(&self.surfaces, true).block_name();
+            let block_b = pair.surface(&self.surfaces, false).block_name();
+            !allowed.allows(block_a, block_b)
+        })
+    }
+
+    /// Largest gap or overlap over every face pair
+    pub fn max_distance(&self) -> f64 {
+        self.pairs
+            .iter()
+            .flat_map(|pair| &pair.results.pairs)
+            .fold(0.0_f64, |max, pair| max.max(pair.distance.abs()))
+    }
+
+    /// Face sizes compared across each interface, flagged above
+    /// `max_size_ratio`
+    pub fn refinement_report(&self, max_size_ratio: f64) -> RefinementReport {
+        let mut report = RefinementReport::new(max_size_ratio);
+        report.interfaces = self
+            .pairs
+            .iter()
+            .filter_map(|pair| {
+                MeshDensityAssessment::compute(
+                    &pair.results,
+                    pair.surface(&self.surfaces, true),
+                    pair.surface(&self.surfaces, false),
+                    max_size_ratio,
+                )
+            })
+            .collect();
+        report
+    }
+
+    /// Contact patches of each interface checked against the bolt pressure
+    /// `cone`
+    pub fn footprint_report(&self, cone: PressureCone) -> FootprintReport {
+        let mut report = FootprintReport::new(cone);
+        report.interfaces = self
+            .pairs
+            .iter()
+            .map(|pair| {
+                InterfaceFootprints::compute(
+                    &pair.results,
+                    pair.surface(&self.surfaces, true),
+                    pair.surface(&self.surfaces, false),
+                    &cone,
+                )
+            })
+            .collect();
+        report
+    }
+
+    /// Fasteners found with a diametral clearance of at most
+    /// `max_clearance_ratio` of the hole diameter
+    pub fn fastener_report(&self, max_clearance_ratio: f64) -> FastenerReport {
+        FastenerReport::new(max_clearance_ratio, self.fasteners.clone())
+    }
+
+    /// Thermal conductance of each interface
+    pub fn thermal_conductances(
+        &self,
+        config: &ThermalConfig,
+    ) -> Result<Vec<InterfaceConductance>> {
+        self.pairs
+            .iter()
+            .map(|pair| {
+                InterfaceConductance::compute(
+                    config,
+                    &config.correlation,
+                    pair.surface(&self.surfaces, true),
+                    pair.surface(&self.surfaces, false),
+                    &pair.metrics,
+                )
+            })
+            .collect()
+    }
+
+    /// Node conformity of each interface, in pair order
+    pub fn conformity(&self, tolerance: f64) -> Vec<InterfaceConformity> {
+        self.pairs
+            .iter()
+            .map(|pair| {
+                InterfaceConformity::compute(
+                    &pair.results,
+                    pair.surface(&self.surfaces, true),
+                    pair.surface(&self.surfaces, false),
+                    tolerance,
+                )
+            })
+            .collect()
+    }
+
+    /// Add the unmatched nodes of each non-conforming interface to `mesh` as
+    /// a node set named after the pair
+    ///
+    /// Returns the name of each node set added, in pair order; existing node
+    /// sets are kept and their pairs get `None`.
+    pub fn add_unmatched_node_sets(
+        &self,
+        conformity: &[InterfaceConformity],
+        mesh: &mut Mesh,
+    ) -> Vec<Option<String>> {
+        self.pairs
+            .iter()
+            .zip(conformity)
+            .map(|(pair, conformity)| {
+                if conformity.unmatched_nodes.is_empty() {
+                    return None;
+                }
+                match mesh
+                    .node_sets
+                    .entry(format!("auto_contact_p{}_unmatched", pair.id))
+                {
+                    Entry::Occupied(existing) => {
+                        tracing::warn!(
+                            "Node set '{}' already exists, not exporting unmatched nodes",
+                            existing.key()
+                        );
+                        None
+                    }
+                    Entry::Vacant(slot) => {
+                        let name = slot.key().clone();
+                        slot.insert(conformity.unmatched_nodes.clone());
+                        Some(name)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Outline loops of the contact zones of side A and side B of each
+    /// interface, in pair order
+    pub fn outlines(&self) -> Vec<[Vec<EdgeLoop>; 2]> {
+        self.pairs
+            .iter()
+            .map(|pair| {
+                [true, false].map(|is_a| {
+                    let surface = pair.surface(&self.surfaces, is_a);
+                    boundary_loops(&surface.subset(&pair.results.paired_faces(is_a)))
+                })
+            })
+            .collect()
+    }
+
+    /// Contact status of the nodes of `mesh`, numbered by pair
+    pub fn nodal_contact(&self, mesh: &Mesh) -> NodalContact {
+        NodalContact::from_pairs(
+            mesh.num_nodes(),
+            self.pairs.iter().map(|pair| {
+                (
+                    &pair.results,
+                    pair.surface(&self.surfaces, true),
+                    pair.surface(&self.surfaces, false),
+                )
+            }),
+        )
+    }
+
+    /// Pairs consolidated into physical interfaces where their contact
+    /// zones overlap
+    pub fn merged_interfaces(&self) -> Vec<MergedInterface> {
+        let pairs: Vec<_> = self
+            .pairs
+            .iter()
+            .map(|pair| DetectedPair {
+                surface_a: pair.side_a.surface,
+                surface_b: pair.side_b.surface,
+                results: &pair.results,
+            })
+            .collect();
+        // Relative gap tolerances were resolved per pair; the widest counts
+        let tolerance = match self.criteria.relative_max_gap {
+            Some(_) => self
+                .pairs
+                .iter()
+                .map(|pair| pair.results.criteria.max_gap_distance)
+                .fold(0.0, f64::max),
+            None => self.criteria.max_gap_distance,
+        };
+        merge_contact_pairs(&pairs, &self.surfaces, tolerance)
+    }
+}
+
+/// Detect all contacts between the surfaces of `mesh`
+pub fn detect_all(mesh: &Mesh, options: &AutoContactOptions) -> Result<AutoContactReport> {
+    plan_all(mesh, options)?.run(mesh, options, |_, _, _| {})
+}
+
+/// Extract the surfaces of `mesh` and find the surface pairs to test
+#[tracing::instrument(skip_all)]
+pub fn plan_all(mesh: &Mesh, options: &AutoContactOptions) -> Result<AutoContactPlan> {
+    let (mut surfaces, _) = options.exclusion_zones.apply(extract_surface(mesh)?);
+    if let (Some(region), true) = (&options.region, options.clip_to_region) {
+        surfaces = surfaces
+            .iter()
+            .map(|s| restrict_surface(s, region).0)
+            .filter(|s| !s.faces.is_empty())
+            .collect();
+    }
+    if let Some(scope) = &options.scope {
+        surfaces.retain(|s| scope.includes_block(s.block_name()));
+    }
+    let (mut surfaces, filtered_patches) = filter_patches(surfaces, &options.patch_filter);
+
+    // Cylinders are found on the full surfaces, which share node numbering
+    let fasteners = options
+        .fastener_clearance
+        .map_or_else(Vec::new, |ratio| find_fastener_fits(mesh, &surfaces, ratio));
+
+    // A relative gap tolerance is resolved per pair from the size of the
+    // whole parts, before they are clipped or decimated
+    let part_sizes = options
+        .criteria
+        .relative_max_gap
+        .map_or_else(HashMap::new, |relative| relative.measure.of_blocks(mesh));
+
+    // Preview runs detect on coarse copies of the surfaces
+    let mut criteria = options.criteria.clone();
+    let mut preview_face_size = None;
+    if let Some(target_faces) = options.preview {
+        surfaces = surfaces.iter().map(|s| s.decimate(target_faces)).collect();
+        let face_size = surfaces
+            .iter()
+            .flat_map(|s| s.face_areas.iter())
+            .fold(0.0_f64, |size, &area| size.max(area.sqrt()));
+        // Relative gap tolerances are widened once resolved for each pair
+        if criteria.relative_max_gap.is_none() {
+            widen_for_preview(&mut criteria, face_size);
+        }
+        preview_face_size = Some(face_size);
+    }
+    let criteria_of = |i: usize, j: usize| {
+        pair_criteria(
+            &criteria,
+            &part_sizes,
+            preview_face_size,
+            &surfaces[i],
+            &surfaces[j],
+        )
+    };
+
+    let num_surfaces = surfaces.len();
+    let mut total_tests = 0;
+
+    // Broad phase: surfaces whose bounding volumes are further apart than the
+    // search radius cannot have any face pairs
+    let bounds: Vec<_> = surfaces.iter().map(SurfaceBounds::of_surface).collect();
+    // With a relative gap tolerance every pair has its own search radius and
+    // the graph records the largest
+    let margin = match criteria.relative_max_gap {
+        Some(_) => 0.0,
+        None => criteria.search_radius(),
+    };
+    let mut broad_phase = options
+        .record_broad_phase
+        .then(|| BroadPhaseGraph::new(&surfaces, &bounds, margin));
+    let mut candidates: Vec<(usize, usize)> = Vec::new();
+    for i in 0..num_surfaces {
+        for j in (i + 1)..num_surfaces {
+            if let Some(scope) = &options.scope {
+                if !scope.allows(surfaces[i].block_name(), surfaces[j].block_name()) {
+                    continue;
+                }
+            }
+            total_tests += 1;
+            let radius = criteria_of(i, j).search_radius();
+            let status =
+                BroadPhaseStatus::of_bounds(bounds[i].as_ref(), bounds[j].as_ref(), radius);
+            if let Some(graph) = broad_phase.as_mut() {
+                graph.margin = graph.margin.max(radius);
+                graph.add_edge(i, j, status);
+            }
+            if status == BroadPhaseStatus::Candidate {
+                candidates.push((i, j));
+            }
+        }
+    }
+    let skipped_far_apart = total_tests - candidates.len();
+
+    // Coarse pass if requested: rank the remaining pairs by their estimated
+    // contact and drop those without any hit
+    let mut coarse_estimates = None;
+    let mut skipped_coarse = 0;
+    if let Some(stride) = options.coarse_stride {
+        let _span = tracing::info_span!("coarse_pass", candidates = candidates.len()).entered();
+        let mut ranked: Vec<((usize, usize), CoarseEstimate)> = candidates
+            .iter()
+            .map(|&(i, j)| {
+                let criteria = criteria_of(i, j);
+                let estimate =
+                    coarse_contact_estimate(&surfaces[i], &surfaces[j], &criteria, stride);
+                ((i, j), estimate)
+            })
+            .filter(|(_, estimate)| estimate.is_likely_contact())
+            .collect();
+        ranked.sort_by_key(|(_, estimate)| std::cmp::Reverse(estimate.estimated_pairs()));
+        skipped_coarse = candidates.len() - ranked.len();
+        for ((i, j), estimate) in &ranked {
+            tracing::info!(
+                "Likely contact: {} ↔ {} (~{} face pairs)",
+                surfaces[*i].part_name,
+                surfaces[*j].part_name,
+                estimate.estimated_pairs()
+            );
+        }
+        if let Some(graph) = broad_phase.as_mut() {
+            let hits: HashSet<(usize, usize)> = ranked.iter().map(|(pair, _)| *pair).collect();
+            for edge in &mut graph.edges {
+                if edge.status == BroadPhaseStatus::Candidate
+                    && !hits.contains(&(edge.surface_a, edge.surface_b))
+                {
+                    edge.status = BroadPhaseStatus::NoCoarseHit;
+                }
+            }
+        }
+        let (ranked_candidates, estimates) = ranked.into_iter().unzip();
+        candidates = ranked_candidates;
+        coarse_estimates = Some(estimates);
+    }
+
+    Ok(AutoContactPlan {
+        surfaces,
+        filtered_patches,
+        criteria,
+        part_sizes,
+        preview_face_size,
+        total_tests,
+        candidates,
+        coarse_estimates,
+        skipped_far_apart,
+        skipped_coarse,
+        broad_phase,
+        fasteners,
+    })
+}
+
+/// Centroids of coarse faces on either side of an interface can be a face
+/// apart, so preview runs search at least one decimated face size around them
+fn widen_for_preview(criteria: &mut ContactCriteria, face_size: f64) {
+    if criteria.max_gap_distance > 0.0 {
+        criteria.search_radius_multiplier = criteria
+            .search_radius_multiplier
+            .max(face_size / criteria.max_gap_distance);
+    }
+}
+
+/// `criteria` for two surfaces, with a relative gap tolerance resolved for
+/// their parts (and widened again in preview runs)
+fn pair_criteria<'c>(
+    criteria: &'c ContactCriteria,
+    part_sizes: &HashMap<String, f64>,
+    preview_face_size: Option<f64>,
+    surface_a: &SurfaceMesh,
+    surface_b: &SurfaceMesh,
+) -> Cow<'c, ContactCriteria> {
+    let mut resolved = criteria.for_parts(part_sizes, surface_a, surface_b);
+    if let (Cow::Owned(resolved), Some(face_size)) = (&mut resolved, preview_face_size) {
+        widen_for_preview(resolved, face_size);
+    }
+    resolved
+}
+
+impl AutoContactPlan {
+    /// Criteria for surfaces `i` and `j`, with a relative gap tolerance
+    /// resolved for their parts
+    pub fn pair_criteria(&self, i: usize, j: usize) -> Cow<'_, ContactCriteria> {
+        pair_criteria(
+            &self.criteria,
+            &self.part_sizes,
+            self.preview_face_size,
+            &self.surfaces[i],
+            &self.surfaces[j],
+        )
+    }
+
+    /// Detect contact on every candidate pair
+    ///
+    /// `on_pair` is called before each pair is tested with its position in
+    /// [`AutoContactPlan::candidates`] and its two surfaces, e.g. to drive a
+    /// progress bar; pairs revisited after exceeding the budget are passed
+    /// again once all others are done.
+    pub fn run(
+        self,
+        mesh: &Mesh,
+        options: &AutoContactOptions,
+        on_pair: impl FnMut(usize, &SurfaceMesh, &SurfaceMesh),
+    ) -> Result<AutoContactReport> {
+        self.run_with(mesh, options, on_pair, |_, _| Ok(()))
+    }
+
+    /// Like [`AutoContactPlan::run`], also calling `on_found` with each pair
+    /// in contact as soon as it is detected, together with all surfaces
+    ///
+    /// Pairs are found in test order, not in the surface order of the final
+    /// report, so they are not numbered yet (their `id` is 0). This lets callers write results progressively so that a long
+    /// run can be monitored and a crash keeps what was found; an error from
+    /// `on_found` aborts the run.
+    #[tracing::instrument(skip_all, fields(tests = self.candidates.len()))]
+    pub fn run_with(
+        self,
+        mesh: &Mesh,
+        options: &AutoContactOptions,
+        mut on_pair: impl FnMut(usize, &SurfaceMesh, &SurfaceMesh),
+        mut on_found: impl FnMut(&ContactInterface, &[SurfaceMesh]) -> Result<()>,
+    ) -> Result<AutoContactReport> {
+        let volume = options.occlusion_check.then(|| VolumeIndex::new(mesh));
+        let region = options.region.as_ref().filter(|_| !options.clip_to_region);
+
+        let mut pairs = Vec::new();
+        let mut rejected_facing = 0;
+        let mut rejected_occluded = 0;
+        let mut rejections = self
+            .criteria
+            .record_rejections
+            .then(RejectionStats::default);
+
+        // Signed distance fields of the B surfaces, dropped after their last test
+        let sdf_spacing = options.sdf_spacing.filter(|_| region.is_none());
+        let mut fields: HashMap<usize, SignedDistanceField> = HashMap::new();
+        let mut remaining_tests: HashMap<usize, usize> = HashMap::new();
+        for &(_, j) in &self.candidates {
+            *remaining_tests.entry(j).or_default() += 1;
+        }
+        // Pairs over budget are appended to the tests again, without limits,
+        // if they are to be revisited
+        let budget = &options.budget;
+        let mut tests: Vec<(usize, bool)> =
+            (0..self.candidates.len()).map(|idx| (idx, true)).collect();
+        let mut skipped_budget: Vec<BudgetSkip> = Vec::new();
+        let mut below_acceptance = 0;
+        let mut next = 0;
+        while let Some(&(idx, limited)) = tests.get(next) {
+            next += 1;
+            let (i, j) = self.candidates[idx];
+            let (surface_a, surface_b) = (&self.surfaces[i], &self.surfaces[j]);
+            on_pair(idx, surface_a, surface_b);
+            let criteria = self.pair_criteria(i, j);
+
+            let over_faces = budget
+                .max_faces
+                .is_some_and(|max| surface_a.num_faces() + surface_b.num_faces() > max);
+            let deadline = budget
+                .time_limit
+                .filter(|_| limited)
+                .map(|limit| Instant::now() + limit);
+
+            // Clipped surfaces already lie entirely inside the region
+            let outcome = match (region, sdf_spacing) {
+                _ if limited && over_faces => None,
+                (Some(region), _) => {
+                    region_pairs(surface_a, surface_b, &criteria, region, deadline)?
+                }
+                (None, Some(spacing)) => {
+                    let field = match fields.entry(j) {
+                        Entry::Occupied(entry) => entry.into_mut(),
+                        // Sized for B alone, the largest any pair with B resolves to
+                        Entry::Vacant(entry) => entry.insert(SignedDistanceField::for_criteria(
+                            surface_b,
+                            spacing,
+                            &pair_criteria(
+                                &self.criteria,
+                                &self.part_sizes,
+                                self.preview_face_size,
+                                surface_b,
+                                surface_b,
+                            ),
+                        )?),
+                    };
+                    Some(detect_contact_pairs_sdf(surface_a, surface_b, field, &criteria)?)
+                }
+                (None, None) => search_pairs(
+                    surface_a,
+                    surface_b,
+                    &criteria,
+                    criteria.algorithm.algorithm(),
+                    deadline,
+                )?,
+            };
+            if let Some(remaining) = remaining_tests.get_mut(&j) {
+                *remaining = remaining.saturating_sub(1);
+                if *remaining == 0 {
+                    fields.remove(&j);
+                }
+            }
+            let Some(mut results) = outcome else {
+                let limit = if limited && over_faces {
+                    BudgetLimit::Faces
+                } else {
+                    BudgetLimit::Time
+                };
+                tracing::warn!(
+                    "Skipped {} ↔ {} (budget: {})",
+                    surface_a.part_name,
+                    surface_b.part_name,
+                    limit
+                );
+                skipped_budget.push(BudgetSkip {
+                    part_a: surface_a.part_name.clone(),
+                    part_b: surface_b.part_name.clone(),
+                    surface_a: i,
+                    surface_b: j,
+                    limit,
+                    revisited: budget.revisit_skipped,
+                });
+                if budget.revisit_skipped {
+                    // Revisits build their field again if it was dropped
+                    *remaining_tests.entry(j).or_default() += 1;
+                    tests.push((idx, false));
+                }
+                continue;
+            };
+
+            // Drop pairs formed through surface A's own material (thin walls)
+            if let Some(volume) = &volume {
+                remove_occluded_pairs(&mut results, surface_a, volume);
+            }
+            rejected_facing += results.rejected_facing;
+            rejected_occluded += results.rejected_occluded;
+            if let (Some(total), Some(pair)) = (rejections.as_mut(), &results.rejections) {
+                total.merge(pair);
+            }
+
+            // Check if this pair has significant contact
+            if results.num_pairs() < options.min_pairs {
+                if results.num_pairs() > 0 {
+                    below_acceptance += 1;
+                }
+                continue;
+            }
+            let metrics = PairMetrics::compute(&results, surface_a, surface_b);
+            if !metrics.meets_acceptance(&criteria) {
+                tracing::info!(
+                    "Too little contact: {} ↔ {} ({} pairs, paired area {:.6})",
+                    surface_a.part_name,
+                    surface_b.part_name,
+                    results.num_pairs(),
+                    metrics.surface_a.paired_area
+                );
+                below_acceptance += 1;
+                continue;
+            }
+            tracing::info!(
+                "Found contact: {} ↔ {} ({} pairs)",
+                surface_a.part_name,
+                surface_b.part_name,
+                results.num_pairs()
+            );
+            let pair = ContactInterface::new(
+                0,
+                InterfaceSide::master(surface_a.part_name.clone(), i),
+                InterfaceSide::slave(surface_b.part_name.clone(), j),
+                results,
+                metrics,
+            );
+            on_found(&pair, &self.surfaces)?;
+            pairs.push(pair);
+        }
+
+        // Fasteners: holes and shafts not paired yet are tested with the gap
+        // tolerance widened to their clearance
+        let mut fasteners = self.fasteners;
+        for fit in &mut fasteners {
+            for (i, j) in fit.surface_pairs() {
+                if let Some(pair) = pairs
+                    .iter()
+                    .find(|pair| (pair.side_a.surface, pair.side_b.surface) == (i, j))
+                {
+                    fit.face_pairs += pair.results.num_pairs();
+                    continue;
+                }
+                let (surface_a, surface_b) = (&self.surfaces[i], &self.surfaces[j]);
+                let criteria = fit.criteria(&pair_criteria(
+                    &self.criteria,
+                    &self.part_sizes,
+                    self.preview_face_size,
+                    surface_a,
+                    surface_b,
+                ));
+                let status = BroadPhaseStatus::of_bounds(
+                    SurfaceBounds::of_surface(surface_a).as_ref(),
+                    SurfaceBounds::of_surface(surface_b).as_ref(),
+                    criteria.search_radius(),
+                );
+                if status != BroadPhaseStatus::Candidate {
+                    continue;
+                }
+
+                let outcome = match region {
+                    Some(region) => region_pairs(surface_a, surface_b, &criteria, region, None)?,
+                    None => Some(detect_contact_pairs(surface_a, surface_b, &criteria)?),
+                };
+                let Some(mut results) = outcome else {
+                    continue;
+                };
+                if let Some(volume) = &volume {
+                    remove_occluded_pairs(&mut results, surface_a, volume);
+                }
+                if results.num_pairs() < options.min_pairs {
+                    continue;
+                }
+                let metrics = PairMetrics::compute(&results, surface_a, surface_b);
+                if !metrics.meets_acceptance(&criteria) {
+                    continue;
+                }
+                tracing::info!(
+                    "Found fastener contact: {} ↔ {} ({} pairs)",
+                    surface_a.part_name,
+                    surface_b.part_name,
+                    results.num_pairs()
+                );
+                fit.face_pairs += results.num_pairs();
+                let pair = ContactInterface::new(
+                    0,
+                    InterfaceSide::master(surface_a.part_name.clone(), i),
+                    InterfaceSide::slave(surface_b.part_name.clone(), j),
+                    results,
+                    metrics,
+                );
+                on_found(&pair, &self.surfaces)?;
+                pairs.push(pair);
+            }
+        }
+
+        // Report in surface order whatever order the pairs were tested in,
+        // numbered in that order
+        pairs.sort_by_key(|pair| (pair.side_a.surface, pair.side_b.surface));
+        for (idx, pair) in pairs.iter_mut().enumerate() {
+            pair.id = idx + 1;
+        }
+
+        tracing::info!(
+            "Skipped {} of {} surface pairs whose bounding volumes are too far apart",
+            self.skipped_far_apart,
+            self.total_tests
+        );
+        if self.coarse_estimates.is_some() {
+            tracing::info!(
+                "Skipped {} of {} surface pairs without a coarse hit",
+                self.skipped_coarse,
+                self.total_tests - self.skipped_far_apart
+            );
+        }
+
+        Ok(AutoContactReport {
+            surfaces: self.surfaces,
+            filtered_patches: self.filtered_patches,
+            criteria: self.criteria,
+            pairs,
+            total_tests: self.total_tests,
+            scope: options.scope.clone(),
+            skipped_far_apart: self.skipped_far_apart,
+            skipped_coarse: self.skipped_coarse,
+            skipped_budget,
+            below_acceptance,
+            rejected_facing,
+            rejected_occluded,
+            rejections,
+            fasteners,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::relative::GapTolerance;
+    use crate::mesh::{HexElement, Point};
+
+    /// Two unit cubes stacked along z with a small gap
+    fn stacked_cubes(gap: f64) -> Mesh {
+        let mut mesh = Mesh::new();
+        for (block, z0) in [("Lower", 0.0), ("Upper", 1.0 + gap)] {
+            let base = mesh.nodes.len();
+            for z in [z0, z0 + 1.0] {
+                mesh.nodes.push(Point::new(0.0, 0.0, z));
+                mesh.nodes.push(Point::new(1.0, 0.0, z));
+                mesh.nodes.push(Point::new(1.0, 1.0, z));
+                mesh.nodes.push(Point::new(0.0, 1.0, z));
+            }
+            let element = mesh.elements.len();
+            mesh.elements
+                .push(HexElement::new(std::array::from_fn(|k| base + k)));
+            mesh.element_blocks.insert(block.to_string(), vec![element]);
+        }
+        mesh
+    }
+
+    #[test]
+    fn test_detect_all() {
+        let mesh = stacked_cubes(0.001);
+        let options = AutoContactOptions {
+            criteria: ContactCriteria::new(0.01, 0.001, 180.0),
+            ..Default::default()
+        };
+
+        let plan = plan_all(&mesh, &options).unwrap();
+        assert_eq!(plan.surfaces.len(), 12);
+        assert_eq!(plan.total_tests, 66);
+        assert!(plan.candidates.len() < plan.total_tests);
+
+        let report = detect_all(&mesh, &options).unwrap();
+        assert_eq!(report.pairs.len(), 1);
+        let pair = &report.pairs[0];
+        assert_eq!(pair.results.num_pairs(), 1);
+        assert!((pair.results.pairs[0].distance.abs() - 0.001).abs() < 1e-9);
+        assert_ne!(
+            pair.surface(&report.surfaces, true).block_name(),
+            pair.surface(&report.surfaces, false).block_name()
+        );
+
+        let summary = report.run_summary(&mesh);
+        assert!(summary.is_connected());
+        assert_eq!(summary.num_contact_pairs, 1);
+    }
+
+    #[test]
+    fn test_floating_parts() {
+        // A third cube well away from the stack
+        let mut mesh = stacked_cubes(0.001);
+        let base = mesh.nodes.len();
+        for z in [0.0, 1.0] {
+            mesh.nodes.push(Point::new(5.0, 0.0, z));
+            mesh.nodes.push(Point::new(6.0, 0.0, z));
+            mesh.nodes.push(Point::new(6.0, 1.0, z));
+            mesh.nodes.push(Point::new(5.0, 1.0, z));
+        }
+        let element = mesh.elements.len();
+        mesh.elements
+            .push(HexElement::new(std::array::from_fn(|k| base + k)));
+        mesh.element_blocks.insert("Bracket".to_string(), vec![element]);
+
+        let options = AutoContactOptions {
+            criteria: ContactCriteria::new(0.01, 0.001, 180.0),
+            ..Default::default()
+        };
+        let report = detect_all(&mesh, &options).unwrap();
+        let check = report.interface_graph(&mesh).check_connectivity();
+        assert!(!check.is_connected());
+        assert_eq!(check.floating_parts, ["Bracket"]);
+        assert_eq!(check.components, [vec!["Lower", "Upper"], vec!["Bracket"]]);
+    }
+
+    #[test]
+    fn test_report_post_processing() {
+        let mut mesh = stacked_cubes(0.001);
+        let options = AutoContactOptions {
+            criteria: ContactCriteria::new(0.01, 0.001, 180.0),
+            ..Default::default()
+        };
+        let report = detect_all(&mesh, &options).unwrap();
+        assert!((report.max_distance() - 0.001).abs() < 1e-9);
+
+        let allowed = AllowedContacts::parse("Lower:Upper").unwrap();
+        assert_eq!(report.unexpected_contacts(&allowed).count(), 0);
+        let allowed = AllowedContacts::parse("Lower:Bracket").unwrap();
+        assert_eq!(report.unexpected_contacts(&allowed).count(), 1);
+
+        assert_eq!(report.refinement_report(2.0).interfaces.len(), 1);
+        assert_eq!(report.merged_interfaces().len(), 1);
+        assert_eq!(report.nodal_contact(&mesh).num_in_contact(), 8);
+        for loops in &report.outlines()[0] {
+            assert_eq!(loops.len(), 1);
+            assert!((loops[0].length - 4.0).abs() < 1e-12);
+        }
+
+        // The faces are 0.001 apart: no node matches within 1e-6, each
+        // pair's unmatched nodes are exported once
+        let conformity = report.conformity(1e-6);
+        assert!(!conformity[0].is_conforming());
+        let added = report.add_unmatched_node_sets(&conformity, &mut mesh);
+        assert_eq!(added, [Some("auto_contact_p1_unmatched".to_string())]);
+        assert_eq!(mesh.node_sets["auto_contact_p1_unmatched"].len(), 8);
+        assert_eq!(report.add_unmatched_node_sets(&conformity, &mut mesh), [None]);
+        assert!(report.conformity(0.01)[0].is_conforming());
+    }
+
+    #[test]
+    fn test_relative_gap() {
+        // Unit cubes: a bounding box diagonal of √3 against a gap of 0.001
+        let mesh = stacked_cubes(0.001);
+        let detect = |gap: &str| {
+            let mut options = AutoContactOptions {
+                criteria: ContactCriteria::new(0.01, 0.001, 180.0),
+                ..Default::default()
+            };
+            gap.parse::<GapTolerance>().unwrap().apply(&mut options.criteria);
+            detect_all(&mesh, &options).unwrap()
+        };
+
+        let report = detect("0.1%");
+        assert_eq!(report.pairs.len(), 1);
+        let resolved = &report.pairs[0].results.criteria;
+        assert!((resolved.max_gap_distance - 0.001 * 3f64.sqrt()).abs() < 1e-12);
+        assert!(detect("0.05%").pairs.is_empty());
+    }
+
+    #[test]
+    fn test_assembly_scope() {
+        let mesh = stacked_cubes(0.001);
+        let blocks = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+        let mut options = AutoContactOptions {
+            criteria: ContactCriteria::new(0.01, 0.001, 180.0),
+            scope: Some(AssemblyScope::Within(blocks(&["Lower"]))),
+            ..Default::default()
+        };
+        let report = detect_all(&mesh, &options).unwrap();
+        assert_eq!(report.surfaces.len(), 6);
+        assert_eq!(report.total_tests, 15);
+        assert!(report.pairs.is_empty());
+        assert_eq!(report.interface_graph(&mesh).parts, ["Lower"]);
+
+        options.scope = Some(AssemblyScope::Between(
+            blocks(&["Lower"]),
+            blocks(&["Upper"]),
+        ));
+        let report = detect_all(&mesh, &options).unwrap();
+        assert_eq!(report.total_tests, 36);
+        assert_eq!(report.pairs.len(), 1);
+    }
+
+    #[test]
+    fn test_pair_acceptance() {
+        let mesh = stacked_cubes(0.001);
+        let mut options = AutoContactOptions {
+            criteria: ContactCriteria::new(0.01, 0.001, 180.0),
+            ..Default::default()
+        };
+
+        // The touching faces are unit squares, each a whole patch
+        for (area, coverage, accepted) in [
+            (Some(0.5), None, true),
+            (Some(2.0), None, false),
+            (None, Some(1.0), true),
+            (Some(0.5), Some(1.5), false),
+        ] {
+            options.criteria.min_paired_area = area;
+            options.criteria.min_coverage_fraction = coverage;
+            let report = detect_all(&mesh, &options).unwrap();
+            assert_eq!(report.pairs.len(), accepted as usize);
+            assert_eq!(report.below_acceptance, !accepted as usize);
+        }
+    }
+
+    #[test]
+    fn test_pair_budget() {
+        let mesh = stacked_cubes(0.001);
+        let mut options = AutoContactOptions {
+            criteria: ContactCriteria::new(0.01, 0.001, 180.0),
+            budget: PairBudget {
+                max_faces: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let report = detect_all(&mesh, &options).unwrap();
+        assert!(report.pairs.is_empty());
+        let candidates = plan_all(&mesh, &options).unwrap().candidates.len();
+        assert_eq!(report.skipped_budget.len(), candidates);
+        assert!(report
+            .skipped_budget
+            .iter()
+            .all(|skip| skip.limit == BudgetLimit::Faces && !skip.revisited));
+
+        options.budget.revisit_skipped = true;
+        let mut tested = 0;
+        let report = plan_all(&mesh, &options)
+            .unwrap()
+            .run(&mesh, &options, |_, _, _| tested += 1)
+            .unwrap();
+        assert_eq!(report.pairs.len(), 1);
+        assert_eq!(tested, 2 * candidates);
+        assert!(report.skipped_budget.iter().all(|skip| skip.revisited));
+
+        // A zero time limit has passed before the first face is searched
+        options.budget = PairBudget {
+            time_limit: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        let report = detect_all(&mesh, &options).unwrap();
+        assert!(report.pairs.is_empty());
+        assert_eq!(report.skipped_budget.len(), candidates);
+        assert!(report
+            .skipped_budget
+            .iter()
+            .all(|skip| skip.limit == BudgetLimit::Time));
+    }
+
+    #[test]
+    fn test_broad_phase_record() {
+        let mesh = stacked_cubes(0.001);
+        let mut options = AutoContactOptions {
+            criteria: ContactCriteria::new(0.01, 0.001, 180.0),
+            coarse_stride: Some(1),
+            ..Default::default()
+        };
+        assert!(plan_all(&mesh, &options).unwrap().broad_phase.is_none());
+
+        options.record_broad_phase = true;
+        let plan = plan_all(&mesh, &options).unwrap();
+        let graph = plan.broad_phase.as_ref().unwrap();
+        assert_eq!(graph.surfaces.len(), 12);
+        assert_eq!(graph.edges.len(), plan.total_tests);
+        assert_eq!(
+            graph.count(BroadPhaseStatus::Candidate),
+            plan.candidates.len()
+        );
+        assert_eq!(
+            graph.count(BroadPhaseStatus::SpheresApart) + graph.count(BroadPhaseStatus::BoxesApart),
+            plan.skipped_far_apart
+        );
+        assert_eq!(
+            graph.count(BroadPhaseStatus::NoCoarseHit),
+            plan.skipped_coarse
+        );
+        assert!(plan.skipped_coarse > 0);
+
+        let graph = graph.clone();
+        let report = plan.run(&mesh, &options, |_, _, _| {}).unwrap();
+        let pair = &report.pairs[0];
+        assert_eq!(
+            graph.status_of(&pair.side_b.part, &pair.side_a.part),
+            Some(BroadPhaseStatus::Candidate)
+        );
+
+        let path = std::env::temp_dir().join("test_broad_phase.vtp");
+        crate::io::write_broad_phase_to_vtp(&graph, &path, None).unwrap();
+        assert!(path.exists());
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_pairs_reported_as_found() {
+        let mesh = stacked_cubes(0.001);
+        let options = AutoContactOptions {
+            criteria: ContactCriteria::new(0.01, 0.001, 180.0),
+            ..Default::default()
+        };
+
+        let tested = std::cell::Cell::new(0);
+        let mut found = Vec::new();
+        let report = plan_all(&mesh, &options)
+            .unwrap()
+            .run_with(
+                &mesh,
+                &options,
+                |_, _, _| tested.set(tested.get() + 1),
+                |pair, surfaces| {
+                    // Reported right after the pair's own test
+                    assert!(tested.get() > 0);
+                    found.push((
+                        pair.surface(surfaces, true).part_name.clone(),
+                        pair.results.num_pairs(),
+                    ));
+                    Ok(())
+                },
+            )
+            .unwrap();
+        assert_eq!(found, vec![(report.pairs[0].side_a.part.clone(), 1)]);
+        assert_eq!(report.pairs[0].id, 1);
+
+        // An error from the callback aborts the run
+        let aborted = plan_all(&mesh, &options).unwrap().run_with(
+            &mesh,
+            &options,
+            |_, _, _| {},
+            |_, _| {
+                Err(crate::error::ContactDetectorError::ConfigError(
+                    "disk full".to_string(),
+                ))
+            },
+        );
+        assert!(aborted.is_err());
+    }
+}
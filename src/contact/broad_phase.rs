@@ -0,0 +1,147 @@
+//! Record of the auto-contact broad phase
+//!
+//! Before any face is compared, auto-contact drops surface pairs whose
+//! bounding spheres or oriented boxes are further apart than the search radius
+//! and, with a coarse pass, pairs without a coarse hit. When a pair that
+//! should be in contact never shows up, the reason is usually one of these
+//! filters; a [`BroadPhaseGraph`] keeps the bounding volumes of every surface
+//! and the outcome for every surface pair so it can be inspected (as JSON, or
+//! as boxes and lines in ParaView via [`crate::io::write_broad_phase_to_vtp`]).
+
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::{SurfaceBounds, SurfaceMesh};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Outcome of the broad phase for one surface pair
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BroadPhaseStatus {
+    /// Passed on to detection
+    Candidate,
+
+    /// Bounding spheres further apart than the search radius
+    SpheresApart,
+
+    /// Spheres close enough, oriented boxes further apart than the search
+    /// radius
+    BoxesApart,
+
+    /// Bounding volumes close enough, but the coarse pass found no hit
+    NoCoarseHit,
+}
+
+impl BroadPhaseStatus {
+    /// Classify a surface pair by its bounds; surfaces without bounds (no
+    /// faces) are always candidates
+    pub fn of_bounds(a: Option<&SurfaceBounds>, b: Option<&SurfaceBounds>, margin: f64) -> Self {
+        match (a, b) {
+            (Some(a), Some(b)) if !a.sphere.within(&b.sphere, margin) => Self::SpheresApart,
+            (Some(a), Some(b)) if !a.obb.within(&b.obb, margin) => Self::BoxesApart,
+            _ => Self::Candidate,
+        }
+    }
+
+    /// Integer code written to cell data (0 = candidate, 1 = spheres apart,
+    /// 2 = boxes apart, 3 = no coarse hit)
+    pub fn code(self) -> i32 {
+        match self {
+            Self::Candidate => 0,
+            Self::SpheresApart => 1,
+            Self::BoxesApart => 2,
+            Self::NoCoarseHit => 3,
+        }
+    }
+}
+
+/// Bounding volumes of one surface
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BroadPhaseSurface {
+    pub name: String,
+    pub num_faces: usize,
+
+    /// `None` for surfaces without faces
+    pub bounds: Option<SurfaceBounds>,
+}
+
+/// One surface pair and what the broad phase did with it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BroadPhaseEdge {
+    /// Indices into [`BroadPhaseGraph::surfaces`], `surface_a < surface_b`
+    pub surface_a: usize,
+    pub surface_b: usize,
+    pub part_a: String,
+    pub part_b: String,
+    pub status: BroadPhaseStatus,
+}
+
+/// Bounding volumes of all surfaces and the outcome for every surface pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BroadPhaseGraph {
+    /// Distance within which bounding volumes count as close (the search
+    /// radius of the criteria)
+    pub margin: f64,
+
+    pub surfaces: Vec<BroadPhaseSurface>,
+
+    /// Every surface pair (n choose 2), in test order before any ranking
+    pub edges: Vec<BroadPhaseEdge>,
+}
+
+impl BroadPhaseGraph {
+    /// Graph of the given surfaces and their bounds, without edges yet
+    pub fn new(surfaces: &[SurfaceMesh], bounds: &[Option<SurfaceBounds>], margin: f64) -> Self {
+        Self {
+            margin,
+            surfaces: surfaces
+                .iter()
+                .zip(bounds)
+                .map(|(surface, bounds)| BroadPhaseSurface {
+                    name: surface.part_name.clone(),
+                    num_faces: surface.num_faces(),
+                    bounds: *bounds,
+                })
+                .collect(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Record the outcome for surfaces `a` and `b`
+    pub fn add_edge(&mut self, a: usize, b: usize, status: BroadPhaseStatus) {
+        self.edges.push(BroadPhaseEdge {
+            surface_a: a,
+            surface_b: b,
+            part_a: self.surfaces[a].name.clone(),
+            part_b: self.surfaces[b].name.clone(),
+            status,
+        });
+    }
+
+    /// Number of surface pairs with the given outcome
+    pub fn count(&self, status: BroadPhaseStatus) -> usize {
+        self.edges.iter().filter(|e| e.status == status).count()
+    }
+
+    /// Outcome for the pair of the named parts (in either order)
+    pub fn status_of(&self, part_a: &str, part_b: &str) -> Option<BroadPhaseStatus> {
+        self.edges
+            .iter()
+            .find(|e| {
+                (e.part_a == part_a && e.part_b == part_b)
+                    || (e.part_a == part_b && e.part_b == part_a)
+            })
+            .map(|e| e.status)
+    }
+
+    /// Export the graph to a JSON file
+    pub fn export<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            ContactDetectorError::ConfigError(format!(
+                "Failed to serialize broad phase graph: {}",
+                e
+            ))
+        })?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
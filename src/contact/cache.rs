@@ -0,0 +1,368 @@
+//! Caching of detection results between runs
+//!
+//! Results are stored as JSON files named after a content key: a hash of both
+//! surfaces' geometry, the detection criteria, any extra context that affects
+//! detection (such as a region of interest) and the crate version. A repeated
+//! run with unchanged inputs loads the stored results instead of detecting
+//! again; any change to the inputs changes the key, so stale entries are never
+//! read, only left behind.
+//!
+//! Surface extraction does not guarantee a face order, so keys ignore it and
+//! stored face indices refer to the faces sorted by their coordinates; they
+//! are mapped back to the current surfaces on load.
+
+use crate::contact::types::{ContactCriteria, ContactPair, ContactResults, RejectionStats};
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::types::{Point, SurfaceMesh};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Name of the cache directory created inside an output directory
+pub const CACHE_DIR_NAME: &str = ".contact_cache";
+
+/// Stored form of [`ContactPair`]
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedPair {
+    a: usize,
+    b: usize,
+    distance: f64,
+    normal_angle: f64,
+    contact_point: [f64; 3],
+}
+
+/// Stored form of [`ContactResults`]
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResults {
+    key: String,
+    surface_a_name: String,
+    surface_b_name: String,
+    pairs: Vec<CachedPair>,
+    unpaired_a: Vec<usize>,
+    unpaired_b: Vec<usize>,
+    criteria: ContactCriteria,
+    rejected_facing: usize,
+    rejected_occluded: usize,
+    #[serde(default)]
+    rejections: Option<RejectionStats>,
+}
+
+/// Corner coordinates of a face, as bits so they can be ordered and hashed
+fn face_coords(surface: &SurfaceMesh, face_idx: usize) -> Vec<[u64; 3]> {
+    surface.faces[face_idx]
+        .corners()
+        .iter()
+        .map(|&n| {
+            let p = &surface.nodes[n];
+            [p.x.to_bits(), p.y.to_bits(), p.z.to_bits()]
+        })
+        .collect()
+}
+
+/// Face indices sorted by corner coordinates
+fn canonical_order(surface: &SurfaceMesh) -> Vec<usize> {
+    let coords: Vec<_> = (0..surface.faces.len())
+        .map(|i| face_coords(surface, i))
+        .collect();
+    let mut order: Vec<usize> = (0..surface.faces.len()).collect();
+    order.sort_by(|&i, &j| coords[i].cmp(&coords[j]));
+    order
+}
+
+/// Hash the geometry of a surface, independent of face order: its name, the
+/// corner coordinates of every face and the face tags of every layer
+pub fn hash_surface<H: Hasher>(surface: &SurfaceMesh, state: &mut H) {
+    surface.part_name.hash(state);
+    surface.faces.len().hash(state);
+    let order = canonical_order(surface);
+    for &face_idx in &order {
+        face_coords(surface, face_idx).hash(state);
+    }
+    let tags = &surface.face_tags;
+    for name in tags.names() {
+        name.hash(state);
+        for &face_idx in &order {
+            tags.get(name, face_idx).hash(state);
+        }
+    }
+}
+
+/// Hash the fields of the detection criteria that change the pairs found
+///
+/// The spatial index and the acceptance thresholds (`min_paired_area`,
+/// `min_coverage_fraction`) are left out: they do not change the results.
+pub fn hash_criteria<H: Hasher>(criteria: &ContactCriteria, state: &mut H) {
+    [
+        criteria.max_gap_distance,
+        criteria.max_penetration,
+        criteria.max_normal_angle,
+        criteria.search_radius_multiplier,
+        criteria.min_facing,
+    ]
+    .map(f64::to_bits)
+    .hash(state);
+    criteria
+        .relative_max_gap
+        .map(|relative| (relative.percent.to_bits(), relative.measure))
+        .hash(state);
+    criteria.algorithm.hash(state);
+    criteria.record_rejections.hash(state);
+    criteria.excluded_tags.hash(state);
+}
+
+/// [`Hasher`] over SHA-256, so keys stay the same across Rust releases
+///
+/// `DefaultHasher` makes no such promise, which would orphan every cache
+/// entry after a toolchain update.
+#[derive(Default)]
+struct StableHasher(Sha256);
+
+impl StableHasher {
+    /// Hex digest of everything written so far
+    fn hex(self) -> String {
+        self.0
+            .finalize()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.0.clone().finalize();
+        u64::from_le_bytes(digest[..8].try_into().unwrap())
+    }
+}
+
+/// Directory of cached detection results
+#[derive(Debug, Clone)]
+pub struct ResultCache {
+    dir: PathBuf,
+}
+
+impl ResultCache {
+    /// Use (and create if needed) the cache directory inside `output_dir`
+    pub fn in_output_dir<P: AsRef<Path>>(output_dir: P) -> Result<Self> {
+        let dir = output_dir.as_ref().join(CACHE_DIR_NAME);
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Content key for detecting `surface_a` against `surface_b`
+    ///
+    /// `context` covers anything else that changes the results, e.g. the
+    /// faces kept by a region of interest.
+    pub fn key(
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+        criteria: &ContactCriteria,
+        context: impl Hash,
+    ) -> String {
+        let mut state = StableHasher::default();
+        env!("CARGO_PKG_VERSION").hash(&mut state);
+        hash_surface(surface_a, &mut state);
+        hash_surface(surface_b, &mut state);
+        hash_criteria(criteria, &mut state);
+        context.hash(&mut state);
+        state.hex()
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    /// Cached results for `key` (computed from these surfaces), if present
+    /// and readable
+    pub fn load(
+        &self,
+        key: &str,
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+    ) -> Option<ContactResults> {
+        let text = std::fs::read_to_string(self.entry_path(key)).ok()?;
+        let cached: CachedResults = match serde_json::from_str(&text) {
+            Ok(cached) => cached,
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable cache entry {}: {}", key, e);
+                return None;
+            }
+        };
+        if cached.key != key {
+            return None;
+        }
+
+        let order_a = canonical_order(surface_a);
+        let order_b = canonical_order(surface_b);
+        let valid = cached
+            .pairs
+            .iter()
+            .all(|p| p.a < order_a.len() && p.b < order_b.len())
+            && cached.unpaired_a.iter().all(|&i| i < order_a.len())
+            && cached.unpaired_b.iter().all(|&i| i < order_b.len());
+        if !valid {
+            return None;
+        }
+
+        let mut results = ContactResults::new(
+            cached.surface_a_name,
+            cached.surface_b_name,
+            cached.criteria,
+        );
+        results.pairs = cached
+            .pairs
+            .into_iter()
+            .map(|p| ContactPair {
+                surface_a_face_id: order_a[p.a],
+                surface_b_face_id: order_b[p.b],
+                distance: p.distance,
+                normal_angle: p.normal_angle,
+                contact_point: Point::from(p.contact_point),
+            })
+            .collect();
+        results.unpaired_a = cached.unpaired_a.iter().map(|&i| order_a[i]).collect();
+        results.unpaired_b = cached.unpaired_b.iter().map(|&i| order_b[i]).collect();
+        results.sort_by_face();
+        results.rejected_facing = cached.rejected_facing;
+        results.rejected_occluded = cached.rejected_occluded;
+        results.rejections = cached.rejections;
+
+        tracing::debug!("Loaded cached results {}", key);
+        Some(results)
+    }
+
+    /// Store results for these surfaces under `key`
+    pub fn store(
+        &self,
+        key: &str,
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+        results: &ContactResults,
+    ) -> Result<()> {
+        let rank = |surface: &SurfaceMesh| {
+            let mut rank = vec![0; surface.faces.len()];
+            for (position, face_idx) in canonical_order(surface).into_iter().enumerate() {
+                rank[face_idx] = position;
+            }
+            rank
+        };
+        let (rank_a, rank_b) = (rank(surface_a), rank(surface_b));
+
+        let cached = CachedResults {
+            key: key.to_string(),
+            surface_a_name: results.surface_a_name.clone(),
+            surface_b_name: results.surface_b_name.clone(),
+            pairs: results
+                .pairs
+                .iter()
+                .map(|p| CachedPair {
+                    a: rank_a[p.surface_a_face_id],
+                    b: rank_b[p.surface_b_face_id],
+                    distance: p.distance,
+                    normal_angle: p.normal_angle,
+                    contact_point: p.contact_point.coords.into(),
+                })
+                .collect(),
+            unpaired_a: results.unpaired_a.iter().map(|&i| rank_a[i]).collect(),
+            unpaired_b: results.unpaired_b.iter().map(|&i| rank_b[i]).collect(),
+            criteria: results.criteria.clone(),
+            rejected_facing: results.rejected_facing,
+            rejected_occluded: results.rejected_occluded,
+            rejections: results.rejections.clone(),
+        };
+
+        let text = serde_json::to_string(&cached).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to serialize cache entry: {}", e))
+        })?;
+        std::fs::write(self.entry_path(key), text)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::fixtures::strip;
+
+    fn reversed(surface: &SurfaceMesh) -> SurfaceMesh {
+        let order: Vec<usize> = (0..surface.num_faces()).rev().collect();
+        surface.subset(&order)
+    }
+
+    #[test]
+    fn test_key_changes_with_inputs() {
+        let a = strip("A", 0.0, 1);
+        let b = strip("B", 0.001, 3);
+        let criteria = ContactCriteria::default();
+
+        let key = ResultCache::key(&a, &b, &criteria, ());
+        assert_eq!(key, ResultCache::key(&a, &b, &criteria, ()));
+        // Face order does not matter
+        assert_eq!(key, ResultCache::key(&a, &reversed(&b), &criteria, ()));
+
+        let moved = strip("B", 0.002, 3);
+        assert_ne!(key, ResultCache::key(&a, &moved, &criteria, ()));
+
+        let mut looser = criteria.clone();
+        looser.max_gap_distance *= 2.0;
+        assert_ne!(key, ResultCache::key(&a, &b, &looser, ()));
+
+        assert_ne!(key, ResultCache::key(&a, &b, &criteria, "roi"));
+
+        // Tags and tag exclusions change the pairs found
+        let mut excluding = criteria.clone();
+        excluding.excluded_tags.push("coating".parse().unwrap());
+        assert_ne!(key, ResultCache::key(&a, &b, &excluding, ()));
+
+        let mut tagged = b.clone();
+        tagged.face_tags.set("coating", 1, "coated");
+        let tagged_key = ResultCache::key(&a, &tagged, &criteria, ());
+        assert_ne!(key, tagged_key);
+        tagged.face_tags.set("coating", 1, "bare");
+        assert_ne!(tagged_key, ResultCache::key(&a, &tagged, &criteria, ()));
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let dir = std::env::temp_dir().join("cd_result_cache_test");
+        let cache = ResultCache::in_output_dir(&dir).unwrap();
+        let a = strip("A", 0.0, 1);
+        let b = strip("B", 0.001, 3);
+
+        let mut results =
+            ContactResults::new("A".to_string(), "B".to_string(), ContactCriteria::default());
+        results.pairs.push(ContactPair {
+            surface_a_face_id: 0,
+            surface_b_face_id: 0,
+            distance: 0.001,
+            normal_angle: 3.5,
+            contact_point: Point::new(0.5, 0.5, 0.001),
+        });
+        results.unpaired_b = vec![1, 2];
+        results.rejected_facing = 4;
+
+        cache.store("abc", &a, &b, &results).unwrap();
+
+        let loaded = cache.load("abc", &a, &b).unwrap();
+        assert_eq!(loaded.surface_b_name, "B");
+        assert_eq!(loaded.num_pairs(), 1);
+        assert_eq!(loaded.pairs[0].surface_b_face_id, 0);
+        assert_eq!(loaded.pairs[0].contact_point, Point::new(0.5, 0.5, 0.001));
+        assert_eq!(loaded.unpaired_b, vec![1, 2]);
+        assert_eq!(loaded.rejected_facing, 4);
+
+        // Same surface extracted with a different face order
+        let loaded = cache.load("abc", &a, &reversed(&b)).unwrap();
+        assert_eq!(loaded.pairs[0].surface_b_face_id, 2);
+        assert_eq!(loaded.unpaired_b, vec![0, 1]);
+
+        assert!(cache.load("missing", &a, &b).is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
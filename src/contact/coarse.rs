@@ -0,0 +1,144 @@
+//! Coarse contact estimate between two surfaces
+//!
+//! On large assemblies most surface pairs that pass the bounding volume test
+//! still have no contact. A coarse pass checks only every `stride`-th face of
+//! surface A, and only against the gap and angle criteria (no facing or
+//! occlusion tests), which is enough to rank pairs by how likely they are to
+//! touch and to skip the ones without any hit before full-resolution
+//! detection. Contacts spanning fewer than about `stride` faces of A can be
+//! missed.
+
+use crate::contact::spatial_index::CentroidIndex;
+use crate::contact::types::ContactCriteria;
+use crate::mesh::geometry::{angle_between_vectors, signed_distance_to_plane};
+use crate::mesh::types::SurfaceMesh;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Result of a coarse pass between two surfaces
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoarseEstimate {
+    /// Every `stride`-th face of surface A was sampled
+    pub stride: usize,
+
+    /// Number of sampled faces
+    pub sampled_faces: usize,
+
+    /// Sampled faces with a face of B in gap and angle range
+    pub hits: usize,
+}
+
+impl CoarseEstimate {
+    /// Estimated number of face pairs at full resolution
+    pub fn estimated_pairs(&self) -> usize {
+        self.hits * self.stride
+    }
+
+    /// Whether any sampled face found a partner
+    pub fn is_likely_contact(&self) -> bool {
+        self.hits > 0
+    }
+}
+
+/// Estimate the contact between two surfaces from every `stride`-th face of
+/// surface A (a stride of 0 is treated as 1)
+#[tracing::instrument(level = "debug", skip_all, fields(surface_a = %surface_a.part_name, surface_b = %surface_b.part_name))]
+pub fn coarse_contact_estimate(
+    surface_a: &SurfaceMesh,
+    surface_b: &SurfaceMesh,
+    criteria: &ContactCriteria,
+    stride: usize,
+) -> CoarseEstimate {
+    // Threshold for parallelization (below this, overhead isn't worth it)
+    const PARALLEL_THRESHOLD: usize = 1000;
+
+    let stride = stride.max(1);
+    let criteria = &*criteria.for_surfaces(surface_a, surface_b);
+    let index_b = CentroidIndex::build(surface_b, criteria.spatial_index, criteria.search_radius());
+    let samples: Vec<usize> = (0..surface_a.faces.len()).step_by(stride).collect();
+
+    let has_partner = |&face_a_idx: &usize| {
+        let centroid_a = &surface_a.face_centroids[face_a_idx];
+        let normal_a = &surface_a.face_normals[face_a_idx];
+        index_b
+            .within(centroid_a, criteria.search_radius())
+            .into_iter()
+            .any(|face_b_idx| {
+                let distance = signed_distance_to_plane(
+                    &surface_b.face_centroids[face_b_idx],
+                    centroid_a,
+                    normal_a,
+                );
+                criteria.is_in_range(distance)
+                    && criteria.is_angle_valid(angle_between_vectors(
+                        normal_a,
+                        &surface_b.face_normals[face_b_idx],
+                    ))
+            })
+    };
+
+    #[cfg(feature = "parallel")]
+    let hits = if samples.len() >= PARALLEL_THRESHOLD {
+        samples.par_iter().filter(|f| has_partner(f)).count()
+    } else {
+        samples.iter().filter(|f| has_partner(f)).count()
+    };
+
+    #[cfg(not(feature = "parallel"))]
+    let hits = samples.iter().filter(|f| has_partner(f)).count();
+
+    CoarseEstimate {
+        stride,
+        sampled_faces: samples.len(),
+        hits,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::detection::detect_contact_pairs;
+    use crate::mesh::types::{Point, QuadFace, Vec3};
+
+    /// `n` x `n` grid of unit quads at height `z` with normals along `normal_z`
+    fn make_plate(n: usize, z: f64, normal_z: f64) -> SurfaceMesh {
+        let mut surface = SurfaceMesh::new(format!("plate_{}", z));
+        for j in 0..=n {
+            for i in 0..=n {
+                surface.nodes.push(Point::new(i as f64, j as f64, z));
+            }
+        }
+        for j in 0..n {
+            for i in 0..n {
+                let n0 = j * (n + 1) + i;
+                surface
+                    .faces
+                    .push(QuadFace::new([n0, n0 + 1, n0 + n + 2, n0 + n + 1]));
+                surface
+                    .face_centroids
+                    .push(Point::new(i as f64 + 0.5, j as f64 + 0.5, z));
+                surface.face_normals.push(Vec3::new(0.0, 0.0, normal_z));
+                surface.face_areas.push(1.0);
+            }
+        }
+        surface
+    }
+
+    #[test]
+    fn test_coarse_estimate() {
+        let lower = make_plate(10, 0.0, 1.0);
+        let upper = make_plate(10, 0.001, -1.0);
+        let far = make_plate(10, 0.5, -1.0);
+        // Opposite normals are 180 degrees apart
+        let criteria = ContactCriteria::new(0.005, 0.001, 180.0);
+
+        let estimate = coarse_contact_estimate(&lower, &upper, &criteria, 7);
+        assert_eq!(estimate.sampled_faces, 15);
+        assert_eq!(estimate.hits, 15);
+        let full = detect_contact_pairs(&lower, &upper, &criteria).unwrap();
+        assert!(estimate.estimated_pairs().abs_diff(full.num_pairs()) <= 7);
+
+        assert!(!coarse_contact_estimate(&lower, &far, &criteria, 7).is_likely_contact());
+    }
+}
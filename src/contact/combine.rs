@@ -0,0 +1,243 @@
+//! Combining detection results computed in parts
+//!
+//! Several workflows detect one surface pair piece by piece: shards of a
+//! large surface are searched separately and merged, patches of a block are
+//! rolled up into the block's surface, and a symmetric check runs A → B and
+//! B → A and reconciles the two passes. [`ContactResults::merge`] does the
+//! combining for all of them. Face indices of each part refer to the
+//! surfaces it was detected on, so every face is traced back to the face of
+//! the combined surfaces it came from by its corner positions, which stay the
+//! same whatever subset, patch or node numbering the part used.
+
+use crate::contact::types::{ContactPair, ContactResults};
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::geometry::closest_point_on_face;
+use crate::mesh::types::SurfaceMesh;
+use std::collections::{HashMap, HashSet};
+
+/// Detection results of part of a surface pair, with the surfaces their face
+/// indices refer to
+#[derive(Debug, Clone, Copy)]
+pub struct PartialResults<'a> {
+    /// Detection results of the part
+    pub results: &'a ContactResults,
+
+    /// Surface the A faces of `results` index into
+    pub surface_a: &'a SurfaceMesh,
+
+    /// Surface the B faces of `results` index into
+    pub surface_b: &'a SurfaceMesh,
+
+    /// Whether the part was detected from the combined surface B to surface
+    /// A, e.g. the second pass of a symmetric check
+    pub swapped: bool,
+}
+
+impl<'a> PartialResults<'a> {
+    /// Results detected in the same direction as the combined pair
+    pub fn new(
+        results: &'a ContactResults,
+        surface_a: &'a SurfaceMesh,
+        surface_b: &'a SurfaceMesh,
+    ) -> Self {
+        Self {
+            results,
+            surface_a,
+            surface_b,
+            swapped: false,
+        }
+    }
+}
+
+/// Corner positions of a face, as bits and in sorted order so the key does
+/// not depend on the first corner or the orientation
+type FaceKey = Vec<[u64; 3]>;
+
+fn face_key(surface: &SurfaceMesh, face: usize) -> FaceKey {
+    let mut corners: FaceKey = surface.faces[face]
+        .corners()
+        .iter()
+        .map(|&n| {
+            let p = &surface.nodes[n];
+            [p.x.to_bits(), p.y.to_bits(), p.z.to_bits()]
+        })
+        .collect();
+    corners.sort_unstable();
+    corners
+}
+
+/// Face indices of a combined surface by face key
+struct FaceLookup<'s> {
+    surface: &'s SurfaceMesh,
+    faces: HashMap<FaceKey, usize>,
+}
+
+impl<'s> FaceLookup<'s> {
+    fn new(surface: &'s SurfaceMesh) -> Self {
+        let faces = (0..surface.num_faces())
+            .map(|face| (face_key(surface, face), face))
+            .collect();
+        Self { surface, faces }
+    }
+
+    /// Index in the combined surface of face `face` of `part`
+    fn find(&self, part: &SurfaceMesh, face: usize) -> Result<usize> {
+        let key = (face < part.num_faces()).then(|| face_key(part, face));
+        key.and_then(|key| self.faces.get(&key).copied())
+            .ok_or_else(|| {
+                ContactDetectorError::InvalidMeshTopology(format!(
+                    "Face {} of '{}' is not a face of '{}'",
+                    face, part.part_name, self.surface.part_name
+                ))
+            })
+    }
+}
+
+impl ContactResults {
+    /// Combine the results of parts of the pair `surface_a` ↔ `surface_b`
+    /// into results indexing the faces of these two surfaces
+    ///
+    /// A face pair found by several parts is kept once, as the first part
+    /// lists it. Pairs of swapped parts get their contact point projected
+    /// onto surface B. The criteria are those of the first part; rejection
+    /// counts are added up. Faces of the combined surfaces without pairs are
+    /// unpaired, including faces no part covered. Every face of a part must
+    /// be a face of the combined surface on its side.
+    pub fn merge(
+        parts: &[PartialResults<'_>],
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+    ) -> Result<ContactResults> {
+        let criteria = parts
+            .first()
+            .map(|part| part.results.criteria.clone())
+            .unwrap_or_default();
+        let mut merged = ContactResults::new(
+            surface_a.part_name.clone(),
+            surface_b.part_name.clone(),
+            criteria,
+        );
+        let lookup_a = FaceLookup::new(surface_a);
+        let lookup_b = FaceLookup::new(surface_b);
+
+        let mut seen = HashSet::new();
+        for part in parts {
+            let (lookup_a, lookup_b) = if part.swapped {
+                (&lookup_b, &lookup_a)
+            } else {
+                (&lookup_a, &lookup_b)
+            };
+            for pair in &part.results.pairs {
+                let face_a = lookup_a.find(part.surface_a, pair.surface_a_face_id)?;
+                let face_b = lookup_b.find(part.surface_b, pair.surface_b_face_id)?;
+
+                let (face_a, face_b, contact_point) = if part.swapped {
+                    let point = closest_point_on_face(
+                        &pair.contact_point,
+                        &surface_b.faces[face_a],
+                        &surface_b.nodes,
+                    );
+                    (face_b, face_a, point)
+                } else {
+                    (face_a, face_b, pair.contact_point)
+                };
+                if seen.insert((face_a, face_b)) {
+                    merged.pairs.push(ContactPair {
+                        surface_a_face_id: face_a,
+                        surface_b_face_id: face_b,
+                        distance: pair.distance,
+                        normal_angle: pair.normal_angle,
+                        contact_point,
+                    });
+                }
+            }
+
+            merged.rejected_facing += part.results.rejected_facing;
+            merged.rejected_occluded += part.results.rejected_occluded;
+            if let Some(rejections) = &part.results.rejections {
+                merged
+                    .rejections
+                    .get_or_insert_with(Default::default)
+                    .merge(rejections);
+            }
+        }
+
+        for (surface, is_a) in [(surface_a, true), (surface_b, false)] {
+            let paired: HashSet<usize> = merged.paired_faces(is_a).into_iter().collect();
+            let unpaired = (0..surface.num_faces()).filter(|face| !paired.contains(face));
+            if is_a {
+                merged.unpaired_a.extend(unpaired);
+            } else {
+                merged.unpaired_b.extend(unpaired);
+            }
+        }
+        merged.sort_by_face();
+        Ok(merged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::ContactCriteria;
+    use crate::mesh::fixtures::strip;
+    use crate::mesh::types::Point;
+
+    fn pair(face_a: usize, face_b: usize, contact_point: Point) -> ContactPair {
+        ContactPair {
+            surface_a_face_id: face_a,
+            surface_b_face_id: face_b,
+            distance: 0.01,
+            normal_angle: 179.0,
+            contact_point,
+        }
+    }
+
+    #[test]
+    fn test_merge_shards_and_passes() {
+        let lower = strip("Lower", 0.0, 4);
+        let upper = strip("Upper", 0.01, 4);
+
+        // Two shards of the lower surface, each re-indexed from 0
+        let lower_left = lower.subset(&[0, 1]);
+        let lower_right = lower.subset(&[2, 3]);
+        let mut left = ContactResults::new("L".into(), "Upper".into(), ContactCriteria::default());
+        left.pairs.push(pair(1, 1, Point::new(1.5, 0.5, 0.01)));
+        left.rejected_facing = 2;
+        let mut right = ContactResults::new("R".into(), "Upper".into(), ContactCriteria::default());
+        right.pairs.push(pair(0, 2, Point::new(2.5, 0.5, 0.01)));
+        right.rejected_facing = 1;
+
+        // The reverse pass finds one pair again and one more
+        let mut reverse =
+            ContactResults::new("Upper".into(), "Lower".into(), ContactCriteria::default());
+        reverse.pairs.push(pair(2, 2, Point::new(2.5, 0.5, 0.0)));
+        reverse.pairs.push(pair(3, 3, Point::new(3.5, 0.5, 0.0)));
+
+        let parts = [
+            PartialResults::new(&left, &lower_left, &upper),
+            PartialResults::new(&right, &lower_right, &upper),
+            PartialResults {
+                swapped: true,
+                ..PartialResults::new(&reverse, &upper, &lower)
+            },
+        ];
+        let merged = ContactResults::merge(&parts, &lower, &upper).unwrap();
+        assert_eq!(merged.surface_a_name, "Lower");
+        let faces: Vec<_> = merged
+            .pairs
+            .iter()
+            .map(|p| (p.surface_a_face_id, p.surface_b_face_id))
+            .collect();
+        assert_eq!(faces, [(1, 1), (2, 2), (3, 3)]);
+        assert!((merged.pairs[2].contact_point - Point::new(3.5, 0.5, 0.01)).norm() < 1e-12);
+        assert_eq!(merged.unpaired_a, [0]);
+        assert_eq!(merged.unpaired_b, [0]);
+        assert_eq!(merged.rejected_facing, 3);
+
+        // A part on a surface that is not part of the pair
+        let other = strip("Other", 5.0, 4);
+        let stray = [PartialResults::new(&left, &other, &upper)];
+        assert!(ContactResults::merge(&stray, &lower, &upper).is_err());
+    }
+}
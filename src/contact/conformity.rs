@@ -0,0 +1,177 @@
+//! Node conformity of contact interfaces
+//!
+//! A tied interface can be modelled by merging or equivalencing nodes only
+//! if the meshes on both sides conform, i.e. every node of one side has a
+//! node of the other side at the same location. Otherwise a tie constraint or
+//! true contact is needed. This module measures how many interface nodes
+//! have a coincident partner and lists those that do not.
+
+use crate::contact::types::ContactResults;
+use crate::mesh::types::SurfaceMesh;
+use kiddo::ImmutableKdTree;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::num::NonZero;
+
+/// Node conformity of one detected interface
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceConformity {
+    /// Distance within which two nodes count as coincident
+    pub tolerance: f64,
+
+    /// Number of nodes of the paired faces on surface A
+    pub num_nodes_a: usize,
+
+    /// Number of nodes of the paired faces on surface B
+    pub num_nodes_b: usize,
+
+    /// Nodes on A with a coincident node on B
+    pub matched_a: usize,
+
+    /// Nodes on B with a coincident node on A
+    pub matched_b: usize,
+
+    /// Mesh node indices (on either side) without a coincident partner, sorted
+    pub unmatched_nodes: Vec<usize>,
+}
+
+impl InterfaceConformity {
+    /// Match the nodes of the paired faces of both surfaces
+    ///
+    /// Node indices refer to the surfaces' node lists, i.e. the mesh nodes for
+    /// extracted surfaces.
+    pub fn compute(
+        results: &ContactResults,
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+        tolerance: f64,
+    ) -> Self {
+        let nodes_a = paired_nodes(results, surface_a, true);
+        let nodes_b = paired_nodes(results, surface_b, false);
+
+        let unmatched_a = unmatched(&nodes_a, surface_a, &nodes_b, surface_b, tolerance);
+        let unmatched_b = unmatched(&nodes_b, surface_b, &nodes_a, surface_a, tolerance);
+
+        let unmatched_nodes: BTreeSet<usize> =
+            unmatched_a.iter().chain(&unmatched_b).copied().collect();
+
+        Self {
+            tolerance,
+            num_nodes_a: nodes_a.len(),
+            num_nodes_b: nodes_b.len(),
+            matched_a: nodes_a.len() - unmatched_a.len(),
+            matched_b: nodes_b.len() - unmatched_b.len(),
+            unmatched_nodes: unmatched_nodes.into_iter().collect(),
+        }
+    }
+
+    /// Percentage of interface nodes (both sides) with a coincident partner
+    pub fn matched_percent(&self) -> f64 {
+        let total = self.num_nodes_a + self.num_nodes_b;
+        if total == 0 {
+            return 0.0;
+        }
+        100.0 * (self.matched_a + self.matched_b) as f64 / total as f64
+    }
+
+    /// Whether every interface node has a coincident partner
+    pub fn is_conforming(&self) -> bool {
+        self.num_nodes_a + self.num_nodes_b > 0 && self.unmatched_nodes.is_empty()
+    }
+}
+
+/// Sorted node indices of the paired faces of one surface
+fn paired_nodes(results: &ContactResults, surface: &SurfaceMesh, is_surface_a: bool) -> Vec<usize> {
+    let nodes: BTreeSet<usize> = results
+        .paired_faces(is_surface_a)
+        .into_iter()
+        .flat_map(|face_idx| surface.faces[face_idx].corners().to_vec())
+        .collect();
+    nodes.into_iter().collect()
+}
+
+/// Nodes of `own` with no node of `other` within `tolerance`
+fn unmatched(
+    own: &[usize],
+    own_surface: &SurfaceMesh,
+    other: &[usize],
+    other_surface: &SurfaceMesh,
+    tolerance: f64,
+) -> Vec<usize> {
+    if other.is_empty() {
+        return own.to_vec();
+    }
+
+    let points: Vec<[f64; 3]> = other
+        .iter()
+        .map(|&n| {
+            let p = &other_surface.nodes[n];
+            [p.x, p.y, p.z]
+        })
+        .collect();
+    let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&points);
+
+    own.iter()
+        .copied()
+        .filter(|&n| {
+            let p = &own_surface.nodes[n];
+            let nearest =
+                tree.nearest_n::<kiddo::SquaredEuclidean>(&[p.x, p.y, p.z], NonZero::<usize>::MIN);
+            nearest[0].distance > tolerance * tolerance
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::{ContactCriteria, ContactPair};
+    use crate::mesh::fixtures::strip;
+
+    fn pair_all(a: &SurfaceMesh, b: &SurfaceMesh) -> ContactResults {
+        let mut results =
+            ContactResults::new("A".to_string(), "B".to_string(), ContactCriteria::default());
+        for face_a in 0..a.num_faces() {
+            let face_b = face_a * b.num_faces() / a.num_faces();
+            results.pairs.push(ContactPair {
+                surface_a_face_id: face_a,
+                surface_b_face_id: face_b,
+                distance: 0.0,
+                normal_angle: 0.0,
+                contact_point: a.face_centroids[face_a],
+            });
+        }
+        results
+    }
+
+    #[test]
+    fn test_conforming_interface() {
+        let a = strip("A", 0.0, 2);
+        let b = strip("B", 1e-7, 2);
+
+        let conformity = InterfaceConformity::compute(&pair_all(&a, &b), &a, &b, 1e-6);
+        assert_eq!(conformity.num_nodes_a, 6);
+        assert_eq!(conformity.matched_b, 6);
+        assert!(conformity.is_conforming());
+        assert_eq!(conformity.matched_percent(), 100.0);
+    }
+
+    #[test]
+    fn test_non_conforming_interface() {
+        // A has a node row at x = 1 that B (one face twice as long) lacks
+        let a = strip("A", 0.0, 2);
+        let mut b = strip("B", 0.0, 1);
+        for node in &mut b.nodes {
+            node.x *= 2.0;
+        }
+
+        let conformity = InterfaceConformity::compute(&pair_all(&a, &b), &a, &b, 1e-6);
+        assert_eq!(conformity.num_nodes_a, 6);
+        assert_eq!(conformity.num_nodes_b, 4);
+        assert_eq!(conformity.matched_a, 4);
+        assert_eq!(conformity.matched_b, 4);
+        assert_eq!(conformity.unmatched_nodes, vec![2, 3]);
+        assert!(!conformity.is_conforming());
+        assert!((conformity.matched_percent() - 80.0).abs() < 1e-12);
+    }
+}
@@ -1,28 +1,78 @@
 //! Contact pair detection algorithm
 
-use crate::contact::types::{ContactCriteria, ContactPair, ContactResults};
-use crate::error::Result;
-use crate::mesh::geometry::{
-    angle_between_vectors, project_point_to_plane, signed_distance_to_plane,
+use crate::contact::algorithm::{CandidateGenerator, ContactDetectionAlgorithm, FacePair};
+use crate::contact::types::{
+    ContactCriteria, ContactPair, ContactResults, RejectionReason, RejectionStats,
 };
+use crate::error::Result;
+use crate::mesh::geometry::{angle_between_vectors, closest_point_on_face};
+use crate::mesh::roi::{restrict_surface, RegionOfInterest};
 use crate::mesh::types::SurfaceMesh;
 use kiddo::ImmutableKdTree;
 use std::collections::HashSet;
+use std::time::Instant;
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-/// Detect contact pairs between two surfaces
+/// Detect contact pairs between two surfaces with the algorithm selected in
+/// the criteria
 pub fn detect_contact_pairs(
     surface_a: &SurfaceMesh,
     surface_b: &SurfaceMesh,
     criteria: &ContactCriteria,
 ) -> Result<ContactResults> {
-    log::info!(
-        "Detecting contact pairs between '{}' and '{}'",
+    detect_contact_pairs_with(surface_a, surface_b, criteria, criteria.algorithm.algorithm())
+}
+
+/// Detect contact pairs between two surfaces with the algorithm selected in
+/// the criteria, giving up once `deadline` has passed
+///
+/// `None` if detection did not finish in time, e.g. to skip surface pairs
+/// that would stall a run over many pairs.
+pub fn detect_contact_pairs_until(
+    surface_a: &SurfaceMesh,
+    surface_b: &SurfaceMesh,
+    criteria: &ContactCriteria,
+    deadline: Instant,
+) -> Result<Option<ContactResults>> {
+    search_pairs(
+        surface_a,
+        surface_b,
+        criteria,
+        criteria.algorithm.algorithm(),
+        Some(deadline),
+    )
+}
+
+/// Detect contact pairs between two surfaces, measuring candidate pairs with
+/// the given algorithm (`criteria.algorithm` is ignored)
+pub fn detect_contact_pairs_with(
+    surface_a: &SurfaceMesh,
+    surface_b: &SurfaceMesh,
+    criteria: &ContactCriteria,
+    algorithm: &dyn ContactDetectionAlgorithm,
+) -> Result<ContactResults> {
+    let results = search_pairs(surface_a, surface_b, criteria, algorithm, None)?;
+    Ok(results.expect("detection without a deadline runs to completion"))
+}
+
+/// Candidate search over the faces of A, `None` if `deadline` passes first
+#[tracing::instrument(skip_all, fields(surface_a = %surface_a.part_name, surface_b = %surface_b.part_name, algorithm = algorithm.name()))]
+pub(crate) fn search_pairs(
+    surface_a: &SurfaceMesh,
+    surface_b: &SurfaceMesh,
+    criteria: &ContactCriteria,
+    algorithm: &dyn ContactDetectionAlgorithm,
+    deadline: Option<Instant>,
+) -> Result<Option<ContactResults>> {
+    tracing::info!(
+        "Detecting contact pairs between '{}' and '{}' ({})",
         surface_a.part_name,
-        surface_b.part_name
+        surface_b.part_name,
+        algorithm.name()
     );
+    let criteria = &*criteria.for_surfaces(surface_a, surface_b);
 
     let mut results = ContactResults::new(
         surface_a.part_name.clone(),
@@ -31,50 +81,55 @@ pub fn detect_contact_pairs(
     );
 
     // Build spatial index for surface B
-    log::info!("Building spatial index for surface B...");
-    let tree_b = build_face_kdtree(surface_b);
+    tracing::info!("Building spatial index ({:?}) for surface B...", criteria.spatial_index);
+    let index_b = CandidateGenerator::new(surface_b, criteria.spatial_index, criteria.search_radius());
 
     // For each face on surface A, find closest face on surface B (parallelized for large datasets)
-    log::info!("Searching for contact pairs...");
+    tracing::info!("Searching for contact pairs...");
 
     // Threshold for parallelization (below this, overhead isn't worth it)
     const PARALLEL_THRESHOLD: usize = 1000;
 
+    // Each face gives up once the deadline has passed, so that collecting
+    // into an Option stops at the first face that does
+    let match_face = |face_a_idx: usize| {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return None;
+        }
+        Some(find_best_match(face_a_idx, surface_a, surface_b, &index_b, criteria, algorithm))
+    };
+
     #[cfg(feature = "parallel")]
-    let face_results: Vec<_> = if surface_a.faces.len() >= PARALLEL_THRESHOLD {
-        surface_a
-            .faces
-            .par_iter()
-            .enumerate()
-            .map(|(face_a_idx, _face_a)| {
-                find_best_match(face_a_idx, surface_a, surface_b, &tree_b, criteria)
-            })
+    let face_results: Option<Vec<_>> = if surface_a.faces.len() >= PARALLEL_THRESHOLD {
+        (0..surface_a.faces.len())
+            .into_par_iter()
+            .map(match_face)
             .collect()
     } else {
-        surface_a
-            .faces
-            .iter()
-            .enumerate()
-            .map(|(face_a_idx, _face_a)| {
-                find_best_match(face_a_idx, surface_a, surface_b, &tree_b, criteria)
-            })
-            .collect()
+        (0..surface_a.faces.len()).map(match_face).collect()
     };
 
     #[cfg(not(feature = "parallel"))]
-    let face_results: Vec<_> = surface_a
-        .faces
-        .iter()
-        .enumerate()
-        .map(|(face_a_idx, _face_a)| {
-            find_best_match(face_a_idx, surface_a, surface_b, &tree_b, criteria)
-        })
-        .collect();
+    let face_results: Option<Vec<_>> = (0..surface_a.faces.len()).map(match_face).collect();
+
+    let Some(face_results) = face_results else {
+        tracing::info!(
+            "Gave up on '{}' ↔ '{}': deadline passed",
+            surface_a.part_name,
+            surface_b.part_name
+        );
+        return Ok(None);
+    };
 
     // Collect results
     let mut paired_b = HashSet::new();
-    for (face_a_idx, result) in face_results.into_iter().enumerate() {
-        match result {
+    let mut rejections = criteria.record_rejections.then(RejectionStats::default);
+    for (face_a_idx, face_match) in face_results.into_iter().enumerate() {
+        results.rejected_facing += face_match.rejected_facing;
+        if let (Some(total), Some(face)) = (rejections.as_mut(), &face_match.rejections) {
+            total.merge(face);
+        }
+        match face_match.pair {
             Some(pair) => {
                 paired_b.insert(pair.surface_b_face_id);
                 results.pairs.push(pair);
@@ -104,15 +159,97 @@ pub fn detect_contact_pairs(
         .collect();
 
     results.unpaired_b = unpaired_b;
+    results.rejections = rejections;
+    results.sort_by_face();
 
-    log::info!(
+    tracing::info!(
         "Found {} contact pairs, {} unpaired on A, {} unpaired on B",
         results.num_pairs(),
         results.unpaired_a.len(),
         results.unpaired_b.len()
     );
+    if results.rejected_facing > 0 {
+        tracing::debug!(
+            "Rejected {} candidate pairs that do not face each other",
+            results.rejected_facing
+        );
+    }
 
-    Ok(results)
+    Ok(Some(results))
+}
+
+/// Detect contact pairs considering only faces inside a region of interest
+///
+/// Face indices in the returned results refer to the original (unrestricted)
+/// surfaces. Faces outside the region are skipped in pairing and reported as
+/// unpaired.
+pub fn detect_contact_pairs_in_region(
+    surface_a: &SurfaceMesh,
+    surface_b: &SurfaceMesh,
+    criteria: &ContactCriteria,
+    roi: &RegionOfInterest,
+) -> Result<ContactResults> {
+    let results = region_pairs(surface_a, surface_b, criteria, roi, None)?;
+    Ok(results.expect("detection without a deadline runs to completion"))
+}
+
+/// Detection restricted to a region, `None` if `deadline` passes first
+#[tracing::instrument(skip_all, fields(surface_a = %surface_a.part_name, surface_b = %surface_b.part_name))]
+pub(crate) fn region_pairs(
+    surface_a: &SurfaceMesh,
+    surface_b: &SurfaceMesh,
+    criteria: &ContactCriteria,
+    roi: &RegionOfInterest,
+    deadline: Option<Instant>,
+) -> Result<Option<ContactResults>> {
+    // Parts are sized as a whole, not by what lies inside the region
+    let criteria = &*criteria.for_surfaces(surface_a, surface_b);
+    let (roi_a, map_a) = restrict_surface(surface_a, roi);
+    let (roi_b, map_b) = restrict_surface(surface_b, roi);
+
+    tracing::debug!(
+        "ROI keeps {}/{} faces on A and {}/{} faces on B",
+        map_a.len(),
+        surface_a.faces.len(),
+        map_b.len(),
+        surface_b.faces.len()
+    );
+
+    let algorithm = criteria.algorithm.algorithm();
+    let Some(mut results) = search_pairs(&roi_a, &roi_b, criteria, algorithm, deadline)? else {
+        return Ok(None);
+    };
+
+    for pair in &mut results.pairs {
+        pair.surface_a_face_id = map_a[pair.surface_a_face_id];
+        pair.surface_b_face_id = map_b[pair.surface_b_face_id];
+    }
+
+    let paired_a: HashSet<usize> = results.pairs.iter().map(|p| p.surface_a_face_id).collect();
+    let paired_b: HashSet<usize> = results.pairs.iter().map(|p| p.surface_b_face_id).collect();
+    results.unpaired_a = (0..surface_a.faces.len())
+        .filter(|i| !paired_a.contains(i))
+        .collect();
+    results.unpaired_b = (0..surface_b.faces.len())
+        .filter(|i| !paired_b.contains(i))
+        .collect();
+    results.sort_by_face();
+
+    Ok(Some(results))
+}
+
+/// Relative length below which the offset between two faces has no direction
+const FACING_TOLERANCE: f64 = 1e-9;
+
+/// Best match of one face of surface A
+struct FaceMatch {
+    pair: Option<ContactPair>,
+
+    /// Candidates rejected by the facing test
+    rejected_facing: usize,
+
+    /// Why candidates (and the face, if unpaired) were rejected, if recorded
+    rejections: Option<RejectionStats>,
 }
 
 /// Find the best matching face on surface B for a given face on surface A
@@ -120,33 +257,66 @@ fn find_best_match(
     face_a_idx: usize,
     surface_a: &SurfaceMesh,
     surface_b: &SurfaceMesh,
-    tree_b: &ImmutableKdTree<f64, 3>,
+    index_b: &CandidateGenerator,
     criteria: &ContactCriteria,
-) -> Option<ContactPair> {
+    algorithm: &dyn ContactDetectionAlgorithm,
+) -> FaceMatch {
     let centroid_a = &surface_a.face_centroids[face_a_idx];
     let normal_a = &surface_a.face_normals[face_a_idx];
+    let mut rejections = criteria.record_rejections.then(RejectionStats::default);
 
-    // Query k-d tree for nearest faces on surface B
-    let search_radius = criteria.search_radius();
-    let nearest = tree_b.within::<kiddo::SquaredEuclidean>(
-        &[centroid_a.x, centroid_a.y, centroid_a.z],
-        search_radius * search_radius,
-    );
+    // Tagged faces are left unpaired without looking for candidates
+    if criteria.is_face_excluded(surface_a, face_a_idx) {
+        if let Some(rejections) = rejections.as_mut() {
+            rejections.add_unpaired_face(RejectionReason::Tagged);
+        }
+        return FaceMatch {
+            pair: None,
+            rejected_facing: 0,
+            rejections,
+        };
+    }
+
+    // Query the spatial index for nearest faces on surface B
+    let nearest = index_b.candidates(centroid_a);
 
     // Find best matching face on B
     let mut best_match: Option<ContactPair> = None;
     let mut best_distance_abs = f64::MAX;
+    let mut rejected_facing = 0;
+    let mut reject = |reason| {
+        if let Some(rejections) = rejections.as_mut() {
+            rejections.add_candidate(reason);
+        }
+    };
 
-    for neighbor in nearest.iter() {
-        let face_b_idx = neighbor.item as usize;
-        let centroid_b = &surface_b.face_centroids[face_b_idx];
+    for face_b_idx in nearest {
+        if criteria.is_face_excluded(surface_b, face_b_idx) {
+            reject(RejectionReason::Tagged);
+            continue;
+        }
         let normal_b = &surface_b.face_normals[face_b_idx];
 
-        // Compute signed distance from A to B along A's normal
-        let distance = signed_distance_to_plane(centroid_b, centroid_a, normal_a);
+        // Measure the gap from A to B
+        let pair = FacePair {
+            surface_a,
+            face_a: face_a_idx,
+            surface_b,
+            face_b: face_b_idx,
+        };
+        let Some(measurement) = algorithm.measure(&pair) else {
+            reject(RejectionReason::NotMeasured);
+            continue;
+        };
+        let distance = measurement.distance;
 
         // Check if distance is within range
         if !criteria.is_in_range(distance) {
+            reject(if distance > 0.0 {
+                RejectionReason::GapTooLarge
+            } else {
+                RejectionReason::PenetrationTooDeep
+            });
             continue;
         }
 
@@ -155,11 +325,17 @@ fn find_best_match(
 
         // Check if angle is within tolerance
         if !criteria.is_angle_valid(angle) {
+            reject(RejectionReason::AngleTooLarge);
             continue;
         }
 
-        // Project centroid A onto B's plane to get contact point
-        let contact_point = project_point_to_plane(centroid_a, centroid_b, normal_b);
+        // Check that the faces face each other rather than sitting side by
+        // side, e.g. across the edge of a thin part
+        if !criteria.is_facing_valid(facing(face_a_idx, surface_a, face_b_idx, surface_b)) {
+            rejected_facing += 1;
+            reject(RejectionReason::NotFacing);
+            continue;
+        }
 
         // Keep track of the best match (smallest absolute distance)
         let distance_abs = distance.abs();
@@ -170,16 +346,52 @@ fn find_best_match(
                 surface_b_face_id: face_b_idx,
                 distance,
                 normal_angle: angle,
-                contact_point,
+                contact_point: measurement.contact_point,
             });
         }
     }
 
-    best_match
+    // An unpaired face is put down to the furthest check its candidates got to
+    if let Some(rejections) = rejections.as_mut().filter(|_| best_match.is_none()) {
+        let reason = rejections
+            .candidates
+            .keys()
+            .max()
+            .copied()
+            .unwrap_or(RejectionReason::NoCandidates);
+        rejections.add_unpaired_face(reason);
+    }
+
+    FaceMatch {
+        pair: best_match,
+        rejected_facing,
+        rejections,
+    }
+}
+
+/// |Cosine| between A's normal and the direction from A's centroid to the
+/// closest point of face B (1 when the faces touch at A's centroid)
+pub(crate) fn facing(
+    face_a_idx: usize,
+    surface_a: &SurfaceMesh,
+    face_b_idx: usize,
+    surface_b: &SurfaceMesh,
+) -> f64 {
+    let centroid_a = &surface_a.face_centroids[face_a_idx];
+    let closest_b =
+        closest_point_on_face(centroid_a, &surface_b.faces[face_b_idx], &surface_b.nodes);
+    let offset = closest_b - centroid_a;
+
+    let length = offset.norm();
+    if length <= FACING_TOLERANCE * surface_a.face_areas[face_a_idx].sqrt() {
+        return 1.0;
+    }
+
+    offset.dot(&surface_a.face_normals[face_a_idx]).abs() / length
 }
 
 /// Build a k-d tree for spatial indexing of face centroids
-fn build_face_kdtree(surface: &SurfaceMesh) -> ImmutableKdTree<f64, 3> {
+pub(crate) fn build_face_kdtree(surface: &SurfaceMesh) -> ImmutableKdTree<f64, 3> {
     // Collect all points
     let points: Vec<[f64; 3]> = surface
         .face_centroids
@@ -214,6 +426,7 @@ mod tests {
             face_centroids: vec![Point::new(0.5, 0.5, 0.0)],
             face_areas: vec![1.0],
             nodes: nodes_a,
+            face_tags: Default::default(),
         };
 
         // Surface B: flat square at z=0.001 (small gap)
@@ -233,6 +446,7 @@ mod tests {
             face_centroids: vec![Point::new(0.5, 0.5, 0.001)],
             face_areas: vec![1.0],
             nodes: nodes_b,
+            face_tags: Default::default(),
         };
 
         (surface_a, surface_b)
@@ -255,6 +469,166 @@ mod tests {
         assert!((pair.normal_angle - 180.0).abs() < 1.0); // Opposite normals
     }
 
+    #[test]
+    fn test_detect_contact_pairs_until_deadline() {
+        let (surface_a, surface_b) = make_parallel_surfaces();
+        let criteria = ContactCriteria::new(0.005, 0.001, 180.0);
+
+        let expired = Instant::now();
+        assert!(detect_contact_pairs_until(&surface_a, &surface_b, &criteria, expired)
+            .unwrap()
+            .is_none());
+
+        let later = Instant::now() + std::time::Duration::from_secs(60);
+        let results = detect_contact_pairs_until(&surface_a, &surface_b, &criteria, later)
+            .unwrap()
+            .unwrap();
+        assert_eq!(results.num_pairs(), 1);
+    }
+
+    #[test]
+    fn test_detect_contact_pairs_in_region_excludes_outside() {
+        use crate::mesh::roi::RoiSpec;
+
+        let (surface_a, surface_b) = make_parallel_surfaces();
+        let criteria = ContactCriteria::new(0.005, 0.001, 180.0);
+
+        let inside = RoiSpec::Sphere { center: [0.5, 0.5, 0.0], radius: 0.1 }.load().unwrap();
+        let results =
+            detect_contact_pairs_in_region(&surface_a, &surface_b, &criteria, &inside).unwrap();
+        assert_eq!(results.num_pairs(), 1);
+
+        let outside = RoiSpec::Sphere { center: [5.0, 5.0, 5.0], radius: 0.1 }.load().unwrap();
+        let results =
+            detect_contact_pairs_in_region(&surface_a, &surface_b, &criteria, &outside).unwrap();
+        assert_eq!(results.num_pairs(), 0);
+        assert_eq!(results.unpaired_a, vec![0]);
+        assert_eq!(results.unpaired_b, vec![0]);
+    }
+
+    #[test]
+    fn test_facing_rejects_sideways_pairs() {
+        let (surface_a, mut surface_b) = make_parallel_surfaces();
+        let criteria = ContactCriteria::new(0.005, 0.001, 180.0);
+
+        // Move B beside A: still within gap range of A's plane and the search
+        // radius, but not across from it
+        for node in &mut surface_b.nodes {
+            node.x += 1.002;
+        }
+        surface_b.face_centroids[0].x += 1.002;
+        let mut criteria_wide = criteria.clone();
+        criteria_wide.search_radius_multiplier = 400.0;
+
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria_wide).unwrap();
+        assert_eq!(results.num_pairs(), 0);
+        assert_eq!(results.rejected_facing, 1);
+
+        criteria_wide.min_facing = 0.0;
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria_wide).unwrap();
+        assert_eq!(results.num_pairs(), 1);
+        assert_eq!(results.rejected_facing, 0);
+
+        // Faces directly across pass
+        let (surface_a, surface_b) = make_parallel_surfaces();
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria).unwrap();
+        assert_eq!(results.num_pairs(), 1);
+        assert_eq!(results.rejected_facing, 0);
+    }
+
+    #[test]
+    fn test_algorithm_selection() {
+        use crate::contact::algorithm::DetectionAlgorithmKind;
+
+        // B shifted so A's normal through its centroid misses it
+        let (surface_a, mut surface_b) = make_parallel_surfaces();
+        for node in &mut surface_b.nodes {
+            node.x += 0.7;
+        }
+        surface_b.face_centroids[0].x += 0.7;
+        let mut criteria = ContactCriteria::new(0.005, 0.001, 180.0);
+        criteria.search_radius_multiplier = 400.0;
+        criteria.min_facing = 0.0;
+
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria).unwrap();
+        assert_eq!(results.num_pairs(), 1);
+
+        criteria.algorithm = DetectionAlgorithmKind::RayCast;
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria).unwrap();
+        assert_eq!(results.num_pairs(), 0);
+
+        criteria.algorithm = DetectionAlgorithmKind::Mortar;
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria).unwrap();
+        assert_eq!(results.num_pairs(), 1);
+        assert!((results.pairs[0].contact_point.x - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rejection_stats() {
+        use crate::contact::types::RejectionReason;
+
+        let (surface_a, surface_b) = make_parallel_surfaces();
+        let mut criteria = ContactCriteria::new(0.0005, 0.001, 180.0);
+        criteria.search_radius_multiplier = 10.0;
+
+        // Not recorded unless asked for
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria).unwrap();
+        assert!(results.rejections.is_none());
+
+        // The gap of 0.001 is the binding criterion
+        criteria.record_rejections = true;
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria).unwrap();
+        assert_eq!(results.num_pairs(), 0);
+        let rejections = results.rejections.unwrap();
+        assert_eq!(rejections.candidates[&RejectionReason::GapTooLarge], 1);
+        assert_eq!(rejections.binding(), Some((RejectionReason::GapTooLarge, 1)));
+
+        // With the gap allowed, the angle is next
+        criteria.max_gap_distance = 0.005;
+        criteria.max_normal_angle = 10.0;
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria).unwrap();
+        let rejections = results.rejections.unwrap();
+        assert_eq!(rejections.binding(), Some((RejectionReason::AngleTooLarge, 1)));
+
+        // No candidates within a tiny search radius
+        criteria.search_radius_multiplier = 0.1;
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria).unwrap();
+        let rejections = results.rejections.unwrap();
+        assert!(rejections.candidates.is_empty());
+        assert_eq!(rejections.binding(), Some((RejectionReason::NoCandidates, 1)));
+    }
+
+    #[test]
+    fn test_excluded_tags_prevent_pairing() {
+        use crate::contact::types::RejectionReason;
+
+        let (mut surface_a, mut surface_b) = make_parallel_surfaces();
+        let mut criteria = ContactCriteria::new(0.005, 0.001, 180.0);
+        criteria.record_rejections = true;
+        criteria.excluded_tags = vec!["coating=coated".parse().unwrap()];
+
+        // Other values of the layer still pair
+        surface_b.face_tags.set("coating", 0, "bare");
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria).unwrap();
+        assert_eq!(results.num_pairs(), 1);
+
+        // A tagged candidate is rejected...
+        surface_b.face_tags.set("coating", 0, "coated");
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria).unwrap();
+        assert_eq!(results.num_pairs(), 0);
+        let rejections = results.rejections.unwrap();
+        assert_eq!(rejections.candidates[&RejectionReason::Tagged], 1);
+
+        // ...and a tagged face of A is not even looked up
+        let surface_b = make_parallel_surfaces().1;
+        surface_a.face_tags.set("coating", 0, "coated");
+        let results = detect_contact_pairs(&surface_a, &surface_b, &criteria).unwrap();
+        assert_eq!(results.num_pairs(), 0);
+        let rejections = results.rejections.unwrap();
+        assert!(rejections.candidates.is_empty());
+        assert_eq!(rejections.binding(), Some((RejectionReason::Tagged, 1)));
+    }
+
     #[test]
     fn test_build_face_kdtree() {
         use std::num::NonZero;
@@ -0,0 +1,218 @@
+//! Signed deviation of points from a surface (scan-to-mesh comparison)
+//!
+//! For as-built inspection, every point of a point cloud is matched to the
+//! closest location on a mesh surface. The deviation is the distance to that
+//! location, positive on the side the face normal points to.
+
+use crate::contact::detection::build_face_kdtree;
+use crate::format::FloatFormat;
+use crate::mesh::geometry::{closest_point_on_face, distance};
+use crate::mesh::types::{Point, SurfaceMesh};
+use std::num::NonZero;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Number of faces (by nearest centroid) tested for the closest point
+const CANDIDATE_FACES: usize = 8;
+
+/// Deviation of one point from the surface
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointDeviation {
+    /// Face containing the closest surface point
+    pub face_index: usize,
+
+    /// Closest point on the surface
+    pub closest_point: Point,
+
+    /// Signed distance to the closest point (+ along the face normal)
+    pub deviation: f64,
+}
+
+/// Compute the deviation of every point from `surface`
+///
+/// Points farther than `max_distance` from the surface (if given) get `None`,
+/// so stray scan points are not reported as huge deviations. The closest point
+/// is searched among the faces with the nearest centroids, which is exact for
+/// reasonably uniform meshes.
+#[tracing::instrument(skip_all, fields(surface = %surface.part_name, points = points.len()))]
+pub fn compute_point_deviations(
+    points: &[Point],
+    surface: &SurfaceMesh,
+    max_distance: Option<f64>,
+) -> Vec<Option<PointDeviation>> {
+    if surface.faces.is_empty() {
+        return vec![None; points.len()];
+    }
+
+    let tree = build_face_kdtree(surface);
+    let num_candidates = NonZero::new(CANDIDATE_FACES.min(surface.num_faces())).unwrap();
+
+    let deviation_of = |point: &Point| -> Option<PointDeviation> {
+        let candidates =
+            tree.nearest_n::<kiddo::SquaredEuclidean>(&[point.x, point.y, point.z], num_candidates);
+
+        let (face_index, closest_point) = candidates
+            .iter()
+            .map(|neighbor| {
+                let face_index = neighbor.item as usize;
+                let face = &surface.faces[face_index];
+                (
+                    face_index,
+                    closest_point_on_face(point, face, &surface.nodes),
+                )
+            })
+            .min_by(|(_, x), (_, y)| distance(point, x).total_cmp(&distance(point, y)))?;
+
+        let dist = distance(point, &closest_point);
+        if max_distance.is_some_and(|max| dist > max) {
+            return None;
+        }
+
+        let side = (point - closest_point).dot(&surface.face_normals[face_index]);
+        Some(PointDeviation {
+            face_index,
+            closest_point,
+            deviation: if side < 0.0 { -dist } else { dist },
+        })
+    };
+
+    // Threshold for parallelization (below this, overhead isn't worth it)
+    const PARALLEL_THRESHOLD: usize = 5000;
+
+    #[cfg(feature = "parallel")]
+    let deviations: Vec<_> = if points.len() >= PARALLEL_THRESHOLD {
+        points.par_iter().map(deviation_of).collect()
+    } else {
+        points.iter().map(deviation_of).collect()
+    };
+
+    #[cfg(not(feature = "parallel"))]
+    let deviations: Vec<_> = points.iter().map(deviation_of).collect();
+
+    deviations
+}
+
+/// Summary statistics of point deviations
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeviationSummary {
+    /// Number of points
+    pub num_points: usize,
+
+    /// Number of points matched to the surface
+    pub num_matched: usize,
+
+    /// Smallest (most negative) deviation
+    pub min: f64,
+
+    /// Largest deviation
+    pub max: f64,
+
+    /// Mean deviation
+    pub mean: f64,
+
+    /// Root-mean-square deviation
+    pub rms: f64,
+}
+
+impl DeviationSummary {
+    /// Summarize the matched points
+    pub fn compute(deviations: &[Option<PointDeviation>]) -> Self {
+        let values: Vec<f64> = deviations.iter().flatten().map(|d| d.deviation).collect();
+        let mut summary = Self {
+            num_points: deviations.len(),
+            num_matched: values.len(),
+            ..Self::default()
+        };
+
+        if !values.is_empty() {
+            let n = values.len() as f64;
+            summary.min = values.iter().copied().fold(f64::INFINITY, f64::min);
+            summary.max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            summary.mean = values.iter().sum::<f64>() / n;
+            summary.rms = (values.iter().map(|v| v * v).sum::<f64>() / n).sqrt();
+        }
+
+        summary
+    }
+
+    /// Print the summary to the console
+    pub fn print_summary(&self, surface_name: &str, format: FloatFormat) {
+        println!("\n{}", "=".repeat(60));
+        println!("POINT DEVIATION: {}", surface_name);
+        println!("{}", "=".repeat(60));
+        println!();
+        println!("  Points:          {}", self.num_points);
+        println!("  Matched:         {}", self.num_matched);
+        println!("  Unmatched:       {}", self.num_points - self.num_matched);
+        println!();
+
+        if self.num_matched > 0 {
+            println!("  Deviation:");
+            println!("    Min:       {}", format.show(self.min));
+            println!("    Max:       {}", format.show(self.max));
+            println!("    Mean:      {}", format.show(self.mean));
+            println!("    RMS:       {}", format.show(self.rms));
+            println!();
+        }
+
+        println!("{}", "=".repeat(60));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::types::{QuadFace, Vec3};
+
+    fn make_plane() -> SurfaceMesh {
+        let mut surface = SurfaceMesh::new("plane".to_string());
+        surface.nodes = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(2.0, 0.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(2.0, 1.0, 0.0),
+        ];
+        for (face, centroid) in [
+            (QuadFace::new([0, 1, 4, 3]), Point::new(0.5, 0.5, 0.0)),
+            (QuadFace::new([1, 2, 5, 4]), Point::new(1.5, 0.5, 0.0)),
+        ] {
+            surface.faces.push(face);
+            surface.face_normals.push(Vec3::new(0.0, 0.0, 1.0));
+            surface.face_centroids.push(centroid);
+            surface.face_areas.push(1.0);
+        }
+        surface
+    }
+
+    #[test]
+    fn test_point_deviations() {
+        let surface = make_plane();
+        let points = [
+            Point::new(0.25, 0.5, 0.1),
+            Point::new(1.9, 0.2, -0.05),
+            Point::new(5.0, 0.5, 0.0),
+        ];
+
+        let deviations = compute_point_deviations(&points, &surface, Some(1.0));
+
+        let first = deviations[0].unwrap();
+        assert_eq!(first.face_index, 0);
+        assert!((first.deviation - 0.1).abs() < 1e-12);
+
+        let second = deviations[1].unwrap();
+        assert_eq!(second.face_index, 1);
+        assert!((second.deviation + 0.05).abs() < 1e-12);
+
+        // Too far from the surface
+        assert!(deviations[2].is_none());
+
+        let summary = DeviationSummary::compute(&deviations);
+        assert_eq!(summary.num_points, 3);
+        assert_eq!(summary.num_matched, 2);
+        assert!((summary.min + 0.05).abs() < 1e-12);
+        assert!((summary.max - 0.1).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,285 @@
+//! Incrementally updated face index for interactive use
+//!
+//! Detection runs build a [`CentroidIndex`](crate::contact::spatial_index)
+//! once per surface and throw it away. Interactive front ends (a TUI, or a
+//! service re-checking contact after a patch is edited or a part is moved)
+//! instead change a few surfaces at a time and query again straight away.
+//! [`DynamicFaceIndex`] keeps the face centroids of many surfaces in one
+//! mutable k-d tree: faces are inserted, removed and moved in place, so a
+//! transformed part costs an update of its own faces rather than a rebuild of
+//! the whole index.
+//!
+//! The tree sits behind a read-write lock and every method takes `&self`, so
+//! the index can be shared between threads (e.g. in an `Arc`): queries run
+//! concurrently and wait only while an update is applied.
+
+use crate::mesh::types::{Point, SurfaceMesh};
+use kiddo::KdTree;
+use nalgebra::Isometry3;
+use std::collections::HashMap;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A face of one of the surfaces in a [`DynamicFaceIndex`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FaceKey {
+    /// Caller-chosen ID of the surface
+    pub surface: u32,
+
+    /// Index of the face in the surface
+    pub face: u32,
+}
+
+impl FaceKey {
+    fn item(self) -> u64 {
+        (u64::from(self.surface) << 32) | u64::from(self.face)
+    }
+
+    fn from_item(item: u64) -> Self {
+        Self {
+            surface: (item >> 32) as u32,
+            face: item as u32,
+        }
+    }
+}
+
+/// Face centroids of many surfaces in a mutable k-d tree, safe to update and
+/// query from several threads
+#[derive(Debug, Default)]
+pub struct DynamicFaceIndex {
+    inner: RwLock<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    tree: KdTree<f64, 3>,
+
+    /// Position each face was inserted at, by surface then face; removal
+    /// from the tree needs the exact coordinates
+    surfaces: HashMap<u32, HashMap<u32, [f64; 3]>>,
+}
+
+impl Inner {
+    fn insert(&mut self, key: FaceKey, centroid: &Point) {
+        self.remove(key);
+        let position = [centroid.x, centroid.y, centroid.z];
+        self.tree.add(&position, key.item());
+        self.surfaces
+            .entry(key.surface)
+            .or_default()
+            .insert(key.face, position);
+    }
+
+    fn remove(&mut self, key: FaceKey) -> bool {
+        let Some(faces) = self.surfaces.get_mut(&key.surface) else {
+            return false;
+        };
+        let Some(position) = faces.remove(&key.face) else {
+            return false;
+        };
+        if faces.is_empty() {
+            self.surfaces.remove(&key.surface);
+        }
+        self.tree.remove(&position, key.item());
+        true
+    }
+}
+
+impl DynamicFaceIndex {
+    /// Empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of indexed faces
+    pub fn len(&self) -> usize {
+        self.read().tree.size() as usize
+    }
+
+    /// Whether no faces are indexed
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of faces indexed for a surface
+    pub fn num_faces(&self, surface: u32) -> usize {
+        self.read().surfaces.get(&surface).map_or(0, HashMap::len)
+    }
+
+    /// Index all faces of `mesh` as surface `surface`, replacing the faces
+    /// indexed for it before
+    pub fn insert_surface(&self, surface: u32, mesh: &SurfaceMesh) {
+        let mut inner = self.write();
+        for (face, position) in inner.surfaces.remove(&surface).unwrap_or_default() {
+            inner
+                .tree
+                .remove(&position, FaceKey { surface, face }.item());
+        }
+        for (face, centroid) in mesh.face_centroids.iter().enumerate() {
+            inner.insert(
+                FaceKey {
+                    surface,
+                    face: face as u32,
+                },
+                centroid,
+            );
+        }
+    }
+
+    /// Remove all faces of a surface, returning how many were indexed
+    pub fn remove_surface(&self, surface: u32) -> usize {
+        let mut inner = self.write();
+        let faces = inner.surfaces.remove(&surface).unwrap_or_default();
+        for (&face, position) in &faces {
+            inner
+                .tree
+                .remove(position, FaceKey { surface, face }.item());
+        }
+        faces.len()
+    }
+
+    /// Re-index some faces of surface `surface` from their current centroids
+    /// in `mesh`, e.g. after a patch of it was edited; faces past the end of
+    /// `mesh` are removed
+    pub fn update_faces(&self, surface: u32, mesh: &SurfaceMesh, faces: &[usize]) {
+        let mut inner = self.write();
+        for &face in faces {
+            let key = FaceKey {
+                surface,
+                face: face as u32,
+            };
+            match mesh.face_centroids.get(face) {
+                Some(centroid) => inner.insert(key, centroid),
+                None => {
+                    inner.remove(key);
+                }
+            }
+        }
+    }
+
+    /// Index a single face at `centroid`, moving it if it is indexed already
+    pub fn insert_face(&self, key: FaceKey, centroid: &Point) {
+        self.write().insert(key, centroid);
+    }
+
+    /// Remove a single face, returning whether it was indexed
+    pub fn remove_face(&self, key: FaceKey) -> bool {
+        self.write().remove(key)
+    }
+
+    /// Move all faces of a surface by a rigid motion, e.g. after the part
+    /// was repositioned
+    pub fn transform_surface(&self, surface: u32, motion: &Isometry3<f64>) {
+        let mut inner = self.write();
+        let Some(faces) = inner.surfaces.remove(&surface) else {
+            return;
+        };
+        for (face, position) in faces {
+            let key = FaceKey { surface, face };
+            inner.tree.remove(&position, key.item());
+            let moved = motion * Point::from(position);
+            inner.insert(key, &moved);
+        }
+    }
+
+    /// Faces whose centroids are within `radius` of `query`, nearest first
+    pub fn within(&self, query: &Point, radius: f64) -> Vec<FaceKey> {
+        self.read()
+            .tree
+            .within::<kiddo::SquaredEuclidean>(&[query.x, query.y, query.z], radius * radius)
+            .iter()
+            .map(|neighbor| FaceKey::from_item(neighbor.item))
+            .collect()
+    }
+
+    /// Faces of one surface whose centroids are within `radius` of `query`,
+    /// nearest first
+    pub fn within_surface(&self, surface: u32, query: &Point, radius: f64) -> Vec<usize> {
+        self.within(query, radius)
+            .into_iter()
+            .filter(|key| key.surface == surface)
+            .map(|key| key.face as usize)
+            .collect()
+    }
+
+    // A panic during an update leaves at worst some faces missing or stale,
+    // which later updates and queries tolerate, so poisoning is ignored
+    fn read(&self) -> RwLockReadGuard<'_, Inner> {
+        self.inner.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Inner> {
+        self.inner.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::fixtures::strip;
+
+    #[test]
+    fn test_dynamic_face_index() {
+        let index = DynamicFaceIndex::new();
+        index.insert_surface(1, &strip("strip", 0.0, 100));
+        index.insert_surface(2, &strip("strip", 1.0, 100));
+        assert_eq!(index.len(), 200);
+
+        let near = index.within(&Point::new(10.5, 0.5, 0.1), 0.5);
+        assert_eq!(
+            near,
+            [FaceKey {
+                surface: 1,
+                face: 10
+            }]
+        );
+        let query = Point::new(10.2, 0.5, 0.9);
+        assert_eq!(index.within_surface(2, &query, 1.0), [10, 9]);
+
+        // Lift surface 2 out of reach, then bring one face back
+        index.transform_surface(2, &Isometry3::translation(0.0, 0.0, 5.0));
+        assert!(index.within_surface(2, &query, 1.0).is_empty());
+        assert_eq!(
+            index.within_surface(2, &Point::new(10.5, 0.5, 6.0), 0.1),
+            [10]
+        );
+        assert_eq!(index.len(), 200);
+
+        let mut edited = strip("strip", 6.0, 100);
+        edited.face_centroids[3] = Point::new(3.5, 0.5, 0.0);
+        edited.face_centroids.truncate(50);
+        index.update_faces(2, &edited, &[3, 99]);
+        assert_eq!(index.num_faces(2), 99);
+        let near = index.within(&Point::new(3.5, 0.5, 0.0), 0.01);
+        assert_eq!(near.len(), 2);
+
+        // Replacing and removing
+        index.insert_surface(1, &strip("strip", 0.0, 10));
+        assert_eq!(index.num_faces(1), 10);
+        assert!(index.remove_face(FaceKey {
+            surface: 1,
+            face: 0
+        }));
+        assert!(!index.remove_face(FaceKey {
+            surface: 1,
+            face: 0
+        }));
+        assert_eq!(index.remove_surface(1), 9);
+        assert_eq!(index.len(), 99);
+    }
+
+    #[test]
+    fn test_concurrent_updates() {
+        let index = DynamicFaceIndex::new();
+        std::thread::scope(|scope| {
+            for surface in 0..4 {
+                let index = &index;
+                scope.spawn(move || {
+                    index.insert_surface(surface, &strip("strip", surface as f64, 50));
+                    let query = Point::new(25.5, 0.5, surface as f64);
+                    assert_eq!(index.within_surface(surface, &query, 0.1), [25]);
+                });
+            }
+        });
+        assert_eq!(index.len(), 200);
+    }
+}
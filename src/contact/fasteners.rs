@@ -0,0 +1,589 @@
+//! Bolt and pin interfaces found from cylindrical surfaces
+//!
+//! A fastener sits in its hole with a clearance that is usually far larger
+//! than the gap tolerance of contact detection, so automatic detection never
+//! pairs a bolt shank with its bore. They are found by shape instead: skin
+//! patches of a block that meet at smooth edges are merged into regions, and
+//! a region that fits a cylinder (see [`CylinderFit`]) around at least half
+//! its circumference is a hole if the part lies outside it and a shaft if
+//! the part lies inside. A hole and a shaft of different blocks on a common
+//! axis, with the shaft inside the hole, form a [`FastenerFit`], which
+//! reports the clearance and widens the gap tolerance to pair them.
+
+use crate::contact::fitting::CylinderFit;
+use crate::contact::types::ContactCriteria;
+use crate::error::{ContactDetectorError, Result};
+use crate::format::{FloatFormat, Glyphs};
+use crate::mesh::geometry::angle_between_vectors;
+use crate::mesh::types::{Mesh, Point, QuadFace, SurfaceMesh, Vec3};
+use crate::mesh::union_find::UnionFind;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::path::Path;
+
+/// Largest angle (degrees) between the normals of two faces across an edge
+/// for their patches to belong to the same smooth region
+const SMOOTH_EDGE_ANGLE: f64 = 50.0;
+
+/// Largest cylinder fit residual, relative to the radius
+const CYLINDER_TOLERANCE: f64 = 0.05;
+
+/// Smallest mean alignment of the face normals with the radial direction
+const MIN_RADIAL_ALIGNMENT: f64 = 0.95;
+
+/// Largest angle (degrees) between the axes of a hole and its shaft
+const AXIS_ANGLE_TOLERANCE: f64 = 5.0;
+
+/// Interference and axis offset tolerated beyond the clearance, relative to
+/// the hole diameter
+const FIT_TOLERANCE: f64 = 0.02;
+
+/// Margin on the largest radial gap of a fit when widening the gap tolerance
+const GAP_MARGIN: f64 = 0.25;
+
+/// Default largest diametral clearance, relative to the hole diameter
+pub const DEFAULT_MAX_CLEARANCE_RATIO: f64 = 0.2;
+
+/// Whether a cylindrical region is a bore or a pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CylinderRole {
+    /// Normals point to the axis
+    Hole,
+
+    /// Normals point away from the axis
+    Shaft,
+}
+
+/// Patches of one block forming a cylinder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CylindricalRegion {
+    /// Element block of the patches
+    pub block: String,
+
+    /// Indices of the patches in the surfaces searched
+    pub surfaces: Vec<usize>,
+
+    pub role: CylinderRole,
+
+    /// Cylinder through the face centroids
+    pub fit: CylinderFit,
+
+    /// Ends of the region on the axis, from the extent of its nodes
+    pub ends: [[f64; 3]; 2],
+}
+
+impl CylindricalRegion {
+    pub fn diameter(&self) -> f64 {
+        2.0 * self.fit.radius
+    }
+}
+
+/// Cylinder through the faces of the given surfaces and its ends, or `None`
+/// if they are not a cylinder covering at least half its circumference
+fn fit_cylinder(
+    surfaces: &[SurfaceMesh],
+    members: &[usize],
+) -> Option<(CylinderFit, [[f64; 3]; 2])> {
+    let faces: Vec<(&SurfaceMesh, usize)> = members
+        .iter()
+        .flat_map(|&s| (0..surfaces[s].num_faces()).map(move |f| (&surfaces[s], f)))
+        .collect();
+    let centroids: Vec<_> = faces.iter().map(|(s, f)| s.face_centroids[*f]).collect();
+    let normals: Vec<_> = faces.iter().map(|(s, f)| s.face_normals[*f]).collect();
+    let fit = CylinderFit::fit(&centroids, &normals)?;
+    if fit.max_residual > CYLINDER_TOLERANCE * fit.radius {
+        return None;
+    }
+
+    // Alignment of the normals with the radial direction (skin normals may
+    // point either way), and the angle of each face around the axis
+    let axis = Vec3::from(fit.axis);
+    let axis_point = Vec3::from(fit.axis_point);
+    let reference = if axis.x.abs() < 0.9 {
+        Vec3::x()
+    } else {
+        Vec3::y()
+    };
+    let u = reference.cross(&axis).normalize();
+    let v = axis.cross(&u);
+    let mut alignment = 0.0;
+    let mut angles = Vec::with_capacity(faces.len());
+    for (centroid, normal) in centroids.iter().zip(&normals) {
+        let radial = radial_offset(centroid, &axis_point, &axis);
+        let r = radial.norm();
+        if r == 0.0 {
+            return None;
+        }
+        alignment += normal.dot(&radial).abs() / r;
+        angles.push(radial.dot(&v).atan2(radial.dot(&u)));
+    }
+    if alignment / (faces.len() as f64) < MIN_RADIAL_ALIGNMENT {
+        return None;
+    }
+
+    // A strip of a large cylinder is a curved wall, not a hole or a pin
+    angles.sort_by(f64::total_cmp);
+    let largest_gap = angles
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .fold(angles[0] + 2.0 * PI - angles[angles.len() - 1], f64::max);
+    if largest_gap > PI {
+        return None;
+    }
+
+    let (lo, hi) = faces
+        .iter()
+        .flat_map(|(s, f)| s.faces[*f].corners().iter().map(move |&n| s.nodes[n]))
+        .map(|p| (p.coords - axis_point).dot(&axis))
+        .fold((f64::MAX, f64::MIN), |(lo, hi), t| (lo.min(t), hi.max(t)));
+    let end = |t: f64| (axis_point + axis * t).into();
+    Some((fit, [end(lo), end(hi)]))
+}
+
+/// Component of the offset of `point` from the axis perpendicular to it
+fn radial_offset(point: &Point, axis_point: &Vec3, axis: &Vec3) -> Vec3 {
+    let d = point.coords - axis_point;
+    d - axis * d.dot(axis)
+}
+
+/// Find the cylindrical holes and shafts among the skin patches of `mesh`
+///
+/// Patches are merged across edges between faces whose normals are within
+/// [`SMOOTH_EDGE_ANGLE`] of each other, so `surfaces` must share the node
+/// numbering of `mesh`. Whether a cylinder is a hole or a shaft is told by
+/// the elements behind its faces, which lie outside a hole and inside a
+/// shaft.
+pub fn find_cylindrical_regions(mesh: &Mesh, surfaces: &[SurfaceMesh]) -> Vec<CylindricalRegion> {
+    // Faces of every surface on each edge, keyed by block so that regions
+    // never span two parts
+    let mut edges: Vec<((&str, usize, usize), usize, usize)> = Vec::new();
+    for (s, surface) in surfaces.iter().enumerate() {
+        for (f, face) in surface.faces.iter().enumerate() {
+            let corners = face.corners();
+            for k in 0..corners.len() {
+                let (a, b) = (corners[k], corners[(k + 1) % corners.len()]);
+                edges.push(((surface.block_name(), a.min(b), a.max(b)), s, f));
+            }
+        }
+    }
+    edges.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+    let mut components = UnionFind::new(surfaces.len());
+    for run in edges.chunk_by(|a, b| a.0 == b.0) {
+        // Only manifold edges between two patches
+        if let [(_, s1, f1), (_, s2, f2)] = run {
+            if s1 != s2
+                && angle_between_vectors(
+                    &surfaces[*s1].face_normals[*f1],
+                    &surfaces[*s2].face_normals[*f2],
+                ) <= SMOOTH_EDGE_ANGLE
+            {
+                components.union(*s1, *s2);
+            }
+        }
+    }
+
+    // A single patch never goes round far enough to be a cylinder
+    let cylinders: Vec<(Vec<usize>, CylinderFit, [[f64; 3]; 2])> = components
+        .components()
+        .into_iter()
+        .filter(|members| members.len() > 1)
+        .filter_map(|members| {
+            let (fit, ends) = fit_cylinder(surfaces, &members)?;
+            Some((members, fit, ends))
+        })
+        .collect();
+
+    // Element behind each face of the cylinders
+    let mut owners: HashMap<QuadFace, Option<usize>> = cylinders
+        .iter()
+        .flat_map(|(members, ..)| members)
+        .flat_map(|&s| {
+            surfaces[s]
+                .faces
+                .iter()
+                .map(|face| (face.canonical(), None))
+        })
+        .collect();
+    for (e, element) in mesh.elements.iter().enumerate() {
+        for face in element.faces() {
+            if let Some(owner) = owners.get_mut(&face.canonical()) {
+                *owner = Some(e);
+            }
+        }
+    }
+
+    cylinders
+        .into_iter()
+        .map(|(members, fit, ends)| {
+            let axis = Vec3::from(fit.axis);
+            let axis_point = Vec3::from(fit.axis_point);
+            let (mut outside, mut total) = (0, 0);
+            for &s in &members {
+                let surface = &surfaces[s];
+                for (face, centroid) in surface.faces.iter().zip(&surface.face_centroids) {
+                    let Some(Some(e)) = owners.get(&face.canonical()) else {
+                        continue;
+                    };
+                    let element = &mesh.elements[*e];
+                    let element_centroid = Point::from(
+                        element
+                            .node_ids
+                            .iter()
+                            .fold(Vec3::zeros(), |sum, &n| sum + mesh.nodes[n].coords)
+                            / element.node_ids.len() as f64,
+                    );
+                    let element_radius = radial_offset(&element_centroid, &axis_point, &axis);
+                    let face_radius = radial_offset(centroid, &axis_point, &axis);
+                    if element_radius.norm() > face_radius.norm() {
+                        outside += 1;
+                    }
+                    total += 1;
+                }
+            }
+
+            CylindricalRegion {
+                block: surfaces[members[0]].block_name().to_string(),
+                surfaces: members,
+                role: if 2 * outside > total {
+                    CylinderRole::Hole
+                } else {
+                    CylinderRole::Shaft
+                },
+                fit,
+                ends,
+            }
+        })
+        .collect()
+}
+
+/// A shaft inside a coaxial hole of another part
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FastenerFit {
+    /// Indices of the patches of the hole and of the shaft
+    pub hole_surfaces: Vec<usize>,
+    pub shaft_surfaces: Vec<usize>,
+
+    /// Element blocks of the hole and of the shaft
+    pub hole_block: String,
+    pub shaft_block: String,
+
+    pub hole_diameter: f64,
+    pub shaft_diameter: f64,
+
+    /// Diametral clearance, hole minus shaft diameter (negative for an
+    /// interference fit)
+    pub clearance: f64,
+
+    /// Offset between the axes of the hole and the shaft
+    pub eccentricity: f64,
+
+    /// Length over which the shaft is inside the hole
+    pub engagement: f64,
+
+    /// Axis of the hole, through its middle
+    pub axis_point: [f64; 3],
+    pub axis: [f64; 3],
+
+    /// Face pairs found between the hole and the shaft
+    pub face_pairs: usize,
+}
+
+impl FastenerFit {
+    /// The fit of `shaft` in `hole`, or `None` if they are not coaxial, do
+    /// not overlap along the axis, or the clearance exceeds
+    /// `max_clearance_ratio` of the hole diameter
+    pub fn between(
+        hole: &CylindricalRegion,
+        shaft: &CylindricalRegion,
+        max_clearance_ratio: f64,
+    ) -> Option<Self> {
+        if hole.role != CylinderRole::Hole
+            || shaft.role != CylinderRole::Shaft
+            || hole.block == shaft.block
+        {
+            return None;
+        }
+        let axis = Vec3::from(hole.fit.axis);
+        let shaft_axis = Vec3::from(shaft.fit.axis);
+        if axis.dot(&shaft_axis).abs() < AXIS_ANGLE_TOLERANCE.to_radians().cos() {
+            return None;
+        }
+
+        let (hole_diameter, shaft_diameter) = (hole.diameter(), shaft.diameter());
+        let clearance = hole_diameter - shaft_diameter;
+        if clearance < -FIT_TOLERANCE * hole_diameter
+            || clearance > max_clearance_ratio * hole_diameter
+        {
+            return None;
+        }
+
+        let axis_point = Vec3::from(hole.fit.axis_point);
+        let offset = Vec3::from(shaft.fit.axis_point) - axis_point;
+        let eccentricity = (offset - axis * offset.dot(&axis)).norm();
+        if eccentricity > clearance.max(0.0) / 2.0 + FIT_TOLERANCE * hole_diameter {
+            return None;
+        }
+
+        // Overlap of the two regions along the hole's axis
+        let along = |ends: &[[f64; 3]; 2]| {
+            let [a, b] = ends.map(|end| (Vec3::from(end) - axis_point).dot(&axis));
+            (a.min(b), a.max(b))
+        };
+        let (hole_lo, hole_hi) = along(&hole.ends);
+        let (shaft_lo, shaft_hi) = along(&shaft.ends);
+        let engagement = hole_hi.min(shaft_hi) - hole_lo.max(shaft_lo);
+        if engagement <= 0.0 {
+            return None;
+        }
+
+        Some(Self {
+            hole_surfaces: hole.surfaces.clone(),
+            shaft_surfaces: shaft.surfaces.clone(),
+            hole_block: hole.block.clone(),
+            shaft_block: shaft.block.clone(),
+            hole_diameter,
+            shaft_diameter,
+            clearance,
+            eccentricity,
+            engagement,
+            axis_point: hole.fit.axis_point,
+            axis: hole.fit.axis,
+            face_pairs: 0,
+        })
+    }
+
+    /// Largest radial gap between the shaft and the hole
+    pub fn max_radial_gap(&self) -> f64 {
+        (self.clearance / 2.0 + self.eccentricity).max(0.0)
+    }
+
+    /// `criteria` with the gap tolerance widened to the largest radial gap
+    /// of the fit
+    pub fn criteria(&self, criteria: &ContactCriteria) -> ContactCriteria {
+        let mut widened = criteria.clone();
+        widened.max_gap_distance = criteria
+            .max_gap_distance
+            .max(self.max_radial_gap() * (1.0 + GAP_MARGIN));
+        widened
+    }
+
+    /// Pairs (i, j), i < j, of a hole patch and a shaft patch
+    pub fn surface_pairs(&self) -> Vec<(usize, usize)> {
+        self.hole_surfaces
+            .iter()
+            .flat_map(|&h| {
+                self.shaft_surfaces
+                    .iter()
+                    .map(move |&s| (h.min(s), h.max(s)))
+            })
+            .collect()
+    }
+}
+
+/// Find the shafts inside holes of other parts among the skin patches of
+/// `mesh` (see [`find_cylindrical_regions`])
+#[tracing::instrument(skip_all, fields(surfaces = surfaces.len()))]
+pub fn find_fastener_fits(
+    mesh: &Mesh,
+    surfaces: &[SurfaceMesh],
+    max_clearance_ratio: f64,
+) -> Vec<FastenerFit> {
+    let regions = find_cylindrical_regions(mesh, surfaces);
+    let mut fits = Vec::new();
+    for hole in &regions {
+        for shaft in &regions {
+            if let Some(fit) = FastenerFit::between(hole, shaft, max_clearance_ratio) {
+                tracing::info!(
+                    "Fastener fit: {} ↔ {} (hole {:.6}, shaft {:.6})",
+                    fit.hole_block,
+                    fit.shaft_block,
+                    fit.hole_diameter,
+                    fit.shaft_diameter
+                );
+                fits.push(fit);
+            }
+        }
+    }
+    fits
+}
+
+/// Clearance fits of the fasteners of a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FastenerReport {
+    /// Largest diametral clearance searched, relative to the hole diameter
+    pub max_clearance_ratio: f64,
+
+    pub fits: Vec<FastenerFit>,
+}
+
+impl FastenerReport {
+    pub fn new(max_clearance_ratio: f64, fits: Vec<FastenerFit>) -> Self {
+        Self {
+            max_clearance_ratio,
+            fits,
+        }
+    }
+
+    /// Print the fit of each fastener
+    pub fn print_summary(&self, format: FloatFormat, glyphs: Glyphs) {
+        println!("\n{}", "=".repeat(60));
+        println!("FASTENER FITS");
+        println!("{}", "=".repeat(60));
+        println!();
+        println!(
+            "  {} shaft(s) in holes of other parts (clearance up to {:.0}% of the hole diameter)",
+            self.fits.len(),
+            self.max_clearance_ratio * 100.0
+        );
+
+        for fit in &self.fits {
+            let [x, y, z] = fit.axis_point;
+            println!();
+            println!(
+                "  {} (hole) {} {} (shaft) at ({:.4}, {:.4}, {:.4})",
+                fit.hole_block,
+                glyphs.between(),
+                fit.shaft_block,
+                x,
+                y,
+                z
+            );
+            println!(
+                "    Diameters: hole {}, shaft {}",
+                format.show(fit.hole_diameter),
+                format.show(fit.shaft_diameter)
+            );
+            println!(
+                "    Clearance: {}{} (eccentricity {})",
+                format.show(fit.clearance),
+                if fit.clearance < 0.0 {
+                    ", interference"
+                } else {
+                    ""
+                },
+                format.show(fit.eccentricity)
+            );
+            println!(
+                "    Engagement: {}, {} face pair(s)",
+                format.show(fit.engagement),
+                fit.face_pairs
+            );
+        }
+        println!();
+        println!("{}", "=".repeat(60));
+    }
+
+    /// Export the report to a JSON file
+    pub fn export<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to serialize fastener report: {}", e))
+        })?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::auto::{detect_all, AutoContactOptions};
+    use crate::mesh::extract_surface;
+    use crate::mesh::types::HexElement;
+
+    /// Add a block of hex elements filling a tube around the z axis between
+    /// the given radii and heights
+    fn add_tube(mesh: &mut Mesh, block: &str, radii: &[f64], heights: &[f64]) {
+        let segments = 16;
+        let base = mesh.nodes.len();
+        let node =
+            |k: usize, i: usize, l: usize| base + (l * segments + k % segments) * radii.len() + i;
+        for &z in heights {
+            for k in 0..segments {
+                let angle = 2.0 * PI * k as f64 / segments as f64;
+                for &r in radii {
+                    mesh.nodes
+                        .push(Point::new(r * angle.cos(), r * angle.sin(), z));
+                }
+            }
+        }
+
+        let mut elements = Vec::new();
+        for l in 0..heights.len() - 1 {
+            for k in 0..segments {
+                for i in 0..radii.len() - 1 {
+                    elements.push(mesh.elements.len());
+                    mesh.elements.push(HexElement::new([
+                        node(k, i, l),
+                        node(k, i + 1, l),
+                        node(k + 1, i + 1, l),
+                        node(k + 1, i, l),
+                        node(k, i, l + 1),
+                        node(k, i + 1, l + 1),
+                        node(k + 1, i + 1, l + 1),
+                        node(k + 1, i, l + 1),
+                    ]));
+                }
+            }
+        }
+        mesh.element_blocks.insert(block.to_string(), elements);
+    }
+
+    #[test]
+    fn test_fastener_fits() {
+        // A hollow bolt through a plate with a radial clearance of 0.1
+        let mut mesh = Mesh::new();
+        add_tube(&mut mesh, "Plate", &[1.1, 1.5, 2.0], &[0.0, 0.5, 1.0]);
+        add_tube(&mut mesh, "Bolt", &[0.5, 1.0], &[-0.5, 0.0, 0.5, 1.0, 1.5]);
+        let surfaces = extract_surface(&mesh).unwrap();
+
+        let regions = find_cylindrical_regions(&mesh, &surfaces);
+        let role_of = |block: &str, radius: f64| {
+            regions
+                .iter()
+                .find(|r| r.block == block && (r.fit.radius - radius).abs() < 0.05)
+                .map(|r| r.role)
+        };
+        assert_eq!(regions.len(), 4);
+        assert_eq!(role_of("Plate", 1.1), Some(CylinderRole::Hole));
+        assert_eq!(role_of("Plate", 2.0), Some(CylinderRole::Shaft));
+        assert_eq!(role_of("Bolt", 0.5), Some(CylinderRole::Hole));
+        assert_eq!(role_of("Bolt", 1.0), Some(CylinderRole::Shaft));
+
+        let fits = find_fastener_fits(&mesh, &surfaces, DEFAULT_MAX_CLEARANCE_RATIO);
+        assert_eq!(fits.len(), 1);
+        let fit = &fits[0];
+        assert_eq!(
+            (fit.hole_block.as_str(), fit.shaft_block.as_str()),
+            ("Plate", "Bolt")
+        );
+        assert!((fit.clearance - 0.2 * (PI / 16.0).cos()).abs() < 1e-9);
+        assert!(fit.eccentricity < 1e-9);
+        assert!((fit.engagement - 1.0).abs() < 1e-9);
+        assert!(find_fastener_fits(&mesh, &surfaces, 0.05).is_empty());
+
+        // The clearance is far beyond the gap tolerance, but not the widened
+        // one of the fit
+        let mut options = AutoContactOptions {
+            criteria: ContactCriteria::new(0.005, 0.001, 180.0),
+            ..Default::default()
+        };
+        assert!(detect_all(&mesh, &options).unwrap().pairs.is_empty());
+        assert!(fit.criteria(&options.criteria).max_gap_distance > fit.max_radial_gap());
+
+        options.fastener_clearance = Some(DEFAULT_MAX_CLEARANCE_RATIO);
+        let report = detect_all(&mesh, &options).unwrap();
+        assert!(!report.pairs.is_empty());
+        assert_eq!(report.fasteners.len(), 1);
+        assert_eq!(
+            report.fasteners[0].face_pairs,
+            report
+                .pairs
+                .iter()
+                .map(|pair| pair.results.num_pairs())
+                .sum::<usize>()
+        );
+    }
+}
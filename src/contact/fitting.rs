@@ -0,0 +1,651 @@
+//! Primitive fits of contact patches
+//!
+//! The paired faces of a surface form its contact patch. Fitting a plane to
+//! the patch's face centroids tells how flat the mating surface is (e.g.
+//! whether a flange is planar enough to seal a gasket), along with its
+//! orientation and in-plane size. Cylinder and sphere fits describe curved
+//! interfaces such as pins in bores or ball joints by their axis, center and
+//! radius. Together with the spread of the face normals, the fits classify an
+//! interface as a planar flange, a cylindrical fit, spherical or irregular.
+
+use crate::contact::types::ContactResults;
+use crate::format::FloatFormat;
+use crate::mesh::types::{Point, SurfaceMesh, Vec3};
+use nalgebra::{Matrix3, Matrix4, SymmetricEigen, Vector3, Vector4};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Relative spread below which points are considered collinear (no plane)
+const COLLINEAR_TOLERANCE: f64 = 1e-12;
+
+/// Relative spread of face normals below which a patch is considered flat
+/// (no cylinder axis)
+const FLAT_NORMALS_TOLERANCE: f64 = 1e-6;
+
+/// Largest angle (degrees) between a face normal and the mean normal of a
+/// planar patch
+const PLANAR_NORMAL_SPREAD: f64 = 5.0;
+
+/// Largest fit deviation, relative to the patch size (planes) or radius
+/// (cylinders, spheres), for a patch to count as that shape
+const SHAPE_TOLERANCE: f64 = 0.02;
+
+/// Least-squares plane through a set of points
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlaneFit {
+    /// Centroid of the points (a point on the plane)
+    pub centroid: [f64; 3],
+
+    /// Unit plane normal
+    pub normal: [f64; 3],
+
+    /// Flatness deviation: largest distance of a point to the plane
+    pub max_deviation: f64,
+
+    /// Root mean square distance of the points to the plane
+    pub rms_deviation: f64,
+
+    /// Principal directions in the plane (unit vectors), longest extent
+    /// first
+    pub in_plane_axes: [[f64; 3]; 2],
+
+    /// Extents along `in_plane_axes`
+    pub extents: [f64; 2],
+}
+
+impl PlaneFit {
+    /// Fit a plane to `points`, or `None` if there are fewer than three or
+    /// they are collinear
+    pub fn fit(points: &[Point]) -> Option<Self> {
+        if points.len() < 3 {
+            return None;
+        }
+
+        let n = points.len() as f64;
+        let mean = points.iter().fold(Vec3::zeros(), |sum, p| sum + p.coords) / n;
+        let covariance = points.iter().fold(Matrix3::zeros(), |sum, p| {
+            let d = p.coords - mean;
+            sum + d * d.transpose()
+        }) / n;
+        let eigen = SymmetricEigen::new(covariance);
+
+        // Eigenvalues in ascending order: the smallest is along the normal
+        let mut order = [0, 1, 2];
+        order.sort_by(|&a, &b| eigen.eigenvalues[a].total_cmp(&eigen.eigenvalues[b]));
+        if eigen.eigenvalues[order[1]] <= COLLINEAR_TOLERANCE * eigen.eigenvalues[order[2]] {
+            return None;
+        }
+        let normal: Vec3 = eigen.eigenvectors.column(order[0]).normalize();
+
+        let distances: Vec<f64> = points
+            .iter()
+            .map(|p| (p.coords - mean).dot(&normal))
+            .collect();
+        let max_deviation = distances.iter().fold(0.0f64, |m, d| m.max(d.abs()));
+        let rms_deviation = (distances.iter().map(|d| d * d).sum::<f64>() / n).sqrt();
+
+        let mut plane = Self {
+            centroid: mean.into(),
+            normal: normal.into(),
+            max_deviation,
+            rms_deviation,
+            in_plane_axes: [
+                eigen.eigenvectors.column(order[2]).normalize().into(),
+                eigen.eigenvectors.column(order[1]).normalize().into(),
+            ],
+            extents: [0.0; 2],
+        };
+        plane.extents = plane.in_plane_extents(points);
+        Some(plane)
+    }
+
+    /// Extents of `points` projected onto the in-plane axes
+    pub fn in_plane_extents(&self, points: &[Point]) -> [f64; 2] {
+        self.in_plane_axes.map(|axis| {
+            let axis = Vec3::from(axis);
+            let (lo, hi) = points.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| {
+                let t = p.coords.dot(&axis);
+                (lo.min(t), hi.max(t))
+            });
+            (hi - lo).max(0.0)
+        })
+    }
+
+    /// Fit a plane to the face centroids of a surface's contact patch (its
+    /// paired faces), with the normal oriented like the patch's faces and
+    /// the extents spanning the faces' nodes
+    pub fn of_patch(
+        results: &ContactResults,
+        surface: &SurfaceMesh,
+        is_surface_a: bool,
+    ) -> Option<Self> {
+        let faces = results.paired_faces(is_surface_a);
+        let centroids: Vec<Point> = faces
+            .iter()
+            .filter_map(|&f| surface.face_centroids.get(f).copied())
+            .collect();
+        let mut plane = Self::fit(&centroids)?;
+
+        let face_normal_sum = faces
+            .iter()
+            .filter_map(|&f| surface.face_normals.get(f))
+            .fold(Vec3::zeros(), |sum, n| sum + n);
+        if Vec3::from(plane.normal).dot(&face_normal_sum) < 0.0 {
+            plane.normal = plane.normal.map(|c| -c);
+        }
+
+        let nodes: BTreeSet<usize> = faces
+            .iter()
+            .flat_map(|&f| surface.faces[f].corners().iter().copied())
+            .collect();
+        let node_points: Vec<Point> = nodes
+            .into_iter()
+            .filter_map(|n| surface.nodes.get(n).copied())
+            .collect();
+        if !node_points.is_empty() {
+            plane.extents = plane.in_plane_extents(&node_points);
+        }
+        Some(plane)
+    }
+}
+
+/// Least-squares cylinder through a set of points
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CylinderFit {
+    /// Point on the axis, level with the middle of the points
+    pub axis_point: [f64; 3],
+
+    /// Unit axis direction
+    pub axis: [f64; 3],
+
+    pub radius: f64,
+
+    /// Extent of the points along the axis
+    pub length: f64,
+
+    /// Largest difference between a point's distance to the axis and the
+    /// radius
+    pub max_residual: f64,
+
+    /// Root mean square of those differences
+    pub rms_residual: f64,
+}
+
+impl CylinderFit {
+    /// Fit a cylinder to `points` with surface `normals`, or `None` if there
+    /// are fewer than three points or the normals are all parallel (a flat
+    /// patch)
+    ///
+    /// The axis is the direction most perpendicular to the normals; the
+    /// cross section is a least-squares circle through the points projected
+    /// along it.
+    pub fn fit(points: &[Point], normals: &[Vec3]) -> Option<Self> {
+        if points.len() < 3 || normals.is_empty() {
+            return None;
+        }
+
+        let tensor = normals
+            .iter()
+            .fold(Matrix3::zeros(), |sum, n| sum + n * n.transpose());
+        let eigen = SymmetricEigen::new(tensor);
+        let mut order = [0, 1, 2];
+        order.sort_by(|&a, &b| eigen.eigenvalues[a].total_cmp(&eigen.eigenvalues[b]));
+        if eigen.eigenvalues[order[1]] <= FLAT_NORMALS_TOLERANCE * eigen.eigenvalues[order[2]] {
+            return None;
+        }
+        let axis: Vec3 = eigen.eigenvectors.column(order[0]).normalize();
+        let u: Vec3 = eigen.eigenvectors.column(order[1]).normalize();
+        let v = axis.cross(&u);
+
+        let n = points.len() as f64;
+        let mean = points.iter().fold(Vec3::zeros(), |sum, p| sum + p.coords) / n;
+        let planar: Vec<(f64, f64)> = points
+            .iter()
+            .map(|p| {
+                let d = p.coords - mean;
+                (d.dot(&u), d.dot(&v))
+            })
+            .collect();
+        let (cx, cy, radius) = fit_circle(&planar)?;
+        let center = mean + u * cx + v * cy;
+
+        let along: Vec<f64> = points
+            .iter()
+            .map(|p| (p.coords - center).dot(&axis))
+            .collect();
+        let (lo, hi) = along
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(lo, hi), &t| (lo.min(t), hi.max(t)));
+        let residuals: Vec<f64> = points
+            .iter()
+            .zip(&along)
+            .map(|(p, &t)| ((p.coords - center) - axis * t).norm() - radius)
+            .collect();
+        let (max_residual, rms_residual) = residual_stats(&residuals);
+
+        Some(Self {
+            axis_point: (center + axis * ((lo + hi) / 2.0)).into(),
+            axis: axis.into(),
+            radius,
+            length: hi - lo,
+            max_residual,
+            rms_residual,
+        })
+    }
+
+    /// Fit a cylinder to the face centroids and normals of a surface's
+    /// contact patch (its paired faces)
+    pub fn of_patch(
+        results: &ContactResults,
+        surface: &SurfaceMesh,
+        is_surface_a: bool,
+    ) -> Option<Self> {
+        let (centroids, normals) = patch_samples(results, surface, is_surface_a);
+        Self::fit(&centroids, &normals)
+    }
+}
+
+/// Least-squares sphere through a set of points
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SphereFit {
+    pub center: [f64; 3],
+
+    pub radius: f64,
+
+    /// Largest difference between a point's distance to the center and the
+    /// radius
+    pub max_residual: f64,
+
+    /// Root mean square of those differences
+    pub rms_residual: f64,
+}
+
+impl SphereFit {
+    /// Fit a sphere to `points` (algebraic least squares), or `None` if
+    /// there are fewer than four or they are coplanar
+    pub fn fit(points: &[Point]) -> Option<Self> {
+        if points.len() < 4 {
+            return None;
+        }
+
+        // |p - c|^2 = r^2  <=>  2 c.p + (r^2 - |c|^2) = |p|^2, in coordinates
+        // relative to the mean for conditioning
+        let n = points.len() as f64;
+        let mean = points.iter().fold(Vec3::zeros(), |sum, p| sum + p.coords) / n;
+        let mut normal_matrix = Matrix4::zeros();
+        let mut rhs = Vector4::zeros();
+        for p in points {
+            let d = p.coords - mean;
+            let row = Vector4::new(2.0 * d.x, 2.0 * d.y, 2.0 * d.z, 1.0);
+            normal_matrix += row * row.transpose();
+            rhs += row * d.norm_squared();
+        }
+        let scale = normal_matrix.abs().max();
+        let solution = normal_matrix.svd(true, true);
+        if solution.singular_values.min() <= COLLINEAR_TOLERANCE * scale {
+            return None;
+        }
+        let x = solution.solve(&rhs, 0.0).ok()?;
+
+        let offset = Vector3::new(x[0], x[1], x[2]);
+        let radius = (x[3] + offset.norm_squared()).max(0.0).sqrt();
+        let center = mean + offset;
+        let residuals: Vec<f64> = points
+            .iter()
+            .map(|p| (p.coords - center).norm() - radius)
+            .collect();
+        let (max_residual, rms_residual) = residual_stats(&residuals);
+
+        Some(Self {
+            center: center.into(),
+            radius,
+            max_residual,
+            rms_residual,
+        })
+    }
+
+    /// Fit a sphere to the face centroids of a surface's contact patch (its
+    /// paired faces)
+    pub fn of_patch(
+        results: &ContactResults,
+        surface: &SurfaceMesh,
+        is_surface_a: bool,
+    ) -> Option<Self> {
+        Self::fit(&patch_samples(results, surface, is_surface_a).0)
+    }
+}
+
+/// Shape of a contact interface, deciding how it is set up in a solver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InterfaceType {
+    /// Flat mating faces (flanges, gasket seats, stacked plates)
+    PlanarFlange,
+
+    /// Cylindrical faces (pins in bores, shafts in bearings)
+    CylindricalFit,
+
+    /// Spherical faces (ball joints, spherical seats)
+    Spherical,
+
+    /// Anything else, or patches too small to tell
+    #[default]
+    Irregular,
+}
+
+impl InterfaceType {
+    /// Name as written to metadata and manifests
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InterfaceType::PlanarFlange => "planar_flange",
+            InterfaceType::CylindricalFit => "cylindrical_fit",
+            InterfaceType::Spherical => "spherical",
+            InterfaceType::Irregular => "irregular",
+        }
+    }
+
+    /// Type of an interface from the fits of both of its sides: the shape
+    /// both sides agree on, otherwise irregular
+    pub fn of_pair(fits_a: &PatchFits, fits_b: &PatchFits) -> Self {
+        let (type_a, type_b) = (fits_a.classify(), fits_b.classify());
+        if type_a == type_b {
+            type_a
+        } else {
+            InterfaceType::Irregular
+        }
+    }
+}
+
+impl std::fmt::Display for InterfaceType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// All primitive fits of one contact patch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PatchFits {
+    pub plane: Option<PlaneFit>,
+    pub cylinder: Option<CylinderFit>,
+    pub sphere: Option<SphereFit>,
+
+    /// Largest angle (degrees) between a face normal and the patch's mean
+    /// normal
+    pub normal_spread: f64,
+}
+
+impl PatchFits {
+    /// Fit every primitive to a surface's contact patch (its paired faces)
+    pub fn of_patch(results: &ContactResults, surface: &SurfaceMesh, is_surface_a: bool) -> Self {
+        let (_, normals) = patch_samples(results, surface, is_surface_a);
+        let mean = normals.iter().fold(Vec3::zeros(), |sum, n| sum + n);
+        let normal_spread = if mean.norm() > 0.0 {
+            let mean = mean.normalize();
+            normals
+                .iter()
+                .map(|n| {
+                    n.normalize()
+                        .dot(&mean)
+                        .clamp(-1.0, 1.0)
+                        .acos()
+                        .to_degrees()
+                })
+                .fold(0.0, f64::max)
+        } else {
+            180.0
+        };
+
+        Self {
+            plane: PlaneFit::of_patch(results, surface, is_surface_a),
+            cylinder: CylinderFit::of_patch(results, surface, is_surface_a),
+            sphere: SphereFit::of_patch(results, surface, is_surface_a),
+            normal_spread,
+        }
+    }
+
+    /// Shape of the patch
+    ///
+    /// Planar if the normals stay within a few degrees of each other and the
+    /// centroids within 2% of the patch size of their plane (patches with
+    /// too few faces for a plane are judged by their normals alone);
+    /// otherwise the curved fit with the smallest relative residual, if it is
+    /// within 2% of its radius.
+    pub fn classify(&self) -> InterfaceType {
+        if self.normal_spread <= PLANAR_NORMAL_SPREAD {
+            let flat = self.plane.is_none_or(|plane| {
+                let size = plane.extents[0].hypot(plane.extents[1]);
+                plane.max_deviation <= SHAPE_TOLERANCE * size
+            });
+            if flat {
+                return InterfaceType::PlanarFlange;
+            }
+        }
+
+        let curved = [
+            self.cylinder
+                .map(|c| (InterfaceType::CylindricalFit, c.max_residual / c.radius)),
+            self.sphere
+                .map(|s| (InterfaceType::Spherical, s.max_residual / s.radius)),
+        ];
+        curved
+            .into_iter()
+            .flatten()
+            .filter(|(_, relative)| *relative <= SHAPE_TOLERANCE)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(InterfaceType::Irregular, |(shape, _)| shape)
+    }
+
+    /// Print the plane fit of the patch of surface `label`, and curved fits
+    /// that beat it
+    pub fn print(&self, label: &str, format: FloatFormat) {
+        if let Some(plane) = self.plane {
+            println!(
+                "  Flatness ({}):    {} (normal [{:.3}, {:.3}, {:.3}], {:.4} x {:.4} in plane)",
+                label,
+                format.show(plane.max_deviation),
+                plane.normal[0],
+                plane.normal[1],
+                plane.normal[2],
+                plane.extents[0],
+                plane.extents[1]
+            );
+        }
+
+        // Curved fits are only worth showing when they beat the plane
+        let plane_rms = self.plane.map_or(f64::MAX, |p| p.rms_deviation);
+        if let Some(cylinder) = self.cylinder.filter(|c| c.rms_residual < plane_rms) {
+            println!(
+                "  Cylinder ({}):    radius {}, axis [{:.3}, {:.3}, {:.3}] through ({:.4}, {:.4}, {:.4}), residual {}",
+                label,
+                format.show(cylinder.radius),
+                cylinder.axis[0],
+                cylinder.axis[1],
+                cylinder.axis[2],
+                cylinder.axis_point[0],
+                cylinder.axis_point[1],
+                cylinder.axis_point[2],
+                format.show(cylinder.max_residual)
+            );
+        }
+        if let Some(sphere) = self.sphere.filter(|s| s.rms_residual < plane_rms) {
+            println!(
+                "  Sphere ({}):      radius {}, center ({:.4}, {:.4}, {:.4}), residual {}",
+                label,
+                format.show(sphere.radius),
+                sphere.center[0],
+                sphere.center[1],
+                sphere.center[2],
+                sphere.max_residual
+            );
+        }
+    }
+}
+
+/// Face centroids and normals of the paired faces of a surface
+fn patch_samples(
+    results: &ContactResults,
+    surface: &SurfaceMesh,
+    is_surface_a: bool,
+) -> (Vec<Point>, Vec<Vec3>) {
+    results
+        .paired_faces(is_surface_a)
+        .into_iter()
+        .filter_map(|f| {
+            Some((
+                *surface.face_centroids.get(f)?,
+                *surface.face_normals.get(f)?,
+            ))
+        })
+        .unzip()
+}
+
+/// Least-squares circle (center x, center y, radius) through 2D points
+pub(crate) fn fit_circle(points: &[(f64, f64)]) -> Option<(f64, f64, f64)> {
+    // x^2 + y^2 = 2 a x + 2 b y + c, with r^2 = c + a^2 + b^2
+    let mut normal_matrix = Matrix3::zeros();
+    let mut rhs = Vector3::zeros();
+    for &(x, y) in points {
+        let row = Vector3::new(2.0 * x, 2.0 * y, 1.0);
+        normal_matrix += row * row.transpose();
+        rhs += row * (x * x + y * y);
+    }
+    let scale = normal_matrix.abs().max();
+    let solution = normal_matrix.svd(true, true);
+    if solution.singular_values.min() <= COLLINEAR_TOLERANCE * scale {
+        return None;
+    }
+    let x = solution.solve(&rhs, 0.0).ok()?;
+    Some((
+        x[0],
+        x[1],
+        (x[2] + x[0] * x[0] + x[1] * x[1]).max(0.0).sqrt(),
+    ))
+}
+
+/// Largest absolute value and root mean square of residuals
+fn residual_stats(residuals: &[f64]) -> (f64, f64) {
+    let max = residuals.iter().fold(0.0f64, |m, r| m.max(r.abs()));
+    let rms = (residuals.iter().map(|r| r * r).sum::<f64>() / residuals.len() as f64).sqrt();
+    (max, rms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plane_fit_of_wavy_patch() {
+        // 4 x 2 grid in a plane tilted about x, alternating +/- 0.01 off it
+        let (c, s) = (30f64.to_radians().cos(), 30f64.to_radians().sin());
+        let mut points = Vec::new();
+        for i in 0..=4 {
+            for j in 0..=2 {
+                let w = if (i + j) % 2 == 0 { 0.01 } else { -0.01 };
+                let (x, y) = (i as f64, j as f64);
+                points.push(Point::new(x, y * c - w * s, y * s + w * c));
+            }
+        }
+        let plane = PlaneFit::fit(&points).unwrap();
+
+        assert!((Vec3::from(plane.normal).dot(&Vec3::new(0.0, -s, c)).abs() - 1.0).abs() < 1e-9);
+        assert!((plane.max_deviation - 0.01).abs() < 1e-3);
+        assert!(plane.rms_deviation <= plane.max_deviation);
+        assert!((plane.extents[0] - 4.0).abs() < 1e-3);
+        assert!((plane.extents[1] - 2.0).abs() < 1e-2);
+
+        let line: Vec<Point> = (0..5).map(|i| Point::new(i as f64, 0.0, 0.0)).collect();
+        assert!(PlaneFit::fit(&line).is_none());
+    }
+
+    #[test]
+    fn test_cylinder_and_sphere_fits() {
+        // Quarter of a cylinder of radius 2 about an axis along (1, 1, 0)
+        // through (1, 0, 0)
+        let axis = Vec3::new(1.0, 1.0, 0.0).normalize();
+        let u = Vec3::z();
+        let v = axis.cross(&u);
+        let mut points = Vec::new();
+        let mut normals = Vec::new();
+        for i in 0..=6 {
+            for k in 0..=3 {
+                let theta = i as f64 * 15f64.to_radians();
+                let radial = u * theta.cos() + v * theta.sin();
+                points.push(Point::new(1.0, 0.0, 0.0) + axis * k as f64 + radial * 2.0);
+                normals.push(radial);
+            }
+        }
+        let cylinder = CylinderFit::fit(&points, &normals).unwrap();
+        assert!((Vec3::from(cylinder.axis).dot(&axis).abs() - 1.0).abs() < 1e-9);
+        assert!((cylinder.radius - 2.0).abs() < 1e-9);
+        assert!((cylinder.length - 3.0).abs() < 1e-9);
+        assert!(cylinder.max_residual < 1e-9);
+        let to_axis = Point::from(cylinder.axis_point) - Point::new(1.0, 0.0, 0.0);
+        assert!(to_axis.cross(&axis).norm() < 1e-9);
+
+        // Flat patch: no axis
+        assert!(CylinderFit::fit(&points, &vec![u; points.len()]).is_none());
+
+        // Cap of a sphere of radius 3 centered at (1, 2, 3)
+        let center = Point::new(1.0, 2.0, 3.0);
+        let cap: Vec<Point> = (0..5)
+            .flat_map(|i| (0..8).map(move |j| (i, j)))
+            .map(|(i, j)| {
+                let (polar, azimuth) = (0.1 + 0.1 * i as f64, j as f64 * 45f64.to_radians());
+                center
+                    + Vec3::new(
+                        polar.sin() * azimuth.cos(),
+                        polar.sin() * azimuth.sin(),
+                        polar.cos(),
+                    ) * 3.0
+            })
+            .collect();
+        let sphere = SphereFit::fit(&cap).unwrap();
+        assert!((Point::from(sphere.center) - center).norm() < 1e-6);
+        assert!((sphere.radius - 3.0).abs() < 1e-6);
+        assert!(sphere.max_residual < 1e-6);
+
+        // Coplanar points: no sphere
+        let flat: Vec<Point> = cap.iter().map(|p| Point::new(p.x, p.y, 0.0)).collect();
+        assert!(SphereFit::fit(&flat).is_none());
+    }
+
+    #[test]
+    fn test_classify_patches() {
+        // Half of a cylinder of radius 1 along z
+        let (mut points, mut normals) = (Vec::new(), Vec::new());
+        for i in 0..=8 {
+            for k in 0..=2 {
+                let theta = i as f64 * 22.5f64.to_radians();
+                let radial = Vec3::new(theta.cos(), theta.sin(), 0.0);
+                points.push(Point::from(radial + Vec3::z() * k as f64));
+                normals.push(radial);
+            }
+        }
+        let cylinder = PatchFits {
+            plane: PlaneFit::fit(&points),
+            cylinder: CylinderFit::fit(&points, &normals),
+            sphere: SphereFit::fit(&points),
+            normal_spread: 90.0,
+        };
+        assert_eq!(cylinder.classify(), InterfaceType::CylindricalFit);
+
+        let flat: Vec<Point> = points.iter().map(|p| Point::new(p.x, p.y, 0.0)).collect();
+        let planar = PatchFits {
+            plane: PlaneFit::fit(&flat),
+            cylinder: None,
+            sphere: None,
+            normal_spread: 0.0,
+        };
+        assert_eq!(planar.classify(), InterfaceType::PlanarFlange);
+
+        // Sides that disagree make an irregular interface
+        assert_eq!(
+            InterfaceType::of_pair(&planar, &planar),
+            InterfaceType::PlanarFlange
+        );
+        assert_eq!(
+            InterfaceType::of_pair(&planar, &cylinder),
+            InterfaceType::Irregular
+        );
+    }
+}
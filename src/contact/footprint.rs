@@ -0,0 +1,449 @@
+//! Contact patch dimensions and bolted-joint pressure cone footprints
+//!
+//! The paired faces of a surface split into contact patches, groups of faces
+//! connected through shared edges. Each patch is described by its area and
+//! its principal dimensions: the extents of its nodes along the principal
+//! axes of a plane fit. Circular inner outlines of a patch are taken as bolt
+//! holes.
+//!
+//! In a bolted joint the clamping force spreads from under the bolt head
+//! through the clamped part in a cone (VDI 2230). At the interface it loads a
+//! disk around the hole whose diameter is the head bearing diameter plus
+//! `2 * clamp_length * tan(cone_angle)`. A patch narrower than that footprint
+//! does not carry the clamping load the way a hand calculation assumes, and
+//! is flagged.
+
+use crate::contact::fitting::{fit_circle, PlaneFit};
+use crate::contact::types::ContactResults;
+use crate::error::{ContactDetectorError, Result};
+use crate::format::{FloatFormat, Glyphs};
+use crate::mesh::edges::boundary_loops;
+use crate::mesh::types::{Point, SurfaceMesh, Vec3};
+use crate::mesh::union_find::UnionFind;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+/// Largest distance of an outline node from its fitted circle, relative to
+/// the radius, for the outline to count as a hole (loose enough for holes
+/// meshed with 8 edges)
+const HOLE_ROUNDNESS: f64 = 0.1;
+
+/// Pressure cone of the bolts clamping an interface
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PressureCone {
+    /// Half angle of the cone in degrees (about 30 for steel)
+    pub cone_angle: f64,
+
+    /// Bearing diameter of the bolt head or washer relative to the hole
+    /// diameter
+    pub head_ratio: f64,
+
+    /// Clamped thickness between the bolt head and the interface; the hole
+    /// diameter if not given
+    pub clamp_length: Option<f64>,
+}
+
+impl Default for PressureCone {
+    fn default() -> Self {
+        Self {
+            cone_angle: 30.0,
+            head_ratio: 1.5,
+            clamp_length: None,
+        }
+    }
+}
+
+impl PressureCone {
+    /// Diameter of the footprint of the cone around a hole of the given
+    /// diameter
+    pub fn footprint_diameter(&self, hole_diameter: f64) -> f64 {
+        let clamp_length = self.clamp_length.unwrap_or(hole_diameter);
+        self.head_ratio * hole_diameter + 2.0 * clamp_length * self.cone_angle.to_radians().tan()
+    }
+}
+
+/// Circular inner outline of a contact patch
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoltHole {
+    pub center: [f64; 3],
+
+    pub diameter: f64,
+}
+
+/// Edge-connected group of paired faces of one surface
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContactPatch {
+    /// Faces of the patch, ascending
+    #[serde(skip)]
+    pub faces: Vec<usize>,
+
+    /// Number of faces
+    pub num_faces: usize,
+
+    /// Total face area
+    pub area: f64,
+
+    /// Principal directions in the patch plane (unit vectors), longest
+    /// extent first
+    pub principal_axes: [[f64; 3]; 2],
+
+    /// Extents of the patch along `principal_axes`
+    pub principal_lengths: [f64; 2],
+
+    /// Bolt holes through the patch
+    pub holes: Vec<BoltHole>,
+}
+
+impl ContactPatch {
+    /// Split the paired faces of a surface into patches, ordered by their
+    /// smallest face
+    pub fn of_surface(
+        results: &ContactResults,
+        surface: &SurfaceMesh,
+        is_surface_a: bool,
+    ) -> Vec<Self> {
+        let faces = results.paired_faces(is_surface_a);
+
+        // Faces sharing an edge belong to the same patch
+        let mut components = UnionFind::new(faces.len());
+        let edges = faces
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &face)| {
+                let corners = surface.faces[face].corners();
+                (0..corners.len()).map(move |k| {
+                    let (a, b) = (corners[k], corners[(k + 1) % corners.len()]);
+                    ((a.min(b), a.max(b)), i)
+                })
+            })
+            .collect();
+        components.union_by_key(edges);
+
+        components
+            .components()
+            .into_iter()
+            .map(|members| {
+                let patch_faces: Vec<usize> = members.into_iter().map(|i| faces[i]).collect();
+                Self::of_faces(surface, patch_faces)
+            })
+            .collect()
+    }
+
+    /// Dimensions and holes of a patch made of the given faces
+    fn of_faces(surface: &SurfaceMesh, faces: Vec<usize>) -> Self {
+        let area = faces.iter().map(|&f| surface.face_areas[f]).sum();
+        let nodes: BTreeSet<usize> = faces
+            .iter()
+            .flat_map(|&f| surface.faces[f].corners().iter().copied())
+            .collect();
+        let points: Vec<Point> = nodes.into_iter().map(|n| surface.nodes[n]).collect();
+
+        let mut patch = Self {
+            num_faces: faces.len(),
+            faces,
+            area,
+            principal_axes: [[0.0; 3]; 2],
+            principal_lengths: [0.0; 2],
+            holes: Vec::new(),
+        };
+        let Some(plane) = PlaneFit::fit(&points) else {
+            return patch;
+        };
+        patch.principal_axes = plane.in_plane_axes;
+        patch.principal_lengths = plane.extents;
+
+        // The longest outline is the outer one; round inner ones are holes
+        let [u, v] = plane.in_plane_axes.map(Vec3::from);
+        let origin = Vec3::from(plane.centroid);
+        let loops = boundary_loops(&surface.subset(&patch.faces));
+        patch.holes = loops
+            .iter()
+            .skip(1)
+            .filter(|outline| outline.closed && outline.nodes.len() >= 3)
+            .filter_map(|outline| {
+                let planar: Vec<(f64, f64)> = outline
+                    .nodes
+                    .iter()
+                    .map(|&n| {
+                        let d = surface.nodes[n].coords - origin;
+                        (d.dot(&u), d.dot(&v))
+                    })
+                    .collect();
+                let (cx, cy, radius) = fit_circle(&planar)?;
+                let is_round = planar.iter().all(|&(x, y)| {
+                    ((x - cx).hypot(y - cy) - radius).abs() <= HOLE_ROUNDNESS * radius
+                });
+                is_round.then(|| BoltHole {
+                    center: (origin + u * cx + v * cy).into(),
+                    diameter: 2.0 * radius,
+                })
+            })
+            .collect();
+        patch
+    }
+
+    /// Smaller principal length, the width of the patch
+    pub fn width(&self) -> f64 {
+        self.principal_lengths[1]
+    }
+}
+
+/// Pressure cone footprint of one bolt hole against its patch
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HoleFootprint {
+    /// Index of the patch in [`InterfaceFootprints::patches`]
+    pub patch: usize,
+
+    pub hole: BoltHole,
+
+    /// Expected footprint diameter of the pressure cone
+    pub footprint_diameter: f64,
+
+    /// Width of the patch (its smaller principal length)
+    pub patch_width: f64,
+
+    /// Whether the patch is narrower than the footprint
+    pub undersized: bool,
+}
+
+/// Contact patches of one interface and the footprints of its bolt holes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceFootprints {
+    /// Surface the patches are taken from (surface A)
+    pub surface: String,
+
+    /// Other surface of the interface
+    pub partner: String,
+
+    pub patches: Vec<ContactPatch>,
+
+    pub holes: Vec<HoleFootprint>,
+}
+
+impl InterfaceFootprints {
+    /// Patches of surface A of a detected pair, with each bolt hole checked
+    /// against the footprint of `cone`
+    #[tracing::instrument(level = "debug", skip_all, fields(surface = %surface_a.part_name))]
+    pub fn compute(
+        results: &ContactResults,
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+        cone: &PressureCone,
+    ) -> Self {
+        let patches = ContactPatch::of_surface(results, surface_a, true);
+        let holes = patches
+            .iter()
+            .enumerate()
+            .flat_map(|(index, patch)| {
+                patch.holes.iter().map(move |&hole| {
+                    let footprint_diameter = cone.footprint_diameter(hole.diameter);
+                    HoleFootprint {
+                        patch: index,
+                        hole,
+                        footprint_diameter,
+                        patch_width: patch.width(),
+                        undersized: patch.width() < footprint_diameter,
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            surface: surface_a.part_name.clone(),
+            partner: surface_b.part_name.clone(),
+            patches,
+            holes,
+        }
+    }
+
+    /// Bolt holes whose patch is narrower than the footprint
+    pub fn undersized(&self) -> impl Iterator<Item = &HoleFootprint> {
+        self.holes.iter().filter(|h| h.undersized)
+    }
+}
+
+/// Pressure cone footprint check over all detected interfaces
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FootprintReport {
+    pub cone: PressureCone,
+
+    /// One entry per detected pair
+    pub interfaces: Vec<InterfaceFootprints>,
+}
+
+impl FootprintReport {
+    /// Create an empty report for the given cone
+    pub fn new(cone: PressureCone) -> Self {
+        Self {
+            cone,
+            interfaces: Vec::new(),
+        }
+    }
+
+    /// Print the patch dimensions and the undersized patches to the console
+    pub fn print_summary(&self, format: FloatFormat, glyphs: Glyphs) {
+        println!("\n{}", "=".repeat(60));
+        println!("CONTACT PATCHES AND BOLT FOOTPRINTS");
+        println!("{}", "=".repeat(60));
+        println!();
+        println!(
+            "  Pressure cone: {:.1}{} half angle, head {:.2}x hole diameter, clamp length {}",
+            self.cone.cone_angle,
+            glyphs.degrees(),
+            self.cone.head_ratio,
+            self.cone.clamp_length.map_or_else(
+                || "= hole diameter".to_string(),
+                |l| format.show(l).to_string()
+            )
+        );
+
+        for interface in &self.interfaces {
+            println!();
+            println!(
+                "  {} {} {}: {} patch(es), {} bolt hole(s)",
+                interface.surface,
+                glyphs.between(),
+                interface.partner,
+                interface.patches.len(),
+                interface.holes.len()
+            );
+            for (index, patch) in interface.patches.iter().enumerate() {
+                println!(
+                    "    Patch {}: {} x {}, area {}, {} hole(s)",
+                    index,
+                    format.show(patch.principal_lengths[0]),
+                    format.show(patch.principal_lengths[1]),
+                    format.show(patch.area),
+                    patch.holes.len()
+                );
+            }
+            for footprint in interface.undersized() {
+                let [x, y, z] = footprint.hole.center;
+                println!(
+                    "    WARNING: patch {} is {} wide, less than the {} footprint of \
+                     the hole of diameter {} at ({:.4}, {:.4}, {:.4})",
+                    footprint.patch,
+                    format.show(footprint.patch_width),
+                    format.show(footprint.footprint_diameter),
+                    format.show(footprint.hole.diameter),
+                    x,
+                    y,
+                    z
+                );
+            }
+        }
+        println!();
+        println!("{}", "=".repeat(60));
+    }
+
+    /// Export the report to a JSON file
+    pub fn export<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            ContactDetectorError::ConfigError(format!(
+                "Failed to serialize footprint report: {}",
+                e
+            ))
+        })?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::{ContactCriteria, ContactPair};
+    use crate::mesh::types::QuadFace;
+
+    /// Flat `n x n` grid of unit faces at z = 0
+    fn grid(n: usize) -> SurfaceMesh {
+        let mut surface = SurfaceMesh::new("Flange".to_string());
+        for j in 0..=n {
+            for i in 0..=n {
+                surface.nodes.push(Point::new(i as f64, j as f64, 0.0));
+            }
+        }
+        for j in 0..n {
+            for i in 0..n {
+                let a = j * (n + 1) + i;
+                surface
+                    .faces
+                    .push(QuadFace::new([a, a + 1, a + n + 2, a + n + 1]));
+                surface.face_normals.push(Vec3::new(0.0, 0.0, 1.0));
+                surface
+                    .face_centroids
+                    .push(Point::new(i as f64 + 0.5, j as f64 + 0.5, 0.0));
+                surface.face_areas.push(1.0);
+            }
+        }
+        surface
+    }
+
+    fn paired(faces: impl Iterator<Item = usize>) -> ContactResults {
+        let mut results =
+            ContactResults::new("A".to_string(), "B".to_string(), ContactCriteria::default());
+        for face in faces {
+            results.pairs.push(ContactPair {
+                surface_a_face_id: face,
+                surface_b_face_id: 0,
+                distance: 0.0,
+                normal_angle: 180.0,
+                contact_point: Point::origin(),
+            });
+        }
+        results
+    }
+
+    #[test]
+    fn test_patches_and_footprints() {
+        // 9 x 3 strip with the middle face of each 3 x 3 block left out as a
+        // (square, 1 wide) hole, plus a separate 2 x 1 patch
+        let surface = grid(9);
+        let strip = (0..3).flat_map(|j| (0..9).map(move |i| j * 9 + i));
+        let holes = [10, 13, 16];
+        let results = paired(strip.filter(|face| !holes.contains(face)).chain([72, 73]));
+
+        let patches = ContactPatch::of_surface(&results, &surface, true);
+        assert_eq!(patches.len(), 2);
+        assert_eq!(patches[0].num_faces, 24);
+        assert_eq!(patches[0].area, 24.0);
+        let [length, width] = patches[0].principal_lengths;
+        assert!((length - 9.0).abs() < 1e-9 && (width - 3.0).abs() < 1e-9);
+        assert!(patches[0].principal_axes[0][0].abs() > 0.999);
+        assert_eq!(patches[0].holes.len(), 3);
+        let mut centers: Vec<f64> = patches[0].holes.iter().map(|h| h.center[0]).collect();
+        centers.sort_by(f64::total_cmp);
+        assert!((centers[0] - 1.5).abs() < 1e-9 && (centers[2] - 7.5).abs() < 1e-9);
+        assert!(patches[1].holes.is_empty());
+        assert_eq!(patches[1].principal_lengths, [2.0, 1.0]);
+
+        // The corners of a square hole lie on a circle of diameter sqrt(2);
+        // by default its footprint is 1.5 d + 2 d tan 30°
+        let d = patches[0].holes[0].diameter;
+        assert!((d - 2f64.sqrt()).abs() < 1e-9);
+        let expected = 1.5 * d + 2.0 * d * 30f64.to_radians().tan();
+        assert!((PressureCone::default().footprint_diameter(d) - expected).abs() < 1e-12);
+
+        let cone = PressureCone {
+            clamp_length: Some(0.5),
+            ..PressureCone::default()
+        };
+
+        let other = grid(1);
+        let report = InterfaceFootprints::compute(&results, &surface, &other, &cone);
+        assert_eq!(report.holes.len(), 3);
+        assert_eq!(report.undersized().count(), 0);
+
+        // A long clamp spreads the cone beyond the 3 wide strip
+        let deep = PressureCone {
+            clamp_length: Some(2.0),
+            ..cone
+        };
+        let report = InterfaceFootprints::compute(&results, &surface, &other, &deep);
+        assert_eq!(report.undersized().count(), 3);
+        assert!(report
+            .undersized()
+            .all(|h| h.patch == 0 && h.patch_width == width));
+    }
+}
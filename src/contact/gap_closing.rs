@@ -0,0 +1,173 @@
+//! Rigid motion of part B that closes the measured gaps
+//!
+//! Every face pair measures a signed gap `d` along the normal `n` of its face
+//! on A. Moving B by a small rigid motion — a translation `t` and a rotation
+//! `ω` (radians) about a center `c` — changes that gap to
+//! `d + n · (t + ω × (p - c))` at the contact point `p`. The motion that
+//! minimizes the area-weighted squared gaps over all pairs is a 6 x 6 linear
+//! least-squares problem; it says how far a part has to be shifted (and
+//! tilted) to sit flush, e.g. to correct a CAD positioning error.
+//!
+//! Directions the pairs do not constrain (sliding along a flat interface,
+//! spinning about a pin's axis) get no motion; the number of constrained
+//! degrees of freedom is reported alongside.
+
+use crate::contact::types::ContactResults;
+use crate::mesh::types::{SurfaceMesh, Vec3};
+use nalgebra::{Matrix6, Vector6};
+use serde::{Deserialize, Serialize};
+
+/// Singular values below this fraction of the largest one are treated as
+/// unconstrained directions
+const RANK_TOLERANCE: f64 = 1e-9;
+
+/// Least-squares rigid motion of surface B that closes the gaps to surface A
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GapClosingTransform {
+    /// Translation of B
+    pub translation: [f64; 3],
+
+    /// Small rotation of B about `center` (rotation vector, radians)
+    pub rotation: [f64; 3],
+
+    /// Center of rotation: the area-weighted mean of the contact points
+    pub center: [f64; 3],
+
+    /// Area-weighted root mean square gap of the face pairs as detected
+    pub rms_gap_before: f64,
+
+    /// Area-weighted root mean square gap left after the motion (to first
+    /// order)
+    pub rms_gap_after: f64,
+
+    /// Number of independent directions (of 6) the pairs constrain
+    pub constrained_dofs: usize,
+}
+
+impl GapClosingTransform {
+    /// Fit the motion to the face pairs of `results`, weighted by the areas
+    /// of their faces on `surface_a`, or `None` if there are no pairs
+    pub fn fit(results: &ContactResults, surface_a: &SurfaceMesh) -> Option<Self> {
+        let rows: Vec<(Vec3, Vec3, f64, f64)> = results
+            .pairs
+            .iter()
+            .filter_map(|pair| {
+                let face = pair.surface_a_face_id;
+                let normal = surface_a.face_normals.get(face)?;
+                let area = surface_a.face_areas.get(face)?;
+                Some((*normal, pair.contact_point.coords, pair.distance, *area))
+            })
+            .collect();
+        let total_area: f64 = rows.iter().map(|row| row.3).sum();
+        if rows.is_empty() || total_area <= 0.0 {
+            return None;
+        }
+
+        let center = rows
+            .iter()
+            .fold(Vec3::zeros(), |sum, row| sum + row.1 * row.3)
+            / total_area;
+
+        // Rotation columns scaled by the patch radius, so that all six
+        // unknowns are lengths
+        let radius = (rows
+            .iter()
+            .map(|row| (row.1 - center).norm_squared() * row.3)
+            .sum::<f64>()
+            / total_area)
+            .sqrt();
+        let scale = if radius > 0.0 { radius } else { 1.0 };
+
+        let jacobian = |normal: &Vec3, point: &Vec3| {
+            let moment = (point - center).cross(normal) / scale;
+            Vector6::new(normal.x, normal.y, normal.z, moment.x, moment.y, moment.z)
+        };
+        let mut normal_matrix = Matrix6::zeros();
+        let mut rhs = Vector6::zeros();
+        for (normal, point, distance, area) in &rows {
+            let row = jacobian(normal, point);
+            normal_matrix += row * row.transpose() * *area;
+            rhs -= row * (*distance * *area);
+        }
+
+        let solution = normal_matrix.svd(true, true);
+        let tolerance = RANK_TOLERANCE * solution.singular_values.max();
+        let constrained_dofs = solution
+            .singular_values
+            .iter()
+            .filter(|&&s| s > tolerance)
+            .count();
+        let x = solution.solve(&rhs, tolerance).ok()?;
+
+        let rms = |residuals: Vec<f64>| {
+            (residuals
+                .iter()
+                .zip(&rows)
+                .map(|(residual, row)| residual * residual * row.3)
+                .sum::<f64>()
+                / total_area)
+                .sqrt()
+        };
+        let rms_gap_before = rms(rows.iter().map(|row| row.2).collect());
+        let rms_gap_after = rms(rows
+            .iter()
+            .map(|row| row.2 + jacobian(&row.0, &row.1).dot(&x))
+            .collect());
+
+        Some(Self {
+            translation: [x[0], x[1], x[2]],
+            rotation: [x[3] / scale, x[4] / scale, x[5] / scale],
+            center: center.into(),
+            rms_gap_before,
+            rms_gap_after,
+            constrained_dofs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::{ContactCriteria, ContactPair};
+    use crate::mesh::types::{Point, QuadFace};
+
+    #[test]
+    fn test_gap_closing_transform() {
+        // A 5 x 5 grid of unit faces facing +z; B hovers 0.01 above, tilted
+        // by 0.002 rad about y (gap grows with x)
+        let mut surface = SurfaceMesh::new("A".to_string());
+        let mut results =
+            ContactResults::new("A".to_string(), "B".to_string(), ContactCriteria::default());
+        for i in 0..25 {
+            let (x, y) = ((i % 5) as f64 - 2.0, (i / 5) as f64 - 2.0);
+            surface.faces.push(QuadFace::new([0, 0, 0, 0]));
+            surface.face_normals.push(Vec3::z());
+            surface.face_centroids.push(Point::new(x, y, 0.0));
+            surface.face_areas.push(1.0);
+            let distance = 0.01 + 0.002 * x;
+            results.pairs.push(ContactPair {
+                surface_a_face_id: i,
+                surface_b_face_id: i,
+                distance,
+                normal_angle: 180.0,
+                contact_point: Point::new(x, y, distance),
+            });
+        }
+
+        let transform = GapClosingTransform::fit(&results, &surface).unwrap();
+        assert_eq!(transform.constrained_dofs, 3);
+        assert!((transform.translation[2] + 0.01).abs() < 1e-9);
+        assert!(transform.translation[0].abs() < 1e-9);
+        assert!(transform.translation[1].abs() < 1e-9);
+
+        // Rotating about +y lowers points at positive x
+        assert!((transform.rotation[1] - 0.002).abs() < 1e-6);
+        assert!(transform.rotation[0].abs() < 1e-9);
+        assert!(transform.rotation[2].abs() < 1e-9);
+        assert!(transform.rms_gap_before > 0.01);
+        assert!(transform.rms_gap_after < 1e-4);
+
+        results.pairs.clear();
+        assert!(GapClosingTransform::fit(&results, &surface).is_none());
+    }
+}
@@ -0,0 +1,251 @@
+//! Contact interfaces: a detected surface pair with everything derived from it
+//!
+//! Auto-contact and batch analysis find surface pairs in contact, and each
+//! pair then gets metrics, sideset names for the Exodus export and the
+//! manifest, and files of its own (VTU results, decimated copies). A
+//! [`ContactInterface`] keeps all of these together, so exporters take the
+//! interface instead of matching up lists of names, indices and results by
+//! position.
+
+use crate::contact::fitting::{InterfaceType, PatchFits};
+use crate::contact::metrics::PairMetrics;
+use crate::contact::types::ContactResults;
+use crate::mesh::types::SurfaceMesh;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Role of a surface in its interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SurfaceRole {
+    Master,
+    Slave,
+}
+
+/// One side of a contact interface
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceSide {
+    /// Name of the surface (patch, block, side set or group)
+    pub part: String,
+
+    /// Index of the surface in the surfaces the interface was detected on
+    pub surface: usize,
+
+    /// Role in the interface
+    pub role: SurfaceRole,
+
+    /// Sideset exported for this side, once named
+    pub sideset: Option<String>,
+}
+
+impl InterfaceSide {
+    /// Master side, surface A of the detection
+    pub fn master(part: String, surface: usize) -> Self {
+        Self {
+            part,
+            surface,
+            role: SurfaceRole::Master,
+            sideset: None,
+        }
+    }
+
+    /// Slave side, surface B of the detection
+    pub fn slave(part: String, surface: usize) -> Self {
+        Self {
+            part,
+            surface,
+            role: SurfaceRole::Slave,
+            sideset: None,
+        }
+    }
+}
+
+/// A surface pair checked for contact, with its results, metrics, sidesets
+/// and files
+#[derive(Debug, Clone)]
+pub struct ContactInterface {
+    /// 1-based position among the interfaces of the run, used in file and
+    /// sideset names (0 until the run has ordered its interfaces)
+    pub id: usize,
+
+    /// Surface A (the master)
+    pub side_a: InterfaceSide,
+
+    /// Surface B (the slave)
+    pub side_b: InterfaceSide,
+
+    /// Face pairs between the two surfaces
+    pub results: ContactResults,
+
+    /// Metrics of both sides
+    pub metrics: PairMetrics,
+
+    /// Output files of this interface alone, written by the run or kept from
+    /// an earlier one
+    pub artifacts: Vec<PathBuf>,
+}
+
+impl ContactInterface {
+    pub fn new(
+        id: usize,
+        side_a: InterfaceSide,
+        side_b: InterfaceSide,
+        results: ContactResults,
+        metrics: PairMetrics,
+    ) -> Self {
+        Self {
+            id,
+            side_a,
+            side_b,
+            results,
+            metrics,
+            artifacts: Vec::new(),
+        }
+    }
+
+    /// Surface A (`true`) or surface B of `surfaces`, the surfaces the
+    /// interface was detected on
+    pub fn surface<'s>(&self, surfaces: &'s [SurfaceMesh], is_surface_a: bool) -> &'s SurfaceMesh {
+        &surfaces[self.side(is_surface_a).surface]
+    }
+
+    /// Side A (`true`) or side B
+    pub fn side(&self, is_surface_a: bool) -> &InterfaceSide {
+        if is_surface_a {
+            &self.side_a
+        } else {
+            &self.side_b
+        }
+    }
+
+    /// Both sides, A first
+    pub fn sides(&self) -> [&InterfaceSide; 2] {
+        [&self.side_a, &self.side_b]
+    }
+
+    /// Whether the interface connects the surfaces named `part_a` and
+    /// `part_b`, in either order
+    pub fn connects(&self, part_a: &str, part_b: &str) -> bool {
+        let parts = (self.side_a.part.as_str(), self.side_b.part.as_str());
+        parts == (part_a, part_b) || parts == (part_b, part_a)
+    }
+
+    /// Metrics with those of the side named `part` first (`None` if neither
+    /// side has that name)
+    pub fn metrics_from(&self, part: &str) -> Option<PairMetrics> {
+        if self.side_a.part == part {
+            Some(self.metrics.clone())
+        } else if self.side_b.part == part {
+            Some(self.metrics.reversed())
+        } else {
+            None
+        }
+    }
+
+    /// Sideset names of side A and side B, once both are named
+    pub fn sidesets(&self) -> Option<(&str, &str)> {
+        Some((
+            self.side_a.sideset.as_deref()?,
+            self.side_b.sideset.as_deref()?,
+        ))
+    }
+
+    /// Faces of side A (`true`) or side B to export as its sideset: the
+    /// paired faces only, or the whole surface
+    pub fn sideset_faces(
+        &self,
+        surfaces: &[SurfaceMesh],
+        is_surface_a: bool,
+        paired_only: bool,
+    ) -> Vec<usize> {
+        if paired_only {
+            self.results.paired_faces(is_surface_a)
+        } else {
+            (0..self.surface(surfaces, is_surface_a).num_faces()).collect()
+        }
+    }
+
+    /// Primitive fits of the contact patches of side A and side B
+    pub fn patch_fits(&self, surfaces: &[SurfaceMesh]) -> [PatchFits; 2] {
+        [true, false]
+            .map(|is_a| PatchFits::of_patch(&self.results, self.surface(surfaces, is_a), is_a))
+    }
+
+    /// Shape of the interface, from the fits of both patches
+    pub fn interface_type(&self, surfaces: &[SurfaceMesh]) -> InterfaceType {
+        let [fits_a, fits_b] = self.patch_fits(surfaces);
+        InterfaceType::of_pair(&fits_a, &fits_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::{ContactCriteria, ContactPair};
+    use crate::mesh::types::{Point, QuadFace, Vec3};
+
+    fn square(name: &str, z: f64) -> SurfaceMesh {
+        let mut surface = SurfaceMesh::new(name.to_string());
+        surface.nodes = vec![
+            Point::new(0.0, 0.0, z),
+            Point::new(1.0, 0.0, z),
+            Point::new(1.0, 1.0, z),
+            Point::new(0.0, 1.0, z),
+        ];
+        surface.faces.push(QuadFace::new([0, 1, 2, 3]));
+        surface.face_centroids.push(Point::new(0.5, 0.5, z));
+        surface.face_normals.push(Vec3::z());
+        surface.face_areas.push(1.0);
+        surface
+    }
+
+    #[test]
+    fn test_contact_interface() {
+        let surfaces = vec![square("Lower", 0.0), square("Upper", 0.01)];
+        let mut results =
+            ContactResults::new("Lower".into(), "Upper".into(), ContactCriteria::default());
+        results.pairs.push(ContactPair {
+            surface_a_face_id: 0,
+            surface_b_face_id: 0,
+            distance: 0.01,
+            normal_angle: 180.0,
+            contact_point: Point::new(0.5, 0.5, 0.01),
+        });
+        let metrics = PairMetrics::compute(&results, &surfaces[0], &surfaces[1]);
+
+        let mut interface = ContactInterface::new(
+            1,
+            InterfaceSide::master("Lower".into(), 0),
+            InterfaceSide::slave("Upper".into(), 1),
+            results,
+            metrics,
+        );
+        assert_eq!(interface.surface(&surfaces, false).part_name, "Upper");
+        assert_eq!(interface.side(true).role, SurfaceRole::Master);
+        assert!(interface.connects("Upper", "Lower"));
+        assert!(!interface.connects("Lower", "Other"));
+        assert_eq!(
+            interface
+                .metrics_from("Upper")
+                .unwrap()
+                .surface_a
+                .total_area,
+            interface.metrics.surface_b.total_area
+        );
+        assert!(interface.metrics_from("Other").is_none());
+        assert_eq!(interface.sideset_faces(&surfaces, false, true), [0]);
+        assert_eq!(
+            interface.interface_type(&surfaces),
+            InterfaceType::PlanarFlange
+        );
+
+        // Sidesets count once both sides are named
+        interface.side_a.sideset = Some("contact_1_master".into());
+        assert_eq!(interface.sidesets(), None);
+        interface.side_b.sideset = Some("contact_1_slave".into());
+        assert_eq!(
+            interface.sidesets(),
+            Some(("contact_1_master", "contact_1_slave"))
+        );
+    }
+}
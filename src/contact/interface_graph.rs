@@ -0,0 +1,445 @@
+//! Which parts touch which
+//!
+//! The interface graph has the parts (element blocks) of an assembly as
+//! nodes and their detected interfaces as edges, each edge summing the
+//! contact pairs between two parts: their paired area and smallest gap. It
+//! is written as GraphViz dot for a one-page connectivity overview (`dot
+//! -Tsvg interface_graph.dot`) and as a JSON adjacency list for scripts.
+//! Parts without any interface appear as isolated nodes, and
+//! [`InterfaceGraph::check_connectivity`] reports them along with groups of
+//! parts that touch nothing outside their group.
+
+use crate::contact::metrics::PairMetrics;
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::types::SurfaceMesh;
+use crate::mesh::union_find::UnionFind;
+use crate::warnings::{WarningKind, Warnings};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Contact between two parts, over all their contact pairs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceEdge {
+    /// Parts (element blocks), in name order; the same part twice for a
+    /// part touching itself
+    pub part_a: String,
+    pub part_b: String,
+
+    /// Number of contact pairs between the parts
+    pub contact_pairs: usize,
+
+    /// Paired area, measured on the first surface of each pair
+    pub paired_area: f64,
+
+    /// Smallest paired gap (negative for a penetration)
+    pub min_gap: f64,
+}
+
+/// Neighbour of a part in the adjacency list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceNeighbor {
+    pub part: String,
+    pub contact_pairs: usize,
+    pub paired_area: f64,
+    pub min_gap: f64,
+}
+
+/// Parts as nodes and their interfaces as edges
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct InterfaceGraph {
+    /// All parts, sorted by name
+    pub parts: Vec<String>,
+
+    /// Interfaces, sorted by their parts
+    pub edges: Vec<InterfaceEdge>,
+}
+
+impl InterfaceGraph {
+    /// Graph of the given parts without interfaces
+    pub fn new<S: AsRef<str>>(parts: impl IntoIterator<Item = S>) -> Self {
+        let parts: BTreeSet<String> = parts.into_iter().map(|p| p.as_ref().to_string()).collect();
+        Self {
+            parts: parts.into_iter().collect(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Add a detected contact pair to the interface between the parts of
+    /// its surfaces
+    pub fn add_pair(
+        &mut self,
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+        metrics: &PairMetrics,
+    ) {
+        self.add_interface(
+            surface_a.block_name(),
+            surface_b.block_name(),
+            1,
+            metrics.surface_a.paired_area,
+            metrics.surface_a.min_distance,
+        );
+    }
+
+    /// Graph of groups of parts, e.g. the assemblies of a mesh (see
+    /// [`Mesh::block_assemblies`](crate::mesh::Mesh::block_assemblies))
+    ///
+    /// Each part counts towards every group it is in and stays a node of its
+    /// own if it is in none. Interfaces inside a group become self-edges.
+    pub fn roll_up(&self, groups: &HashMap<String, Vec<String>>) -> InterfaceGraph {
+        let groups_of = |part: &String| match groups.get(part) {
+            Some(names) if !names.is_empty() => names.clone(),
+            _ => vec![part.clone()],
+        };
+        let mut rolled = InterfaceGraph::new(self.parts.iter().flat_map(groups_of));
+        for edge in &self.edges {
+            for group_a in groups_of(&edge.part_a) {
+                for group_b in groups_of(&edge.part_b) {
+                    rolled.add_interface(
+                        &group_a,
+                        &group_b,
+                        edge.contact_pairs,
+                        edge.paired_area,
+                        edge.min_gap,
+                    );
+                }
+            }
+        }
+        rolled
+    }
+
+    /// Merge contact pairs into the interface between two parts
+    fn add_interface(
+        &mut self,
+        part_a: &str,
+        part_b: &str,
+        contact_pairs: usize,
+        paired_area: f64,
+        min_gap: f64,
+    ) {
+        let (part_a, part_b) = if part_a <= part_b {
+            (part_a, part_b)
+        } else {
+            (part_b, part_a)
+        };
+        for part in [part_a, part_b] {
+            if let Err(index) = self.parts.binary_search_by(|p| p.as_str().cmp(part)) {
+                self.parts.insert(index, part.to_string());
+            }
+        }
+
+        match self.edges.binary_search_by(|edge| {
+            (edge.part_a.as_str(), edge.part_b.as_str()).cmp(&(part_a, part_b))
+        }) {
+            Ok(index) => {
+                let edge = &mut self.edges[index];
+                edge.contact_pairs += contact_pairs;
+                edge.paired_area += paired_area;
+                edge.min_gap = edge.min_gap.min(min_gap);
+            }
+            Err(index) => self.edges.insert(
+                index,
+                InterfaceEdge {
+                    part_a: part_a.to_string(),
+                    part_b: part_b.to_string(),
+                    contact_pairs,
+                    paired_area,
+                    min_gap,
+                },
+            ),
+        }
+    }
+
+    /// Neighbours of every part, each list sorted by name (empty for parts
+    /// without interfaces)
+    pub fn adjacency(&self) -> BTreeMap<String, Vec<InterfaceNeighbor>> {
+        let mut adjacency: BTreeMap<String, Vec<InterfaceNeighbor>> = self
+            .parts
+            .iter()
+            .map(|part| (part.clone(), Vec::new()))
+            .collect();
+        for edge in &self.edges {
+            let neighbor = |part: &str| InterfaceNeighbor {
+                part: part.to_string(),
+                contact_pairs: edge.contact_pairs,
+                paired_area: edge.paired_area,
+                min_gap: edge.min_gap,
+            };
+            adjacency
+                .entry(edge.part_a.clone())
+                .or_default()
+                .push(neighbor(&edge.part_b));
+            if edge.part_a != edge.part_b {
+                adjacency
+                    .entry(edge.part_b.clone())
+                    .or_default()
+                    .push(neighbor(&edge.part_a));
+            }
+        }
+        for neighbors in adjacency.values_mut() {
+            neighbors.sort_by(|a, b| a.part.cmp(&b.part));
+        }
+        adjacency
+    }
+
+    /// Floating parts and connected components of the parts
+    pub fn check_connectivity(&self) -> ConnectivityCheck {
+        let index = |part: &str| self.parts.binary_search_by(|p| p.as_str().cmp(part));
+        let mut sets = UnionFind::new(self.parts.len());
+        let mut anchored = vec![false; self.parts.len()];
+        for edge in self.edges.iter().filter(|edge| edge.part_a != edge.part_b) {
+            if let (Ok(a), Ok(b)) = (index(&edge.part_a), index(&edge.part_b)) {
+                sets.union(a, b);
+                anchored[a] = true;
+                anchored[b] = true;
+            }
+        }
+
+        let mut components: Vec<Vec<String>> = sets
+            .components()
+            .into_iter()
+            .map(|members| members.into_iter().map(|i| self.parts[i].clone()).collect())
+            .collect();
+        components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+        ConnectivityCheck {
+            floating_parts: (0..self.parts.len())
+                .filter(|&i| !anchored[i])
+                .map(|i| self.parts[i].clone())
+                .collect(),
+            components,
+        }
+    }
+
+    /// The graph in GraphViz dot, edges labelled with their paired area and
+    /// smallest gap
+    pub fn to_dot(&self) -> String {
+        let quote = |name: &str| format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut dot = String::from("graph interfaces {\n    node [shape=box];\n");
+        for part in &self.parts {
+            let _ = writeln!(dot, "    {};", quote(part));
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                dot,
+                "    {} -- {} [label=\"area {:.6}\\nmin gap {:.6}\\n{} pair(s)\"];",
+                quote(&edge.part_a),
+                quote(&edge.part_b),
+                edge.paired_area,
+                edge.min_gap,
+                edge.contact_pairs
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Write the graph in GraphViz dot
+    pub fn write_dot<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, self.to_dot())?;
+        Ok(())
+    }
+
+    /// Write the parts and their adjacency list as JSON
+    pub fn export_json<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        #[derive(Serialize)]
+        struct AdjacencyFile<'a> {
+            parts: &'a [String],
+            adjacency: BTreeMap<String, Vec<InterfaceNeighbor>>,
+        }
+
+        let file = AdjacencyFile {
+            parts: &self.parts,
+            adjacency: self.adjacency(),
+        };
+        let json = serde_json::to_string_pretty(&file).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to serialize interface graph: {}", e))
+        })?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// Parts left unconnected by the detected interfaces
+///
+/// A floating part or a group of parts touching nothing else usually means a
+/// modeling error (a missing or too narrow contact), which shows up in the
+/// solver as rigid body modes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConnectivityCheck {
+    /// Parts without an interface to another part, sorted by name
+    pub floating_parts: Vec<String>,
+
+    /// Groups of parts connected through interfaces, largest first; a
+    /// single group if the assembly holds together
+    pub components: Vec<Vec<String>>,
+}
+
+impl ConnectivityCheck {
+    /// Whether all parts are connected through interfaces
+    pub fn is_connected(&self) -> bool {
+        self.components.len() <= 1
+    }
+
+    /// A warning for each floating part, and one if the assembly splits
+    /// into disconnected components
+    pub fn warnings(&self) -> Warnings {
+        let mut warnings = Warnings::new();
+        for part in &self.floating_parts {
+            warnings.push(
+                WarningKind::FloatingPart,
+                format!(
+                    "Part '{}' has no interface to any other part (floating)",
+                    part
+                ),
+            );
+        }
+        if !self.is_connected() {
+            warnings.push(
+                WarningKind::DisconnectedAssembly,
+                format!(
+                    "Assembly splits into {} disconnected components",
+                    self.components.len()
+                ),
+            );
+        }
+        warnings
+    }
+
+    /// Print the floating parts and the disconnected components
+    pub fn print(&self) {
+        println!("Assembly connectivity:");
+        if self.is_connected() {
+            println!(
+                "  All {} part(s) form one connected assembly",
+                self.components.first().map_or(0, Vec::len)
+            );
+            return;
+        }
+        if !self.floating_parts.is_empty() {
+            println!(
+                "  Floating parts ({}): {}",
+                self.floating_parts.len(),
+                self.floating_parts.join(", ")
+            );
+        }
+        println!("  {} disconnected component(s):", self.components.len());
+        for (index, component) in self.components.iter().enumerate() {
+            println!("    {}: {}", index + 1, component.join(", "));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::detection::detect_contact_pairs;
+    use crate::contact::types::ContactCriteria;
+    use crate::mesh::types::{Point, QuadFace, Vec3};
+
+    /// A unit square at height `z`
+    fn square(name: &str, z: f64, normal: f64) -> SurfaceMesh {
+        let mut surface = SurfaceMesh::new(name.to_string());
+        surface.nodes = vec![
+            Point::new(0.0, 0.0, z),
+            Point::new(1.0, 0.0, z),
+            Point::new(1.0, 1.0, z),
+            Point::new(0.0, 1.0, z),
+        ];
+        surface.faces.push(QuadFace::new([0, 1, 2, 3]));
+        surface.face_normals.push(Vec3::new(0.0, 0.0, normal));
+        surface.face_centroids.push(Point::new(0.5, 0.5, z));
+        surface.face_areas.push(1.0);
+        surface
+    }
+
+    #[test]
+    fn test_interface_graph() {
+        let criteria = ContactCriteria::new(0.01, 0.01, 180.0);
+        let mut graph = InterfaceGraph::new(["Plate", "Bolt", "Cover"]);
+        for (a, b, gap) in [
+            ("Plate:patch_0", "Bolt:patch_0", 0.002),
+            ("Bolt:patch_1", "Plate:patch_2", -0.001),
+            ("Plate:patch_1", "Nut:patch_0", 0.0),
+        ] {
+            let (surface_a, surface_b) = (square(a, 0.0, 1.0), square(b, gap, -1.0));
+            let results = detect_contact_pairs(&surface_a, &surface_b, &criteria).unwrap();
+            let metrics = PairMetrics::compute(&results, &surface_a, &surface_b);
+            graph.add_pair(&surface_a, &surface_b, &metrics);
+        }
+
+        // Pairs between the same parts form one interface in either order
+        assert_eq!(graph.parts, ["Bolt", "Cover", "Nut", "Plate"]);
+        assert_eq!(graph.edges.len(), 2);
+        let edge = &graph.edges[0];
+        assert_eq!(
+            (edge.part_a.as_str(), edge.part_b.as_str()),
+            ("Bolt", "Plate")
+        );
+        assert_eq!(edge.contact_pairs, 2);
+        assert!((edge.paired_area - 2.0).abs() < 1e-12);
+        assert!((edge.min_gap + 0.001).abs() < 1e-12);
+
+        let adjacency = graph.adjacency();
+        assert!(adjacency["Cover"].is_empty());
+        let plate: Vec<&str> = adjacency["Plate"].iter().map(|n| n.part.as_str()).collect();
+        assert_eq!(plate, ["Bolt", "Nut"]);
+
+        // Rolled up to assemblies; Cover is in none
+        let groups = HashMap::from([
+            ("Bolt".to_string(), vec!["Fastener".to_string()]),
+            ("Nut".to_string(), vec!["Fastener".to_string()]),
+            ("Plate".to_string(), vec!["Body".to_string()]),
+        ]);
+        let rolled = graph.roll_up(&groups);
+        assert_eq!(rolled.parts, ["Body", "Cover", "Fastener"]);
+        assert_eq!(rolled.edges.len(), 1);
+        assert_eq!(rolled.edges[0].contact_pairs, 3);
+        assert!((rolled.edges[0].paired_area - 3.0).abs() < 1e-12);
+        let groups = HashMap::from([
+            ("Bolt".to_string(), vec!["Body".to_string()]),
+            ("Plate".to_string(), vec!["Body".to_string()]),
+        ]);
+        let rolled = graph.roll_up(&groups);
+        let edges: Vec<(&str, &str)> = rolled
+            .edges
+            .iter()
+            .map(|edge| (edge.part_a.as_str(), edge.part_b.as_str()))
+            .collect();
+        assert_eq!(edges, [("Body", "Body"), ("Body", "Nut")]);
+
+        // Cover floats; Bolt, Nut and Plate hold together
+        let check = graph.check_connectivity();
+        assert!(!check.is_connected());
+        assert_eq!(check.floating_parts, ["Cover"]);
+        assert_eq!(
+            check.components,
+            [vec!["Bolt", "Nut", "Plate"], vec!["Cover"]]
+        );
+        let warnings = check.warnings();
+        assert_eq!(warnings.of_kind(WarningKind::FloatingPart).count(), 1);
+        assert_eq!(
+            warnings.of_kind(WarningKind::DisconnectedAssembly).count(),
+            1
+        );
+        let mut cover = square("Cover:patch_0", 0.0, 1.0);
+        let metrics = PairMetrics::compute(
+            &detect_contact_pairs(&cover, &cover, &criteria).unwrap(),
+            &cover,
+            &cover,
+        );
+        graph.add_pair(&cover, &cover, &metrics);
+        assert_eq!(graph.check_connectivity().floating_parts, ["Cover"]);
+        cover.part_name = "Nut:patch_1".to_string();
+        let lid = square("Cover:patch_1", 0.001, -1.0);
+        let results = detect_contact_pairs(&cover, &lid, &criteria).unwrap();
+        graph.add_pair(&cover, &lid, &PairMetrics::compute(&results, &cover, &lid));
+        assert!(graph.check_connectivity().is_connected());
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("graph interfaces {"));
+        assert!(dot.contains("    \"Cover\";\n"));
+        assert!(dot.contains("\"Bolt\" -- \"Plate\" [label=\"area 2.000000\\nmin gap -0.001000"));
+    }
+}
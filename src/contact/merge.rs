@@ -0,0 +1,287 @@
+//! Consolidation of overlapping contact pairs into interfaces
+//!
+//! Patch subdivision can split both sides of a single physical interface into
+//! several surface patches, so auto-contact reports multiple surface pairs for
+//! what an analyst considers one contact. This module groups such pairs: two
+//! pairs belong to the same interface if they share paired faces on a common
+//! surface, or if they connect the same pair of blocks and their contact
+//! regions overlap spatially.
+
+use crate::contact::types::ContactResults;
+use crate::mesh::types::SurfaceMesh;
+use crate::mesh::union_find::UnionFind;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+
+/// A detected surface pair, referring to surfaces by index
+#[derive(Debug, Clone, Copy)]
+pub struct DetectedPair<'a> {
+    /// Index of surface A in the surface list
+    pub surface_a: usize,
+
+    /// Index of surface B in the surface list
+    pub surface_b: usize,
+
+    /// Detection results for this pair
+    pub results: &'a ContactResults,
+}
+
+/// One physical contact interface made of one or more detected pairs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergedInterface {
+    /// Sequential interface ID (1-based)
+    pub interface_id: usize,
+
+    /// 1-based positions of the merged pairs in the input list (matching the
+    /// `pair_id` used in contact metadata)
+    pub pair_ids: Vec<usize>,
+
+    /// Names of all surfaces taking part in the interface (sorted)
+    pub surfaces: Vec<String>,
+
+    /// Names of the blocks taking part in the interface (sorted)
+    pub blocks: Vec<String>,
+
+    /// Total number of face pairs across merged pairs
+    pub num_face_pairs: usize,
+
+    /// Minimum corner of the contact region bounding box
+    pub bbox_min: [f64; 3],
+
+    /// Maximum corner of the contact region bounding box
+    pub bbox_max: [f64; 3],
+}
+
+/// Axis-aligned bounding box of the paired face centroids of one pair
+#[derive(Debug, Clone, Copy)]
+struct ContactBox {
+    min: [f64; 3],
+    max: [f64; 3],
+}
+
+impl ContactBox {
+    fn empty() -> Self {
+        Self {
+            min: [f64::MAX; 3],
+            max: [f64::MIN; 3],
+        }
+    }
+
+    fn include(&mut self, p: &crate::mesh::types::Point) {
+        for i in 0..3 {
+            self.min[i] = self.min[i].min(p[i]);
+            self.max[i] = self.max[i].max(p[i]);
+        }
+    }
+
+    fn merge(&mut self, other: &ContactBox) {
+        for i in 0..3 {
+            self.min[i] = self.min[i].min(other.min[i]);
+            self.max[i] = self.max[i].max(other.max[i]);
+        }
+    }
+
+    fn overlaps(&self, other: &ContactBox, tolerance: f64) -> bool {
+        (0..3).all(|i| {
+            self.min[i] - tolerance <= other.max[i] && other.min[i] - tolerance <= self.max[i]
+        })
+    }
+}
+
+/// Group detected pairs into consolidated interfaces
+///
+/// `tolerance` expands contact bounding boxes before the spatial overlap test;
+/// the detection gap distance is a sensible choice. Pairs without any face
+/// pairs each form their own interface.
+pub fn merge_contact_pairs(
+    pairs: &[DetectedPair],
+    surfaces: &[SurfaceMesh],
+    tolerance: f64,
+) -> Vec<MergedInterface> {
+    let n = pairs.len();
+
+    // Paired faces per (pair, surface) and contact bounding boxes
+    let mut faces_on: Vec<[(usize, HashSet<usize>); 2]> = Vec::with_capacity(n);
+    let mut boxes = Vec::with_capacity(n);
+    for pair in pairs {
+        let faces_a: HashSet<usize> = pair
+            .results
+            .pairs
+            .iter()
+            .map(|p| p.surface_a_face_id)
+            .collect();
+        let faces_b: HashSet<usize> = pair
+            .results
+            .pairs
+            .iter()
+            .map(|p| p.surface_b_face_id)
+            .collect();
+
+        let mut bbox = ContactBox::empty();
+        for &f in &faces_a {
+            bbox.include(&surfaces[pair.surface_a].face_centroids[f]);
+        }
+        for &f in &faces_b {
+            bbox.include(&surfaces[pair.surface_b].face_centroids[f]);
+        }
+
+        faces_on.push([(pair.surface_a, faces_a), (pair.surface_b, faces_b)]);
+        boxes.push(bbox);
+    }
+
+    let block_pair = |pair: &DetectedPair| {
+        let a = surfaces[pair.surface_a].block_name();
+        let b = surfaces[pair.surface_b].block_name();
+        if a <= b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    };
+
+    let mut sets = UnionFind::new(n);
+    for p in 0..n {
+        if pairs[p].results.pairs.is_empty() {
+            continue;
+        }
+        for q in (p + 1)..n {
+            if pairs[q].results.pairs.is_empty() {
+                continue;
+            }
+
+            let shares_faces = faces_on[p].iter().any(|(surf_p, faces_p)| {
+                faces_on[q]
+                    .iter()
+                    .any(|(surf_q, faces_q)| surf_p == surf_q && !faces_p.is_disjoint(faces_q))
+            });
+            let overlaps = block_pair(&pairs[p]) == block_pair(&pairs[q])
+                && boxes[p].overlaps(&boxes[q], tolerance);
+
+            if shares_faces || overlaps {
+                sets.union(p, q);
+            }
+        }
+    }
+
+    // Groups in order of their first pair
+    sets.components()
+        .into_iter()
+        .enumerate()
+        .map(|(idx, pair_indices)| {
+            let mut surface_names = BTreeSet::new();
+            let mut block_names = BTreeSet::new();
+            let mut bbox = ContactBox::empty();
+            let mut num_face_pairs = 0;
+
+            for &i in &pair_indices {
+                for s in [pairs[i].surface_a, pairs[i].surface_b] {
+                    surface_names.insert(surfaces[s].part_name.clone());
+                    block_names.insert(surfaces[s].block_name().to_string());
+                }
+                bbox.merge(&boxes[i]);
+                num_face_pairs += pairs[i].results.num_pairs();
+            }
+
+            MergedInterface {
+                interface_id: idx + 1,
+                pair_ids: pair_indices.iter().map(|i| i + 1).collect(),
+                surfaces: surface_names.into_iter().collect(),
+                blocks: block_names.into_iter().collect(),
+                num_face_pairs,
+                bbox_min: bbox.min,
+                bbox_max: bbox.max,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::{ContactCriteria, ContactPair};
+    use crate::mesh::types::{Point, QuadFace, Vec3};
+
+    fn make_patch(name: &str, centroids: &[[f64; 3]]) -> SurfaceMesh {
+        let mut surface = SurfaceMesh::new(name.to_string());
+        for c in centroids {
+            surface.faces.push(QuadFace::new([0, 1, 2, 3]));
+            surface.face_normals.push(Vec3::new(0.0, 0.0, 1.0));
+            surface.face_centroids.push(Point::new(c[0], c[1], c[2]));
+            surface.face_areas.push(1.0);
+        }
+        surface
+    }
+
+    fn make_results(face_pairs: &[(usize, usize)]) -> ContactResults {
+        let mut results =
+            ContactResults::new("a".to_string(), "b".to_string(), ContactCriteria::default());
+        for &(a, b) in face_pairs {
+            results.pairs.push(ContactPair {
+                surface_a_face_id: a,
+                surface_b_face_id: b,
+                distance: 0.0,
+                normal_angle: 180.0,
+                contact_point: Point::origin(),
+            });
+        }
+        results
+    }
+
+    #[test]
+    fn test_merges_pairs_sharing_faces() {
+        let surfaces = vec![
+            make_patch("Block1:patch_0", &[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]]),
+            make_patch("Block2:patch_0", &[[0.0, 0.0, 0.0]]),
+            make_patch("Block2:patch_1", &[[1.0, 0.0, 0.0]]),
+        ];
+        // Both pairs use face 0 on surface 0
+        let r1 = make_results(&[(0, 0)]);
+        let r2 = make_results(&[(0, 0)]);
+        let pairs = [
+            DetectedPair {
+                surface_a: 0,
+                surface_b: 1,
+                results: &r1,
+            },
+            DetectedPair {
+                surface_a: 0,
+                surface_b: 2,
+                results: &r2,
+            },
+        ];
+
+        let merged = merge_contact_pairs(&pairs, &surfaces, 0.0);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].pair_ids, vec![1, 2]);
+        assert_eq!(merged[0].blocks, vec!["Block1", "Block2"]);
+        assert_eq!(merged[0].num_face_pairs, 2);
+    }
+
+    #[test]
+    fn test_keeps_separate_interfaces_apart() {
+        let surfaces = vec![
+            make_patch("Block1:patch_0", &[[0.0, 0.0, 0.0]]),
+            make_patch("Block2:patch_0", &[[0.0, 0.0, 0.0]]),
+            make_patch("Block1:patch_1", &[[10.0, 0.0, 0.0]]),
+            make_patch("Block2:patch_1", &[[10.0, 0.0, 0.0]]),
+        ];
+        let r1 = make_results(&[(0, 0)]);
+        let r2 = make_results(&[(0, 0)]);
+        let pairs = [
+            DetectedPair {
+                surface_a: 0,
+                surface_b: 1,
+                results: &r1,
+            },
+            DetectedPair {
+                surface_a: 2,
+                surface_b: 3,
+                results: &r2,
+            },
+        ];
+
+        assert_eq!(merge_contact_pairs(&pairs, &surfaces, 0.1).len(), 2);
+        // A large tolerance makes the same-block contact regions overlap
+        assert_eq!(merge_contact_pairs(&pairs, &surfaces, 20.0).len(), 1);
+    }
+}
@@ -1,6 +1,9 @@
 //! Surface-level and element-level metric computation
 
-use crate::contact::types::ContactResults;
+use crate::contact::fitting::{InterfaceType, PatchFits};
+use crate::contact::refinement::{MeshDensityAssessment, DEFAULT_MAX_SIZE_RATIO};
+use crate::contact::types::{ContactCriteria, ContactResults};
+use crate::format::{FloatFormat, Glyphs};
 use crate::mesh::types::SurfaceMesh;
 use serde::{Deserialize, Serialize};
 
@@ -36,6 +39,56 @@ pub struct SurfaceMetrics {
 
     /// Number of unpaired faces
     pub num_unpaired: usize,
+
+    /// Deviation of paired faces from exact opposition, whichever way their
+    /// normals point
+    #[serde(default)]
+    pub opposition_deviation: AlignmentQuantiles,
+}
+
+/// Quantiles of the deviation of paired faces from exact opposition, in
+/// degrees; 0 everywhere for perfectly opposed faces
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AlignmentQuantiles {
+    pub median: f64,
+    pub p90: f64,
+    pub max: f64,
+}
+
+impl AlignmentQuantiles {
+    /// Quantiles of the opposition deviations of the given pairs
+    pub fn of_pairs(results: &ContactResults) -> Self {
+        let mut deviations: Vec<f64> = results
+            .pairs
+            .iter()
+            .map(|pair| opposition_deviation(pair.normal_angle))
+            .collect();
+        if deviations.is_empty() {
+            return Self::default();
+        }
+        deviations.sort_by(f64::total_cmp);
+
+        // Nearest-rank quantile
+        let quantile = |q: f64| {
+            let rank = (q * deviations.len() as f64).ceil() as usize;
+            deviations[rank.clamp(1, deviations.len()) - 1]
+        };
+        Self {
+            median: quantile(0.5),
+            p90: quantile(0.9),
+            max: deviations[deviations.len() - 1],
+        }
+    }
+}
+
+/// Deviation of a pair's faces from exact opposition, in degrees: the angle
+/// between their planes, min(angle, 180° − angle)
+///
+/// Extracted boundary faces keep the node order of their canonical form, so
+/// the normals of two mated faces come out either parallel (0°) or opposite
+/// (180°) depending on node numbering; both count as opposed.
+pub fn opposition_deviation(normal_angle: f64) -> f64 {
+    normal_angle.min(180.0 - normal_angle)
 }
 
 impl SurfaceMetrics {
@@ -45,6 +98,7 @@ impl SurfaceMetrics {
     /// * `results` - Contact detection results
     /// * `surface` - Surface mesh to compute metrics for
     /// * `is_surface_a` - Whether this is surface A (true) or surface B (false)
+    #[tracing::instrument(level = "debug", skip_all, fields(surface = %surface.part_name))]
     pub fn compute(results: &ContactResults, surface: &SurfaceMesh, is_surface_a: bool) -> Self {
         let total_area: f64 = surface.face_areas.iter().sum();
 
@@ -125,24 +179,25 @@ impl SurfaceMetrics {
             avg_normal_angle,
             num_pairs,
             num_unpaired,
+            opposition_deviation: AlignmentQuantiles::of_pairs(results),
         }
     }
 
-    /// Print metrics summary
-    pub fn print_summary(&self, surface_name: &str) {
+    /// Print metrics summary, lengths and areas in `format`
+    pub fn print_summary(&self, surface_name: &str, format: FloatFormat, glyphs: Glyphs) {
         println!("\n{}", "=".repeat(60));
         println!("SURFACE METRICS: {}", surface_name);
         println!("{}", "=".repeat(60));
         println!();
-        println!("  Total Area:      {:.6}", self.total_area);
+        println!("  Total Area:      {}", format.show(self.total_area));
         println!(
-            "  Paired Area:     {:.6}  ({:.1}%)",
-            self.paired_area,
+            "  Paired Area:     {}  ({:.1}%)",
+            format.show(self.paired_area),
             self.paired_area / self.total_area * 100.0
         );
         println!(
-            "  Unpaired Area:   {:.6}  ({:.1}%)",
-            self.unpaired_area,
+            "  Unpaired Area:   {}  ({:.1}%)",
+            format.show(self.unpaired_area),
             self.unpaired_area / self.total_area * 100.0
         );
         println!();
@@ -152,18 +207,224 @@ impl SurfaceMetrics {
 
         if self.num_pairs > 0 {
             println!("  Distance Statistics (area-weighted):");
-            println!("    Average:   {:.6}", self.avg_distance);
-            println!("    Std Dev:   {:.6}", self.std_dev_distance);
-            println!("    Min:       {:.6}", self.min_distance);
-            println!("    Max:       {:.6}", self.max_distance);
+            println!("    Average:   {}", format.show(self.avg_distance));
+            println!("    Std Dev:   {}", format.show(self.std_dev_distance));
+            println!("    Min:       {}", format.show(self.min_distance));
+            println!("    Max:       {}", format.show(self.max_distance));
             println!();
             println!("  Normal Angle:");
-            println!("    Average:   {:.2}°", self.avg_normal_angle);
+            println!("    Average:   {:.2}{}", self.avg_normal_angle, glyphs.degrees());
+            println!();
+            println!("  Deviation from Opposed Faces:");
+            let deviation = &self.opposition_deviation;
+            let degrees = glyphs.degrees();
+            println!("    Median:    {:.2}{}", deviation.median, degrees);
+            println!("    90th pct:  {:.2}{}", deviation.p90, degrees);
+            println!("    Max:       {:.2}{}", deviation.max, degrees);
             println!();
         }
 
         println!("{}", "=".repeat(60));
     }
+
+    /// Paired fraction of the surface area, in [0, 1]
+    pub fn coverage(&self) -> f64 {
+        if self.total_area > 0.0 {
+            self.paired_area / self.total_area
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Contact metrics of a detected pair, from both of its sides
+///
+/// Gap and angle statistics are the same seen from either side and are read
+/// from `surface_a`; areas, coverage and face counts differ per side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairMetrics {
+    /// Metrics of surface A (the master)
+    pub surface_a: SurfaceMetrics,
+
+    /// Metrics of surface B (the slave)
+    pub surface_b: SurfaceMetrics,
+
+    /// Median ratio of A to B face size (edge length) across the pairs;
+    /// above 1 surface A is coarser, 1 without pairs
+    pub size_ratio: f64,
+
+    /// Shape of the interface
+    pub interface_type: InterfaceType,
+}
+
+impl PairMetrics {
+    /// Compute the metrics of both sides of a pair
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(surface_a = %surface_a.part_name, surface_b = %surface_b.part_name)
+    )]
+    pub fn compute(
+        results: &ContactResults,
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+    ) -> Self {
+        let fits_a = PatchFits::of_patch(results, surface_a, true);
+        let fits_b = PatchFits::of_patch(results, surface_b, false);
+        Self {
+            surface_a: SurfaceMetrics::compute(results, surface_a, true),
+            surface_b: SurfaceMetrics::compute(results, surface_b, false),
+            size_ratio: MeshDensityAssessment::compute(
+                results,
+                surface_a,
+                surface_b,
+                DEFAULT_MAX_SIZE_RATIO,
+            )
+            .map_or(1.0, |assessment| assessment.median_size_ratio),
+            interface_type: InterfaceType::of_pair(&fits_a, &fits_b),
+        }
+    }
+
+    /// Number of face pairs
+    pub fn num_pairs(&self) -> usize {
+        self.surface_a.num_pairs
+    }
+
+    /// Paired fraction of surface A's area
+    pub fn coverage_a(&self) -> f64 {
+        self.surface_a.coverage()
+    }
+
+    /// Paired fraction of surface B's area
+    pub fn coverage_b(&self) -> f64 {
+        self.surface_b.coverage()
+    }
+
+    /// Whether the pair is large enough to count as contact under the
+    /// criteria's minimum paired area and minimum coverage
+    pub fn meets_acceptance(&self, criteria: &ContactCriteria) -> bool {
+        criteria
+            .min_paired_area
+            .is_none_or(|min| self.surface_a.paired_area >= min)
+            && criteria
+                .min_coverage_fraction
+                .is_none_or(|min| self.coverage_a().max(self.coverage_b()) >= min)
+    }
+
+    /// Normal alignment class from the average normal angle: "opposed",
+    /// "aligned" or "angled"
+    pub fn normal_alignment(&self) -> &'static str {
+        if self.surface_a.avg_normal_angle > 150.0 {
+            "opposed"
+        } else if self.surface_a.avg_normal_angle < 30.0 {
+            "aligned"
+        } else {
+            "angled"
+        }
+    }
+
+    /// Metrics of the pair seen from surface B
+    pub fn reversed(&self) -> Self {
+        Self {
+            surface_a: self.surface_b.clone(),
+            surface_b: self.surface_a.clone(),
+            size_ratio: if self.size_ratio > 0.0 {
+                1.0 / self.size_ratio
+            } else {
+                1.0
+            },
+            interface_type: self.interface_type,
+        }
+    }
+
+    /// Print the metrics of both sides followed by the pair totals
+    pub fn print_summary(
+        &self,
+        surface_a_name: &str,
+        surface_b_name: &str,
+        format: FloatFormat,
+        glyphs: Glyphs,
+    ) {
+        self.surface_a.print_summary(surface_a_name, format, glyphs);
+        self.surface_b.print_summary(surface_b_name, format, glyphs);
+        println!();
+        println!(
+            "  Coverage:        {:.1}% / {:.1}%",
+            self.coverage_a() * 100.0,
+            self.coverage_b() * 100.0
+        );
+        println!("  Size ratio:      {:.2} (A/B face size)", self.size_ratio);
+        println!("  Alignment:       {}", self.normal_alignment());
+        println!("  Interface type:  {}", self.interface_type);
+    }
+}
+
+/// Per-face quantity derived from contact results
+///
+/// Used e.g. to populate Exodus side set distribution factors for solvers that
+/// use them as weights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaceValueSource {
+    /// Signed gap of the face's contact pair (+ gap, - overlap)
+    Gap,
+
+    /// Fraction of the face area overlapped by its partner face(s), in [0, 1]
+    OverlapFraction,
+
+    /// Angle between the normals of the face's contact pair, in degrees
+    NormalAngle,
+
+    /// 1 for faces in a contact pair, 0 otherwise
+    Paired,
+}
+
+/// Compute one value per face of `surface` from contact results
+///
+/// `partner` is the other surface of the pair. Unpaired faces get 0.0. When
+/// several pairs reference the same face (possible on surface B), the gap and
+/// angle of the closest pair are used and overlap fractions are summed and
+/// capped at 1.
+pub fn per_face_values(
+    results: &ContactResults,
+    surface: &SurfaceMesh,
+    partner: &SurfaceMesh,
+    is_surface_a: bool,
+    source: FaceValueSource,
+) -> Vec<f64> {
+    let mut values = vec![0.0f64; surface.faces.len()];
+    let mut closest = vec![f64::INFINITY; surface.faces.len()];
+
+    for pair in &results.pairs {
+        let (face_idx, partner_idx) = if is_surface_a {
+            (pair.surface_a_face_id, pair.surface_b_face_id)
+        } else {
+            (pair.surface_b_face_id, pair.surface_a_face_id)
+        };
+
+        match source {
+            FaceValueSource::Gap | FaceValueSource::NormalAngle => {
+                if pair.distance.abs() < closest[face_idx] {
+                    closest[face_idx] = pair.distance.abs();
+                    values[face_idx] = if source == FaceValueSource::Gap {
+                        pair.distance
+                    } else {
+                        pair.normal_angle
+                    };
+                }
+            }
+            FaceValueSource::OverlapFraction => {
+                let own_area = surface.face_areas[face_idx];
+                if own_area > 0.0 {
+                    let overlap = own_area.min(partner.face_areas[partner_idx]) / own_area;
+                    values[face_idx] = (values[face_idx] + overlap).min(1.0);
+                }
+            }
+            FaceValueSource::Paired => values[face_idx] = 1.0,
+        }
+    }
+
+    values
 }
 
 #[cfg(test)]
@@ -180,6 +441,7 @@ mod tests {
             face_centroids: vec![Point::new(0.5, 0.5, 0.0), Point::new(1.5, 0.5, 0.0)],
             face_areas: vec![1.0, 2.0],
             nodes: vec![],
+            face_tags: Default::default(),
         };
 
         let mut results = ContactResults::new(
@@ -227,5 +489,106 @@ mod tests {
 
         // Simple average of angles
         assert_eq!(metrics.avg_normal_angle, 15.0);
+
+        // Deviations from opposition are 10° and 20°
+        assert_eq!(metrics.opposition_deviation.median, 10.0);
+        assert_eq!(metrics.opposition_deviation.p90, 20.0);
+        assert_eq!(metrics.opposition_deviation.max, 20.0);
+    }
+
+    #[test]
+    fn test_pair_metrics() {
+        let (mut results, surface) = make_test_data();
+        results.unpaired_b = vec![1];
+        results.pairs.pop();
+        let metrics = PairMetrics::compute(&results, &surface, &surface);
+
+        assert_eq!(metrics.num_pairs(), 1);
+        assert!((metrics.coverage_a() - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(metrics.surface_b.num_unpaired, 1);
+        assert_eq!(metrics.size_ratio, 1.0);
+        assert_eq!(metrics.normal_alignment(), "aligned");
+
+        let reversed = metrics.reversed();
+        assert_eq!(reversed.surface_a.num_unpaired, 1);
+        assert_eq!(reversed.surface_b.num_unpaired, 0);
+    }
+
+    #[test]
+    fn test_per_face_values() {
+        let (results, surface) = make_test_data();
+        let mut partner = surface.clone();
+        partner.face_areas = vec![0.5, 4.0];
+
+        let gaps = per_face_values(&results, &surface, &partner, true, FaceValueSource::Gap);
+        assert_eq!(gaps, vec![0.001, 0.002]);
+
+        let overlap = per_face_values(
+            &results,
+            &surface,
+            &partner,
+            true,
+            FaceValueSource::OverlapFraction,
+        );
+        assert_eq!(overlap, vec![0.5, 1.0]);
+
+        let angles = per_face_values(
+            &results,
+            &surface,
+            &partner,
+            true,
+            FaceValueSource::NormalAngle,
+        );
+        assert_eq!(angles, vec![10.0, 20.0]);
+
+        let mut one_paired = results.clone();
+        one_paired.pairs.truncate(1);
+        let paired = per_face_values(&one_paired, &surface, &partner, true, FaceValueSource::Paired);
+        assert_eq!(paired, vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_opposition_deviation_of_extracted_surfaces() {
+        use crate::contact::detection::detect_contact_pairs;
+        use crate::mesh::{extract_surface, HexElement, Mesh};
+
+        // Parallel and opposite normals both count as opposed
+        assert_eq!(opposition_deviation(0.0), 0.0);
+        assert_eq!(opposition_deviation(180.0), 0.0);
+        assert_eq!(opposition_deviation(170.0), 10.0);
+
+        // Two unit cubes stacked along z, 0.001 apart
+        let mut mesh = Mesh::new();
+        for (block, z0) in [("Lower", 0.0), ("Upper", 1.001)] {
+            let base = mesh.nodes.len();
+            for z in [z0, z0 + 1.0] {
+                mesh.nodes.push(Point::new(0.0, 0.0, z));
+                mesh.nodes.push(Point::new(1.0, 0.0, z));
+                mesh.nodes.push(Point::new(1.0, 1.0, z));
+                mesh.nodes.push(Point::new(0.0, 1.0, z));
+            }
+            let element = mesh.elements.len();
+            mesh.elements
+                .push(HexElement::new(std::array::from_fn(|k| base + k)));
+            mesh.element_blocks.insert(block.to_string(), vec![element]);
+        }
+
+        let surfaces = extract_surface(&mesh).unwrap();
+        let criteria = ContactCriteria::new(0.005, 0.001, 180.0);
+        let mut in_contact = Vec::new();
+        for surface_a in surfaces.iter().filter(|s| s.block_name() == "Lower") {
+            for surface_b in surfaces.iter().filter(|s| s.block_name() == "Upper") {
+                let results = detect_contact_pairs(surface_a, surface_b, &criteria).unwrap();
+                if results.num_pairs() > 0 {
+                    in_contact.push(PairMetrics::compute(&results, surface_a, surface_b));
+                }
+            }
+        }
+
+        // Only the mated faces pair up, and they are exactly opposed
+        assert_eq!(in_contact.len(), 1);
+        let deviation = in_contact[0].surface_a.opposition_deviation;
+        assert!(deviation.median.abs() < 1e-9);
+        assert!(deviation.max.abs() < 1e-9);
     }
 }
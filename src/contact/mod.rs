@@ -1,9 +1,65 @@
 //! Contact detection module
 
+pub mod algorithm;
+pub mod arrays;
+pub mod auto;
+pub mod broad_phase;
+pub mod cache;
+pub mod coarse;
+pub mod combine;
+pub mod conformity;
 pub mod detection;
+pub mod deviation;
+pub mod dynamic_index;
+pub mod fasteners;
+pub mod fitting;
+pub mod footprint;
+pub mod gap_closing;
+pub mod interface;
+pub mod interface_graph;
+pub mod merge;
 pub mod metrics;
+pub mod nodal;
+pub mod occlusion;
+pub mod probe;
+pub mod query;
+pub mod refinement;
+pub mod relative;
+pub mod revision;
+pub mod sdf;
+pub mod spatial_index;
+pub mod summary;
+pub mod thermal;
 pub mod types;
 
+pub use algorithm::*;
+pub use arrays::*;
+pub use auto::*;
+pub use broad_phase::*;
+pub use cache::*;
+pub use coarse::*;
+pub use combine::*;
+pub use conformity::*;
 pub use detection::*;
+pub use deviation::*;
+pub use dynamic_index::*;
+pub use fasteners::*;
+pub use fitting::*;
+pub use footprint::*;
+pub use gap_closing::*;
+pub use interface::*;
+pub use interface_graph::*;
+pub use merge::*;
 pub use metrics::*;
+pub use nodal::*;
+pub use occlusion::*;
+pub use probe::*;
+pub use query::*;
+pub use refinement::*;
+pub use relative::*;
+pub use revision::*;
+pub use sdf::*;
+pub use spatial_index::*;
+pub use summary::*;
+pub use thermal::*;
 pub use types::*;
@@ -0,0 +1,166 @@
+//! Contact status of the volume mesh nodes
+//!
+//! Face pairs live on the extracted surfaces, but the faces index the nodes of
+//! the volume mesh directly, so the result of every surface pair can be
+//! written back onto the solid mesh as nodal fields and overlaid on the
+//! solver's own results.
+
+use crate::contact::types::ContactResults;
+use crate::mesh::{Mesh, SurfaceMesh};
+
+/// Name of the nodal field holding the contact status
+pub const NODAL_STATUS_FIELD: &str = "contact_status";
+
+/// Name of the nodal field holding the contact gap
+pub const NODAL_GAP_FIELD: &str = "contact_gap";
+
+/// Contact status and gap of each node of a volume mesh
+#[derive(Debug, Clone)]
+pub struct NodalContact {
+    /// 0 for nodes of no paired face, otherwise the 1-based ID of the surface
+    /// pair whose paired faces use the node (the lowest ID if several do)
+    pub status: Vec<i32>,
+
+    /// Mean distance of the face pairs around the node within its surface
+    /// pair (0 for nodes not in contact)
+    pub gap: Vec<f64>,
+}
+
+impl NodalContact {
+    /// No node in contact
+    pub fn new(num_nodes: usize) -> Self {
+        Self {
+            status: vec![0; num_nodes],
+            gap: vec![0.0; num_nodes],
+        }
+    }
+
+    /// Contact status of the surface pairs, numbered from 1 in order
+    pub fn from_pairs<'a>(
+        num_nodes: usize,
+        pairs: impl IntoIterator<Item = (&'a ContactResults, &'a SurfaceMesh, &'a SurfaceMesh)>,
+    ) -> Self {
+        let mut nodal = Self::new(num_nodes);
+        for (idx, (results, surface_a, surface_b)) in pairs.into_iter().enumerate() {
+            nodal.add_pair(idx + 1, results, surface_a, surface_b);
+        }
+        nodal
+    }
+
+    /// Mark the nodes of the paired faces of one surface pair
+    ///
+    /// Nodes already claimed by an earlier pair keep their status and gap.
+    /// Node indices outside the mesh (surfaces not extracted from it) are
+    /// skipped.
+    pub fn add_pair(
+        &mut self,
+        pair_id: usize,
+        results: &ContactResults,
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+    ) {
+        let mut sums: Vec<(usize, f64)> = Vec::new();
+        let mut touched: Vec<usize> = Vec::new();
+        for pair in &results.pairs {
+            let faces = [
+                surface_a.faces.get(pair.surface_a_face_id),
+                surface_b.faces.get(pair.surface_b_face_id),
+            ];
+            for node in faces.into_iter().flatten().flat_map(|f| f.node_ids) {
+                if node >= self.status.len() || self.status[node] != 0 {
+                    continue;
+                }
+                if sums.len() <= node {
+                    sums.resize(node + 1, (0, 0.0));
+                }
+                if sums[node].0 == 0 {
+                    touched.push(node);
+                }
+                sums[node].0 += 1;
+                sums[node].1 += pair.distance;
+            }
+        }
+
+        for node in touched {
+            let (count, sum) = sums[node];
+            self.status[node] = pair_id as i32;
+            self.gap[node] = sum / count as f64;
+        }
+    }
+
+    /// Number of nodes in contact
+    pub fn num_in_contact(&self) -> usize {
+        self.status.iter().filter(|&&s| s != 0).count()
+    }
+
+    /// Add the status and gap as nodal fields of the mesh
+    pub fn add_to_mesh(&self, mesh: &mut Mesh) {
+        mesh.node_fields.insert(
+            NODAL_STATUS_FIELD.to_string(),
+            self.status.iter().map(|&s| s as f64).collect(),
+        );
+        mesh.node_fields
+            .insert(NODAL_GAP_FIELD.to_string(), self.gap.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::{ContactCriteria, ContactPair};
+    use crate::mesh::types::{Point, QuadFace, Vec3};
+
+    fn surface(name: &str, faces: &[[usize; 4]]) -> SurfaceMesh {
+        let mut surface = SurfaceMesh::new(name.to_string());
+        for &nodes in faces {
+            surface.faces.push(QuadFace::new(nodes));
+            surface.face_normals.push(Vec3::z());
+            surface.face_centroids.push(Point::origin());
+            surface.face_areas.push(1.0);
+        }
+        surface
+    }
+
+    fn results(pairs: &[(usize, usize, f64)]) -> ContactResults {
+        let mut results =
+            ContactResults::new("A".to_string(), "B".to_string(), ContactCriteria::default());
+        for &(a, b, distance) in pairs {
+            results.pairs.push(ContactPair {
+                surface_a_face_id: a,
+                surface_b_face_id: b,
+                distance,
+                normal_angle: 180.0,
+                contact_point: Point::origin(),
+            });
+        }
+        results
+    }
+
+    #[test]
+    fn test_nodal_contact() {
+        let a = surface("A", &[[0, 1, 2, 3], [1, 4, 5, 2]]);
+        let b = surface("B", &[[6, 7, 8, 9]]);
+        let c = surface("C", &[[9, 10, 11, 12]]);
+        let first = results(&[(0, 0, 0.002), (1, 0, 0.004)]);
+        let second = results(&[(0, 0, -0.001)]);
+
+        let nodal = NodalContact::from_pairs(14, [(&first, &a, &b), (&second, &b, &c)]);
+        assert_eq!(nodal.status[..13], [1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2]);
+        assert_eq!(nodal.status[13], 0);
+        assert_eq!(nodal.num_in_contact(), 13);
+
+        // Nodes shared by both faces of A average their gaps; node 9 stays
+        // with the first pair
+        assert!((nodal.gap[1] - 0.003).abs() < 1e-12);
+        assert!((nodal.gap[0] - 0.002).abs() < 1e-12);
+        assert!((nodal.gap[6] - 0.003).abs() < 1e-12);
+        assert!((nodal.gap[9] - 0.003).abs() < 1e-12);
+        assert!((nodal.gap[10] + 0.001).abs() < 1e-12);
+        assert_eq!(nodal.gap[13], 0.0);
+
+        let mut mesh = Mesh::new();
+        nodal.add_to_mesh(&mut mesh);
+        assert_eq!(mesh.node_fields[NODAL_STATUS_FIELD][10], 2.0);
+        assert_eq!(mesh.node_fields[NODAL_GAP_FIELD].len(), 14);
+    }
+}
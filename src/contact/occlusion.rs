@@ -0,0 +1,332 @@
+//! Thin-wall occlusion check
+//!
+//! On thin parts the far wall of a component lies within the gap tolerance of
+//! a neighbor, so it pairs with that neighbor through the part's own material.
+//! A pair is genuine only if the segment from A's face centroid to its contact
+//! point on B stays outside A's elements; pairs whose segment crosses more
+//! material than the penetration tolerance are rejected.
+
+use crate::contact::types::{ContactPair, ContactResults};
+use crate::mesh::types::{Mesh, Point, SurfaceMesh, Vec3};
+use kiddo::ImmutableKdTree;
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Relative length below which a segment counts as not entering an element
+const OCCLUSION_TOLERANCE: f64 = 1e-9;
+
+/// Tetrahedra splitting a HEX8 along its 0-6 diagonal
+const HEX_TETS: [[usize; 4]; 6] = [
+    [0, 1, 2, 6],
+    [0, 2, 3, 6],
+    [0, 3, 7, 6],
+    [0, 7, 4, 6],
+    [0, 4, 5, 6],
+    [0, 5, 1, 6],
+];
+
+/// Spatial index of the elements of one block
+struct BlockVolume {
+    /// Node coordinates of each element
+    elements: Vec<[Point; 8]>,
+
+    /// k-d tree of element centroids
+    tree: ImmutableKdTree<f64, 3>,
+
+    /// Largest distance from an element centroid to one of its nodes
+    max_radius: f64,
+}
+
+/// Element volumes of a mesh, indexed per block, for occlusion queries
+pub struct VolumeIndex {
+    blocks: HashMap<String, BlockVolume>,
+}
+
+impl VolumeIndex {
+    /// Index the elements of every block of `mesh`
+    #[tracing::instrument(skip_all, fields(blocks = mesh.num_blocks()))]
+    pub fn new(mesh: &Mesh) -> Self {
+        let blocks = mesh
+            .element_blocks
+            .iter()
+            .map(|(block_name, elem_indices)| {
+                let elements: Vec<[Point; 8]> = elem_indices
+                    .iter()
+                    .map(|&i| mesh.elements[i].node_ids.map(|n| mesh.nodes[n]))
+                    .collect();
+
+                let centroids: Vec<[f64; 3]> = elements
+                    .iter()
+                    .map(|nodes| {
+                        let c = element_centroid(nodes);
+                        [c.x, c.y, c.z]
+                    })
+                    .collect();
+
+                let max_radius = elements
+                    .iter()
+                    .map(|nodes| {
+                        let c = element_centroid(nodes);
+                        nodes.iter().map(|n| (n - c).norm()).fold(0.0, f64::max)
+                    })
+                    .fold(0.0, f64::max);
+
+                let volume = BlockVolume {
+                    elements,
+                    tree: ImmutableKdTree::new_from_slice(&centroids),
+                    max_radius,
+                };
+                (block_name.clone(), volume)
+            })
+            .collect();
+
+        Self { blocks }
+    }
+
+    /// Length of the segment `start`-`end` that lies inside the elements of
+    /// `block_name` (0 for unknown blocks)
+    pub fn length_inside(&self, block_name: &str, start: &Point, end: &Point) -> f64 {
+        let Some(block) = self.blocks.get(block_name) else {
+            return 0.0;
+        };
+        if block.elements.is_empty() {
+            return 0.0;
+        }
+
+        let length = (end - start).norm();
+        let midpoint = Point::from((start.coords + end.coords) / 2.0);
+        let radius = length / 2.0 + block.max_radius;
+
+        // Tetrahedra share faces, so a segment lying in a shared face is
+        // clipped by both; merge the intervals rather than summing them
+        let mut intervals: Vec<(f64, f64)> = block
+            .tree
+            .within::<kiddo::SquaredEuclidean>(
+                &[midpoint.x, midpoint.y, midpoint.z],
+                radius * radius,
+            )
+            .iter()
+            .flat_map(|neighbor| {
+                let nodes = &block.elements[neighbor.item as usize];
+                HEX_TETS.iter().filter_map(move |tet| {
+                    segment_interval_in_tet(start, end, tet.map(|i| nodes[i]))
+                })
+            })
+            .collect();
+        intervals.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut inside = 0.0;
+        let mut covered_to = 0.0_f64;
+        for (t0, t1) in intervals {
+            let t0 = t0.max(covered_to);
+            if t1 > t0 {
+                inside += t1 - t0;
+                covered_to = t1;
+            }
+        }
+
+        inside * length
+    }
+}
+
+/// Remove pairs whose segment from A's centroid to the contact point passes
+/// through A's own volume
+///
+/// Surface A's block is taken from its name (see [`SurfaceMesh::block_name`]).
+/// Removed faces become unpaired; returns the number of removed pairs, which
+/// is also added to `results.rejected_occluded`.
+#[tracing::instrument(skip_all, fields(surface_a = %surface_a.part_name))]
+pub fn remove_occluded_pairs(
+    results: &mut ContactResults,
+    surface_a: &SurfaceMesh,
+    volume: &VolumeIndex,
+) -> usize {
+    let block_name = surface_a.block_name();
+    let max_penetration = results.criteria.max_penetration;
+
+    let is_occluded = |pair: &ContactPair| {
+        let centroid = &surface_a.face_centroids[pair.surface_a_face_id];
+        let inside = volume.length_inside(block_name, centroid, &pair.contact_point);
+        let length = (pair.contact_point - centroid).norm();
+        inside > max_penetration + OCCLUSION_TOLERANCE * length
+    };
+
+    // Threshold for parallelization (below this, overhead isn't worth it)
+    const PARALLEL_THRESHOLD: usize = 1000;
+
+    #[cfg(feature = "parallel")]
+    let occluded: Vec<bool> = if results.pairs.len() >= PARALLEL_THRESHOLD {
+        results.pairs.par_iter().map(is_occluded).collect()
+    } else {
+        results.pairs.iter().map(is_occluded).collect()
+    };
+
+    #[cfg(not(feature = "parallel"))]
+    let occluded: Vec<bool> = results.pairs.iter().map(is_occluded).collect();
+
+    let (removed, kept): (Vec<_>, Vec<_>) = results
+        .pairs
+        .drain(..)
+        .zip(occluded)
+        .partition(|(_, occluded)| *occluded);
+    results.pairs = kept.into_iter().map(|(pair, _)| pair).collect();
+
+    let still_paired_b: HashSet<usize> =
+        results.pairs.iter().map(|p| p.surface_b_face_id).collect();
+    for (pair, _) in &removed {
+        results.unpaired_a.push(pair.surface_a_face_id);
+        if !still_paired_b.contains(&pair.surface_b_face_id) {
+            results.unpaired_b.push(pair.surface_b_face_id);
+        }
+    }
+    results.sort_by_face();
+    results.unpaired_b.dedup();
+
+    if !removed.is_empty() {
+        tracing::debug!(
+            "Removed {} pairs passing through the volume of '{}'",
+            removed.len(),
+            block_name
+        );
+    }
+
+    results.rejected_occluded += removed.len();
+    removed.len()
+}
+
+fn element_centroid(nodes: &[Point; 8]) -> Point {
+    Point::from(nodes.iter().map(|n| n.coords).sum::<Vec3>() / 8.0)
+}
+
+/// Parameter interval of the segment `start`-`end` inside a tetrahedron
+/// (Cyrus-Beck clipping)
+fn segment_interval_in_tet(start: &Point, end: &Point, tet: [Point; 4]) -> Option<(f64, f64)> {
+    let direction = end - start;
+    let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+
+    for opposite in 0..4 {
+        let face: Vec<&Point> = (0..4).filter(|&i| i != opposite).map(|i| &tet[i]).collect();
+        let mut normal = (face[1] - face[0]).cross(&(face[2] - face[0]));
+        let toward_opposite = normal.dot(&(tet[opposite] - face[0]));
+        if toward_opposite == 0.0 {
+            // Degenerate (flat) tetrahedron
+            return None;
+        }
+        if toward_opposite > 0.0 {
+            normal = -normal;
+        }
+
+        // Inside where normal . (p - face[0]) <= 0
+        let offset = normal.dot(&(start - face[0]));
+        let rate = normal.dot(&direction);
+        if rate == 0.0 {
+            if offset > 0.0 {
+                return None;
+            }
+            continue;
+        }
+
+        let t = -offset / rate;
+        if rate > 0.0 {
+            t1 = t1.min(t);
+        } else {
+            t0 = t0.max(t);
+        }
+        if t0 >= t1 {
+            return None;
+        }
+    }
+
+    Some((t0, t1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::ContactCriteria;
+    use crate::mesh::types::{HexElement, QuadFace};
+
+    /// One-element plate [0,1] x [0,1] x [0,0.1]
+    fn make_plate() -> Mesh {
+        let mut mesh = Mesh::new();
+        for z in [0.0, 0.1] {
+            mesh.nodes.extend([
+                Point::new(0.0, 0.0, z),
+                Point::new(1.0, 0.0, z),
+                Point::new(1.0, 1.0, z),
+                Point::new(0.0, 1.0, z),
+            ]);
+        }
+        mesh.elements
+            .push(HexElement::new([0, 1, 2, 3, 4, 5, 6, 7]));
+        mesh.element_blocks.insert("Plate".to_string(), vec![0]);
+        mesh
+    }
+
+    #[test]
+    fn test_length_inside() {
+        let volume = VolumeIndex::new(&make_plate());
+
+        // Through the full thickness
+        let inside = volume.length_inside(
+            "Plate",
+            &Point::new(0.5, 0.5, 0.0),
+            &Point::new(0.5, 0.5, 0.15),
+        );
+        assert!((inside - 0.1).abs() < 1e-12);
+
+        // Outward from the top face
+        let inside = volume.length_inside(
+            "Plate",
+            &Point::new(0.3, 0.6, 0.1),
+            &Point::new(0.3, 0.6, 0.2),
+        );
+        assert!(inside.abs() < 1e-12);
+
+        assert_eq!(
+            volume.length_inside("Other", &Point::origin(), &Point::new(0.5, 0.5, 0.5)),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_remove_occluded_pairs() {
+        let mesh = make_plate();
+        let volume = VolumeIndex::new(&mesh);
+
+        // Bottom and top faces of the plate as surface A
+        let mut surface_a = SurfaceMesh::new("Plate:patch_0".to_string());
+        surface_a.nodes = mesh.nodes.clone();
+        surface_a.faces = vec![QuadFace::new([0, 3, 2, 1]), QuadFace::new([4, 5, 6, 7])];
+        surface_a.face_normals = vec![Vec3::new(0.0, 0.0, -1.0), Vec3::new(0.0, 0.0, 1.0)];
+        surface_a.face_centroids = vec![Point::new(0.5, 0.5, 0.0), Point::new(0.5, 0.5, 0.1)];
+        surface_a.face_areas = vec![1.0, 1.0];
+
+        // Both faces paired with the same face of a neighbor above the plate
+        let mut results = ContactResults::new(
+            surface_a.part_name.clone(),
+            "Lid:patch_0".to_string(),
+            ContactCriteria::new(0.2, 0.001, 180.0),
+        );
+        for (face_a, distance) in [(0, 0.12), (1, 0.02)] {
+            results.pairs.push(ContactPair {
+                surface_a_face_id: face_a,
+                surface_b_face_id: 0,
+                distance,
+                normal_angle: 0.0,
+                contact_point: Point::new(0.5, 0.5, 0.12),
+            });
+        }
+
+        let removed = remove_occluded_pairs(&mut results, &surface_a, &volume);
+
+        assert_eq!(removed, 1);
+        assert_eq!(results.rejected_occluded, 1);
+        assert_eq!(results.num_pairs(), 1);
+        assert_eq!(results.pairs[0].surface_a_face_id, 1);
+        assert_eq!(results.unpaired_a, vec![0]);
+        assert!(results.unpaired_b.is_empty());
+    }
+}
@@ -0,0 +1,190 @@
+//! Gap trend along a path across a contact interface
+//!
+//! Reports plot the gap of an interface against the distance along a line
+//! drawn across it (ParaView's plot-over-line). The path is a polyline,
+//! sampled evenly by arclength; each sample takes the gap of the nearest face
+//! of surface A, so the profile steps from face to face like the cell data
+//! it is read from.
+
+use crate::contact::deviation::compute_point_deviations;
+use crate::contact::metrics::{per_face_values, FaceValueSource};
+use crate::contact::types::ContactResults;
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::geometry::distance;
+use crate::mesh::types::{Point, SurfaceMesh};
+
+/// Gap at one sample of a path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GapSample {
+    /// Distance along the path from its first vertex
+    pub arclength: f64,
+
+    /// Sample point on the path
+    pub point: Point,
+
+    /// Face of surface A nearest to the sample
+    pub face_index: usize,
+
+    /// Distance from the sample to that face
+    pub offset: f64,
+
+    /// Gap of the face (+ for gap, - for overlap), `None` if unpaired
+    pub gap: Option<f64>,
+
+    /// Angle between the normals of the face's pair in degrees, `None` if
+    /// unpaired
+    pub normal_angle: Option<f64>,
+}
+
+/// `num_samples` points spaced evenly along a polyline, with their arclength
+///
+/// The first and last samples are the ends of the polyline.
+pub fn sample_polyline(vertices: &[Point], num_samples: usize) -> Result<Vec<(f64, Point)>> {
+    if vertices.len() < 2 {
+        return Err(ContactDetectorError::ConfigError(format!(
+            "A path needs at least 2 points, got {}",
+            vertices.len()
+        )));
+    }
+    if num_samples < 2 {
+        return Err(ContactDetectorError::ConfigError(format!(
+            "A path needs at least 2 samples, got {}",
+            num_samples
+        )));
+    }
+
+    // Arclength at each vertex
+    let mut lengths = vec![0.0];
+    for segment in vertices.windows(2) {
+        lengths.push(lengths[lengths.len() - 1] + distance(&segment[0], &segment[1]));
+    }
+    let total = lengths[lengths.len() - 1];
+    if total <= 0.0 {
+        return Err(ContactDetectorError::ConfigError(
+            "The path has zero length".to_string(),
+        ));
+    }
+
+    let mut segment = 0;
+    let samples = (0..num_samples)
+        .map(|i| {
+            let arclength = total * i as f64 / (num_samples - 1) as f64;
+            while segment + 2 < vertices.len() && lengths[segment + 1] < arclength {
+                segment += 1;
+            }
+            let length = lengths[segment + 1] - lengths[segment];
+            let t = if length > 0.0 {
+                ((arclength - lengths[segment]) / length).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let point = vertices[segment] + (vertices[segment + 1] - vertices[segment]) * t;
+            (arclength, point)
+        })
+        .collect();
+    Ok(samples)
+}
+
+/// Sample the gap of a contact pair along a polyline
+///
+/// `results` are the contact results of `surface_a` against `surface_b`.
+/// The path should run along the interface: `offset` tells how far each
+/// sample is from the face it reads.
+#[tracing::instrument(skip_all, fields(surface_a = %surface_a.part_name, samples = num_samples))]
+pub fn probe_gap_along_path(
+    path: &[Point],
+    num_samples: usize,
+    surface_a: &SurfaceMesh,
+    surface_b: &SurfaceMesh,
+    results: &ContactResults,
+) -> Result<Vec<GapSample>> {
+    if surface_a.faces.is_empty() {
+        return Err(ContactDetectorError::InvalidMeshTopology(format!(
+            "Surface '{}' has no faces to probe",
+            surface_a.part_name
+        )));
+    }
+
+    let samples = sample_polyline(path, num_samples)?;
+    let points: Vec<Point> = samples.iter().map(|(_, point)| *point).collect();
+    let nearest = compute_point_deviations(&points, surface_a, None);
+
+    let face_values = |source| per_face_values(results, surface_a, surface_b, true, source);
+    let (gaps, angles, paired) = (
+        face_values(FaceValueSource::Gap),
+        face_values(FaceValueSource::NormalAngle),
+        face_values(FaceValueSource::Paired),
+    );
+
+    Ok(samples
+        .into_iter()
+        .zip(nearest)
+        .filter_map(|((arclength, point), nearest)| {
+            let nearest = nearest?;
+            let face = nearest.face_index;
+            let is_paired = paired[face] > 0.0;
+            Some(GapSample {
+                arclength,
+                point,
+                face_index: face,
+                offset: nearest.deviation.abs(),
+                gap: is_paired.then_some(gaps[face]),
+                normal_angle: is_paired.then_some(angles[face]),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::detection::detect_contact_pairs;
+    use crate::contact::types::ContactCriteria;
+    use crate::mesh::fixtures::strip;
+    use crate::mesh::types::Vec3;
+
+    #[test]
+    fn test_sample_polyline() {
+        let path = [
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(1.0, 3.0, 0.0),
+        ];
+        let samples = sample_polyline(&path, 5).unwrap();
+        assert_eq!(samples.len(), 5);
+        assert_eq!(samples[0], (0.0, path[0]));
+        assert_eq!(samples[1], (1.0, path[1]));
+        assert_eq!(samples[2], (2.0, Point::new(1.0, 1.0, 0.0)));
+        assert_eq!(samples[4], (4.0, path[2]));
+
+        assert!(sample_polyline(&path[..1], 5).is_err());
+        assert!(sample_polyline(&path, 1).is_err());
+        assert!(sample_polyline(&[path[0], path[0]], 5).is_err());
+    }
+
+    #[test]
+    fn test_probe_gap_along_path() {
+        // Upper strip only covers the first two of three faces
+        let lower = strip("Lower", 0.0, 3);
+        let mut upper = strip("Upper", 0.002, 2);
+        upper.face_normals = vec![Vec3::new(0.0, 0.0, -1.0); 2];
+        let criteria = ContactCriteria::new(0.005, 0.001, 180.0);
+        let results = detect_contact_pairs(&lower, &upper, &criteria).unwrap();
+
+        let path = [Point::new(0.5, 0.5, 0.001), Point::new(2.5, 0.5, 0.001)];
+        let samples = probe_gap_along_path(&path, 3, &lower, &upper, &results).unwrap();
+        assert_eq!(samples.len(), 3);
+        assert_eq!(
+            samples.iter().map(|s| s.face_index).collect::<Vec<_>>(),
+            [0, 1, 2]
+        );
+        assert!((samples[1].arclength - 1.0).abs() < 1e-12);
+        assert!((samples[1].offset - 0.001).abs() < 1e-12);
+        assert!((samples[0].gap.unwrap() - 0.002).abs() < 1e-12);
+        assert!(samples[1].normal_angle.is_some());
+        assert_eq!(samples[2].gap, None);
+
+        let empty = SurfaceMesh::new("Empty".to_string());
+        assert!(probe_gap_along_path(&path, 3, &empty, &upper, &results).is_err());
+    }
+}
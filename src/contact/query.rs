@@ -0,0 +1,219 @@
+//! Point queries against extracted surfaces
+//!
+//! Given a coordinate (typically picked in a viewer), find the nearest skin face
+//! and report whether, and with which partner face, it is paired for contact.
+
+use crate::contact::detection::detect_contact_pairs;
+use crate::contact::types::{point_array, ContactCriteria};
+use crate::error::Result;
+use crate::mesh::geometry::distance;
+use crate::mesh::types::{Point, SurfaceMesh, Vec3};
+use serde::{Serialize, Serializer};
+
+/// Partner face paired with a queried face
+#[derive(Debug, Clone, Serialize)]
+pub struct FacePartner {
+    /// Name of the partner surface
+    pub surface_name: String,
+
+    /// Face index on the partner surface
+    pub face_index: usize,
+
+    /// Signed gap between the faces (+ for gap, - for overlap)
+    pub gap: f64,
+
+    /// Angle between the face normals in degrees
+    pub normal_angle: f64,
+
+    /// Centroid of the partner face
+    #[serde(with = "point_array")]
+    pub centroid: Point,
+}
+
+/// Result of a point query
+#[derive(Debug, Clone, Serialize)]
+pub struct FaceQuery {
+    /// Queried point
+    #[serde(with = "point_array")]
+    pub point: Point,
+
+    /// Index of the surface containing the nearest face
+    pub surface_index: usize,
+
+    /// Name of the surface (patch) containing the nearest face
+    pub surface_name: String,
+
+    /// Element block the surface was extracted from
+    pub block_name: String,
+
+    /// Face index within the surface
+    pub face_index: usize,
+
+    /// Centroid of the nearest face
+    #[serde(with = "point_array")]
+    pub centroid: Point,
+
+    /// Outward normal of the nearest face
+    #[serde(serialize_with = "vector_array")]
+    pub normal: Vec3,
+
+    /// Area of the nearest face
+    pub area: f64,
+
+    /// Distance from the queried point to the face centroid
+    pub distance: f64,
+
+    /// Contact partners of this face (empty if unpaired)
+    pub partners: Vec<FacePartner>,
+}
+
+/// Serialize a vector as `[x, y, z]`
+fn vector_array<S: Serializer>(
+    vector: &Vec3,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    [vector.x, vector.y, vector.z].serialize(serializer)
+}
+
+impl FaceQuery {
+    /// Whether the face is paired with at least one other face
+    pub fn is_paired(&self) -> bool {
+        !self.partners.is_empty()
+    }
+}
+
+/// Find the face whose centroid is nearest to `point`
+///
+/// Returns `(surface_index, face_index, distance)`, or `None` if there are no faces.
+pub fn find_nearest_face(surfaces: &[SurfaceMesh], point: &Point) -> Option<(usize, usize, f64)> {
+    let mut best: Option<(usize, usize, f64)> = None;
+
+    for (surface_idx, surface) in surfaces.iter().enumerate() {
+        for (face_idx, centroid) in surface.face_centroids.iter().enumerate() {
+            let d = distance(point, centroid);
+            if best.is_none_or(|(_, _, best_d)| d < best_d) {
+                best = Some((surface_idx, face_idx, d));
+            }
+        }
+    }
+
+    best
+}
+
+/// Find the nearest face to `point` and its contact partners
+///
+/// Pairing is evaluated the same way as `auto-contact`: every other surface is
+/// tested with the lower surface index as surface A.
+#[tracing::instrument(skip_all)]
+pub fn query_point(
+    surfaces: &[SurfaceMesh],
+    point: &Point,
+    criteria: &ContactCriteria,
+) -> Result<Option<FaceQuery>> {
+    let Some((surface_idx, face_idx, dist)) = find_nearest_face(surfaces, point) else {
+        return Ok(None);
+    };
+
+    let surface = &surfaces[surface_idx];
+    let mut partners = Vec::new();
+
+    for (other_idx, other) in surfaces.iter().enumerate() {
+        if other_idx == surface_idx {
+            continue;
+        }
+
+        let queried_is_a = surface_idx < other_idx;
+        let results = if queried_is_a {
+            detect_contact_pairs(surface, other, criteria)?
+        } else {
+            detect_contact_pairs(other, surface, criteria)?
+        };
+
+        for pair in results.pairs_for_face(queried_is_a, face_idx) {
+            let partner_face = if queried_is_a {
+                pair.surface_b_face_id
+            } else {
+                pair.surface_a_face_id
+            };
+
+            partners.push(FacePartner {
+                surface_name: other.part_name.clone(),
+                face_index: partner_face,
+                gap: pair.distance,
+                normal_angle: pair.normal_angle,
+                centroid: other.face_centroids[partner_face],
+            });
+        }
+    }
+
+    Ok(Some(FaceQuery {
+        point: *point,
+        surface_index: surface_idx,
+        surface_name: surface.part_name.clone(),
+        block_name: surface.block_name().to_string(),
+        face_index: face_idx,
+        centroid: surface.face_centroids[face_idx],
+        normal: surface.face_normals[face_idx],
+        area: surface.face_areas[face_idx],
+        distance: dist,
+        partners,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::types::QuadFace;
+
+    fn make_square(name: &str, z: f64, normal_z: f64) -> SurfaceMesh {
+        SurfaceMesh {
+            part_name: name.to_string(),
+            faces: vec![QuadFace::new([0, 1, 2, 3])],
+            face_normals: vec![Vec3::new(0.0, 0.0, normal_z)],
+            face_centroids: vec![Point::new(0.5, 0.5, z)],
+            face_areas: vec![1.0],
+            nodes: vec![
+                Point::new(0.0, 0.0, z),
+                Point::new(1.0, 0.0, z),
+                Point::new(1.0, 1.0, z),
+                Point::new(0.0, 1.0, z),
+            ],
+            face_tags: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_find_nearest_face() {
+        let surfaces = vec![
+            make_square("Block1:patch_0", 0.0, 1.0),
+            make_square("Block2:patch_0", 5.0, -1.0),
+        ];
+
+        let (surface_idx, face_idx, _) =
+            find_nearest_face(&surfaces, &Point::new(0.5, 0.5, 4.0)).unwrap();
+        assert_eq!(surface_idx, 1);
+        assert_eq!(face_idx, 0);
+
+        assert!(find_nearest_face(&[], &Point::origin()).is_none());
+    }
+
+    #[test]
+    fn test_query_point_reports_partner() {
+        let surfaces = vec![
+            make_square("Block1:patch_0", 0.0, 1.0),
+            make_square("Block2:patch_0", 0.001, -1.0),
+        ];
+        let criteria = ContactCriteria::new(0.005, 0.001, 180.0);
+
+        // Query from B's side so the queried face is surface B in the detection
+        let query = query_point(&surfaces, &Point::new(0.5, 0.5, 0.01), &criteria)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(query.surface_name, "Block2:patch_0");
+        assert_eq!(query.block_name, "Block2");
+        assert!(query.is_paired());
+        assert_eq!(query.partners[0].surface_name, "Block1:patch_0");
+        assert!((query.partners[0].gap - 0.001).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,243 @@
+//! Mesh density mismatch at contact interfaces
+//!
+//! Contact solvers converge poorly when the two sides of an interface are
+//! meshed at very different densities. For each detected pair this compares
+//! the size of every paired face with the size of its partner and flags
+//! interfaces whose typical size ratio exceeds a limit, with a suggestion of
+//! which side to refine.
+
+use crate::contact::types::ContactResults;
+use crate::error::{ContactDetectorError, Result};
+use crate::format::Glyphs;
+use crate::mesh::types::SurfaceMesh;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Default limit on the master/slave face size ratio
+pub const DEFAULT_MAX_SIZE_RATIO: f64 = 4.0;
+
+/// Mesh density comparison of one contact interface
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeshDensityAssessment {
+    /// Master surface (surface A)
+    pub master: String,
+
+    /// Slave surface (surface B)
+    pub slave: String,
+
+    /// Number of face pairs compared
+    pub num_face_pairs: usize,
+
+    /// Median ratio of master to slave face size (edge length, i.e. the
+    /// square root of the area ratio); above 1 the master is coarser
+    pub median_size_ratio: f64,
+
+    /// Smallest per-pair size ratio
+    pub min_size_ratio: f64,
+
+    /// Largest per-pair size ratio
+    pub max_size_ratio: f64,
+
+    /// Whether the median ratio exceeds the limit in either direction
+    pub flagged: bool,
+
+    /// Suggested action for flagged interfaces
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl MeshDensityAssessment {
+    /// Compare face sizes across the pairs of `results`
+    ///
+    /// Returns `None` if there are no pairs. The interface is flagged when
+    /// either side is more than `max_ratio` times coarser than the other.
+    pub fn compute(
+        results: &ContactResults,
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+        max_ratio: f64,
+    ) -> Option<Self> {
+        let mut ratios: Vec<f64> = results
+            .pairs
+            .iter()
+            .filter_map(|pair| {
+                let area_a = surface_a.face_areas[pair.surface_a_face_id];
+                let area_b = surface_b.face_areas[pair.surface_b_face_id];
+                (area_a > 0.0 && area_b > 0.0).then(|| (area_a / area_b).sqrt())
+            })
+            .collect();
+        if ratios.is_empty() {
+            return None;
+        }
+        ratios.sort_by(f64::total_cmp);
+
+        let mid = ratios.len() / 2;
+        let median = if ratios.len().is_multiple_of(2) {
+            (ratios[mid - 1] + ratios[mid]) / 2.0
+        } else {
+            ratios[mid]
+        };
+
+        let suggestion = if median > max_ratio {
+            Some(format!(
+                "refine master '{}' (faces {:.1}x larger than on '{}')",
+                surface_a.part_name, median, surface_b.part_name
+            ))
+        } else if median < 1.0 / max_ratio {
+            Some(format!(
+                "refine slave '{}' (faces {:.1}x larger than on '{}')",
+                surface_b.part_name,
+                1.0 / median,
+                surface_a.part_name
+            ))
+        } else {
+            None
+        };
+
+        Some(Self {
+            master: surface_a.part_name.clone(),
+            slave: surface_b.part_name.clone(),
+            num_face_pairs: ratios.len(),
+            median_size_ratio: median,
+            min_size_ratio: ratios[0],
+            max_size_ratio: ratios[ratios.len() - 1],
+            flagged: suggestion.is_some(),
+            suggestion,
+        })
+    }
+}
+
+/// Mesh refinement report over all detected interfaces
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RefinementReport {
+    /// Size ratio limit used for flagging
+    pub max_size_ratio: f64,
+
+    /// One assessment per detected pair
+    pub interfaces: Vec<MeshDensityAssessment>,
+}
+
+impl RefinementReport {
+    /// Create an empty report with the given limit
+    pub fn new(max_size_ratio: f64) -> Self {
+        Self {
+            max_size_ratio,
+            interfaces: Vec::new(),
+        }
+    }
+
+    /// Interfaces exceeding the size ratio limit
+    pub fn flagged(&self) -> impl Iterator<Item = &MeshDensityAssessment> {
+        self.interfaces.iter().filter(|i| i.flagged)
+    }
+
+    /// Print the flagged interfaces to the console
+    pub fn print_summary(&self, glyphs: Glyphs) {
+        println!("\n{}", "=".repeat(60));
+        println!("MESH DENSITY AT CONTACT INTERFACES");
+        println!("{}", "=".repeat(60));
+        println!();
+        println!(
+            "  Interfaces checked: {} (limit {:.1}x)",
+            self.interfaces.len(),
+            self.max_size_ratio
+        );
+
+        let flagged: Vec<_> = self.flagged().collect();
+        if flagged.is_empty() {
+            println!("  No interface exceeds the size ratio limit");
+        } else {
+            println!("  Flagged:            {}", flagged.len());
+            println!();
+            for assessment in flagged {
+                println!(
+                    "  {} {} {}: median size ratio {:.2} (range {:.2}-{:.2})",
+                    assessment.master,
+                    glyphs.between(),
+                    assessment.slave,
+                    assessment.median_size_ratio,
+                    assessment.min_size_ratio,
+                    assessment.max_size_ratio
+                );
+                if let Some(suggestion) = &assessment.suggestion {
+                    println!("    -> {}", suggestion);
+                }
+            }
+        }
+        println!();
+        println!("{}", "=".repeat(60));
+    }
+
+    /// Export the report to a JSON file
+    pub fn export<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            ContactDetectorError::ConfigError(format!(
+                "Failed to serialize refinement report: {}",
+                e
+            ))
+        })?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::{ContactCriteria, ContactPair};
+    use crate::mesh::types::Point;
+
+    fn surface_with_areas(name: &str, areas: &[f64]) -> SurfaceMesh {
+        let mut surface = SurfaceMesh::new(name.to_string());
+        surface.face_areas = areas.to_vec();
+        surface
+    }
+
+    fn results_for(pairs: &[(usize, usize)]) -> ContactResults {
+        let mut results =
+            ContactResults::new("A".to_string(), "B".to_string(), ContactCriteria::default());
+        for &(a, b) in pairs {
+            results.pairs.push(ContactPair {
+                surface_a_face_id: a,
+                surface_b_face_id: b,
+                distance: 0.0,
+                normal_angle: 0.0,
+                contact_point: Point::origin(),
+            });
+        }
+        results
+    }
+
+    #[test]
+    fn test_coarse_master_is_flagged() {
+        // Master faces 25x the area (5x the size) of slave faces
+        let a = surface_with_areas("A", &[25.0, 25.0]);
+        let b = surface_with_areas("B", &[1.0, 1.0, 1.0]);
+        let results = results_for(&[(0, 0), (0, 1), (1, 2)]);
+
+        let assessment =
+            MeshDensityAssessment::compute(&results, &a, &b, DEFAULT_MAX_SIZE_RATIO).unwrap();
+        assert_eq!(assessment.num_face_pairs, 3);
+        assert!((assessment.median_size_ratio - 5.0).abs() < 1e-12);
+        assert!(assessment.flagged);
+        assert!(assessment.suggestion.unwrap().starts_with("refine master"));
+    }
+
+    #[test]
+    fn test_similar_density_and_coarse_slave() {
+        let a = surface_with_areas("A", &[1.0, 4.0]);
+        let b = surface_with_areas("B", &[1.0, 1.0]);
+        let assessment =
+            MeshDensityAssessment::compute(&results_for(&[(0, 0), (1, 1)]), &a, &b, 4.0).unwrap();
+        assert!((assessment.median_size_ratio - 1.5).abs() < 1e-12);
+        assert!(!assessment.flagged);
+
+        let coarse_slave = surface_with_areas("B", &[100.0]);
+        let assessment =
+            MeshDensityAssessment::compute(&results_for(&[(0, 0)]), &a, &coarse_slave, 4.0)
+                .unwrap();
+        assert!(assessment.suggestion.unwrap().starts_with("refine slave"));
+
+        assert!(MeshDensityAssessment::compute(&results_for(&[]), &a, &b, 4.0).is_none());
+    }
+}
@@ -0,0 +1,384 @@
+//! Tolerances relative to the size of the parts
+//!
+//! A gap tolerance that suits a 50 mm bracket is far too loose for a 2 mm pin
+//! and too tight for a 2 m frame. Given as a percentage of a part size
+//! (`"0.5%"`), the gap tolerance is instead resolved for each pair of
+//! surfaces as it is tested, so one configuration serves assemblies of any
+//! scale. A part is sized by the diagonal of its bounding box or by the
+//! median edge length of its elements ([`PartSizeMeasure`]); a pair takes the
+//! smaller of its two parts, so that a small part is never paired across a
+//! gap sized for its large neighbour.
+//!
+//! ```text
+//! --max-gap 0.5%          0.5 % of the bounding box diagonal
+//! --max-gap 40%:edge      40 % of the median element edge length
+//! ```
+
+use crate::contact::types::ContactCriteria;
+use crate::mesh::stats::Distribution;
+use crate::mesh::types::{Mesh, Point, SurfaceMesh};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// How the size of a part is measured for relative tolerances
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartSizeMeasure {
+    /// Diagonal of the axis-aligned bounding box
+    #[default]
+    BoundingBoxDiagonal,
+
+    /// Median length of the element (or face) edges
+    MedianEdgeLength,
+}
+
+impl PartSizeMeasure {
+    /// Name used after the `:` of a relative tolerance
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BoundingBoxDiagonal => "diagonal",
+            Self::MedianEdgeLength => "edge",
+        }
+    }
+
+    /// What is measured, for reports
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::BoundingBoxDiagonal => "bounding box diagonal",
+            Self::MedianEdgeLength => "median edge length",
+        }
+    }
+
+    /// Size of every part (element block) of `mesh`; blocks without
+    /// elements are left out
+    pub fn of_blocks(&self, mesh: &Mesh) -> HashMap<String, f64> {
+        mesh.element_blocks
+            .iter()
+            .filter_map(|(name, elements)| {
+                let faces: Vec<[Point; 4]> = elements
+                    .iter()
+                    .filter_map(|&idx| mesh.elements.get(idx))
+                    .flat_map(|element| element.faces())
+                    .map(|face| face.node_ids.map(|n| mesh.nodes[n]))
+                    .collect();
+                let size = self.of_polygons(faces.iter().map(|face| &face[..]))?;
+                Some((name.clone(), size))
+            })
+            .collect()
+    }
+
+    /// Size of a surface from its faces alone, for parts known only by their
+    /// surface
+    pub fn of_surface(&self, surface: &SurfaceMesh) -> Option<f64> {
+        let faces: Vec<Vec<Point>> = surface
+            .faces
+            .iter()
+            .filter_map(|face| {
+                face.corners()
+                    .iter()
+                    .map(|&n| surface.nodes.get(n).copied())
+                    .collect()
+            })
+            .collect();
+        self.of_polygons(faces.iter().map(Vec::as_slice))
+    }
+
+    /// Size of a set of faces given by their corners, `None` if there are
+    /// none
+    fn of_polygons<'p>(&self, faces: impl Iterator<Item = &'p [Point]>) -> Option<f64> {
+        match self {
+            Self::BoundingBoxDiagonal => {
+                let grow = |bounds: Option<(Point, Point)>, p: &Point| match bounds {
+                    None => Some((*p, *p)),
+                    Some((lo, hi)) => Some((lo.inf(p), hi.sup(p))),
+                };
+                let (lo, hi) = faces.flatten().fold(None, grow)?;
+                Some((hi - lo).norm())
+            }
+            Self::MedianEdgeLength => {
+                let lengths = faces
+                    .flat_map(|corners| {
+                        (0..corners.len())
+                            .map(move |i| (corners[(i + 1) % corners.len()] - corners[i]).norm())
+                    })
+                    .collect();
+                Distribution::from_values(lengths).map(|d| d.median)
+            }
+        }
+    }
+}
+
+/// Tolerance given as a percentage of the size of the parts in contact
+///
+/// Written as a percentage string in config files, like on the command line
+/// (`"0.5%"`, `"40%:edge"`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RelativeTolerance {
+    /// Percentage of the part size (0.5 for "0.5%")
+    pub percent: f64,
+
+    /// How the size of a part is measured
+    pub measure: PartSizeMeasure,
+}
+
+impl RelativeTolerance {
+    /// The tolerance for a pair of parts of the given sizes (the smaller one
+    /// counts)
+    pub fn resolve(&self, size_a: f64, size_b: f64) -> f64 {
+        self.percent / 100.0 * size_a.min(size_b)
+    }
+}
+
+impl FromStr for RelativeTolerance {
+    type Err = String;
+
+    /// Parse "P%" or "P%:MEASURE", MEASURE being "diagonal" (the default)
+    /// or "edge"
+    fn from_str(value: &str) -> std::result::Result<Self, String> {
+        let invalid = || {
+            format!(
+                "Invalid relative tolerance '{}': expected a percentage such as \"0.5%\", \
+                 optionally followed by \":diagonal\" or \":edge\"",
+                value
+            )
+        };
+        let (percent, measure) = value.trim().split_once('%').ok_or_else(invalid)?;
+        let measure = match measure.trim() {
+            "" | ":diagonal" => PartSizeMeasure::BoundingBoxDiagonal,
+            ":edge" => PartSizeMeasure::MedianEdgeLength,
+            _ => return Err(invalid()),
+        };
+        match percent.trim().parse::<f64>() {
+            Ok(percent) if percent.is_finite() && percent > 0.0 => Ok(Self { percent, measure }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl TryFrom<String> for RelativeTolerance {
+    type Error = String;
+
+    fn try_from(value: String) -> std::result::Result<Self, String> {
+        value.parse()
+    }
+}
+
+impl From<RelativeTolerance> for String {
+    fn from(tolerance: RelativeTolerance) -> Self {
+        tolerance.to_string()
+    }
+}
+
+impl fmt::Display for RelativeTolerance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}%", self.percent)?;
+        if self.measure != PartSizeMeasure::default() {
+            write!(f, ":{}", self.measure.name())?;
+        }
+        Ok(())
+    }
+}
+
+/// Gap tolerance given either as a distance or relative to the part size
+///
+/// Written as a number (`0.005`) or a percentage string (`"0.5%"`,
+/// `"40%:edge"`).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "GapToleranceRepr", into = "GapToleranceRepr")]
+pub enum GapTolerance {
+    /// Fixed distance
+    Absolute(f64),
+
+    /// Fraction of the size of the parts, resolved per pair
+    Relative(RelativeTolerance),
+}
+
+impl GapTolerance {
+    /// Set the gap tolerance of `criteria`
+    pub fn apply(&self, criteria: &mut ContactCriteria) {
+        match *self {
+            GapTolerance::Absolute(distance) => {
+                criteria.max_gap_distance = distance;
+                criteria.relative_max_gap = None;
+            }
+            GapTolerance::Relative(relative) => criteria.relative_max_gap = Some(relative),
+        }
+    }
+}
+
+impl FromStr for GapTolerance {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, String> {
+        if value.contains('%') {
+            return value.parse().map(GapTolerance::Relative);
+        }
+        value
+            .trim()
+            .parse()
+            .map(GapTolerance::Absolute)
+            .map_err(|_| {
+                format!(
+                    "Invalid gap tolerance '{}': expected a distance or a percentage",
+                    value
+                )
+            })
+    }
+}
+
+impl fmt::Display for GapTolerance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GapTolerance::Absolute(distance) => write!(f, "{}", distance),
+            GapTolerance::Relative(relative) => write!(f, "{}", relative),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum GapToleranceRepr {
+    Absolute(f64),
+    Text(String),
+}
+
+impl TryFrom<GapToleranceRepr> for GapTolerance {
+    type Error = String;
+
+    fn try_from(repr: GapToleranceRepr) -> std::result::Result<Self, String> {
+        match repr {
+            GapToleranceRepr::Absolute(distance) => Ok(GapTolerance::Absolute(distance)),
+            GapToleranceRepr::Text(text) => text.parse(),
+        }
+    }
+}
+
+impl From<GapTolerance> for GapToleranceRepr {
+    fn from(tolerance: GapTolerance) -> Self {
+        match tolerance {
+            GapTolerance::Absolute(distance) => GapToleranceRepr::Absolute(distance),
+            relative => GapToleranceRepr::Text(relative.to_string()),
+        }
+    }
+}
+
+impl ContactCriteria {
+    /// Criteria for a pair of parts of the given sizes: a relative gap
+    /// tolerance is resolved to a distance, the rest is unchanged
+    pub fn for_part_sizes(&self, size_a: f64, size_b: f64) -> ContactCriteria {
+        let mut criteria = self.clone();
+        if let Some(relative) = criteria.relative_max_gap.take() {
+            criteria.max_gap_distance = relative.resolve(size_a, size_b);
+        }
+        criteria
+    }
+
+    /// Criteria for a pair of surfaces, sized by their own faces if the gap
+    /// tolerance is relative (and not resolved for their parts yet)
+    pub fn for_surfaces(&self, surface_a: &SurfaceMesh, surface_b: &SurfaceMesh) -> Cow<'_, Self> {
+        self.for_parts(&HashMap::new(), surface_a, surface_b)
+    }
+
+    /// Criteria for a pair of surfaces, sized by the part (block) they belong
+    /// to in `part_sizes` (see [`PartSizeMeasure::of_blocks`]) or else by
+    /// their own faces, if the gap tolerance is relative
+    pub fn for_parts(
+        &self,
+        part_sizes: &HashMap<String, f64>,
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+    ) -> Cow<'_, Self> {
+        let Some(relative) = &self.relative_max_gap else {
+            return Cow::Borrowed(self);
+        };
+        let size = |surface: &SurfaceMesh| {
+            part_sizes
+                .get(surface.block_name())
+                .copied()
+                .or_else(|| relative.measure.of_surface(surface))
+                .unwrap_or(0.0)
+        };
+        Cow::Owned(self.for_part_sizes(size(surface_a), size(surface_b)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::types::{HexElement, QuadFace};
+
+    #[test]
+    fn test_parse_gap_tolerance() {
+        assert_eq!(
+            "0.01".parse::<GapTolerance>(),
+            Ok(GapTolerance::Absolute(0.01))
+        );
+        let relative: GapTolerance = "0.5%".parse().unwrap();
+        assert_eq!(
+            relative,
+            GapTolerance::Relative(RelativeTolerance {
+                percent: 0.5,
+                measure: PartSizeMeasure::BoundingBoxDiagonal,
+            })
+        );
+        let edge: RelativeTolerance = "40%:edge".parse().unwrap();
+        assert_eq!(edge.measure, PartSizeMeasure::MedianEdgeLength);
+        assert_eq!(edge.to_string(), "40%:edge");
+        for invalid in ["%", "-1%", "1%:volume", "abc"] {
+            assert!(invalid.parse::<GapTolerance>().is_err(), "{}", invalid);
+        }
+
+        // Numbers and percentage strings in config files
+        let parsed: Vec<GapTolerance> = serde_json::from_str(r#"[0.002, "0.5%"]"#).unwrap();
+        assert_eq!(parsed, [GapTolerance::Absolute(0.002), relative]);
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), r#"[0.002,"0.5%"]"#);
+    }
+
+    #[test]
+    fn test_relative_gap_per_pair() {
+        // A 2 x 1 x 1 block of two unit cubes
+        let mut mesh = Mesh::new();
+        for x in 0..3 {
+            for (y, z) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+                mesh.nodes.push(Point::new(x as f64, y, z));
+            }
+        }
+        mesh.elements = vec![
+            HexElement::new([0, 4, 5, 1, 3, 7, 6, 2]),
+            HexElement::new([4, 8, 9, 5, 7, 11, 10, 6]),
+        ];
+        mesh.element_blocks.insert("Bar".to_string(), vec![0, 1]);
+
+        let diagonal = PartSizeMeasure::BoundingBoxDiagonal.of_blocks(&mesh);
+        assert!((diagonal["Bar"] - 6f64.sqrt()).abs() < 1e-12);
+        let edge = PartSizeMeasure::MedianEdgeLength.of_blocks(&mesh);
+        assert!((edge["Bar"] - 1.0).abs() < 1e-12);
+
+        let mut criteria = ContactCriteria::default();
+        GapTolerance::Relative("10%".parse().unwrap()).apply(&mut criteria);
+        let resolved = criteria.for_part_sizes(2.0, 0.5);
+        assert!((resolved.max_gap_distance - 0.05).abs() < 1e-12);
+        assert!(resolved.relative_max_gap.is_none());
+
+        let mut square = SurfaceMesh::new("Square".to_string());
+        square.nodes = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(3.0, 0.0, 0.0),
+            Point::new(3.0, 4.0, 0.0),
+            Point::new(0.0, 4.0, 0.0),
+        ];
+        square.faces.push(QuadFace::new([0, 1, 2, 3]));
+        let resolved = criteria.for_surfaces(&square, &square);
+        assert!((resolved.max_gap_distance - 0.5).abs() < 1e-12);
+
+        GapTolerance::Absolute(0.01).apply(&mut criteria);
+        assert!(matches!(
+            criteria.for_surfaces(&square, &square),
+            Cow::Borrowed(_)
+        ));
+    }
+}
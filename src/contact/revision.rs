@@ -0,0 +1,352 @@
+//! Matching contact pairs across design revisions
+//!
+//! Surface names such as `Block:patch_3` follow the patch numbering, which
+//! changes whenever a part is re-meshed or a patch is split or merged, so
+//! pairs of two runs cannot be matched by name. Instead the contact patches
+//! are matched by location: two pairs are the same interface if their contact
+//! points overlap in space. Each pair of either run is then classified as
+//! persisting, added (only in the new run) or removed (only in the old run).
+
+use crate::contact::types::ContactResults;
+use crate::format::{FloatFormat, Glyphs};
+use kiddo::ImmutableKdTree;
+use serde::{Deserialize, Serialize};
+use std::num::NonZero;
+
+/// Smallest overlap for two pairs to count as the same interface
+pub const MIN_PATCH_OVERLAP: f64 = 0.1;
+
+/// What happened to a contact pair between two revisions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContactEvent {
+    /// Present in both revisions
+    Persisted,
+
+    /// Only in the new revision
+    Added,
+
+    /// Only in the old revision
+    Removed,
+}
+
+impl ContactEvent {
+    /// Lowercase event name, as serialized
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContactEvent::Persisted => "persisted",
+            ContactEvent::Added => "added",
+            ContactEvent::Removed => "removed",
+        }
+    }
+}
+
+/// Side of a matched pair in one revision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevisionPair {
+    /// Index of the pair in its run
+    pub index: usize,
+
+    /// Surface names of the pair in its run
+    pub surface_a: String,
+    pub surface_b: String,
+
+    /// Number of face pairs
+    pub num_pairs: usize,
+
+    /// Mean signed distance of the face pairs
+    pub avg_distance: f64,
+}
+
+impl RevisionPair {
+    fn new(index: usize, results: &ContactResults) -> Self {
+        Self {
+            index,
+            surface_a: results.surface_a_name.clone(),
+            surface_b: results.surface_b_name.clone(),
+            num_pairs: results.num_pairs(),
+            avg_distance: results.avg_distance(),
+        }
+    }
+}
+
+/// A contact pair tracked from an old to a new revision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PairMatch {
+    pub event: ContactEvent,
+
+    /// The pair in the old revision (none if added)
+    pub before: Option<RevisionPair>,
+
+    /// The pair in the new revision (none if removed)
+    pub after: Option<RevisionPair>,
+
+    /// Fraction of the contact points of both pairs lying within the
+    /// matching tolerance of a contact point of the other (0 if unmatched)
+    pub overlap: f64,
+}
+
+/// Contact pairs of two revisions matched by the overlap of their patches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevisionComparison {
+    /// Persisting pairs (in the old run's order), then removed and added
+    /// pairs
+    pub matches: Vec<PairMatch>,
+}
+
+impl RevisionComparison {
+    /// Match the pairs of `before` to those of `after`
+    ///
+    /// Contact points count as overlapping within `tolerance`, by default
+    /// the larger typical spacing of the contact points of the two patches (a
+    /// re-meshed patch's points lie within about one spacing of the old
+    /// ones). Pairs are matched one to one, best overlap first, down to
+    /// [`MIN_PATCH_OVERLAP`].
+    pub fn compare(
+        before: &[ContactResults],
+        after: &[ContactResults],
+        tolerance: Option<f64>,
+    ) -> Self {
+        let patches_before: Vec<Patch> = before.iter().map(Patch::new).collect();
+        let patches_after: Vec<Patch> = after.iter().map(Patch::new).collect();
+
+        let mut candidates: Vec<(f64, usize, usize)> = Vec::new();
+        for (i, old) in patches_before.iter().enumerate() {
+            for (j, new) in patches_after.iter().enumerate() {
+                let tolerance = tolerance.unwrap_or(old.spacing.max(new.spacing));
+                let overlap = old.overlap(new, tolerance);
+                if overlap >= MIN_PATCH_OVERLAP {
+                    candidates.push((overlap, i, j));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+        let mut matched_before: Vec<Option<(usize, f64)>> = vec![None; before.len()];
+        let mut matched_after = vec![false; after.len()];
+        for (overlap, i, j) in candidates {
+            if matched_before[i].is_none() && !matched_after[j] {
+                matched_before[i] = Some((j, overlap));
+                matched_after[j] = true;
+            }
+        }
+
+        let mut matches = Vec::new();
+        let mut removed = Vec::new();
+        for (i, matched) in matched_before.iter().enumerate() {
+            let old = Some(RevisionPair::new(i, &before[i]));
+            match matched {
+                Some((j, overlap)) => matches.push(PairMatch {
+                    event: ContactEvent::Persisted,
+                    before: old,
+                    after: Some(RevisionPair::new(*j, &after[*j])),
+                    overlap: *overlap,
+                }),
+                None => removed.push(PairMatch {
+                    event: ContactEvent::Removed,
+                    before: old,
+                    after: None,
+                    overlap: 0.0,
+                }),
+            }
+        }
+        matches.extend(removed);
+        matches.extend(
+            (0..after.len())
+                .filter(|&j| !matched_after[j])
+                .map(|j| PairMatch {
+                    event: ContactEvent::Added,
+                    before: None,
+                    after: Some(RevisionPair::new(j, &after[j])),
+                    overlap: 0.0,
+                }),
+        );
+
+        Self { matches }
+    }
+
+    /// Number of pairs with the given event
+    pub fn count(&self, event: ContactEvent) -> usize {
+        self.matches.iter().filter(|m| m.event == event).count()
+    }
+
+    /// Print the matched, removed and added pairs
+    pub fn print_summary(&self, format: FloatFormat, glyphs: Glyphs) {
+        println!("\n{}", "=".repeat(60));
+        println!("CONTACT PAIRS ACROSS REVISIONS");
+        println!("{}", "=".repeat(60));
+        println!();
+        println!("  Persisted:       {}", self.count(ContactEvent::Persisted));
+        println!("  Removed:         {}", self.count(ContactEvent::Removed));
+        println!("  Added:           {}", self.count(ContactEvent::Added));
+        println!();
+
+        let describe = |pair: &RevisionPair| {
+            format!(
+                "#{} {} {} {} ({} pairs, avg distance {})",
+                pair.index + 1,
+                pair.surface_a,
+                glyphs.between(),
+                pair.surface_b,
+                pair.num_pairs,
+                format.show(pair.avg_distance)
+            )
+        };
+        for m in &self.matches {
+            match (&m.before, &m.after) {
+                (Some(before), Some(after)) => {
+                    println!("  persisted ({:.0}% overlap)", 100.0 * m.overlap);
+                    println!("    before: {}", describe(before));
+                    println!("    after:  {}", describe(after));
+                }
+                (Some(pair), None) | (None, Some(pair)) => {
+                    println!("  {}", m.event.as_str());
+                    println!("    {}", describe(pair));
+                }
+                (None, None) => {}
+            }
+        }
+
+        println!("{}", "=".repeat(60));
+    }
+}
+
+/// Contact points of one pair, for overlap queries
+struct Patch {
+    points: Vec<[f64; 3]>,
+    tree: ImmutableKdTree<f64, 3>,
+
+    /// Median distance of a contact point to its nearest neighbor
+    spacing: f64,
+}
+
+impl Patch {
+    fn new(results: &ContactResults) -> Self {
+        let points: Vec<[f64; 3]> = results
+            .pairs
+            .iter()
+            .map(|pair| {
+                let p = &pair.contact_point;
+                [p.x, p.y, p.z]
+            })
+            .collect();
+        let tree: ImmutableKdTree<f64, 3> = ImmutableKdTree::new_from_slice(&points);
+
+        // The nearest neighbor of a point other than itself is the second
+        let mut spacings: Vec<f64> = if points.len() > 1 {
+            points
+                .iter()
+                .map(|p| {
+                    tree.nearest_n::<kiddo::SquaredEuclidean>(p, NonZero::new(2).unwrap())[1]
+                        .distance
+                        .sqrt()
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        spacings.sort_by(f64::total_cmp);
+        let spacing = spacings.get(spacings.len() / 2).copied().unwrap_or(0.0);
+
+        Self {
+            points,
+            tree,
+            spacing,
+        }
+    }
+
+    /// Number of these points with a point of `other` within `tolerance`
+    fn covered_by(&self, other: &Patch, tolerance: f64) -> usize {
+        if other.points.is_empty() {
+            return 0;
+        }
+        self.points
+            .iter()
+            .filter(|p| {
+                other
+                    .tree
+                    .nearest_n::<kiddo::SquaredEuclidean>(p, NonZero::<usize>::MIN)[0]
+                    .distance
+                    <= tolerance * tolerance
+            })
+            .count()
+    }
+
+    /// Fraction of the points of both patches covered by the other
+    fn overlap(&self, other: &Patch, tolerance: f64) -> f64 {
+        let total = self.points.len() + other.points.len();
+        if total == 0 {
+            return 0.0;
+        }
+        (self.covered_by(other, tolerance) + other.covered_by(self, tolerance)) as f64
+            / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::{ContactCriteria, ContactPair};
+    use crate::mesh::types::Point;
+
+    /// Pair with contact points at the centers of an n x n grid over a
+    /// `size` square at `origin` in the plane z = 0
+    fn patch(a: &str, b: &str, origin: [f64; 2], size: f64, n: usize) -> ContactResults {
+        let mut results =
+            ContactResults::new(a.to_string(), b.to_string(), ContactCriteria::default());
+        let h = size / n as f64;
+        for i in 0..n * n {
+            let (x, y) = ((i % n) as f64 + 0.5, (i / n) as f64 + 0.5);
+            results.pairs.push(ContactPair {
+                surface_a_face_id: i,
+                surface_b_face_id: i,
+                distance: 0.001,
+                normal_angle: 180.0,
+                contact_point: Point::new(origin[0] + x * h, origin[1] + y * h, 0.0),
+            });
+        }
+        results
+    }
+
+    #[test]
+    fn test_revision_matching() {
+        // Two interfaces, re-meshed and renumbered (patch_0 and patch_1
+        // swapped); a third interface disappears and a new one shows up
+        let before = vec![
+            patch("A:patch_0", "B:patch_0", [0.0, 0.0], 1.0, 10),
+            patch("A:patch_1", "C:patch_0", [5.0, 0.0], 1.0, 10),
+            patch("A:patch_2", "D:patch_0", [10.0, 0.0], 1.0, 10),
+        ];
+        let after = vec![
+            patch("A:patch_0", "C:patch_0", [5.0, 0.0], 1.0, 7),
+            patch("A:patch_1", "B:patch_0", [0.0, 0.0], 1.0, 13),
+            patch("A:patch_2", "E:patch_0", [0.0, 5.0], 1.0, 10),
+        ];
+
+        let comparison = RevisionComparison::compare(&before, &after, None);
+        assert_eq!(comparison.count(ContactEvent::Persisted), 2);
+        assert_eq!(comparison.count(ContactEvent::Removed), 1);
+        assert_eq!(comparison.count(ContactEvent::Added), 1);
+
+        let index = |side: &Option<RevisionPair>| side.as_ref().map(|p| p.index);
+        let pairs: Vec<_> = comparison
+            .matches
+            .iter()
+            .map(|m| (m.event, index(&m.before), index(&m.after)))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (ContactEvent::Persisted, Some(0), Some(1)),
+                (ContactEvent::Persisted, Some(1), Some(0)),
+                (ContactEvent::Removed, Some(2), None),
+                (ContactEvent::Added, None, Some(2)),
+            ]
+        );
+        assert!(comparison.matches[0].overlap > 0.9);
+
+        // A tolerance too tight for the re-meshed points matches nothing
+        let comparison = RevisionComparison::compare(&before, &after, Some(1e-6));
+        assert_eq!(comparison.count(ContactEvent::Persisted), 0);
+    }
+}
@@ -0,0 +1,356 @@
+//! Contact detection against a signed distance field
+//!
+//! Regular detection searches a spatial index of surface B for every face of
+//! surface A. When one large surface is tested against many small ones (e.g.
+//! dozens of brackets against a chassis skin), it is cheaper to voxelize the
+//! large surface once: [`SignedDistanceField::build`] stores its signed
+//! distance at the grid points of a narrow band around it, and
+//! [`detect_contact_pairs_sdf`] looks up the gap of each face of A by
+//! trilinear interpolation at its centroid. The nearest face of B is kept per
+//! grid point, so the normal angle, facing and contact point of a pair are
+//! still computed against a real face.
+//!
+//! The field is positive on the side B's normals point to; gaps are flipped
+//! where the normals of A and B point the same way, so that they have the
+//! sign of the other algorithms (positive along A's normal towards B). The
+//! gap is only as accurate as the interpolation, so choose a voxel spacing well
+//! below the maximum gap, and building the field costs about as much as
+//! detecting against B a few times: it pays off when B is tested against many
+//! surfaces.
+
+use crate::contact::detection::facing;
+use crate::contact::types::{
+    ContactCriteria, ContactPair, ContactResults, RejectionReason, RejectionStats,
+};
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::geometry::{angle_between_vectors, closest_point_on_face};
+use crate::mesh::types::{Point, SurfaceMesh};
+use std::collections::HashMap;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Grid point of the field: signed distance and nearest face of the surface
+#[derive(Debug, Clone, Copy)]
+struct GridValue {
+    distance: f64,
+    face: usize,
+}
+
+/// Signed distance of a point to the surface of a field
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SdfSample {
+    /// Interpolated signed distance
+    pub distance: f64,
+
+    /// Face of the surface nearest to the closest grid point
+    pub face: usize,
+}
+
+/// Narrow-band signed distance field of a surface on a regular grid
+#[derive(Debug, Clone)]
+pub struct SignedDistanceField {
+    spacing: f64,
+    band: f64,
+    values: HashMap<[i64; 3], GridValue>,
+}
+
+impl SignedDistanceField {
+    /// Voxelize `surface` with grid points `spacing` apart, storing those
+    /// within `band` of it
+    pub fn build(surface: &SurfaceMesh, spacing: f64, band: f64) -> Result<Self> {
+        if !(spacing.is_finite() && spacing > 0.0) {
+            return Err(ContactDetectorError::ConfigError(format!(
+                "Signed distance field spacing must be positive, got {}",
+                spacing
+            )));
+        }
+
+        let mut values: HashMap<[i64; 3], GridValue> = HashMap::new();
+        for (face_idx, face) in surface.faces.iter().enumerate() {
+            let mut lo = [i64::MAX; 3];
+            let mut hi = [i64::MIN; 3];
+            for &node in face.corners() {
+                let p = &surface.nodes[node];
+                for axis in 0..3 {
+                    lo[axis] = lo[axis].min(((p[axis] - band) / spacing).floor() as i64);
+                    hi[axis] = hi[axis].max(((p[axis] + band) / spacing).ceil() as i64);
+                }
+            }
+
+            for i in lo[0]..=hi[0] {
+                for j in lo[1]..=hi[1] {
+                    for k in lo[2]..=hi[2] {
+                        let point =
+                            Point::new(i as f64 * spacing, j as f64 * spacing, k as f64 * spacing);
+                        let offset = point - closest_point_on_face(&point, face, &surface.nodes);
+                        let unsigned = offset.norm();
+                        if unsigned > band {
+                            continue;
+                        }
+                        let value = GridValue {
+                            distance: if offset.dot(&surface.face_normals[face_idx]) < 0.0 {
+                                -unsigned
+                            } else {
+                                unsigned
+                            },
+                            face: face_idx,
+                        };
+                        values
+                            .entry([i, j, k])
+                            .and_modify(|v| {
+                                if unsigned < v.distance.abs() {
+                                    *v = value;
+                                }
+                            })
+                            .or_insert(value);
+                    }
+                }
+            }
+        }
+
+        tracing::debug!(
+            "Signed distance field of '{}': {} grid points at spacing {}",
+            surface.part_name,
+            values.len(),
+            spacing
+        );
+        Ok(Self {
+            spacing,
+            band,
+            values,
+        })
+    }
+
+    /// Field with a band wide enough to evaluate every gap the criteria
+    /// accept
+    pub fn for_criteria(
+        surface: &SurfaceMesh,
+        spacing: f64,
+        criteria: &ContactCriteria,
+    ) -> Result<Self> {
+        // A relative gap is at most that of the surface paired with itself,
+        // since a pair takes the smaller of its two parts
+        let criteria = criteria.for_surfaces(surface, surface);
+        // Grid points around an accepted point are up to a cell diagonal away
+        let band = criteria.max_gap_distance.max(criteria.max_penetration) + spacing * 3f64.sqrt();
+        Self::build(surface, spacing, band)
+    }
+
+    /// Distance between grid points
+    pub fn spacing(&self) -> f64 {
+        self.spacing
+    }
+
+    /// Distance from the surface up to which grid points are stored
+    pub fn band(&self) -> f64 {
+        self.band
+    }
+
+    /// Number of stored grid points
+    pub fn num_grid_points(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Signed distance at `point`, `None` outside the band
+    pub fn sample(&self, point: &Point) -> Option<SdfSample> {
+        let scaled = point.coords / self.spacing;
+        let base = scaled.map(|c| c.floor());
+        let fraction = scaled - base;
+
+        let mut distance = 0.0;
+        let mut nearest = (f64::MIN, 0);
+        for corner in 0..8 {
+            let offset = [corner & 1, (corner >> 1) & 1, (corner >> 2) & 1];
+            let key: [i64; 3] = std::array::from_fn(|axis| base[axis] as i64 + offset[axis]);
+            let value = self.values.get(&key)?;
+            let weight: f64 = (0..3)
+                .map(|axis| {
+                    if offset[axis] == 1 {
+                        fraction[axis]
+                    } else {
+                        1.0 - fraction[axis]
+                    }
+                })
+                .product();
+            distance += weight * value.distance;
+            if weight > nearest.0 {
+                nearest = (weight, value.face);
+            }
+        }
+
+        Some(SdfSample {
+            distance,
+            face: nearest.1,
+        })
+    }
+}
+
+/// Detect contact pairs between surface A and the surface of a signed
+/// distance field, given as `surface_b`
+///
+/// Every face of A has a single candidate, the face of B nearest to where
+/// its centroid samples the field; `criteria.algorithm` and the spatial
+/// index are not used.
+#[tracing::instrument(skip_all, fields(surface_a = %surface_a.part_name, surface_b = %surface_b.part_name))]
+pub fn detect_contact_pairs_sdf(
+    surface_a: &SurfaceMesh,
+    surface_b: &SurfaceMesh,
+    field: &SignedDistanceField,
+    criteria: &ContactCriteria,
+) -> Result<ContactResults> {
+    let criteria = &*criteria.for_surfaces(surface_a, surface_b);
+    let evaluate = |face_a: usize| -> std::result::Result<ContactPair, RejectionReason> {
+        if criteria.is_face_excluded(surface_a, face_a) {
+            return Err(RejectionReason::Tagged);
+        }
+        let centroid = &surface_a.face_centroids[face_a];
+        let sample = field
+            .sample(centroid)
+            .ok_or(RejectionReason::NoCandidates)?;
+        if criteria.is_face_excluded(surface_b, sample.face) {
+            return Err(RejectionReason::Tagged);
+        }
+        let (normal_a, normal_b) = (
+            &surface_a.face_normals[face_a],
+            &surface_b.face_normals[sample.face],
+        );
+        let distance = if normal_a.dot(normal_b) > 0.0 {
+            -sample.distance
+        } else {
+            sample.distance
+        };
+        if !criteria.is_in_range(distance) {
+            return Err(if distance > 0.0 {
+                RejectionReason::GapTooLarge
+            } else {
+                RejectionReason::PenetrationTooDeep
+            });
+        }
+        let angle = angle_between_vectors(normal_a, normal_b);
+        if !criteria.is_angle_valid(angle) {
+            return Err(RejectionReason::AngleTooLarge);
+        }
+        if !criteria.is_facing_valid(facing(face_a, surface_a, sample.face, surface_b)) {
+            return Err(RejectionReason::NotFacing);
+        }
+        Ok(ContactPair {
+            surface_a_face_id: face_a,
+            surface_b_face_id: sample.face,
+            distance,
+            normal_angle: angle,
+            contact_point: closest_point_on_face(
+                centroid,
+                &surface_b.faces[sample.face],
+                &surface_b.nodes,
+            ),
+        })
+    };
+
+    #[cfg(feature = "parallel")]
+    let outcomes: Vec<_> = (0..surface_a.num_faces())
+        .into_par_iter()
+        .map(evaluate)
+        .collect();
+    #[cfg(not(feature = "parallel"))]
+    let outcomes: Vec<_> = (0..surface_a.num_faces()).map(evaluate).collect();
+
+    let mut results = ContactResults::new(
+        surface_a.part_name.clone(),
+        surface_b.part_name.clone(),
+        criteria.clone(),
+    );
+    let mut rejections = criteria.record_rejections.then(RejectionStats::default);
+    let mut paired_b = vec![false; surface_b.num_faces()];
+    for (face_a, outcome) in outcomes.into_iter().enumerate() {
+        match outcome {
+            Ok(pair) => {
+                paired_b[pair.surface_b_face_id] = true;
+                results.pairs.push(pair);
+            }
+            Err(reason) => {
+                if reason == RejectionReason::NotFacing {
+                    results.rejected_facing += 1;
+                }
+                if let Some(rejections) = rejections.as_mut() {
+                    if reason != RejectionReason::NoCandidates {
+                        rejections.add_candidate(reason);
+                    }
+                    rejections.add_unpaired_face(reason);
+                }
+                results.unpaired_a.push(face_a);
+            }
+        }
+    }
+    results.unpaired_b = (0..surface_b.num_faces())
+        .filter(|&face| !paired_b[face])
+        .collect();
+    results.rejections = rejections;
+    results.sort_by_face();
+
+    tracing::info!(
+        "Found {} contact pairs by signed distance field, {} unpaired on A, {} unpaired on B",
+        results.num_pairs(),
+        results.unpaired_a.len(),
+        results.unpaired_b.len()
+    );
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::detection::detect_contact_pairs;
+    use crate::mesh::fixtures::grid;
+    use crate::mesh::types::Vec3;
+
+    /// n × n unit-square grid at height z with normals along `normal_z`
+    fn plate(name: &str, n: usize, z: f64, normal_z: f64) -> SurfaceMesh {
+        let mut surface = grid(name, n, n, z);
+        surface.face_normals.fill(Vec3::new(0.0, 0.0, normal_z));
+        surface
+    }
+
+    #[test]
+    fn test_sdf_detection_matches_search() {
+        let chassis = plate("Chassis", 4, 0.0, 1.0);
+        let criteria = ContactCriteria::new(0.1, 0.02, 180.0);
+
+        let field = SignedDistanceField::for_criteria(&chassis, 0.05, &criteria).unwrap();
+        assert!(field.num_grid_points() > 0);
+        let sample = field.sample(&Point::new(2.5, 3.5, 0.03)).unwrap();
+        assert!((sample.distance - 0.03).abs() < 1e-9);
+        assert_eq!(sample.face, 2 * 4 + 3);
+        assert!(field.sample(&Point::new(2.5, 3.5, 1.0)).is_none());
+
+        // Brackets above the chassis facing it and below it facing the same
+        // way: the gap has the sign the search gives either way
+        for bracket in [plate("Above", 2, 0.03, -1.0), plate("Below", 2, -0.03, 1.0)] {
+            let by_field = detect_contact_pairs_sdf(&bracket, &chassis, &field, &criteria).unwrap();
+            let by_search = detect_contact_pairs(&bracket, &chassis, &criteria).unwrap();
+            assert_eq!(by_field.num_pairs(), 4);
+            assert_eq!(by_search.num_pairs(), 4);
+            assert_eq!(by_field.unpaired_b, by_search.unpaired_b);
+            for (a, b) in by_field.pairs.iter().zip(&by_search.pairs) {
+                assert_eq!(a.surface_a_face_id, b.surface_a_face_id);
+                assert_eq!(a.surface_b_face_id, b.surface_b_face_id);
+                assert!((a.distance - 0.03).abs() < 1e-9);
+                assert!((a.distance - b.distance).abs() < 1e-9);
+                assert!((a.contact_point - b.contact_point).norm() < 1e-9);
+            }
+        }
+
+        // Too far away: outside the band, nothing to pair with
+        let mut criteria = criteria;
+        criteria.record_rejections = true;
+        let far = plate("Far", 2, 0.5, -1.0);
+        let results = detect_contact_pairs_sdf(&far, &chassis, &field, &criteria).unwrap();
+        assert_eq!(results.num_pairs(), 0);
+        assert_eq!(
+            results.rejections.unwrap().binding(),
+            Some((RejectionReason::NoCandidates, 4))
+        );
+
+        assert!(SignedDistanceField::build(&chassis, 0.0, 1.0).is_err());
+    }
+}
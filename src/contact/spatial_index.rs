@@ -0,0 +1,324 @@
+//! Spatial indices over face centroids
+//!
+//! Contact detection looks up the faces of surface B within the search radius
+//! of every face centroid of surface A. A k-d tree handles any distribution
+//! of faces; for dense, uniformly meshed surfaces (faces about the size of
+//! the search radius or smaller) a uniform grid hash with cells twice the
+//! search radius is faster, answering each query from the at most eight cells
+//! overlapping the search sphere.
+
+use crate::mesh::types::{Point, SurfaceMesh};
+use kiddo::ImmutableKdTree;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// Spatial index used to find candidate faces during detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpatialIndexKind {
+    /// k-d tree (kiddo), suited to any face distribution
+    #[default]
+    KdTree,
+
+    /// Uniform grid hash, faster for dense surfaces of similar-sized faces
+    GridHash,
+}
+
+/// Largest number of cells per point for which the grid is stored as a
+/// directly indexed table rather than hashed
+const DENSE_CELLS_PER_POINT: usize = 8;
+
+/// Uniform grid of cells hashing the points they contain
+///
+/// Points are stored sorted by cell so that each cell is a contiguous run.
+/// When the grid over the points' bounding box is compact, cells are indexed
+/// directly; otherwise (e.g. cells much smaller than the point spacing) they
+/// are looked up by a spatial hash of their coordinates, where distinct cells
+/// sharing a hash only add candidates that the distance check removes.
+#[derive(Debug, Clone)]
+pub struct GridHash {
+    cell_size: f64,
+    entries: Vec<(Point, usize)>,
+    table: CellTable,
+}
+
+#[derive(Debug, Clone)]
+enum CellTable {
+    /// Start of each cell's run in x-fastest order over the bounding grid,
+    /// plus the end of the last run
+    Dense {
+        origin: [i64; 3],
+        dims: [usize; 3],
+        starts: Vec<usize>,
+    },
+
+    /// Run (start, end) of each occupied cell by cell key
+    Hashed(HashMap<u64, (usize, usize), BuildHasherDefault<CellKeyHasher>>),
+}
+
+impl GridHash {
+    /// Hash `points` into cubic cells of edge `cell_size` (must be positive)
+    pub fn new(points: &[Point], cell_size: f64) -> Self {
+        assert!(cell_size > 0.0, "grid hash cell size must be positive");
+
+        let cells: Vec<[i64; 3]> = points.iter().map(|p| cell_of(p, cell_size)).collect();
+        let (lo, hi) = cells
+            .iter()
+            .fold(([i64::MAX; 3], [i64::MIN; 3]), |(lo, hi), c| {
+                (
+                    std::array::from_fn(|k| lo[k].min(c[k])),
+                    std::array::from_fn(|k| hi[k].max(c[k])),
+                )
+            });
+        let dims: Option<[usize; 3]> = (!cells.is_empty())
+            .then(|| {
+                let dims: [Option<usize>; 3] =
+                    std::array::from_fn(|k| usize::try_from(hi[k].checked_sub(lo[k])? + 1).ok());
+                Some([dims[0]?, dims[1]?, dims[2]?])
+            })
+            .flatten();
+        let num_cells = dims.and_then(|d| d[0].checked_mul(d[1])?.checked_mul(d[2]));
+
+        match (dims, num_cells) {
+            (Some(dims), Some(num_cells)) if num_cells <= DENSE_CELLS_PER_POINT * points.len() => {
+                let linear = |c: &[i64; 3]| {
+                    let [x, y, z] = std::array::from_fn(|k| (c[k] - lo[k]) as usize);
+                    (z * dims[1] + y) * dims[0] + x
+                };
+
+                // Counting sort of the points by cell
+                let mut starts = vec![0; num_cells + 1];
+                for c in &cells {
+                    starts[linear(c) + 1] += 1;
+                }
+                for i in 0..num_cells {
+                    starts[i + 1] += starts[i];
+                }
+                let mut next = starts.clone();
+                let mut entries = vec![(Point::origin(), 0); points.len()];
+                for (idx, c) in cells.iter().enumerate() {
+                    let slot = &mut next[linear(c)];
+                    entries[*slot] = (points[idx], idx);
+                    *slot += 1;
+                }
+
+                Self {
+                    cell_size,
+                    entries,
+                    table: CellTable::Dense {
+                        origin: lo,
+                        dims,
+                        starts,
+                    },
+                }
+            }
+            _ => {
+                let mut keyed: Vec<(u64, usize)> = cells
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, c)| (cell_key(*c), idx))
+                    .collect();
+                keyed.sort_unstable();
+
+                let mut runs = HashMap::with_hasher(BuildHasherDefault::default());
+                let mut start = 0;
+                for run in keyed.chunk_by(|a, b| a.0 == b.0) {
+                    runs.insert(run[0].0, (start, start + run.len()));
+                    start += run.len();
+                }
+
+                Self {
+                    cell_size,
+                    entries: keyed
+                        .into_iter()
+                        .map(|(_, idx)| (points[idx], idx))
+                        .collect(),
+                    table: CellTable::Hashed(runs),
+                }
+            }
+        }
+    }
+
+    /// Edge length of the cells
+    pub fn cell_size(&self) -> f64 {
+        self.cell_size
+    }
+
+    /// Number of indexed points
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no points are indexed
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Indices of the points within `radius` of `query`, nearest first
+    pub fn within(&self, query: &Point, radius: f64) -> Vec<usize> {
+        let offset = Point::new(radius, radius, radius).coords;
+        let lo = cell_of(&(query - offset), self.cell_size);
+        let hi = cell_of(&(query + offset), self.cell_size);
+
+        let radius_sq = radius * radius;
+        let mut found: Vec<(f64, usize)> = Vec::new();
+        let mut scan = |run: &[(Point, usize)]| {
+            for (p, idx) in run {
+                let distance_sq = (p - query).norm_squared();
+                if distance_sq <= radius_sq {
+                    found.push((distance_sq, *idx));
+                }
+            }
+        };
+
+        match &self.table {
+            CellTable::Dense {
+                origin,
+                dims,
+                starts,
+            } => {
+                // Cells of the query's bounding box inside the grid; each row
+                // of cells along x is one contiguous run
+                let range = |k: usize| {
+                    let first = (lo[k] - origin[k]).max(0);
+                    let last = (hi[k] - origin[k]).min(dims[k] as i64 - 1);
+                    (first <= last).then_some((first as usize, last as usize))
+                };
+                if let (Some(xs), Some(ys), Some(zs)) = (range(0), range(1), range(2)) {
+                    for z in zs.0..=zs.1 {
+                        for y in ys.0..=ys.1 {
+                            let row = (z * dims[1] + y) * dims[0];
+                            scan(&self.entries[starts[row + xs.0]..starts[row + xs.1 + 1]]);
+                        }
+                    }
+                }
+            }
+            CellTable::Hashed(runs) => {
+                // Cells overlapping the query's bounding box, each hash
+                // visited once
+                let mut keys = Vec::new();
+                for x in lo[0]..=hi[0] {
+                    for y in lo[1]..=hi[1] {
+                        for z in lo[2]..=hi[2] {
+                            keys.push(cell_key([x, y, z]));
+                        }
+                    }
+                }
+                keys.sort_unstable();
+                keys.dedup();
+                for key in keys {
+                    if let Some(&(start, end)) = runs.get(&key) {
+                        scan(&self.entries[start..end]);
+                    }
+                }
+            }
+        }
+
+        found.sort_unstable_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        found.into_iter().map(|(_, idx)| idx).collect()
+    }
+}
+
+/// Integer coordinates of the cell containing `p`
+fn cell_of(p: &Point, cell_size: f64) -> [i64; 3] {
+    [
+        (p.x / cell_size).floor() as i64,
+        (p.y / cell_size).floor() as i64,
+        (p.z / cell_size).floor() as i64,
+    ]
+}
+
+/// Spatial hash of cell coordinates (Teschner et al. primes)
+fn cell_key(cell: [i64; 3]) -> u64 {
+    (cell[0] as u64).wrapping_mul(73_856_093)
+        ^ (cell[1] as u64).wrapping_mul(19_349_663)
+        ^ (cell[2] as u64).wrapping_mul(83_492_791)
+}
+
+/// Hasher for cell keys, which are hashes already: only spreads their bits
+#[derive(Debug, Default)]
+struct CellKeyHasher(u64);
+
+impl Hasher for CellKeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write_u64(byte as u64);
+        }
+    }
+
+    fn write_u64(&mut self, key: u64) {
+        self.0 = (self.0 ^ key).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    }
+}
+
+/// Face centroid index of a surface, of the kind selected in the criteria
+pub(crate) enum CentroidIndex {
+    KdTree(ImmutableKdTree<f64, 3>),
+    GridHash(GridHash),
+}
+
+impl CentroidIndex {
+    /// Index the face centroids of `surface` for queries of `search_radius`
+    pub(crate) fn build(surface: &SurfaceMesh, kind: SpatialIndexKind, search_radius: f64) -> Self {
+        match kind {
+            SpatialIndexKind::GridHash if search_radius > 0.0 => {
+                CentroidIndex::GridHash(GridHash::new(&surface.face_centroids, 2.0 * search_radius))
+            }
+            _ => CentroidIndex::KdTree(crate::contact::detection::build_face_kdtree(surface)),
+        }
+    }
+
+    /// Indices of the faces whose centroids are within `radius` of `query`,
+    /// nearest first
+    pub(crate) fn within(&self, query: &Point, radius: f64) -> Vec<usize> {
+        match self {
+            CentroidIndex::KdTree(tree) => tree
+                .within::<kiddo::SquaredEuclidean>(&[query.x, query.y, query.z], radius * radius)
+                .iter()
+                .map(|neighbor| neighbor.item as usize)
+                .collect(),
+            CentroidIndex::GridHash(grid) => grid.within(query, radius),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_hash_matches_brute_force() {
+        let points: Vec<Point> = (0..500)
+            .map(|i| {
+                let t = i as f64;
+                Point::new((t * 0.37) % 5.0 - 2.5, (t * 0.71) % 5.0, (t * 0.13) % 1.0)
+            })
+            .collect();
+
+        // Compact grids are indexed directly, fine ones (0.02) hashed
+        for (cell_size, radius) in [(0.5, 0.5), (0.2, 0.7), (1.0, 0.3), (0.02, 0.4)] {
+            let grid = GridHash::new(&points, cell_size);
+            for query in [
+                Point::new(0.0, 2.5, 0.5),
+                Point::new(-2.4, 0.1, 0.0),
+                Point::new(40.0, -7.0, 3.0),
+            ] {
+                let mut expected: Vec<usize> = (0..points.len())
+                    .filter(|&i| (points[i] - query).norm() <= radius)
+                    .collect();
+                let mut found = grid.within(&query, radius);
+                assert!(found
+                    .windows(2)
+                    .all(|w| (points[w[0]] - query).norm() <= (points[w[1]] - query).norm()));
+                found.sort_unstable();
+                expected.sort_unstable();
+                assert_eq!(found, expected);
+            }
+        }
+    }
+}
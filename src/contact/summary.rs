@@ -0,0 +1,237 @@
+//! Run-level rollup of the detected contact pairs
+//!
+//! One glance at the summary tells whether an assembly is connected as
+//! intended: how many interfaces were found, how much area is in contact,
+//! the deepest penetration anywhere, and which parts (element blocks) touch
+//! nothing at all.
+
+use crate::contact::metrics::PairMetrics;
+use crate::contact::types::ContactResults;
+use crate::format::{FloatFormat, Glyphs};
+use crate::mesh::types::SurfaceMesh;
+use serde::{Deserialize, Serialize};
+
+/// Deepest penetration of a run and where it is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorstPenetration {
+    /// Contact pair it was found in
+    pub pair_id: usize,
+    pub surface_a: String,
+    pub surface_b: String,
+
+    /// Penetration depth (positive)
+    pub depth: f64,
+}
+
+/// Totals over all contact pairs of a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunSummary {
+    /// Number of parts (element blocks) in the mesh
+    pub num_parts: usize,
+
+    /// Number of detected contact pairs
+    pub num_contact_pairs: usize,
+
+    /// Number of physical interfaces (the contact pairs unless merged)
+    pub num_interfaces: usize,
+
+    /// Paired area over all contact pairs, measured on their first surface
+    pub total_paired_area: f64,
+
+    /// Deepest penetration over all contact pairs, if any overlap
+    pub worst_penetration: Option<WorstPenetration>,
+
+    /// Parts not in any contact pair, sorted by name
+    pub parts_without_contact: Vec<String>,
+
+    /// Contact pairs not on the allowed list, as "A ↔ B"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unexpected_contacts: Vec<String>,
+
+    /// Groups of parts touching nothing outside their group, largest first;
+    /// empty if the assembly holds together (see
+    /// [`InterfaceGraph::check_connectivity`](crate::contact::InterfaceGraph::check_connectivity))
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disconnected_components: Vec<Vec<String>>,
+}
+
+impl RunSummary {
+    /// Empty summary over the given parts
+    pub fn new<S: AsRef<str>>(parts: impl IntoIterator<Item = S>) -> Self {
+        let mut parts_without_contact: Vec<String> =
+            parts.into_iter().map(|p| p.as_ref().to_string()).collect();
+        parts_without_contact.sort();
+        parts_without_contact.dedup();
+        Self {
+            num_parts: parts_without_contact.len(),
+            num_contact_pairs: 0,
+            num_interfaces: 0,
+            total_paired_area: 0.0,
+            worst_penetration: None,
+            parts_without_contact,
+            unexpected_contacts: Vec::new(),
+            disconnected_components: Vec::new(),
+        }
+    }
+
+    /// Add a detected contact pair; the parts of both surfaces count as
+    /// in contact and the pair as one interface
+    pub fn add_pair(
+        &mut self,
+        pair_id: usize,
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+        results: &ContactResults,
+        metrics: &PairMetrics,
+    ) {
+        self.num_contact_pairs += 1;
+        self.num_interfaces += 1;
+        self.total_paired_area += metrics.surface_a.paired_area;
+
+        let deepest = results
+            .pairs
+            .iter()
+            .map(|pair| pair.distance)
+            .fold(0.0_f64, f64::min);
+        let worst = self.worst_penetration.as_ref().map_or(0.0, |w| w.depth);
+        if -deepest > worst {
+            self.worst_penetration = Some(WorstPenetration {
+                pair_id,
+                surface_a: surface_a.part_name.clone(),
+                surface_b: surface_b.part_name.clone(),
+                depth: -deepest,
+            });
+        }
+
+        self.mark_in_contact(surface_a.block_name());
+        self.mark_in_contact(surface_b.block_name());
+    }
+
+    /// Count `part` as in contact, e.g. a member of a surface group
+    pub fn mark_in_contact(&mut self, part: &str) {
+        self.parts_without_contact.retain(|p| p != part);
+    }
+
+    /// Whether every part is in at least one contact pair
+    pub fn is_connected(&self) -> bool {
+        self.parts_without_contact.is_empty()
+    }
+
+    /// Print the summary
+    pub fn print(&self, format: FloatFormat, glyphs: Glyphs) {
+        println!("Run summary:");
+        println!("  Contact pairs:     {}", self.num_contact_pairs);
+        println!("  Interfaces:        {}", self.num_interfaces);
+        println!(
+            "  Total paired area: {}",
+            format.show(self.total_paired_area)
+        );
+        match &self.worst_penetration {
+            Some(worst) => println!(
+                "  Worst penetration: {} (pair {}: {} {} {})",
+                format.show(worst.depth),
+                worst.pair_id,
+                worst.surface_a,
+                glyphs.between(),
+                worst.surface_b
+            ),
+            None => println!("  Worst penetration: none"),
+        }
+        if !self.unexpected_contacts.is_empty() {
+            println!(
+                "  Unexpected contacts ({}): {}",
+                self.unexpected_contacts.len(),
+                glyphs.apply(&self.unexpected_contacts.join(", "))
+            );
+        }
+        if self.is_connected() {
+            println!("  All {} part(s) are in contact", self.num_parts);
+        } else {
+            println!(
+                "  Parts without contact ({} of {}): {}",
+                self.parts_without_contact.len(),
+                self.num_parts,
+                self.parts_without_contact.join(", ")
+            );
+        }
+        if !self.disconnected_components.is_empty() {
+            println!(
+                "  Disconnected components ({}):",
+                self.disconnected_components.len()
+            );
+            for (index, component) in self.disconnected_components.iter().enumerate() {
+                println!("    {}: {}", index + 1, component.join(", "));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::{ContactCriteria, ContactPair};
+    use crate::mesh::types::{Point, QuadFace, Vec3};
+
+    fn patch(name: &str) -> SurfaceMesh {
+        let mut surface = SurfaceMesh::new(name.to_string());
+        surface.faces.push(QuadFace::new([0, 1, 2, 3]));
+        surface.nodes = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+        ];
+        surface.face_normals.push(Vec3::new(0.0, 0.0, 1.0));
+        surface.face_areas.push(1.0);
+        surface.face_centroids.push(Point::new(0.5, 0.5, 0.0));
+        surface
+    }
+
+    fn results(distance: f64) -> ContactResults {
+        let mut results =
+            ContactResults::new("A".to_string(), "B".to_string(), ContactCriteria::default());
+        results.pairs.push(ContactPair {
+            surface_a_face_id: 0,
+            surface_b_face_id: 0,
+            distance,
+            normal_angle: 180.0,
+            contact_point: Point::new(0.5, 0.5, 0.0),
+        });
+        results
+    }
+
+    #[test]
+    fn test_run_summary() {
+        let base = patch("Base:patch_0");
+        let lid = patch("Lid:patch_2");
+        let bolt = patch("Bolt:patch_1");
+        let mut summary = RunSummary::new(["Lid", "Bolt", "Base", "Washer"]);
+        assert_eq!(summary.num_parts, 4);
+
+        for (pair_id, (a, b, distance)) in [
+            (&base, &lid, 0.001),
+            (&bolt, &base, -0.002),
+            (&lid, &bolt, -0.0005),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let results = results(distance);
+            let metrics = PairMetrics::compute(&results, a, b);
+            summary.add_pair(pair_id + 1, a, b, &results, &metrics);
+        }
+
+        assert_eq!(summary.num_contact_pairs, 3);
+        assert_eq!(summary.num_interfaces, 3);
+        assert!((summary.total_paired_area - 3.0).abs() < 1e-12);
+        let worst = summary.worst_penetration.as_ref().unwrap();
+        assert_eq!(worst.pair_id, 2);
+        assert_eq!(worst.surface_a, "Bolt:patch_1");
+        assert!((worst.depth - 0.002).abs() < 1e-12);
+        assert_eq!(summary.parts_without_contact, vec!["Washer".to_string()]);
+        assert!(!summary.is_connected());
+
+        summary.mark_in_contact("Washer");
+        assert!(summary.is_connected());
+    }
+}
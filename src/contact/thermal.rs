@@ -0,0 +1,372 @@
+//! Thermal contact conductance of detected interfaces
+//!
+//! Thermal models need a heat transfer coefficient across each contact
+//! interface. It is estimated from the detected pair (paired area and mean
+//! gap) and the materials on either side, through a
+//! [`ConductanceCorrelation`]. The built-in correlations are selected in a
+//! [`ThermalConfig`], which also maps element blocks to materials:
+//!
+//! ```json
+//! {
+//!   "materials": {
+//!     "steel": {"conductivity": 45.0, "roughness": 1.6e-6},
+//!     "aluminum": {"conductivity": 167.0, "roughness": 0.8e-6}
+//!   },
+//!   "parts": {"Flange": "steel", "Housing": "aluminum"},
+//!   "default_material": "steel",
+//!   "correlation": {"model": "gap_conduction", "gas_conductivity": 0.026, "jump_distance": 1e-6}
+//! }
+//! ```
+
+use crate::contact::metrics::PairMetrics;
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::types::SurfaceMesh;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Thermal properties of a material
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThermalMaterial {
+    /// Thermal conductivity
+    pub conductivity: f64,
+
+    /// RMS surface roughness
+    #[serde(default)]
+    pub roughness: f64,
+}
+
+/// Heat transfer coefficient (conductance per unit area) of an interface
+/// from its mean gap and the materials on either side
+pub trait ConductanceCorrelation {
+    /// Conductance per unit area across a mean gap `gap` (negative for an
+    /// overlap) between materials `a` and `b`
+    fn conductance(&self, gap: f64, a: &ThermalMaterial, b: &ThermalMaterial) -> Result<f64>;
+}
+
+/// Built-in conductance correlations, with their coefficients
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum ConductanceModel {
+    /// The same conductance for every interface
+    Constant { conductance: f64 },
+
+    /// Conduction through the gas filling the gap,
+    /// `gas_conductivity / (gap + roughness + jump_distance)`, with the gap
+    /// taken as 0 when closed and the combined roughness of both surfaces
+    GapConduction {
+        gas_conductivity: f64,
+
+        /// Temperature jump distance of the gas, which bounds the
+        /// conductance of a closed gap
+        jump_distance: f64,
+    },
+
+    /// `coefficient * (gap / reference_gap)^-exponent`, with gaps below the
+    /// reference gap taken as the reference gap
+    PowerLaw {
+        coefficient: f64,
+        exponent: f64,
+        reference_gap: f64,
+    },
+
+    /// Conduction through the touching asperities of rough surfaces under
+    /// load (Mikic), `1.25 k (m / roughness) (pressure / hardness)^0.95`,
+    /// with `k` the harmonic mean conductivity and the combined roughness
+    /// of both surfaces
+    SolidSpot {
+        contact_pressure: f64,
+        hardness: f64,
+
+        /// Mean asperity slope `m`
+        asperity_slope: f64,
+    },
+}
+
+/// Combined RMS roughness of two surfaces
+fn combined_roughness(a: &ThermalMaterial, b: &ThermalMaterial) -> f64 {
+    a.roughness.hypot(b.roughness)
+}
+
+impl ConductanceModel {
+    /// Check that the coefficients are usable
+    pub fn validate(&self) -> Result<()> {
+        let positive = |name: &str, value: f64| {
+            if value > 0.0 && value.is_finite() {
+                Ok(())
+            } else {
+                Err(ContactDetectorError::ConfigError(format!(
+                    "Conductance correlation: {} must be positive, got {}",
+                    name, value
+                )))
+            }
+        };
+        match *self {
+            ConductanceModel::Constant { conductance } => positive("conductance", conductance),
+            ConductanceModel::GapConduction {
+                gas_conductivity,
+                jump_distance,
+            } => {
+                positive("gas_conductivity", gas_conductivity)?;
+                positive("jump_distance", jump_distance)
+            }
+            ConductanceModel::PowerLaw {
+                coefficient,
+                reference_gap,
+                ..
+            } => {
+                positive("coefficient", coefficient)?;
+                positive("reference_gap", reference_gap)
+            }
+            ConductanceModel::SolidSpot {
+                contact_pressure,
+                hardness,
+                asperity_slope,
+            } => {
+                positive("contact_pressure", contact_pressure)?;
+                positive("hardness", hardness)?;
+                positive("asperity_slope", asperity_slope)
+            }
+        }
+    }
+}
+
+impl ConductanceCorrelation for ConductanceModel {
+    fn conductance(&self, gap: f64, a: &ThermalMaterial, b: &ThermalMaterial) -> Result<f64> {
+        Ok(match *self {
+            ConductanceModel::Constant { conductance } => conductance,
+            ConductanceModel::GapConduction {
+                gas_conductivity,
+                jump_distance,
+            } => gas_conductivity / (gap.max(0.0) + combined_roughness(a, b) + jump_distance),
+            ConductanceModel::PowerLaw {
+                coefficient,
+                exponent,
+                reference_gap,
+            } => coefficient * (gap.max(reference_gap) / reference_gap).powf(-exponent),
+            ConductanceModel::SolidSpot {
+                contact_pressure,
+                hardness,
+                asperity_slope,
+            } => {
+                let roughness = combined_roughness(a, b);
+                if roughness <= 0.0 {
+                    return Err(ContactDetectorError::ConfigError(
+                        "The solid_spot correlation needs the roughness of the materials"
+                            .to_string(),
+                    ));
+                }
+                let conductivity =
+                    2.0 * a.conductivity * b.conductivity / (a.conductivity + b.conductivity);
+                1.25 * conductivity
+                    * (asperity_slope / roughness)
+                    * (contact_pressure / hardness).powf(0.95)
+            }
+        })
+    }
+}
+
+/// Materials of the parts and the conductance correlation, read from a JSON
+/// file (see the [module documentation](self))
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThermalConfig {
+    /// Materials by name
+    pub materials: BTreeMap<String, ThermalMaterial>,
+
+    /// Material of each element block
+    #[serde(default)]
+    pub parts: BTreeMap<String, String>,
+
+    /// Material of the blocks missing from `parts`
+    #[serde(default)]
+    pub default_material: Option<String>,
+
+    pub correlation: ConductanceModel,
+}
+
+impl ThermalConfig {
+    /// Load and check a thermal configuration from a JSON file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to read thermal config: {}", e))
+        })?;
+        let config: Self = serde_json::from_str(&content).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to parse thermal config: {}", e))
+        })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that every referenced material is defined and the correlation
+    /// coefficients are usable
+    pub fn validate(&self) -> Result<()> {
+        for material in self.parts.values().chain(&self.default_material) {
+            if !self.materials.contains_key(material) {
+                return Err(ContactDetectorError::ConfigError(format!(
+                    "Thermal config: unknown material '{}'. Defined materials: {}",
+                    material,
+                    self.materials
+                        .keys()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )));
+            }
+        }
+        self.correlation.validate()
+    }
+
+    /// Name and properties of the material of an element block
+    pub fn material_of(&self, block: &str) -> Result<(&str, &ThermalMaterial)> {
+        let name = self
+            .parts
+            .get(block)
+            .or(self.default_material.as_ref())
+            .ok_or_else(|| {
+                ContactDetectorError::ConfigError(format!(
+                    "Thermal config: no material for part '{}' (add it to \"parts\" or set \
+                     \"default_material\")",
+                    block
+                ))
+            })?;
+        let material = self.materials.get(name).ok_or_else(|| {
+            ContactDetectorError::ConfigError(format!(
+                "Thermal config: unknown material '{}'",
+                name
+            ))
+        })?;
+        Ok((name, material))
+    }
+}
+
+/// Conductance estimate of one contact interface
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterfaceConductance {
+    /// Names of the surfaces
+    pub part_a: String,
+    pub part_b: String,
+
+    /// Materials of their element blocks
+    pub material_a: String,
+    pub material_b: String,
+
+    /// Paired area of surface A
+    pub paired_area: f64,
+
+    /// Area-weighted mean gap of the paired faces of surface A
+    pub mean_gap: f64,
+
+    /// Conductance per unit area
+    pub conductance: f64,
+
+    /// Conductance over the paired area
+    pub total_conductance: f64,
+}
+
+impl InterfaceConductance {
+    /// Estimate the conductance of the pair of `surface_a` and `surface_b`
+    /// with the given metrics
+    pub fn compute(
+        config: &ThermalConfig,
+        correlation: &dyn ConductanceCorrelation,
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+        metrics: &PairMetrics,
+    ) -> Result<Self> {
+        let (material_a, properties_a) = config.material_of(surface_a.block_name())?;
+        let (material_b, properties_b) = config.material_of(surface_b.block_name())?;
+        let paired_area = metrics.surface_a.paired_area;
+        let mean_gap = metrics.surface_a.avg_distance;
+        let conductance = correlation.conductance(mean_gap, properties_a, properties_b)?;
+
+        Ok(Self {
+            part_a: surface_a.part_name.clone(),
+            part_b: surface_b.part_name.clone(),
+            material_a: material_a.to_string(),
+            material_b: material_b.to_string(),
+            paired_area,
+            mean_gap,
+            conductance,
+            total_conductance: conductance * paired_area,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(correlation: ConductanceModel) -> ThermalConfig {
+        let json = r#"{
+            "materials": {
+                "steel": {"conductivity": 45.0, "roughness": 3e-6},
+                "aluminum": {"conductivity": 167.0, "roughness": 4e-6}
+            },
+            "parts": {"Housing": "aluminum"},
+            "default_material": "steel",
+            "correlation": {"model": "constant", "conductance": 1.0}
+        }"#;
+        let mut config: ThermalConfig = serde_json::from_str(json).unwrap();
+        config.correlation = correlation;
+        config.validate().unwrap();
+        config
+    }
+
+    #[test]
+    fn test_conductance_correlations() {
+        let config = config(ConductanceModel::GapConduction {
+            gas_conductivity: 0.026,
+            jump_distance: 5e-6,
+        });
+        let (name, steel) = config.material_of("Flange").unwrap();
+        assert_eq!(name, "steel");
+        let (name, aluminum) = config.material_of("Housing").unwrap();
+        assert_eq!(name, "aluminum");
+
+        // Closed gaps conduct through the roughness and jump distance only
+        let gap = |gap: f64| {
+            config
+                .correlation
+                .conductance(gap, steel, aluminum)
+                .unwrap()
+        };
+        assert!((gap(0.0) - 0.026 / 10e-6).abs() < 1e-6);
+        assert_eq!(gap(-1e-5), gap(0.0));
+        assert!((gap(1e-5) - 0.026 / 20e-6).abs() < 1e-6);
+
+        let power_law = ConductanceModel::PowerLaw {
+            coefficient: 1000.0,
+            exponent: 1.0,
+            reference_gap: 1e-4,
+        };
+        assert_eq!(power_law.conductance(0.0, steel, steel).unwrap(), 1000.0);
+        assert!((power_law.conductance(2e-4, steel, steel).unwrap() - 500.0).abs() < 1e-9);
+
+        // Harmonic mean conductivity 2 * 45 * 167 / 212, combined roughness 5e-6
+        let solid_spot = ConductanceModel::SolidSpot {
+            contact_pressure: 1e6,
+            hardness: 1e9,
+            asperity_slope: 0.1,
+        };
+        let expected = 1.25 * (2.0 * 45.0 * 167.0 / 212.0) * (0.1 / 5e-6) * 1e-3_f64.powf(0.95);
+        let h = solid_spot.conductance(0.0, steel, aluminum).unwrap();
+        assert!((h - expected).abs() < 1e-9 * expected);
+        let smooth = ThermalMaterial {
+            conductivity: 45.0,
+            roughness: 0.0,
+        };
+        assert!(solid_spot.conductance(0.0, &smooth, &smooth).is_err());
+
+        // Coefficients and materials are checked
+        assert!(ConductanceModel::Constant { conductance: -1.0 }
+            .validate()
+            .is_err());
+        let mut unknown = config.clone();
+        unknown
+            .parts
+            .insert("Bolt".to_string(), "titanium".to_string());
+        assert!(unknown.validate().is_err());
+        unknown.default_material = None;
+        assert!(unknown.material_of("Cover").is_err());
+    }
+}
@@ -1,10 +1,16 @@
 //! Contact detection data types
 
-use crate::mesh::types::Point;
+use crate::contact::algorithm::DetectionAlgorithmKind;
+use crate::contact::relative::RelativeTolerance;
+use crate::contact::spatial_index::SpatialIndexKind;
+use crate::format::{FloatFormat, Glyphs};
+use crate::mesh::tags::TagFilter;
+use crate::mesh::types::{Point, SurfaceMesh};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Contact pair between two surface faces
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContactPair {
     /// Surface A face index
     pub surface_a_face_id: usize,
@@ -19,15 +25,36 @@ pub struct ContactPair {
     pub normal_angle: f64,
 
     /// Contact point on surface B
+    #[serde(with = "point_array")]
     pub contact_point: Point,
 }
 
+/// (De)serialize a point as `[x, y, z]`
+pub(crate) mod point_array {
+    use crate::mesh::types::Point;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(point: &Point, serializer: S) -> Result<S::Ok, S::Error> {
+        [point.x, point.y, point.z].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Point, D::Error> {
+        let [x, y, z] = <[f64; 3]>::deserialize(deserializer)?;
+        Ok(Point::new(x, y, z))
+    }
+}
+
 /// Criteria for contact detection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContactCriteria {
     /// Maximum gap distance to consider as contact
     pub max_gap_distance: f64,
 
+    /// Maximum gap relative to the size of the parts in contact, resolved
+    /// per pair into `max_gap_distance` (see [`ContactCriteria::for_surfaces`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_max_gap: Option<RelativeTolerance>,
+
     /// Maximum penetration distance (overlap)
     pub max_penetration: f64,
 
@@ -36,15 +63,60 @@ pub struct ContactCriteria {
 
     /// Search radius multiplier for spatial queries
     pub search_radius_multiplier: f64,
+
+    /// Minimum facing of a pair: the |cosine| between A's normal and the
+    /// direction from A's centroid to the closest point of face B (0 disables)
+    #[serde(default = "default_min_facing")]
+    pub min_facing: f64,
+
+    /// Spatial index used to find candidate faces
+    #[serde(default)]
+    pub spatial_index: SpatialIndexKind,
+
+    /// Algorithm measuring the gap between candidate faces
+    #[serde(default)]
+    pub algorithm: DetectionAlgorithmKind,
+
+    /// Count why candidates and unpaired faces were rejected (see
+    /// [`ContactResults::rejections`])
+    #[serde(default)]
+    pub record_rejections: bool,
+
+    /// Minimum paired area of surface A for two surfaces to count as in
+    /// contact; unlike a minimum number of face pairs it does not depend on
+    /// the mesh density
+    #[serde(default)]
+    pub min_paired_area: Option<f64>,
+
+    /// Minimum paired fraction of the area of either surface (the larger of
+    /// the two coverages) for two surfaces to count as in contact
+    #[serde(default)]
+    pub min_coverage_fraction: Option<f64>,
+
+    /// Faces (of either surface) matching any of these tags are never paired
+    #[serde(default)]
+    pub excluded_tags: Vec<TagFilter>,
+}
+
+fn default_min_facing() -> f64 {
+    0.5
 }
 
 impl Default for ContactCriteria {
     fn default() -> Self {
         Self {
             max_gap_distance: 0.005,
+            relative_max_gap: None,
             max_penetration: 0.001,
             max_normal_angle: 45.0,
             search_radius_multiplier: 2.0,
+            min_facing: default_min_facing(),
+            spatial_index: SpatialIndexKind::default(),
+            algorithm: DetectionAlgorithmKind::default(),
+            record_rejections: false,
+            min_paired_area: None,
+            min_coverage_fraction: None,
+            excluded_tags: Vec::new(),
         }
     }
 }
@@ -54,9 +126,17 @@ impl ContactCriteria {
     pub fn new(max_gap: f64, max_penetration: f64, max_angle: f64) -> Self {
         Self {
             max_gap_distance: max_gap,
+            relative_max_gap: None,
             max_penetration,
             max_normal_angle: max_angle,
             search_radius_multiplier: 2.0,
+            min_facing: default_min_facing(),
+            spatial_index: SpatialIndexKind::default(),
+            algorithm: DetectionAlgorithmKind::default(),
+            record_rejections: false,
+            min_paired_area: None,
+            min_coverage_fraction: None,
+            excluded_tags: Vec::new(),
         }
     }
 
@@ -74,10 +154,140 @@ impl ContactCriteria {
     pub fn is_angle_valid(&self, angle: f64) -> bool {
         angle <= self.max_normal_angle
     }
+
+    /// How far a distance is from failing the range check, negative if it
+    /// fails: `max_gap_distance - distance` for gaps and
+    /// `max_penetration - |distance|` for overlaps
+    pub fn distance_margin(&self, distance: f64) -> f64 {
+        if distance >= 0.0 {
+            self.max_gap_distance - distance
+        } else {
+            self.max_penetration + distance
+        }
+    }
+
+    /// How far a normal angle is from failing the angle check, in degrees
+    /// (negative if it fails)
+    pub fn angle_margin(&self, angle: f64) -> f64 {
+        self.max_normal_angle - angle
+    }
+
+    /// Check if a pair's facing (see [`ContactCriteria::min_facing`]) is sufficient
+    pub fn is_facing_valid(&self, facing: f64) -> bool {
+        facing >= self.min_facing
+    }
+
+    /// Check if a face of `surface` matches one of the excluded tags
+    pub fn is_face_excluded(&self, surface: &SurfaceMesh, face: usize) -> bool {
+        self.excluded_tags
+            .iter()
+            .any(|filter| filter.matches(&surface.face_tags, face))
+    }
+}
+
+/// Check a candidate face pair failed, in the order the checks run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionReason {
+    /// No face of B within the search radius
+    NoCandidates,
+
+    /// A face matching [`ContactCriteria::excluded_tags`]
+    Tagged,
+
+    /// The detection algorithm found no contact geometry (e.g. a ray missed)
+    NotMeasured,
+
+    /// Gap above the maximum gap
+    GapTooLarge,
+
+    /// Overlap beyond the maximum penetration
+    PenetrationTooDeep,
+
+    /// Normal angle above the maximum angle
+    AngleTooLarge,
+
+    /// Faces side by side rather than facing each other
+    NotFacing,
+}
+
+impl RejectionReason {
+    /// The criterion (CLI option) responsible, for reports
+    pub fn criterion(self) -> &'static str {
+        match self {
+            RejectionReason::NoCandidates => "search radius (--max-gap)",
+            RejectionReason::Tagged => "excluded face tags",
+            RejectionReason::NotMeasured => "detection algorithm (--algorithm)",
+            RejectionReason::GapTooLarge => "--max-gap",
+            RejectionReason::PenetrationTooDeep => "--max-penetration",
+            RejectionReason::AngleTooLarge => "--max-angle",
+            RejectionReason::NotFacing => "--min-facing",
+        }
+    }
+}
+
+/// Why candidates were rejected, collected when
+/// [`ContactCriteria::record_rejections`] is set
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RejectionStats {
+    /// Candidate face pairs rejected by each check
+    pub candidates: BTreeMap<RejectionReason, usize>,
+
+    /// Unpaired faces of surface A by the furthest check any of their
+    /// candidates reached (`no_candidates` if there were none)
+    pub unpaired_faces: BTreeMap<RejectionReason, usize>,
+}
+
+impl RejectionStats {
+    /// Count a rejected candidate
+    pub fn add_candidate(&mut self, reason: RejectionReason) {
+        *self.candidates.entry(reason).or_default() += 1;
+    }
+
+    /// Count an unpaired face of surface A
+    pub fn add_unpaired_face(&mut self, reason: RejectionReason) {
+        *self.unpaired_faces.entry(reason).or_default() += 1;
+    }
+
+    /// Add the counts of another pair, e.g. for run totals
+    pub fn merge(&mut self, other: &RejectionStats) {
+        for (reason, count) in &other.candidates {
+            *self.candidates.entry(*reason).or_default() += count;
+        }
+        for (reason, count) in &other.unpaired_faces {
+            *self.unpaired_faces.entry(*reason).or_default() += count;
+        }
+    }
+
+    /// The check keeping the most faces of A unpaired: the criterion to relax
+    /// when detection finds less than expected
+    pub fn binding(&self) -> Option<(RejectionReason, usize)> {
+        self.unpaired_faces
+            .iter()
+            .map(|(&reason, &count)| (reason, count))
+            .filter(|&(_, count)| count > 0)
+            .max_by_key(|&(reason, count)| (count, std::cmp::Reverse(reason)))
+    }
+
+    /// Print the unpaired face counts, most frequent first
+    pub fn print_summary(&self, indent: &str) {
+        let mut reasons: Vec<_> = self.unpaired_faces.iter().collect();
+        reasons.sort_by_key(|&(_, count)| std::cmp::Reverse(*count));
+        for (reason, count) in reasons {
+            let candidates = self.candidates.get(reason).copied().unwrap_or(0);
+            println!(
+                "{}{:<22} {} unpaired face(s), {} candidate(s)",
+                indent,
+                format!("{}:", reason.criterion()),
+                count,
+                candidates
+            );
+        }
+    }
 }
 
 /// Results from contact detection
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContactResults {
     /// Name of surface A
     pub surface_a_name: String,
@@ -96,6 +306,19 @@ pub struct ContactResults {
 
     /// Criteria used for detection
     pub criteria: ContactCriteria,
+
+    /// Number of candidate pairs that met the gap and angle criteria but
+    /// failed the facing test
+    #[serde(default)]
+    pub rejected_facing: usize,
+
+    /// Number of pairs removed because they pass through surface A's volume
+    #[serde(default)]
+    pub rejected_occluded: usize,
+
+    /// Why candidates were rejected (if recorded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejections: Option<RejectionStats>,
 }
 
 impl ContactResults {
@@ -108,14 +331,103 @@ impl ContactResults {
             unpaired_a: Vec::new(),
             unpaired_b: Vec::new(),
             criteria,
+            rejected_facing: 0,
+            rejected_occluded: 0,
+            rejections: None,
         }
     }
 
+    /// Put pairs in order of (face A, face B) and unpaired faces in ascending
+    /// order, so that results don't depend on how they were computed (e.g.
+    /// the scheduling of parallel detection or a cache round trip)
+    pub fn sort_by_face(&mut self) {
+        self.pairs
+            .sort_unstable_by_key(|p| (p.surface_a_face_id, p.surface_b_face_id));
+        self.unpaired_a.sort_unstable();
+        self.unpaired_a.dedup();
+        self.unpaired_b.sort_unstable();
+        self.unpaired_b.dedup();
+    }
+
     /// Get number of contact pairs
     pub fn num_pairs(&self) -> usize {
         self.pairs.len()
     }
 
+    /// Sorted, de-duplicated indices of the faces that take part in a pair on
+    /// surface A (`surface_a = true`) or surface B
+    pub fn paired_faces(&self, surface_a: bool) -> Vec<usize> {
+        let mut faces: Vec<usize> = self
+            .pairs
+            .iter()
+            .map(|p| if surface_a { p.surface_a_face_id } else { p.surface_b_face_id })
+            .collect();
+        faces.sort_unstable();
+        faces.dedup();
+        faces
+    }
+
+    /// Pairs of face `face` of surface A (`surface_a = true`) or surface B
+    pub fn pairs_for_face(
+        &self,
+        surface_a: bool,
+        face: usize,
+    ) -> impl Iterator<Item = &ContactPair> + '_ {
+        self.pairs.iter().filter(move |p| {
+            face == if surface_a { p.surface_a_face_id } else { p.surface_b_face_id }
+        })
+    }
+
+    /// Pairs of face `face` of surface A
+    pub fn pairs_for_face_a(&self, face: usize) -> impl Iterator<Item = &ContactPair> + '_ {
+        self.pairs_for_face(true, face)
+    }
+
+    /// Pairs of face `face` of surface B
+    pub fn pairs_for_face_b(&self, face: usize) -> impl Iterator<Item = &ContactPair> + '_ {
+        self.pairs_for_face(false, face)
+    }
+
+    /// Whether face `face` of surface A (`surface_a = true`) or surface B
+    /// takes part in a pair
+    pub fn is_face_paired(&self, surface_a: bool, face: usize) -> bool {
+        self.pairs_for_face(surface_a, face).next().is_some()
+    }
+
+    /// Pairs from the deepest penetration to the widest gap
+    pub fn pairs_sorted_by_distance(&self) -> Vec<&ContactPair> {
+        let mut pairs: Vec<&ContactPair> = self.pairs.iter().collect();
+        pairs.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        pairs
+    }
+
+    /// Results keeping only the pairs matching `keep`
+    ///
+    /// Faces that lose all their pairs are added to the unpaired faces; the
+    /// criteria and rejection counts of the detection are kept as they are.
+    pub fn filter<F>(&self, mut keep: F) -> ContactResults
+    where
+        F: FnMut(&ContactPair) -> bool,
+    {
+        let mut filtered = self.clone();
+        filtered.pairs.retain(|p| keep(p));
+
+        for surface_a in [true, false] {
+            let still_paired = filtered.paired_faces(surface_a);
+            let lost = self
+                .paired_faces(surface_a)
+                .into_iter()
+                .filter(|f| still_paired.binary_search(f).is_err());
+            if surface_a {
+                filtered.unpaired_a.extend(lost);
+            } else {
+                filtered.unpaired_b.extend(lost);
+            }
+        }
+        filtered.sort_by_face();
+        filtered
+    }
+
     /// Get average distance
     pub fn avg_distance(&self) -> f64 {
         if self.pairs.is_empty() {
@@ -171,8 +483,8 @@ impl ContactResults {
         sum / self.pairs.len() as f64
     }
 
-    /// Print summary statistics
-    pub fn print_summary(&self) {
+    /// Print summary statistics, lengths in `format`
+    pub fn print_summary(&self, format: FloatFormat, glyphs: Glyphs) {
         println!("\n{}", "=".repeat(60));
         println!("CONTACT DETECTION RESULTS");
         println!("{}", "=".repeat(60));
@@ -183,26 +495,46 @@ impl ContactResults {
         println!("  Contact Pairs: {}", self.num_pairs());
         println!("  Unpaired A:    {}", self.unpaired_a.len());
         println!("  Unpaired B:    {}", self.unpaired_b.len());
+        println!("  Rejected (facing): {}", self.rejected_facing);
+        println!("  Rejected (occluded): {}", self.rejected_occluded);
         println!();
 
+        if let Some(rejections) = &self.rejections {
+            println!("  Rejections (binding criterion first):");
+            rejections.print_summary("    ");
+            println!();
+        }
+
         if !self.pairs.is_empty() {
             println!("  Distance Statistics:");
-            println!("    Average: {:.6}", self.avg_distance());
-            println!("    Min:     {:.6}", self.min_distance());
-            println!("    Max:     {:.6}", self.max_distance());
+            println!("    Average: {}", format.show(self.avg_distance()));
+            println!("    Min:     {}", format.show(self.min_distance()));
+            println!("    Max:     {}", format.show(self.max_distance()));
             println!();
             println!("  Normal Angle Statistics:");
-            println!("    Average: {:.2}°", self.avg_normal_angle());
+            println!(
+                "    Average: {:.2}{}",
+                self.avg_normal_angle(),
+                glyphs.degrees()
+            );
             println!();
         }
 
         println!("  Criteria:");
-        println!("    Max Gap:         {:.6}", self.criteria.max_gap_distance);
-        println!("    Max Penetration: {:.6}", self.criteria.max_penetration);
         println!(
-            "    Max Angle:       {:.1}°",
-            self.criteria.max_normal_angle
+            "    Max Gap:         {}",
+            format.show(self.criteria.max_gap_distance)
+        );
+        println!(
+            "    Max Penetration: {}",
+            format.show(self.criteria.max_penetration)
+        );
+        println!(
+            "    Max Angle:       {:.1}{}",
+            self.criteria.max_normal_angle,
+            glyphs.degrees()
         );
+        println!("    Min Facing:      {:.3}", self.criteria.min_facing);
         println!();
         println!("{}", "=".repeat(60));
     }
@@ -241,6 +573,35 @@ mod tests {
         assert!(!criteria.is_angle_valid(90.0));
     }
 
+    #[test]
+    fn test_contact_criteria_margins() {
+        let criteria = ContactCriteria::default();
+
+        assert!((criteria.distance_margin(0.002) - 0.003).abs() < 1e-12);
+        assert!((criteria.distance_margin(-0.0004) - 0.0006).abs() < 1e-12);
+        assert!(criteria.distance_margin(-0.002) < 0.0);
+        assert!((criteria.angle_margin(30.0) - 15.0).abs() < 1e-12);
+        assert!(criteria.angle_margin(90.0) < 0.0);
+    }
+
+    #[test]
+    fn test_contact_criteria_is_facing_valid() {
+        let mut criteria = ContactCriteria::default();
+
+        assert!(criteria.is_facing_valid(1.0));
+        assert!(!criteria.is_facing_valid(0.1)); // Mostly sideways
+
+        criteria.min_facing = 0.0;
+        assert!(criteria.is_facing_valid(0.0));
+
+        // Older configuration files without the field get the default
+        let criteria: ContactCriteria = serde_json::from_str(
+            r#"{"max_gap_distance": 0.1, "max_penetration": 0.1, "max_normal_angle": 30.0, "search_radius_multiplier": 2.0}"#,
+        )
+        .unwrap();
+        assert_eq!(criteria.min_facing, 0.5);
+    }
+
     #[test]
     fn test_contact_results_nan_handling() {
         use crate::mesh::Point;
@@ -295,4 +656,72 @@ mod tests {
         let avg = results.avg_distance();
         assert!((avg - 1.0).abs() < 1e-10); // (0.5 + 1.5 + NaN + inf) / 4 should handle NaN/inf properly
     }
+
+    #[test]
+    fn test_paired_faces() {
+        use crate::mesh::Point;
+
+        let mut results = ContactResults::new(
+            "Surface A".to_string(),
+            "Surface B".to_string(),
+            ContactCriteria::default(),
+        );
+        for (a, b) in [(3, 0), (1, 0), (3, 2)] {
+            results.pairs.push(ContactPair {
+                surface_a_face_id: a,
+                surface_b_face_id: b,
+                distance: 0.0,
+                normal_angle: 180.0,
+                contact_point: Point::origin(),
+            });
+        }
+
+        assert_eq!(results.paired_faces(true), vec![1, 3]);
+        assert_eq!(results.paired_faces(false), vec![0, 2]);
+    }
+    #[test]
+    fn test_pair_queries() {
+        use crate::mesh::Point;
+
+        let mut results = ContactResults::new(
+            "Surface A".to_string(),
+            "Surface B".to_string(),
+            ContactCriteria::default(),
+        );
+        for (a, b, distance) in [(3, 0, 0.002), (1, 0, -0.0005), (3, 2, 0.004)] {
+            results.pairs.push(ContactPair {
+                surface_a_face_id: a,
+                surface_b_face_id: b,
+                distance,
+                normal_angle: 180.0,
+                contact_point: Point::origin(),
+            });
+        }
+        results.unpaired_a = vec![0, 2];
+        results.unpaired_b = vec![1];
+
+        assert_eq!(results.pairs_for_face_a(3).count(), 2);
+        assert_eq!(results.pairs_for_face_b(0).count(), 2);
+        assert!(results.is_face_paired(true, 1));
+        assert!(!results.is_face_paired(true, 0));
+        assert!(results.is_face_paired(false, 2));
+
+        let distances: Vec<f64> = results
+            .pairs_sorted_by_distance()
+            .iter()
+            .map(|p| p.distance)
+            .collect();
+        assert_eq!(distances, vec![-0.0005, 0.002, 0.004]);
+
+        // Dropping the widest gap leaves face 2 of B without a pair
+        let close = results.filter(|p| p.distance < 0.003);
+        assert_eq!(close.num_pairs(), 2);
+        assert_eq!(close.unpaired_a, vec![0, 2]);
+        assert_eq!(close.unpaired_b, vec![1, 2]);
+
+        let penetrating = results.filter(|p| p.distance < 0.0);
+        assert_eq!(penetrating.unpaired_a, vec![0, 2, 3]);
+        assert_eq!(penetrating.unpaired_b, vec![1, 2]);
+        assert_eq!(results.num_pairs(), 3);
+    }
 }
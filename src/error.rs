@@ -70,6 +70,13 @@ pub enum ContactDetectorError {
     /// (zero-area or zero-length normals).
     #[error("Geometry error: {0}")]
     GeometryError(String),
+
+    /// Contact check failed
+    ///
+    /// Detected contacts violate the expected contacts declared in the
+    /// configuration.
+    #[error("Contact check failed: {0}")]
+    ContactCheckFailed(String),
 }
 
 /// Convenience type alias for Results with [`ContactDetectorError`]
@@ -0,0 +1,312 @@
+//! Formatting of numbers and symbols in reports and text files
+//!
+//! Console reports write lengths and areas with six decimals, which hides
+//! micron-scale gaps of a model in meters and clutters reports of a model in
+//! millimeters; CSV files and JSON metadata write every digit. A
+//! [`FloatFormat`] sets the notation and precision instead, and
+//! [`FloatFormats`] applies one consistently to the console, CSV and JSON
+//! outputs once the user asks for it (`--notation`, `--precision` or the
+//! settings file).
+//!
+//! ```text
+//! fixed, 6        0.000012
+//! scientific, 3   1.235e-5
+//! shortest        0.0000123456789
+//! ```
+//!
+//! Console reports also write pairs as "A ↔ B" and angles in "°", which
+//! some cluster terminals garble; [`Glyphs::Ascii`] (`--ascii`) writes
+//! "A <-> B" and "deg" instead.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+/// How a floating-point value is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Notation {
+    /// A fixed number of decimals
+    Fixed,
+
+    /// Mantissa with a fixed number of decimals and an exponent
+    Scientific,
+
+    /// The fewest digits that read back to the same value (precision is
+    /// ignored)
+    Shortest,
+}
+
+impl Notation {
+    /// Name on the command line and in settings
+    pub fn name(&self) -> &'static str {
+        match self {
+            Notation::Fixed => "fixed",
+            Notation::Scientific => "scientific",
+            Notation::Shortest => "shortest",
+        }
+    }
+}
+
+impl FromStr for Notation {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, String> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "fixed" => Ok(Notation::Fixed),
+            "scientific" | "sci" => Ok(Notation::Scientific),
+            "shortest" => Ok(Notation::Shortest),
+            _ => Err(format!(
+                "Invalid notation '{}': expected fixed, scientific or shortest",
+                value
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Notation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Notation and precision of floating-point values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FloatFormat {
+    pub notation: Notation,
+
+    /// Decimals (of the mantissa in scientific notation)
+    pub precision: usize,
+}
+
+impl FloatFormat {
+    /// Six decimals, as console reports have always used
+    pub const CONSOLE: FloatFormat = FloatFormat::fixed(6);
+
+    /// Every digit needed to read the value back, as files have always used
+    pub const EXACT: FloatFormat = FloatFormat {
+        notation: Notation::Shortest,
+        precision: 0,
+    };
+
+    /// `precision` decimals
+    pub const fn fixed(precision: usize) -> Self {
+        Self {
+            notation: Notation::Fixed,
+            precision,
+        }
+    }
+
+    /// Scientific notation with `precision` decimals in the mantissa
+    pub const fn scientific(precision: usize) -> Self {
+        Self {
+            notation: Notation::Scientific,
+            precision,
+        }
+    }
+
+    /// `value` written in this format, e.g. as an argument of `println!`
+    /// (widths and alignment of the format string apply)
+    pub fn show(self, value: f64) -> FormattedFloat {
+        FormattedFloat {
+            value,
+            format: self,
+        }
+    }
+
+    /// `value` rounded to what this format writes, for outputs that store
+    /// numbers rather than text (JSON)
+    pub fn round(self, value: f64) -> f64 {
+        match self.notation {
+            Notation::Shortest => value,
+            _ if !value.is_finite() => value,
+            _ => self.show(value).to_string().parse().unwrap_or(value),
+        }
+    }
+
+    /// Round every number in a JSON document (integers stay as they are)
+    pub fn round_json(self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Number(number) if number.is_f64() => {
+                if let Some(rounded) = number
+                    .as_f64()
+                    .and_then(|v| serde_json::Number::from_f64(self.round(v)))
+                {
+                    *number = rounded;
+                }
+            }
+            serde_json::Value::Array(items) => {
+                items.iter_mut().for_each(|item| self.round_json(item))
+            }
+            serde_json::Value::Object(fields) => {
+                fields.values_mut().for_each(|field| self.round_json(field))
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A value and the format to write it in (see [`FloatFormat::show`])
+#[derive(Debug, Clone, Copy)]
+pub struct FormattedFloat {
+    value: f64,
+    format: FloatFormat,
+}
+
+impl fmt::Display for FormattedFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = self.format.precision;
+        let text = match self.format.notation {
+            Notation::Fixed => format!("{:.*}", precision, self.value),
+            Notation::Scientific => format!("{:.*e}", precision, self.value),
+            Notation::Shortest => self.value.to_string(),
+        };
+        f.pad(&text)
+    }
+}
+
+/// Formats of the console and of written files
+///
+/// Unless a notation or precision is requested both keep their historic
+/// formats ([`FloatFormat::CONSOLE`] and [`FloatFormat::EXACT`]); once one
+/// is, the same format applies to both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloatFormats {
+    /// Console reports
+    pub console: FloatFormat,
+
+    /// CSV files and JSON metadata
+    pub files: FloatFormat,
+}
+
+impl FloatFormats {
+    /// Formats for the requested notation and precision (fixed notation if
+    /// only a precision is given, six decimals if only a notation is)
+    pub fn new(notation: Option<Notation>, precision: Option<usize>) -> Self {
+        if notation.is_none() && precision.is_none() {
+            return Self::default();
+        }
+        let format = FloatFormat {
+            notation: notation.unwrap_or(Notation::Fixed),
+            precision: precision.unwrap_or(FloatFormat::CONSOLE.precision),
+        };
+        Self {
+            console: format,
+            files: format,
+        }
+    }
+}
+
+impl Default for FloatFormats {
+    fn default() -> Self {
+        Self {
+            console: FloatFormat::CONSOLE,
+            files: FloatFormat::EXACT,
+        }
+    }
+}
+
+/// Symbols written to the console
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Glyphs {
+    /// "↔", "°" and "×"
+    #[default]
+    Unicode,
+
+    /// "<->", " deg" and "x", for terminals that cannot show the others
+    Ascii,
+}
+
+impl Glyphs {
+    /// Between the two sides of a pair
+    pub fn between(self) -> &'static str {
+        match self {
+            Glyphs::Unicode => "↔",
+            Glyphs::Ascii => "<->",
+        }
+    }
+
+    /// After an angle in degrees
+    pub fn degrees(self) -> &'static str {
+        match self {
+            Glyphs::Unicode => "°",
+            Glyphs::Ascii => " deg",
+        }
+    }
+
+    /// Between the factors of a product, e.g. face counts
+    pub fn times(self) -> &'static str {
+        match self {
+            Glyphs::Unicode => "×",
+            Glyphs::Ascii => "x",
+        }
+    }
+
+    /// `text` with the Unicode symbols replaced by these, for messages
+    /// written before the glyphs were known (warnings, stored labels)
+    pub fn apply(self, text: &str) -> Cow<'_, str> {
+        if self == Glyphs::Unicode || text.is_ascii() {
+            return Cow::Borrowed(text);
+        }
+        let mut ascii = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '↔' => ascii.push_str(self.between()),
+                '°' => ascii.push_str(self.degrees()),
+                '×' => ascii.push_str(self.times()),
+                c => ascii.push(c),
+            }
+        }
+        Cow::Owned(ascii)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_float_formats() {
+        let gap = 0.0000123456789;
+        assert_eq!(FloatFormat::CONSOLE.show(gap).to_string(), "0.000012");
+        assert_eq!(FloatFormat::scientific(3).show(gap).to_string(), "1.235e-5");
+        assert_eq!(FloatFormat::EXACT.show(gap).to_string(), "0.0000123456789");
+        assert_eq!(
+            format!("[{:>8}]", FloatFormat::fixed(2).show(1.0)),
+            "[    1.00]"
+        );
+
+        assert_eq!(FloatFormat::scientific(2).round(gap), 1.23e-5);
+        assert_eq!(FloatFormat::EXACT.round(gap), gap);
+        let mut json = serde_json::json!({"gap": gap, "pairs": 12, "areas": [0.123456789]});
+        FloatFormat::fixed(3).round_json(&mut json);
+        assert_eq!(
+            json,
+            serde_json::json!({"gap": 0.0, "pairs": 12, "areas": [0.123]})
+        );
+
+        // Files keep every digit until a format is requested
+        assert_eq!(FloatFormats::default().files, FloatFormat::EXACT);
+        let formats = FloatFormats::new(None, Some(9));
+        assert_eq!(formats.console, FloatFormat::fixed(9));
+        assert_eq!(formats.files, formats.console);
+        assert_eq!(
+            FloatFormats::new(Some("sci".parse().unwrap()), None).files,
+            FloatFormat::scientific(6)
+        );
+        assert!("engineering".parse::<Notation>().is_err());
+    }
+
+    #[test]
+    fn test_ascii_glyphs() {
+        let line = "Lower ↔ Upper: 12 × 40 faces, max angle 3.5°";
+        assert_eq!(Glyphs::Unicode.apply(line), line);
+        assert_eq!(
+            Glyphs::Ascii.apply(line),
+            "Lower <-> Upper: 12 x 40 faces, max angle 3.5 deg"
+        );
+        assert!(Glyphs::Ascii.apply(line).is_ascii());
+        assert!(matches!(Glyphs::Ascii.apply("A <-> B"), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,268 @@
+//! Compact binary mesh format (`.cdmesh`)
+//!
+//! Intended for caching and for handing meshes between processes, where JSON
+//! parsing dominates load time. A file starts with an 8-byte magic string and
+//! a little-endian `u32` format version, followed by the bincode-encoded mesh.
+//! Files may be gzip or zstd compressed (`.cdmesh.gz`, `.cdmesh.zst`).
+
+use crate::error::{ContactDetectorError, Result};
+use crate::io::compression::{open_reader, CompressedWriter, Compression};
+use crate::io::registry::{MeshReader, MeshWriter};
+use crate::mesh::{BlockAssembly, HexElement, Mesh, MeshProvenance, Point};
+use crate::warnings::Warnings;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of every binary mesh file
+pub const BINARY_MESH_MAGIC: [u8; 8] = *b"CDMESH\0\0";
+
+/// Current binary mesh format version
+pub const BINARY_MESH_VERSION: u32 = 2;
+
+/// File extension of the binary mesh format
+pub const BINARY_MESH_EXTENSION: &str = "cdmesh";
+
+/// On-disk layout. bincode is not self-describing, so unlike the JSON schema
+/// no field may be skipped or defaulted; layout changes need a version bump.
+/// Maps are ordered so that a mesh always encodes to the same bytes (bincode
+/// encodes ordered and hash maps alike).
+#[derive(Serialize, Deserialize)]
+struct BinaryMesh {
+    nodes: Vec<[f64; 3]>,
+    elements: Vec<[usize; 8]>,
+    element_blocks: BTreeMap<String, Vec<usize>>,
+    material_ids: Vec<i32>,
+    node_sets: BTreeMap<String, Vec<usize>>,
+    side_sets: BTreeMap<String, Vec<(usize, u8)>>,
+    side_set_dist_factors: BTreeMap<String, Vec<f64>>,
+    side_set_fields: BTreeMap<String, BTreeMap<String, Vec<f64>>>,
+    node_num_map: Vec<i32>,
+    elem_num_map: Vec<i32>,
+    node_fields: BTreeMap<String, Vec<f64>>,
+    element_fields: BTreeMap<String, Vec<f64>>,
+    units: Option<String>,
+    /// Assembly name -> (blocks, nested assemblies); [`BlockAssembly`] skips
+    /// empty lists when serialized, which bincode cannot read back
+    assemblies: BTreeMap<String, (Vec<String>, Vec<String>)>,
+    provenance_source: Option<String>,
+    provenance_format: Option<String>,
+    provenance_history: Vec<String>,
+}
+
+/// Whether a path names a binary mesh file, optionally compressed
+pub fn is_binary_mesh_path<P: AsRef<Path>>(path: P) -> bool {
+    let path = path.as_ref();
+    let path = match Compression::from_path(path) {
+        Compression::None => path.to_path_buf(),
+        _ => path.with_extension(""),
+    };
+    path.extension().and_then(|e| e.to_str()) == Some(BINARY_MESH_EXTENSION)
+}
+
+/// Read a mesh from a binary mesh file
+#[tracing::instrument(skip_all, fields(path = %path.as_ref().display()))]
+pub fn read_binary_mesh<P: AsRef<Path>>(path: P) -> Result<Mesh> {
+    let mut reader = open_reader(path.as_ref())?;
+
+    let mut header = [0u8; 12];
+    reader.read_exact(&mut header).map_err(|_| {
+        ContactDetectorError::ConfigError("Not a binary mesh file: file too short".to_string())
+    })?;
+    if header[..8] != BINARY_MESH_MAGIC {
+        return Err(ContactDetectorError::ConfigError(
+            "Not a binary mesh file: bad magic bytes".to_string(),
+        ));
+    }
+    let version = u32::from_le_bytes([header[8], header[9], header[10], header[11]]);
+    if version != BINARY_MESH_VERSION {
+        return Err(ContactDetectorError::ConfigError(format!(
+            "Unsupported binary mesh version {} (supported: {})",
+            version, BINARY_MESH_VERSION
+        )));
+    }
+
+    let binary: BinaryMesh = bincode::deserialize_from(reader).map_err(|e| {
+        ContactDetectorError::ConfigError(format!("Failed to parse binary mesh: {}", e))
+    })?;
+
+    let mut mesh = Mesh::new();
+    mesh.nodes = binary
+        .nodes
+        .into_iter()
+        .map(|[x, y, z]| Point::new(x, y, z))
+        .collect();
+    mesh.elements = binary.elements.into_iter().map(HexElement::new).collect();
+    mesh.element_blocks = binary.element_blocks.into_iter().collect();
+    mesh.material_ids = binary.material_ids;
+    mesh.node_sets = binary.node_sets.into_iter().collect();
+    mesh.side_sets = binary.side_sets.into_iter().collect();
+    mesh.side_set_dist_factors = binary.side_set_dist_factors.into_iter().collect();
+    mesh.side_set_fields = binary
+        .side_set_fields
+        .into_iter()
+        .map(|(name, fields)| (name, fields.into_iter().collect()))
+        .collect();
+    mesh.node_num_map = binary.node_num_map;
+    mesh.elem_num_map = binary.elem_num_map;
+    mesh.node_fields = binary.node_fields.into_iter().collect();
+    mesh.element_fields = binary.element_fields.into_iter().collect();
+    mesh.units = binary.units;
+    mesh.assemblies = binary
+        .assemblies
+        .into_iter()
+        .map(|(name, (blocks, assemblies))| (name, BlockAssembly { blocks, assemblies }))
+        .collect();
+    mesh.provenance = MeshProvenance {
+        source: binary.provenance_source,
+        format: binary.provenance_format,
+        history: binary.provenance_history,
+        ..Default::default()
+    };
+
+    tracing::info!(
+        "Read binary mesh with {} nodes, {} elements",
+        mesh.num_nodes(),
+        mesh.num_elements()
+    );
+
+    Ok(mesh)
+}
+
+/// Write a mesh to a binary mesh file (compressed if the path ends in `.gz` or `.zst`)
+#[tracing::instrument(skip_all, fields(path = %path.as_ref().display()))]
+pub fn write_binary_mesh<P: AsRef<Path>>(mesh: &Mesh, path: P) -> Result<()> {
+    let binary = BinaryMesh {
+        nodes: mesh.nodes.iter().map(|p| [p.x, p.y, p.z]).collect(),
+        elements: mesh.elements.iter().map(|e| e.node_ids).collect(),
+        element_blocks: mesh.element_blocks.clone().into_iter().collect(),
+        material_ids: mesh.material_ids.clone(),
+        node_sets: mesh.node_sets.clone().into_iter().collect(),
+        side_sets: mesh.side_sets.clone().into_iter().collect(),
+        side_set_dist_factors: mesh.side_set_dist_factors.clone().into_iter().collect(),
+        side_set_fields: mesh
+            .side_set_fields
+            .iter()
+            .map(|(name, fields)| (name.clone(), fields.clone().into_iter().collect()))
+            .collect(),
+        node_num_map: mesh.node_num_map.clone(),
+        elem_num_map: mesh.elem_num_map.clone(),
+        node_fields: mesh.node_fields.clone().into_iter().collect(),
+        element_fields: mesh.element_fields.clone().into_iter().collect(),
+        units: mesh.units.clone(),
+        assemblies: mesh
+            .assemblies
+            .iter()
+            .map(|(name, assembly)| {
+                let members = (assembly.blocks.clone(), assembly.assemblies.clone());
+                (name.clone(), members)
+            })
+            .collect(),
+        provenance_source: mesh.provenance.source.clone(),
+        provenance_format: mesh.provenance.format.clone(),
+        provenance_history: mesh.provenance.history.clone(),
+    };
+
+    let mut writer = CompressedWriter::create(path.as_ref())?;
+    writer.write_all(&BINARY_MESH_MAGIC)?;
+    writer.write_all(&BINARY_MESH_VERSION.to_le_bytes())?;
+    bincode::serialize_into(&mut writer, &binary).map_err(|e| {
+        ContactDetectorError::ConfigError(format!("Failed to write binary mesh: {}", e))
+    })?;
+    writer.finish()
+}
+
+/// The binary mesh format in a [`crate::io::FormatRegistry`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BinaryMeshFormat;
+
+impl MeshReader for BinaryMeshFormat {
+    fn name(&self) -> &str {
+        BINARY_MESH_EXTENSION
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[BINARY_MESH_EXTENSION]
+    }
+
+    fn sniff(&self, header: &[u8]) -> bool {
+        header.starts_with(&BINARY_MESH_MAGIC)
+    }
+
+    fn read(&self, path: &Path) -> Result<(Mesh, Warnings)> {
+        Ok((read_binary_mesh(path)?, Warnings::new()))
+    }
+}
+
+impl MeshWriter for BinaryMeshFormat {
+    fn name(&self) -> &str {
+        BINARY_MESH_EXTENSION
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &[BINARY_MESH_EXTENSION]
+    }
+
+    fn write(&self, mesh: &Mesh, path: &Path) -> Result<()> {
+        write_binary_mesh(mesh, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let mut mesh = Mesh::new();
+        mesh.nodes = (0..8)
+            .map(|i| Point::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+            .collect();
+        mesh.elements = vec![HexElement::new([0, 1, 3, 2, 4, 5, 7, 6])];
+        mesh.element_blocks.insert("Block1".to_string(), vec![0]);
+        mesh.side_sets.insert("top".to_string(), vec![(0, 6)]);
+        mesh.side_set_fields.insert(
+            "top".to_string(),
+            [("contact_gap".to_string(), vec![0.25])]
+                .into_iter()
+                .collect(),
+        );
+        mesh.elem_num_map = vec![42];
+        mesh.assemblies.insert(
+            "Engine".to_string(),
+            BlockAssembly {
+                blocks: vec!["Block1".to_string()],
+                assemblies: Vec::new(),
+            },
+        );
+        mesh.units = Some("mm".to_string());
+        mesh.provenance.history.push("converted".to_string());
+
+        for name in ["test_mesh.cdmesh", "test_mesh.cdmesh.zst"] {
+            let path = std::env::temp_dir().join(name);
+            assert!(is_binary_mesh_path(&path));
+            write_binary_mesh(&mesh, &path).unwrap();
+
+            let loaded = read_binary_mesh(&path).unwrap();
+            assert_eq!(loaded.nodes, mesh.nodes);
+            assert_eq!(loaded.elements[0].node_ids, mesh.elements[0].node_ids);
+            assert_eq!(loaded.side_sets, mesh.side_sets);
+            assert_eq!(loaded.side_set_fields, mesh.side_set_fields);
+            assert_eq!(loaded.assemblies, mesh.assemblies);
+            assert_eq!(loaded.elem_num_map, vec![42]);
+            assert_eq!(loaded.units.as_deref(), Some("mm"));
+            assert_eq!(loaded.provenance, mesh.provenance);
+
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+
+    #[test]
+    fn test_rejects_other_files() {
+        let path = std::env::temp_dir().join("test_not_a_mesh.cdmesh");
+        std::fs::write(&path, b"{\"nodes\": []}").unwrap();
+        assert!(read_binary_mesh(&path).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+}
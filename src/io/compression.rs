@@ -0,0 +1,169 @@
+//! Transparent gzip / zstd compression for text output files
+//!
+//! Readers detect compression from the file's magic bytes, so a compressed
+//! file is read correctly whatever it is called. Writers choose compression
+//! from the extension: `.gz` for gzip, `.zst` for zstd, anything else plain.
+
+use crate::error::Result;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Compression applied to a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Uncompressed
+    None,
+
+    /// gzip (`.gz`)
+    Gzip,
+
+    /// Zstandard (`.zst`)
+    Zstd,
+}
+
+impl Compression {
+    /// Compression implied by a path's final extension
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path.as_ref().extension().and_then(|e| e.to_str()) {
+            Some("gz") => Compression::Gzip,
+            Some("zst") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// Compression identified from the leading bytes of a file
+    fn from_magic(bytes: &[u8]) -> Self {
+        if bytes.starts_with(&GZIP_MAGIC) {
+            Compression::Gzip
+        } else if bytes.starts_with(&ZSTD_MAGIC) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+}
+
+/// Whether a path names a JSON file, optionally compressed
+/// (`.json`, `.json.gz`, `.json.zst`)
+pub fn is_json_path<P: AsRef<Path>>(path: P) -> bool {
+    let path = path.as_ref();
+    let path = match Compression::from_path(path) {
+        Compression::None => path.to_path_buf(),
+        _ => path.with_extension(""),
+    };
+    path.extension().and_then(|e| e.to_str()) == Some("json")
+}
+
+/// Open a file for reading, decompressing it if needed
+pub fn open_reader<P: AsRef<Path>>(path: P) -> Result<Box<dyn Read>> {
+    let mut reader = BufReader::new(File::open(path.as_ref())?);
+    let compression = Compression::from_magic(reader.fill_buf()?);
+
+    Ok(match compression {
+        Compression::None => Box::new(reader),
+        Compression::Gzip => Box::new(flate2::bufread::MultiGzDecoder::new(reader)),
+        Compression::Zstd => Box::new(zstd::Decoder::with_buffer(reader)?),
+    })
+}
+
+/// Writer that compresses according to the output file extension
+///
+/// Call [`CompressedWriter::finish`] once done; it writes the compression
+/// trailer and reports errors that would otherwise be lost on drop.
+pub enum CompressedWriter {
+    /// Uncompressed output
+    Plain(BufWriter<File>),
+
+    /// gzip output
+    Gzip(flate2::write::GzEncoder<BufWriter<File>>),
+
+    /// Zstandard output
+    Zstd(zstd::Encoder<'static, BufWriter<File>>),
+}
+
+impl CompressedWriter {
+    /// Create a file, choosing compression from its extension
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = BufWriter::new(File::create(path.as_ref())?);
+
+        Ok(match Compression::from_path(path) {
+            Compression::None => CompressedWriter::Plain(file),
+            Compression::Gzip => CompressedWriter::Gzip(flate2::write::GzEncoder::new(
+                file,
+                flate2::Compression::default(),
+            )),
+            Compression::Zstd => CompressedWriter::Zstd(zstd::Encoder::new(file, 0)?),
+        })
+    }
+
+    /// Flush all data and finalize the compressed stream
+    pub fn finish(self) -> Result<()> {
+        let mut file = match self {
+            CompressedWriter::Plain(file) => file,
+            CompressedWriter::Gzip(encoder) => encoder.finish()?,
+            CompressedWriter::Zstd(encoder) => encoder.finish()?,
+        };
+        file.flush()?;
+        Ok(())
+    }
+}
+
+impl Write for CompressedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            CompressedWriter::Plain(w) => w.write(buf),
+            CompressedWriter::Gzip(w) => w.write(buf),
+            CompressedWriter::Zstd(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            CompressedWriter::Plain(w) => w.flush(),
+            CompressedWriter::Gzip(w) => w.flush(),
+            CompressedWriter::Zstd(w) => w.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_json_path() {
+        assert!(is_json_path("mesh.json"));
+        assert!(is_json_path("mesh.json.gz"));
+        assert!(is_json_path("mesh.json.zst"));
+        assert!(!is_json_path("mesh.exo"));
+        assert!(!is_json_path("mesh.gz"));
+    }
+
+    #[test]
+    fn test_round_trip_all_compressions() {
+        let dir = std::env::temp_dir();
+        for name in [
+            "cd_compress.txt",
+            "cd_compress.txt.gz",
+            "cd_compress.txt.zst",
+        ] {
+            let path = dir.join(name);
+            let mut writer = CompressedWriter::create(&path).unwrap();
+            writer.write_all(b"hello contact").unwrap();
+            writer.finish().unwrap();
+
+            let mut text = String::new();
+            open_reader(&path)
+                .unwrap()
+                .read_to_string(&mut text)
+                .unwrap();
+            assert_eq!(text, "hello contact");
+
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Thermal contact conductance table
+//!
+//! One CSV row per contact interface, for import into a thermal solver:
+//!
+//! ```text
+//! interface,part_a,part_b,material_a,material_b,paired_area,mean_gap,conductance,total_conductance
+//! ```
+//!
+//! `conductance` is per unit area; `total_conductance` is over the paired
+//! area. Interfaces are numbered from 1 in the order of the contact pairs.
+
+use crate::contact::InterfaceConductance;
+use crate::error::Result;
+use crate::format::FloatFormat;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Write the conductance of each interface as CSV
+#[tracing::instrument(skip_all, fields(interfaces = interfaces.len()))]
+pub fn write_conductance_table(
+    interfaces: &[InterfaceConductance],
+    output_path: &Path,
+    format: FloatFormat,
+) -> Result<()> {
+    let mut out = BufWriter::new(File::create(output_path)?);
+    writeln!(
+        out,
+        "interface,part_a,part_b,material_a,material_b,paired_area,mean_gap,conductance,\
+         total_conductance"
+    )?;
+    for (index, interface) in interfaces.iter().enumerate() {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{}",
+            index + 1,
+            interface.part_a,
+            interface.part_b,
+            interface.material_a,
+            interface.material_b,
+            format.show(interface.paired_area),
+            format.show(interface.mean_gap),
+            format.show(interface.conductance),
+            format.show(interface.total_conductance)
+        )?;
+    }
+    out.flush()?;
+    Ok(())
+}
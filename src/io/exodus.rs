@@ -3,13 +3,95 @@
 //! Exodus II is a NetCDF-based file format for finite element data.
 //! This module provides functionality to read and write Exodus II files.
 
+use crate::contact::{AutoContactReport, ContactInterface, FaceValueSource};
 use crate::error::{ContactDetectorError, Result};
-use crate::mesh::{HexElement, Mesh, Point};
+use crate::io::exodus_names::{
+    decode_name, encode_names, fit_names, EXODUS_MAX_NAME_LEN, EXODUS_NAME_LEN_LIMIT,
+};
+use crate::io::registry::{MeshReader, MeshWriter};
+use crate::io::surface_writer::{sideset_variables, AttributeSet, FaceContext};
+use crate::mesh::{
+    BlockAssembly, BlockReadRecord, BlockReadStatus, ElementSideIndex, HexElement, Mesh, Point,
+};
+use crate::warnings::{WarningKind, Warnings};
+use std::collections::HashMap;
 use std::path::Path;
 
+/// Entity type of assembly members that are element blocks (`EX_ELEM_BLOCK`)
+const EX_ELEM_BLOCK: i64 = 1;
+
+/// Entity type of assembly members that are assemblies (`EX_ASSEMBLY`)
+const EX_ASSEMBLY: i64 = 16;
+
 /// Exodus II file reader
 pub struct ExodusReader {
     file: netcdf::File,
+    path: std::path::PathBuf,
+}
+
+/// An element block as stored in an Exodus file, whether or not it can be
+/// read
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExodusBlockInfo {
+    /// Block ID (`eb_prop1`), or its 1-based position without one
+    pub id: i64,
+
+    /// Block name, as it is named in a read mesh
+    pub name: String,
+
+    /// Element type, e.g. `HEX8` or `TETRA4`
+    pub element_type: String,
+
+    pub num_elements: usize,
+    pub nodes_per_element: usize,
+
+    /// Names of the element attributes (`attrib_name<n>`), or `attribute_<i>`
+    /// for unnamed ones
+    pub attribute_names: Vec<String>,
+}
+
+impl ExodusBlockInfo {
+    /// Whether the block is read into a mesh (8-node hexahedra)
+    pub fn is_supported(&self) -> bool {
+        is_hex_type(&self.element_type) && self.nodes_per_element == 8
+    }
+}
+
+/// What an Exodus file contains besides the mesh itself: all element blocks,
+/// the names of the result variables and the time steps
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExodusFileInfo {
+    /// Database title
+    pub title: Option<String>,
+
+    pub num_dim: usize,
+    pub num_nodes: usize,
+    pub num_elements: usize,
+
+    /// All element blocks in file order, including unsupported ones
+    pub blocks: Vec<ExodusBlockInfo>,
+
+    /// Names of the global, nodal, element, node set and side set variables
+    pub global_variables: Vec<String>,
+    pub nodal_variables: Vec<String>,
+    pub element_variables: Vec<String>,
+    pub node_set_variables: Vec<String>,
+    pub side_set_variables: Vec<String>,
+
+    /// Time value of each time step
+    pub times: Vec<f64>,
+}
+
+impl ExodusFileInfo {
+    /// Number of time steps
+    pub fn num_time_steps(&self) -> usize {
+        self.times.len()
+    }
+}
+
+/// Whether an Exodus element type names a hexahedron
+fn is_hex_type(element_type: &str) -> bool {
+    element_type.to_uppercase().starts_with("HEX")
 }
 
 impl ExodusReader {
@@ -19,21 +101,113 @@ impl ExodusReader {
             ContactDetectorError::ExodusReadError(format!("Failed to open file: {}", e))
         })?;
 
-        Ok(Self { file })
+        Ok(Self {
+            file,
+            path: path.as_ref().to_path_buf(),
+        })
+    }
+
+    /// Describe the file: all element blocks (including those a mesh read
+    /// skips), result variable names and time steps
+    pub fn file_info(&self) -> Result<ExodusFileInfo> {
+        let title = self.file.attribute("title").and_then(|attr| match attr.value() {
+            Ok(netcdf::AttributeValue::Str(title)) => Some(title),
+            _ => None,
+        });
+        let dimension = |name: &str| self.file.dimension(name).map_or(0, |d| d.len());
+
+        let block_ids: Vec<i64> = self
+            .file
+            .variable("eb_prop1")
+            .and_then(|var| var.get::<i64, _>(..).ok())
+            .map(|ids| ids.into_iter().collect())
+            .unwrap_or_default();
+        let mut blocks = Vec::new();
+        for blk_id in 1..=dimension("num_el_blk") {
+            let var = self.file.variable(&format!("connect{}", blk_id));
+            let num_attributes = dimension(&format!("num_att_in_blk{}", blk_id));
+            let mut attribute_names = self
+                .file
+                .variable(&format!("attrib_name{}", blk_id))
+                .and_then(|var| self.read_string_array(&var).ok())
+                .unwrap_or_default();
+            attribute_names.resize(num_attributes.max(attribute_names.len()), String::new());
+            for (i, name) in attribute_names.iter_mut().enumerate() {
+                if name.is_empty() {
+                    *name = format!("attribute_{}", i + 1);
+                }
+            }
+            blocks.push(ExodusBlockInfo {
+                id: block_ids.get(blk_id - 1).copied().unwrap_or(blk_id as i64),
+                name: self
+                    .get_block_name(blk_id)
+                    .unwrap_or_else(|| format!("Block_{}", blk_id)),
+                element_type: var
+                    .as_ref()
+                    .and_then(|var| string_attribute(var, "elem_type"))
+                    .unwrap_or_else(|| "unknown".to_string()),
+                num_elements: dimension(&format!("num_el_in_blk{}", blk_id)),
+                nodes_per_element: dimension(&format!("num_nod_per_el{}", blk_id)),
+                attribute_names,
+            });
+        }
+
+        let variable_names = |name: &str| {
+            self.file
+                .variable(name)
+                .and_then(|var| self.read_string_array(&var).ok())
+                .unwrap_or_default()
+        };
+        let times = match self.file.variable("time_whole") {
+            Some(var) => var
+                .get::<f64, _>(..)
+                .map_err(|e| {
+                    ContactDetectorError::NetcdfError(format!("Failed to read time values: {}", e))
+                })?
+                .into_iter()
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Ok(ExodusFileInfo {
+            title,
+            num_dim: dimension("num_dim"),
+            num_nodes: dimension("num_nodes"),
+            num_elements: dimension("num_elem"),
+            blocks,
+            global_variables: variable_names("name_glo_var"),
+            nodal_variables: variable_names("name_nod_var"),
+            element_variables: variable_names("name_elem_var"),
+            node_set_variables: variable_names("name_nset_var"),
+            side_set_variables: variable_names("name_sset_var"),
+            times,
+        })
     }
 
     /// Read the complete mesh from the Exodus file
+    ///
+    /// Non-fatal problems (skipped blocks or sets) are only logged; use
+    /// [`ExodusReader::read_mesh_with_warnings`] to inspect them.
     pub fn read_mesh(&self) -> Result<Mesh> {
-        log::info!("Reading Exodus II mesh...");
+        self.read_mesh_with_warnings().map(|(mesh, _)| mesh)
+    }
+
+    /// Read the complete mesh, returning any skipped data as warnings
+    #[tracing::instrument(skip_all)]
+    pub fn read_mesh_with_warnings(&self) -> Result<(Mesh, Warnings)> {
+        tracing::info!("Reading Exodus II mesh...");
 
         let mut mesh = Mesh::new();
+        let mut warnings = Warnings::new();
+        mesh.provenance.source = Some(self.path.display().to_string());
+        mesh.provenance.format = Some("exodus".to_string());
 
         // Read dimensions
         let num_nodes = self.get_dimension("num_nodes")?;
         let num_elem = self.get_dimension("num_elem")?;
         let num_dim = self.get_dimension("num_dim")?;
 
-        log::debug!(
+        tracing::debug!(
             "Mesh dimensions: {} nodes, {} elements, {} spatial dimensions",
             num_nodes,
             num_elem,
@@ -42,26 +216,61 @@ impl ExodusReader {
 
         // Read nodes
         mesh.nodes = self.read_nodes(num_nodes, num_dim)?;
-        log::debug!("Read {} nodes", mesh.nodes.len());
+        tracing::debug!("Read {} nodes", mesh.nodes.len());
 
         // Read element blocks
-        self.read_element_blocks(&mut mesh)?;
-        log::debug!(
+        let elem_file_positions = self.read_element_blocks(&mut mesh, &mut warnings)?;
+        tracing::debug!(
             "Read {} elements in {} blocks",
             mesh.num_elements(),
             mesh.num_blocks()
         );
 
+        // Read global ID maps. Skipped blocks shift internal element indices, so
+        // element IDs are looked up by position in the file.
+        if let Some(map) = self.read_num_map("node_num_map", num_nodes)? {
+            mesh.node_num_map = map;
+        }
+        match self.read_num_map("elem_num_map", num_elem)? {
+            Some(map) => {
+                mesh.elem_num_map = elem_file_positions
+                    .iter()
+                    .map(|&pos| map.get(pos).copied().unwrap_or(pos as i32 + 1))
+                    .collect();
+            }
+            None if elem_file_positions.iter().enumerate().any(|(i, &pos)| i != pos) => {
+                mesh.elem_num_map = elem_file_positions.iter().map(|&pos| pos as i32 + 1).collect();
+            }
+            None => {}
+        }
+
+        self.check_names(&mut warnings);
+
+        // Read assemblies of element blocks
+        self.read_assemblies(&mut mesh, &mut warnings)?;
+        if !mesh.assemblies.is_empty() {
+            tracing::debug!("Read {} assemblies", mesh.assemblies.len());
+        }
+
         // Read node sets
-        self.read_node_sets(&mut mesh)?;
-        log::debug!("Read {} node sets", mesh.node_sets.len());
+        self.read_node_sets(&mut mesh, &mut warnings)?;
+        tracing::debug!("Read {} node sets", mesh.node_sets.len());
 
         // Read side sets
-        self.read_side_sets(&mut mesh)?;
-        log::debug!("Read {} side sets", mesh.side_sets.len());
+        let mut file_pos_to_elem = vec![None; num_elem];
+        for (elem_idx, &pos) in elem_file_positions.iter().enumerate() {
+            if let Some(slot) = file_pos_to_elem.get_mut(pos) {
+                *slot = Some(elem_idx);
+            }
+        }
+        self.read_side_sets(&mut mesh, &file_pos_to_elem, &mut warnings)?;
+        tracing::debug!("Read {} side sets", mesh.side_sets.len());
 
-        log::info!("Successfully read Exodus II mesh");
-        Ok(mesh)
+        tracing::info!(
+            "Successfully read Exodus II mesh ({} warnings)",
+            warnings.len()
+        );
+        Ok((mesh, warnings))
     }
 
     /// Get a dimension value from the file
@@ -80,10 +289,27 @@ impl ExodusReader {
             )));
         }
 
-        // Read coordinate arrays
-        let coordx = self.read_variable_f64("coordx", num_nodes)?;
-        let coordy = self.read_variable_f64("coordy", num_nodes)?;
-        let coordz = self.read_variable_f64("coordz", num_nodes)?;
+        // Read coordinate arrays, either one per axis or (in older files) a
+        // single coord(num_dim, num_nodes) array
+        let (coordx, coordy, coordz) = if self.file.variable("coordx").is_some() {
+            (
+                self.read_variable_f64("coordx", num_nodes)?,
+                self.read_variable_f64("coordy", num_nodes)?,
+                self.read_variable_f64("coordz", num_nodes)?,
+            )
+        } else if self.file.variable("coord").is_some() {
+            let coord = self.read_variable_f64("coord", num_dim * num_nodes)?;
+            let axes = self.coordinate_axes();
+            let row = |axis: usize| {
+                let start = axes[axis] * num_nodes;
+                coord[start..start + num_nodes].to_vec()
+            };
+            (row(0), row(1), row(2))
+        } else {
+            return Err(ContactDetectorError::ExodusReadError(
+                "No nodal coordinates found (expected coordx/coordy/coordz or coord)".to_string(),
+            ));
+        };
 
         // Combine into points
         let nodes = coordx
@@ -96,22 +322,60 @@ impl ExodusReader {
         Ok(nodes)
     }
 
+    /// Row of the x, y and z coordinates in a 2D `coord` array
+    ///
+    /// Taken from `coor_names` when it names the axes (e.g. `z`, `x`, `y`),
+    /// otherwise rows are assumed to be in x, y, z order.
+    fn coordinate_axes(&self) -> [usize; 3] {
+        let names = self
+            .file
+            .variable("coor_names")
+            .and_then(|var| self.read_string_array(&var).ok())
+            .unwrap_or_default();
+        let row_of = |axis: &str| {
+            names
+                .iter()
+                .position(|name| name.trim().eq_ignore_ascii_case(axis))
+        };
+        match (row_of("x"), row_of("y"), row_of("z")) {
+            (Some(x), Some(y), Some(z)) if x < 3 && y < 3 && z < 3 => [x, y, z],
+            _ => [0, 1, 2],
+        }
+    }
+
     /// Read all element blocks
-    fn read_element_blocks(&self, mesh: &mut Mesh) -> Result<()> {
+    ///
+    /// Returns the 0-based position in the file of every element read, which
+    /// differs from the element index once a non-hexahedral block is skipped.
+    fn read_element_blocks(&self, mesh: &mut Mesh, warnings: &mut Warnings) -> Result<Vec<usize>> {
         let num_el_blk = match self.file.dimension("num_el_blk") {
             Some(dim) => dim.len(),
-            None => return Ok(()), // No element blocks
+            None => return Ok(Vec::new()), // No element blocks
         };
 
+        let mut file_positions = Vec::new();
+        let mut file_offset = 0;
         for blk_id in 1..=num_el_blk {
-            self.read_element_block(mesh, blk_id)?;
+            let start = mesh.elements.len();
+            let record = self.read_element_block(mesh, blk_id, warnings)?;
+            mesh.provenance.blocks.push(record);
+            file_positions.extend(file_offset..file_offset + (mesh.elements.len() - start));
+
+            file_offset += self
+                .get_dimension(&format!("num_el_in_blk{}", blk_id))
+                .unwrap_or(mesh.elements.len() - start);
         }
 
-        Ok(())
+        Ok(file_positions)
     }
 
-    /// Read a single element block
-    fn read_element_block(&self, mesh: &mut Mesh, blk_id: usize) -> Result<()> {
+    /// Read a single element block, reporting whether it was read or skipped
+    fn read_element_block(
+        &self,
+        mesh: &mut Mesh,
+        blk_id: usize,
+        warnings: &mut Warnings,
+    ) -> Result<BlockReadRecord> {
         // Get element block metadata
         let connect_var = format!("connect{}", blk_id);
         let var = self.file.variable(&connect_var).ok_or_else(|| {
@@ -122,34 +386,34 @@ impl ExodusReader {
         })?;
 
         // Get element type from attribute
-        let elem_type = var
-            .attribute("elem_type")
-            .and_then(|attr| attr.value().ok())
-            .and_then(|val| {
-                if let netcdf::AttributeValue::Str(s) = val {
-                    Some(s)
-                } else {
-                    None
-                }
-            })
-            .ok_or_else(|| {
-                ContactDetectorError::ExodusReadError(format!(
-                    "Element type attribute not found for block {}",
-                    blk_id
-                ))
-            })?;
+        let elem_type = string_attribute(&var, "elem_type").ok_or_else(|| {
+            ContactDetectorError::ExodusReadError(format!(
+                "Element type attribute not found for block {}",
+                blk_id
+            ))
+        })?;
 
-        log::debug!("Reading element block {}: type = {}", blk_id, elem_type);
+        tracing::debug!("Reading element block {}: type = {}", blk_id, elem_type);
+
+        // Get block name
+        let block_name = self
+            .get_block_name(blk_id)
+            .unwrap_or_else(|| format!("Block_{}", blk_id));
 
         // Check if this is a hex block
-        let elem_type_upper = elem_type.to_uppercase();
-        if !elem_type_upper.starts_with("HEX") && !elem_type_upper.starts_with("HEXAHEDRON") {
-            log::warn!(
-                "Skipping non-hexahedral block {} (type: {})",
-                blk_id,
-                elem_type
+        if !is_hex_type(&elem_type) {
+            let reason = format!("unsupported element type {} (only HEX8 is read)", elem_type);
+            warnings.push(
+                WarningKind::SkippedBlock,
+                format!("Skipping block {} '{}': {}", blk_id, block_name, reason),
             );
-            return Ok(());
+            return Ok(BlockReadRecord {
+                name: block_name,
+                element_type: elem_type,
+                num_elements: var.dimensions().first().map_or(0, |d| d.len()),
+                status: BlockReadStatus::Skipped,
+                reason: Some(reason),
+            });
         }
 
         // Read connectivity array
@@ -180,18 +444,13 @@ impl ExodusReader {
         })?;
         let connectivity: Vec<i32> = connectivity_array.into_iter().collect();
 
-        // Get block name
-        let block_name = self
-            .get_block_name(blk_id)
-            .unwrap_or_else(|| format!("Block_{}", blk_id));
-
         // Convert to hex elements
         let block_start_idx = mesh.elements.len();
         for elem_idx in 0..num_elem_in_blk {
             let offset = elem_idx * num_nodes_per_elem;
             let mut node_ids = [0usize; 8];
 
-            for i in 0..8 {
+            for (i, node_id_slot) in node_ids.iter_mut().enumerate() {
                 // Convert from 1-based to 0-based indexing
                 let conn_idx = offset + i;
                 let node_value = *connectivity.get(conn_idx).ok_or_else(|| {
@@ -208,7 +467,7 @@ impl ExodusReader {
                         node_value
                     ))
                 })?;
-                node_ids[i] = node_id;
+                *node_id_slot = node_id;
             }
 
             mesh.elements.push(HexElement::new(node_ids));
@@ -218,9 +477,44 @@ impl ExodusReader {
 
         // Store block indices
         let block_indices: Vec<usize> = (block_start_idx..mesh.elements.len()).collect();
-        mesh.element_blocks.insert(block_name, block_indices);
+        mesh.element_blocks.insert(block_name.clone(), block_indices);
+
+        Ok(BlockReadRecord {
+            name: block_name,
+            element_type: elem_type,
+            num_elements: num_elem_in_blk,
+            status: BlockReadStatus::Read,
+            reason: None,
+        })
+    }
 
-        Ok(())
+    /// Warn about block and set names that are not valid UTF-8 (they are read
+    /// with the invalid bytes replaced by `_`)
+    fn check_names(&self, warnings: &mut Warnings) {
+        for (variable, what) in [
+            ("eb_names", "Element block"),
+            ("ss_names", "Side set"),
+            ("ns_names", "Node set"),
+        ] {
+            let Some(var) = self.file.variable(variable) else {
+                continue;
+            };
+            let dims = var.dimensions();
+            let (Some(width), Ok(chars)) = (dims.last().map(|d| d.len()), var.get::<u8, _>(..))
+            else {
+                continue;
+            };
+            let chars: Vec<u8> = chars.into_iter().collect();
+            for row in chars.chunks(width.max(1)) {
+                let (name, valid) = decode_name(row);
+                if !valid {
+                    warnings.push(
+                        WarningKind::InvalidName,
+                        format!("{} name is not valid UTF-8, read as '{}'", what, name),
+                    );
+                }
+            }
+        }
     }
 
     /// Get element block name
@@ -244,8 +538,99 @@ impl ExodusReader {
         None
     }
 
+    /// Read assembly records (`assembly_entity<id>` variables), resolving
+    /// their members from element block and assembly IDs to names
+    fn read_assemblies(&self, mesh: &mut Mesh, warnings: &mut Warnings) -> Result<()> {
+        let mut records = Vec::new();
+        for var in self.file.variables() {
+            let var_name = var.name();
+            let Some(suffix) = var_name.strip_prefix("assembly_entity") else {
+                continue;
+            };
+            let id = int_attribute(&var, "_id").or_else(|| suffix.parse().ok());
+            let name = string_attribute(&var, "_name")
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| format!("Assembly_{}", suffix));
+            let entity_type = int_attribute(&var, "_type");
+            let members = var.get::<i64, _>(..).map_err(|e| {
+                ContactDetectorError::NetcdfError(format!(
+                    "Failed to read assembly '{}': {}",
+                    name, e
+                ))
+            })?;
+            records.push((
+                id,
+                name,
+                entity_type,
+                members.into_iter().collect::<Vec<i64>>(),
+            ));
+        }
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        // Members are referenced by ID: element blocks by their eb_prop1
+        // entry, assemblies by their own
+        let block_ids: Vec<i64> = self
+            .file
+            .variable("eb_prop1")
+            .and_then(|var| var.get::<i64, _>(..).ok())
+            .map(|ids| ids.into_iter().collect())
+            .unwrap_or_default();
+        let block_name = |id: i64| {
+            let blk_id = match block_ids.iter().position(|&b| b == id) {
+                Some(pos) => pos + 1,
+                None if block_ids.is_empty() && id > 0 => id as usize,
+                None => return format!("Block_{}", id),
+            };
+            self.get_block_name(blk_id)
+                .unwrap_or_else(|| format!("Block_{}", blk_id))
+        };
+        let assembly_names: HashMap<i64, String> = records
+            .iter()
+            .filter_map(|(id, name, _, _)| Some(((*id)?, name.clone())))
+            .collect();
+
+        for (_, name, entity_type, members) in records {
+            let assembly = match entity_type {
+                Some(EX_ELEM_BLOCK) => BlockAssembly {
+                    blocks: members.into_iter().map(&block_name).collect(),
+                    assemblies: Vec::new(),
+                },
+                Some(EX_ASSEMBLY) => BlockAssembly {
+                    blocks: Vec::new(),
+                    assemblies: members
+                        .into_iter()
+                        .map(|id| {
+                            assembly_names
+                                .get(&id)
+                                .cloned()
+                                .unwrap_or_else(|| format!("Assembly_{}", id))
+                        })
+                        .collect(),
+                },
+                other => {
+                    warnings.push(
+                        WarningKind::SkippedAssembly,
+                        format!(
+                            "Skipping assembly '{}': members of entity type {} (only element \
+                             blocks and assemblies are read)",
+                            name,
+                            other.map_or_else(|| "unknown".to_string(), |t| t.to_string())
+                        ),
+                    );
+                    continue;
+                }
+            };
+            mesh.assemblies.insert(name, assembly);
+        }
+
+        Ok(())
+    }
+
     /// Read node sets
-    fn read_node_sets(&self, mesh: &mut Mesh) -> Result<()> {
+    fn read_node_sets(&self, mesh: &mut Mesh, warnings: &mut Warnings) -> Result<()> {
         let num_node_sets = match self.file.dimension("num_node_sets") {
             Some(dim) => dim.len(),
             None => return Ok(()), // No node sets
@@ -274,7 +659,10 @@ impl ExodusReader {
                                 mesh.node_sets.insert(name, indices);
                             }
                             Err(e) => {
-                                log::warn!("Skipping node set '{}': {}", name, e);
+                                warnings.push(
+                                    WarningKind::SkippedNodeSet,
+                                    format!("Skipping node set '{}': {}", name, e),
+                                );
                             }
                         }
                     }
@@ -298,12 +686,19 @@ impl ExodusReader {
     }
 
     /// Read side sets
-    fn read_side_sets(&self, mesh: &mut Mesh) -> Result<()> {
+    fn read_side_sets(
+        &self,
+        mesh: &mut Mesh,
+        file_pos_to_elem: &[Option<usize>],
+        warnings: &mut Warnings,
+    ) -> Result<()> {
         let num_side_sets = match self.file.dimension("num_side_sets") {
             Some(dim) => dim.len(),
             None => return Ok(()), // No side sets
         };
 
+        let variable_names = self.read_side_set_variable_names(warnings);
+
         for ss_id in 1..=num_side_sets {
             if let Ok(name) = self.get_sideset_name(ss_id) {
                 let elem_var = format!("elem_ss{}", ss_id);
@@ -318,24 +713,52 @@ impl ExodusReader {
                         // Convert from 1-based to 0-based indexing with validation
                         let side_list: Result<Vec<(usize, u8)>> = elems_array
                             .into_iter()
-                            .zip(sides_array.into_iter())
+                            .zip(sides_array)
                             .map(|(e, s)| {
-                                let elem_id = (e as usize).checked_sub(1).ok_or_else(|| {
+                                let elem_pos = (e as usize).checked_sub(1).ok_or_else(|| {
                                     ContactDetectorError::InvalidMeshTopology(format!(
                                         "Invalid element ID in side set '{}': {} (expected 1-based indexing)",
                                         name, e
                                     ))
                                 })?;
-                                Ok((elem_id, s as u8))
+                                let elem_idx = file_pos_to_elem
+                                    .get(elem_pos)
+                                    .copied()
+                                    .flatten()
+                                    .ok_or_else(|| {
+                                        ContactDetectorError::InvalidMeshTopology(format!(
+                                            "Side set '{}' references element {} outside the hexahedral blocks",
+                                            name, e
+                                        ))
+                                    })?;
+                                Ok((elem_idx, s as u8))
                             })
                             .collect();
 
                         match side_list {
                             Ok(list) => {
+                                self.read_side_set_dist_factors(
+                                    mesh,
+                                    ss_id,
+                                    &name,
+                                    list.len(),
+                                    warnings,
+                                );
+                                self.read_side_set_variables(
+                                    mesh,
+                                    ss_id,
+                                    &name,
+                                    list.len(),
+                                    &variable_names,
+                                    warnings,
+                                );
                                 mesh.side_sets.insert(name, list);
                             }
                             Err(e) => {
-                                log::warn!("Skipping side set '{}': {}", name, e);
+                                warnings.push(
+                                    WarningKind::SkippedSideSet,
+                                    format!("Skipping side set '{}': {}", name, e),
+                                );
                             }
                         }
                     }
@@ -346,6 +769,119 @@ impl ExodusReader {
         Ok(())
     }
 
+    /// Read distribution factors (`dist_fact_ss*`) for a side set, if present
+    fn read_side_set_dist_factors(
+        &self,
+        mesh: &mut Mesh,
+        ss_id: usize,
+        name: &str,
+        num_sides: usize,
+        warnings: &mut Warnings,
+    ) {
+        let Some(var) = self.file.variable(&format!("dist_fact_ss{}", ss_id)) else {
+            return;
+        };
+
+        match var.get::<f64, _>(..) {
+            Ok(values) => {
+                let factors: Vec<f64> = values.into_iter().collect();
+                if factors.len() == num_sides * 4 {
+                    mesh.side_set_dist_factors.insert(name.to_string(), factors);
+                } else {
+                    warnings.push(
+                        WarningKind::SkippedDistFactors,
+                        format!(
+                            "Skipping distribution factors of side set '{}': {} values for {} sides",
+                            name,
+                            factors.len(),
+                            num_sides
+                        ),
+                    );
+                }
+            }
+            Err(e) => warnings.push(
+                WarningKind::SkippedDistFactors,
+                format!(
+                    "Skipping distribution factors of side set '{}': {}",
+                    name, e
+                ),
+            ),
+        }
+    }
+
+    /// Names of the side set variables (`name_sset_var`), if any
+    fn read_side_set_variable_names(&self, warnings: &mut Warnings) -> Vec<String> {
+        let Some(var) = self.file.variable("name_sset_var") else {
+            return Vec::new();
+        };
+        match self.read_string_array(&var) {
+            Ok(names) => names,
+            Err(e) => {
+                warnings.push(
+                    WarningKind::SkippedSideSetVariable,
+                    format!("Skipping side set variables: {}", e),
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    /// Read the side set variables (`vals_sset_var*ss*`) of a side set at the
+    /// last time step, if present
+    fn read_side_set_variables(
+        &self,
+        mesh: &mut Mesh,
+        ss_id: usize,
+        name: &str,
+        num_sides: usize,
+        variable_names: &[String],
+        warnings: &mut Warnings,
+    ) {
+        let num_steps = self.file.dimension("time_step").map_or(0, |d| d.len());
+        let Some(last_step) = num_steps.checked_sub(1) else {
+            return;
+        };
+
+        for (var_idx, var_name) in variable_names.iter().enumerate() {
+            let Some(var) = self
+                .file
+                .variable(&format!("vals_sset_var{}ss{}", var_idx + 1, ss_id))
+            else {
+                continue;
+            };
+
+            match var.get::<f64, _>((last_step, ..)) {
+                Ok(values) => {
+                    let values: Vec<f64> = values.into_iter().collect();
+                    if values.len() == num_sides {
+                        mesh.side_set_fields
+                            .entry(name.to_string())
+                            .or_default()
+                            .insert(var_name.clone(), values);
+                    } else {
+                        warnings.push(
+                            WarningKind::SkippedSideSetVariable,
+                            format!(
+                                "Skipping variable '{}' of side set '{}': {} values for {} sides",
+                                var_name,
+                                name,
+                                values.len(),
+                                num_sides
+                            ),
+                        );
+                    }
+                }
+                Err(e) => warnings.push(
+                    WarningKind::SkippedSideSetVariable,
+                    format!(
+                        "Skipping variable '{}' of side set '{}': {}",
+                        var_name, name, e
+                    ),
+                ),
+            }
+        }
+    }
+
     /// Get side set name
     fn get_sideset_name(&self, ss_id: usize) -> Result<String> {
         if let Some(var) = self.file.variable("ss_names") {
@@ -381,6 +917,29 @@ impl ExodusReader {
         Ok(data)
     }
 
+    /// Read an optional ID map (`node_num_map` / `elem_num_map`)
+    fn read_num_map(&self, name: &str, expected_len: usize) -> Result<Option<Vec<i32>>> {
+        let Some(var) = self.file.variable(name) else {
+            return Ok(None);
+        };
+
+        let values = var.get::<i32, _>(..).map_err(|e| {
+            ContactDetectorError::NetcdfError(format!("Failed to read variable '{}': {}", name, e))
+        })?;
+        let map: Vec<i32> = values.into_iter().collect();
+
+        if map.len() != expected_len {
+            return Err(ContactDetectorError::ExodusReadError(format!(
+                "Variable '{}' has wrong length: expected {}, got {}",
+                name,
+                expected_len,
+                map.len()
+            )));
+        }
+
+        Ok(Some(map))
+    }
+
     /// Read a string array from a NetCDF variable (stored as 2D char array)
     fn read_string_array(&self, var: &netcdf::Variable) -> Result<Vec<String>> {
         // NetCDF strings are typically stored as 2D char arrays
@@ -397,10 +956,7 @@ impl ExodusReader {
                 ContactDetectorError::NetcdfError(format!("Failed to read string array: {}", e))
             })?;
             let chars: Vec<u8> = chars_array.into_iter().collect();
-            let s = String::from_utf8_lossy(&chars)
-                .trim_end_matches('\0')
-                .to_string();
-            return Ok(vec![s]);
+            return Ok(vec![decode_name(&chars).0]);
         }
 
         if dims.len() == 2 {
@@ -417,12 +973,7 @@ impl ExodusReader {
             for i in 0..num_strings {
                 let start = i * string_len;
                 let end = start + string_len;
-                let string_bytes = &chars[start..end];
-                let s = String::from_utf8_lossy(string_bytes)
-                    .trim_end_matches('\0')
-                    .trim()
-                    .to_string();
-                strings.push(s);
+                strings.push(decode_name(&chars[start..end]).0);
             }
 
             return Ok(strings);
@@ -435,12 +986,109 @@ impl ExodusReader {
     }
 }
 
+/// Value of a text attribute of a variable
+fn string_attribute(var: &netcdf::Variable, name: &str) -> Option<String> {
+    match var.attribute(name)?.value().ok()? {
+        netcdf::AttributeValue::Str(value) => Some(value),
+        _ => None,
+    }
+}
+
+/// Value of an integer attribute of a variable
+fn int_attribute(var: &netcdf::Variable, name: &str) -> Option<i64> {
+    match var.attribute(name)?.value().ok()? {
+        netcdf::AttributeValue::Int(value) => Some(value as i64),
+        netcdf::AttributeValue::Ints(values) => values.first().map(|&v| v as i64),
+        netcdf::AttributeValue::Longlong(value) => Some(value),
+        netcdf::AttributeValue::Longlongs(values) => values.first().copied(),
+        _ => None,
+    }
+}
+
+/// Options for writing Exodus II files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExodusWriteOptions {
+    /// Longest block, set and variable name to store (at most 255); longer
+    /// names are truncated and made unique
+    pub max_name_length: usize,
+}
+
+impl Default for ExodusWriteOptions {
+    fn default() -> Self {
+        Self {
+            max_name_length: EXODUS_MAX_NAME_LEN,
+        }
+    }
+}
+
+/// Magic bytes of the NetCDF classic/64-bit formats and of HDF5 (NetCDF-4),
+/// the containers of Exodus II files
+const NETCDF_MAGIC: &[u8] = b"CDF";
+const HDF5_MAGIC: &[u8] = b"\x89HDF\r\n\x1a\n";
+
+/// The Exodus II format in a [`crate::io::FormatRegistry`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExodusFormat {
+    /// Options used when writing
+    pub write_options: ExodusWriteOptions,
+}
+
+impl MeshReader for ExodusFormat {
+    fn name(&self) -> &str {
+        "exodus"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["exo", "e", "g", "gen", "ex2", "exoii"]
+    }
+
+    fn sniff(&self, header: &[u8]) -> bool {
+        header.starts_with(NETCDF_MAGIC) || header.starts_with(HDF5_MAGIC)
+    }
+
+    fn read(&self, path: &Path) -> Result<(Mesh, Warnings)> {
+        ExodusReader::open(path)?.read_mesh_with_warnings()
+    }
+}
+
+impl MeshWriter for ExodusFormat {
+    fn name(&self) -> &str {
+        "exodus"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["exo", "e", "g", "gen", "ex2", "exoii"]
+    }
+
+    fn write(&self, mesh: &Mesh, path: &Path) -> Result<()> {
+        write_exodus_with_options(mesh, path, &self.write_options)
+    }
+}
+
 /// Write a mesh to an Exodus II file
 ///
 /// This is a simplified Exodus writer that writes hex meshes.
 /// It creates a basic Exodus file with nodes, elements, and element blocks.
 pub fn write_exodus(mesh: &Mesh, output_path: &Path) -> Result<()> {
-    log::info!(
+    write_exodus_with_options(mesh, output_path, &ExodusWriteOptions::default())
+}
+
+/// Write a mesh to an Exodus II file with the given options
+#[tracing::instrument(skip_all, fields(path = %output_path.display()))]
+pub fn write_exodus_with_options(
+    mesh: &Mesh,
+    output_path: &Path,
+    options: &ExodusWriteOptions,
+) -> Result<()> {
+    let max_name_len = options.max_name_length;
+    if !(1..=EXODUS_NAME_LEN_LIMIT).contains(&max_name_len) {
+        return Err(ContactDetectorError::ConfigError(format!(
+            "Exodus name length must be between 1 and {}, got {}",
+            EXODUS_NAME_LEN_LIMIT, max_name_len
+        )));
+    }
+
+    tracing::info!(
         "Writing mesh with {} elements to {:?}",
         mesh.num_elements(),
         output_path
@@ -483,6 +1131,14 @@ pub fn write_exodus(mesh: &Mesh, output_path: &Path) -> Result<()> {
             ContactDetectorError::ExodusReadError(format!("Failed to add file_size attribute: {}", e))
         })?;
 
+    file.add_attribute("maximum_name_length", max_name_len as i32)
+        .map_err(|e| {
+            ContactDetectorError::ExodusReadError(format!(
+                "Failed to add maximum_name_length attribute: {}",
+                e
+            ))
+        })?;
+
     // Add dimensions
     file.add_dimension("num_dim", 3)
         .map_err(|e| {
@@ -515,16 +1171,19 @@ pub fn write_exodus(mesh: &Mesh, output_path: &Path) -> Result<()> {
             ))
         })?;
 
-    file.add_dimension("num_qa_rec", 0)
+    file.add_dimension("len_name", max_name_len + 1)
         .map_err(|e| {
-            ContactDetectorError::ExodusReadError(format!("Failed to add num_qa_rec dimension: {}", e))
+            ContactDetectorError::ExodusReadError(format!("Failed to add len_name dimension: {}", e))
         })?;
 
-    file.add_dimension("num_info", 0)
+    file.add_dimension("len_line", 81)
         .map_err(|e| {
-            ContactDetectorError::ExodusReadError(format!("Failed to add num_info dimension: {}", e))
+            ContactDetectorError::ExodusReadError(format!("Failed to add len_line dimension: {}", e))
         })?;
 
+    // QA and info records are omitted entirely: NetCDF turns zero-length
+    // dimensions into unlimited ones, which strict readers reject.
+    // time_step is the one dimension that is meant to be unlimited.
     file.add_dimension("time_step", 0)
         .map_err(|e| {
             ContactDetectorError::ExodusReadError(format!(
@@ -533,6 +1192,22 @@ pub fn write_exodus(mesh: &Mesh, output_path: &Path) -> Result<()> {
             ))
         })?;
 
+    file.add_variable::<f64>("time_whole", &["time_step"])
+        .map_err(|e| {
+            ContactDetectorError::ExodusReadError(format!("Failed to add time_whole variable: {}", e))
+        })?;
+
+    // Write coordinate names
+    let coor_names = encode_names(&["x", "y", "z"], max_name_len);
+    let mut var = file
+        .add_variable::<u8>("coor_names", &["num_dim", "len_name"])
+        .map_err(|e| {
+            ContactDetectorError::ExodusReadError(format!("Failed to add coor_names variable: {}", e))
+        })?;
+    var.put_values(&coor_names, ..).map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!("Failed to write coor_names data: {}", e))
+    })?;
+
     // Write coordinate arrays
     let coordx: Vec<f64> = mesh.nodes.iter().map(|p| p.x).collect();
     let coordy: Vec<f64> = mesh.nodes.iter().map(|p| p.y).collect();
@@ -630,19 +1305,12 @@ pub fn write_exodus(mesh: &Mesh, output_path: &Path) -> Result<()> {
     }
 
     // Write element block names
-    let max_name_len = 33;
     let num_blocks = mesh.num_blocks();
-    let mut eb_names = vec![0u8; num_blocks * max_name_len];
-
-    for (blk_idx, (block_name, _)) in sorted_blocks.iter().enumerate() {
-        let start = blk_idx * max_name_len;
-        let bytes = block_name.as_bytes();
-        let copy_len = bytes.len().min(max_name_len - 1); // Leave room for null terminator
-        eb_names[start..start + copy_len].copy_from_slice(&bytes[..copy_len]);
-    }
+    let block_names: Vec<&String> = sorted_blocks.iter().map(|(name, _)| *name).collect();
+    let eb_names = encode_names(&fit_names(&block_names, max_name_len), max_name_len);
 
     let mut var = file
-        .add_variable::<u8>("eb_names", &["num_el_blk", "len_string"])
+        .add_variable::<u8>("eb_names", &["num_el_blk", "len_name"])
         .map_err(|e| {
             ContactDetectorError::ExodusReadError(format!("Failed to add eb_names variable: {}", e))
         })?;
@@ -650,30 +1318,110 @@ pub fn write_exodus(mesh: &Mesh, output_path: &Path) -> Result<()> {
         ContactDetectorError::ExodusReadError(format!("Failed to write eb_names data: {}", e))
     })?;
 
+    write_ids_and_status(&mut file, "eb", "num_el_blk", num_blocks)?;
+
+    // Blocks are written sorted by name, so an element's position in the file
+    // can differ from its index in the mesh
+    let file_order: Vec<usize> = sorted_blocks
+        .iter()
+        .flat_map(|(_, elem_indices)| elem_indices.iter().copied())
+        .collect();
+    let mut elem_file_pos = vec![None; mesh.num_elements()];
+    for (pos, &elem_idx) in file_order.iter().enumerate() {
+        elem_file_pos[elem_idx] = Some(pos);
+    }
+
+    // Write global ID maps so results can be correlated with the source model
+    let node_num_map: Vec<i32> = (0..mesh.num_nodes()).map(|i| mesh.global_node_id(i)).collect();
+    let mut var = file
+        .add_variable::<i32>("node_num_map", &["num_nodes"])
+        .map_err(|e| {
+            ContactDetectorError::ExodusReadError(format!("Failed to add node_num_map variable: {}", e))
+        })?;
+    var.put_values(&node_num_map, ..).map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!("Failed to write node_num_map data: {}", e))
+    })?;
+
+    let elem_num_map: Vec<i32> = file_order.iter().map(|&i| mesh.global_elem_id(i)).collect();
+    let mut var = file
+        .add_variable::<i32>("elem_num_map", &["num_elem"])
+        .map_err(|e| {
+            ContactDetectorError::ExodusReadError(format!("Failed to add elem_num_map variable: {}", e))
+        })?;
+    var.put_values(&elem_num_map, ..).map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!("Failed to write elem_num_map data: {}", e))
+    })?;
+
     // Write side sets if any
     if !mesh.side_sets.is_empty() {
-        write_side_sets(&mut file, mesh)?;
+        write_side_sets(&mut file, mesh, &elem_file_pos, max_name_len)?;
     }
 
     // Write node sets if any
     if !mesh.node_sets.is_empty() {
-        write_node_sets(&mut file, mesh)?;
+        write_node_sets(&mut file, mesh, max_name_len)?;
+    }
+
+    // Write nodal variables if any
+    if !mesh.node_fields.is_empty() {
+        write_nodal_variables(&mut file, mesh, max_name_len)?;
     }
 
-    log::info!("Successfully wrote Exodus file to {:?}", output_path);
+    tracing::info!("Successfully wrote Exodus file to {:?}", output_path);
+
+    Ok(())
+}
+
+/// Write the `{prefix}_prop1` ID array and `{prefix}_status` array of a set of
+/// blocks/sets. IDs are the 1-based positions used in the per-entity variable
+/// names (`connect1`, `elem_ss1`, ...); all entities are marked active.
+fn write_ids_and_status(
+    file: &mut netcdf::FileMut,
+    prefix: &str,
+    dim_name: &str,
+    count: usize,
+) -> Result<()> {
+    let ids: Vec<i32> = (1..=count as i32).collect();
+
+    let prop_name = format!("{}_prop1", prefix);
+    let mut var = file.add_variable::<i32>(&prop_name, &[dim_name]).map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!("Failed to add {} variable: {}", prop_name, e))
+    })?;
+    var.put_attribute("name", "ID").map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!(
+            "Failed to add name attribute to {}: {}",
+            prop_name, e
+        ))
+    })?;
+    var.put_values(&ids, ..).map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!("Failed to write {} data: {}", prop_name, e))
+    })?;
+
+    let status_name = format!("{}_status", prefix);
+    let mut var = file.add_variable::<i32>(&status_name, &[dim_name]).map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!("Failed to add {} variable: {}", status_name, e))
+    })?;
+    var.put_values(&vec![1i32; count], ..).map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!("Failed to write {} data: {}", status_name, e))
+    })?;
 
     Ok(())
 }
 
 /// Write side sets to an Exodus file
-fn write_side_sets(file: &mut netcdf::FileMut, mesh: &Mesh) -> Result<()> {
+fn write_side_sets(
+    file: &mut netcdf::FileMut,
+    mesh: &Mesh,
+    elem_file_pos: &[Option<usize>],
+    max_name_len: usize,
+) -> Result<()> {
     let num_side_sets = mesh.side_sets.len();
 
     if num_side_sets == 0 {
         return Ok(());
     }
 
-    log::debug!("Writing {} side sets", num_side_sets);
+    tracing::debug!("Writing {} side sets", num_side_sets);
 
     // Add num_side_sets dimension
     file.add_dimension("num_side_sets", num_side_sets)
@@ -693,7 +1441,7 @@ fn write_side_sets(file: &mut netcdf::FileMut, mesh: &Mesh) -> Result<()> {
         let ss_id = ss_idx + 1;
         let num_sides_in_set = side_list.len();
 
-        log::debug!(
+        tracing::debug!(
             "Writing side set {}: '{}' with {} sides",
             ss_id,
             ss_name,
@@ -710,8 +1458,23 @@ fn write_side_sets(file: &mut netcdf::FileMut, mesh: &Mesh) -> Result<()> {
                 ))
             })?;
 
-        // Convert side list to 1-based indexing
-        let elem_ids: Vec<i32> = side_list.iter().map(|(e, _)| (*e + 1) as i32).collect();
+        // Convert side list to 1-based positions in the written element order
+        let elem_ids: Vec<i32> = side_list
+            .iter()
+            .map(|(e, _)| {
+                elem_file_pos
+                    .get(*e)
+                    .copied()
+                    .flatten()
+                    .map(|pos| (pos + 1) as i32)
+                    .ok_or_else(|| {
+                        ContactDetectorError::InvalidMeshTopology(format!(
+                            "Side set '{}' references element {} which is not in any block",
+                            ss_name, e
+                        ))
+                    })
+            })
+            .collect::<Result<_>>()?;
         let side_ids: Vec<i32> = side_list.iter().map(|(_, s)| *s as i32).collect();
 
         // Create element list variable
@@ -747,21 +1510,50 @@ fn write_side_sets(file: &mut netcdf::FileMut, mesh: &Mesh) -> Result<()> {
                 side_var_name, e
             ))
         })?;
-    }
 
-    // Write side set names
-    let max_name_len = 33;
-    let mut ss_names = vec![0u8; num_side_sets * max_name_len];
+        // Write distribution factors (4 per quad side) if present
+        if let Some(factors) = mesh.side_set_dist_factors.get(*ss_name) {
+            if factors.len() == num_sides_in_set * 4 {
+                let df_dim_name = format!("num_df_ss{}", ss_id);
+                file.add_dimension(&df_dim_name, factors.len()).map_err(|e| {
+                    ContactDetectorError::ExodusReadError(format!(
+                        "Failed to add {} dimension: {}",
+                        df_dim_name, e
+                    ))
+                })?;
 
-    for (ss_idx, (ss_name, _)) in sorted_sidesets.iter().enumerate() {
-        let start = ss_idx * max_name_len;
-        let bytes = ss_name.as_bytes();
-        let copy_len = bytes.len().min(max_name_len - 1);
-        ss_names[start..start + copy_len].copy_from_slice(&bytes[..copy_len]);
+                let df_var_name = format!("dist_fact_ss{}", ss_id);
+                let mut df_var = file
+                    .add_variable::<f64>(&df_var_name, &[&df_dim_name])
+                    .map_err(|e| {
+                        ContactDetectorError::ExodusReadError(format!(
+                            "Failed to add {} variable: {}",
+                            df_var_name, e
+                        ))
+                    })?;
+                df_var.put_values(factors, ..).map_err(|e| {
+                    ContactDetectorError::ExodusReadError(format!(
+                        "Failed to write {} data: {}",
+                        df_var_name, e
+                    ))
+                })?;
+            } else {
+                tracing::warn!(
+                    "Not writing distribution factors of side set '{}': {} values for {} sides",
+                    ss_name,
+                    factors.len(),
+                    num_sides_in_set
+                );
+            }
+        }
     }
 
+    // Write side set names
+    let sorted_names: Vec<&String> = sorted_sidesets.iter().map(|(name, _)| *name).collect();
+    let ss_names = encode_names(&fit_names(&sorted_names, max_name_len), max_name_len);
+
     let mut var = file
-        .add_variable::<u8>("ss_names", &["num_side_sets", "len_string"])
+        .add_variable::<u8>("ss_names", &["num_side_sets", "len_name"])
         .map_err(|e| {
             ContactDetectorError::ExodusReadError(format!("Failed to add ss_names variable: {}", e))
         })?;
@@ -769,18 +1561,216 @@ fn write_side_sets(file: &mut netcdf::FileMut, mesh: &Mesh) -> Result<()> {
         ContactDetectorError::ExodusReadError(format!("Failed to write ss_names data: {}", e))
     })?;
 
+    write_ids_and_status(file, "ss", "num_side_sets", num_side_sets)?;
+
+    write_side_set_variables(file, mesh, &sorted_names, max_name_len)?;
+
+    Ok(())
+}
+
+/// Write [`Mesh::side_set_fields`] as side set variables of a single time step
+///
+/// `sorted_names` are the side set names in the order they were written.
+/// Variables whose length does not match their side set are skipped.
+fn write_side_set_variables(
+    file: &mut netcdf::FileMut,
+    mesh: &Mesh,
+    sorted_names: &[&String],
+    max_name_len: usize,
+) -> Result<()> {
+    let set_fields: Vec<Vec<(&String, &Vec<f64>)>> = sorted_names
+        .iter()
+        .map(|ss_name| {
+            let num_sides = mesh.side_sets.get(*ss_name).map_or(0, Vec::len);
+            mesh.side_set_fields
+                .get(*ss_name)
+                .into_iter()
+                .flatten()
+                .filter(|(var_name, values)| {
+                    if values.len() == num_sides {
+                        true
+                    } else {
+                        tracing::warn!(
+                            "Not writing variable '{}' of side set '{}': {} values for {} sides",
+                            var_name,
+                            ss_name,
+                            values.len(),
+                            num_sides
+                        );
+                        false
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut variable_names: Vec<&String> = set_fields
+        .iter()
+        .flatten()
+        .map(|(var_name, _)| *var_name)
+        .collect();
+    variable_names.sort();
+    variable_names.dedup();
+    if variable_names.is_empty() {
+        return Ok(());
+    }
+
+    tracing::debug!("Writing {} side set variables", variable_names.len());
+
+    file.add_dimension("num_sset_var", variable_names.len())
+        .map_err(|e| {
+            ContactDetectorError::ExodusReadError(format!(
+                "Failed to add num_sset_var dimension: {}",
+                e
+            ))
+        })?;
+
+    let name_chars = encode_names(&fit_names(&variable_names, max_name_len), max_name_len);
+    let mut var = file
+        .add_variable::<u8>("name_sset_var", &["num_sset_var", "len_name"])
+        .map_err(|e| {
+            ContactDetectorError::ExodusReadError(format!("Failed to add name_sset_var variable: {}", e))
+        })?;
+    var.put_values(&name_chars, ..).map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!("Failed to write name_sset_var data: {}", e))
+    })?;
+
+    // The results are a single time step at time 0
+    let mut var = file.variable_mut("time_whole").ok_or_else(|| {
+        ContactDetectorError::ExodusReadError("time_whole variable missing".to_string())
+    })?;
+    var.put_values(&[0.0f64], 0..1).map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!("Failed to write time_whole data: {}", e))
+    })?;
+
+    // Truth table: which side sets carry which variable
+    let mut truth_table = vec![0i32; sorted_names.len() * variable_names.len()];
+    for (ss_idx, fields) in set_fields.iter().enumerate() {
+        let ss_id = ss_idx + 1;
+        for (var_name, values) in fields {
+            let var_idx = variable_names
+                .binary_search(var_name)
+                .expect("variable name collected above");
+            truth_table[ss_idx * variable_names.len() + var_idx] = 1;
+
+            let vals_name = format!("vals_sset_var{}ss{}", var_idx + 1, ss_id);
+            let side_dim = format!("num_side_ss{}", ss_id);
+            let mut var = file
+                .add_variable::<f64>(&vals_name, &["time_step", &side_dim])
+                .map_err(|e| {
+                    ContactDetectorError::ExodusReadError(format!(
+                        "Failed to add {} variable: {}",
+                        vals_name, e
+                    ))
+                })?;
+            var.put_values(values, (0, ..)).map_err(|e| {
+                ContactDetectorError::ExodusReadError(format!(
+                    "Failed to write {} data: {}",
+                    vals_name, e
+                ))
+            })?;
+        }
+    }
+
+    let mut var = file
+        .add_variable::<i32>("sset_var_tab", &["num_side_sets", "num_sset_var"])
+        .map_err(|e| {
+            ContactDetectorError::ExodusReadError(format!("Failed to add sset_var_tab variable: {}", e))
+        })?;
+    var.put_values(&truth_table, ..).map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!("Failed to write sset_var_tab data: {}", e))
+    })?;
+
+    Ok(())
+}
+
+/// Write the nodal fields of a mesh as nodal variables (`vals_nod_var*`) of a
+/// single time step at time 0
+fn write_nodal_variables(
+    file: &mut netcdf::FileMut,
+    mesh: &Mesh,
+    max_name_len: usize,
+) -> Result<()> {
+    let mut variable_names: Vec<&String> = mesh
+        .node_fields
+        .iter()
+        .filter(|(var_name, values)| {
+            if values.len() == mesh.num_nodes() {
+                true
+            } else {
+                tracing::warn!(
+                    "Not writing nodal variable '{}': {} values for {} nodes",
+                    var_name,
+                    values.len(),
+                    mesh.num_nodes()
+                );
+                false
+            }
+        })
+        .map(|(var_name, _)| var_name)
+        .collect();
+    variable_names.sort();
+    if variable_names.is_empty() {
+        return Ok(());
+    }
+
+    tracing::debug!("Writing {} nodal variables", variable_names.len());
+
+    file.add_dimension("num_nod_var", variable_names.len())
+        .map_err(|e| {
+            ContactDetectorError::ExodusReadError(format!(
+                "Failed to add num_nod_var dimension: {}",
+                e
+            ))
+        })?;
+
+    let name_chars = encode_names(&fit_names(&variable_names, max_name_len), max_name_len);
+    let mut var = file
+        .add_variable::<u8>("name_nod_var", &["num_nod_var", "len_name"])
+        .map_err(|e| {
+            ContactDetectorError::ExodusReadError(format!("Failed to add name_nod_var variable: {}", e))
+        })?;
+    var.put_values(&name_chars, ..).map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!("Failed to write name_nod_var data: {}", e))
+    })?;
+
+    let mut var = file.variable_mut("time_whole").ok_or_else(|| {
+        ContactDetectorError::ExodusReadError("time_whole variable missing".to_string())
+    })?;
+    var.put_values(&[0.0f64], 0..1).map_err(|e| {
+        ContactDetectorError::ExodusReadError(format!("Failed to write time_whole data: {}", e))
+    })?;
+
+    for (var_idx, var_name) in variable_names.iter().enumerate() {
+        let vals_name = format!("vals_nod_var{}", var_idx + 1);
+        let mut var = file
+            .add_variable::<f64>(&vals_name, &["time_step", "num_nodes"])
+            .map_err(|e| {
+                ContactDetectorError::ExodusReadError(format!(
+                    "Failed to add {} variable: {}",
+                    vals_name, e
+                ))
+            })?;
+        var.put_values(&mesh.node_fields[*var_name], (0, ..)).map_err(|e| {
+            ContactDetectorError::ExodusReadError(format!(
+                "Failed to write {} data: {}",
+                vals_name, e
+            ))
+        })?;
+    }
+
     Ok(())
 }
 
 /// Write node sets to an Exodus file
-fn write_node_sets(file: &mut netcdf::FileMut, mesh: &Mesh) -> Result<()> {
+fn write_node_sets(file: &mut netcdf::FileMut, mesh: &Mesh, max_name_len: usize) -> Result<()> {
     let num_node_sets = mesh.node_sets.len();
 
     if num_node_sets == 0 {
         return Ok(());
     }
 
-    log::debug!("Writing {} node sets", num_node_sets);
+    tracing::debug!("Writing {} node sets", num_node_sets);
 
     // Add num_node_sets dimension
     file.add_dimension("num_node_sets", num_node_sets)
@@ -800,7 +1790,7 @@ fn write_node_sets(file: &mut netcdf::FileMut, mesh: &Mesh) -> Result<()> {
         let ns_id = ns_idx + 1;
         let num_nodes_in_set = node_list.len();
 
-        log::debug!(
+        tracing::debug!(
             "Writing node set {}: '{}' with {} nodes",
             ns_id,
             ns_name,
@@ -833,18 +1823,11 @@ fn write_node_sets(file: &mut netcdf::FileMut, mesh: &Mesh) -> Result<()> {
     }
 
     // Write node set names
-    let max_name_len = 33;
-    let mut ns_names = vec![0u8; num_node_sets * max_name_len];
-
-    for (ns_idx, (ns_name, _)) in sorted_nodesets.iter().enumerate() {
-        let start = ns_idx * max_name_len;
-        let bytes = ns_name.as_bytes();
-        let copy_len = bytes.len().min(max_name_len - 1);
-        ns_names[start..start + copy_len].copy_from_slice(&bytes[..copy_len]);
-    }
+    let node_set_names: Vec<&String> = sorted_nodesets.iter().map(|(name, _)| *name).collect();
+    let ns_names = encode_names(&fit_names(&node_set_names, max_name_len), max_name_len);
 
     let mut var = file
-        .add_variable::<u8>("ns_names", &["num_node_sets", "len_string"])
+        .add_variable::<u8>("ns_names", &["num_node_sets", "len_name"])
         .map_err(|e| {
             ContactDetectorError::ExodusReadError(format!("Failed to add ns_names variable: {}", e))
         })?;
@@ -852,6 +1835,8 @@ fn write_node_sets(file: &mut netcdf::FileMut, mesh: &Mesh) -> Result<()> {
         ContactDetectorError::ExodusReadError(format!("Failed to write ns_names data: {}", e))
     })?;
 
+    write_ids_and_status(file, "ns", "num_node_sets", num_node_sets)?;
+
     Ok(())
 }
 
@@ -859,78 +1844,249 @@ fn write_node_sets(file: &mut netcdf::FileMut, mesh: &Mesh) -> Result<()> {
 ///
 /// This function maps surface faces from contact detection back to the original
 /// hexahedral mesh elements and their face IDs for Exodus II sideset export.
+/// Faces that cannot be found in the mesh are recorded in `warnings`.
+///
+/// This builds an [`ElementSideIndex`] over the whole mesh; when converting
+/// several surfaces, build the index once and use [`surface_to_sideset_indexed`].
+#[tracing::instrument(level = "debug", skip_all, fields(surface = %surface.part_name))]
 pub fn surface_to_sideset(
     surface: &crate::mesh::SurfaceMesh,
     mesh: &Mesh,
+    warnings: &mut Warnings,
 ) -> Result<Vec<(usize, u8)>> {
-    use std::collections::HashMap;
+    Ok(surface_to_sideset_indexed(
+        surface,
+        &ElementSideIndex::new(mesh),
+        warnings,
+    ))
+}
+
+/// Convert contact surface faces to sideset format using a prebuilt index
+#[tracing::instrument(level = "debug", skip_all, fields(surface = %surface.part_name))]
+pub fn surface_to_sideset_indexed(
+    surface: &crate::mesh::SurfaceMesh,
+    index: &ElementSideIndex,
+    warnings: &mut Warnings,
+) -> Vec<(usize, u8)> {
+    let sideset: Vec<(usize, u8)> = map_surface_faces(surface, index, warnings)
+        .into_iter()
+        .flatten()
+        .collect();
+
+    tracing::debug!("Mapped {} surface faces to sideset", sideset.len());
+
+    sideset
+}
 
-    log::debug!(
+/// Map each surface face to `(element_idx, Exodus side ID)`, or `None` if not found
+fn map_surface_faces(
+    surface: &crate::mesh::SurfaceMesh,
+    index: &ElementSideIndex,
+    warnings: &mut Warnings,
+) -> Vec<Option<(usize, u8)>> {
+    tracing::debug!(
         "Converting surface '{}' with {} faces to sideset format",
         surface.part_name,
         surface.faces.len()
     );
 
-    // Build a map from canonical face to (element_idx, face_id)
-    let mut face_to_elem_and_id: HashMap<crate::mesh::QuadFace, (usize, u8)> = HashMap::new();
-
-    for (elem_idx, element) in mesh.elements.iter().enumerate() {
-        let hex_faces = element.faces();
-        for (face_id, face) in hex_faces.iter().enumerate() {
-            let canonical = face.canonical();
-            face_to_elem_and_id.insert(canonical, (elem_idx, face_id as u8));
-        }
-    }
+    surface
+        .faces
+        .iter()
+        .map(|face| {
+            let entry = index.get(face);
+            if entry.is_none() {
+                warnings.push(
+                    WarningKind::UnmappedFace,
+                    format!(
+                        "Surface '{}' face with nodes {:?} not found in mesh",
+                        surface.part_name, face.node_ids
+                    ),
+                );
+            }
+            entry
+        })
+        .collect()
+}
 
-    // Map each surface face to (element_idx, face_id)
-    let mut sideset = Vec::new();
+/// Add contact surface sidesets to a mesh
+///
+/// This function takes a mesh and adds sidesets for detected contact surfaces,
+/// using the names given by the caller (see [`crate::io::SidesetNamer`]).
+/// Unmapped faces and empty sidesets are returned as warnings.
+#[tracing::instrument(skip_all, fields(count = contact_surfaces.len()))]
+pub fn add_contact_sidesets_to_mesh(
+    mesh: &mut Mesh,
+    contact_surfaces: &[(String, &crate::mesh::SurfaceMesh)],
+    original_mesh: &Mesh,
+) -> Result<Warnings> {
+    let with_data: Vec<_> = contact_surfaces
+        .iter()
+        .map(|(name, surface)| (name.clone(), *surface, SidesetFaceData::default()))
+        .collect();
 
-    for face in &surface.faces {
-        let canonical = face.canonical();
+    add_contact_sidesets_with_data(mesh, &with_data, original_mesh)
+}
 
-        if let Some(&(elem_idx, face_id)) = face_to_elem_and_id.get(&canonical) {
-            sideset.push((elem_idx, face_id));
-        } else {
-            log::warn!(
-                "Surface face with nodes {:?} not found in mesh",
-                face.node_ids
-            );
-        }
-    }
+/// Add contact surface sidesets with distribution factors to a mesh
+///
+/// Like [`add_contact_sidesets_to_mesh`], but each surface comes with one
+/// value per face (see [`crate::contact::per_face_values`]) that is written
+/// as the distribution factor of all four nodes of the corresponding side.
+#[tracing::instrument(skip_all, fields(count = contact_surfaces.len()))]
+pub fn add_contact_sidesets_with_dist_factors(
+    mesh: &mut Mesh,
+    contact_surfaces: &[(String, &crate::mesh::SurfaceMesh, Vec<f64>)],
+    original_mesh: &Mesh,
+) -> Result<Warnings> {
+    let with_data: Vec<_> = contact_surfaces
+        .iter()
+        .map(|(name, surface, values)| {
+            let data = SidesetFaceData {
+                dist_factors: Some(values.clone()),
+                ..Default::default()
+            };
+            (name.clone(), *surface, data)
+        })
+        .collect();
 
-    log::debug!("Mapped {} surface faces to sideset", sideset.len());
+    add_contact_sidesets_with_data(mesh, &with_data, original_mesh)
+}
 
-    Ok(sideset)
+/// Per-face values exported with a contact sideset
+///
+/// Every vector holds one value per face of the sideset's surface.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SidesetFaceData {
+    /// Distribution factor of each face, written for all four of its nodes
+    pub dist_factors: Option<Vec<f64>>,
+
+    /// Side set variables by name (see [`Mesh::side_set_fields`])
+    pub variables: Vec<(String, Vec<f64>)>,
 }
 
-/// Add contact surface sidesets to a mesh
+/// Add contact surface sidesets with distribution factors and/or side set
+/// variables to a mesh
 ///
-/// This function takes a mesh and adds sidesets for detected contact surfaces.
-/// The sidesets are named using the format "auto_contact_{surface_name}".
-pub fn add_contact_sidesets_to_mesh(
+/// Generalizes [`add_contact_sidesets_with_dist_factors`]; the values of
+/// faces that cannot be mapped to an element side are dropped with the face.
+#[tracing::instrument(skip_all, fields(count = contact_surfaces.len()))]
+pub fn add_contact_sidesets_with_data(
     mesh: &mut Mesh,
-    contact_surfaces: &[(String, &crate::mesh::SurfaceMesh)],
+    contact_surfaces: &[(String, &crate::mesh::SurfaceMesh, SidesetFaceData)],
     original_mesh: &Mesh,
-) -> Result<()> {
-    for (sideset_name, surface) in contact_surfaces {
-        log::info!("Adding sideset '{}' for surface '{}'", sideset_name, surface.part_name);
+) -> Result<Warnings> {
+    for (name, surface, data) in contact_surfaces {
+        let value_lists = data
+            .dist_factors
+            .iter()
+            .chain(data.variables.iter().map(|(_, values)| values));
+        for values in value_lists {
+            if values.len() != surface.faces.len() {
+                return Err(ContactDetectorError::ConfigError(format!(
+                    "Sideset '{}': {} face values for {} faces",
+                    name,
+                    values.len(),
+                    surface.faces.len()
+                )));
+            }
+        }
+    }
+
+    let mut warnings = Warnings::new();
+    let index = ElementSideIndex::new(original_mesh);
+
+    for (sideset_name, surface, data) in contact_surfaces {
+        tracing::info!("Adding sideset '{}' for surface '{}'", sideset_name, surface.part_name);
 
-        let sideset = surface_to_sideset(surface, original_mesh)?;
+        let mapped = map_surface_faces(surface, &index, &mut warnings);
+        let kept: Vec<usize> = (0..mapped.len()).filter(|&i| mapped[i].is_some()).collect();
+        let sideset: Vec<(usize, u8)> = mapped.into_iter().flatten().collect();
 
         if !sideset.is_empty() {
             mesh.side_sets.insert(sideset_name.clone(), sideset);
+            if let Some(values) = &data.dist_factors {
+                let factors = kept.iter().flat_map(|&i| [values[i]; 4]).collect();
+                mesh.side_set_dist_factors
+                    .insert(sideset_name.clone(), factors);
+            }
+            if !data.variables.is_empty() {
+                let fields = data
+                    .variables
+                    .iter()
+                    .map(|(var_name, values)| {
+                        (var_name.clone(), kept.iter().map(|&i| values[i]).collect())
+                    })
+                    .collect();
+                mesh.side_set_fields.insert(sideset_name.clone(), fields);
+            }
         } else {
-            log::warn!("Skipping empty sideset '{}'", sideset_name);
+            warnings.push(
+                WarningKind::EmptySideSet,
+                format!("Skipping empty sideset '{}'", sideset_name),
+            );
         }
     }
 
-    Ok(())
+    Ok(warnings)
+}
+
+/// Add sidesets for both sides of every interface of an auto-contact run to
+/// a mesh, with the fields of `attributes` as side set variables
+///
+/// Sidesets are named after the interface sides (see
+/// [`SidesetNamer::name_interfaces`](crate::io::SidesetNamer::name_interfaces))
+/// and hold the paired faces only if `paired_only`. Distribution factors are
+/// taken from `dist_factors`, if given.
+pub fn add_auto_contact_sidesets(
+    mesh: &mut Mesh,
+    report: &AutoContactReport,
+    attributes: &AttributeSet,
+    dist_factors: Option<FaceValueSource>,
+    paired_only: bool,
+    original_mesh: &Mesh,
+) -> Result<Warnings> {
+    let surfaces = &report.surfaces;
+    let sides: Vec<(&ContactInterface, bool)> = report
+        .pairs
+        .iter()
+        .flat_map(|interface| [(interface, true), (interface, false)])
+        .collect();
+    let face_selections: Vec<Vec<usize>> = sides
+        .iter()
+        .map(|(interface, is_a)| interface.sideset_faces(surfaces, *is_a, paired_only))
+        .collect();
+    let export_surfaces: Vec<crate::mesh::SurfaceMesh> = sides
+        .iter()
+        .zip(&face_selections)
+        .map(|((interface, is_a), faces)| interface.surface(surfaces, *is_a).subset(faces))
+        .collect();
+
+    let with_data = sides
+        .iter()
+        .zip(&face_selections)
+        .zip(&export_surfaces)
+        .map(|(((interface, is_a), faces), export_surface)| {
+            let context = FaceContext::of_interface(interface, surfaces, *is_a);
+            let data = SidesetFaceData {
+                dist_factors: dist_factors.map(|source| {
+                    let values = context.values(source);
+                    faces.iter().map(|&f| values[f]).collect()
+                }),
+                variables: sideset_variables(&attributes.evaluate(&context)?, faces),
+            };
+            let name = interface.side(*is_a).sideset.clone().unwrap_or_default();
+            Ok((name, export_surface, data))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    add_contact_sidesets_with_data(mesh, &with_data, original_mesh)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::mesh::{HexElement, Point, QuadFace, SurfaceMesh, Vec3};
+    use std::collections::HashMap;
 
     #[test]
     #[ignore] // Only run when test file is available
@@ -971,13 +2127,14 @@ mod tests {
         surface.nodes = mesh.nodes.clone();
 
         // Convert to sideset
-        let result = surface_to_sideset(&surface, &mesh);
+        let mut warnings = Warnings::new();
+        let result = surface_to_sideset(&surface, &mesh, &mut warnings);
         assert!(result.is_ok());
 
         let sideset = result.unwrap();
         assert_eq!(sideset.len(), 1);
         assert_eq!(sideset[0].0, 0); // element 0
-        assert_eq!(sideset[0].1, 1); // face 1 (top face)
+        assert_eq!(sideset[0].1, 6); // Exodus side 6 (top face)
     }
 
     #[test]
@@ -1012,6 +2169,7 @@ mod tests {
         let contact_surfaces = vec![("auto_contact_Block1_patch_1".to_string(), &surface)];
         let result = add_contact_sidesets_to_mesh(&mut mesh, &contact_surfaces, &original_mesh);
         assert!(result.is_ok());
+        assert!(result.unwrap().is_empty());
 
         // Verify sideset was added
         assert_eq!(mesh.side_sets.len(), 1);
@@ -1022,6 +2180,40 @@ mod tests {
         assert_eq!(sideset[0].0, 0); // element 0
     }
 
+    #[test]
+    fn test_add_contact_sidesets_with_dist_factors() {
+        let mut mesh = Mesh::new();
+        mesh.nodes = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(0.0, 0.0, 1.0),
+            Point::new(1.0, 0.0, 1.0),
+            Point::new(1.0, 1.0, 1.0),
+            Point::new(0.0, 1.0, 1.0),
+        ];
+        mesh.elements = vec![HexElement::new([0, 1, 2, 3, 4, 5, 6, 7])];
+        mesh.element_blocks.insert("Block1".to_string(), vec![0]);
+
+        let mut surface = SurfaceMesh::new("Block1:patch_1".to_string());
+        surface.faces = vec![QuadFace::new([4, 5, 6, 7]), QuadFace::new([10, 11, 12, 13])];
+        surface.face_normals = vec![Vec3::new(0.0, 0.0, 1.0); 2];
+        surface.face_areas = vec![1.0; 2];
+        surface.nodes = mesh.nodes.clone();
+
+        let original_mesh = mesh.clone();
+        let contact_surfaces = vec![("contact".to_string(), &surface, vec![0.25, 0.75])];
+        let warnings =
+            add_contact_sidesets_with_dist_factors(&mut mesh, &contact_surfaces, &original_mesh)
+                .unwrap();
+
+        // The second face is not in the mesh; its value must not shift the first
+        assert_eq!(warnings.of_kind(WarningKind::UnmappedFace).count(), 1);
+        assert_eq!(mesh.side_sets["contact"], vec![(0, 6)]);
+        assert_eq!(mesh.side_set_dist_factors["contact"], vec![0.25; 4]);
+    }
+
     #[test]
     fn test_write_exodus_with_sidesets() {
         // Create a mesh with sidesets
@@ -1054,10 +2246,465 @@ mod tests {
         // Verify file was created
         assert!(output_path.exists());
 
+        // Verify the canonical variables strict readers require
+        let file = netcdf::open(&output_path).unwrap();
+        for name in [
+            "coor_names",
+            "eb_prop1",
+            "eb_status",
+            "ss_prop1",
+            "ss_status",
+            "node_num_map",
+            "elem_num_map",
+        ] {
+            assert!(file.variable(name).is_some(), "missing variable {}", name);
+        }
+        let ss_ids: Vec<i32> = file
+            .variable("ss_prop1")
+            .unwrap()
+            .get::<i32, _>(..)
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(ss_ids, vec![1]);
+        drop(file);
+
         // Clean up
         let _ = std::fs::remove_file(&output_path);
     }
 
+    #[test]
+    fn test_write_exodus_names_and_ids() {
+        let mut mesh = Mesh::new();
+        for z in [0.0, 1.0, 2.0] {
+            mesh.nodes.extend([
+                Point::new(0.0, 0.0, z),
+                Point::new(1.0, 0.0, z),
+                Point::new(1.0, 1.0, z),
+                Point::new(0.0, 1.0, z),
+            ]);
+        }
+        mesh.elements = vec![
+            HexElement::new([0, 1, 2, 3, 4, 5, 6, 7]),
+            HexElement::new([4, 5, 6, 7, 8, 9, 10, 11]),
+        ];
+        mesh.element_blocks.insert("Lower".to_string(), vec![0]);
+        mesh.element_blocks.insert("Upper".to_string(), vec![1]);
+        mesh.node_sets.insert("base".to_string(), vec![0, 1, 2, 3]);
+
+        let output_path = std::env::temp_dir().join("test_mesh_names_and_ids.exo");
+        write_exodus(&mesh, &output_path).unwrap();
+
+        let file = netcdf::open(&output_path).unwrap();
+        assert_eq!(file.dimension("len_name").unwrap().len(), 33);
+
+        // One NUL-padded name per axis
+        let coor_names: Vec<u8> = file
+            .variable("coor_names")
+            .unwrap()
+            .get::<u8, _>(..)
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(coor_names.len(), 3 * 33);
+        for (i, axis) in [b'x', b'y', b'z'].into_iter().enumerate() {
+            assert_eq!(coor_names[i * 33], axis);
+            assert!(coor_names[i * 33 + 1..(i + 1) * 33].iter().all(|&c| c == 0));
+        }
+
+        // Blocks and sets are numbered from 1 and all active
+        let values = |name: &str| -> Vec<i32> {
+            file.variable(name)
+                .unwrap()
+                .get::<i32, _>(..)
+                .unwrap()
+                .into_iter()
+                .collect()
+        };
+        assert_eq!(values("eb_prop1"), vec![1, 2]);
+        assert_eq!(values("eb_status"), vec![1, 1]);
+        assert_eq!(values("ns_prop1"), vec![1]);
+        assert_eq!(values("ns_status"), vec![1]);
+        assert_eq!(
+            string_attribute(&file.variable("eb_prop1").unwrap(), "name").as_deref(),
+            Some("ID")
+        );
+        drop(file);
+
+        let _ = std::fs::remove_file(&output_path);
+    }
+
+    #[test]
+    fn test_sideset_dist_factors_round_trip() {
+        let mut mesh = Mesh::new();
+        mesh.nodes = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(0.0, 0.0, 1.0),
+            Point::new(1.0, 0.0, 1.0),
+            Point::new(1.0, 1.0, 1.0),
+            Point::new(0.0, 1.0, 1.0),
+        ];
+        mesh.elements = vec![HexElement::new([0, 1, 2, 3, 4, 5, 6, 7])];
+        mesh.element_blocks.insert("Block1".to_string(), vec![0]);
+        mesh.side_sets.insert("weighted".to_string(), vec![(0, 6)]);
+        mesh.side_set_dist_factors
+            .insert("weighted".to_string(), vec![0.1, 0.2, 0.3, 0.4]);
+
+        let output_path = std::env::temp_dir().join("test_mesh_dist_factors.exo");
+        write_exodus(&mesh, &output_path).unwrap();
+
+        let (read_back, warnings) = ExodusReader::open(&output_path)
+            .unwrap()
+            .read_mesh_with_warnings()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(
+            read_back.side_set_dist_factors["weighted"],
+            vec![0.1, 0.2, 0.3, 0.4]
+        );
+
+        let _ = std::fs::remove_file(&output_path);
+    }
+
+    #[test]
+    fn test_sideset_variables_round_trip() {
+        let mut mesh = Mesh::new();
+        for z in [0.0, 1.0] {
+            mesh.nodes.extend([
+                Point::new(0.0, 0.0, z),
+                Point::new(1.0, 0.0, z),
+                Point::new(1.0, 1.0, z),
+                Point::new(0.0, 1.0, z),
+            ]);
+        }
+        mesh.elements = vec![HexElement::new([0, 1, 2, 3, 4, 5, 6, 7])];
+        mesh.element_blocks.insert("Block1".to_string(), vec![0]);
+        mesh.side_sets.insert("bottom".to_string(), vec![(0, 5)]);
+        mesh.side_sets.insert("top".to_string(), vec![(0, 6)]);
+        mesh.side_set_fields.insert(
+            "top".to_string(),
+            HashMap::from([
+                ("contact_gap".to_string(), vec![0.002]),
+                ("contact_angle".to_string(), vec![3.5]),
+            ]),
+        );
+
+        let output_path = std::env::temp_dir().join("test_mesh_sideset_variables.exo");
+        write_exodus(&mesh, &output_path).unwrap();
+
+        let (read_back, warnings) = ExodusReader::open(&output_path)
+            .unwrap()
+            .read_mesh_with_warnings()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(read_back.side_set_fields["top"]["contact_gap"], vec![0.002]);
+        assert_eq!(read_back.side_set_fields["top"]["contact_angle"], vec![3.5]);
+        // Not in the truth table for "bottom"
+        assert!(!read_back.side_set_fields.contains_key("bottom"));
+
+        let _ = std::fs::remove_file(&output_path);
+    }
+
+    #[test]
+    fn test_file_info() {
+        let mut mesh = Mesh::new();
+        for z in [0.0, 1.0] {
+            mesh.nodes.extend([
+                Point::new(0.0, 0.0, z),
+                Point::new(1.0, 0.0, z),
+                Point::new(1.0, 1.0, z),
+                Point::new(0.0, 1.0, z),
+            ]);
+        }
+        mesh.elements = vec![HexElement::new([0, 1, 2, 3, 4, 5, 6, 7])];
+        mesh.element_blocks.insert("Block1".to_string(), vec![0]);
+        mesh.side_sets.insert("top".to_string(), vec![(0, 6)]);
+        mesh.side_set_fields.insert(
+            "top".to_string(),
+            HashMap::from([("contact_gap".to_string(), vec![0.002])]),
+        );
+
+        let output_path = std::env::temp_dir().join("test_mesh_file_info.exo");
+        write_exodus(&mesh, &output_path).unwrap();
+
+        let info = ExodusReader::open(&output_path).unwrap().file_info().unwrap();
+        assert_eq!(info.title.as_deref(), Some("Mesh exported from contact-detector"));
+        assert_eq!((info.num_dim, info.num_nodes, info.num_elements), (3, 8, 1));
+        assert_eq!(info.blocks.len(), 1);
+        let block = &info.blocks[0];
+        assert_eq!(block.name, "Block1");
+        assert_eq!(block.element_type, "HEX8");
+        assert_eq!((block.num_elements, block.nodes_per_element), (1, 8));
+        assert!(block.is_supported());
+        assert!(block.attribute_names.is_empty());
+        assert_eq!(info.side_set_variables, vec!["contact_gap".to_string()]);
+        assert!(info.nodal_variables.is_empty());
+        assert_eq!(info.num_time_steps(), 1);
+
+        let _ = std::fs::remove_file(&output_path);
+    }
+
+    #[test]
+    fn test_read_2d_coord_layout() {
+        let path = std::env::temp_dir().join("test_mesh_coord_2d.exo");
+        {
+            let mut file = netcdf::create(&path).unwrap();
+            file.add_dimension("num_dim", 3).unwrap();
+            file.add_dimension("num_nodes", 8).unwrap();
+            file.add_dimension("num_elem", 1).unwrap();
+            file.add_dimension("num_el_blk", 1).unwrap();
+            file.add_dimension("num_el_in_blk1", 1).unwrap();
+            file.add_dimension("num_nod_per_el1", 8).unwrap();
+            file.add_dimension("len_name", 33).unwrap();
+
+            // Rows stored in z, x, y order, as named by coor_names
+            let mut coor_names = vec![0u8; 3 * 33];
+            for (i, axis) in ["z", "x", "y"].iter().enumerate() {
+                coor_names[i * 33] = axis.as_bytes()[0];
+            }
+            file.add_variable::<u8>("coor_names", &["num_dim", "len_name"])
+                .unwrap()
+                .put_values(&coor_names, ..)
+                .unwrap();
+
+            let corners: Vec<[f64; 3]> = (0..8)
+                .map(|i| [(i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64 * 2.0])
+                .collect();
+            let coord: Vec<f64> = [2, 0, 1]
+                .iter()
+                .flat_map(|&axis| corners.iter().map(move |c| c[axis]))
+                .collect();
+            file.add_variable::<f64>("coord", &["num_dim", "num_nodes"])
+                .unwrap()
+                .put_values(&coord, ..)
+                .unwrap();
+
+            let mut var = file
+                .add_variable::<i32>("connect1", &["num_el_in_blk1", "num_nod_per_el1"])
+                .unwrap();
+            var.put_attribute("elem_type", "HEX8").unwrap();
+            var.put_values(&[1, 2, 4, 3, 5, 6, 8, 7], ..).unwrap();
+        }
+
+        let mesh = ExodusReader::open(&path).unwrap().read_mesh().unwrap();
+        assert_eq!(mesh.num_nodes(), 8);
+        assert_eq!(mesh.num_elements(), 1);
+        assert_eq!(mesh.nodes[1], Point::new(1.0, 0.0, 0.0));
+        assert_eq!(mesh.nodes[6], Point::new(0.0, 1.0, 2.0));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_block_read_report() {
+        let path = std::env::temp_dir().join("test_mesh_block_report.exo");
+        {
+            let mut file = netcdf::create(&path).unwrap();
+            file.add_dimension("num_dim", 3).unwrap();
+            file.add_dimension("num_nodes", 8).unwrap();
+            file.add_dimension("num_elem", 3).unwrap();
+            file.add_dimension("num_el_blk", 2).unwrap();
+            file.add_dimension("num_el_in_blk1", 1).unwrap();
+            file.add_dimension("num_nod_per_el1", 8).unwrap();
+            file.add_dimension("num_el_in_blk2", 2).unwrap();
+            file.add_dimension("num_nod_per_el2", 4).unwrap();
+
+            let coord: Vec<f64> = (0..3)
+                .flat_map(|axis| (0..8).map(move |i| ((i >> axis) & 1) as f64))
+                .collect();
+            file.add_variable::<f64>("coord", &["num_dim", "num_nodes"])
+                .unwrap()
+                .put_values(&coord, ..)
+                .unwrap();
+
+            let mut var = file
+                .add_variable::<i32>("connect1", &["num_el_in_blk1", "num_nod_per_el1"])
+                .unwrap();
+            var.put_attribute("elem_type", "HEX8").unwrap();
+            var.put_values(&[1, 2, 4, 3, 5, 6, 8, 7], ..).unwrap();
+
+            let mut var = file
+                .add_variable::<i32>("connect2", &["num_el_in_blk2", "num_nod_per_el2"])
+                .unwrap();
+            var.put_attribute("elem_type", "TETRA4").unwrap();
+            var.put_values(&[1, 2, 3, 5, 2, 4, 3, 8], ..).unwrap();
+        }
+
+        let (mesh, warnings) = ExodusReader::open(&path)
+            .unwrap()
+            .read_mesh_with_warnings()
+            .unwrap();
+        assert_eq!(mesh.num_elements(), 1);
+        assert_eq!(warnings.of_kind(WarningKind::SkippedBlock).count(), 1);
+
+        // Both blocks are reported in file order, the tetrahedra as skipped
+        let blocks = &mesh.provenance.blocks;
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].name, "Block_1");
+        assert_eq!(blocks[0].element_type, "HEX8");
+        assert_eq!(blocks[0].num_elements, 1);
+        assert_eq!(blocks[0].status, BlockReadStatus::Read);
+        assert!(blocks[0].reason.is_none());
+
+        let skipped: Vec<_> = mesh.provenance.skipped_blocks().collect();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].name, "Block_2");
+        assert_eq!(skipped[0].element_type, "TETRA4");
+        assert_eq!(skipped[0].num_elements, 2);
+        assert!(skipped[0].reason.as_deref().unwrap().contains("TETRA4"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_read_assemblies() {
+        let path = std::env::temp_dir().join("test_mesh_assemblies.exo");
+        {
+            let mut file = netcdf::create(&path).unwrap();
+            file.add_dimension("num_dim", 3).unwrap();
+            file.add_dimension("num_nodes", 8).unwrap();
+            file.add_dimension("num_elem", 1).unwrap();
+            file.add_dimension("num_el_blk", 1).unwrap();
+            file.add_dimension("num_el_in_blk1", 1).unwrap();
+            file.add_dimension("num_nod_per_el1", 8).unwrap();
+            file.add_dimension("len_name", 33).unwrap();
+
+            let coord: Vec<f64> = (0..3)
+                .flat_map(|axis| (0..8).map(move |i| ((i >> axis) & 1) as f64))
+                .collect();
+            file.add_variable::<f64>("coord", &["num_dim", "num_nodes"])
+                .unwrap()
+                .put_values(&coord, ..)
+                .unwrap();
+            let mut var = file
+                .add_variable::<i32>("connect1", &["num_el_in_blk1", "num_nod_per_el1"])
+                .unwrap();
+            var.put_attribute("elem_type", "HEX8").unwrap();
+            var.put_values(&[1, 2, 4, 3, 5, 6, 8, 7], ..).unwrap();
+            file.add_variable::<i32>("eb_prop1", &["num_el_blk"])
+                .unwrap()
+                .put_values(&[10], ..)
+                .unwrap();
+            let mut eb_names = vec![0u8; 33];
+            eb_names[..4].copy_from_slice(b"Head");
+            file.add_variable::<u8>("eb_names", &["num_el_blk", "len_name"])
+                .unwrap()
+                .put_values(&eb_names, ..)
+                .unwrap();
+
+            // Engine groups Top, which groups block 10; Fixed groups node sets
+            for (id, name, entity_type, members) in [
+                (1, "Top", EX_ELEM_BLOCK as i32, vec![10]),
+                (2, "Engine", EX_ASSEMBLY as i32, vec![1]),
+                (3, "Fixed", 2, vec![1]),
+            ] {
+                let dim = format!("num_entity_assembly{}", id);
+                file.add_dimension(&dim, members.len()).unwrap();
+                let mut var = file
+                    .add_variable::<i32>(&format!("assembly_entity{}", id), &[dim.as_str()])
+                    .unwrap();
+                var.put_attribute("_id", id).unwrap();
+                var.put_attribute("_name", name).unwrap();
+                var.put_attribute("_type", entity_type).unwrap();
+                var.put_values(&members, ..).unwrap();
+            }
+        }
+
+        let (mesh, warnings) = ExodusReader::open(&path)
+            .unwrap()
+            .read_mesh_with_warnings()
+            .unwrap();
+        assert_eq!(mesh.assemblies.len(), 2);
+        assert_eq!(mesh.assemblies["Top"].blocks, ["Head"]);
+        assert_eq!(mesh.assemblies["Engine"].assemblies, ["Top"]);
+        assert_eq!(warnings.of_kind(WarningKind::SkippedAssembly).count(), 1);
+        let engine: Vec<String> = mesh.assembly_blocks("Engine").unwrap().into_iter().collect();
+        assert_eq!(engine, ["Head"]);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_long_names() {
+        let mut mesh = Mesh::new();
+        for z in [0.0, 1.0] {
+            mesh.nodes.extend([
+                Point::new(0.0, 0.0, z),
+                Point::new(1.0, 0.0, z),
+                Point::new(1.0, 1.0, z),
+                Point::new(0.0, 1.0, z),
+            ]);
+        }
+        mesh.elements = vec![HexElement::new([0, 1, 2, 3, 4, 5, 6, 7])];
+        let block = "Gehäuse_outer_housing_with_a_very_long_name";
+        mesh.element_blocks.insert(block.to_string(), vec![0]);
+        let long = "contact_between_housing_and_cover_plate";
+        mesh.side_sets.insert(format!("{}_master", long), vec![(0, 6)]);
+        mesh.side_sets.insert(format!("{}_slave", long), vec![(0, 5)]);
+
+        // Long names are kept where the file allows them
+        let output_path = std::env::temp_dir().join("test_mesh_long_names.exo");
+        let options = ExodusWriteOptions { max_name_length: 64 };
+        write_exodus_with_options(&mesh, &output_path, &options).unwrap();
+        let (loaded, warnings) = ExodusReader::open(&output_path)
+            .unwrap()
+            .read_mesh_with_warnings()
+            .unwrap();
+        assert!(loaded.element_blocks.contains_key(block));
+        assert!(loaded.side_sets.contains_key(&format!("{}_slave", long)));
+        assert!(warnings.of_kind(WarningKind::InvalidName).next().is_none());
+
+        // Truncated to the default of 32 characters, names stay unique
+        write_exodus(&mesh, &output_path).unwrap();
+        let loaded = ExodusReader::open(&output_path).unwrap().read_mesh().unwrap();
+        assert_eq!(loaded.side_sets.len(), 2);
+        assert!(loaded.side_sets.keys().all(|name| name.len() <= 32));
+        assert!(loaded.element_blocks.keys().all(|name| name.starts_with("Gehäuse")));
+
+        let options = ExodusWriteOptions { max_name_length: 300 };
+        assert!(write_exodus_with_options(&mesh, &output_path, &options).is_err());
+
+        let _ = std::fs::remove_file(&output_path);
+    }
+
+    #[test]
+    fn test_num_maps_round_trip() {
+        // Two elements whose blocks are written in the reverse of mesh order
+        let mut mesh = Mesh::new();
+        for z in [0.0, 1.0, 2.0] {
+            mesh.nodes.extend([
+                Point::new(0.0, 0.0, z),
+                Point::new(1.0, 0.0, z),
+                Point::new(1.0, 1.0, z),
+                Point::new(0.0, 1.0, z),
+            ]);
+        }
+        mesh.elements = vec![
+            HexElement::new([0, 1, 2, 3, 4, 5, 6, 7]),
+            HexElement::new([4, 5, 6, 7, 8, 9, 10, 11]),
+        ];
+        mesh.element_blocks.insert("B_lower".to_string(), vec![0]);
+        mesh.element_blocks.insert("A_upper".to_string(), vec![1]);
+        mesh.node_num_map = (0..12).map(|i| 1000 + i).collect();
+        mesh.elem_num_map = vec![501, 502];
+        mesh.side_sets.insert("top".to_string(), vec![(1, 6)]);
+
+        let output_path = std::env::temp_dir().join("test_mesh_num_maps.exo");
+        write_exodus(&mesh, &output_path).unwrap();
+
+        let read_back = ExodusReader::open(&output_path).unwrap().read_mesh().unwrap();
+        assert_eq!(read_back.node_num_map, mesh.node_num_map);
+
+        let upper = read_back.element_blocks["A_upper"][0];
+        assert_eq!(read_back.global_elem_id(upper), 502);
+        assert_eq!(read_back.side_sets["top"], vec![(upper, 6)]);
+
+        let _ = std::fs::remove_file(&output_path);
+    }
+
     #[test]
     fn test_write_exodus_with_nodesets() {
         // Create a mesh with nodesets
@@ -1135,7 +2782,8 @@ mod tests {
         surface.nodes = mesh.nodes.clone();
 
         // Convert to sideset
-        let result = surface_to_sideset(&surface, &mesh);
+        let mut warnings = Warnings::new();
+        let result = surface_to_sideset(&surface, &mesh, &mut warnings);
         assert!(result.is_ok());
 
         let sideset = result.unwrap();
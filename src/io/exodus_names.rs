@@ -0,0 +1,134 @@
+//! Encoding of Exodus entity names
+//!
+//! Exodus stores block, set and variable names as fixed-width, null-padded
+//! byte rows whose width is the `len_name` dimension (32 characters plus a
+//! terminator by default, up to 255 characters). Names read from a file are
+//! decoded up to the first null byte with invalid UTF-8 replaced; names
+//! written to a file are truncated on a character boundary and made unique
+//! again afterwards, so truncation never merges two entities.
+
+use std::collections::HashSet;
+
+/// Maximum length of an Exodus entity name (excluding the null terminator)
+/// unless a file declares longer names
+pub const EXODUS_MAX_NAME_LEN: usize = 32;
+
+/// Longest name the Exodus format allows
+pub const EXODUS_NAME_LEN_LIMIT: usize = 255;
+
+/// Decode a null-padded name, returning whether it was valid UTF-8
+///
+/// Invalid byte sequences are replaced with `_`.
+pub fn decode_name(bytes: &[u8]) -> (String, bool) {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    let bytes = &bytes[..end];
+    match std::str::from_utf8(bytes) {
+        Ok(name) => (name.trim().to_string(), true),
+        Err(_) => {
+            let name: String = bytes
+                .utf8_chunks()
+                .flat_map(|chunk| {
+                    let replacement = (!chunk.invalid().is_empty()).then_some('_');
+                    chunk.valid().chars().chain(replacement)
+                })
+                .collect();
+            (name.trim().to_string(), false)
+        }
+    }
+}
+
+/// Truncate a name to at most `max_len` bytes without splitting a character
+pub fn truncate_name(name: &str, max_len: usize) -> &str {
+    let mut end = name.len().min(max_len);
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    &name[..end]
+}
+
+/// Fit names into `max_len` bytes, keeping them unique
+///
+/// Names that collide after truncation get a `_2`, `_3`, ... suffix. Returns
+/// the fitted names in input order.
+pub fn fit_names<S: AsRef<str>>(names: &[S], max_len: usize) -> Vec<String> {
+    let mut used: HashSet<String> = HashSet::new();
+    let mut fitted = Vec::with_capacity(names.len());
+    for name in names {
+        let name = name.as_ref();
+        let base = truncate_name(name, max_len).to_string();
+        let mut candidate = base.clone();
+        let mut n = 2;
+        while used.contains(&candidate) {
+            let suffix = format!("_{}", n);
+            candidate = format!(
+                "{}{}",
+                truncate_name(&base, max_len.saturating_sub(suffix.len())),
+                suffix
+            );
+            n += 1;
+        }
+        if candidate != name {
+            tracing::warn!("Exodus name '{}' written as '{}'", name, candidate);
+        }
+        used.insert(candidate.clone());
+        fitted.push(candidate);
+    }
+    fitted
+}
+
+/// Encode names as null-padded rows of `max_len + 1` bytes
+///
+/// Names must already fit (see [`fit_names`]).
+pub fn encode_names<S: AsRef<str>>(names: &[S], max_len: usize) -> Vec<u8> {
+    let width = max_len + 1;
+    let mut bytes = vec![0u8; names.len() * width];
+    for (i, name) in names.iter().enumerate() {
+        let name = truncate_name(name.as_ref(), max_len).as_bytes();
+        bytes[i * width..i * width + name.len()].copy_from_slice(name);
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_names() {
+        assert_eq!(decode_name(b"Block_1\0\0\0"), ("Block_1".to_string(), true));
+        assert_eq!(decode_name(b"top\0junk"), ("top".to_string(), true));
+        assert_eq!(
+            decode_name("Dichtung_ä".as_bytes()),
+            ("Dichtung_ä".to_string(), true)
+        );
+        assert_eq!(
+            decode_name(b"seal\xff\xfeside\0"),
+            ("seal__side".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn test_fit_names_keeps_them_unique() {
+        let long = "a_very_long_sideset_name_that_exceeds_32_characters";
+        let names = [
+            format!("{}_master", long),
+            format!("{}_slave", long),
+            "short".to_string(),
+        ];
+        let fitted = fit_names(&names, 32);
+        assert_eq!(fitted[0], &long[..32]);
+        assert_eq!(fitted[1], format!("{}_2", &long[..30]));
+        assert_eq!(fitted[2], "short");
+        assert!(fitted.iter().all(|name| name.len() <= 32));
+
+        // Longer names are kept where the file allows them
+        let fitted = fit_names(&names, 80);
+        assert_eq!(fitted[..2], names[..2]);
+
+        // Multi-byte characters are never split
+        assert_eq!(truncate_name("Gehäuse", 4), "Geh");
+        let encoded = encode_names(&["Gehäuse"], 4);
+        assert_eq!(encoded.len(), 5);
+        assert_eq!(decode_name(&encoded), ("Geh".to_string(), true));
+    }
+}
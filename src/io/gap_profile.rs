@@ -0,0 +1,78 @@
+//! Paths and gap profiles of the `probe` command
+//!
+//! A path is a JSON polyline, either a bare list of points or an object with
+//! a `points` list:
+//!
+//! ```json
+//! {"points": [[0.0, 0.5, 1.0], [2.0, 0.5, 1.0]]}
+//! ```
+//!
+//! The gap profile sampled along it is written as CSV, one row per sample.
+
+use crate::contact::GapSample;
+use crate::error::{ContactDetectorError, Result};
+use crate::format::FloatFormat;
+use crate::mesh::types::Point;
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// On-disk layout of a path
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PolylineFile {
+    Points(Vec<[f64; 3]>),
+    Object { points: Vec<[f64; 3]> },
+}
+
+/// Read the points of a JSON polyline
+#[tracing::instrument(skip_all, fields(path = %path.as_ref().display()))]
+pub fn read_polyline<P: AsRef<Path>>(path: P) -> Result<Vec<Point>> {
+    let reader = BufReader::new(File::open(path.as_ref())?);
+    let file: PolylineFile = serde_json::from_reader(reader).map_err(|e| {
+        ContactDetectorError::ConfigError(format!(
+            "Failed to parse path {}: {} (expected a list of [x, y, z] points)",
+            path.as_ref().display(),
+            e
+        ))
+    })?;
+    let (PolylineFile::Points(points) | PolylineFile::Object { points }) = file;
+    Ok(points
+        .into_iter()
+        .map(|[x, y, z]| Point::new(x, y, z))
+        .collect())
+}
+
+/// Write a gap profile as CSV
+///
+/// Samples on unpaired faces are written with an empty gap and normal angle,
+/// so plotting tools show breaks in the curve instead of a zero gap.
+#[tracing::instrument(skip_all, fields(samples = samples.len()))]
+pub fn write_gap_profile(
+    samples: &[GapSample],
+    output_path: &Path,
+    format: FloatFormat,
+) -> Result<()> {
+    let optional =
+        |value: Option<f64>| value.map_or_else(String::new, |v| format.show(v).to_string());
+
+    let mut out = BufWriter::new(File::create(output_path)?);
+    writeln!(out, "arclength,x,y,z,gap,normal_angle,face_id,offset")?;
+    for sample in samples {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{}",
+            format.show(sample.arclength),
+            format.show(sample.point.x),
+            format.show(sample.point.y),
+            format.show(sample.point.z),
+            optional(sample.gap),
+            optional(sample.normal_angle),
+            sample.face_index,
+            format.show(sample.offset)
+        )?;
+    }
+    out.flush()?;
+    Ok(())
+}
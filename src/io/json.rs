@@ -1,29 +1,103 @@
 //! Simple JSON mesh format for testing (alternative to Exodus when HDF5 unavailable)
+//!
+//! The schema is versioned through a top-level `version` field:
+//! - version 1 (no `version` field): geometry, blocks, sets, ID maps
+//! - version 2: adds material IDs, nodal/element fields, units and provenance,
+//!   and optionally assemblies of blocks
+//!
+//! Older files are read transparently; files from a newer version are rejected.
 
 use crate::error::{ContactDetectorError, Result};
-use crate::mesh::{HexElement, Mesh, Point};
+use crate::io::compression::{open_reader, CompressedWriter};
+use crate::io::registry::{MeshReader, MeshWriter};
+use crate::mesh::{BlockAssembly, HexElement, Mesh, MeshProvenance, Point};
+use crate::warnings::Warnings;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
+use std::collections::BTreeMap;
 use std::path::Path;
 
+/// Current JSON mesh schema version
+pub const JSON_MESH_VERSION: u32 = 2;
+
+fn legacy_version() -> u32 {
+    1
+}
+
+/// On-disk layout; maps are ordered so that a mesh always writes the same file
 #[derive(Debug, Serialize, Deserialize)]
 struct JsonMesh {
+    #[serde(default = "legacy_version")]
+    version: u32,
     nodes: Vec<[f64; 3]>,
     elements: Vec<[usize; 8]>,
     #[serde(default)]
-    element_blocks: HashMap<String, Vec<usize>>,
+    element_blocks: BTreeMap<String, Vec<usize>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    material_ids: Vec<i32>,
+    #[serde(default)]
+    node_sets: BTreeMap<String, Vec<usize>>,
     #[serde(default)]
-    node_sets: HashMap<String, Vec<usize>>,
+    side_sets: BTreeMap<String, Vec<(usize, u8)>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    side_set_dist_factors: BTreeMap<String, Vec<f64>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    node_num_map: Vec<i32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    elem_num_map: Vec<i32>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    node_fields: BTreeMap<String, Vec<f64>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    element_fields: BTreeMap<String, Vec<f64>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    units: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    assemblies: BTreeMap<String, BlockAssembly>,
     #[serde(default)]
-    side_sets: HashMap<String, Vec<(usize, u8)>>,
+    provenance: MeshProvenance,
+}
+
+impl JsonMesh {
+    /// Check the version and that per-node/per-element arrays have matching lengths
+    fn validate(&self) -> Result<()> {
+        if self.version > JSON_MESH_VERSION {
+            return Err(ContactDetectorError::ConfigError(format!(
+                "Unsupported JSON mesh version {} (newest supported: {})",
+                self.version, JSON_MESH_VERSION
+            )));
+        }
+
+        let num_nodes = self.nodes.len();
+        let num_elements = self.elements.len();
+        let mut checks = vec![
+            ("material_ids", self.material_ids.len(), num_elements),
+            ("node_num_map", self.node_num_map.len(), num_nodes),
+            ("elem_num_map", self.elem_num_map.len(), num_elements),
+        ];
+        for (name, values) in &self.node_fields {
+            checks.push((name.as_str(), values.len(), num_nodes));
+        }
+        for (name, values) in &self.element_fields {
+            checks.push((name.as_str(), values.len(), num_elements));
+        }
+
+        for (name, len, expected) in checks {
+            if len != 0 && len != expected {
+                return Err(ContactDetectorError::ConfigError(format!(
+                    "Failed to parse JSON mesh: '{}' has {} values, expected {}",
+                    name, len, expected
+                )));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Read a mesh from a JSON file
 ///
 /// This is an alternative to Exodus II format, useful for testing or when
-/// HDF5/NetCDF libraries are not available.
+/// HDF5/NetCDF libraries are not available. Gzip and zstd compressed files
+/// are detected and decompressed transparently.
 ///
 /// # Arguments
 /// * `path` - Path to the JSON mesh file
@@ -33,13 +107,13 @@ struct JsonMesh {
 ///
 /// # Errors
 /// Returns an error if the file cannot be read or parsed as valid JSON
+#[tracing::instrument(skip_all, fields(path = %path.as_ref().display()))]
 pub fn read_json_mesh<P: AsRef<Path>>(path: P) -> Result<Mesh> {
-    let file = File::open(path.as_ref()).map_err(ContactDetectorError::IoError)?;
-
-    let reader = BufReader::new(file);
+    let reader = open_reader(path.as_ref())?;
     let json_mesh: JsonMesh = serde_json::from_reader(reader).map_err(|e| {
         ContactDetectorError::ConfigError(format!("Failed to parse JSON mesh: {}", e))
     })?;
+    json_mesh.validate()?;
 
     let mut mesh = Mesh::new();
 
@@ -58,16 +132,32 @@ pub fn read_json_mesh<P: AsRef<Path>>(path: P) -> Result<Mesh> {
         .collect();
 
     // Copy metadata
-    mesh.element_blocks = json_mesh.element_blocks;
-    mesh.node_sets = json_mesh.node_sets;
-    mesh.side_sets = json_mesh.side_sets;
+    mesh.element_blocks = json_mesh.element_blocks.into_iter().collect();
+    mesh.node_sets = json_mesh.node_sets.into_iter().collect();
+    mesh.side_sets = json_mesh.side_sets.into_iter().collect();
+    mesh.side_set_dist_factors = json_mesh.side_set_dist_factors.into_iter().collect();
+    mesh.node_num_map = json_mesh.node_num_map;
+    mesh.elem_num_map = json_mesh.elem_num_map;
+    mesh.material_ids = json_mesh.material_ids;
+    mesh.node_fields = json_mesh.node_fields.into_iter().collect();
+    mesh.element_fields = json_mesh.element_fields.into_iter().collect();
+    mesh.units = json_mesh.units;
+    mesh.assemblies = json_mesh.assemblies.into_iter().collect();
+    mesh.provenance = json_mesh.provenance;
+
+    // Meshes that never went through another format originate here
+    if mesh.provenance.source.is_none() {
+        mesh.provenance.source = Some(path.as_ref().display().to_string());
+        mesh.provenance.format = Some("json".to_string());
+    }
 
     Ok(mesh)
 }
 
 /// Write a mesh to a JSON file
 ///
-/// Serializes the mesh data structure to a human-readable JSON format.
+/// Serializes the mesh data structure to a human-readable JSON format,
+/// compressed if the path ends in `.gz` or `.zst`.
 ///
 /// # Arguments
 /// * `mesh` - The mesh to write
@@ -75,26 +165,73 @@ pub fn read_json_mesh<P: AsRef<Path>>(path: P) -> Result<Mesh> {
 ///
 /// # Errors
 /// Returns an error if the file cannot be created or written
+#[tracing::instrument(skip_all, fields(path = %path.as_ref().display()))]
 pub fn write_json_mesh<P: AsRef<Path>>(mesh: &Mesh, path: P) -> Result<()> {
     let json_mesh = JsonMesh {
+        version: JSON_MESH_VERSION,
         nodes: mesh.nodes.iter().map(|p| [p.x, p.y, p.z]).collect(),
         elements: mesh.elements.iter().map(|e| e.node_ids).collect(),
-        element_blocks: mesh.element_blocks.clone(),
-        node_sets: mesh.node_sets.clone(),
-        side_sets: mesh.side_sets.clone(),
+        element_blocks: mesh.element_blocks.clone().into_iter().collect(),
+        material_ids: mesh.material_ids.clone(),
+        node_sets: mesh.node_sets.clone().into_iter().collect(),
+        side_sets: mesh.side_sets.clone().into_iter().collect(),
+        side_set_dist_factors: mesh.side_set_dist_factors.clone().into_iter().collect(),
+        node_num_map: mesh.node_num_map.clone(),
+        elem_num_map: mesh.elem_num_map.clone(),
+        node_fields: mesh.node_fields.clone().into_iter().collect(),
+        element_fields: mesh.element_fields.clone().into_iter().collect(),
+        units: mesh.units.clone(),
+        assemblies: mesh.assemblies.clone().into_iter().collect(),
+        provenance: mesh.provenance.clone(),
     };
 
-    let file = File::create(path.as_ref())?;
-    serde_json::to_writer_pretty(file, &json_mesh).map_err(|e| {
+    let mut writer = CompressedWriter::create(path.as_ref())?;
+    serde_json::to_writer_pretty(&mut writer, &json_mesh).map_err(|e| {
         ContactDetectorError::ConfigError(format!("Failed to write JSON mesh: {}", e))
     })?;
+    writer.finish()
+}
+
+/// The JSON mesh format in a [`crate::io::FormatRegistry`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonMeshFormat;
+
+impl MeshReader for JsonMeshFormat {
+    fn name(&self) -> &str {
+        "json"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+
+    fn sniff(&self, header: &[u8]) -> bool {
+        header.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{')
+    }
 
-    Ok(())
+    fn read(&self, path: &Path) -> Result<(Mesh, Warnings)> {
+        Ok((read_json_mesh(path)?, Warnings::new()))
+    }
+}
+
+impl MeshWriter for JsonMeshFormat {
+    fn name(&self) -> &str {
+        "json"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["json"]
+    }
+
+    fn write(&self, mesh: &Mesh, path: &Path) -> Result<()> {
+        write_json_mesh(mesh, path)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mesh::{BlockReadRecord, BlockReadStatus};
 
     #[test]
     fn test_json_roundtrip() {
@@ -120,4 +257,83 @@ mod tests {
         assert_eq!(loaded.num_elements(), 1);
         assert_eq!(loaded.num_blocks(), 1);
     }
+
+    #[test]
+    fn test_json_v2_roundtrip() {
+        let mut mesh = Mesh::new();
+        mesh.nodes = (0..8)
+            .map(|i| Point::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+            .collect();
+        mesh.elements = vec![HexElement::new([0, 1, 3, 2, 4, 5, 7, 6])];
+        mesh.element_blocks.insert("Block1".to_string(), vec![0]);
+        mesh.material_ids = vec![7];
+        mesh.node_fields
+            .insert("temperature".to_string(), vec![20.0; 8]);
+        mesh.element_fields.insert("stress".to_string(), vec![1.5]);
+        mesh.units = Some("mm".to_string());
+        mesh.assemblies.insert(
+            "Engine".to_string(),
+            BlockAssembly {
+                blocks: vec!["Block1".to_string()],
+                assemblies: Vec::new(),
+            },
+        );
+        mesh.provenance.source = Some("model.exo".to_string());
+        mesh.provenance.format = Some("exodus".to_string());
+        mesh.provenance.blocks.push(BlockReadRecord {
+            name: "Seal".to_string(),
+            element_type: "TETRA4".to_string(),
+            num_elements: 12,
+            status: BlockReadStatus::Skipped,
+            reason: Some("unsupported element type TETRA4".to_string()),
+        });
+
+        let path = std::env::temp_dir().join("test_mesh_v2.json");
+        write_json_mesh(&mesh, &path).unwrap();
+        let loaded = read_json_mesh(&path).unwrap();
+
+        assert_eq!(loaded.material_ids, vec![7]);
+        assert_eq!(loaded.node_fields["temperature"], vec![20.0; 8]);
+        assert_eq!(loaded.element_fields["stress"], vec![1.5]);
+        assert_eq!(loaded.units.as_deref(), Some("mm"));
+        assert_eq!(loaded.assemblies, mesh.assemblies);
+        assert_eq!(loaded.provenance, mesh.provenance);
+        assert_eq!(loaded.provenance.skipped_blocks().count(), 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_compressed_json_roundtrip() {
+        let mut mesh = Mesh::new();
+        mesh.nodes = vec![Point::new(1.0, 2.0, 3.0)];
+
+        for name in ["test_mesh.json.gz", "test_mesh.json.zst"] {
+            let path = std::env::temp_dir().join(name);
+            write_json_mesh(&mesh, &path).unwrap();
+            assert!(serde_json::from_slice::<serde_json::Value>(&std::fs::read(&path).unwrap()).is_err());
+
+            let loaded = read_json_mesh(&path).unwrap();
+            assert_eq!(loaded.nodes, mesh.nodes);
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+
+    #[test]
+    fn test_json_version_and_length_checks() {
+        let legacy: JsonMesh =
+            serde_json::from_str(r#"{"nodes": [[0, 0, 0]], "elements": []}"#).unwrap();
+        assert_eq!(legacy.version, 1);
+        assert!(legacy.validate().is_ok());
+
+        let future: JsonMesh =
+            serde_json::from_str(r#"{"version": 99, "nodes": [], "elements": []}"#).unwrap();
+        assert!(future.validate().is_err());
+
+        let bad_field: JsonMesh = serde_json::from_str(
+            r#"{"version": 2, "nodes": [[0, 0, 0]], "elements": [], "node_fields": {"t": [1, 2]}}"#,
+        )
+        .unwrap();
+        assert!(bad_field.validate().is_err());
+    }
 }
@@ -0,0 +1,189 @@
+//! Legends for the integer category arrays of the VTK outputs
+//!
+//! Cell arrays such as `ContactRole` or `ContactState` hold integer codes,
+//! which viewers display as bare numbers. The legend maps the codes of each
+//! such array to names and is written as JSON next to the outputs; the
+//! ParaView state script also applies it as categorical annotations.
+
+use crate::error::{ContactDetectorError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Version of the legend file format
+pub const LEGEND_VERSION: u32 = 1;
+
+/// Contact state of a face, written as the `ContactState` (multi-block) or
+/// `contact_state` (per-pair VTU) cell array
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactState {
+    /// Not paired with a face of the other surface
+    Unpaired,
+
+    /// Paired across a gap (or touching)
+    Paired,
+
+    /// Paired with the surfaces overlapping
+    Penetrating,
+}
+
+impl ContactState {
+    /// All states in code order
+    pub const ALL: [ContactState; 3] = [
+        ContactState::Unpaired,
+        ContactState::Paired,
+        ContactState::Penetrating,
+    ];
+
+    /// State of a face from the distance to its partner, if it has one
+    pub fn of_distance(distance: Option<f64>) -> Self {
+        match distance {
+            None => ContactState::Unpaired,
+            Some(d) if d < 0.0 => ContactState::Penetrating,
+            Some(_) => ContactState::Paired,
+        }
+    }
+
+    /// Integer code written to the cell arrays
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Name of the state
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContactState::Unpaired => "unpaired",
+            ContactState::Paired => "paired",
+            ContactState::Penetrating => "penetrating",
+        }
+    }
+}
+
+impl std::fmt::Display for ContactState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One code of a category array and its name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LegendEntry {
+    pub code: i32,
+    pub label: String,
+}
+
+/// Names of the integer codes of the category arrays, by array name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputLegend {
+    /// Legend file format version
+    pub version: u32,
+
+    /// Codes and names of each array
+    pub arrays: BTreeMap<String, Vec<LegendEntry>>,
+}
+
+impl Default for OutputLegend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OutputLegend {
+    /// Legend of the arrays whose codes are fixed: contact roles, paired
+    /// flags and contact states
+    pub fn new() -> Self {
+        let mut legend = Self {
+            version: LEGEND_VERSION,
+            arrays: BTreeMap::new(),
+        };
+        legend.insert("ContactRole", [(0, "master"), (1, "slave")]);
+        for name in ["IsPaired", "is_paired"] {
+            legend.insert(name, [(0, "unpaired"), (1, "paired")]);
+        }
+        for name in ["ContactState", "contact_state"] {
+            legend.insert(name, ContactState::ALL.map(|s| (s.code(), s.as_str())));
+        }
+        legend
+    }
+
+    fn insert<'a>(&mut self, array: &str, entries: impl IntoIterator<Item = (i32, &'a str)>) {
+        self.arrays.insert(
+            array.to_string(),
+            entries
+                .into_iter()
+                .map(|(code, label)| LegendEntry {
+                    code,
+                    label: label.to_string(),
+                })
+                .collect(),
+        );
+    }
+
+    /// Name the contact pairs of `ContactPairId` and `ContactSurfaceId`
+    pub fn add_pair_labels(&mut self, labels: impl IntoIterator<Item = (usize, String)>) {
+        let entries: Vec<LegendEntry> = labels
+            .into_iter()
+            .map(|(pair_id, label)| LegendEntry {
+                code: pair_id as i32,
+                label,
+            })
+            .collect();
+        for name in ["ContactPairId", "ContactSurfaceId"] {
+            self.arrays.insert(name.to_string(), entries.clone());
+        }
+    }
+
+    /// Name of `code` in `array`, if known
+    pub fn label(&self, array: &str, code: i32) -> Option<&str> {
+        self.arrays
+            .get(array)?
+            .iter()
+            .find(|entry| entry.code == code)
+            .map(|entry| entry.label.as_str())
+    }
+
+    /// Write the legend to a JSON file
+    pub fn export<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to serialize legend: {}", e))
+        })?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Load a legend from a JSON file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to parse legend: {}", e))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legend_roundtrip() {
+        assert_eq!(ContactState::of_distance(None), ContactState::Unpaired);
+        assert_eq!(ContactState::of_distance(Some(0.0)), ContactState::Paired);
+        assert_eq!(
+            ContactState::of_distance(Some(-1e-4)),
+            ContactState::Penetrating
+        );
+
+        let mut legend = OutputLegend::new();
+        legend.add_pair_labels([(1, "Lower ↔ Upper".to_string())]);
+        assert_eq!(legend.label("ContactRole", 1), Some("slave"));
+        assert_eq!(legend.label("contact_state", 2), Some("penetrating"));
+        assert_eq!(legend.label("ContactPairId", 1), Some("Lower ↔ Upper"));
+        assert_eq!(legend.label("ContactPairId", 2), None);
+
+        let path = std::env::temp_dir().join("test_output_legend.json");
+        legend.export(&path).unwrap();
+        assert_eq!(OutputLegend::load(&path).unwrap(), legend);
+        let _ = std::fs::remove_file(&path);
+    }
+}
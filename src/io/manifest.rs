@@ -0,0 +1,534 @@
+//! Solver-agnostic contact manifest
+//!
+//! The manifest is the neutral hand-off from detection to solver input
+//! translators. Unlike [`ContactMetadata`](crate::io::ContactMetadata), which
+//! summarizes a run for people, it defines every contact surface completely
+//! by its element sides, so a translator needs nothing but the manifest and
+//! the mesh file.
+//!
+//! # Schema (version 1)
+//!
+//! ```yaml
+//! schema: contact-manifest
+//! schema_version: 1
+//! generator: contact-detector 0.1.0
+//! created: 2024-05-01T12:00:00+00:00
+//! mesh_file: model.exo
+//! criteria: { max_gap: 0.005, max_penetration: 0.001, max_angle: 45.0, min_facing: 0.5 }
+//! surfaces:
+//!   - name: auto_contact_p1_master   # sideset name used on Exodus export
+//!     source: Block1:patch_3         # detected surface patch
+//!     block: Block1
+//!     role: master
+//!     area: 1.0
+//!     faces:                         # 1-based element number, Exodus side 1-6
+//!       - { element: 17, side: 6 }
+//! interfaces:
+//!   - id: 1
+//!     master: auto_contact_p1_master
+//!     slave: auto_contact_p1_slave
+//!     num_face_pairs: 64
+//!     interface_type: planar_flange  # or cylindrical_fit, spherical, irregular
+//!     gap: { min: 0.0, max: 0.0012, mean: 0.0004 }
+//!     recommended: { search_distance: 0.0015, max_penetration: 0.001 }
+//! ```
+//!
+//! The file is JSON, or YAML if its name ends in `.yaml` or `.yml`. Fields
+//! are only ever added within a schema version; readers should ignore fields
+//! they do not know and reject manifests with a newer `schema_version`.
+
+use crate::contact::{
+    AutoContactReport, ContactCriteria, ContactResults, InterfaceType, RelativeTolerance,
+};
+pub use crate::contact::SurfaceRole;
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::{ElementSideIndex, Mesh, SurfaceMesh};
+use crate::warnings::{WarningKind, Warnings};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Value of the `schema` field identifying a contact manifest
+pub const CONTACT_MANIFEST_SCHEMA: &str = "contact-manifest";
+
+/// Current contact manifest schema version
+pub const CONTACT_MANIFEST_VERSION: u32 = 1;
+
+/// Relative margin added to measured gaps for the recommended tolerances
+const TOLERANCE_MARGIN: f64 = 0.25;
+
+/// Neutral description of all detected contact interfaces
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContactManifest {
+    /// Always [`CONTACT_MANIFEST_SCHEMA`]
+    pub schema: String,
+
+    /// Schema version the file was written with
+    pub schema_version: u32,
+
+    /// Program and version that produced the manifest
+    pub generator: String,
+
+    /// Creation time (RFC 3339)
+    pub created: String,
+
+    /// Mesh file the element numbers refer to
+    pub mesh_file: String,
+
+    /// Criteria used for detection
+    pub criteria: ManifestCriteria,
+
+    /// Contact surfaces, each referenced by name from an interface
+    pub surfaces: Vec<ManifestSurface>,
+
+    /// Detected interfaces
+    pub interfaces: Vec<ManifestInterface>,
+}
+
+/// Detection criteria recorded in a manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestCriteria {
+    pub max_gap: f64,
+    /// Gap tolerance relative to the part size, resolved per pair (`max_gap`
+    /// is then unused)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_max_gap: Option<RelativeTolerance>,
+    pub max_penetration: f64,
+    pub max_angle: f64,
+    pub min_facing: f64,
+}
+
+/// One face of a contact surface, as an element side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElementSide {
+    /// 1-based element number in mesh file order
+    pub element: usize,
+
+    /// Exodus II side number (1-6)
+    pub side: u8,
+}
+
+/// A contact surface defined by element sides
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestSurface {
+    /// Unique surface name (the sideset name used on Exodus export)
+    pub name: String,
+
+    /// Detected surface patch the faces come from
+    pub source: String,
+
+    /// Element block owning the faces
+    pub block: String,
+
+    /// Role in its interface
+    pub role: SurfaceRole,
+
+    /// Total area of the faces
+    pub area: f64,
+
+    /// Faces of the surface
+    pub faces: Vec<ElementSide>,
+}
+
+impl ManifestSurface {
+    /// Describe the faces `face_indices` of `surface` by their element sides,
+    /// sorted by element number
+    ///
+    /// Faces that are not found in `index` are left out and reported in
+    /// `warnings`.
+    pub fn new(
+        name: String,
+        role: SurfaceRole,
+        surface: &SurfaceMesh,
+        face_indices: &[usize],
+        index: &ElementSideIndex,
+        warnings: &mut Warnings,
+    ) -> Self {
+        let mut faces = Vec::with_capacity(face_indices.len());
+        for &face_idx in face_indices {
+            let face = &surface.faces[face_idx];
+            match index.get(face) {
+                Some((elem_idx, side)) => faces.push(ElementSide {
+                    element: elem_idx + 1,
+                    side,
+                }),
+                None => warnings.push(
+                    WarningKind::UnmappedFace,
+                    format!(
+                        "Manifest surface '{}': face with nodes {:?} not found in mesh",
+                        name, face.node_ids
+                    ),
+                ),
+            }
+        }
+
+        faces.sort_unstable_by_key(|f| (f.element, f.side));
+
+        Self {
+            name,
+            source: surface.part_name.clone(),
+            block: surface.block_name().to_string(),
+            role,
+            area: face_indices.iter().map(|&i| surface.face_areas[i]).sum(),
+            faces,
+        }
+    }
+}
+
+/// Measured gap statistics of an interface (negative values are penetration)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GapStatistics {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+}
+
+/// Tolerances suggested for the solver's contact definition
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RecommendedTolerances {
+    /// Search distance covering every measured gap
+    pub search_distance: f64,
+
+    /// Penetration tolerance covering every measured overlap
+    pub max_penetration: f64,
+}
+
+/// A detected interface between a master and a slave surface
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestInterface {
+    /// 1-based interface ID (the contact pair ID of the run)
+    pub id: usize,
+
+    /// Name of the master surface
+    pub master: String,
+
+    /// Name of the slave surface
+    pub slave: String,
+
+    /// Number of detected face pairs
+    pub num_face_pairs: usize,
+
+    /// Shape of the interface (planar flange, cylindrical fit, ...)
+    #[serde(default)]
+    pub interface_type: InterfaceType,
+
+    /// Measured gaps over the face pairs
+    pub gap: GapStatistics,
+
+    /// Suggested solver tolerances
+    pub recommended: RecommendedTolerances,
+}
+
+impl ContactManifest {
+    /// Create an empty manifest for a mesh file
+    pub fn new(mesh_file: String, criteria: &ContactCriteria) -> Self {
+        Self {
+            schema: CONTACT_MANIFEST_SCHEMA.to_string(),
+            schema_version: CONTACT_MANIFEST_VERSION,
+            generator: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            created: chrono::Utc::now().to_rfc3339(),
+            mesh_file,
+            criteria: ManifestCriteria {
+                max_gap: criteria.max_gap_distance,
+                relative_max_gap: criteria.relative_max_gap,
+                max_penetration: criteria.max_penetration,
+                max_angle: criteria.max_normal_angle,
+                min_facing: criteria.min_facing,
+            },
+            surfaces: Vec::new(),
+            interfaces: Vec::new(),
+        }
+    }
+
+    /// Manifest of every interface of an auto-contact run on `mesh`
+    ///
+    /// Surfaces are named after the sidesets of their interface sides (see
+    /// [`SidesetNamer::name_interfaces`](crate::io::SidesetNamer::name_interfaces))
+    /// and hold their paired faces only if `paired_only`, like the exported
+    /// sidesets. Faces not found in the mesh are reported in `warnings`.
+    pub fn of_auto_contact(
+        mesh_file: String,
+        report: &AutoContactReport,
+        mesh: &Mesh,
+        paired_only: bool,
+        warnings: &mut Warnings,
+    ) -> Self {
+        let index = ElementSideIndex::new(mesh);
+        let mut manifest = Self::new(mesh_file, &report.criteria);
+        for interface in &report.pairs {
+            let mut surface = |is_a: bool| {
+                let side = interface.side(is_a);
+                ManifestSurface::new(
+                    side.sideset.clone().unwrap_or_default(),
+                    side.role,
+                    interface.surface(&report.surfaces, is_a),
+                    &interface.sideset_faces(&report.surfaces, is_a, paired_only),
+                    &index,
+                    warnings,
+                )
+            };
+            let master = surface(true);
+            let slave = surface(false);
+            manifest.add_interface(
+                interface.id,
+                master,
+                slave,
+                &interface.results,
+                interface.interface_type(&report.surfaces),
+            );
+        }
+        manifest
+    }
+
+    /// Add an interface between two surfaces with the results detected on it
+    pub fn add_interface(
+        &mut self,
+        id: usize,
+        master: ManifestSurface,
+        slave: ManifestSurface,
+        results: &ContactResults,
+        interface_type: InterfaceType,
+    ) {
+        let distances = results.pairs.iter().map(|p| p.distance);
+        let (min, max) = distances
+            .clone()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), d| {
+                (lo.min(d), hi.max(d))
+            });
+        let gap = if results.pairs.is_empty() {
+            GapStatistics {
+                min: 0.0,
+                max: 0.0,
+                mean: 0.0,
+            }
+        } else {
+            GapStatistics {
+                min,
+                max,
+                mean: distances.sum::<f64>() / results.pairs.len() as f64,
+            }
+        };
+
+        let with_margin =
+            |measured: f64, floor: f64| (measured * (1.0 + TOLERANCE_MARGIN)).max(floor);
+        let recommended = RecommendedTolerances {
+            search_distance: with_margin(gap.max, results.criteria.max_penetration),
+            max_penetration: with_margin(-gap.min, results.criteria.max_penetration),
+        };
+
+        self.interfaces.push(ManifestInterface {
+            id,
+            master: master.name.clone(),
+            slave: slave.name.clone(),
+            num_face_pairs: results.num_pairs(),
+            interface_type,
+            gap,
+            recommended,
+        });
+        self.surfaces.push(master);
+        self.surfaces.push(slave);
+    }
+
+    /// Surface with the given name
+    pub fn surface(&self, name: &str) -> Option<&ManifestSurface> {
+        self.surfaces.iter().find(|s| s.name == name)
+    }
+
+    /// Write the manifest as YAML (`.yaml`/`.yml`) or JSON (anything else)
+    pub fn export<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let text = if is_yaml_path(path) {
+            serde_yaml::to_string(self).map_err(|e| {
+                ContactDetectorError::ConfigError(format!(
+                    "Failed to write contact manifest: {}",
+                    e
+                ))
+            })?
+        } else {
+            serde_json::to_string_pretty(self).map_err(|e| {
+                ContactDetectorError::ConfigError(format!(
+                    "Failed to write contact manifest: {}",
+                    e
+                ))
+            })?
+        };
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// Read a manifest written by [`ContactManifest::export`]
+    ///
+    /// Fails on manifests of another schema or a newer schema version.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let manifest: Self = if is_yaml_path(path) {
+            serde_yaml::from_str(&text).map_err(|e| e.to_string())
+        } else {
+            serde_json::from_str(&text).map_err(|e| e.to_string())
+        }
+        .map_err(|e| {
+            ContactDetectorError::ConfigError(format!(
+                "Failed to parse contact manifest {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        if manifest.schema != CONTACT_MANIFEST_SCHEMA {
+            return Err(ContactDetectorError::ConfigError(format!(
+                "{} is not a contact manifest (schema '{}')",
+                path.display(),
+                manifest.schema
+            )));
+        }
+        if manifest.schema_version > CONTACT_MANIFEST_VERSION {
+            return Err(ContactDetectorError::ConfigError(format!(
+                "Contact manifest {} has schema version {}, newest supported is {}",
+                path.display(),
+                manifest.schema_version,
+                CONTACT_MANIFEST_VERSION
+            )));
+        }
+        Ok(manifest)
+    }
+}
+
+/// Whether a path names a YAML file
+pub fn is_yaml_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("yaml") || e.eq_ignore_ascii_case("yml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::ContactPair;
+    use crate::mesh::{extract_surface, HexElement, Mesh, Point};
+
+    /// Two unit cubes stacked along z with a gap of 0.001
+    fn two_cubes() -> Mesh {
+        let mut mesh = Mesh::new();
+        for z in [0.0, 1.0, 1.001, 2.001] {
+            mesh.nodes.extend([
+                Point::new(0.0, 0.0, z),
+                Point::new(1.0, 0.0, z),
+                Point::new(1.0, 1.0, z),
+                Point::new(0.0, 1.0, z),
+            ]);
+        }
+        mesh.elements
+            .push(HexElement::new([0, 1, 2, 3, 4, 5, 6, 7]));
+        mesh.elements
+            .push(HexElement::new([8, 9, 10, 11, 12, 13, 14, 15]));
+        mesh.element_blocks.insert("Lower".to_string(), vec![0]);
+        mesh.element_blocks.insert("Upper".to_string(), vec![1]);
+        mesh
+    }
+
+    fn sample_manifest() -> ContactManifest {
+        let mesh = two_cubes();
+        let index = ElementSideIndex::new(&mesh);
+        let surfaces = extract_surface(&mesh).unwrap();
+        // Face of `block` at height `z`
+        let find_face = |block: &str, z: f64| {
+            surfaces
+                .iter()
+                .filter(|s| s.block_name() == block)
+                .find_map(|s| {
+                    (0..s.num_faces())
+                        .find(|&i| (s.face_centroids[i].z - z).abs() < 1e-9)
+                        .map(|i| (s, i))
+                })
+                .unwrap()
+        };
+        let (lower, top) = find_face("Lower", 1.0);
+        let (upper, bottom) = find_face("Upper", 1.001);
+
+        let criteria = ContactCriteria::default();
+        let mut results =
+            ContactResults::new("Lower".to_string(), "Upper".to_string(), criteria.clone());
+        results.pairs.push(ContactPair {
+            surface_a_face_id: top,
+            surface_b_face_id: bottom,
+            distance: 0.001,
+            normal_angle: 0.0,
+            contact_point: Point::new(0.5, 0.5, 1.001),
+        });
+
+        let mut warnings = Warnings::new();
+        let master = ManifestSurface::new(
+            "p1_master".to_string(),
+            SurfaceRole::Master,
+            lower,
+            &[top],
+            &index,
+            &mut warnings,
+        );
+        let slave = ManifestSurface::new(
+            "p1_slave".to_string(),
+            SurfaceRole::Slave,
+            upper,
+            &[bottom],
+            &index,
+            &mut warnings,
+        );
+        assert!(warnings.is_empty());
+
+        let mut manifest = ContactManifest::new("two_cubes.exo".to_string(), &criteria);
+        manifest.add_interface(1, master, slave, &results, InterfaceType::PlanarFlange);
+        manifest
+    }
+
+    #[test]
+    fn test_manifest_contents() {
+        let manifest = sample_manifest();
+        assert_eq!(manifest.schema_version, CONTACT_MANIFEST_VERSION);
+        assert_eq!(manifest.interfaces.len(), 1);
+
+        let interface = &manifest.interfaces[0];
+        assert_eq!(interface.num_face_pairs, 1);
+        assert_eq!(interface.gap.max, 0.001);
+        assert!((interface.recommended.search_distance - 0.00125).abs() < 1e-12);
+
+        // Top of element 1 is Exodus side 6, bottom of element 2 side 5
+        let master = manifest.surface(&interface.master).unwrap();
+        assert_eq!(master.block, "Lower");
+        assert_eq!(
+            master.faces,
+            vec![ElementSide {
+                element: 1,
+                side: 6
+            }]
+        );
+        let slave = manifest.surface(&interface.slave).unwrap();
+        assert_eq!(slave.role, SurfaceRole::Slave);
+        assert_eq!(
+            slave.faces,
+            vec![ElementSide {
+                element: 2,
+                side: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn test_round_trip_json_and_yaml() {
+        let manifest = sample_manifest();
+        let dir = std::env::temp_dir();
+
+        for name in ["cd_manifest_test.json", "cd_manifest_test.yaml"] {
+            let path = dir.join(name);
+            manifest.export(&path).unwrap();
+            assert_eq!(ContactManifest::load(&path).unwrap(), manifest);
+            let _ = std::fs::remove_file(&path);
+        }
+
+        let mut newer = manifest.clone();
+        newer.schema_version = CONTACT_MANIFEST_VERSION + 1;
+        let path = dir.join("cd_manifest_newer.json");
+        newer.export(&path).unwrap();
+        assert!(ContactManifest::load(&path).is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,9 +1,15 @@
 //! JSON metadata export for contact detection results
 
-use crate::contact::{ContactCriteria, ContactResults, SurfaceMetrics};
+use crate::contact::{
+    AlignmentQuantiles, AutoContactReport, BudgetSkip, ContactCriteria, ContactInterface, ContactResults, CylinderFit, GapClosingTransform,
+    InterfaceConformity, InterfaceType, MergedInterface, PairMetrics, PatchFits, PlaneFit, RejectionStats, RelativeTolerance, RunSummary, SphereFit,
+};
 use crate::error::Result;
-use crate::mesh::SurfaceMesh;
+use crate::format::{FloatFormat, Notation};
+use crate::mesh::{BlockMass, BlockReadRecord, FilteredPatch, Mesh, SurfaceBounds, SurfaceMesh};
+use crate::warnings::Warnings;
 use serde::{Deserialize, Serialize};
+use std::io::Write;
 use std::path::Path;
 
 /// Complete metadata export for contact detection analysis
@@ -20,15 +26,59 @@ pub struct ContactMetadata {
 
     /// All detected contact pairs
     pub contact_pairs: Vec<ContactPairMetadata>,
+
+    /// Detected pairs consolidated into physical interfaces (if merging was requested)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub interfaces: Vec<MergedInterface>,
+
+    /// Surface patches excluded from detection by the patch filter
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filtered_patches: Vec<FilteredPatch>,
+
+    /// Element blocks of the mesh file and whether each was read; skipped
+    /// blocks are invisible to detection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mesh_blocks: Vec<BlockReadRecord>,
+
+    /// Volume, centroid and inertia of each element block
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub block_mass: Vec<BlockMass>,
+
+    /// Non-fatal warnings raised while reading or exporting
+    #[serde(default, skip_serializing_if = "Warnings::is_empty")]
+    pub warnings: Warnings,
+
+    /// Totals over the whole run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<RunSummary>,
+
+    /// Why candidates were rejected over every tested surface pair,
+    /// including those without contact (if recorded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejections: Option<RejectionStats>,
+
+    /// Surface pairs skipped because they exceeded the per-pair budget
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_budget: Vec<BudgetSkip>,
 }
 
 /// JSON representation of detection criteria
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DetectionCriteriaJson {
     pub max_gap: f64,
+    /// Gap tolerance relative to the part size, resolved per pair (`max_gap`
+    /// is then unused)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_max_gap: Option<RelativeTolerance>,
     pub max_penetration: f64,
     pub max_angle: f64,
+    #[serde(default)]
+    pub min_facing: f64,
     pub min_pairs: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_paired_area: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_coverage_fraction: Option<f64>,
 }
 
 /// Metadata for a single contact pair
@@ -38,6 +88,18 @@ pub struct ContactPairMetadata {
     pub surface_a: SurfaceInfo,
     pub surface_b: SurfaceInfo,
     pub contact_statistics: ContactStatistics,
+
+    /// Shape of the interface, from the primitive fits of both patches
+    #[serde(default)]
+    pub interface_type: InterfaceType,
+
+    /// Node conformity of the interface (if checked)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conformity: Option<InterfaceConformity>,
+
+    /// Rigid motion of surface B that would best close the measured gaps
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gap_closing: Option<GapClosingTransform>,
 }
 
 /// Information about a single surface in a contact pair
@@ -53,6 +115,23 @@ pub struct SurfaceInfo {
     pub total_area: f64,
     pub paired_area: f64,
     pub avg_normal: [f64; 3],
+
+    /// Oriented bounding box and bounding sphere of the surface
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<SurfaceBounds>,
+
+    /// Least-squares plane through the contact patch (paired faces), with
+    /// its flatness deviation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patch_plane: Option<PlaneFit>,
+
+    /// Least-squares cylinder through the contact patch (e.g. a pin or bore)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patch_cylinder: Option<CylinderFit>,
+
+    /// Least-squares sphere through the contact patch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub patch_sphere: Option<SphereFit>,
 }
 
 /// Contact statistics for a pair
@@ -65,6 +144,28 @@ pub struct ContactStatistics {
     pub std_dev_distance: f64,
     pub avg_normal_angle: f64,
     pub normal_alignment: String,
+
+    /// Paired fraction of each surface's area
+    #[serde(default)]
+    pub coverage_a: f64,
+    #[serde(default)]
+    pub coverage_b: f64,
+
+    /// Median ratio of A to B face size
+    #[serde(default)]
+    pub size_ratio: f64,
+
+    /// Deviation of paired normals from exact opposition, in degrees
+    #[serde(default)]
+    pub opposition_deviation: AlignmentQuantiles,
+    #[serde(default)]
+    pub rejected_facing: usize,
+    #[serde(default)]
+    pub rejected_occluded: usize,
+
+    /// Why candidates were rejected (if recorded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejections: Option<RejectionStats>,
 }
 
 impl ContactMetadata {
@@ -81,11 +182,70 @@ impl ContactMetadata {
             timestamp,
             detection_criteria: DetectionCriteriaJson {
                 max_gap: criteria.max_gap_distance,
+                relative_max_gap: criteria.relative_max_gap,
                 max_penetration: criteria.max_penetration,
                 max_angle: criteria.max_normal_angle,
+                min_facing: criteria.min_facing,
                 min_pairs,
+                min_paired_area: criteria.min_paired_area,
+                min_coverage_fraction: criteria.min_coverage_fraction,
             },
             contact_pairs: Vec::new(),
+            interfaces: Vec::new(),
+            filtered_patches: Vec::new(),
+            mesh_blocks: Vec::new(),
+            block_mass: Vec::new(),
+            warnings: Warnings::new(),
+            summary: None,
+            rejections: None,
+            skipped_budget: Vec::new(),
+        }
+    }
+
+    /// Metadata of an auto-contact run on `mesh`, with every pair it found
+    pub fn of_auto_contact(
+        mesh_file: String,
+        report: &AutoContactReport,
+        mesh: &Mesh,
+        min_pairs: usize,
+    ) -> Self {
+        let mut metadata = Self::new(mesh_file, &report.criteria, min_pairs);
+        metadata.filtered_patches = report.filtered_patches.clone();
+        metadata.mesh_blocks = mesh.provenance.blocks.clone();
+        metadata.block_mass = BlockMass::of_blocks(mesh);
+        metadata.rejections = report.rejections.clone();
+        metadata.skipped_budget = report.skipped_budget.clone();
+        for pair in &report.pairs {
+            metadata.add_contact_pair(
+                pair.id,
+                pair.surface(&report.surfaces, true),
+                pair.surface(&report.surfaces, false),
+                &pair.results,
+                &pair.metrics,
+            );
+        }
+        metadata
+    }
+
+    /// Record the node conformity of each contact pair, in pair order
+    pub fn set_conformity(&mut self, conformity: &[InterfaceConformity]) {
+        for (entry, conformity) in self.contact_pairs.iter_mut().zip(conformity) {
+            entry.conformity = Some(conformity.clone());
+        }
+    }
+
+    /// Name the sidesets of each contact pair as `interfaces` were exported
+    pub fn set_sideset_names(&mut self, interfaces: &[ContactInterface]) {
+        for entry in &mut self.contact_pairs {
+            let Some((name_a, name_b)) = interfaces
+                .iter()
+                .find(|interface| interface.id == entry.pair_id)
+                .and_then(|interface| interface.sidesets())
+            else {
+                continue;
+            };
+            entry.surface_a.sideset_name = name_a.to_string();
+            entry.surface_b.sideset_name = name_b.to_string();
         }
     }
 
@@ -96,9 +256,56 @@ impl ContactMetadata {
         surface_a: &SurfaceMesh,
         surface_b: &SurfaceMesh,
         results: &ContactResults,
-        metrics_a: &SurfaceMetrics,
-        metrics_b: &SurfaceMetrics,
+        metrics: &PairMetrics,
     ) {
+        self.contact_pairs.push(ContactPairMetadata::new(
+            pair_id, surface_a, surface_b, results, metrics,
+        ));
+    }
+
+    /// Export metadata to JSON file (compressed if the path ends in `.gz` or `.zst`)
+    pub fn export<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.export_with(path, FloatFormat::EXACT)
+    }
+
+    /// Export metadata to JSON file with its numbers rounded to `format`
+    pub fn export_with<P: AsRef<Path>>(&self, path: P, format: FloatFormat) -> Result<()> {
+        let error = |e: serde_json::Error| {
+            crate::error::ContactDetectorError::ConfigError(format!("Failed to write JSON metadata: {}", e))
+        };
+        let mut writer = crate::io::compression::CompressedWriter::create(path.as_ref())?;
+        if format.notation == Notation::Shortest {
+            serde_json::to_writer_pretty(&mut writer, self).map_err(error)?;
+        } else {
+            let mut value = serde_json::to_value(self).map_err(error)?;
+            format.round_json(&mut value);
+            serde_json::to_writer_pretty(&mut writer, &value).map_err(error)?;
+        }
+        writer.finish()
+    }
+
+    /// Load metadata from a JSON file, plain or compressed
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let reader = crate::io::compression::open_reader(path.as_ref())?;
+        serde_json::from_reader(reader).map_err(|e| {
+            crate::error::ContactDetectorError::ConfigError(format!("Failed to parse JSON metadata: {}", e))
+        })
+    }
+}
+
+impl ContactPairMetadata {
+    /// Metadata of a detected pair
+    pub fn new(
+        pair_id: usize,
+        surface_a: &SurfaceMesh,
+        surface_b: &SurfaceMesh,
+        results: &ContactResults,
+        metrics: &PairMetrics,
+    ) -> Self {
+        let (metrics_a, metrics_b) = (&metrics.surface_a, &metrics.surface_b);
+        let fits_a = PatchFits::of_patch(results, surface_a, true);
+        let fits_b = PatchFits::of_patch(results, surface_b, false);
+
         // Compute average normals for each surface
         let avg_normal_a = compute_average_normal(surface_a);
         let avg_normal_b = compute_average_normal(surface_b);
@@ -111,16 +318,7 @@ impl ContactMetadata {
         let sideset_a = format!("auto_contact_{}", sanitize_name(&surface_a.part_name));
         let sideset_b = format!("auto_contact_{}", sanitize_name(&surface_b.part_name));
 
-        // Determine normal alignment
-        let normal_alignment = if metrics_a.avg_normal_angle > 150.0 {
-            "opposed".to_string()
-        } else if metrics_a.avg_normal_angle < 30.0 {
-            "aligned".to_string()
-        } else {
-            "angled".to_string()
-        };
-
-        let pair_metadata = ContactPairMetadata {
+        Self {
             pair_id,
             surface_a: SurfaceInfo {
                 name: surface_a.part_name.clone(),
@@ -133,6 +331,10 @@ impl ContactMetadata {
                 total_area: metrics_a.total_area,
                 paired_area: metrics_a.paired_area,
                 avg_normal: avg_normal_a,
+                bounds: SurfaceBounds::of_surface(surface_a),
+                patch_plane: fits_a.plane,
+                patch_cylinder: fits_a.cylinder,
+                patch_sphere: fits_a.sphere,
             },
             surface_b: SurfaceInfo {
                 name: surface_b.part_name.clone(),
@@ -145,6 +347,10 @@ impl ContactMetadata {
                 total_area: metrics_b.total_area,
                 paired_area: metrics_b.paired_area,
                 avg_normal: avg_normal_b,
+                bounds: SurfaceBounds::of_surface(surface_b),
+                patch_plane: fits_b.plane,
+                patch_cylinder: fits_b.cylinder,
+                patch_sphere: fits_b.sphere,
             },
             contact_statistics: ContactStatistics {
                 num_pairs: results.num_pairs(),
@@ -153,23 +359,38 @@ impl ContactMetadata {
                 max_distance: metrics_a.max_distance,
                 std_dev_distance: metrics_a.std_dev_distance,
                 avg_normal_angle: metrics_a.avg_normal_angle,
-                normal_alignment,
+                normal_alignment: metrics.normal_alignment().to_string(),
+                coverage_a: metrics.coverage_a(),
+                coverage_b: metrics.coverage_b(),
+                size_ratio: metrics.size_ratio,
+                opposition_deviation: metrics_a.opposition_deviation,
+                rejected_facing: results.rejected_facing,
+                rejected_occluded: results.rejected_occluded,
+                rejections: results.rejections.clone(),
             },
-        };
-
-        self.contact_pairs.push(pair_metadata);
+            interface_type: metrics.interface_type,
+            conformity: None,
+            gap_closing: GapClosingTransform::fit(results, surface_a),
+        }
     }
 
-    /// Export metadata to JSON file
-    pub fn export<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        let file = std::fs::File::create(path.as_ref())?;
-        serde_json::to_writer_pretty(file, self).map_err(|e| {
+    /// Append this pair as one JSON line to a log file, creating it if needed
+    pub fn append_to<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut line = serde_json::to_string(self).map_err(|e| {
             crate::error::ContactDetectorError::ConfigError(format!("Failed to write JSON metadata: {}", e))
         })?;
+        line.push('\n');
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
         Ok(())
     }
 }
 
+
 /// Compute the average normal vector for a surface
 fn compute_average_normal(surface: &SurfaceMesh) -> [f64; 3] {
     if surface.face_normals.is_empty() {
@@ -208,7 +429,7 @@ fn parse_surface_name(name: &str) -> (Option<usize>, Option<usize>) {
 
     // Try to extract block ID
     if let Some(block_part) = name.split(':').next() {
-        if let Some(num_str) = block_part.split('_').last() {
+        if let Some(num_str) = block_part.split('_').next_back() {
             if let Ok(id) = num_str.parse::<usize>() {
                 block_id = Some(id);
             }
@@ -217,7 +438,7 @@ fn parse_surface_name(name: &str) -> (Option<usize>, Option<usize>) {
 
     // Try to extract patch ID
     if let Some(patch_part) = name.split(':').nth(1) {
-        if let Some(num_str) = patch_part.split('_').last() {
+        if let Some(num_str) = patch_part.split('_').next_back() {
             if let Ok(id) = num_str.parse::<usize>() {
                 patch_id = Some(id);
             }
@@ -327,11 +548,10 @@ mod tests {
         results.unpaired_b = vec![5, 6, 7];
 
         // Create metrics
-        let metrics_a = crate::contact::SurfaceMetrics::compute(&results, &surface_a, true);
-        let metrics_b = crate::contact::SurfaceMetrics::compute(&results, &surface_b, false);
+        let metrics = PairMetrics::compute(&results, &surface_a, &surface_b);
 
         // Add contact pair
-        metadata.add_contact_pair(1, &surface_a, &surface_b, &results, &metrics_a, &metrics_b);
+        metadata.add_contact_pair(1, &surface_a, &surface_b, &results, &metrics);
 
         assert_eq!(metadata.contact_pairs.len(), 1);
         assert_eq!(metadata.contact_pairs[0].pair_id, 1);
@@ -346,6 +566,27 @@ mod tests {
             metadata.contact_pairs[0].contact_statistics.normal_alignment,
             "opposed"
         );
+        assert_eq!(
+            metadata.contact_pairs[0].contact_statistics.coverage_a,
+            metrics.coverage_a()
+        );
+
+        // Pairs logged as they are found, one JSON line each
+        let log_path = std::env::temp_dir().join("test_contact_pairs.jsonl");
+        let _ = std::fs::remove_file(&log_path);
+        metadata.contact_pairs[0].append_to(&log_path).unwrap();
+        ContactPairMetadata::new(2, &surface_b, &surface_a, &results, &metrics)
+            .append_to(&log_path)
+            .unwrap();
+        let log = std::fs::read_to_string(&log_path).unwrap();
+        let logged: Vec<ContactPairMetadata> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(logged.len(), 2);
+        assert_eq!(logged[1].pair_id, 2);
+        assert_eq!(logged[1].surface_a.name, "Block_2:patch_1");
+        let _ = std::fs::remove_file(&log_path);
     }
 
     #[test]
@@ -365,4 +606,18 @@ mod tests {
         // Clean up
         let _ = std::fs::remove_file(&output_path);
     }
+
+    #[test]
+    fn test_metadata_export_compressed() {
+        let criteria = ContactCriteria::new(0.01, 0.01, 30.0);
+        let metadata = ContactMetadata::new("test_mesh.exo".to_string(), &criteria, 1);
+
+        let output_path = std::env::temp_dir().join("test_metadata.json.gz");
+        metadata.export(&output_path).unwrap();
+
+        let loaded = ContactMetadata::load(&output_path).unwrap();
+        assert_eq!(loaded.mesh_file, "test_mesh.exo");
+
+        let _ = std::fs::remove_file(&output_path);
+    }
 }
@@ -3,18 +3,96 @@
 #[cfg(feature = "exodus")]
 pub mod exodus;
 
+pub mod binary;
+pub mod compression;
+pub mod conductance_table;
+pub mod exodus_names;
+pub mod gap_profile;
 pub mod json;
+pub mod legend;
+pub mod manifest;
 pub mod metadata;
+pub mod obj;
+pub mod output_naming;
+pub mod paraview;
+pub mod ply;
+pub mod point_cloud;
+pub mod registry;
+pub mod results;
+pub mod run_manifest;
+pub mod sideset_naming;
+pub mod stl;
+pub mod surface_import;
+pub mod surface_writer;
+pub mod vtk_version;
+pub mod vtp;
 pub mod vtu;
+pub mod vtu_stream;
 pub mod vtm;
 
 #[cfg(feature = "exodus")]
-pub use exodus::{add_contact_sidesets_to_mesh, surface_to_sideset, write_exodus, ExodusReader};
+pub use exodus::{
+    add_auto_contact_sidesets, add_contact_sidesets_to_mesh, add_contact_sidesets_with_data,
+    add_contact_sidesets_with_dist_factors, surface_to_sideset, surface_to_sideset_indexed,
+    write_exodus, write_exodus_with_options, ExodusBlockInfo, ExodusFileInfo, ExodusFormat,
+    ExodusReader, ExodusWriteOptions, SidesetFaceData,
+};
 
-pub use json::{read_json_mesh, write_json_mesh};
-pub use metadata::ContactMetadata;
+pub use binary::{is_binary_mesh_path, read_binary_mesh, write_binary_mesh, BinaryMeshFormat};
+pub use compression::{is_json_path, Compression};
+pub use conductance_table::write_conductance_table;
+pub use exodus_names::{EXODUS_MAX_NAME_LEN, EXODUS_NAME_LEN_LIMIT};
+pub use gap_profile::{read_polyline, write_gap_profile};
+pub use json::{read_json_mesh, write_json_mesh, JsonMeshFormat, JSON_MESH_VERSION};
+pub use legend::{ContactState, LegendEntry, OutputLegend, LEGEND_VERSION};
+pub use manifest::{
+    ContactManifest, ElementSide, ManifestSurface, SurfaceRole, CONTACT_MANIFEST_SCHEMA,
+    CONTACT_MANIFEST_VERSION,
+};
+pub use metadata::{ContactMetadata, ContactPairMetadata};
+pub use obj::{read_obj_faces, write_surfaces_to_obj};
+pub use output_naming::{OutputNamer, OverwritePolicy, DEFAULT_OUTPUT_NAME_TEMPLATE};
+pub use paraview::{ParaViewScript, ScriptLayer};
+pub use registry::{FormatRegistry, MeshReader, MeshWriter};
+pub use results::{ContactResultsFile, CONTACT_RESULTS_VERSION};
+pub use ply::{write_surfaces_to_ply, FaceScalar};
+pub use point_cloud::{read_point_cloud, write_point_deviations};
+pub use run_manifest::{
+    file_sha256, ArtifactRecord, InputRecord, RunManifest, RUN_MANIFEST_FILE_NAME,
+    RUN_MANIFEST_VERSION,
+};
+pub use sideset_naming::{SidesetNamer, SidesetRole, DEFAULT_SIDESET_NAME_TEMPLATE};
+pub use stl::read_stl_triangles;
+pub use surface_import::{is_surface_file_path, read_surface_file};
+pub use surface_writer::{
+    sideset_variables, AttributeProvider, AttributeSet, CsvSurfaceWriter, FaceAttribute,
+    FaceContext, FnAttribute, SurfaceFormats, SurfaceWriter, VtpSurfaceWriter, VtuSurfaceWriter,
+};
+pub use vtk_version::{VtkFileKind, VtkVersion};
+pub use vtp::{write_broad_phase_to_vtp, write_holes_to_vtp, write_outlines_to_vtp, OutlineGroup};
 pub use vtu::{
-    write_contact_surfaces_with_skin, write_surface_to_vtu, write_surface_with_contact_metadata,
-    write_surfaces_to_vtu, write_vtk,
+    write_contact_surfaces_with_skin, write_decimated_contact_vtu, write_merged_skin_to_vtu,
+    write_surface_to_vtu, write_surfaces_to_vtu, write_vtk,
+};
+pub use vtu_stream::{
+    write_surface_to_vtu_streaming, CellArray, CellValues, STREAMING_THRESHOLD_FACES,
 };
 pub use vtm::MultiBlockBuilder;
+
+/// Read a mesh stored in a format that needs no system libraries
+///
+/// JSON (`.json`) and binary (`.cdmesh`) meshes, optionally gzip/zstd
+/// compressed, are read here; `None` means the path has another extension
+/// and should be read as Exodus II.
+pub fn read_portable_mesh<P: AsRef<std::path::Path>>(
+    path: P,
+) -> crate::error::Result<Option<crate::mesh::Mesh>> {
+    let path = path.as_ref();
+    if is_json_path(path) {
+        read_json_mesh(path).map(Some)
+    } else if is_binary_mesh_path(path) {
+        read_binary_mesh(path).map(Some)
+    } else {
+        Ok(None)
+    }
+}
@@ -0,0 +1,238 @@
+//! Wavefront OBJ surface reader and writer
+//!
+//! Each surface becomes a group (`g`) with its own vertices, so the patches
+//! can be selected separately in MeshLab or Blender. OBJ has no per-face
+//! scalar data; use the PLY writer when values such as gap are needed.
+
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::geometry::{face_polygons, Triangulation};
+use crate::mesh::types::{Point, QuadFace, SurfaceMesh};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Write surfaces to a Wavefront OBJ file, one group per surface
+///
+/// With a triangulation, each quad becomes two triangles sharing its normal.
+#[tracing::instrument(skip_all, fields(count = surfaces.len()))]
+pub fn write_surfaces_to_obj(
+    surfaces: &[SurfaceMesh],
+    output_path: &Path,
+    triangulation: Option<Triangulation>,
+) -> Result<()> {
+    tracing::info!(
+        "Writing {} surfaces to OBJ {:?}",
+        surfaces.len(),
+        output_path
+    );
+
+    let mut out = BufWriter::new(File::create(output_path)?);
+    writeln!(out, "# Surfaces exported from contact-detector")?;
+
+    // OBJ indices are 1-based and global across the file
+    let mut vertex_offset = 0;
+    let mut normal_offset = 0;
+
+    for surface in surfaces {
+        writeln!(out, "g {}", group_name(&surface.part_name))?;
+
+        // Only write the nodes referenced by this surface
+        let mut local_ids = HashMap::new();
+        let mut used_nodes = Vec::new();
+        for face in &surface.faces {
+            for &node_id in &face.node_ids {
+                local_ids.entry(node_id).or_insert_with(|| {
+                    used_nodes.push(node_id);
+                    used_nodes.len() - 1
+                });
+            }
+        }
+
+        for &node_id in &used_nodes {
+            let p = &surface.nodes[node_id];
+            writeln!(out, "v {} {} {}", p.x, p.y, p.z)?;
+        }
+        for n in &surface.face_normals {
+            writeln!(out, "vn {} {} {}", n.x, n.y, n.z)?;
+        }
+
+        for (face_idx, face) in surface.faces.iter().enumerate() {
+            let normal = normal_offset + face_idx + 1;
+            for polygon in face_polygons(face, &surface.nodes, triangulation) {
+                write!(out, "f")?;
+                for node_id in &polygon {
+                    write!(
+                        out,
+                        " {}//{}",
+                        vertex_offset + local_ids[node_id] + 1,
+                        normal
+                    )?;
+                }
+                writeln!(out)?;
+            }
+        }
+
+        vertex_offset += used_nodes.len();
+        normal_offset += surface.face_normals.len();
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+/// Read the vertices and polygon faces of an OBJ file
+///
+/// Triangles and quads are kept as they are; larger polygons are split into a
+/// triangle fan. Texture and normal indices, groups and materials are ignored.
+#[tracing::instrument(skip_all, fields(path = %path.as_ref().display()))]
+pub fn read_obj_faces<P: AsRef<Path>>(path: P) -> Result<(Vec<Point>, Vec<QuadFace>)> {
+    let text = std::fs::read_to_string(path.as_ref())?;
+    let (nodes, faces) = parse_obj(&text)?;
+
+    tracing::info!(
+        "Read {} vertices, {} faces from OBJ",
+        nodes.len(),
+        faces.len()
+    );
+
+    Ok((nodes, faces))
+}
+
+fn parse_obj(text: &str) -> Result<(Vec<Point>, Vec<QuadFace>)> {
+    let parse_error = |line_no: usize, message: String| {
+        ContactDetectorError::ConfigError(format!(
+            "Failed to parse OBJ file: line {}: {}",
+            line_no + 1,
+            message
+        ))
+    };
+
+    let mut nodes = Vec::new();
+    let mut faces = Vec::new();
+
+    for (line_no, line) in text.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("v") => {
+                let coords: Vec<f64> = tokens
+                    .take(3)
+                    .map(|t| t.parse::<f64>())
+                    .collect::<std::result::Result<_, _>>()
+                    .map_err(|e| parse_error(line_no, e.to_string()))?;
+                if coords.len() != 3 {
+                    return Err(parse_error(
+                        line_no,
+                        "expected 3 vertex coordinates".to_string(),
+                    ));
+                }
+                nodes.push(Point::new(coords[0], coords[1], coords[2]));
+            }
+            Some("f") => {
+                let ids: Vec<usize> = tokens
+                    .map(|t| resolve_vertex(t, nodes.len()))
+                    .collect::<Option<_>>()
+                    .ok_or_else(|| parse_error(line_no, "invalid vertex index".to_string()))?;
+                match ids.len() {
+                    3 => faces.push(QuadFace::triangle([ids[0], ids[1], ids[2]])),
+                    4 => faces.push(QuadFace::new([ids[0], ids[1], ids[2], ids[3]])),
+                    n if n > 4 => {
+                        for i in 1..n - 1 {
+                            faces.push(QuadFace::triangle([ids[0], ids[i], ids[i + 1]]));
+                        }
+                    }
+                    n => {
+                        return Err(parse_error(
+                            line_no,
+                            format!("face has {} vertices, expected at least 3", n),
+                        ))
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok((nodes, faces))
+}
+
+/// Resolve a face vertex reference (`v`, `v/vt`, `v//vn` or `v/vt/vn`) to a
+/// 0-based node index; negative indices count back from the last vertex
+fn resolve_vertex(token: &str, num_nodes: usize) -> Option<usize> {
+    let index: i64 = token.split('/').next()?.parse().ok()?;
+    let resolved = if index < 0 {
+        num_nodes as i64 + index
+    } else {
+        index - 1
+    };
+    (0..num_nodes as i64)
+        .contains(&resolved)
+        .then_some(resolved as usize)
+}
+
+/// OBJ group names end at whitespace, so replace it
+fn group_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::types::Vec3;
+
+    #[test]
+    fn test_write_obj() {
+        let mut surface = SurfaceMesh::new("Block 1:patch_0".to_string());
+        surface.nodes = vec![
+            Point::new(9.0, 9.0, 9.0), // unused node is not written
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+        ];
+        surface.faces.push(QuadFace::new([1, 2, 3, 4]));
+        surface.face_normals.push(Vec3::new(0.0, 0.0, 1.0));
+        surface.face_centroids.push(Point::new(0.5, 0.5, 0.0));
+        surface.face_areas.push(1.0);
+
+        let path = std::env::temp_dir().join("test_surfaces.obj");
+        write_surfaces_to_obj(&[surface.clone(), surface.clone()], &path, None).unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(text.lines().filter(|l| l.starts_with("v ")).count(), 8);
+        assert!(text.contains("g Block_1:patch_0"));
+        // Second group's indices continue after the first group's
+        assert!(text.contains("f 5//2 6//2 7//2 8//2"));
+
+        write_surfaces_to_obj(&[surface], &path, Some(Triangulation::Diagonal02)).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.contains("f 1//1 2//1 3//1\nf 1//1 3//1 4//1\n"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_parse_obj() {
+        let text = "# test
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 0.5 2 0
+f 1/1/1 2/2/1 3/3/1 4/4/1
+f -3 -1 -2
+f 1 2 3 5 4
+";
+        let (nodes, faces) = parse_obj(text).unwrap();
+        assert_eq!(nodes.len(), 5);
+        assert_eq!(faces[0], QuadFace::new([0, 1, 2, 3]));
+        assert_eq!(faces[1], QuadFace::triangle([2, 4, 3]));
+        // Pentagon becomes a fan of three triangles
+        assert_eq!(faces.len(), 5);
+        assert_eq!(faces[4], QuadFace::triangle([0, 4, 3]));
+
+        assert!(parse_obj("v 0 0 0\nf 1 2 3\n").is_err());
+    }
+}
@@ -0,0 +1,204 @@
+//! Naming of per-pair output files and handling of existing outputs
+//!
+//! Result files of each contact pair are named from a template, so runs with
+//! different criteria can share an output directory without clobbering each
+//! other (e.g. by putting `{timestamp}` in the name). What happens to a file
+//! that already exists is decided by an [`OverwritePolicy`]; by default the
+//! run stops rather than silently replacing earlier results.
+
+use crate::error::{ContactDetectorError, Result};
+use std::path::Path;
+
+/// Default template for per-pair output files
+pub const DEFAULT_OUTPUT_NAME_TEMPLATE: &str = "contact_{a}_{b}";
+
+const PLACEHOLDERS: [&str; 6] = ["pair", "a", "b", "block_a", "block_b", "timestamp"];
+
+/// Generates per-pair output file names from a template
+///
+/// Supported placeholders: `{pair}` (1-based pair ID), `{a}` and `{b}`
+/// (surface names), `{block_a}` and `{block_b}` (element block names) and
+/// `{timestamp}` (start of the run, `YYYYMMDD_HHMMSS`, the same for every
+/// file of a run).
+#[derive(Debug, Clone)]
+pub struct OutputNamer {
+    template: String,
+    timestamp: String,
+}
+
+impl OutputNamer {
+    /// Create a namer stamping names with the current local time
+    pub fn new(template: &str) -> Result<Self> {
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+        Self::with_timestamp(template, &timestamp)
+    }
+
+    /// Create a namer with a fixed `{timestamp}`
+    pub fn with_timestamp(template: &str, timestamp: &str) -> Result<Self> {
+        validate_output_template(template)?;
+        Ok(Self {
+            template: template.to_string(),
+            timestamp: timestamp.to_string(),
+        })
+    }
+
+    /// The template names are generated from
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// File name for a contact pair, with the given extension (e.g. `vtu`
+    /// or `vis.vtu`)
+    pub fn file_name(
+        &self,
+        pair_id: usize,
+        surface_a: &str,
+        surface_b: &str,
+        extension: &str,
+    ) -> String {
+        let block = |name: &str| name.split(':').next().unwrap_or(name).to_string();
+        let stem = self
+            .template
+            .replace("{pair}", &pair_id.to_string())
+            .replace("{block_a}", &sanitize(&block(surface_a)))
+            .replace("{block_b}", &sanitize(&block(surface_b)))
+            .replace("{a}", &sanitize(surface_a))
+            .replace("{b}", &sanitize(surface_b))
+            .replace("{timestamp}", &self.timestamp);
+        format!("{}.{}", stem, extension)
+    }
+}
+
+/// What to do when an output file already exists
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverwritePolicy {
+    /// Stop with an error
+    #[default]
+    Error,
+
+    /// Replace the file
+    Overwrite,
+
+    /// Keep the existing file and don't write a new one
+    Skip,
+}
+
+impl OverwritePolicy {
+    /// Whether `path` should be written: always if it doesn't exist yet,
+    /// otherwise as the policy says
+    pub fn claim(&self, path: &Path) -> Result<bool> {
+        if !path.exists() {
+            return Ok(true);
+        }
+        match self {
+            OverwritePolicy::Overwrite => Ok(true),
+            OverwritePolicy::Skip => {
+                tracing::info!("Keeping existing output {:?}", path);
+                Ok(false)
+            }
+            OverwritePolicy::Error => Err(ContactDetectorError::ConfigError(format!(
+                "Output file {} already exists (use --overwrite to replace it or \
+                 --skip-existing to keep it)",
+                path.display()
+            ))),
+        }
+    }
+}
+
+/// Check that an output name template only uses known placeholders
+pub fn validate_output_template(template: &str) -> Result<()> {
+    if template.contains(['/', '\\']) {
+        return Err(ContactDetectorError::ConfigError(format!(
+            "Output name template '{}' must not contain path separators",
+            template
+        )));
+    }
+    validate_placeholders(template, "output name", &PLACEHOLDERS)
+}
+
+/// Check that `template` only uses the given placeholders; `what` names the
+/// template in error messages
+pub(crate) fn validate_placeholders(
+    template: &str,
+    what: &str,
+    placeholders: &[&str],
+) -> Result<()> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..].find('}').ok_or_else(|| {
+            ContactDetectorError::ConfigError(format!(
+                "Unclosed placeholder in {} template '{}'",
+                what, template
+            ))
+        })?;
+        let placeholder = &rest[start + 1..start + end];
+        if !placeholders.contains(&placeholder) {
+            return Err(ContactDetectorError::ConfigError(format!(
+                "Unknown placeholder '{{{}}}' in {} template '{}' (expected one of: {})",
+                placeholder,
+                what,
+                template,
+                placeholders
+                    .iter()
+                    .map(|p| format!("{{{}}}", p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        rest = &rest[start + end + 1..];
+    }
+
+    Ok(())
+}
+
+/// Replace characters that are awkward in file names
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_names_and_policies() {
+        let namer = OutputNamer::with_timestamp(DEFAULT_OUTPUT_NAME_TEMPLATE, "x").unwrap();
+        assert_eq!(
+            namer.file_name(1, "Block_1:patch_0", "Block_2:patch_3", "vtu"),
+            "contact_Block_1_patch_0_Block_2_patch_3.vtu"
+        );
+
+        let namer = OutputNamer::with_timestamp(
+            "p{pair}_{block_a}-{block_b}_{timestamp}",
+            "20260101_120000",
+        )
+        .unwrap();
+        assert_eq!(
+            namer.file_name(3, "Lid:patch_2", "Base", "vis.vtu"),
+            "p3_Lid-Base_20260101_120000.vis.vtu"
+        );
+
+        assert!(OutputNamer::new("contact_{pairs}").is_err());
+        assert!(OutputNamer::new("contact_{pair").is_err());
+        assert!(OutputNamer::new("runs/contact_{pair}").is_err());
+
+        let path = std::env::temp_dir().join("test_overwrite_policy.txt");
+        let _ = std::fs::remove_file(&path);
+        for policy in [OverwritePolicy::Error, OverwritePolicy::Skip] {
+            assert!(policy.claim(&path).unwrap());
+        }
+        std::fs::write(&path, "earlier results").unwrap();
+        assert!(OverwritePolicy::Error.claim(&path).is_err());
+        assert!(OverwritePolicy::Overwrite.claim(&path).unwrap());
+        assert!(!OverwritePolicy::Skip.claim(&path).unwrap());
+        let _ = std::fs::remove_file(&path);
+    }
+}
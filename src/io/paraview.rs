@@ -0,0 +1,259 @@
+//! ParaView state scripts
+//!
+//! A generated pvpython script loads the files written by a run and sets up
+//! a ready-made view: contact surfaces colored by gap distance with a
+//! diverging colormap centered at zero, the paired faces extracted by a
+//! threshold and drawn opaque over the translucent full surfaces, and
+//! context geometry (e.g. contact outlines) in a plain color. With a legend,
+//! the integer category arrays are annotated with their names. Files are
+//! referenced relative to the script, so the output directory can be moved.
+//! Open it with `paraview --script=<script>` or from ParaView's Python shell
+//! (View > Python Shell > Run Script).
+
+use crate::error::Result;
+use crate::io::legend::OutputLegend;
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Colormap preset used for distances
+const DISTANCE_PRESET: &str = "Cool to Warm";
+
+/// Largest value of the paired-face thresholds
+const THRESHOLD_MAX: i32 = i32::MAX;
+
+/// How a dataset is shown by the script
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptLayer {
+    /// Contact surfaces colored by distance; faces whose `paired_array` cell
+    /// value is at least `paired_min` are the paired ones
+    Contact {
+        distance_array: String,
+        paired_array: String,
+        paired_min: i32,
+    },
+
+    /// Context geometry in a plain color
+    Context,
+}
+
+/// Builder for a ParaView Python state script
+#[derive(Debug, Clone, Default)]
+pub struct ParaViewScript {
+    /// Files relative to the script and how to show them
+    datasets: Vec<(String, ScriptLayer)>,
+
+    /// Half-width of the distance color range around zero
+    distance_range: f64,
+
+    /// Names of the codes of the category arrays
+    legend: Option<OutputLegend>,
+}
+
+impl ParaViewScript {
+    /// Script whose distance colormaps span `-distance_range..=distance_range`
+    pub fn new(distance_range: f64) -> Self {
+        Self {
+            datasets: Vec::new(),
+            distance_range,
+            legend: None,
+        }
+    }
+
+    /// Annotate the category arrays with the names from `legend`
+    pub fn set_legend(&mut self, legend: OutputLegend) {
+        self.legend = Some(legend);
+    }
+
+    /// Show contact surfaces from `file` (relative to the script)
+    pub fn add_contact(
+        &mut self,
+        file: impl Into<String>,
+        distance_array: &str,
+        paired_array: &str,
+        paired_min: i32,
+    ) {
+        self.datasets.push((
+            file.into(),
+            ScriptLayer::Contact {
+                distance_array: distance_array.to_string(),
+                paired_array: paired_array.to_string(),
+                paired_min,
+            },
+        ));
+    }
+
+    /// Show context geometry from `file` (relative to the script)
+    pub fn add_context(&mut self, file: impl Into<String>) {
+        self.datasets.push((file.into(), ScriptLayer::Context));
+    }
+
+    /// Number of datasets loaded by the script
+    pub fn len(&self) -> usize {
+        self.datasets.len()
+    }
+
+    /// Whether the script loads no datasets
+    pub fn is_empty(&self) -> bool {
+        self.datasets.is_empty()
+    }
+
+    /// Python source of the script
+    pub fn to_python(&self) -> String {
+        let mut py = String::new();
+        // Writing to a String cannot fail
+        let _ = self.write_python(&mut py);
+        py
+    }
+
+    fn write_python(&self, py: &mut String) -> std::fmt::Result {
+        writeln!(py, "# ParaView state script generated by contact-detector")?;
+        writeln!(
+            py,
+            "# Open with `paraview --script=<this file>` or from ParaView's Python shell"
+        )?;
+        writeln!(py, "# (View > Python Shell > Run Script).")?;
+        writeln!(py, "import os")?;
+        writeln!(py, "from paraview.simple import *")?;
+        writeln!(py)?;
+        writeln!(py, "HERE = os.path.dirname(os.path.abspath(__file__))")?;
+        writeln!(py, "DISTANCE_RANGE = {:?}", self.distance_range)?;
+        writeln!(py)?;
+        writeln!(py, "view = GetActiveViewOrCreate('RenderView')")?;
+        writeln!(py)?;
+        writeln!(py, "def color_by_distance(display, array):")?;
+        writeln!(py, "    ColorBy(display, ('CELLS', array))")?;
+        writeln!(py, "    lut = GetColorTransferFunction(array)")?;
+        writeln!(
+            py,
+            "    lut.ApplyPreset({}, True)",
+            python_str(DISTANCE_PRESET)
+        )?;
+        writeln!(py, "    lut.AutomaticRescaleRangeMode = 'Never'")?;
+        writeln!(
+            py,
+            "    lut.RescaleTransferFunction(-DISTANCE_RANGE, DISTANCE_RANGE)"
+        )?;
+        writeln!(py, "    display.SetScalarBarVisibility(view, True)")?;
+
+        if let Some(legend) = &self.legend {
+            writeln!(py)?;
+            writeln!(py, "# Names of the codes of the category arrays")?;
+            for (array, entries) in &legend.arrays {
+                let annotations: Vec<String> = entries
+                    .iter()
+                    .flat_map(|entry| {
+                        [
+                            python_str(&entry.code.to_string()),
+                            python_str(&entry.label),
+                        ]
+                    })
+                    .collect();
+                writeln!(py, "lut = GetColorTransferFunction({})", python_str(array))?;
+                writeln!(py, "lut.InterpretValuesAsCategories = 1")?;
+                writeln!(py, "lut.Annotations = [{}]", annotations.join(", "))?;
+            }
+        }
+
+        for (idx, (file, layer)) in self.datasets.iter().enumerate() {
+            let n = idx + 1;
+            writeln!(py)?;
+            writeln!(py, "# {}", file)?;
+            writeln!(
+                py,
+                "source_{n} = OpenDataFile(os.path.join(HERE, {}))",
+                python_str(file)
+            )?;
+            writeln!(py, "RenameSource({}, source_{n})", python_str(file))?;
+            writeln!(py, "display_{n} = Show(source_{n}, view)")?;
+            match layer {
+                ScriptLayer::Contact {
+                    distance_array,
+                    paired_array,
+                    paired_min,
+                } => {
+                    let distance = python_str(distance_array);
+                    writeln!(py, "color_by_distance(display_{n}, {distance})")?;
+                    writeln!(py, "display_{n}.Opacity = 0.3")?;
+                    writeln!(
+                        py,
+                        "paired_{n} = Threshold(registrationName={}, Input=source_{n})",
+                        python_str(&format!("{} (paired)", file))
+                    )?;
+                    writeln!(
+                        py,
+                        "paired_{n}.Scalars = ['CELLS', {}]",
+                        python_str(paired_array)
+                    )?;
+                    writeln!(py, "paired_{n}.LowerThreshold = {paired_min}")?;
+                    writeln!(py, "paired_{n}.UpperThreshold = {THRESHOLD_MAX}")?;
+                    writeln!(py, "paired_display_{n} = Show(paired_{n}, view)")?;
+                    writeln!(py, "color_by_distance(paired_display_{n}, {distance})")?;
+                }
+                ScriptLayer::Context => {
+                    writeln!(py, "ColorBy(display_{n}, None)")?;
+                    writeln!(py, "display_{n}.AmbientColor = [0.1, 0.1, 0.1]")?;
+                    writeln!(py, "display_{n}.DiffuseColor = [0.1, 0.1, 0.1]")?;
+                    writeln!(py, "display_{n}.LineWidth = 2.0")?;
+                }
+            }
+        }
+
+        writeln!(py)?;
+        writeln!(py, "view.ResetCamera()")?;
+        writeln!(py, "Render()")?;
+        Ok(())
+    }
+
+    /// Write the script to `path`
+    pub fn write(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_python())?;
+        tracing::info!("Wrote ParaView state script to {:?}", path);
+        Ok(())
+    }
+}
+
+/// Python string literal for `s`
+fn python_str(s: &str) -> String {
+    let mut literal = String::with_capacity(s.len() + 2);
+    literal.push('\'');
+    for c in s.chars() {
+        match c {
+            '\\' => literal.push_str("\\\\"),
+            '\'' => literal.push_str("\\'"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('\'');
+    literal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paraview_script() {
+        let mut script = ParaViewScript::new(0.002);
+        script.add_contact("contact_analysis.vtm", "Distance", "IsPaired", 1);
+        script.add_context("contact_outlines.vtp");
+        let py = script.to_python();
+
+        assert!(py.contains("DISTANCE_RANGE = 0.002"));
+        assert!(py.contains("source_1 = OpenDataFile(os.path.join(HERE, 'contact_analysis.vtm'))"));
+        assert!(py.contains("color_by_distance(display_1, 'Distance')"));
+        assert!(py.contains("paired_1.Scalars = ['CELLS', 'IsPaired']"));
+        assert!(py.contains("paired_1.LowerThreshold = 1"));
+        assert!(py.contains("source_2 = OpenDataFile(os.path.join(HERE, 'contact_outlines.vtp'))"));
+        assert!(!py.contains("paired_2"));
+        assert!(!py.contains("InterpretValuesAsCategories"));
+
+        script.set_legend(OutputLegend::new());
+        let py = script.to_python();
+        assert!(py.contains("lut = GetColorTransferFunction('ContactRole')"));
+        assert!(py.contains("lut.Annotations = ['0', 'master', '1', 'slave']"));
+
+        assert_eq!(python_str("it's a\\b"), "'it\\'s a\\\\b'");
+    }
+}
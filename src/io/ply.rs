@@ -0,0 +1,187 @@
+//! Binary PLY surface writer
+//!
+//! Writes little-endian binary PLY with the surfaces' faces, split into
+//! triangles when a triangulation is given. Every face carries the index of
+//! the surface it belongs to and its area; callers can attach more per-face
+//! scalars (e.g. gap distance) as additional face properties.
+
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::geometry::{face_polygons, Triangulation};
+use crate::mesh::types::SurfaceMesh;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// A named per-face scalar, with one value per face across all surfaces
+/// (in surface order)
+#[derive(Debug, Clone, Copy)]
+pub struct FaceScalar<'a> {
+    /// Property name written to the PLY header
+    pub name: &'a str,
+
+    /// One value per face
+    pub values: &'a [f64],
+}
+
+/// Write surfaces to a binary PLY file
+///
+/// Surface names are recorded as header comments in `surface_id` order.
+/// When triangulating, both triangles of a quad carry the quad's values
+/// (including its area).
+#[tracing::instrument(skip_all, fields(count = surfaces.len()))]
+pub fn write_surfaces_to_ply(
+    surfaces: &[SurfaceMesh],
+    face_scalars: &[FaceScalar],
+    output_path: &Path,
+    triangulation: Option<Triangulation>,
+) -> Result<()> {
+    let num_faces: usize = surfaces.iter().map(|s| s.num_faces()).sum();
+    for scalar in face_scalars {
+        if scalar.values.len() != num_faces {
+            return Err(ContactDetectorError::ConfigError(format!(
+                "PLY face property '{}' has {} values for {} faces",
+                scalar.name,
+                scalar.values.len(),
+                num_faces
+            )));
+        }
+    }
+
+    tracing::info!(
+        "Writing {} surfaces ({} faces) to PLY {:?}",
+        surfaces.len(),
+        num_faces,
+        output_path
+    );
+
+    // Compact each surface's referenced nodes into one vertex list. Each
+    // output face records its source face index for the scalar lookup.
+    let mut vertices = Vec::new();
+    let mut faces = Vec::with_capacity(num_faces);
+    let mut face_offset = 0;
+    for (surface_id, surface) in surfaces.iter().enumerate() {
+        let mut local_ids = HashMap::new();
+        for (face_idx, face) in surface.faces.iter().enumerate() {
+            for polygon in face_polygons(face, &surface.nodes, triangulation) {
+                let indices: Vec<u32> = polygon
+                    .iter()
+                    .map(|&node_id| {
+                        *local_ids.entry(node_id).or_insert_with(|| {
+                            vertices.push(surface.nodes[node_id]);
+                            vertices.len() - 1
+                        }) as u32
+                    })
+                    .collect();
+                faces.push((
+                    indices,
+                    surface_id as i32,
+                    surface.face_areas[face_idx],
+                    face_offset + face_idx,
+                ));
+            }
+        }
+        face_offset += surface.num_faces();
+    }
+
+    let mut out = BufWriter::new(File::create(output_path)?);
+
+    writeln!(out, "ply")?;
+    writeln!(out, "format binary_little_endian 1.0")?;
+    writeln!(out, "comment Surfaces exported from contact-detector")?;
+    for (surface_id, surface) in surfaces.iter().enumerate() {
+        writeln!(out, "comment surface {} {}", surface_id, surface.part_name)?;
+    }
+    writeln!(out, "element vertex {}", vertices.len())?;
+    writeln!(out, "property double x")?;
+    writeln!(out, "property double y")?;
+    writeln!(out, "property double z")?;
+    writeln!(out, "element face {}", faces.len())?;
+    writeln!(out, "property list uchar uint vertex_indices")?;
+    writeln!(out, "property int surface_id")?;
+    writeln!(out, "property double area")?;
+    for scalar in face_scalars {
+        writeln!(out, "property double {}", scalar.name)?;
+    }
+    writeln!(out, "end_header")?;
+
+    for p in &vertices {
+        for c in [p.x, p.y, p.z] {
+            out.write_all(&c.to_le_bytes())?;
+        }
+    }
+
+    for (indices, surface_id, area, face_idx) in &faces {
+        out.write_all(&[indices.len() as u8])?;
+        for index in indices {
+            out.write_all(&index.to_le_bytes())?;
+        }
+        out.write_all(&surface_id.to_le_bytes())?;
+        out.write_all(&area.to_le_bytes())?;
+        for scalar in face_scalars {
+            out.write_all(&scalar.values[*face_idx].to_le_bytes())?;
+        }
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::types::{Point, QuadFace, Vec3};
+
+    #[test]
+    fn test_write_ply() {
+        let mut surface = SurfaceMesh::new("Block1:patch_0".to_string());
+        surface.nodes = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+        ];
+        surface.faces.push(QuadFace::new([0, 1, 2, 3]));
+        surface.face_normals.push(Vec3::new(0.0, 0.0, 1.0));
+        surface.face_centroids.push(Point::new(0.5, 0.5, 0.0));
+        surface.face_areas.push(1.0);
+
+        let path = std::env::temp_dir().join("test_surfaces.ply");
+        let gap = [0.25];
+        let scalars = [FaceScalar {
+            name: "gap",
+            values: &gap,
+        }];
+        write_surfaces_to_ply(&[surface.clone()], &scalars, &path, None).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        let header_end = bytes
+            .windows(11)
+            .position(|w| w == b"end_header\n")
+            .unwrap()
+            + 11;
+        let header = std::str::from_utf8(&bytes[..header_end]).unwrap();
+        assert!(header.contains("property double gap"));
+        assert!(header.contains("comment surface 0 Block1:patch_0"));
+
+        // 4 vertices * 24 bytes + 1 face * (1 + 16 + 4 + 8 + 8) bytes
+        assert_eq!(bytes.len() - header_end, 4 * 24 + 37);
+        assert_eq!(&bytes[bytes.len() - 8..], &0.25f64.to_le_bytes());
+
+        // Triangles: 2 faces * (1 + 12 + 4 + 8 + 8) bytes, scalars duplicated
+        let tri = Some(Triangulation::ShortestDiagonal);
+        write_surfaces_to_ply(&[surface.clone()], &scalars, &path, tri).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(bytes.len() - header_end, 4 * 24 + 2 * 33);
+        assert_eq!(&bytes[bytes.len() - 8..], &0.25f64.to_le_bytes());
+
+        // Mismatched scalar length is rejected
+        let empty = [FaceScalar {
+            name: "x",
+            values: &[],
+        }];
+        assert!(write_surfaces_to_ply(&[surface], &empty, &path, None).is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
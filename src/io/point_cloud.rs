@@ -0,0 +1,407 @@
+//! Point cloud reader (CSV, PLY) and deviation writer
+//!
+//! CSV files hold one point per line as `x,y,z` (commas, semicolons or
+//! whitespace separate the values; extra columns are ignored and a header
+//! line is skipped). PLY files may be ASCII or binary; only the `x`, `y` and
+//! `z` properties of the `vertex` element are read.
+
+use crate::contact::PointDeviation;
+use crate::error::{ContactDetectorError, Result};
+use crate::format::FloatFormat;
+use crate::io::vtk_version::VtkVersion;
+use crate::mesh::types::Point;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use vtkio::model::*;
+
+/// Read a point cloud from a CSV or PLY file
+#[tracing::instrument(skip_all, fields(path = %path.as_ref().display()))]
+pub fn read_point_cloud<P: AsRef<Path>>(path: P) -> Result<Vec<Point>> {
+    let path = path.as_ref();
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+
+    let points = match extension.as_deref() {
+        Some("ply") => parse_ply(&std::fs::read(path)?)?,
+        Some("csv") | Some("txt") | Some("xyz") => parse_csv(&std::fs::read_to_string(path)?)?,
+        _ => {
+            return Err(ContactDetectorError::ConfigError(format!(
+                "Unsupported point cloud file '{}' (expected .csv, .txt, .xyz or .ply)",
+                path.display()
+            )))
+        }
+    };
+
+    tracing::info!("Read {} points", points.len());
+
+    Ok(points)
+}
+
+fn parse_csv(text: &str) -> Result<Vec<Point>> {
+    let mut points = Vec::new();
+
+    for (line_no, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let coords: std::result::Result<Vec<f64>, _> = line
+            .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+            .filter(|t| !t.is_empty())
+            .take(3)
+            .map(|t| t.parse::<f64>())
+            .collect();
+
+        match coords {
+            Ok(c) if c.len() == 3 => points.push(Point::new(c[0], c[1], c[2])),
+            // A header line before the first point
+            Err(_) if points.is_empty() => continue,
+            _ => {
+                return Err(ContactDetectorError::ConfigError(format!(
+                    "Failed to parse point cloud: line {}: expected 3 coordinates",
+                    line_no + 1
+                )))
+            }
+        }
+    }
+
+    Ok(points)
+}
+
+/// Storage format of a PLY file body
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+/// A PLY element declared in the header
+#[derive(Debug)]
+struct PlyElement {
+    name: String,
+    count: usize,
+    /// Scalar properties as (name, type); `None` type marks a list property
+    properties: Vec<(String, Option<String>)>,
+}
+
+fn ply_error(message: impl Into<String>) -> ContactDetectorError {
+    ContactDetectorError::ConfigError(format!("Failed to parse PLY file: {}", message.into()))
+}
+
+/// Size in bytes of a PLY scalar type
+fn ply_type_size(ty: &str) -> Option<usize> {
+    match ty {
+        "char" | "int8" | "uchar" | "uint8" => Some(1),
+        "short" | "int16" | "ushort" | "uint16" => Some(2),
+        "int" | "int32" | "uint" | "uint32" | "float" | "float32" => Some(4),
+        "double" | "float64" => Some(8),
+        _ => None,
+    }
+}
+
+/// Decode one binary PLY scalar as f64
+fn ply_read_scalar(bytes: &[u8], ty: &str, format: PlyFormat) -> f64 {
+    macro_rules! decode {
+        ($t:ty) => {{
+            let raw = bytes.try_into().expect("scalar slice has the type's size");
+            if format == PlyFormat::BinaryBigEndian {
+                <$t>::from_be_bytes(raw) as f64
+            } else {
+                <$t>::from_le_bytes(raw) as f64
+            }
+        }};
+    }
+
+    match ty {
+        "char" | "int8" => decode!(i8),
+        "uchar" | "uint8" => decode!(u8),
+        "short" | "int16" => decode!(i16),
+        "ushort" | "uint16" => decode!(u16),
+        "int" | "int32" => decode!(i32),
+        "uint" | "uint32" => decode!(u32),
+        "float" | "float32" => decode!(f32),
+        _ => decode!(f64),
+    }
+}
+
+fn parse_ply(bytes: &[u8]) -> Result<Vec<Point>> {
+    const END_HEADER: &[u8] = b"end_header";
+    let header_end = bytes
+        .windows(END_HEADER.len())
+        .position(|w| w == END_HEADER)
+        .ok_or_else(|| ply_error("missing end_header"))?;
+    let body_start = bytes[header_end..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map(|i| header_end + i + 1)
+        .unwrap_or(bytes.len());
+    let header = std::str::from_utf8(&bytes[..header_end])
+        .map_err(|_| ply_error("header is not valid text"))?;
+
+    if !header.starts_with("ply") {
+        return Err(ply_error("missing 'ply' magic"));
+    }
+
+    let mut format = None;
+    let mut elements: Vec<PlyElement> = Vec::new();
+    for line in header.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.as_slice() {
+            ["format", "ascii", ..] => format = Some(PlyFormat::Ascii),
+            ["format", "binary_little_endian", ..] => format = Some(PlyFormat::BinaryLittleEndian),
+            ["format", "binary_big_endian", ..] => format = Some(PlyFormat::BinaryBigEndian),
+            ["element", name, count] => elements.push(PlyElement {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| ply_error(format!("invalid element count '{}'", count)))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", _, _, name] => elements
+                .last_mut()
+                .ok_or_else(|| ply_error("property before element"))?
+                .properties
+                .push((name.to_string(), None)),
+            ["property", ty, name] => {
+                if ply_type_size(ty).is_none() {
+                    return Err(ply_error(format!("unknown property type '{}'", ty)));
+                }
+                elements
+                    .last_mut()
+                    .ok_or_else(|| ply_error("property before element"))?
+                    .properties
+                    .push((name.to_string(), Some(ty.to_string())));
+            }
+            _ => {}
+        }
+    }
+    let format = format.ok_or_else(|| ply_error("missing format line"))?;
+
+    // Elements before the vertices must be skipped, which is only possible
+    // without list properties in binary files
+    let body = &bytes[body_start..];
+    let mut ascii_lines = std::str::from_utf8(body)
+        .ok()
+        .filter(|_| format == PlyFormat::Ascii)
+        .map(|text| text.lines());
+    let mut offset = 0;
+
+    for element in &elements {
+        let has_list = element.properties.iter().any(|(_, ty)| ty.is_none());
+        let is_vertex = element.name == "vertex";
+
+        if is_vertex && has_list {
+            return Err(ply_error("list properties on vertices are not supported"));
+        }
+
+        let coord_columns: Vec<usize> = ["x", "y", "z"]
+            .iter()
+            .filter_map(|axis| element.properties.iter().position(|(name, _)| name == axis))
+            .collect();
+        if is_vertex && coord_columns.len() != 3 {
+            return Err(ply_error("vertex element lacks x, y and z properties"));
+        }
+
+        match (format, ascii_lines.as_mut()) {
+            (PlyFormat::Ascii, Some(lines)) => {
+                if !is_vertex {
+                    lines.nth(element.count.saturating_sub(1));
+                    continue;
+                }
+                let mut points = Vec::with_capacity(element.count);
+                for _ in 0..element.count {
+                    let values: Vec<&str> = lines
+                        .next()
+                        .ok_or_else(|| ply_error("unexpected end of file"))?
+                        .split_whitespace()
+                        .collect();
+                    let coord = |i: usize| -> Result<f64> {
+                        values
+                            .get(coord_columns[i])
+                            .and_then(|v| v.parse().ok())
+                            .ok_or_else(|| ply_error("invalid vertex coordinate"))
+                    };
+                    points.push(Point::new(coord(0)?, coord(1)?, coord(2)?));
+                }
+                return Ok(points);
+            }
+            (PlyFormat::Ascii, None) => return Err(ply_error("ASCII body is not valid text")),
+            _ => {
+                if has_list {
+                    return Err(ply_error(format!(
+                        "cannot skip element '{}' with list properties before the vertices",
+                        element.name
+                    )));
+                }
+
+                let types: Vec<&str> = element
+                    .properties
+                    .iter()
+                    .map(|(_, ty)| ty.as_deref().unwrap_or_default())
+                    .collect();
+                let sizes: Vec<usize> = types.iter().map(|ty| ply_type_size(ty).unwrap()).collect();
+                let stride: usize = sizes.iter().sum();
+                let end = offset + stride * element.count;
+                if end > body.len() {
+                    return Err(ply_error("unexpected end of file"));
+                }
+
+                if !is_vertex {
+                    offset = end;
+                    continue;
+                }
+
+                let starts: Vec<usize> = sizes
+                    .iter()
+                    .scan(0, |start, size| {
+                        let current = *start;
+                        *start += size;
+                        Some(current)
+                    })
+                    .collect();
+                let points = body[offset..end]
+                    .chunks_exact(stride)
+                    .map(|record| {
+                        let coord = |i: usize| {
+                            let column = coord_columns[i];
+                            let start = starts[column];
+                            ply_read_scalar(
+                                &record[start..start + sizes[column]],
+                                types[column],
+                                format,
+                            )
+                        };
+                        Point::new(coord(0), coord(1), coord(2))
+                    })
+                    .collect();
+                return Ok(points);
+            }
+        }
+    }
+
+    Err(ply_error("no vertex element"))
+}
+
+/// Write point deviations to CSV (`.csv`) or to a VTU point cloud
+///
+/// Unmatched points are written with a NaN deviation and a face index of -1;
+/// `format` applies to the CSV values.
+#[tracing::instrument(skip_all, fields(points = points.len()))]
+pub fn write_point_deviations(
+    points: &[Point],
+    deviations: &[Option<PointDeviation>],
+    output_path: &Path,
+    vtk_version: Option<VtkVersion>,
+    format: FloatFormat,
+) -> Result<()> {
+    let values: Vec<f64> = deviations
+        .iter()
+        .map(|d| d.map_or(f64::NAN, |d| d.deviation))
+        .collect();
+    let face_ids: Vec<i32> = deviations
+        .iter()
+        .map(|d| d.map_or(-1, |d| d.face_index as i32))
+        .collect();
+
+    if output_path.extension().and_then(|e| e.to_str()) == Some("csv") {
+        let mut out = BufWriter::new(File::create(output_path)?);
+        writeln!(out, "x,y,z,deviation,face_id")?;
+        for ((p, value), face_id) in points.iter().zip(&values).zip(&face_ids) {
+            writeln!(
+                out,
+                "{},{},{},{},{}",
+                format.show(p.x),
+                format.show(p.y),
+                format.show(p.z),
+                format.show(*value),
+                face_id
+            )?;
+        }
+        out.flush()?;
+        return Ok(());
+    }
+
+    let version = VtkVersion::for_path(vtk_version, output_path)?;
+    let mut ugrid = UnstructuredGridPiece {
+        points: IOBuffer::F64(points.iter().flat_map(|p| [p.x, p.y, p.z]).collect()),
+        cells: Cells {
+            cell_verts: VertexNumbers::XML {
+                connectivity: (0..points.len() as u64).collect(),
+                offsets: (1..=points.len() as u64).collect(),
+            },
+            types: vec![CellType::Vertex; points.len()],
+        },
+        data: Attributes::new(),
+    };
+
+    ugrid.data.point.push(Attribute::DataArray(DataArray {
+        name: "deviation".into(),
+        elem: ElementType::Scalars {
+            num_comp: 1,
+            lookup_table: None,
+        },
+        data: IOBuffer::F64(values),
+    }));
+
+    ugrid.data.point.push(Attribute::DataArray(DataArray {
+        name: "face_id".into(),
+        elem: ElementType::Scalars {
+            num_comp: 1,
+            lookup_table: None,
+        },
+        data: IOBuffer::I32(face_ids),
+    }));
+
+    let vtk = Vtk {
+        version: version.into(),
+        title: "Point deviation".to_string(),
+        byte_order: ByteOrder::LittleEndian,
+        data: DataSet::UnstructuredGrid {
+            pieces: vec![Piece::Inline(Box::new(ugrid))],
+            meta: None,
+        },
+        file_path: None,
+    };
+
+    vtk.export(output_path)
+        .map_err(|e| ContactDetectorError::VtkError(format!("Failed to write VTU file: {}", e)))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let points = parse_csv("x,y,z,intensity\n0,0,1,5\n1.5 2 3\n\n# comment\n4;5;6\n").unwrap();
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[1], Point::new(1.5, 2.0, 3.0));
+        assert!(parse_csv("0,0,1\n1,2\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_ply() {
+        let ascii = b"ply\nformat ascii 1.0\nelement vertex 2\nproperty float x\nproperty float y\nproperty float z\nproperty uchar red\nend_header\n0 0 1 255\n1 2 3 0\n";
+        let points = parse_ply(ascii).unwrap();
+        assert_eq!(
+            points,
+            vec![Point::new(0.0, 0.0, 1.0), Point::new(1.0, 2.0, 3.0)]
+        );
+
+        // Binary with an extra leading property
+        let mut binary = b"ply\nformat binary_little_endian 1.0\nelement vertex 1\nproperty uchar flag\nproperty double x\nproperty double y\nproperty float z\nend_header\n".to_vec();
+        binary.push(7);
+        binary.extend_from_slice(&1.5f64.to_le_bytes());
+        binary.extend_from_slice(&(-2.0f64).to_le_bytes());
+        binary.extend_from_slice(&0.25f32.to_le_bytes());
+        let points = parse_ply(&binary).unwrap();
+        assert_eq!(points, vec![Point::new(1.5, -2.0, 0.25)]);
+    }
+}
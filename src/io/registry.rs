@@ -0,0 +1,276 @@
+//! Pluggable mesh formats
+//!
+//! Every volume mesh format implements [`MeshReader`] and/or [`MeshWriter`]
+//! and is registered in a [`FormatRegistry`], which picks the format of a
+//! file from its extension (ignoring a `.gz`/`.zst` compression suffix) or,
+//! for reading, from its leading bytes. Formats of other crates are added with
+//! [`FormatRegistry::register_reader`] / [`FormatRegistry::register_writer`];
+//! later registrations take precedence, so a built-in format can also be
+//! replaced (e.g. by the same format with different write options).
+
+use crate::error::{ContactDetectorError, Result};
+use crate::io::compression::{open_reader, Compression};
+use crate::mesh::Mesh;
+use crate::warnings::Warnings;
+use std::io::Read;
+use std::path::Path;
+
+/// Number of leading (decompressed) bytes passed to [`MeshReader::sniff`]
+pub const SNIFF_LEN: usize = 16;
+
+/// A mesh file format that can be read
+pub trait MeshReader: Send + Sync {
+    /// Short format name, e.g. `exodus`
+    fn name(&self) -> &str;
+
+    /// Lowercase file extensions of the format, without the dot
+    fn extensions(&self) -> &[&str];
+
+    /// Whether a file starting with `header` is in this format, for files
+    /// whose extension no reader claims
+    fn sniff(&self, _header: &[u8]) -> bool {
+        false
+    }
+
+    /// Read a mesh, with any non-fatal problems
+    fn read(&self, path: &Path) -> Result<(Mesh, Warnings)>;
+}
+
+/// A mesh file format that can be written
+pub trait MeshWriter: Send + Sync {
+    /// Short format name, e.g. `exodus`
+    fn name(&self) -> &str;
+
+    /// Lowercase file extensions of the format, without the dot
+    fn extensions(&self) -> &[&str];
+
+    /// Write a mesh
+    fn write(&self, mesh: &Mesh, path: &Path) -> Result<()>;
+}
+
+/// Mesh formats available for reading and writing
+#[derive(Default)]
+pub struct FormatRegistry {
+    readers: Vec<Box<dyn MeshReader>>,
+    writers: Vec<Box<dyn MeshWriter>>,
+}
+
+impl FormatRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the formats built into this crate: JSON,
+    /// binary (`.cdmesh`) and, with the `exodus` feature, Exodus II
+    pub fn with_builtin_formats() -> Self {
+        let mut registry = Self::new();
+        registry.register_reader(crate::io::json::JsonMeshFormat);
+        registry.register_writer(crate::io::json::JsonMeshFormat);
+        registry.register_reader(crate::io::binary::BinaryMeshFormat);
+        registry.register_writer(crate::io::binary::BinaryMeshFormat);
+        #[cfg(feature = "exodus")]
+        {
+            registry.register_reader(crate::io::exodus::ExodusFormat::default());
+            registry.register_writer(crate::io::exodus::ExodusFormat::default());
+        }
+        registry
+    }
+
+    /// Add a reader, taking precedence over those registered before
+    pub fn register_reader<R: MeshReader + 'static>(&mut self, reader: R) {
+        self.readers.insert(0, Box::new(reader));
+    }
+
+    /// Add a writer, taking precedence over those registered before
+    pub fn register_writer<W: MeshWriter + 'static>(&mut self, writer: W) {
+        self.writers.insert(0, Box::new(writer));
+    }
+
+    /// Names of the formats that can be read
+    pub fn reader_names(&self) -> Vec<&str> {
+        self.readers.iter().map(|r| r.name()).collect()
+    }
+
+    /// Names of the formats that can be written
+    pub fn writer_names(&self) -> Vec<&str> {
+        self.writers.iter().map(|w| w.name()).collect()
+    }
+
+    /// Reader for a file, chosen by extension and otherwise by content
+    pub fn reader_for(&self, path: &Path) -> Option<&dyn MeshReader> {
+        if let Some(extension) = format_extension(path) {
+            let by_extension = self
+                .readers
+                .iter()
+                .find(|r| r.extensions().contains(&extension.as_str()));
+            if let Some(reader) = by_extension {
+                return Some(reader.as_ref());
+            }
+        }
+
+        let header = read_header(path)?;
+        self.readers
+            .iter()
+            .find(|r| r.sniff(&header))
+            .map(|r| r.as_ref())
+    }
+
+    /// Writer for a file, chosen by extension
+    pub fn writer_for(&self, path: &Path) -> Option<&dyn MeshWriter> {
+        let extension = format_extension(path)?;
+        self.writers
+            .iter()
+            .find(|w| w.extensions().contains(&extension.as_str()))
+            .map(|w| w.as_ref())
+    }
+
+    /// Read a mesh in whichever registered format the file is in
+    pub fn read<P: AsRef<Path>>(&self, path: P) -> Result<(Mesh, Warnings)> {
+        let path = path.as_ref();
+        let reader = self.reader_for(path).ok_or_else(|| {
+            ContactDetectorError::ConfigError(format!(
+                "Unrecognized mesh format: {} (readable formats: {}){}",
+                path.display(),
+                self.reader_names().join(", "),
+                exodus_hint()
+            ))
+        })?;
+        tracing::debug!("Reading {:?} as {}", path, reader.name());
+        reader.read(path)
+    }
+
+    /// Write a mesh in the format given by the path's extension
+    pub fn write<P: AsRef<Path>>(&self, mesh: &Mesh, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let writer = self.writer_for(path).ok_or_else(|| {
+            let extensions: Vec<String> = self
+                .writers
+                .iter()
+                .flat_map(|w| w.extensions().iter().map(|e| format!(".{}", e)))
+                .collect();
+            ContactDetectorError::ConfigError(format!(
+                "No mesh writer for {} (supported extensions: {}){}",
+                path.display(),
+                extensions.join(", "),
+                exodus_hint()
+            ))
+        })?;
+        tracing::debug!("Writing {:?} as {}", path, writer.name());
+        writer.write(mesh, path)
+    }
+}
+
+/// Lowercase extension naming the format of a path, below any compression
+/// suffix (`model.json.gz` → `json`)
+fn format_extension(path: &Path) -> Option<String> {
+    let path = match Compression::from_path(path) {
+        Compression::None => path.to_path_buf(),
+        _ => path.with_extension(""),
+    };
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+}
+
+/// Leading bytes of a file, decompressed; `None` if it can't be read
+fn read_header(path: &Path) -> Option<Vec<u8>> {
+    let mut header = Vec::with_capacity(SNIFF_LEN);
+    open_reader(path)
+        .ok()?
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut header)
+        .ok()?;
+    Some(header)
+}
+
+fn exodus_hint() -> &'static str {
+    if cfg!(feature = "exodus") {
+        ""
+    } else {
+        ". Exodus support not compiled in. Install libhdf5-dev and libnetcdf-dev, then rebuild with --features exodus"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::{HexElement, Point};
+
+    /// A format of another crate: writes only the node count
+    struct CountFormat;
+
+    impl MeshReader for CountFormat {
+        fn name(&self) -> &str {
+            "count"
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["count"]
+        }
+
+        fn sniff(&self, header: &[u8]) -> bool {
+            header.starts_with(b"nodes=")
+        }
+
+        fn read(&self, path: &Path) -> Result<(Mesh, Warnings)> {
+            let text = std::fs::read_to_string(path)?;
+            let count: usize = text.trim_start_matches("nodes=").trim().parse().unwrap();
+            let mut mesh = Mesh::new();
+            mesh.nodes = vec![Point::new(0.0, 0.0, 0.0); count];
+            Ok((mesh, Warnings::new()))
+        }
+    }
+
+    impl MeshWriter for CountFormat {
+        fn name(&self) -> &str {
+            "count"
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["count"]
+        }
+
+        fn write(&self, mesh: &Mesh, path: &Path) -> Result<()> {
+            std::fs::write(path, format!("nodes={}", mesh.num_nodes()))?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_registry_dispatch() {
+        let dir = std::env::temp_dir().join("test_format_registry");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut mesh = Mesh::new();
+        mesh.nodes = (0..8)
+            .map(|i| Point::new((i & 1) as f64, ((i >> 1) & 1) as f64, (i >> 2) as f64))
+            .collect();
+        mesh.elements = vec![HexElement::new([0, 1, 3, 2, 4, 5, 7, 6])];
+        mesh.element_blocks.insert("Block1".to_string(), vec![0]);
+
+        let mut registry = FormatRegistry::with_builtin_formats();
+        for name in ["mesh.json", "mesh.JSON.gz", "mesh.cdmesh"] {
+            let path = dir.join(name);
+            registry.write(&mesh, &path).unwrap();
+            let (loaded, _) = registry.read(&path).unwrap();
+            assert_eq!(loaded.num_elements(), 1);
+        }
+
+        // Files with a misleading extension are recognized by content
+        std::fs::copy(dir.join("mesh.cdmesh"), dir.join("mesh.bin")).unwrap();
+        let reader = registry.reader_for(&dir.join("mesh.bin")).unwrap();
+        assert_eq!(reader.name(), "cdmesh");
+
+        assert!(registry.write(&mesh, dir.join("mesh.count")).is_err());
+        registry.register_reader(CountFormat);
+        registry.register_writer(CountFormat);
+        registry.write(&mesh, dir.join("mesh.count")).unwrap();
+        std::fs::copy(dir.join("mesh.count"), dir.join("mesh.txt")).unwrap();
+        let (loaded, _) = registry.read(dir.join("mesh.txt")).unwrap();
+        assert_eq!(loaded.num_nodes(), 8);
+
+        assert!(registry.read(dir.join("missing.xyz")).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
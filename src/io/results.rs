@@ -0,0 +1,180 @@
+//! Saved contact detection results
+//!
+//! Detection results are stored as JSON (gzip or zstd compressed if the path
+//! ends in `.gz` or `.zst`) so that they can be reloaded, given new metrics
+//! and exported again without rerunning detection. Face indices refer to the
+//! surfaces extracted from the source mesh, which extraction reproduces
+//! exactly; [`ContactResults::check_faces`] catches results loaded against
+//! the wrong surfaces.
+//!
+//! The schema is versioned through a top-level `version` field; files from a
+//! newer version are rejected.
+
+use crate::contact::types::ContactResults;
+use crate::error::{ContactDetectorError, Result};
+use crate::io::compression::{open_reader, CompressedWriter};
+use crate::mesh::types::SurfaceMesh;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Current contact results schema version
+pub const CONTACT_RESULTS_VERSION: u32 = 1;
+
+/// Contact results of one or more surface pairs as saved to disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContactResultsFile {
+    /// Schema version ([`CONTACT_RESULTS_VERSION`] when written)
+    pub version: u32,
+
+    /// Version of contact-detector that wrote the file
+    pub generator: String,
+
+    /// Mesh file the results were detected on, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mesh_file: Option<String>,
+
+    /// Results of each surface pair
+    pub results: Vec<ContactResults>,
+}
+
+impl ContactResultsFile {
+    /// File holding the given results
+    pub fn new(results: Vec<ContactResults>) -> Self {
+        Self {
+            version: CONTACT_RESULTS_VERSION,
+            generator: format!("contact-detector {}", env!("CARGO_PKG_VERSION")),
+            mesh_file: None,
+            results,
+        }
+    }
+
+    /// Results of the named surface pair, in the order detected
+    pub fn find(&self, surface_a: &str, surface_b: &str) -> Option<&ContactResults> {
+        self.results
+            .iter()
+            .find(|r| r.surface_a_name == surface_a && r.surface_b_name == surface_b)
+    }
+
+    /// Export to a JSON file (compressed if the path ends in `.gz` or `.zst`)
+    pub fn export<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut writer = CompressedWriter::create(path.as_ref())?;
+        serde_json::to_writer(&mut writer, self).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to write contact results: {}", e))
+        })?;
+        writer.finish()
+    }
+
+    /// Load from a JSON file, plain or compressed
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let reader = open_reader(path.as_ref())?;
+        let file: Self = serde_json::from_reader(reader).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to parse contact results: {}", e))
+        })?;
+        if file.version > CONTACT_RESULTS_VERSION {
+            return Err(ContactDetectorError::ConfigError(format!(
+                "Unsupported contact results version {} (newest supported: {})",
+                file.version, CONTACT_RESULTS_VERSION
+            )));
+        }
+        Ok(file)
+    }
+}
+
+impl ContactResults {
+    /// Check that the face indices fit the given surfaces, e.g. before
+    /// computing metrics of reloaded results
+    pub fn check_faces(&self, surface_a: &SurfaceMesh, surface_b: &SurfaceMesh) -> Result<()> {
+        let (faces_a, faces_b) = (surface_a.num_faces(), surface_b.num_faces());
+        let out_of_range = self
+            .pairs
+            .iter()
+            .any(|p| p.surface_a_face_id >= faces_a || p.surface_b_face_id >= faces_b)
+            || self.unpaired_a.iter().any(|&f| f >= faces_a)
+            || self.unpaired_b.iter().any(|&f| f >= faces_b);
+        if out_of_range {
+            return Err(ContactDetectorError::ConfigError(format!(
+                "Contact results of '{}' ↔ '{}' do not match surfaces '{}' ({} faces) and '{}' ({} faces)",
+                self.surface_a_name,
+                self.surface_b_name,
+                surface_a.part_name,
+                faces_a,
+                surface_b.part_name,
+                faces_b
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::types::{ContactCriteria, ContactPair};
+    use crate::mesh::types::{Point, QuadFace, Vec3};
+
+    fn surface(name: &str, num_faces: usize) -> SurfaceMesh {
+        let mut surface = SurfaceMesh::new(name.to_string());
+        for _ in 0..num_faces {
+            surface.faces.push(QuadFace::new([0, 0, 0, 0]));
+            surface.face_normals.push(Vec3::z());
+            surface.face_centroids.push(Point::origin());
+            surface.face_areas.push(1.0);
+        }
+        surface
+    }
+
+    #[test]
+    fn test_results_round_trip() {
+        let mut criteria = ContactCriteria::new(0.01, 0.002, 30.0);
+        criteria.min_paired_area = Some(0.5);
+        let mut results = ContactResults::new("A".to_string(), "B".to_string(), criteria);
+        results.pairs.push(ContactPair {
+            surface_a_face_id: 1,
+            surface_b_face_id: 0,
+            distance: -0.001,
+            normal_angle: 178.5,
+            contact_point: Point::new(0.5, 0.25, 1.0),
+        });
+        results.unpaired_a.push(0);
+        results.unpaired_b.push(1);
+        results.rejected_facing = 3;
+
+        let dir = std::env::temp_dir();
+        for name in ["test_contact_results.json", "test_contact_results.json.gz"] {
+            let path = dir.join(name);
+            ContactResultsFile::new(vec![results.clone()])
+                .export(&path)
+                .unwrap();
+            let loaded = ContactResultsFile::load(&path).unwrap();
+            assert_eq!(loaded.version, CONTACT_RESULTS_VERSION);
+
+            let reloaded = loaded.find("A", "B").unwrap();
+            assert!(loaded.find("B", "A").is_none());
+            assert_eq!(reloaded.pairs.len(), 1);
+            let pair = &reloaded.pairs[0];
+            assert_eq!((pair.surface_a_face_id, pair.surface_b_face_id), (1, 0));
+            assert_eq!(pair.distance, -0.001);
+            assert_eq!(pair.contact_point, Point::new(0.5, 0.25, 1.0));
+            assert_eq!(reloaded.unpaired_a, vec![0]);
+            assert_eq!(reloaded.rejected_facing, 3);
+            assert_eq!(reloaded.criteria.max_normal_angle, 30.0);
+            assert_eq!(reloaded.criteria.min_paired_area, Some(0.5));
+
+            reloaded
+                .check_faces(&surface("A", 2), &surface("B", 2))
+                .unwrap();
+            assert!(reloaded
+                .check_faces(&surface("A", 2), &surface("B", 1))
+                .is_err());
+            assert!(reloaded
+                .check_faces(&surface("A", 1), &surface("B", 2))
+                .is_err());
+            std::fs::remove_file(&path).ok();
+        }
+
+        let path = dir.join("test_contact_results_future.json");
+        std::fs::write(&path, r#"{"version": 99, "generator": "", "results": []}"#).unwrap();
+        assert!(ContactResultsFile::load(&path).is_err());
+        std::fs::remove_file(&path).ok();
+    }
+}
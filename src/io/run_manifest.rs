@@ -0,0 +1,264 @@
+//! Provenance record of a run
+//!
+//! `run_manifest.json` is written into the output directory at the end of a
+//! run so that results can be traced back to exactly what produced them: the
+//! tool version, the command line, every resolved parameter, the SHA-256 of
+//! each input file, and the size, SHA-256 and write time of every file the run
+//! produced. Files in the output directory that the run didn't touch (e.g.
+//! kept by `--skip-existing`) are not listed.
+
+use crate::contact::CACHE_DIR_NAME;
+use crate::error::{ContactDetectorError, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Version of the run manifest format
+pub const RUN_MANIFEST_VERSION: u32 = 1;
+
+/// File name of the run manifest in the output directory
+pub const RUN_MANIFEST_FILE_NAME: &str = "run_manifest.json";
+
+/// Tolerance when comparing file modification times with the start of a run
+const MTIME_SLACK: Duration = Duration::from_secs(1);
+
+/// An input file of a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputRecord {
+    /// Path as given
+    pub path: String,
+
+    /// What the file was used for, e.g. `mesh` or `config`
+    pub role: String,
+
+    pub bytes: u64,
+    pub sha256: String,
+}
+
+/// A file produced by a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtifactRecord {
+    /// Path relative to the output directory
+    pub path: String,
+
+    pub bytes: u64,
+    pub sha256: String,
+
+    /// When the file was last written (RFC 3339)
+    pub written: String,
+
+    /// Seconds from the start of the run until the file was written
+    pub seconds_after_start: f64,
+}
+
+/// Tool version, parameters, inputs and outputs of one run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunManifest {
+    /// Run manifest format version
+    pub version: u32,
+
+    /// Tool name and version, e.g. `contact-detector 0.1.0`
+    pub tool: String,
+
+    /// Subcommand that was run
+    pub command: String,
+
+    /// Full command line, program name first
+    pub command_line: Vec<String>,
+
+    /// All parameters after defaults were applied
+    pub parameters: serde_json::Value,
+
+    /// Configuration as resolved by the run (e.g. a loaded config file or the
+    /// effective detection criteria)
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub resolved_config: serde_json::Value,
+
+    /// Start and end of the run (RFC 3339) and its duration in seconds
+    pub started: String,
+    pub finished: String,
+    pub duration_seconds: f64,
+
+    pub inputs: Vec<InputRecord>,
+    pub artifacts: Vec<ArtifactRecord>,
+
+    #[serde(skip, default = "Instant::now")]
+    start_instant: Instant,
+
+    #[serde(skip, default = "SystemTime::now")]
+    start_time: SystemTime,
+}
+
+impl RunManifest {
+    /// Start recording a run of `command` with the given parameters
+    pub fn new(command: &str, parameters: serde_json::Value) -> Self {
+        Self {
+            version: RUN_MANIFEST_VERSION,
+            tool: format!("contact-detector {}", env!("CARGO_PKG_VERSION")),
+            command: command.to_string(),
+            command_line: std::env::args().collect(),
+            parameters,
+            resolved_config: serde_json::Value::Null,
+            started: chrono::Utc::now().to_rfc3339(),
+            finished: String::new(),
+            duration_seconds: 0.0,
+            inputs: Vec::new(),
+            artifacts: Vec::new(),
+            start_instant: Instant::now(),
+            start_time: SystemTime::now(),
+        }
+    }
+
+    /// Record the configuration as resolved by the run
+    pub fn set_resolved_config<T: Serialize>(&mut self, config: &T) -> Result<()> {
+        self.resolved_config = serde_json::to_value(config).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to serialize config: {}", e))
+        })?;
+        Ok(())
+    }
+
+    /// Record an input file, hashing its contents
+    pub fn add_input<P: AsRef<Path>>(&mut self, path: P, role: &str) -> Result<()> {
+        let path = path.as_ref();
+        let (bytes, sha256) = file_sha256(path)?;
+        self.inputs.push(InputRecord {
+            path: path.display().to_string(),
+            role: role.to_string(),
+            bytes,
+            sha256,
+        });
+        Ok(())
+    }
+
+    /// Record the files written to `output_dir` since the run started and
+    /// write the manifest there
+    pub fn finish<P: AsRef<Path>>(mut self, output_dir: P) -> Result<()> {
+        let output_dir = output_dir.as_ref();
+        let manifest_path = output_dir.join(RUN_MANIFEST_FILE_NAME);
+
+        let mut files = Vec::new();
+        collect_files(output_dir, &mut files)?;
+        files.sort();
+        for path in files {
+            if path == manifest_path {
+                continue;
+            }
+            // File times come from a coarse clock and can trail the start
+            // time of a file written right away, hence the slack
+            let modified = std::fs::metadata(&path)?.modified()?;
+            if modified + MTIME_SLACK < self.start_time {
+                continue;
+            }
+            let (bytes, sha256) = file_sha256(&path)?;
+            let relative = path.strip_prefix(output_dir).unwrap_or(&path);
+            self.artifacts.push(ArtifactRecord {
+                path: relative.to_string_lossy().replace('\\', "/"),
+                bytes,
+                sha256,
+                written: chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339(),
+                seconds_after_start: modified
+                    .duration_since(self.start_time)
+                    .map_or(0.0, |d| d.as_secs_f64()),
+            });
+        }
+
+        self.finished = chrono::Utc::now().to_rfc3339();
+        self.duration_seconds = self.start_instant.elapsed().as_secs_f64();
+
+        let json = serde_json::to_string_pretty(&self).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to serialize run manifest: {}", e))
+        })?;
+        std::fs::write(&manifest_path, json)?;
+        tracing::info!("Wrote run manifest to {:?}", manifest_path);
+        Ok(())
+    }
+
+    /// Load a run manifest from a JSON file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|e| {
+            ContactDetectorError::ConfigError(format!("Failed to parse run manifest: {}", e))
+        })
+    }
+}
+
+/// Files below `dir`, skipping the result cache
+fn collect_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if path.file_name().is_some_and(|name| name == CACHE_DIR_NAME) {
+                continue;
+            }
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Size and lowercase hex SHA-256 of a file
+pub fn file_sha256<P: AsRef<Path>>(path: P) -> Result<(u64, String)> {
+    use sha2::{Digest, Sha256};
+
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1 << 16];
+    let mut bytes = 0u64;
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+        bytes += n as u64;
+    }
+    let hex = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Ok((bytes, hex))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_manifest() {
+        let dir = std::env::temp_dir().join("test_run_manifest");
+        let _ = std::fs::remove_dir_all(&dir);
+        let input = dir.join("mesh.json");
+        let output = dir.join("out");
+        std::fs::create_dir_all(output.join(CACHE_DIR_NAME)).unwrap();
+        std::fs::create_dir_all(output.join("contact_pairs")).unwrap();
+        std::fs::write(&input, "abc").unwrap();
+
+        let mut manifest = RunManifest::new("auto-contact", serde_json::json!({"max_gap": 0.1}));
+        manifest.add_input(&input, "mesh").unwrap();
+        assert_eq!(
+            manifest.inputs[0].sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(manifest.inputs[0].bytes, 3);
+
+        std::fs::write(output.join("contact_legend.json"), "{}").unwrap();
+        std::fs::write(output.join("contact_pairs").join("pair_1.vtu"), "vtu").unwrap();
+        std::fs::write(output.join(CACHE_DIR_NAME).join("key.json"), "{}").unwrap();
+        manifest.finish(&output).unwrap();
+
+        let loaded = RunManifest::load(output.join(RUN_MANIFEST_FILE_NAME)).unwrap();
+        assert_eq!(loaded.command, "auto-contact");
+        assert_eq!(loaded.parameters["max_gap"], 0.1);
+        let paths: Vec<&str> = loaded.artifacts.iter().map(|a| a.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["contact_legend.json", "contact_pairs/pair_1.vtu"]
+        );
+        assert!(loaded.duration_seconds >= 0.0);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
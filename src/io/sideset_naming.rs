@@ -0,0 +1,213 @@
+//! Naming of exported contact sidesets
+//!
+//! Sideset names come from a template so that every contact pair gets its own
+//! master and slave sideset. Exodus stores names in 32 characters (unless the
+//! file is written with longer names), so rendered names are truncated
+//! explicitly here and made unique afterwards; silently truncating in the
+//! writer could merge two sidesets under the same name.
+
+use crate::contact::{ContactInterface, SurfaceRole};
+use crate::error::Result;
+use crate::io::exodus_names::truncate_name;
+use crate::io::output_naming::validate_placeholders;
+use crate::warnings::{WarningKind, Warnings};
+use std::collections::HashSet;
+
+pub use crate::io::exodus_names::EXODUS_MAX_NAME_LEN;
+
+/// Default template for auto-contact sideset names
+pub const DEFAULT_SIDESET_NAME_TEMPLATE: &str = "auto_contact_p{pair}_{role}";
+
+const PLACEHOLDERS: [&str; 4] = ["pair", "role", "surface", "block"];
+
+/// Side of a contact pair a sideset is created for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SidesetRole {
+    /// Surface A of the pair
+    Master,
+
+    /// Surface B of the pair
+    Slave,
+}
+
+impl SidesetRole {
+    /// Lowercase name used in the `{role}` placeholder
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SidesetRole::Master => "master",
+            SidesetRole::Slave => "slave",
+        }
+    }
+}
+
+impl From<SurfaceRole> for SidesetRole {
+    fn from(role: SurfaceRole) -> Self {
+        match role {
+            SurfaceRole::Master => SidesetRole::Master,
+            SurfaceRole::Slave => SidesetRole::Slave,
+        }
+    }
+}
+
+/// Generates unique, Exodus-compatible sideset names from a template
+///
+/// Supported placeholders: `{pair}` (1-based pair ID), `{role}` (`master` or
+/// `slave`), `{surface}` (surface name) and `{block}` (element block name).
+#[derive(Debug, Clone)]
+pub struct SidesetNamer {
+    template: String,
+    used: HashSet<String>,
+    max_name_len: usize,
+}
+
+impl SidesetNamer {
+    /// Create a namer, reserving the names of sidesets that already exist
+    pub fn new<'a>(template: &str, existing: impl IntoIterator<Item = &'a String>) -> Result<Self> {
+        validate_template(template)?;
+
+        Ok(Self {
+            template: template.to_string(),
+            used: existing.into_iter().cloned().collect(),
+            max_name_len: EXODUS_MAX_NAME_LEN,
+        })
+    }
+
+    /// Allow names of up to `max_len` characters (for files written with
+    /// longer names than the Exodus default of 32)
+    pub fn with_max_name_length(mut self, max_len: usize) -> Self {
+        self.max_name_len = max_len;
+        self
+    }
+
+    /// Name the sideset for one side of a contact pair
+    ///
+    /// Truncation and collision renames are recorded in `warnings`.
+    pub fn name(
+        &mut self,
+        pair_id: usize,
+        role: SidesetRole,
+        surface_name: &str,
+        warnings: &mut Warnings,
+    ) -> String {
+        let block_name = surface_name.split(':').next().unwrap_or(surface_name);
+        let rendered = sanitize(
+            &self
+                .template
+                .replace("{pair}", &pair_id.to_string())
+                .replace("{role}", role.as_str())
+                .replace("{surface}", surface_name)
+                .replace("{block}", block_name),
+        );
+
+        let mut name = truncate_name(&rendered, self.max_name_len).to_string();
+        if name.len() < rendered.len() {
+            warnings.push(
+                WarningKind::RenamedSideSet,
+                format!(
+                    "Sideset name '{}' exceeds {} characters, truncated to '{}'",
+                    rendered, self.max_name_len, name
+                ),
+            );
+        }
+
+        if self.used.contains(&name) {
+            let base = name.clone();
+            let mut n = 2;
+            while self.used.contains(&name) {
+                let suffix = format!("_{}", n);
+                name = format!(
+                    "{}{}",
+                    truncate_name(&base, self.max_name_len - suffix.len()),
+                    suffix
+                );
+                n += 1;
+            }
+            warnings.push(
+                WarningKind::RenamedSideSet,
+                format!(
+                    "Sideset name '{}' is already in use, renamed to '{}'",
+                    base, name
+                ),
+            );
+        }
+
+        self.used.insert(name.clone());
+        name
+    }
+
+    /// Name the sidesets of both sides of every interface
+    pub fn name_interfaces(
+        &mut self,
+        interfaces: &mut [ContactInterface],
+        warnings: &mut Warnings,
+    ) {
+        for interface in interfaces {
+            for side in [&mut interface.side_a, &mut interface.side_b] {
+                let name = self.name(interface.id, side.role.into(), &side.part, warnings);
+                side.sideset = Some(name);
+            }
+        }
+    }
+}
+
+/// Check that a template only uses known placeholders
+pub fn validate_template(template: &str) -> Result<()> {
+    validate_placeholders(template, "sideset name", &PLACEHOLDERS)
+}
+
+/// Replace characters that are awkward in Exodus names and solver input decks
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_template_names_each_pair() {
+        let mut namer = SidesetNamer::new(DEFAULT_SIDESET_NAME_TEMPLATE, []).unwrap();
+        let mut warnings = Warnings::new();
+
+        // The same surface in two pairs gets two distinct sidesets
+        let a = namer.name(1, SidesetRole::Master, "Block_1:patch_0", &mut warnings);
+        let b = namer.name(3, SidesetRole::Master, "Block_1:patch_0", &mut warnings);
+        assert_eq!(a, "auto_contact_p1_master");
+        assert_eq!(b, "auto_contact_p3_master");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_truncation_and_collisions() {
+        let existing = vec!["contact_Block_1".to_string()];
+        let mut namer = SidesetNamer::new("contact_{block}", &existing).unwrap();
+        let mut warnings = Warnings::new();
+
+        let name = namer.name(1, SidesetRole::Master, "Block_1:patch_0", &mut warnings);
+        assert_eq!(name, "contact_Block_1_2");
+
+        let long = "A_very_long_block_name_for_testing:patch_0";
+        let first = namer.name(2, SidesetRole::Master, long, &mut warnings);
+        let second = namer.name(2, SidesetRole::Slave, long, &mut warnings);
+        assert_eq!(first.len(), EXODUS_MAX_NAME_LEN);
+        assert_eq!(second.len(), EXODUS_MAX_NAME_LEN);
+        assert!(second.ends_with("_2"));
+        assert_ne!(first, second);
+
+        assert_eq!(warnings.of_kind(WarningKind::RenamedSideSet).count(), 4);
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        assert!(SidesetNamer::new("contact_{pairs}", []).is_err());
+        assert!(SidesetNamer::new("contact_{pair", []).is_err());
+    }
+}
@@ -0,0 +1,154 @@
+//! STL (stereolithography) triangle reader
+//!
+//! Supports both ASCII and binary STL. Only triangle vertices are read; facet
+//! normals stored in the file are ignored and can be recomputed from winding.
+
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::Point;
+use std::path::Path;
+
+/// A triangle given by its three vertices
+pub type Triangle = [Point; 3];
+
+/// Read all triangles from an ASCII or binary STL file
+#[tracing::instrument(skip_all, fields(path = %path.as_ref().display()))]
+pub fn read_stl_triangles<P: AsRef<Path>>(path: P) -> Result<Vec<Triangle>> {
+    let bytes = std::fs::read(path.as_ref())?;
+
+    let triangles = if is_binary_stl(&bytes) {
+        parse_binary_stl(&bytes)?
+    } else {
+        let text = std::str::from_utf8(&bytes).map_err(|_| {
+            ContactDetectorError::ConfigError(
+                "Failed to parse STL file: not valid ASCII or binary STL".to_string(),
+            )
+        })?;
+        parse_ascii_stl(text)?
+    };
+
+    tracing::info!("Read {} triangles from STL", triangles.len());
+
+    Ok(triangles)
+}
+
+/// Binary STL: 80-byte header, u32 triangle count, then 50 bytes per triangle.
+/// Some exporters write "solid" into the binary header, so the size check is
+/// authoritative rather than the leading keyword.
+fn is_binary_stl(bytes: &[u8]) -> bool {
+    if bytes.len() < 84 {
+        return false;
+    }
+    let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+    bytes.len() == 84 + count * 50
+}
+
+fn parse_binary_stl(bytes: &[u8]) -> Result<Vec<Triangle>> {
+    let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+    let read_f32 = |offset: usize| {
+        f32::from_le_bytes([
+            bytes[offset],
+            bytes[offset + 1],
+            bytes[offset + 2],
+            bytes[offset + 3],
+        ]) as f64
+    };
+
+    let mut triangles = Vec::with_capacity(count);
+    for i in 0..count {
+        // Skip the 12-byte facet normal
+        let base = 84 + i * 50 + 12;
+        let mut tri = [Point::origin(); 3];
+        for (v, vertex) in tri.iter_mut().enumerate() {
+            let offset = base + v * 12;
+            *vertex = Point::new(read_f32(offset), read_f32(offset + 4), read_f32(offset + 8));
+        }
+        triangles.push(tri);
+    }
+
+    Ok(triangles)
+}
+
+fn parse_ascii_stl(text: &str) -> Result<Vec<Triangle>> {
+    let mut triangles = Vec::new();
+    let mut current = Vec::with_capacity(3);
+
+    for (line_no, line) in text.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("vertex") => {
+                let coords: Vec<f64> = tokens
+                    .map(|t| t.parse::<f64>())
+                    .collect::<std::result::Result<_, _>>()
+                    .map_err(|e| {
+                        ContactDetectorError::ConfigError(format!(
+                            "Failed to parse STL file: line {}: {}",
+                            line_no + 1,
+                            e
+                        ))
+                    })?;
+                if coords.len() != 3 {
+                    return Err(ContactDetectorError::ConfigError(format!(
+                        "Failed to parse STL file: line {}: expected 3 vertex coordinates",
+                        line_no + 1
+                    )));
+                }
+                current.push(Point::new(coords[0], coords[1], coords[2]));
+            }
+            Some("endfacet") => {
+                if current.len() != 3 {
+                    return Err(ContactDetectorError::ConfigError(format!(
+                        "Failed to parse STL file: line {}: facet has {} vertices, expected 3",
+                        line_no + 1,
+                        current.len()
+                    )));
+                }
+                triangles.push([current[0], current[1], current[2]]);
+                current.clear();
+            }
+            _ => {}
+        }
+    }
+
+    Ok(triangles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASCII_TRIANGLE: &str = "solid test
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 0 1 0
+    endloop
+  endfacet
+endsolid test
+";
+
+    #[test]
+    fn test_parse_ascii_stl() {
+        let triangles = parse_ascii_stl(ASCII_TRIANGLE).unwrap();
+        assert_eq!(triangles.len(), 1);
+        assert_eq!(triangles[0][1], Point::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_parse_binary_stl() {
+        let mut bytes = vec![0u8; 80];
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&[0u8; 12]);
+        for v in [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]] {
+            for c in v {
+                bytes.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        bytes.extend_from_slice(&[0u8; 2]);
+
+        assert!(is_binary_stl(&bytes));
+        let triangles = parse_binary_stl(&bytes).unwrap();
+        assert_eq!(triangles.len(), 1);
+        assert_eq!(triangles[0][2], Point::new(0.0, 1.0, 0.0));
+    }
+}
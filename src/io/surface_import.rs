@@ -0,0 +1,151 @@
+//! Import of external surface meshes (STL, OBJ)
+//!
+//! An imported surface, e.g. a scan of an as-built part, can stand in for one
+//! side of a contact pair and be checked for gap and penetration against the
+//! FE mesh skin. Triangles are stored as degenerate quads (see
+//! [`QuadFace::triangle`]), so the detection code handles them unchanged.
+
+use crate::error::{ContactDetectorError, Result};
+use crate::io::obj::read_obj_faces;
+use crate::io::stl::read_stl_triangles;
+use crate::mesh::geometry::compute_face_area;
+use crate::mesh::surface::build_surface_mesh;
+use crate::mesh::types::{Point, QuadFace, SurfaceMesh};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Whether a path names a surface mesh file that can be imported
+pub fn is_surface_file_path<P: AsRef<Path>>(path: P) -> bool {
+    matches!(
+        surface_extension(path.as_ref()).as_deref(),
+        Some("stl") | Some("obj")
+    )
+}
+
+/// Read an STL or OBJ file as a surface mesh named after the file stem
+///
+/// Degenerate (zero-area) faces, common in scan data, are dropped with a
+/// warning.
+#[tracing::instrument(skip_all, fields(path = %path.as_ref().display()))]
+pub fn read_surface_file<P: AsRef<Path>>(path: P) -> Result<SurfaceMesh> {
+    let path = path.as_ref();
+    let (nodes, faces) = match surface_extension(path).as_deref() {
+        Some("stl") => weld_triangles(&read_stl_triangles(path)?),
+        Some("obj") => read_obj_faces(path)?,
+        _ => {
+            return Err(ContactDetectorError::ConfigError(format!(
+                "Unsupported surface file '{}' (expected .stl or .obj)",
+                path.display()
+            )))
+        }
+    };
+
+    let num_faces = faces.len();
+    let faces: Vec<QuadFace> = faces
+        .into_iter()
+        .filter(|face| compute_face_area(face, &nodes).is_ok())
+        .collect();
+    if faces.len() < num_faces {
+        tracing::warn!(
+            "Dropped {} degenerate faces from {}",
+            num_faces - faces.len(),
+            path.display()
+        );
+    }
+    if faces.is_empty() {
+        return Err(ContactDetectorError::ConfigError(format!(
+            "Surface file '{}' contains no usable faces",
+            path.display()
+        )));
+    }
+
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "imported".to_string());
+
+    build_surface_mesh(name, faces, &nodes)
+}
+
+fn surface_extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+}
+
+/// Merge coincident STL vertices so triangles share nodes
+fn weld_triangles(triangles: &[[Point; 3]]) -> (Vec<Point>, Vec<QuadFace>) {
+    let mut node_ids = HashMap::new();
+    let mut nodes = Vec::new();
+
+    let faces = triangles
+        .iter()
+        .map(|tri| {
+            QuadFace::triangle(tri.map(|p| {
+                *node_ids
+                    .entry([p.x.to_bits(), p.y.to_bits(), p.z.to_bits()])
+                    .or_insert_with(|| {
+                        nodes.push(p);
+                        nodes.len() - 1
+                    })
+            }))
+        })
+        .collect();
+
+    (nodes, faces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_stl_surface() {
+        // Two triangles of a unit square plus one degenerate sliver
+        let stl = "solid scan
+facet normal 0 0 1
+outer loop
+vertex 0 0 0
+vertex 1 0 0
+vertex 1 1 0
+endloop
+endfacet
+facet normal 0 0 1
+outer loop
+vertex 0 0 0
+vertex 1 1 0
+vertex 0 1 0
+endloop
+endfacet
+facet normal 0 0 1
+outer loop
+vertex 0 0 0
+vertex 1 1 0
+vertex 1 1 0
+endloop
+endfacet
+endsolid scan
+";
+        let path = std::env::temp_dir().join("test_scan_surface.stl");
+        std::fs::write(&path, stl).unwrap();
+
+        assert!(is_surface_file_path(&path));
+        let surface = read_surface_file(&path).unwrap();
+        assert_eq!(surface.part_name, "test_scan_surface");
+        assert_eq!(surface.num_faces(), 2);
+        assert_eq!(surface.nodes.len(), 4); // shared vertices are welded
+        assert!((surface.total_area() - 1.0).abs() < 1e-12);
+        assert!(surface
+            .face_normals
+            .iter()
+            .all(|n| (n.z - 1.0).abs() < 1e-12));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_rejects_unknown_extension() {
+        assert!(!is_surface_file_path("mesh.exo"));
+        assert!(read_surface_file("surface.step").is_err());
+    }
+}
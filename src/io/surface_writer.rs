@@ -0,0 +1,639 @@
+//! Pluggable per-face surface outputs
+//!
+//! Per-face fields of a contact surface are computed by [`AttributeProvider`]s
+//! collected in an [`AttributeSet`], and written by any [`SurfaceWriter`]
+//! backend (VTU, VTP, CSV) or turned into Exodus side set variables with
+//! [`sideset_variables`]. A new field is therefore added once, as a provider,
+//! and appears in every output; a new output format only has to write named
+//! per-face arrays.
+
+use crate::contact::{
+    opposition_deviation, per_face_values, ContactCriteria, ContactInterface, ContactResults,
+    FaceValueSource,
+};
+use crate::error::{ContactDetectorError, Result};
+use crate::format::FloatFormat;
+use crate::io::legend::ContactState;
+use crate::io::vtk_version::VtkVersion;
+use crate::io::vtu_stream::{write_surface_to_vtu_streaming, CellArray, CellValues};
+use crate::mesh::geometry::Triangulation;
+use crate::mesh::types::SurfaceMesh;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use vtkio::model::*;
+
+/// One side of a contact pair, the input of an [`AttributeProvider`]
+#[derive(Debug, Clone, Copy)]
+pub struct FaceContext<'a> {
+    /// Contact results of the pair
+    pub results: &'a ContactResults,
+
+    /// Surface the values are computed for
+    pub surface: &'a SurfaceMesh,
+
+    /// Other surface of the pair
+    pub partner: &'a SurfaceMesh,
+
+    /// Whether `surface` is surface A of the results
+    pub is_surface_a: bool,
+}
+
+impl<'a> FaceContext<'a> {
+    /// Side A (`is_surface_a`) or side B of `results`, on `surface`
+    pub fn new(
+        results: &'a ContactResults,
+        surface: &'a SurfaceMesh,
+        partner: &'a SurfaceMesh,
+        is_surface_a: bool,
+    ) -> Self {
+        Self {
+            results,
+            surface,
+            partner,
+            is_surface_a,
+        }
+    }
+
+    /// Side A (`true`) or side B of `interface`, detected on `surfaces`
+    pub fn of_interface(
+        interface: &'a ContactInterface,
+        surfaces: &'a [SurfaceMesh],
+        is_surface_a: bool,
+    ) -> Self {
+        Self {
+            results: &interface.results,
+            surface: interface.surface(surfaces, is_surface_a),
+            partner: interface.surface(surfaces, !is_surface_a),
+            is_surface_a,
+        }
+    }
+
+    /// Values of a built-in per-face quantity (see [`per_face_values`])
+    pub fn values(&self, source: FaceValueSource) -> Vec<f64> {
+        per_face_values(
+            self.results,
+            self.surface,
+            self.partner,
+            self.is_surface_a,
+            source,
+        )
+    }
+}
+
+/// Computes one named value per face of a contact surface
+pub trait AttributeProvider: Send + Sync {
+    /// Field name, as written to every output
+    fn name(&self) -> &str;
+
+    /// One value per face of `context.surface`
+    fn values(&self, context: &FaceContext) -> Vec<f64>;
+}
+
+impl AttributeProvider for FaceValueSource {
+    fn name(&self) -> &str {
+        match self {
+            FaceValueSource::Gap => "contact_gap",
+            FaceValueSource::OverlapFraction => "contact_overlap_fraction",
+            FaceValueSource::NormalAngle => "contact_angle",
+            FaceValueSource::Paired => "contact_paired",
+        }
+    }
+
+    fn values(&self, context: &FaceContext) -> Vec<f64> {
+        context.values(*self)
+    }
+}
+
+/// An [`AttributeProvider`] computing its values with a function
+pub struct FnAttribute<F> {
+    name: String,
+    compute: F,
+}
+
+impl<F> FnAttribute<F>
+where
+    F: Fn(&FaceContext) -> Vec<f64> + Send + Sync,
+{
+    /// Create a provider of the field `name`
+    pub fn new(name: &str, compute: F) -> Self {
+        Self {
+            name: name.to_string(),
+            compute,
+        }
+    }
+}
+
+impl<F> AttributeProvider for FnAttribute<F>
+where
+    F: Fn(&FaceContext) -> Vec<f64> + Send + Sync,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn values(&self, context: &FaceContext) -> Vec<f64> {
+        (self.compute)(context)
+    }
+}
+
+/// Per-face values of one field
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaceAttribute {
+    pub name: String,
+    pub values: Vec<f64>,
+}
+
+/// Fields computed for every contact surface written
+#[derive(Default)]
+pub struct AttributeSet {
+    providers: Vec<Box<dyn AttributeProvider>>,
+}
+
+impl AttributeSet {
+    /// Create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The fields of every contact surface output: pair index (-1 on
+    /// unpaired faces), gap, normal angle and its deviation from exact
+    /// opposition, paired flag, overlap fraction, the gap and angle margins
+    /// (how far each paired face is from failing the criteria) and the
+    /// [`ContactState`] code. Values other than the pair index and state
+    /// are 0 on unpaired faces.
+    pub fn contact_defaults() -> Self {
+        Self::new()
+            .with(FnAttribute::new("pair_id", pair_ids))
+            .with(FaceValueSource::Gap)
+            .with(FaceValueSource::NormalAngle)
+            .with(FnAttribute::new("opposition_deviation", |context| {
+                paired_values(context, FaceValueSource::NormalAngle, |_, angle| {
+                    opposition_deviation(angle)
+                })
+            }))
+            .with(FaceValueSource::Paired)
+            .with(FaceValueSource::OverlapFraction)
+            .with(FnAttribute::new("gap_margin", |context| {
+                paired_values(context, FaceValueSource::Gap, |criteria, gap| {
+                    criteria.distance_margin(gap)
+                })
+            }))
+            .with(FnAttribute::new("angle_margin", |context| {
+                paired_values(context, FaceValueSource::NormalAngle, |criteria, angle| {
+                    criteria.angle_margin(angle)
+                })
+            }))
+            .with(FnAttribute::new("contact_state", |context| {
+                context
+                    .values(FaceValueSource::Gap)
+                    .into_iter()
+                    .zip(context.values(FaceValueSource::Paired))
+                    .map(|(gap, paired)| {
+                        let distance = (paired > 0.0).then_some(gap);
+                        f64::from(ContactState::of_distance(distance).code())
+                    })
+                    .collect()
+            }))
+    }
+
+    /// Add a field, replacing one of the same name
+    pub fn with<P: AttributeProvider + 'static>(mut self, provider: P) -> Self {
+        self.providers.retain(|p| p.name() != provider.name());
+        self.providers.push(Box::new(provider));
+        self
+    }
+
+    /// Names of the fields, in output order
+    pub fn names(&self) -> Vec<&str> {
+        self.providers.iter().map(|p| p.name()).collect()
+    }
+
+    /// Compute every field for one side of a contact pair, followed by a
+    /// `tag_<name>` field for each tag layer of the surface (see
+    /// [`FaceTags::values`](crate::mesh::FaceTags::values))
+    pub fn evaluate(&self, context: &FaceContext) -> Result<Vec<FaceAttribute>> {
+        let num_faces = context.surface.num_faces();
+        let tags = &context.surface.face_tags;
+        let tag_attributes = tags.names().map(|name| FaceAttribute {
+            name: format!("tag_{}", name),
+            values: tags.values(name, num_faces),
+        });
+
+        self.providers
+            .iter()
+            .map(|provider| {
+                let values = provider.values(context);
+                if values.len() != num_faces {
+                    return Err(ContactDetectorError::ConfigError(format!(
+                        "Face attribute '{}' has {} values for {} faces",
+                        provider.name(),
+                        values.len(),
+                        num_faces
+                    )));
+                }
+                Ok(FaceAttribute {
+                    name: provider.name().to_string(),
+                    values,
+                })
+            })
+            .chain(tag_attributes.map(Ok))
+            .collect()
+    }
+}
+
+/// Index of the pair each face's values come from (its closest pair, as in
+/// [`per_face_values`]), -1 on unpaired faces
+fn pair_ids(context: &FaceContext) -> Vec<f64> {
+    let num_faces = context.surface.num_faces();
+    let mut ids = vec![-1.0; num_faces];
+    let mut closest = vec![f64::INFINITY; num_faces];
+    for (index, pair) in context.results.pairs.iter().enumerate() {
+        let face = if context.is_surface_a {
+            pair.surface_a_face_id
+        } else {
+            pair.surface_b_face_id
+        };
+        if pair.distance.abs() < closest[face] {
+            closest[face] = pair.distance.abs();
+            ids[face] = index as f64;
+        }
+    }
+    ids
+}
+
+/// `derive` applied to each paired face's `source` value, 0 on unpaired
+/// faces
+fn paired_values(
+    context: &FaceContext,
+    source: FaceValueSource,
+    derive: fn(&ContactCriteria, f64) -> f64,
+) -> Vec<f64> {
+    let criteria = &context.results.criteria;
+    context
+        .values(source)
+        .into_iter()
+        .zip(context.values(FaceValueSource::Paired))
+        .map(|(value, paired)| {
+            if paired > 0.0 {
+                derive(criteria, value)
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+/// Attributes of the given faces as Exodus side set variables
+pub fn sideset_variables(attributes: &[FaceAttribute], faces: &[usize]) -> Vec<(String, Vec<f64>)> {
+    attributes
+        .iter()
+        .map(|attribute| {
+            let values = faces.iter().map(|&f| attribute.values[f]).collect();
+            (attribute.name.clone(), values)
+        })
+        .collect()
+}
+
+/// Writes a surface with per-face attributes to a file
+pub trait SurfaceWriter: Send + Sync {
+    /// Short format name, e.g. `vtu`
+    fn name(&self) -> &str;
+
+    /// Lowercase file extensions of the format, without the dot
+    fn extensions(&self) -> &[&str];
+
+    /// Write `surface` with one value per face for every attribute
+    fn write(&self, surface: &SurfaceMesh, attributes: &[FaceAttribute], path: &Path)
+        -> Result<()>;
+}
+
+/// VTU unstructured grid, attributes as cell data
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VtuSurfaceWriter {
+    pub vtk_version: Option<VtkVersion>,
+    pub triangulation: Option<Triangulation>,
+}
+
+impl SurfaceWriter for VtuSurfaceWriter {
+    fn name(&self) -> &str {
+        "vtu"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["vtu"]
+    }
+
+    fn write(
+        &self,
+        surface: &SurfaceMesh,
+        attributes: &[FaceAttribute],
+        path: &Path,
+    ) -> Result<()> {
+        let mut arrays = vec![
+            CellArray {
+                name: "normals",
+                values: CellValues::Vectors(&surface.face_normals),
+            },
+            CellArray {
+                name: "area",
+                values: CellValues::F64(&surface.face_areas),
+            },
+        ];
+        arrays.extend(attributes.iter().map(|attribute| CellArray {
+            name: &attribute.name,
+            values: CellValues::F64(&attribute.values),
+        }));
+        write_surface_to_vtu_streaming(surface, &arrays, path, self.vtk_version, self.triangulation)
+    }
+}
+
+/// VTP polydata, one polygon per face, attributes as cell data
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VtpSurfaceWriter {
+    pub vtk_version: Option<VtkVersion>,
+}
+
+impl SurfaceWriter for VtpSurfaceWriter {
+    fn name(&self) -> &str {
+        "vtp"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["vtp"]
+    }
+
+    fn write(
+        &self,
+        surface: &SurfaceMesh,
+        attributes: &[FaceAttribute],
+        path: &Path,
+    ) -> Result<()> {
+        let points: Vec<f64> = surface.nodes.iter().flat_map(|p| [p.x, p.y, p.z]).collect();
+        let mut connectivity = Vec::new();
+        let mut offsets = Vec::new();
+        for face in &surface.faces {
+            connectivity.extend(face.corners().iter().map(|&n| n as u64));
+            offsets.push(connectivity.len() as u64);
+        }
+
+        let scalars = |name: &str, values: &[f64]| {
+            Attribute::DataArray(DataArray {
+                name: name.into(),
+                elem: ElementType::Scalars {
+                    num_comp: 1,
+                    lookup_table: None,
+                },
+                data: IOBuffer::F64(values.to_vec()),
+            })
+        };
+        let mut polydata = PolyDataPiece {
+            points: IOBuffer::F64(points),
+            verts: None,
+            lines: None,
+            polys: Some(VertexNumbers::XML {
+                connectivity,
+                offsets,
+            }),
+            strips: None,
+            data: Attributes::new(),
+        };
+        polydata.data.cell.push(Attribute::DataArray(DataArray {
+            name: "normals".into(),
+            elem: ElementType::Vectors,
+            data: IOBuffer::F64(
+                surface
+                    .face_normals
+                    .iter()
+                    .flat_map(|n| [n.x, n.y, n.z])
+                    .collect(),
+            ),
+        }));
+        polydata
+            .data
+            .cell
+            .push(scalars("area", &surface.face_areas));
+        for attribute in attributes {
+            polydata
+                .data
+                .cell
+                .push(scalars(&attribute.name, &attribute.values));
+        }
+
+        let vtk = Vtk {
+            version: VtkVersion::for_path(self.vtk_version, path)?.into(),
+            title: surface.part_name.clone(),
+            byte_order: ByteOrder::LittleEndian,
+            data: DataSet::PolyData {
+                pieces: vec![Piece::Inline(Box::new(polydata))],
+                meta: None,
+            },
+            file_path: None,
+        };
+        vtk.export(path).map_err(|e| {
+            ContactDetectorError::VtkError(format!("Failed to write surface polydata: {}", e))
+        })?;
+        Ok(())
+    }
+}
+
+/// CSV table, one row per face: `face`, centroid, `area`, then the attributes
+#[derive(Debug, Clone, Copy)]
+pub struct CsvSurfaceWriter {
+    /// Format of the values (every digit by default)
+    pub format: FloatFormat,
+}
+
+impl Default for CsvSurfaceWriter {
+    fn default() -> Self {
+        Self {
+            format: FloatFormat::EXACT,
+        }
+    }
+}
+
+impl SurfaceWriter for CsvSurfaceWriter {
+    fn name(&self) -> &str {
+        "csv"
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["csv"]
+    }
+
+    fn write(
+        &self,
+        surface: &SurfaceMesh,
+        attributes: &[FaceAttribute],
+        path: &Path,
+    ) -> Result<()> {
+        let mut out = BufWriter::new(std::fs::File::create(path)?);
+        write!(out, "face,x,y,z,area")?;
+        for attribute in attributes {
+            write!(out, ",{}", attribute.name)?;
+        }
+        writeln!(out)?;
+        let format = self.format;
+        for (face, centroid) in surface.face_centroids.iter().enumerate() {
+            write!(
+                out,
+                "{},{},{},{},{}",
+                face,
+                format.show(centroid.x),
+                format.show(centroid.y),
+                format.show(centroid.z),
+                format.show(surface.face_areas[face])
+            )?;
+            for attribute in attributes {
+                write!(out, ",{}", format.show(attribute.values[face]))?;
+            }
+            writeln!(out)?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// Surface output formats, chosen by file extension
+pub struct SurfaceFormats {
+    writers: Vec<Box<dyn SurfaceWriter>>,
+}
+
+impl SurfaceFormats {
+    /// The VTU, VTP and CSV writers
+    pub fn with_builtin_formats(
+        vtk_version: Option<VtkVersion>,
+        triangulation: Option<Triangulation>,
+    ) -> Self {
+        let mut formats = Self {
+            writers: Vec::new(),
+        };
+        formats.register(VtuSurfaceWriter {
+            vtk_version,
+            triangulation,
+        });
+        formats.register(VtpSurfaceWriter { vtk_version });
+        formats.register(CsvSurfaceWriter::default());
+        formats
+    }
+
+    /// Add a writer, taking precedence over those registered before
+    pub fn register<W: SurfaceWriter + 'static>(&mut self, writer: W) {
+        self.writers.insert(0, Box::new(writer));
+    }
+
+    /// Writer for a path's extension
+    pub fn writer_for(&self, path: &Path) -> Option<&dyn SurfaceWriter> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        self.writers
+            .iter()
+            .find(|w| w.extensions().contains(&extension.as_str()))
+            .map(|w| w.as_ref())
+    }
+
+    /// Write a surface in the format given by the path's extension
+    pub fn write(
+        &self,
+        surface: &SurfaceMesh,
+        attributes: &[FaceAttribute],
+        path: &Path,
+    ) -> Result<()> {
+        let writer = self.writer_for(path).ok_or_else(|| {
+            ContactDetectorError::ConfigError(format!(
+                "No surface writer for {} (supported: {})",
+                path.display(),
+                self.writers
+                    .iter()
+                    .map(|w| w.name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })?;
+        writer.write(surface, attributes, path)
+    }
+
+    /// Write one side of a contact pair with the fields of `attributes`
+    pub fn write_contact(
+        &self,
+        attributes: &AttributeSet,
+        context: &FaceContext,
+        path: &Path,
+    ) -> Result<()> {
+        self.write(context.surface, &attributes.evaluate(context)?, path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contact::{ContactCriteria, ContactPair};
+    use crate::mesh::fixtures::strip;
+    use crate::mesh::types::Point;
+
+    #[test]
+    fn test_attributes_reach_every_backend() {
+        let mut lower = strip("Lower", 0.0, 2);
+        lower.face_tags.set("coating", 1, "coated");
+        let upper = strip("Upper", 0.01, 2);
+        let mut results = ContactResults::new(
+            "Lower".to_string(),
+            "Upper".to_string(),
+            ContactCriteria::new(0.05, 0.01, 45.0),
+        );
+        results.pairs.push(ContactPair {
+            surface_a_face_id: 1,
+            surface_b_face_id: 1,
+            distance: 0.01,
+            normal_angle: 5.0,
+            contact_point: Point::new(1.5, 0.5, 0.0),
+        });
+        let context = FaceContext::new(&results, &lower, &upper, true);
+
+        // A new field is added once...
+        let set = AttributeSet::contact_defaults()
+            .with(FnAttribute::new("curvature", |context: &FaceContext| {
+                vec![0.25; context.surface.num_faces()]
+            }));
+        assert_eq!(set.names().len(), 10);
+        let attributes = set.evaluate(&context).unwrap();
+        let field = |name: &str| &attributes.iter().find(|a| a.name == name).unwrap().values;
+        assert_eq!(field("pair_id"), &[-1.0, 0.0]);
+        assert_eq!(
+            field("contact_state"),
+            &[
+                f64::from(ContactState::Unpaired.code()),
+                f64::from(ContactState::Paired.code())
+            ]
+        );
+        let gap_margin = attributes.iter().find(|a| a.name == "gap_margin").unwrap();
+        assert_eq!(gap_margin.values[0], 0.0);
+        assert!((gap_margin.values[1] - 0.04).abs() < 1e-12);
+
+        // Tag layers follow the fields
+        let coating = attributes.last().unwrap();
+        assert_eq!(coating.name, "tag_coating");
+        assert!(coating.values[0].is_nan() && coating.values[1] == 0.0);
+
+        // ...and appears in every output
+        let variables = sideset_variables(&attributes, &[1]);
+        assert!(variables.contains(&("curvature".to_string(), vec![0.25])));
+
+        let dir = std::env::temp_dir().join("test_surface_writers");
+        std::fs::create_dir_all(&dir).unwrap();
+        let formats = SurfaceFormats::with_builtin_formats(None, None);
+        for name in ["lower.vtu", "lower.vtp", "lower.csv"] {
+            formats.write(&lower, &attributes, &dir.join(name)).unwrap();
+            let text = String::from_utf8_lossy(&std::fs::read(dir.join(name)).unwrap()).to_string();
+            assert!(text.contains("curvature"), "{} lacks the new field", name);
+        }
+        let csv = std::fs::read_to_string(dir.join("lower.csv")).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.starts_with("face,x,y,z,area,pair_id,contact_gap,"));
+        assert!(formats
+            .write(&lower, &attributes, &dir.join("lower.stl"))
+            .is_err());
+
+        let bad = AttributeSet::new().with(FnAttribute::new("short", |_: &FaceContext| vec![1.0]));
+        assert!(bad.evaluate(&context).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
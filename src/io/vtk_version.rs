@@ -0,0 +1,245 @@
+//! VTK file format versions
+//!
+//! The version written into a VTK file tells the reader how to interpret it,
+//! so it has to match what the writer actually produces. XML files (`.vtu`,
+//! `.vtp`, `.vtm`) are written with 64-bit block headers, which readers only
+//! honor from version 1.0 on, and ParaView's XML readers reject any major
+//! version above 2. Legacy `.vtk` files are written with the classic `CELLS`
+//! layout of versions 2.0 to 4.2; version 5.1 switched to offset and
+//! connectivity arrays and would be misread.
+
+use crate::error::{ContactDetectorError, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// VTK file format version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VtkVersion {
+    V1_0,
+    V2_0,
+    V2_1,
+    V2_2,
+    V3_0,
+    V4_0,
+    V4_1,
+    V4_2,
+}
+
+/// Kind of VTK file, which decides the versions that can be written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VtkFileKind {
+    /// XML dataset (`.vtu` unstructured grid, `.vtp` poly data)
+    XmlDataSet,
+
+    /// XML multi-block index (`.vtm`)
+    XmlMultiBlock,
+
+    /// Legacy `.vtk` file
+    Legacy,
+}
+
+impl VtkFileKind {
+    /// Kind of the dataset file at `path`, by its extension
+    pub fn of_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("vtk") => VtkFileKind::Legacy,
+            Some(ext) if ext.eq_ignore_ascii_case("vtm") => VtkFileKind::XmlMultiBlock,
+            _ => VtkFileKind::XmlDataSet,
+        }
+    }
+
+    /// Short description for messages
+    fn description(self) -> &'static str {
+        match self {
+            VtkFileKind::XmlDataSet => "XML datasets (.vtu/.vtp)",
+            VtkFileKind::XmlMultiBlock => "XML multi-block files (.vtm)",
+            VtkFileKind::Legacy => "legacy .vtk files",
+        }
+    }
+}
+
+impl VtkVersion {
+    /// All versions, oldest first
+    pub const ALL: [VtkVersion; 8] = [
+        VtkVersion::V1_0,
+        VtkVersion::V2_0,
+        VtkVersion::V2_1,
+        VtkVersion::V2_2,
+        VtkVersion::V3_0,
+        VtkVersion::V4_0,
+        VtkVersion::V4_1,
+        VtkVersion::V4_2,
+    ];
+
+    /// Version written to XML files unless another is requested (read by
+    /// ParaView 5.x and 6.x)
+    pub const DEFAULT_XML: VtkVersion = VtkVersion::V2_2;
+
+    /// Version written to legacy files unless another is requested
+    pub const DEFAULT_LEGACY: VtkVersion = VtkVersion::V4_2;
+
+    /// Major and minor version numbers
+    pub fn numbers(self) -> (u8, u8) {
+        match self {
+            VtkVersion::V1_0 => (1, 0),
+            VtkVersion::V2_0 => (2, 0),
+            VtkVersion::V2_1 => (2, 1),
+            VtkVersion::V2_2 => (2, 2),
+            VtkVersion::V3_0 => (3, 0),
+            VtkVersion::V4_0 => (4, 0),
+            VtkVersion::V4_1 => (4, 1),
+            VtkVersion::V4_2 => (4, 2),
+        }
+    }
+
+    /// The supported version with these numbers
+    pub fn from_numbers(major: u8, minor: u8) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.numbers() == (major, minor))
+            .ok_or_else(|| {
+                ContactDetectorError::ConfigError(format!(
+                    "Unsupported VTK version {}.{} (supported: {})",
+                    major,
+                    minor,
+                    Self::list(Self::ALL.into_iter())
+                ))
+            })
+    }
+
+    /// Whether files of `kind` can be written with this version
+    pub fn supports(self, kind: VtkFileKind) -> bool {
+        match kind {
+            VtkFileKind::XmlDataSet | VtkFileKind::XmlMultiBlock => self <= VtkVersion::V2_2,
+            VtkFileKind::Legacy => matches!(
+                self,
+                VtkVersion::V2_0
+                    | VtkVersion::V3_0
+                    | VtkVersion::V4_0
+                    | VtkVersion::V4_1
+                    | VtkVersion::V4_2
+            ),
+        }
+    }
+
+    /// Default version for files of `kind`
+    pub fn default_for(kind: VtkFileKind) -> Self {
+        match kind {
+            VtkFileKind::Legacy => Self::DEFAULT_LEGACY,
+            _ => Self::DEFAULT_XML,
+        }
+    }
+
+    /// The requested version (or the default) for files of `kind`, or an
+    /// error if it cannot be written as that kind
+    pub fn resolve(requested: Option<Self>, kind: VtkFileKind) -> Result<Self> {
+        let Some(version) = requested else {
+            return Ok(Self::default_for(kind));
+        };
+        if version.supports(kind) {
+            return Ok(version);
+        }
+        let supported = Self::ALL.into_iter().filter(|v| v.supports(kind));
+        Err(ContactDetectorError::VtkError(format!(
+            "VTK version {} cannot be used for {} (supported: {})",
+            version,
+            kind.description(),
+            Self::list(supported)
+        )))
+    }
+
+    /// [`VtkVersion::resolve`] for the file at `path`
+    pub fn for_path(requested: Option<Self>, path: &Path) -> Result<Self> {
+        Self::resolve(requested, VtkFileKind::of_path(path))
+    }
+
+    fn list(versions: impl Iterator<Item = Self>) -> String {
+        versions
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl fmt::Display for VtkVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (major, minor) = self.numbers();
+        write!(f, "{}.{}", major, minor)
+    }
+}
+
+impl FromStr for VtkVersion {
+    type Err = ContactDetectorError;
+
+    /// Parse a version string such as "2.2" or "4.2"
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            ContactDetectorError::ConfigError(format!(
+                "Invalid VTK version format '{}'. Expected format: 'X.Y' (e.g., '2.2')",
+                s
+            ))
+        };
+        let (major, minor) = s.trim().split_once('.').ok_or_else(invalid)?;
+        let major = major.parse::<u8>().map_err(|_| invalid())?;
+        let minor = minor.parse::<u8>().map_err(|_| invalid())?;
+        Self::from_numbers(major, minor)
+    }
+}
+
+impl From<VtkVersion> for vtkio::model::Version {
+    fn from(version: VtkVersion) -> Self {
+        vtkio::model::Version::new(version.numbers())
+    }
+}
+
+impl Serialize for VtkVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for VtkVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vtk_version_parsing_and_support() {
+        assert_eq!("2.2".parse::<VtkVersion>().unwrap(), VtkVersion::V2_2);
+        assert_eq!(" 4.2 ".parse::<VtkVersion>().unwrap(), VtkVersion::V4_2);
+        assert_eq!(VtkVersion::V1_0.to_string(), "1.0");
+        assert!("2".parse::<VtkVersion>().is_err());
+        assert!("2.x".parse::<VtkVersion>().is_err());
+        assert!("5.1".parse::<VtkVersion>().is_err());
+
+        let vtu = Path::new("out/surface.vtu");
+        let vtk = Path::new("out/mesh.VTK");
+        assert_eq!(VtkVersion::for_path(None, vtu).unwrap(), VtkVersion::V2_2);
+        assert_eq!(VtkVersion::for_path(None, vtk).unwrap(), VtkVersion::V4_2);
+        assert_eq!(
+            VtkVersion::for_path(Some(VtkVersion::V2_0), vtk).unwrap(),
+            VtkVersion::V2_0
+        );
+
+        // Newer than ParaView's XML readers, and not a legacy version
+        let error = VtkVersion::for_path(Some(VtkVersion::V4_2), vtu).unwrap_err();
+        assert!(error.to_string().contains("1.0, 2.0, 2.1, 2.2"));
+        assert!(VtkVersion::for_path(Some(VtkVersion::V2_2), vtk).is_err());
+        assert!(VtkVersion::resolve(Some(VtkVersion::V3_0), VtkFileKind::XmlMultiBlock).is_err());
+
+        let json = serde_json::to_string(&VtkVersion::V2_1).unwrap();
+        assert_eq!(json, "\"2.1\"");
+        assert_eq!(
+            serde_json::from_str::<VtkVersion>(&json).unwrap(),
+            VtkVersion::V2_1
+        );
+    }
+}
@@ -3,8 +3,14 @@
 //! This module provides functionality for writing hierarchical multi-block VTK datasets (.vtm)
 //! with support for element blocks, sidesets, nodesets, and contact pairs.
 
+use crate::contact::opposition_deviation;
 use crate::error::{ContactDetectorError, Result};
-use crate::mesh::types::{Mesh, SurfaceMesh};
+use crate::io::legend::ContactState;
+use crate::io::vtk_version::VtkVersion;
+use crate::io::vtu::{expand_cell_data, SurfaceCells};
+use crate::mesh::geometry::Triangulation;
+use crate::mesh::types::{Mesh, QuadFace, SurfaceMesh};
+use crate::warnings::{WarningKind, Warnings};
 use std::fs;
 use std::path::{Path, PathBuf};
 use vtkio::model::*;
@@ -18,10 +24,16 @@ pub struct MultiBlockBuilder {
     base_name: String,
 
     /// VTK version to use
-    vtk_version: (u8, u8),
+    vtk_version: VtkVersion,
+
+    /// Split surface quads into triangles
+    triangulation: Option<Triangulation>,
 
     /// Blocks to include in the multi-block dataset
     blocks: Vec<Block>,
+
+    /// Non-fatal problems encountered while writing blocks
+    warnings: Warnings,
 }
 
 /// Represents a block in the multi-block hierarchy
@@ -39,18 +51,31 @@ struct Block {
 
 impl MultiBlockBuilder {
     /// Create a new multi-block builder
-    pub fn new<P: AsRef<Path>>(output_dir: P, base_name: String, vtk_version: (u8, u8)) -> Self {
+    pub fn new<P: AsRef<Path>>(output_dir: P, base_name: String, vtk_version: VtkVersion) -> Self {
         Self {
             output_dir: output_dir.as_ref().to_path_buf(),
             base_name,
             vtk_version,
+            triangulation: None,
             blocks: Vec::new(),
+            warnings: Warnings::new(),
         }
     }
 
+    /// Write sideset and contact surfaces as triangles instead of quads
+    pub fn set_triangulation(&mut self, triangulation: Option<Triangulation>) {
+        self.triangulation = triangulation;
+    }
+
+    /// Warnings collected while adding blocks
+    pub fn warnings(&self) -> &Warnings {
+        &self.warnings
+    }
+
     /// Add a volume mesh block (element blocks)
+    #[tracing::instrument(skip_all)]
     pub fn add_volume_mesh(&mut self, mesh: &Mesh) -> Result<()> {
-        log::info!("Adding volume mesh blocks to multi-block dataset");
+        tracing::info!("Adding volume mesh blocks to multi-block dataset");
 
         // Create volume directory
         let volume_dir = self.output_dir.join("volume");
@@ -59,7 +84,9 @@ impl MultiBlockBuilder {
         let mut volume_blocks = Vec::new();
 
         // Export each element block as a separate VTU file
-        for (block_name, element_indices) in &mesh.element_blocks {
+        let mut sorted_blocks: Vec<_> = mesh.element_blocks.iter().collect();
+        sorted_blocks.sort_by_key(|(name, _)| name.as_str());
+        for (block_name, element_indices) in sorted_blocks {
             let filename = format!("{}.vtu", sanitize_filename(block_name));
             let file_path = volume_dir.join(&filename);
             let rel_path = PathBuf::from("volume").join(&filename);
@@ -87,13 +114,14 @@ impl MultiBlockBuilder {
     }
 
     /// Add sideset blocks (boundary surfaces)
+    #[tracing::instrument(skip_all)]
     pub fn add_sidesets(&mut self, mesh: &Mesh) -> Result<()> {
         if mesh.side_sets.is_empty() {
-            log::debug!("No sidesets to export");
+            tracing::debug!("No sidesets to export");
             return Ok(());
         }
 
-        log::info!("Adding {} sidesets to multi-block dataset", mesh.side_sets.len());
+        tracing::info!("Adding {} sidesets to multi-block dataset", mesh.side_sets.len());
 
         // Create sidesets directory
         let sidesets_dir = self.output_dir.join("sidesets");
@@ -101,13 +129,23 @@ impl MultiBlockBuilder {
 
         let mut sideset_blocks = Vec::new();
 
-        for (sideset_name, sideset_data) in &mesh.side_sets {
+        let mut sorted_sidesets: Vec<_> = mesh.side_sets.iter().collect();
+        sorted_sidesets.sort_by_key(|(name, _)| name.as_str());
+        for (sideset_name, sideset_data) in sorted_sidesets {
             let filename = format!("{}.vtp", sanitize_filename(sideset_name));
             let file_path = sidesets_dir.join(&filename);
             let rel_path = PathBuf::from("sidesets").join(&filename);
 
             // Write the sideset as polydata
-            write_sideset_polydata(mesh, sideset_name, sideset_data, &file_path, self.vtk_version)?;
+            write_sideset_polydata(
+                mesh,
+                sideset_name,
+                sideset_data,
+                &file_path,
+                self.vtk_version,
+                self.triangulation,
+                &mut self.warnings,
+            )?;
 
             sideset_blocks.push(Block {
                 name: format!("Sideset_{}", sideset_name),
@@ -129,13 +167,14 @@ impl MultiBlockBuilder {
     }
 
     /// Add nodeset blocks (point sets)
+    #[tracing::instrument(skip_all)]
     pub fn add_nodesets(&mut self, mesh: &Mesh) -> Result<()> {
         if mesh.node_sets.is_empty() {
-            log::debug!("No nodesets to export");
+            tracing::debug!("No nodesets to export");
             return Ok(());
         }
 
-        log::info!("Adding {} nodesets to multi-block dataset", mesh.node_sets.len());
+        tracing::info!("Adding {} nodesets to multi-block dataset", mesh.node_sets.len());
 
         // Create nodesets directory
         let nodesets_dir = self.output_dir.join("nodesets");
@@ -143,7 +182,9 @@ impl MultiBlockBuilder {
 
         let mut nodeset_blocks = Vec::new();
 
-        for (nodeset_name, node_indices) in &mesh.node_sets {
+        let mut sorted_nodesets: Vec<_> = mesh.node_sets.iter().collect();
+        sorted_nodesets.sort_by_key(|(name, _)| name.as_str());
+        for (nodeset_name, node_indices) in sorted_nodesets {
             let filename = format!("{}.vtp", sanitize_filename(nodeset_name));
             let file_path = nodesets_dir.join(&filename);
             let rel_path = PathBuf::from("nodesets").join(&filename);
@@ -171,17 +212,18 @@ impl MultiBlockBuilder {
     }
 
     /// Add contact pair blocks with metadata
+    #[tracing::instrument(skip_all, fields(count = contact_pairs.len()))]
     pub fn add_contact_pairs(
         &mut self,
         contact_pairs: &[(String, String, SurfaceMesh, SurfaceMesh, crate::contact::ContactResults)],
         pair_id_offset: usize,
     ) -> Result<()> {
         if contact_pairs.is_empty() {
-            log::debug!("No contact pairs to export");
+            tracing::debug!("No contact pairs to export");
             return Ok(());
         }
 
-        log::info!("Adding {} contact pairs to multi-block dataset", contact_pairs.len());
+        tracing::info!("Adding {} contact pairs to multi-block dataset", contact_pairs.len());
 
         // Create contact pairs directory
         let contact_dir = self.output_dir.join("contact_pairs");
@@ -207,6 +249,7 @@ impl MultiBlockBuilder {
                 0, // ContactRole: 0 = master
                 &master_file_path,
                 self.vtk_version,
+                self.triangulation,
             )?;
 
             pair_blocks.push(Block {
@@ -227,6 +270,7 @@ impl MultiBlockBuilder {
                 1, // ContactRole: 1 = slave
                 &slave_file_path,
                 self.vtk_version,
+                self.triangulation,
             )?;
 
             pair_blocks.push(Block {
@@ -256,16 +300,17 @@ impl MultiBlockBuilder {
     }
 
     /// Write the multi-block meta file (.vtm)
+    #[tracing::instrument(skip_all)]
     pub fn write(&self) -> Result<()> {
         let vtm_path = self.output_dir.join(format!("{}.vtm", self.base_name));
-        log::info!("Writing multi-block meta file to {:?}", vtm_path);
+        tracing::info!("Writing multi-block meta file to {:?}", vtm_path);
 
         // Build XML content
         let mut xml = String::new();
         xml.push_str("<?xml version=\"1.0\"?>\n");
         xml.push_str(&format!(
-            "<VTKFile type=\"vtkMultiBlockDataSet\" version=\"{}.{}\" byte_order=\"LittleEndian\">\n",
-            self.vtk_version.0, self.vtk_version.1
+            "<VTKFile type=\"vtkMultiBlockDataSet\" version=\"{}\" byte_order=\"LittleEndian\">\n",
+            self.vtk_version
         ));
         xml.push_str("  <vtkMultiBlockDataSet>\n");
 
@@ -280,7 +325,7 @@ impl MultiBlockBuilder {
         // Write to file
         fs::write(&vtm_path, xml)?;
 
-        log::info!("Successfully wrote multi-block meta file");
+        tracing::info!("Successfully wrote multi-block meta file");
         Ok(())
     }
 }
@@ -322,21 +367,24 @@ fn write_element_block(
     block_name: &str,
     element_indices: &[usize],
     output_path: &Path,
-    vtk_version: (u8, u8),
+    vtk_version: VtkVersion,
 ) -> Result<()> {
-    log::debug!("Writing element block '{}' with {} elements", block_name, element_indices.len());
+    tracing::debug!("Writing element block '{}' with {} elements", block_name, element_indices.len());
 
     // Collect unique nodes used by this block
     let mut node_map = std::collections::HashMap::new();
     let mut local_nodes = Vec::new();
 
+    let mut global_node_ids = Vec::new();
+
     for &elem_idx in element_indices {
         let elem = &mesh.elements[elem_idx];
         for &node_id in &elem.node_ids {
-            if !node_map.contains_key(&node_id) {
-                node_map.insert(node_id, local_nodes.len());
+            node_map.entry(node_id).or_insert_with(|| {
                 local_nodes.push(mesh.nodes[node_id]);
-            }
+                global_node_ids.push(mesh.global_node_id(node_id));
+                local_nodes.len() - 1
+            });
         }
     }
 
@@ -388,6 +436,30 @@ fn write_element_block(
         data: IOBuffer::I32(vec![block_id as i32; element_indices.len()]),
     }));
 
+    // Add global (solver) element and node IDs
+    ugrid.data.cell.push(Attribute::DataArray(DataArray {
+        name: "GlobalElementId".into(),
+        elem: ElementType::Scalars {
+            num_comp: 1,
+            lookup_table: None,
+        },
+        data: IOBuffer::I32(
+            element_indices
+                .iter()
+                .map(|&idx| mesh.global_elem_id(idx))
+                .collect(),
+        ),
+    }));
+
+    ugrid.data.point.push(Attribute::DataArray(DataArray {
+        name: "GlobalNodeId".into(),
+        elem: ElementType::Scalars {
+            num_comp: 1,
+            lookup_table: None,
+        },
+        data: IOBuffer::I32(global_node_ids),
+    }));
+
     // Add MaterialId if available
     if !mesh.material_ids.is_empty() {
         let material_ids: Vec<i32> = element_indices
@@ -407,7 +479,7 @@ fn write_element_block(
 
     // Create VTK model
     let vtk = Vtk {
-        version: Version::new(vtk_version),
+        version: vtk_version.into(),
         title: format!("Element block: {}", block_name),
         byte_order: ByteOrder::LittleEndian,
         data: DataSet::UnstructuredGrid {
@@ -430,9 +502,11 @@ fn write_sideset_polydata(
     sideset_name: &str,
     sideset_data: &[(usize, u8)],
     output_path: &Path,
-    vtk_version: (u8, u8),
+    vtk_version: VtkVersion,
+    triangulation: Option<Triangulation>,
+    warnings: &mut Warnings,
 ) -> Result<()> {
-    log::debug!("Writing sideset '{}' with {} faces", sideset_name, sideset_data.len());
+    tracing::debug!("Writing sideset '{}' with {} faces", sideset_name, sideset_data.len());
 
     // Collect unique nodes and build faces
     let mut node_map = std::collections::HashMap::new();
@@ -443,29 +517,30 @@ fn write_sideset_polydata(
 
     for &(elem_idx, face_id) in sideset_data {
         let elem = &mesh.elements[elem_idx];
-        let elem_faces = elem.faces();
 
-        // Exodus face IDs are 1-based, convert to 0-based for indexing
-        let face_idx = face_id.saturating_sub(1) as usize;
-        if face_idx >= elem_faces.len() {
-            log::warn!("Invalid face ID {} for element {} (max {}), skipping",
-                face_id, elem_idx, elem_faces.len());
+        // Side IDs use Exodus numbering (1-6)
+        let Some(face) = elem.side_face(face_id) else {
+            warnings.push(
+                WarningKind::InvalidSide,
+                format!(
+                    "Sideset '{}': invalid side ID {} for element {} (expected 1-6), skipping",
+                    sideset_name, face_id, elem_idx
+                ),
+            );
             continue;
-        }
-        let face = elem_faces[face_idx];
+        };
 
         // Remap node IDs to local indices
         let mut local_face = [0usize; 4];
         for (i, &node_id) in face.node_ids.iter().enumerate() {
-            if !node_map.contains_key(&node_id) {
-                node_map.insert(node_id, local_nodes.len());
+            local_face[i] = *node_map.entry(node_id).or_insert_with(|| {
                 local_nodes.push(mesh.nodes[node_id]);
-            }
-            local_face[i] = node_map[&node_id];
+                local_nodes.len() - 1
+            });
         }
 
-        faces.push(local_face);
-        source_elem_ids.push(elem_idx as i32);
+        faces.push(QuadFace::new(local_face));
+        source_elem_ids.push(mesh.global_elem_id(elem_idx));
         source_elem_sides.push(face_id as i32);
     }
 
@@ -475,19 +550,8 @@ fn write_sideset_polydata(
         .flat_map(|p| vec![p.x, p.y, p.z])
         .collect();
 
-    // Create cell connectivity
-    let mut connectivity = Vec::new();
-    for face in &faces {
-        connectivity.extend_from_slice(&face.map(|id| id as u64));
-    }
-
-    // Create cells as VertexNumbers for polydata
-    let polys = VertexNumbers::XML {
-        connectivity,
-        offsets: (0..faces.len())
-            .map(|i| ((i + 1) * 4) as u64)
-            .collect(),
-    };
+    // Create cells (quads, or triangles if requested)
+    let (polys, cell_faces) = SurfaceCells::new(&faces, &local_nodes, triangulation).into_polys();
 
     // Create polydata piece
     let mut polydata = PolyDataPiece {
@@ -529,9 +593,11 @@ fn write_sideset_polydata(
         data: IOBuffer::I32(source_elem_sides),
     }));
 
+    expand_cell_data(&mut polydata.data, faces.len(), &cell_faces);
+
     // Create VTK model
     let vtk = Vtk {
-        version: Version::new(vtk_version),
+        version: vtk_version.into(),
         title: format!("Sideset: {}", sideset_name),
         byte_order: ByteOrder::LittleEndian,
         data: DataSet::PolyData {
@@ -554,9 +620,9 @@ fn write_nodeset_polydata(
     nodeset_name: &str,
     node_indices: &[usize],
     output_path: &Path,
-    vtk_version: (u8, u8),
+    vtk_version: VtkVersion,
 ) -> Result<()> {
-    log::debug!("Writing nodeset '{}' with {} nodes", nodeset_name, node_indices.len());
+    tracing::debug!("Writing nodeset '{}' with {} nodes", nodeset_name, node_indices.len());
 
     // Create point array
     let points: Vec<f64> = node_indices
@@ -596,9 +662,19 @@ fn write_nodeset_polydata(
         data: IOBuffer::I32(vec![0; node_indices.len()]), // All same nodeset
     }));
 
+    // Add global (solver) node IDs
+    polydata.data.point.push(Attribute::DataArray(DataArray {
+        name: "GlobalNodeId".into(),
+        elem: ElementType::Scalars {
+            num_comp: 1,
+            lookup_table: None,
+        },
+        data: IOBuffer::I32(node_indices.iter().map(|&idx| mesh.global_node_id(idx)).collect()),
+    }));
+
     // Create VTK model
     let vtk = Vtk {
-        version: Version::new(vtk_version),
+        version: vtk_version.into(),
         title: format!("Nodeset: {}", nodeset_name),
         byte_order: ByteOrder::LittleEndian,
         data: DataSet::PolyData {
@@ -622,9 +698,10 @@ fn write_contact_surface_polydata(
     contact_pair_id: usize,
     contact_role: i32,
     output_path: &Path,
-    vtk_version: (u8, u8),
+    vtk_version: VtkVersion,
+    triangulation: Option<Triangulation>,
 ) -> Result<()> {
-    log::debug!(
+    tracing::debug!(
         "Writing contact surface '{}' as polydata (pair_id={}, role={})",
         surface.part_name,
         contact_pair_id,
@@ -638,19 +715,9 @@ fn write_contact_surface_polydata(
         .flat_map(|p| vec![p.x, p.y, p.z])
         .collect();
 
-    // Create cell connectivity
-    let mut connectivity = Vec::new();
-    for face in &surface.faces {
-        connectivity.extend_from_slice(&face.node_ids.map(|id| id as u64));
-    }
-
-    // Create cells as VertexNumbers for polydata
-    let polys = VertexNumbers::XML {
-        connectivity,
-        offsets: (0..surface.faces.len())
-            .map(|i| ((i + 1) * 4) as u64)
-            .collect(),
-    };
+    // Create cells (quads, or triangles if requested)
+    let (polys, cell_faces) =
+        SurfaceCells::new(&surface.faces, &surface.nodes, triangulation).into_polys();
 
     // Create polydata piece
     let mut polydata = PolyDataPiece {
@@ -709,12 +776,26 @@ fn write_contact_surface_polydata(
     let mut face_distance = vec![0.0f64; surface.faces.len()];
     let mut face_angle = vec![0.0f64; surface.faces.len()];
     let mut is_paired = vec![0i32; surface.faces.len()];
+    let mut face_deviation = vec![0.0f64; surface.faces.len()];
+    let mut gap_margin = vec![0.0f64; surface.faces.len()];
+    let mut angle_margin = vec![0.0f64; surface.faces.len()];
+    let mut contact_state = vec![ContactState::Unpaired.code(); surface.faces.len()];
 
     for pair in &results.pairs {
-        if pair.surface_a_face_id < surface.faces.len() {
-            face_distance[pair.surface_a_face_id] = pair.distance;
-            face_angle[pair.surface_a_face_id] = pair.normal_angle;
-            is_paired[pair.surface_a_face_id] = 1;
+        // The slave surface is the second surface of the pairs
+        let face_id = if contact_role == 0 {
+            pair.surface_a_face_id
+        } else {
+            pair.surface_b_face_id
+        };
+        if face_id < surface.faces.len() {
+            face_distance[face_id] = pair.distance;
+            face_angle[face_id] = pair.normal_angle;
+            is_paired[face_id] = 1;
+            face_deviation[face_id] = opposition_deviation(pair.normal_angle);
+            gap_margin[face_id] = results.criteria.distance_margin(pair.distance);
+            angle_margin[face_id] = results.criteria.angle_margin(pair.normal_angle);
+            contact_state[face_id] = ContactState::of_distance(Some(pair.distance)).code();
         }
     }
 
@@ -745,9 +826,50 @@ fn write_contact_surface_polydata(
         data: IOBuffer::I32(is_paired),
     }));
 
+    // Add deviation from exactly opposed faces (see opposition_deviation)
+    polydata.data.cell.push(Attribute::DataArray(DataArray {
+        name: "OppositionDeviation".into(),
+        elem: ElementType::Scalars {
+            num_comp: 1,
+            lookup_table: None,
+        },
+        data: IOBuffer::F64(face_deviation),
+    }));
+
+    // Add how close each pair came to failing the gap and angle criteria
+    polydata.data.cell.push(Attribute::DataArray(DataArray {
+        name: "GapMargin".into(),
+        elem: ElementType::Scalars {
+            num_comp: 1,
+            lookup_table: None,
+        },
+        data: IOBuffer::F64(gap_margin),
+    }));
+
+    polydata.data.cell.push(Attribute::DataArray(DataArray {
+        name: "AngleMargin".into(),
+        elem: ElementType::Scalars {
+            num_comp: 1,
+            lookup_table: None,
+        },
+        data: IOBuffer::F64(angle_margin),
+    }));
+
+    // Add ContactState (names in the output legend)
+    polydata.data.cell.push(Attribute::DataArray(DataArray {
+        name: "ContactState".into(),
+        elem: ElementType::Scalars {
+            num_comp: 1,
+            lookup_table: None,
+        },
+        data: IOBuffer::I32(contact_state),
+    }));
+
+    expand_cell_data(&mut polydata.data, surface.num_faces(), &cell_faces);
+
     // Create VTK model
     let vtk = Vtk {
-        version: Version::new(vtk_version),
+        version: vtk_version.into(),
         title: format!("Contact surface: {}", surface.part_name),
         byte_order: ByteOrder::LittleEndian,
         data: DataSet::PolyData {
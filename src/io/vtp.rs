@@ -0,0 +1,295 @@
+//! VTP (VTK PolyData) writers for surface and contact zone outlines, skin
+//! holes and the auto-contact broad phase
+
+use crate::contact::broad_phase::BroadPhaseGraph;
+use crate::error::{ContactDetectorError, Result};
+use crate::io::vtk_version::VtkVersion;
+use crate::mesh::edges::EdgeLoop;
+use crate::mesh::surface::BlockClosure;
+use crate::mesh::types::{Mesh, Point, Vec3};
+use std::collections::BTreeMap;
+use std::path::Path;
+use vtkio::model::*;
+
+/// Outline loops of one side of a contact pair
+#[derive(Debug, Clone)]
+pub struct OutlineGroup {
+    /// Written as `ContactPairId` (1-based, as in the multiblock output)
+    pub pair_id: usize,
+
+    /// Written as `ContactRole` (0 = master/surface A, 1 = slave/surface B)
+    pub role: i32,
+
+    /// Loops of the group
+    pub loops: Vec<EdgeLoop>,
+}
+
+/// Write outline loops as polylines to a `.vtp` file
+///
+/// Each loop is one polyline cell (closed loops repeat their first point)
+/// carrying `ContactPairId`, `ContactRole`, `LoopLength` and `Closed`. Points
+/// carry the global node ID of the mesh node they come from.
+pub fn write_outlines_to_vtp(
+    groups: &[OutlineGroup],
+    mesh: &Mesh,
+    output_path: &Path,
+    vtk_version: Option<VtkVersion>,
+) -> Result<()> {
+    tracing::debug!(
+        "Writing {} outline group(s) to {}",
+        groups.len(),
+        output_path.display()
+    );
+
+    let mut loops = Vec::new();
+    let mut pair_ids = Vec::new();
+    let mut roles = Vec::new();
+    for group in groups {
+        for edge_loop in &group.loops {
+            loops.push(edge_loop);
+            pair_ids.push(group.pair_id as i32);
+            roles.push(group.role);
+        }
+    }
+
+    let cell_arrays = vec![
+        scalars("ContactPairId", IOBuffer::I32(pair_ids)),
+        scalars("ContactRole", IOBuffer::I32(roles)),
+    ];
+    write_loops_to_vtp(
+        &loops,
+        cell_arrays,
+        mesh,
+        "Contact outlines",
+        output_path,
+        VtkVersion::for_path(vtk_version, output_path)?,
+    )
+    .map_err(|e| ContactDetectorError::VtkError(format!("Failed to write outline polydata: {}", e)))
+}
+
+/// Write the holes in the skin of each block as polylines to a `.vtp` file
+///
+/// Each loop is one polyline cell carrying `SourceBlock` (index of the block
+/// in `closures`), `LoopLength` and `Closed`. Points carry the global node ID
+/// of the mesh node they come from.
+pub fn write_holes_to_vtp(
+    closures: &[BlockClosure],
+    mesh: &Mesh,
+    output_path: &Path,
+    vtk_version: Option<VtkVersion>,
+) -> Result<()> {
+    let mut loops = Vec::new();
+    let mut blocks = Vec::new();
+    for (block_idx, closure) in closures.iter().enumerate() {
+        for edge_loop in &closure.holes {
+            loops.push(edge_loop);
+            blocks.push(block_idx as i32);
+        }
+    }
+
+    let cell_arrays = vec![scalars("SourceBlock", IOBuffer::I32(blocks))];
+    write_loops_to_vtp(
+        &loops,
+        cell_arrays,
+        mesh,
+        "Skin holes",
+        output_path,
+        VtkVersion::for_path(vtk_version, output_path)?,
+    )
+    .map_err(|e| ContactDetectorError::VtkError(format!("Failed to write hole polydata: {}", e)))
+}
+
+/// Single-component cell or point array
+fn scalars(name: &str, data: IOBuffer) -> Attribute {
+    Attribute::DataArray(DataArray {
+        name: name.into(),
+        elem: ElementType::Scalars {
+            num_comp: 1,
+            lookup_table: None,
+        },
+        data,
+    })
+}
+
+/// Write loops as polylines, with the given cell arrays (one value per loop)
+/// followed by `LoopLength` and `Closed`
+fn write_loops_to_vtp(
+    loops: &[&EdgeLoop],
+    mut cell_arrays: Vec<Attribute>,
+    mesh: &Mesh,
+    title: &str,
+    output_path: &Path,
+    version: VtkVersion,
+) -> std::result::Result<(), vtkio::Error> {
+    // Compact point list of the nodes used by any loop
+    let mut point_of_node: BTreeMap<usize, u64> = BTreeMap::new();
+    for node in loops.iter().flat_map(|l| &l.nodes) {
+        let next = point_of_node.len() as u64;
+        point_of_node.entry(*node).or_insert(next);
+    }
+    let mut point_nodes = vec![0; point_of_node.len()];
+    for (&node, &point) in &point_of_node {
+        point_nodes[point as usize] = node;
+    }
+
+    let points: Vec<f64> = point_nodes
+        .iter()
+        .flat_map(|&n| {
+            let p: &Point = &mesh.nodes[n];
+            [p.x, p.y, p.z]
+        })
+        .collect();
+
+    let mut connectivity = Vec::new();
+    let mut offsets = Vec::new();
+    let mut lengths = Vec::new();
+    let mut closed = Vec::new();
+    for edge_loop in loops {
+        connectivity.extend(edge_loop.polyline().iter().map(|n| point_of_node[n]));
+        offsets.push(connectivity.len() as u64);
+        lengths.push(edge_loop.length);
+        closed.push(edge_loop.closed as i32);
+    }
+
+    let mut polydata = PolyDataPiece {
+        points: IOBuffer::F64(points),
+        verts: None,
+        lines: Some(VertexNumbers::XML {
+            connectivity,
+            offsets,
+        }),
+        polys: None,
+        strips: None,
+        data: Attributes::new(),
+    };
+    polydata.data.point.push(scalars(
+        "GlobalNodeId",
+        IOBuffer::I32(
+            point_nodes
+                .iter()
+                .map(|&n| mesh.global_node_id(n))
+                .collect(),
+        ),
+    ));
+    cell_arrays.push(scalars("LoopLength", IOBuffer::F64(lengths)));
+    cell_arrays.push(scalars("Closed", IOBuffer::I32(closed)));
+    polydata.data.cell = cell_arrays;
+
+    let vtk = Vtk {
+        version: version.into(),
+        title: title.to_string(),
+        byte_order: ByteOrder::LittleEndian,
+        data: DataSet::PolyData {
+            pieces: vec![Piece::Inline(Box::new(polydata))],
+            meta: None,
+        },
+        file_path: None,
+    };
+
+    vtk.export(output_path)
+}
+
+/// Write the broad phase of an auto-contact run to a `.vtp` file
+///
+/// Each surface's oriented bounding box is written as six quads carrying its
+/// `SurfaceId`; each surface pair is a line between the two box centers
+/// carrying its `BroadPhaseStatus` (see
+/// [`crate::contact::BroadPhaseStatus::code`]). Boxes
+/// have a status of -1 and lines a surface ID of -1, so either can be
+/// thresholded out. Surfaces without faces have no box and no lines.
+pub fn write_broad_phase_to_vtp(
+    graph: &BroadPhaseGraph,
+    output_path: &Path,
+    vtk_version: Option<VtkVersion>,
+) -> Result<()> {
+    // Box corners: bit k of the corner index selects the high side of axis k
+    const BOX_FACES: [[u64; 4]; 6] = [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ];
+
+    let mut points: Vec<f64> = Vec::new();
+    let mut center_point: Vec<Option<u64>> = vec![None; graph.surfaces.len()];
+    let mut polys_connectivity = Vec::new();
+    let mut polys_offsets = Vec::new();
+    let mut box_ids = Vec::new();
+    for (idx, surface) in graph.surfaces.iter().enumerate() {
+        let Some(bounds) = &surface.bounds else {
+            continue;
+        };
+        let obb = &bounds.obb;
+        let center = Point::from(obb.center);
+        center_point[idx] = Some((points.len() / 3) as u64);
+        points.extend_from_slice(&obb.center);
+
+        let base = (points.len() / 3) as u64;
+        for corner in 0..8 {
+            let p = (0..3).fold(center, |p, k| {
+                let sign = if corner & (1 << k) == 0 { -0.5 } else { 0.5 };
+                p + Vec3::from(obb.axes[k]) * (sign * obb.extents[k])
+            });
+            points.extend_from_slice(&[p.x, p.y, p.z]);
+        }
+        for face in BOX_FACES {
+            polys_connectivity.extend(face.iter().map(|c| base + c));
+            polys_offsets.push(polys_connectivity.len() as u64);
+            box_ids.push(idx as i32);
+        }
+    }
+
+    let mut lines_connectivity = Vec::new();
+    let mut lines_offsets = Vec::new();
+    let mut statuses: Vec<i32> = Vec::new();
+    for edge in &graph.edges {
+        if let (Some(a), Some(b)) = (center_point[edge.surface_a], center_point[edge.surface_b]) {
+            lines_connectivity.extend([a, b]);
+            lines_offsets.push(lines_connectivity.len() as u64);
+            statuses.push(edge.status.code());
+        }
+    }
+
+    // Line cells come before polygons in VTK's cell order
+    let mut surface_ids = vec![-1; statuses.len()];
+    surface_ids.extend(&box_ids);
+    statuses.resize(surface_ids.len(), -1);
+
+    let mut polydata = PolyDataPiece {
+        points: IOBuffer::F64(points),
+        verts: None,
+        lines: Some(VertexNumbers::XML {
+            connectivity: lines_connectivity,
+            offsets: lines_offsets,
+        }),
+        polys: Some(VertexNumbers::XML {
+            connectivity: polys_connectivity,
+            offsets: polys_offsets,
+        }),
+        strips: None,
+        data: Attributes::new(),
+    };
+    polydata.data.cell = vec![
+        scalars("SurfaceId", IOBuffer::I32(surface_ids)),
+        scalars("BroadPhaseStatus", IOBuffer::I32(statuses)),
+    ];
+
+    let vtk = Vtk {
+        version: VtkVersion::for_path(vtk_version, output_path)?.into(),
+        title: "Broad phase".to_string(),
+        byte_order: ByteOrder::LittleEndian,
+        data: DataSet::PolyData {
+            pieces: vec![Piece::Inline(Box::new(polydata))],
+            meta: None,
+        },
+        file_path: None,
+    };
+
+    vtk.export(output_path).map_err(|e| {
+        ContactDetectorError::VtkError(format!("Failed to write broad phase polydata: {}", e))
+    })?;
+
+    Ok(())
+}
@@ -1,28 +1,165 @@
 //! VTU (VTK Unstructured Grid) file writer
 
 use crate::error::{ContactDetectorError, Result};
-use crate::mesh::types::{Mesh, SurfaceMesh};
+use crate::io::legend::ContactState;
+use crate::io::vtk_version::VtkVersion;
+use crate::mesh::geometry::{face_polygons, Triangulation};
+use crate::io::vtu_stream::{
+    write_surface_to_vtu_streaming, CellArray, CellValues, STREAMING_THRESHOLD_FACES,
+};
+use crate::mesh::surface::{combine_surfaces, ElementSideIndex};
+use crate::mesh::types::{Mesh, Point, QuadFace, SurfaceMesh};
 use std::path::Path;
 use vtkio::model::*;
 
-/// Default VTK file format version (2.2 for broad compatibility)
-/// This version is compatible with ParaView 6.0.1 and most VTK-based tools
-pub const DEFAULT_VTK_VERSION: (u8, u8) = (2, 2);
+/// VTK cells for a set of quad faces, optionally split into triangles
+pub(crate) struct SurfaceCells {
+    /// Flattened cell connectivity
+    connectivity: Vec<u64>,
+
+    /// End offset of each cell in `connectivity`
+    offsets: Vec<u64>,
+
+    /// Type of each cell
+    types: Vec<CellType>,
+
+    /// Source face of each cell
+    cell_faces: Vec<usize>,
+}
+
+impl SurfaceCells {
+    /// Build one cell per face, or two triangles per quad when triangulating
+    pub(crate) fn new(
+        faces: &[QuadFace],
+        nodes: &[Point],
+        triangulation: Option<Triangulation>,
+    ) -> Self {
+        let mut cells = Self {
+            connectivity: Vec::new(),
+            offsets: Vec::new(),
+            types: Vec::new(),
+            cell_faces: Vec::new(),
+        };
+
+        for (face_idx, face) in faces.iter().enumerate() {
+            for polygon in face_polygons(face, nodes, triangulation) {
+                let cell_type = if polygon.len() == 3 {
+                    CellType::Triangle
+                } else {
+                    CellType::Quad
+                };
+                cells.push(&polygon, cell_type, face_idx);
+            }
+        }
+
+        cells
+    }
+
+    fn push(&mut self, node_ids: &[usize], cell_type: CellType, face_idx: usize) {
+        self.connectivity
+            .extend(node_ids.iter().map(|&id| id as u64));
+        self.offsets.push(self.connectivity.len() as u64);
+        self.types.push(cell_type);
+        self.cell_faces.push(face_idx);
+    }
+
+    /// Polygons for polydata pieces, with the source face of each cell
+    pub(crate) fn into_polys(self) -> (VertexNumbers, Vec<usize>) {
+        let polys = VertexNumbers::XML {
+            connectivity: self.connectivity,
+            offsets: self.offsets,
+        };
+        (polys, self.cell_faces)
+    }
+
+    /// Cells for unstructured grid pieces, with the source face of each cell
+    pub(crate) fn into_cells(self) -> (Cells, Vec<usize>) {
+        let cells = Cells {
+            cell_verts: VertexNumbers::XML {
+                connectivity: self.connectivity,
+                offsets: self.offsets,
+            },
+            types: self.types,
+        };
+        (cells, self.cell_faces)
+    }
+}
+
+/// Repeat per-face cell data so every cell carries the values of its source face
+///
+/// A no-op unless faces were triangulated. Values are copied, not split, so
+/// e.g. `area` on a triangle is the area of the whole source quad.
+pub(crate) fn expand_cell_data(data: &mut Attributes, num_faces: usize, cell_faces: &[usize]) {
+    if cell_faces.len() == num_faces || num_faces == 0 {
+        return;
+    }
+
+    fn repeat<T: Copy>(values: &[T], num_comp: usize, cell_faces: &[usize]) -> Vec<T> {
+        cell_faces
+            .iter()
+            .flat_map(|&face| &values[face * num_comp..(face + 1) * num_comp])
+            .copied()
+            .collect()
+    }
+
+    for attribute in &mut data.cell {
+        if let Attribute::DataArray(array) = attribute {
+            let num_comp = array.data.len() / num_faces;
+            array.data = match &array.data {
+                IOBuffer::U8(v) => IOBuffer::U8(repeat(v, num_comp, cell_faces)),
+                IOBuffer::I8(v) => IOBuffer::I8(repeat(v, num_comp, cell_faces)),
+                IOBuffer::U16(v) => IOBuffer::U16(repeat(v, num_comp, cell_faces)),
+                IOBuffer::I16(v) => IOBuffer::I16(repeat(v, num_comp, cell_faces)),
+                IOBuffer::U32(v) => IOBuffer::U32(repeat(v, num_comp, cell_faces)),
+                IOBuffer::I32(v) => IOBuffer::I32(repeat(v, num_comp, cell_faces)),
+                IOBuffer::U64(v) => IOBuffer::U64(repeat(v, num_comp, cell_faces)),
+                IOBuffer::I64(v) => IOBuffer::I64(repeat(v, num_comp, cell_faces)),
+                IOBuffer::F32(v) => IOBuffer::F32(repeat(v, num_comp, cell_faces)),
+                IOBuffer::F64(v) => IOBuffer::F64(repeat(v, num_comp, cell_faces)),
+                IOBuffer::Bit(_) => unreachable!("bit arrays are not written as cell data"),
+            };
+        }
+    }
+}
 
 /// Write a surface mesh to a VTU file
+///
+/// Surfaces of [`STREAMING_THRESHOLD_FACES`] faces or more are written by the
+/// streaming writer to bound memory use.
+#[tracing::instrument(skip_all, fields(surface = %surface.part_name))]
 pub fn write_surface_to_vtu(
     surface: &SurfaceMesh,
     output_path: &Path,
-    vtk_version: Option<(u8, u8)>,
+    vtk_version: Option<VtkVersion>,
+    triangulation: Option<Triangulation>,
 ) -> Result<()> {
-    let version = vtk_version.unwrap_or(DEFAULT_VTK_VERSION);
-    log::info!(
-        "Writing surface '{}' with {} faces to {:?} (VTK version {}.{})",
+    if surface.num_faces() >= STREAMING_THRESHOLD_FACES {
+        let arrays = [
+            CellArray {
+                name: "normals",
+                values: CellValues::Vectors(&surface.face_normals),
+            },
+            CellArray {
+                name: "area",
+                values: CellValues::F64(&surface.face_areas),
+            },
+        ];
+        return write_surface_to_vtu_streaming(
+            surface,
+            &arrays,
+            output_path,
+            vtk_version,
+            triangulation,
+        );
+    }
+
+    let version = VtkVersion::for_path(vtk_version, output_path)?;
+    tracing::info!(
+        "Writing surface '{}' with {} faces to {:?} (VTK version {})",
         surface.part_name,
         surface.num_faces(),
         output_path,
-        version.0,
-        version.1
+        version
     );
 
     // Create point array from nodes
@@ -32,25 +169,9 @@ pub fn write_surface_to_vtu(
         .flat_map(|p| vec![p.x, p.y, p.z])
         .collect();
 
-    // Create cell connectivity for quad faces
-    let mut connectivity = Vec::new();
-    for face in &surface.faces {
-        connectivity.extend_from_slice(&face.node_ids.map(|id| id as u64));
-    }
-
-    // All cells are quads (VTK_QUAD = 9)
-    let cell_types = vec![CellType::Quad; surface.faces.len()];
-
-    // Create cells with offsets
-    let cells = Cells {
-        cell_verts: VertexNumbers::XML {
-            connectivity,
-            offsets: (0..surface.faces.len())
-                .map(|i| ((i + 1) * 4) as u64)
-                .collect(),
-        },
-        types: cell_types,
-    };
+    // Quad cells (VTK_QUAD = 9), or triangles (VTK_TRIANGLE = 5) if requested
+    let (cells, cell_faces) =
+        SurfaceCells::new(&surface.faces, &surface.nodes, triangulation).into_cells();
 
     // Create unstructured grid piece
     let mut ugrid = UnstructuredGridPiece {
@@ -84,9 +205,11 @@ pub fn write_surface_to_vtu(
         data: IOBuffer::F64(area_data),
     }));
 
+    expand_cell_data(&mut ugrid.data, surface.num_faces(), &cell_faces);
+
     // Create the Vtk model
     let vtk = Vtk {
-        version: Version::new(version),
+        version: version.into(),
         title: format!("Surface mesh: {}", surface.part_name),
         byte_order: ByteOrder::LittleEndian,
         data: DataSet::UnstructuredGrid {
@@ -100,17 +223,19 @@ pub fn write_surface_to_vtu(
     vtk.export(output_path)
         .map_err(|e| ContactDetectorError::VtkError(format!("Failed to write VTU file: {}", e)))?;
 
-    log::info!("Successfully wrote VTU file to {:?}", output_path);
+    tracing::info!("Successfully wrote VTU file to {:?}", output_path);
 
     Ok(())
 }
 
 /// Write multiple surface meshes to separate VTU files
 /// Each surface is written to <output_dir>/<part_name>.vtu
+#[tracing::instrument(skip_all, fields(count = surfaces.len()))]
 pub fn write_surfaces_to_vtu(
     surfaces: &[SurfaceMesh],
     output_dir: &Path,
-    vtk_version: Option<(u8, u8)>,
+    vtk_version: Option<VtkVersion>,
+    triangulation: Option<Triangulation>,
 ) -> Result<()> {
     // Create output directory if it doesn't exist
     std::fs::create_dir_all(output_dir)?;
@@ -118,135 +243,307 @@ pub fn write_surfaces_to_vtu(
     for surface in surfaces {
         let filename = format!("{}.vtu", sanitize_filename(&surface.part_name));
         let output_path = output_dir.join(filename);
-        write_surface_to_vtu(surface, &output_path, vtk_version)?;
+        write_surface_to_vtu(surface, &output_path, vtk_version, triangulation)?;
     }
 
     Ok(())
 }
 
-/// Write surface mesh with contact pair metadata to VTU
-pub fn write_surface_with_contact_metadata(
-    surface: &SurfaceMesh,
-    results: &crate::contact::ContactResults,
-    _metrics: &crate::contact::SurfaceMetrics,
+/// Write all surfaces extracted from a mesh to one VTU file
+///
+/// Besides `normals` and `area`, each cell carries its provenance:
+/// `SourceBlock` (index of the element block in name order), `PatchId`
+/// (index of the surface in `surfaces`), `ElementId` (global ID of the
+/// element the face lies on) and `ElementSide` (Exodus side number 1-6).
+/// Faces that lie on no element side of `mesh` have -1 in the last three.
+///
+/// Skins of [`STREAMING_THRESHOLD_FACES`] faces or more are written by the
+/// streaming writer to bound memory use.
+#[tracing::instrument(skip_all, fields(count = surfaces.len()))]
+pub fn write_merged_skin_to_vtu(
+    mesh: &Mesh,
+    surfaces: &[SurfaceMesh],
     output_path: &Path,
-    vtk_version: Option<(u8, u8)>,
+    vtk_version: Option<VtkVersion>,
+    triangulation: Option<Triangulation>,
 ) -> Result<()> {
-    let version = vtk_version.unwrap_or(DEFAULT_VTK_VERSION);
-    log::info!(
-        "Writing surface '{}' with contact metadata to {:?} (VTK version {}.{})",
-        surface.part_name,
-        output_path,
-        version.0,
-        version.1
-    );
+    let refs: Vec<&SurfaceMesh> = surfaces.iter().collect();
+    let skin = combine_surfaces("skin".to_string(), &refs);
+
+    let mut block_names: Vec<&String> = mesh.element_blocks.keys().collect();
+    block_names.sort();
+    let mut block_of_element = vec![-1i32; mesh.num_elements()];
+    for (block_idx, name) in block_names.iter().enumerate() {
+        for &elem_idx in &mesh.element_blocks[*name] {
+            if let Some(block) = block_of_element.get_mut(elem_idx) {
+                *block = block_idx as i32;
+            }
+        }
+    }
 
-    // Create point array from nodes
-    let points: Vec<f64> = surface
-        .nodes
+    let index = ElementSideIndex::new(mesh);
+    let mut source_block = Vec::with_capacity(skin.num_faces());
+    let mut element_id = Vec::with_capacity(skin.num_faces());
+    let mut element_side = Vec::with_capacity(skin.num_faces());
+    for face in &skin.faces {
+        match index.get(face) {
+            Some((elem_idx, side)) => {
+                source_block.push(block_of_element[elem_idx]);
+                element_id.push(mesh.global_elem_id(elem_idx));
+                element_side.push(side as i32);
+            }
+            None => {
+                source_block.push(-1);
+                element_id.push(-1);
+                element_side.push(-1);
+            }
+        }
+    }
+    let patch_id: Vec<i32> = surfaces
         .iter()
-        .flat_map(|p| vec![p.x, p.y, p.z])
+        .enumerate()
+        .flat_map(|(idx, s)| std::iter::repeat_n(idx as i32, s.num_faces()))
         .collect();
 
-    // Create cell connectivity for quad faces
-    let mut connectivity = Vec::new();
-    for face in &surface.faces {
-        connectivity.extend_from_slice(&face.node_ids.map(|id| id as u64));
+    let arrays = [
+        CellArray {
+            name: "normals",
+            values: CellValues::Vectors(&skin.face_normals),
+        },
+        CellArray {
+            name: "area",
+            values: CellValues::F64(&skin.face_areas),
+        },
+        CellArray {
+            name: "SourceBlock",
+            values: CellValues::I32(&source_block),
+        },
+        CellArray {
+            name: "PatchId",
+            values: CellValues::I32(&patch_id),
+        },
+        CellArray {
+            name: "ElementId",
+            values: CellValues::I32(&element_id),
+        },
+        CellArray {
+            name: "ElementSide",
+            values: CellValues::I32(&element_side),
+        },
+    ];
+    if skin.num_faces() >= STREAMING_THRESHOLD_FACES {
+        return write_surface_to_vtu_streaming(
+            &skin,
+            &arrays,
+            output_path,
+            vtk_version,
+            triangulation,
+        );
     }
 
-    // All cells are quads
-    let cell_types = vec![CellType::Quad; surface.faces.len()];
-
-    // Create cells
-    let cells = Cells {
-        cell_verts: VertexNumbers::XML {
-            connectivity,
-            offsets: (0..surface.faces.len())
-                .map(|i| ((i + 1) * 4) as u64)
-                .collect(),
-        },
-        types: cell_types,
-    };
+    let version = VtkVersion::for_path(vtk_version, output_path)?;
+    tracing::info!(
+        "Writing skin of {} surfaces with {} faces to {:?} (VTK version {})",
+        surfaces.len(),
+        skin.num_faces(),
+        output_path,
+        version
+    );
 
-    // Create unstructured grid piece
+    let points: Vec<f64> = skin.nodes.iter().flat_map(|p| [p.x, p.y, p.z]).collect();
+    let (cells, cell_faces) =
+        SurfaceCells::new(&skin.faces, &skin.nodes, triangulation).into_cells();
     let mut ugrid = UnstructuredGridPiece {
         points: IOBuffer::F64(points),
         cells,
         data: Attributes::new(),
     };
-
-    // Add face normals as cell data
-    let normal_data: Vec<f64> = surface
-        .face_normals
+    ugrid.data.cell = arrays
         .iter()
-        .flat_map(|n| vec![n.x, n.y, n.z])
+        .map(|array| {
+            let (elem, data) = match &array.values {
+                CellValues::Vectors(values) => (
+                    ElementType::Vectors,
+                    IOBuffer::F64(values.iter().flat_map(|v| [v.x, v.y, v.z]).collect()),
+                ),
+                CellValues::F64(values) => (scalar_element(), IOBuffer::F64(values.to_vec())),
+                CellValues::I32(values) => (scalar_element(), IOBuffer::I32(values.to_vec())),
+                CellValues::Computed(value) => (
+                    scalar_element(),
+                    IOBuffer::F64((0..skin.num_faces()).map(value).collect()),
+                ),
+            };
+            Attribute::DataArray(DataArray {
+                name: array.name.into(),
+                elem,
+                data,
+            })
+        })
         .collect();
+    expand_cell_data(&mut ugrid.data, skin.num_faces(), &cell_faces);
 
-    ugrid.data.cell.push(Attribute::DataArray(DataArray {
-        name: "normals".into(),
-        elem: ElementType::Vectors,
-        data: IOBuffer::F64(normal_data),
-    }));
-
-    // Add face areas
-    ugrid.data.cell.push(Attribute::DataArray(DataArray {
-        name: "area".into(),
-        elem: ElementType::Scalars {
-            num_comp: 1,
-            lookup_table: None,
+    let vtk = Vtk {
+        version: version.into(),
+        title: "Skin mesh".to_string(),
+        byte_order: ByteOrder::LittleEndian,
+        data: DataSet::UnstructuredGrid {
+            pieces: vec![Piece::Inline(Box::new(ugrid))],
+            meta: None,
         },
-        data: IOBuffer::F64(surface.face_areas.clone()),
-    }));
+        file_path: None,
+    };
 
-    // Create a map from face index to contact pair
-    let mut face_to_pair = vec![-1i32; surface.faces.len()];
-    let mut face_distance = vec![0.0f64; surface.faces.len()];
-    let mut face_angle = vec![0.0f64; surface.faces.len()];
+    vtk.export(output_path)
+        .map_err(|e| ContactDetectorError::VtkError(format!("Failed to write VTU file: {}", e)))?;
 
+    tracing::info!("Successfully wrote VTU file to {:?}", output_path);
+
+    Ok(())
+}
+
+/// Element type of a single-component cell array
+fn scalar_element() -> ElementType {
+    ElementType::Scalars {
+        num_comp: 1,
+        lookup_table: None,
+    }
+}
+
+/// Share of paired faces at each end of the distance range kept at full
+/// resolution in decimated visualization copies
+const EXTREME_GAP_FRACTION: f64 = 0.01;
+
+/// Write a decimated visualization copy of a surface with contact metadata
+///
+/// Meant for surfaces too large to stay interactive in ParaView, next to the
+/// full-resolution file: the surface is decimated to about `target_faces`
+/// faces (see [`crate::mesh::decimate`]) without merging paired and unpaired
+/// faces, and the paired faces with the smallest and largest distances are
+/// kept as they are. The cell arrays are named as in
+/// [`AttributeSet::contact_defaults`](crate::io::AttributeSet::contact_defaults),
+/// with `contact_gap` and `contact_angle` averaged (area-weighted) over the
+/// merged faces, `pair_id` taken from the first of them, and `source_faces`
+/// giving their number.
+/// Returns the number of faces written.
+#[tracing::instrument(skip_all, fields(surface = %surface.part_name))]
+pub fn write_decimated_contact_vtu(
+    surface: &SurfaceMesh,
+    results: &crate::contact::ContactResults,
+    target_faces: usize,
+    output_path: &Path,
+    vtk_version: Option<VtkVersion>,
+) -> Result<usize> {
+    let version = VtkVersion::for_path(vtk_version, output_path)?;
+
+    let mut face_to_pair = vec![-1i32; surface.faces.len()];
     for (pair_idx, pair) in results.pairs.iter().enumerate() {
         face_to_pair[pair.surface_a_face_id] = pair_idx as i32;
-        face_distance[pair.surface_a_face_id] = pair.distance;
-        face_angle[pair.surface_a_face_id] = pair.normal_angle;
     }
 
-    // Add contact pair ID as cell data
-    ugrid.data.cell.push(Attribute::DataArray(DataArray {
-        name: "pair_id".into(),
-        elem: ElementType::Scalars {
-            num_comp: 1,
-            lookup_table: None,
-        },
-        data: IOBuffer::I32(face_to_pair),
-    }));
+    // Keep the paired faces at both ends of the distance range
+    let mut by_distance: Vec<usize> = (0..results.pairs.len()).collect();
+    by_distance.sort_by(|&a, &b| {
+        results.pairs[a]
+            .distance
+            .total_cmp(&results.pairs[b].distance)
+    });
+    let num_extreme = (by_distance.len() as f64 * EXTREME_GAP_FRACTION).ceil() as usize;
+    let mut keep_faces = vec![false; surface.faces.len()];
+    for &pair_idx in by_distance
+        .iter()
+        .take(num_extreme)
+        .chain(by_distance.iter().rev().take(num_extreme))
+    {
+        keep_faces[results.pairs[pair_idx].surface_a_face_id] = true;
+    }
 
-    // Add distance as cell data
-    ugrid.data.cell.push(Attribute::DataArray(DataArray {
-        name: "distance".into(),
-        elem: ElementType::Scalars {
-            num_comp: 1,
-            lookup_table: None,
-        },
-        data: IOBuffer::F64(face_distance),
-    }));
+    let face_groups: Vec<u32> = face_to_pair.iter().map(|&p| u32::from(p >= 0)).collect();
+    let decimation = crate::mesh::decimate_surface_grouped(
+        surface,
+        target_faces,
+        &face_groups,
+        &keep_faces,
+    );
+    let decimated = &decimation.surface;
+
+    tracing::info!(
+        "Writing decimated copy of '{}' ({} of {} faces) to {:?} (VTK version {})",
+        surface.part_name,
+        decimated.num_faces(),
+        surface.num_faces(),
+        output_path,
+        version
+    );
+
+    // Per-face values of the merged faces
+    let mut pair_ids = Vec::with_capacity(decimated.num_faces());
+    let mut distances = Vec::with_capacity(decimated.num_faces());
+    let mut angles = Vec::with_capacity(decimated.num_faces());
+    let mut source_counts = Vec::with_capacity(decimated.num_faces());
+    let mut states = Vec::with_capacity(decimated.num_faces());
+    for sources in &decimation.source_faces {
+        let pair_id = face_to_pair[sources[0]];
+        let (mut distance, mut angle, mut area) = (0.0, 0.0, 0.0);
+        if pair_id >= 0 {
+            for &face in sources {
+                if let Some(pair) = results.pairs.get(face_to_pair[face] as usize) {
+                    distance += pair.distance * surface.face_areas[face];
+                    angle += pair.normal_angle * surface.face_areas[face];
+                    area += surface.face_areas[face];
+                }
+            }
+        }
+        let mean_distance = (area > 0.0).then(|| distance / area);
+        pair_ids.push(pair_id);
+        states.push(ContactState::of_distance(mean_distance).code());
+        distances.push(mean_distance.unwrap_or(0.0));
+        angles.push(if area > 0.0 { angle / area } else { 0.0 });
+        source_counts.push(sources.len() as i32);
+    }
+
+    let points: Vec<f64> = decimated
+        .nodes
+        .iter()
+        .flat_map(|p| vec![p.x, p.y, p.z])
+        .collect();
+    let (cells, _) = SurfaceCells::new(&decimated.faces, &decimated.nodes, None).into_cells();
+    let mut ugrid = UnstructuredGridPiece {
+        points: IOBuffer::F64(points),
+        cells,
+        data: Attributes::new(),
+    };
 
-    // Add normal angle as cell data
     ugrid.data.cell.push(Attribute::DataArray(DataArray {
-        name: "normal_angle".into(),
-        elem: ElementType::Scalars {
-            num_comp: 1,
-            lookup_table: None,
-        },
-        data: IOBuffer::F64(face_angle),
+        name: "normals".into(),
+        elem: ElementType::Vectors,
+        data: IOBuffer::F64(
+            decimated
+                .face_normals
+                .iter()
+                .flat_map(|n| vec![n.x, n.y, n.z])
+                .collect(),
+        ),
     }));
+    let scalars = |name: &str, data: IOBuffer| {
+        Attribute::DataArray(DataArray {
+            name: name.into(),
+            elem: ElementType::Scalars {
+                num_comp: 1,
+                lookup_table: None,
+            },
+            data,
+        })
+    };
+    ugrid.data.cell.push(scalars("area", IOBuffer::F64(decimated.face_areas.clone())));
+    ugrid.data.cell.push(scalars("pair_id", IOBuffer::I32(pair_ids)));
+    ugrid.data.cell.push(scalars("contact_gap", IOBuffer::F64(distances)));
+    ugrid.data.cell.push(scalars("contact_angle", IOBuffer::F64(angles)));
+    ugrid.data.cell.push(scalars("contact_state", IOBuffer::I32(states)));
+    ugrid.data.cell.push(scalars("source_faces", IOBuffer::I32(source_counts)));
 
-    // Note: Surface-level metrics are printed to console and can be accessed via the metrics parameter
-    // VTK file format limitations prevent easy embedding of arbitrary metadata
-    // Cell data (per-face data) is included above
-
-    // Create the Vtk model
     let vtk = Vtk {
-        version: Version::new(version),
-        title: format!("Surface mesh with contact data: {}", surface.part_name),
+        version: version.into(),
+        title: format!("Decimated surface with contact data: {}", surface.part_name),
         byte_order: ByteOrder::LittleEndian,
         data: DataSet::UnstructuredGrid {
             pieces: vec![Piece::Inline(Box::new(ugrid))],
@@ -255,16 +552,10 @@ pub fn write_surface_with_contact_metadata(
         file_path: None,
     };
 
-    // Write to file
     vtk.export(output_path)
         .map_err(|e| ContactDetectorError::VtkError(format!("Failed to write VTU file: {}", e)))?;
 
-    log::info!(
-        "Successfully wrote VTU file with contact metadata to {:?}",
-        output_path
-    );
-
-    Ok(())
+    Ok(decimated.num_faces())
 }
 
 /// Sanitize a string to be a valid filename
@@ -286,6 +577,8 @@ fn sanitize_filename(name: &str) -> String {
 /// for spatial context. Each face is labeled with a contact_region_id:
 /// - 0: Non-contact skin faces
 /// - Positive values: Contact region IDs (1, 2, 3, ...)
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all)]
 pub fn write_contact_surfaces_with_skin(
     _surface_a: &SurfaceMesh,
     _surface_b: &SurfaceMesh,
@@ -295,14 +588,14 @@ pub fn write_contact_surfaces_with_skin(
     surface_b_name: &str,
     contact_region_id: usize,
     output_path: &Path,
-    vtk_version: Option<(u8, u8)>,
+    vtk_version: Option<VtkVersion>,
+    triangulation: Option<Triangulation>,
 ) -> Result<()> {
-    let version = vtk_version.unwrap_or(DEFAULT_VTK_VERSION);
-    log::info!(
-        "Writing contact surfaces with skin overlay to {:?} (VTK version {}.{})",
+    let version = VtkVersion::for_path(vtk_version, output_path)?;
+    tracing::info!(
+        "Writing contact surfaces with skin overlay to {:?} (VTK version {})",
         output_path,
-        version.0,
-        version.1
+        version
     );
 
     // Combine all surfaces into a single mesh
@@ -335,10 +628,9 @@ pub fn write_contact_surfaces_with_skin(
         all_areas.extend_from_slice(&surface.face_areas);
 
         // Determine contact region ID for this surface
-        let region_id = if surface.part_name == surface_a_name {
-            contact_region_id as i32 // Surface A gets the contact region ID
-        } else if surface.part_name == surface_b_name {
-            contact_region_id as i32 // Surface B gets the same contact region ID
+        // Surfaces A and B share the contact region ID
+        let region_id = if surface.part_name == surface_a_name || surface.part_name == surface_b_name {
+            contact_region_id as i32
         } else {
             0 // Non-contact surfaces get 0
         };
@@ -358,25 +650,8 @@ pub fn write_contact_surfaces_with_skin(
         .flat_map(|p| vec![p.x, p.y, p.z])
         .collect();
 
-    // Create cell connectivity for quad faces
-    let mut connectivity = Vec::new();
-    for face in &all_faces {
-        connectivity.extend_from_slice(&face.node_ids.map(|id| id as u64));
-    }
-
-    // All cells are quads
-    let cell_types = vec![CellType::Quad; all_faces.len()];
-
-    // Create cells
-    let cells = Cells {
-        cell_verts: VertexNumbers::XML {
-            connectivity,
-            offsets: (0..all_faces.len())
-                .map(|i| ((i + 1) * 4) as u64)
-                .collect(),
-        },
-        types: cell_types,
-    };
+    // Create cells (quads, or triangles if requested)
+    let (cells, cell_faces) = SurfaceCells::new(&all_faces, &all_nodes, triangulation).into_cells();
 
     // Create unstructured grid piece
     let mut ugrid = UnstructuredGridPiece {
@@ -422,6 +697,7 @@ pub fn write_contact_surfaces_with_skin(
     let mut face_to_distance = vec![0.0f64; all_faces.len()];
     let mut face_to_angle = vec![0.0f64; all_faces.len()];
     let mut is_paired = vec![0i32; all_faces.len()];
+    let mut contact_state = vec![ContactState::Unpaired.code(); all_faces.len()];
 
     // Track which faces in the combined mesh correspond to surface A
     let mut surface_a_start = 0;
@@ -442,6 +718,7 @@ pub fn write_contact_surfaces_with_skin(
             face_to_distance[face_idx] = pair.distance;
             face_to_angle[face_idx] = pair.normal_angle;
             is_paired[face_idx] = 1;
+            contact_state[face_idx] = ContactState::of_distance(Some(pair.distance)).code();
         }
     }
 
@@ -475,11 +752,24 @@ pub fn write_contact_surfaces_with_skin(
         data: IOBuffer::I32(is_paired),
     }));
 
+    // Add contact state field (names in the output legend)
+    ugrid.data.cell.push(Attribute::DataArray(DataArray {
+        name: "contact_state".into(),
+        elem: ElementType::Scalars {
+            num_comp: 1,
+            lookup_table: None,
+        },
+        data: IOBuffer::I32(contact_state),
+    }));
+
+    expand_cell_data(&mut ugrid.data, all_faces.len(), &cell_faces);
+
     // Create the Vtk model
     let vtk = Vtk {
-        version: Version::new(version),
+        version: version.into(),
+        // Legacy VTK headers are read as ASCII, so no "↔" here
         title: format!(
-            "Contact surfaces with skin: {} ↔ {}",
+            "Contact surfaces with skin: {} <-> {}",
             surface_a_name, surface_b_name
         ),
         byte_order: ByteOrder::LittleEndian,
@@ -494,7 +784,7 @@ pub fn write_contact_surfaces_with_skin(
     vtk.export(output_path)
         .map_err(|e| ContactDetectorError::VtkError(format!("Failed to write VTU file: {}", e)))?;
 
-    log::info!(
+    tracing::info!(
         "Successfully wrote VTU file with contact surfaces and skin to {:?}",
         output_path
     );
@@ -504,15 +794,16 @@ pub fn write_contact_surfaces_with_skin(
 
 /// Write a full mesh (with hex elements) to a VTK file
 ///
-/// This is useful for visualizing synthetic meshes or full 3D meshes.
-pub fn write_vtk(mesh: &Mesh, output_path: &Path, vtk_version: Option<(u8, u8)>) -> Result<()> {
-    let version = vtk_version.unwrap_or(DEFAULT_VTK_VERSION);
-    log::info!(
-        "Writing mesh with {} elements to {:?} (VTK version {}.{})",
+/// This is useful for visualizing synthetic meshes or full 3D meshes. Nodal
+/// and element fields are written as point and cell data.
+#[tracing::instrument(skip_all)]
+pub fn write_vtk(mesh: &Mesh, output_path: &Path, vtk_version: Option<VtkVersion>) -> Result<()> {
+    let version = VtkVersion::for_path(vtk_version, output_path)?;
+    tracing::info!(
+        "Writing mesh with {} elements to {:?} (VTK version {})",
         mesh.num_elements(),
         output_path,
-        version.0,
-        version.1
+        version
     );
 
     // Create point array from nodes
@@ -543,15 +834,48 @@ pub fn write_vtk(mesh: &Mesh, output_path: &Path, vtk_version: Option<(u8, u8)>)
     };
 
     // Create unstructured grid piece
-    let ugrid = UnstructuredGridPiece {
+    let mut ugrid = UnstructuredGridPiece {
         points: IOBuffer::F64(points),
         cells,
         data: Attributes::new(),
     };
 
+    // Nodal and element fields, in name order
+    let fields = |fields: &std::collections::HashMap<String, Vec<f64>>, len: usize| {
+        let mut names: Vec<&String> = fields.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .filter(|name| {
+                let ok = fields[*name].len() == len;
+                if !ok {
+                    tracing::warn!(
+                        "Not writing field '{}': {} values for {}",
+                        name,
+                        fields[*name].len(),
+                        len
+                    );
+                }
+                ok
+            })
+            .map(|name| {
+                Attribute::DataArray(DataArray {
+                    name: name.clone(),
+                    elem: ElementType::Scalars {
+                        num_comp: 1,
+                        lookup_table: None,
+                    },
+                    data: IOBuffer::F64(fields[name].clone()),
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+    ugrid.data.point = fields(&mesh.node_fields, mesh.num_nodes());
+    ugrid.data.cell = fields(&mesh.element_fields, mesh.num_elements());
+
     // Create the Vtk model
     let vtk = Vtk {
-        version: Version::new(version),
+        version: version.into(),
         title: "Hexahedral mesh".to_string(),
         byte_order: ByteOrder::LittleEndian,
         data: DataSet::UnstructuredGrid {
@@ -565,7 +889,7 @@ pub fn write_vtk(mesh: &Mesh, output_path: &Path, vtk_version: Option<(u8, u8)>)
     vtk.export(output_path)
         .map_err(|e| ContactDetectorError::VtkError(format!("Failed to write VTK file: {}", e)))?;
 
-    log::info!("Successfully wrote VTK file to {:?}", output_path);
+    tracing::info!("Successfully wrote VTK file to {:?}", output_path);
 
     Ok(())
 }
@@ -592,6 +916,7 @@ mod tests {
             face_centroids: vec![Point::new(0.5, 0.5, 0.0)],
             face_areas: vec![1.0],
             nodes,
+            face_tags: Default::default(),
         }
     }
 
@@ -602,19 +927,102 @@ mod tests {
         assert_eq!(sanitize_filename("Normal_Name"), "Normal_Name");
     }
 
+    #[test]
+    fn test_triangulated_cells_repeat_cell_data() {
+        let mut surface = make_test_surface();
+        surface.nodes.push(Point::new(2.0, 0.0, 0.0));
+        surface.nodes.push(Point::new(2.0, 1.0, 0.0));
+        surface.faces.push(QuadFace::new([1, 4, 5, 2]));
+
+        let cells = SurfaceCells::new(
+            &surface.faces,
+            &surface.nodes,
+            Some(Triangulation::Diagonal02),
+        );
+        let (cells, cell_faces) = cells.into_cells();
+        assert_eq!(cell_faces, vec![0, 0, 1, 1]);
+        assert_eq!(cells.types, vec![CellType::Triangle; 4]);
+
+        let mut data = Attributes::new();
+        data.cell.push(Attribute::DataArray(DataArray {
+            name: "normals".into(),
+            elem: ElementType::Vectors,
+            data: IOBuffer::F64(vec![0.0, 0.0, 1.0, 0.0, 0.0, -1.0]),
+        }));
+        expand_cell_data(&mut data, surface.num_faces(), &cell_faces);
+
+        let Attribute::DataArray(normals) = &data.cell[0] else {
+            panic!("expected a data array");
+        };
+        assert_eq!(
+            normals.data,
+            IOBuffer::F64(vec![
+                0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, -1.0, 0.0, 0.0, -1.0
+            ])
+        );
+    }
+
     #[test]
     fn test_write_surface_to_vtu() {
         let surface = make_test_surface();
         let temp_dir = std::env::temp_dir();
         let output_path = temp_dir.join("test_surface.vtu");
 
-        let result = write_surface_to_vtu(&surface, &output_path, None);
+        let result = write_surface_to_vtu(&surface, &output_path, None, None);
         assert!(result.is_ok());
 
         // Clean up
         let _ = std::fs::remove_file(&output_path);
     }
 
+    #[test]
+    fn test_write_decimated_contact_vtu() {
+        use crate::contact::{ContactCriteria, ContactPair, ContactResults};
+
+        // 30 x 30 plate, paired on its left half with varying distance
+        let n = 30;
+        let mut surface = SurfaceMesh::new("Plate".to_string());
+        for j in 0..=n {
+            for i in 0..=n {
+                surface.nodes.push(Point::new(i as f64, j as f64, 0.0));
+            }
+        }
+        let mut results = ContactResults::new(
+            "Plate".to_string(),
+            "Other".to_string(),
+            ContactCriteria::new(0.01, 0.01, 180.0),
+        );
+        for j in 0..n {
+            for i in 0..n {
+                let n0 = j * (n + 1) + i;
+                let centroid = Point::new(i as f64 + 0.5, j as f64 + 0.5, 0.0);
+                if i < n / 2 {
+                    results.pairs.push(ContactPair {
+                        surface_a_face_id: surface.faces.len(),
+                        surface_b_face_id: 0,
+                        distance: (i + j) as f64 * 1e-4,
+                        normal_angle: 180.0,
+                        contact_point: centroid,
+                    });
+                }
+                surface
+                    .faces
+                    .push(QuadFace::new([n0, n0 + 1, n0 + n + 2, n0 + n + 1]));
+                surface.face_centroids.push(centroid);
+                surface.face_normals.push(Vec3::new(0.0, 0.0, 1.0));
+                surface.face_areas.push(1.0);
+            }
+        }
+
+        let output_path = std::env::temp_dir().join("test_decimated_contact.vtu");
+        let num_faces =
+            write_decimated_contact_vtu(&surface, &results, 100, &output_path, None).unwrap();
+        assert!(num_faces < surface.num_faces() / 4, "{} faces", num_faces);
+        assert!(output_path.exists());
+
+        let _ = std::fs::remove_file(&output_path);
+    }
+
     #[test]
     fn test_write_contact_surfaces_with_skin() {
         use crate::contact::{ContactCriteria, ContactPair, ContactResults};
@@ -655,6 +1063,7 @@ mod tests {
             1,
             &output_path,
             None,
+            None,
         );
 
         assert!(result.is_ok());
@@ -710,6 +1119,7 @@ mod tests {
             1,
             &output_path,
             None,
+            None,
         );
 
         assert!(result.is_ok());
@@ -0,0 +1,363 @@
+//! Streaming VTU writer for very large surfaces
+//!
+//! The vtkio-based writers build every point, connectivity and attribute
+//! buffer in memory before serializing it, which for multi-million face
+//! surfaces needs several times the memory of the surface itself. This writer
+//! computes the size of each array up front, writes the XML header with their
+//! offsets into an appended raw data section, then serializes the arrays face
+//! by face through a buffered writer. Per-face values are read from the
+//! caller's slices or computed on the fly, so only the surface itself needs
+//! to be in memory.
+//!
+//! VTK and ParaView read the raw appended section by offset; general-purpose
+//! XML parsers may reject it, as the binary data is not valid XML text.
+
+use crate::error::Result;
+use crate::io::vtk_version::VtkVersion;
+use crate::mesh::geometry::{face_polygons, Triangulation};
+use crate::mesh::types::{QuadFace, SurfaceMesh, Vec3};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Surfaces with at least this many faces are written by the streaming writer
+/// in [`crate::io::write_surface_to_vtu`]
+pub const STREAMING_THRESHOLD_FACES: usize = 1_000_000;
+
+/// VTK cell type codes
+const VTK_TRIANGLE: u8 = 5;
+const VTK_QUAD: u8 = 9;
+
+/// Values of a per-face cell array, one per face of the surface
+pub enum CellValues<'a> {
+    /// Stored scalars
+    F64(&'a [f64]),
+
+    /// Stored integer scalars
+    I32(&'a [i32]),
+
+    /// Stored vectors
+    Vectors(&'a [Vec3]),
+
+    /// Scalars computed from the face index while writing
+    Computed(Box<dyn Fn(usize) -> f64 + 'a>),
+}
+
+/// A named per-face cell array
+pub struct CellArray<'a> {
+    /// Array name
+    pub name: &'a str,
+
+    /// Values by face
+    pub values: CellValues<'a>,
+}
+
+impl CellArray<'_> {
+    /// VTK type name, number of components and size of a value in bytes
+    fn layout(&self) -> (&'static str, usize, usize) {
+        match self.values {
+            CellValues::F64(_) | CellValues::Computed(_) => ("Float64", 1, 8),
+            CellValues::I32(_) => ("Int32", 1, 4),
+            CellValues::Vectors(_) => ("Float64", 3, 8),
+        }
+    }
+
+    /// Write the value(s) of a face
+    fn write_value(&self, face_idx: usize, out: &mut impl Write) -> std::io::Result<()> {
+        match &self.values {
+            CellValues::F64(values) => out.write_all(&values[face_idx].to_le_bytes()),
+            CellValues::I32(values) => out.write_all(&values[face_idx].to_le_bytes()),
+            CellValues::Vectors(values) => {
+                let v = values[face_idx];
+                for c in [v.x, v.y, v.z] {
+                    out.write_all(&c.to_le_bytes())?;
+                }
+                Ok(())
+            }
+            CellValues::Computed(value) => out.write_all(&value(face_idx).to_le_bytes()),
+        }
+    }
+
+    /// Number of values, when stored
+    fn len(&self) -> Option<usize> {
+        match &self.values {
+            CellValues::F64(values) => Some(values.len()),
+            CellValues::I32(values) => Some(values.len()),
+            CellValues::Vectors(values) => Some(values.len()),
+            CellValues::Computed(_) => None,
+        }
+    }
+}
+
+/// Number of cells and of connectivity entries written for a face
+fn cell_counts(face: &QuadFace, triangulation: Option<Triangulation>) -> (usize, usize) {
+    match triangulation {
+        Some(_) if !face.is_triangle() => (2, 6),
+        _ => (1, face.corners().len()),
+    }
+}
+
+/// Write a surface and per-face cell arrays to a VTU file without building
+/// the VTK buffers in memory
+///
+/// The output matches the vtkio-based writers (all surface nodes as points,
+/// one cell per face or two triangles per quad when triangulating, cell
+/// arrays repeated on both triangles) except that the data is stored as
+/// appended raw binary.
+#[tracing::instrument(skip_all, fields(surface = %surface.part_name))]
+pub fn write_surface_to_vtu_streaming(
+    surface: &SurfaceMesh,
+    cell_arrays: &[CellArray],
+    output_path: &Path,
+    vtk_version: Option<VtkVersion>,
+    triangulation: Option<Triangulation>,
+) -> Result<()> {
+    let version = VtkVersion::for_path(vtk_version, output_path)?;
+    for array in cell_arrays {
+        if let Some(len) = array.len().filter(|&len| len != surface.num_faces()) {
+            return Err(crate::error::ContactDetectorError::ConfigError(format!(
+                "VTU cell array '{}' has {} values for {} faces",
+                array.name,
+                len,
+                surface.num_faces()
+            )));
+        }
+    }
+    tracing::info!(
+        "Streaming surface '{}' with {} faces to {:?} (VTK version {})",
+        surface.part_name,
+        surface.num_faces(),
+        output_path,
+        version
+    );
+
+    let (num_cells, num_connectivity) = surface
+        .faces
+        .iter()
+        .map(|face| cell_counts(face, triangulation))
+        .fold((0, 0), |(cells, conn), (c, n)| (cells + c, conn + n));
+    let num_points = surface.nodes.len();
+
+    // Each appended block is a UInt64 byte count followed by the data
+    let mut offset = 0;
+    let mut block = |bytes: usize| {
+        let start = offset;
+        offset += 8 + bytes;
+        start
+    };
+    let points_offset = block(num_points * 3 * 8);
+    let connectivity_offset = block(num_connectivity * 8);
+    let offsets_offset = block(num_cells * 8);
+    let types_offset = block(num_cells);
+    let array_offsets: Vec<usize> = cell_arrays
+        .iter()
+        .map(|array| {
+            let (_, num_comp, size) = array.layout();
+            block(num_cells * num_comp * size)
+        })
+        .collect();
+
+    let mut out = BufWriter::new(File::create(output_path)?);
+
+    // XML header
+    writeln!(out, "<?xml version=\"1.0\"?>")?;
+    writeln!(
+        out,
+        "<VTKFile type=\"UnstructuredGrid\" version=\"{}\" byte_order=\"LittleEndian\" header_type=\"UInt64\">",
+        version
+    )?;
+    writeln!(out, "<UnstructuredGrid>")?;
+    writeln!(
+        out,
+        "<Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">",
+        num_points, num_cells
+    )?;
+    writeln!(out, "<Points>")?;
+    writeln!(
+        out,
+        "<DataArray type=\"Float64\" NumberOfComponents=\"3\" format=\"appended\" offset=\"{}\"/>",
+        points_offset
+    )?;
+    writeln!(out, "</Points>")?;
+    writeln!(out, "<Cells>")?;
+    for (name, vtk_type, array_offset) in [
+        ("connectivity", "Int64", connectivity_offset),
+        ("offsets", "Int64", offsets_offset),
+        ("types", "UInt8", types_offset),
+    ] {
+        writeln!(
+            out,
+            "<DataArray type=\"{}\" Name=\"{}\" format=\"appended\" offset=\"{}\"/>",
+            vtk_type, name, array_offset
+        )?;
+    }
+    writeln!(out, "</Cells>")?;
+    let active = |vectors: bool| {
+        cell_arrays
+            .iter()
+            .find(|a| matches!(a.values, CellValues::Vectors(_)) == vectors)
+            .map(|a| {
+                format!(
+                    " {}=\"{}\"",
+                    if vectors { "Vectors" } else { "Scalars" },
+                    a.name
+                )
+            })
+            .unwrap_or_default()
+    };
+    writeln!(out, "<CellData{}{}>", active(false), active(true))?;
+    for (array, array_offset) in cell_arrays.iter().zip(&array_offsets) {
+        let (vtk_type, num_comp, _) = array.layout();
+        writeln!(
+            out,
+            "<DataArray type=\"{}\" Name=\"{}\" NumberOfComponents=\"{}\" format=\"appended\" offset=\"{}\"/>",
+            vtk_type, array.name, num_comp, array_offset
+        )?;
+    }
+    writeln!(out, "</CellData>")?;
+    writeln!(out, "</Piece>")?;
+    writeln!(out, "</UnstructuredGrid>")?;
+    write!(out, "<AppendedData encoding=\"raw\">\n_")?;
+
+    // Points
+    out.write_all(&((num_points * 3 * 8) as u64).to_le_bytes())?;
+    for p in &surface.nodes {
+        for c in [p.x, p.y, p.z] {
+            out.write_all(&c.to_le_bytes())?;
+        }
+    }
+
+    // Cells
+    out.write_all(&((num_connectivity * 8) as u64).to_le_bytes())?;
+    for face in &surface.faces {
+        for polygon in face_polygons(face, &surface.nodes, triangulation) {
+            for node_id in polygon {
+                out.write_all(&(node_id as i64).to_le_bytes())?;
+            }
+        }
+    }
+    out.write_all(&((num_cells * 8) as u64).to_le_bytes())?;
+    let mut end = 0i64;
+    for face in &surface.faces {
+        let (cells, conn) = cell_counts(face, triangulation);
+        for _ in 0..cells {
+            end += (conn / cells) as i64;
+            out.write_all(&end.to_le_bytes())?;
+        }
+    }
+    out.write_all(&(num_cells as u64).to_le_bytes())?;
+    for face in &surface.faces {
+        let (cells, conn) = cell_counts(face, triangulation);
+        let cell_type = if conn / cells == 3 {
+            VTK_TRIANGLE
+        } else {
+            VTK_QUAD
+        };
+        for _ in 0..cells {
+            out.write_all(&[cell_type])?;
+        }
+    }
+
+    // Cell data, repeated on both triangles of a split quad
+    for array in cell_arrays {
+        let (_, num_comp, size) = array.layout();
+        out.write_all(&((num_cells * num_comp * size) as u64).to_le_bytes())?;
+        for (face_idx, face) in surface.faces.iter().enumerate() {
+            for _ in 0..cell_counts(face, triangulation).0 {
+                array.write_value(face_idx, &mut out)?;
+            }
+        }
+    }
+
+    write!(out, "\n</AppendedData>\n</VTKFile>\n")?;
+    out.flush()?;
+
+    tracing::info!("Successfully wrote VTU file to {:?}", output_path);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::types::Point;
+    use vtkio::model::*;
+
+    #[test]
+    fn test_streamed_vtu_reads_back() {
+        let mut surface = SurfaceMesh::new("Streamed".to_string());
+        surface.nodes = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(2.0, 0.0, 0.0),
+        ];
+        surface.faces = vec![QuadFace::new([0, 1, 2, 3]), QuadFace::triangle([1, 4, 2])];
+        surface.face_normals = vec![Vec3::new(0.0, 0.0, 1.0); 2];
+        surface.face_centroids = vec![Point::new(0.5, 0.5, 0.0), Point::new(1.3, 0.3, 0.0)];
+        surface.face_areas = vec![1.0, 0.5];
+        let pair_ids = [7, -1];
+
+        let path = std::env::temp_dir().join("test_streamed_surface.vtu");
+        let arrays = [
+            CellArray {
+                name: "normals",
+                values: CellValues::Vectors(&surface.face_normals),
+            },
+            CellArray {
+                name: "area",
+                values: CellValues::F64(&surface.face_areas),
+            },
+            CellArray {
+                name: "pair_id",
+                values: CellValues::I32(&pair_ids),
+            },
+            CellArray {
+                name: "doubled",
+                values: CellValues::Computed(Box::new(|f| 2.0 * f as f64)),
+            },
+        ];
+        write_surface_to_vtu_streaming(
+            &surface,
+            &arrays,
+            &path,
+            None,
+            Some(Triangulation::Diagonal02),
+        )
+        .unwrap();
+
+        let vtk = Vtk::import(&path).unwrap();
+        let DataSet::UnstructuredGrid { pieces, .. } = vtk.data else {
+            panic!("expected an unstructured grid");
+        };
+        let Piece::Inline(piece) = &pieces[0] else {
+            panic!("expected an inline piece");
+        };
+        assert_eq!(piece.num_points(), 5);
+        assert_eq!(piece.cells.types, vec![CellType::Triangle; 3]);
+        let (connectivity, offsets) = piece.cells.cell_verts.clone().into_xml();
+        assert_eq!(connectivity, vec![0, 1, 2, 0, 2, 3, 1, 4, 2]);
+        assert_eq!(offsets, vec![3, 6, 9]);
+
+        let cell_array = |name: &str| match piece.data.cell.iter().find(|a| a.name() == name) {
+            Some(Attribute::DataArray(array)) => array.data.clone(),
+            _ => panic!("missing cell array {}", name),
+        };
+        assert_eq!(
+            cell_array("area").cast_into::<f64>().unwrap(),
+            vec![1.0, 1.0, 0.5]
+        );
+        assert_eq!(
+            cell_array("pair_id").cast_into::<i32>().unwrap(),
+            vec![7, 7, -1]
+        );
+        assert_eq!(
+            cell_array("doubled").cast_into::<f64>().unwrap(),
+            vec![0.0, 0.0, 2.0]
+        );
+        assert_eq!(cell_array("normals").cast_into::<f64>().unwrap().len(), 9);
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -5,7 +5,10 @@
 pub mod config;
 pub mod contact;
 pub mod error;
+pub mod format;
 pub mod io;
 pub mod mesh;
+pub mod warnings;
 
 pub use error::{ContactDetectorError, Result};
+pub use warnings::{Warning, WarningKind, Warnings};
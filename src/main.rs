@@ -1,7 +1,9 @@
 //! Contact Detector CLI Application
 
 use clap::Parser;
-use contact_detector::Result;
+use contact_detector::format::{FloatFormat, FloatFormats, Glyphs};
+use contact_detector::io::{FormatRegistry, RunManifest};
+use contact_detector::{Result, WarningKind, Warnings};
 
 #[cfg(feature = "exodus")]
 use contact_detector::io::ExodusReader;
@@ -9,11 +11,19 @@ use contact_detector::io::ExodusReader;
 mod cli;
 use cli::{Cli, Commands};
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
-
-    // Set up logging
-    let log_level = if cli.debug {
+/// Set up console (and optional file) tracing output
+///
+/// The console level follows `--quiet`/`--verbose`/`--debug` unless `RUST_LOG`
+/// is set. The log file always records debug events plus the duration of every
+/// closed span, so a single file captures the full trace of a run.
+fn init_tracing(cli: &Cli, glyphs: Glyphs) -> Result<()> {
+    use tracing_subscriber::fmt::format::FmtSpan;
+    use tracing_subscriber::prelude::*;
+    use tracing_subscriber::{fmt, EnvFilter};
+
+    let console_level = if cli.quiet {
+        "error"
+    } else if cli.debug {
         "debug"
     } else if cli.verbose {
         "info"
@@ -21,104 +31,524 @@ fn main() -> Result<()> {
         "warn"
     };
 
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(log_level)).init();
+    let console_filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(console_level));
+    let console_spans = if cli.debug {
+        FmtSpan::CLOSE
+    } else {
+        FmtSpan::NONE
+    };
+    let console_layer = fmt::layer()
+        .with_writer(move || ConsoleWriter { glyphs })
+        .with_span_events(console_spans)
+        .with_filter(console_filter);
+
+    let file_layer = match &cli.log_file {
+        Some(path) => {
+            let file = std::fs::File::create(path)?;
+            Some(
+                fmt::layer()
+                    .with_writer(std::sync::Mutex::new(file))
+                    .with_ansi(false)
+                    .with_span_events(FmtSpan::CLOSE)
+                    .with_filter(EnvFilter::new("debug")),
+            )
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(console_layer)
+        .with(file_layer)
+        .init();
 
-    // Parse VTK version if provided
-    let vtk_version = if let Some(ref version_str) = cli.vtk_version {
-        Some(cli::parse_vtk_version(version_str).map_err(|e| {
-            contact_detector::ContactDetectorError::ConfigError(format!(
-                "Failed to parse VTK version: {}",
-                e
-            ))
-        })?)
+    Ok(())
+}
+
+/// Standard error with the glyphs of log messages replaced for the console
+struct ConsoleWriter {
+    glyphs: Glyphs,
+}
+
+impl std::io::Write for ConsoleWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // Each log event arrives whole, so characters are never split
+        let mut stderr = std::io::stderr();
+        match std::str::from_utf8(buf) {
+            Ok(text) => stderr.write_all(self.glyphs.apply(text).as_bytes())?,
+            Err(_) => stderr.write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        std::io::stderr().flush()
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    // Defaults from contact-detector.toml files, under the flags
+    let settings = contact_detector::config::Settings::discover(&std::env::current_dir()?)?;
+
+    // Pretty symbols unless the console cannot show them
+    let glyphs = if cli.ascii || settings.ascii == Some(true) {
+        Glyphs::Ascii
     } else {
-        None
+        Glyphs::Unicode
+    };
+
+    init_tracing(&cli, glyphs)?;
+
+    for path in &settings.sources {
+        tracing::info!("Using settings from {}", path.display());
+    }
+
+    #[cfg(feature = "parallel")]
+    if let Some(threads) = cli.threads.or(settings.threads) {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .map_err(|e| {
+                contact_detector::ContactDetectorError::ConfigError(format!(
+                    "Failed to set up {} threads: {}",
+                    threads, e
+                ))
+            })?;
+    }
+
+    // Each writer checks the version against the kind of file it writes
+    let vtk_version = cli.vtk_version.or(settings.vtk_version);
+
+    // Reports keep six decimals and files every digit unless asked otherwise
+    let formats = FloatFormats::new(
+        cli.notation.or(settings.notation),
+        cli.precision.or(settings.precision),
+    );
+
+    let load = MeshLoadOptions {
+        assembly: cli.assembly.clone(),
+        split_bodies: cli.split_bodies,
     };
 
+    // All parameters after defaults, for the run manifests
+    let parameters = serde_json::to_value(&cli).unwrap_or_default();
+
     // Dispatch to command handlers
     match cli.command {
-        Commands::Info { input } => cmd_info(input),
+        Commands::Info { input, json } => cmd_info(input, json, glyphs, load),
+        Commands::Convert {
+            input,
+            output,
+            max_name_length,
+            existing,
+        } => cmd_convert(input, output, max_name_length, existing.policy(), load),
         Commands::Skin {
             input,
             output,
             part,
-        } => cmd_skin(input, output, part, vtk_version),
+            merged,
+            fill_holes,
+            exclude_sideset,
+            check_closure,
+            holes_output,
+            triangulate,
+            existing,
+        } => cmd_skin(
+            input,
+            output,
+            part,
+            merged,
+            fill_holes,
+            exclude_sideset,
+            check_closure,
+            holes_output,
+            vtk_version,
+            triangulate.map(Into::into),
+            existing.policy(),
+            formats.console,
+            load,
+        ),
         Commands::Contact {
             input,
             part_a,
             part_b,
-            max_gap,
-            max_penetration,
-            max_angle,
+            surface_b,
+            criteria,
+            spatial_index,
+            algorithm,
+            rejection_stats,
+            no_occlusion_check,
             output,
+            triangulate,
+            save_results,
+            roi,
+            existing,
         } => cmd_contact(
             input,
             part_a,
             part_b,
-            max_gap,
-            max_penetration,
-            max_angle,
+            surface_b,
+            criteria.resolve(&settings)?,
+            spatial_index.into(),
+            algorithm.into(),
+            rejection_stats,
+            no_occlusion_check,
+            output,
+            vtk_version,
+            triangulate.map(Into::into),
+            save_results,
+            roi,
+            existing.policy(),
+            formats.console,
+            glyphs,
+            load,
+        ),
+        Commands::Query {
+            input,
+            point,
+            criteria,
+            json,
+        } => cmd_query(
+            input,
+            point,
+            criteria.resolve(&settings)?,
+            json,
+            formats.console,
+            glyphs,
+            load,
+        ),
+        Commands::Probe {
+            input,
+            path,
+            part_a,
+            part_b,
+            samples,
+            criteria,
+            output,
+            existing,
+        } => cmd_probe(
+            input,
+            path,
+            part_a,
+            part_b,
+            samples,
+            criteria.resolve(&settings)?,
+            output,
+            existing.policy(),
+            formats,
+            load,
+        ),
+        Commands::Deviation {
+            input,
+            points,
+            part,
+            max_distance,
+            output,
+            existing,
+        } => cmd_deviation(
+            input,
+            points,
+            part,
+            max_distance,
             output,
             vtk_version,
+            existing.policy(),
+            formats,
+            load,
         ),
         Commands::Analyze {
             input,
             pairs,
             config,
             output,
-        } => cmd_analyze(input, pairs, config, output, vtk_version),
+            no_cache,
+            dry_run,
+            naming,
+            roi,
+        } => cmd_analyze(
+            input,
+            pairs,
+            config,
+            output,
+            vtk_version,
+            no_cache,
+            dry_run,
+            naming,
+            roi,
+            RunManifest::new("analyze", parameters),
+            formats.console,
+            glyphs,
+            load,
+        ),
         Commands::AutoContact {
             input,
-            max_gap,
-            max_penetration,
-            max_angle,
+            criteria,
+            spatial_index,
+            algorithm,
+            rejection_stats,
+            sdf_spacing,
+            coarse_stride,
+            pair_time_limit,
+            pair_max_faces,
+            revisit_skipped,
+            preview,
+            dry_run,
+            no_occlusion_check,
             min_pairs,
+            min_paired_area,
+            min_coverage_fraction,
+            merge_pairs,
+            allowed_contacts,
+            min_patch_faces,
+            min_patch_area,
             output,
             export_metadata,
+            save_results,
+            export_manifest,
+            manifest_format,
+            export_outlines,
+            export_graph,
+            export_broad_phase,
+            export_nodal_status,
+            vis_max_faces,
+            paraview_script,
+            max_size_ratio,
+            refinement_report,
+            conformity_tolerance,
+            pressure_cone,
+            head_diameter_ratio,
+            clamp_length,
+            detect_fasteners,
+            max_clearance_ratio,
+            thermal_config,
             export_sidesets,
+            sideset_dist_factors,
+            sideset_paired_only,
+            sideset_name_template,
+            max_name_length,
             visualize_with_skin,
             multiblock,
             export_nodesets,
             export_materials,
             export_volume,
+            triangulate,
+            naming,
+            roi,
+            scope,
         } => cmd_auto_contact(
             input,
-            max_gap,
-            max_penetration,
-            max_angle,
+            criteria.resolve(&settings)?,
+            spatial_index.into(),
+            algorithm.into(),
+            rejection_stats,
+            sdf_spacing,
+            coarse_stride,
+            pair_time_limit,
+            pair_max_faces,
+            revisit_skipped,
+            preview,
+            dry_run,
+            no_occlusion_check,
             min_pairs,
+            min_paired_area,
+            min_coverage_fraction,
+            merge_pairs,
+            allowed_contacts,
+            contact_detector::mesh::PatchFilter::new(min_patch_faces, min_patch_area),
             output,
             vtk_version,
             export_metadata,
+            save_results,
+            export_manifest,
+            cli::ManifestFormatArg::resolve(manifest_format, &settings)?,
+            export_outlines,
+            export_graph,
+            export_broad_phase,
+            export_nodal_status,
+            vis_max_faces,
+            paraview_script,
+            max_size_ratio,
+            refinement_report,
+            conformity_tolerance,
+            pressure_cone.map(|cone_angle| contact_detector::contact::PressureCone {
+                cone_angle,
+                head_ratio: head_diameter_ratio,
+                clamp_length,
+            }),
+            detect_fasteners.then_some(max_clearance_ratio),
+            thermal_config,
             export_sidesets,
+            sideset_dist_factors.map(Into::into),
+            sideset_paired_only,
+            sideset_name_template,
+            max_name_length,
             visualize_with_skin,
             multiblock,
             export_nodesets,
             export_materials,
             export_volume,
+            triangulate.map(Into::into),
+            naming,
+            roi,
+            scope,
+            RunManifest::new("auto-contact", parameters),
+            formats,
+            glyphs,
+            load,
         ),
+        Commands::Compare {
+            before,
+            after,
+            tolerance,
+            json,
+        } => cmd_compare(before, after, tolerance, json, formats.console, glyphs),
     }
 }
 
-fn cmd_info(input: std::path::PathBuf) -> Result<()> {
-    println!("Reading mesh file: {}", input.display());
+/// Processing applied to every mesh as it is read
+struct MeshLoadOptions {
+    /// Assembly definition to merge into the mesh
+    assembly: Option<std::path::PathBuf>,
 
-    // Try to read as JSON first, then Exodus if available
-    let mesh = if input.extension().and_then(|s| s.to_str()) == Some("json") {
-        contact_detector::io::read_json_mesh(&input)?
-    } else {
-        #[cfg(feature = "exodus")]
-        {
-            let reader = ExodusReader::open(&input)?;
-            reader.read_mesh()?
-        }
-        #[cfg(not(feature = "exodus"))]
-        {
-            return Err(contact_detector::ContactDetectorError::ConfigError(
-                "Exodus support not compiled in. Install libhdf5-dev and libnetcdf-dev, then rebuild with --features exodus".to_string()
-            ));
+    /// Split blocks into their connected bodies
+    split_bodies: bool,
+}
+
+/// Read a mesh in any supported format and apply the load options
+fn read_mesh(
+    input: &std::path::Path,
+    load: &MeshLoadOptions,
+) -> Result<(contact_detector::mesh::Mesh, Warnings)> {
+    let (mut mesh, mut warnings) = FormatRegistry::with_builtin_formats().read(input)?;
+
+    if let Some(path) = &load.assembly {
+        tracing::info!("Applying assembly definition: {}", path.display());
+        let definition = contact_detector::mesh::AssemblyDefinition::load(path)?;
+        warnings.extend(definition.apply(&mut mesh)?);
+    }
+
+    if load.split_bodies {
+        let split = contact_detector::mesh::split_connected_components(&mut mesh);
+        if !split.is_empty() {
+            println!(
+                "Split {} block(s) into connected bodies: {}",
+                split.len(),
+                split.join(", ")
+            );
+        }
+    }
+
+    Ok((mesh, warnings))
+}
+
+/// Faces of the side sets excluded from skinning and pairing
+fn load_exclusion_zones(
+    mesh: &contact_detector::mesh::Mesh,
+    side_sets: &[String],
+) -> Result<contact_detector::mesh::ExclusionZones> {
+    let zones = contact_detector::mesh::ExclusionZones::from_side_sets(mesh, side_sets)?;
+    if !side_sets.is_empty() {
+        println!(
+            "Excluding {} face(s) of side set(s): {}",
+            zones.num_faces(),
+            side_sets.join(", ")
+        );
+    }
+    Ok(zones)
+}
+
+/// Resolved region-of-interest settings for a command
+struct Roi {
+    region: Option<contact_detector::mesh::RegionOfInterest>,
+    clip_output: bool,
+}
+
+impl Roi {
+    fn load(spec: Option<contact_detector::mesh::RoiSpec>, clip_output: bool) -> Result<Self> {
+        let region = spec.map(|spec| spec.load()).transpose()?;
+        if region.is_none() && clip_output {
+            tracing::warn!("--roi-clip-output has no effect without a region of interest");
+        }
+        Ok(Self {
+            region,
+            clip_output,
+        })
+    }
+
+    /// Drop faces outside the region from the surfaces when output clipping is on
+    fn clip_surfaces(
+        &self,
+        surfaces: Vec<contact_detector::mesh::SurfaceMesh>,
+    ) -> Vec<contact_detector::mesh::SurfaceMesh> {
+        match &self.region {
+            Some(region) if self.clip_output => surfaces
+                .iter()
+                .map(|s| contact_detector::mesh::restrict_surface(s, region).0)
+                .filter(|s| !s.faces.is_empty())
+                .collect(),
+            _ => surfaces,
+        }
+    }
+
+    /// Extra result-cache context: the faces of each surface kept by the
+    /// region when it restricts detection
+    fn cache_context(
+        &self,
+        surface_a: &contact_detector::mesh::SurfaceMesh,
+        surface_b: &contact_detector::mesh::SurfaceMesh,
+    ) -> Option<(Vec<usize>, Vec<usize>)> {
+        match &self.region {
+            Some(region) if !self.clip_output => Some((
+                contact_detector::mesh::restrict_surface(surface_a, region).1,
+                contact_detector::mesh::restrict_surface(surface_b, region).1,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Detect contact pairs, skipping faces outside the region
+    fn detect(
+        &self,
+        surface_a: &contact_detector::mesh::SurfaceMesh,
+        surface_b: &contact_detector::mesh::SurfaceMesh,
+        criteria: &contact_detector::contact::ContactCriteria,
+        volume: Option<&contact_detector::contact::VolumeIndex>,
+    ) -> Result<contact_detector::contact::ContactResults> {
+        let mut results = match &self.region {
+            // Clipped surfaces already lie entirely inside the region
+            Some(region) if !self.clip_output => {
+                contact_detector::contact::detect_contact_pairs_in_region(
+                    surface_a, surface_b, criteria, region,
+                )?
+            }
+            _ => contact_detector::contact::detect_contact_pairs(surface_a, surface_b, criteria)?,
+        };
+
+        // Drop pairs formed through surface A's own material (thin walls)
+        if let Some(volume) = volume {
+            contact_detector::contact::remove_occluded_pairs(&mut results, surface_a, volume);
         }
-    };
+
+        Ok(results)
+    }
+}
+
+#[tracing::instrument(skip_all, fields(input = %input.display()))]
+fn cmd_info(
+    input: std::path::PathBuf,
+    json: bool,
+    glyphs: Glyphs,
+    load: MeshLoadOptions,
+) -> Result<()> {
+    if json {
+        return print_info_json(&input, &load);
+    }
+
+    println!("Reading mesh file: {}", input.display());
+
+    let (mesh, _) = read_mesh(&input, &load)?;
 
     println!("\n{}", "=".repeat(60));
     println!("MESH INFORMATION");
@@ -129,16 +559,111 @@ fn cmd_info(input: std::path::PathBuf) -> Result<()> {
     println!("  Blocks:       {}", mesh.num_blocks());
     println!("  Node Sets:    {}", mesh.node_sets.len());
     println!("  Side Sets:    {}", mesh.side_sets.len());
+    println!("  Geometry:     {:.1} MB", mesh.geometry_bytes() as f64 / 1e6);
     println!();
 
+    let skipped: Vec<_> = mesh.provenance.skipped_blocks().collect();
+    if !skipped.is_empty() {
+        println!("Skipped Blocks (not read, invisible to contact detection):");
+        for block in &skipped {
+            println!(
+                "  - {}: {} {} {} ({})",
+                block.name,
+                block.num_elements,
+                glyphs.times(),
+                block.element_type,
+                block.reason.as_deref().unwrap_or("skipped")
+            );
+        }
+        println!();
+    }
+
     if !mesh.element_blocks.is_empty() {
         println!("Element Blocks:");
         let mut blocks: Vec<_> = mesh.element_blocks.iter().collect();
         blocks.sort_by_key(|(name, _)| *name);
-        for (name, elements) in blocks {
+        for (name, elements) in &blocks {
             println!("  - {}: {} elements", name, elements.len());
         }
         println!();
+
+        println!("Block Bounds (oriented box along principal directions, longest first):");
+        for (name, elements) in &blocks {
+            let Some(bounds) = contact_detector::mesh::SurfaceBounds::of_elements(&mesh, elements)
+            else {
+                continue;
+            };
+            let obb = &bounds.obb;
+            println!(
+                "  - {}: {:.4} x {:.4} x {:.4} centred at ({:.4}, {:.4}, {:.4})",
+                name,
+                obb.extents[0],
+                obb.extents[1],
+                obb.extents[2],
+                obb.center[0],
+                obb.center[1],
+                obb.center[2]
+            );
+            println!(
+                "      long axis ({:.3}, {:.3}, {:.3}), bounding sphere radius {:.4}",
+                obb.axes[0][0], obb.axes[0][1], obb.axes[0][2], bounds.sphere.radius
+            );
+        }
+        println!();
+
+        println!("Block Volumes (enclosed by each block's skin) and Centroids:");
+        for mass in contact_detector::mesh::BlockMass::of_blocks(&mesh) {
+            println!(
+                "  - {}: {:.6e} at ({:.4}, {:.4}, {:.4}){}",
+                mass.block,
+                mass.volume,
+                mass.centroid[0],
+                mass.centroid[1],
+                mass.centroid[2],
+                if mass.closed { "" } else { " (skin not closed)" }
+            );
+        }
+        println!();
+
+        let stats = contact_detector::mesh::MeshStats::compute(&mesh);
+        println!("Element Size (edge length min / median / max, median face area):");
+        for block in &stats.blocks {
+            let (Some(edges), Some(areas)) = (block.edge_length, block.face_area) else {
+                continue;
+            };
+            println!(
+                "  - {}: {:.4} / {:.4} / {:.4}, {:.4}",
+                block.name, edges.min, edges.median, edges.max, areas.median
+            );
+        }
+        if let Some(tolerances) = stats.recommended_tolerances() {
+            println!(
+                "  Recommended: --max-gap {:.4} --max-penetration {:.4} (from '{}')",
+                tolerances.max_gap, tolerances.max_penetration, tolerances.reference_block
+            );
+        }
+        println!();
+    }
+
+    if !mesh.assemblies.is_empty() {
+        println!("Assemblies:");
+        let mut assemblies: Vec<_> = mesh.assemblies.iter().collect();
+        assemblies.sort_by_key(|(name, _)| *name);
+        for (name, assembly) in assemblies {
+            let members: Vec<&str> = assembly
+                .assemblies
+                .iter()
+                .chain(&assembly.blocks)
+                .map(String::as_str)
+                .collect();
+            println!(
+                "  - {}: {} block(s) [{}]",
+                name,
+                mesh.assembly_blocks(name)?.len(),
+                members.join(", ")
+            );
+        }
+        println!();
     }
 
     if !mesh.node_sets.is_empty() {
@@ -161,50 +686,202 @@ fn cmd_info(input: std::path::PathBuf) -> Result<()> {
         println!();
     }
 
+    #[cfg(feature = "exodus")]
+    if FormatRegistry::with_builtin_formats().reader_for(&input).map(|r| r.name()) == Some("exodus") {
+        print_exodus_contents(&ExodusReader::open(&input)?.file_info()?, glyphs);
+    }
+
     println!("{}", "=".repeat(60));
 
     Ok(())
 }
 
+/// Print mesh counts and per-block mass properties as JSON
+fn print_info_json(input: &std::path::Path, load: &MeshLoadOptions) -> Result<()> {
+    use contact_detector::mesh::BlockMass;
+
+    #[derive(serde::Serialize)]
+    struct BlockInfo {
+        num_elements: usize,
+        #[serde(flatten)]
+        mass: BlockMass,
+    }
+
+    #[derive(serde::Serialize)]
+    struct MeshInfo {
+        mesh_file: String,
+        num_nodes: usize,
+        num_elements: usize,
+        num_node_sets: usize,
+        num_side_sets: usize,
+        units: Option<String>,
+        blocks: Vec<BlockInfo>,
+    }
+
+    let (mesh, _) = read_mesh(input, load)?;
+    let info = MeshInfo {
+        mesh_file: input.display().to_string(),
+        num_nodes: mesh.num_nodes(),
+        num_elements: mesh.num_elements(),
+        num_node_sets: mesh.node_sets.len(),
+        num_side_sets: mesh.side_sets.len(),
+        units: mesh.units.clone(),
+        blocks: BlockMass::of_blocks(&mesh)
+            .into_iter()
+            .map(|mass| BlockInfo {
+                num_elements: mesh.element_blocks[&mass.block].len(),
+                mass,
+            })
+            .collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&info).map_err(|e| {
+        contact_detector::ContactDetectorError::ConfigError(format!(
+            "Failed to serialize mesh info: {}",
+            e
+        ))
+    })?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Print what an Exodus file holds beyond the mesh that was read
+#[cfg(feature = "exodus")]
+fn print_exodus_contents(info: &contact_detector::io::ExodusFileInfo, glyphs: Glyphs) {
+    println!("Exodus File Contents:");
+    if let Some(title) = &info.title {
+        println!("  Title:        {}", title);
+    }
+    println!("  Dimensions:   {}", info.num_dim);
+    println!();
+
+    println!("  Element Blocks (as stored):");
+    for block in &info.blocks {
+        println!(
+            "    - {} (ID {}): {} {} {} ({} nodes/element){}",
+            block.name,
+            block.id,
+            block.num_elements,
+            glyphs.times(),
+            block.element_type,
+            block.nodes_per_element,
+            if block.is_supported() { "" } else { " (skipped: unsupported element type)" }
+        );
+        if !block.attribute_names.is_empty() {
+            println!("        attributes: {}", block.attribute_names.join(", "));
+        }
+    }
+    println!();
+
+    let variables = [
+        ("Global", &info.global_variables),
+        ("Nodal", &info.nodal_variables),
+        ("Element", &info.element_variables),
+        ("Node set", &info.node_set_variables),
+        ("Side set", &info.side_set_variables),
+    ];
+    println!("  Variables:");
+    for (kind, names) in variables {
+        if names.is_empty() {
+            println!("    - {}: none", kind);
+        } else {
+            println!("    - {} ({}): {}", kind, names.len(), names.join(", "));
+        }
+    }
+    println!();
+
+    println!("  Time steps:   {}", info.num_time_steps());
+    if !info.times.is_empty() {
+        let times: Vec<String> = info.times.iter().map(|t| format!("{}", t)).collect();
+        println!("  Time values:  {}", times.join(", "));
+    }
+    println!();
+}
+
+#[tracing::instrument(skip_all, fields(input = %input.display()))]
+fn cmd_convert(
+    input: std::path::PathBuf,
+    output: std::path::PathBuf,
+    max_name_length: usize,
+    policy: contact_detector::io::OverwritePolicy,
+    load: MeshLoadOptions,
+) -> Result<()> {
+    if !claim_output(policy, &output)? {
+        return Ok(());
+    }
+
+    println!("Reading mesh file: {}", input.display());
+
+    let (mut mesh, _) = read_mesh(&input, &load)?;
+
+    mesh.provenance
+        .history
+        .push(format!("converted to {}", output.display()));
+
+    // Exodus output is written with the requested name length
+    #[cfg_attr(not(feature = "exodus"), allow(unused_mut))]
+    let mut registry = FormatRegistry::with_builtin_formats();
+    #[cfg(feature = "exodus")]
+    registry.register_writer(contact_detector::io::ExodusFormat {
+        write_options: contact_detector::io::ExodusWriteOptions { max_name_length },
+    });
+    #[cfg(not(feature = "exodus"))]
+    let _ = max_name_length;
+    registry.write(&mesh, &output)?;
+
+    println!(
+        "Wrote {} nodes, {} elements to {}",
+        mesh.num_nodes(),
+        mesh.num_elements(),
+        output.display()
+    );
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all, fields(input = %input.display()))]
 fn cmd_skin(
     input: std::path::PathBuf,
     output: std::path::PathBuf,
     part: Option<String>,
-    vtk_version: Option<(u8, u8)>,
+    merged: bool,
+    fill_holes: Option<f64>,
+    exclude_sideset: Vec<String>,
+    check_closure: bool,
+    holes_output: Option<std::path::PathBuf>,
+    vtk_version: Option<contact_detector::io::VtkVersion>,
+    triangulate: Option<contact_detector::mesh::Triangulation>,
+    policy: contact_detector::io::OverwritePolicy,
+    format: FloatFormat,
+    load: MeshLoadOptions,
 ) -> Result<()> {
-    use contact_detector::io::{write_surface_to_vtu, write_surfaces_to_vtu};
+    use contact_detector::io::{
+        write_merged_skin_to_vtu, write_surface_to_vtu, write_surfaces_to_vtu,
+    };
     use contact_detector::mesh::extract_surface;
 
-    log::info!("Reading mesh file: {}", input.display());
+    if !claim_output(policy, &output)? {
+        return Ok(());
+    }
+
+    tracing::info!("Reading mesh file: {}", input.display());
 
     // Read mesh from file
-    let mesh = if input.extension().and_then(|s| s.to_str()) == Some("json") {
-        contact_detector::io::read_json_mesh(&input)?
-    } else {
-        #[cfg(feature = "exodus")]
-        {
-            let reader = ExodusReader::open(&input)?;
-            reader.read_mesh()?
-        }
-        #[cfg(not(feature = "exodus"))]
-        {
-            return Err(contact_detector::ContactDetectorError::ConfigError(
-                "Exodus support not compiled in. Install libhdf5-dev and libnetcdf-dev, then rebuild with --features exodus".to_string()
-            ));
-        }
-    };
+    let (mesh, _) = read_mesh(&input, &load)?;
 
-    log::info!(
+    tracing::info!(
         "Loaded mesh with {} nodes, {} elements",
         mesh.num_nodes(),
         mesh.num_elements()
     );
 
     // Extract surface
-    let surfaces = extract_surface(&mesh)?;
+    let exclusion_zones = load_exclusion_zones(&mesh, &exclude_sideset)?;
+    let (surfaces, _) = exclusion_zones.apply(extract_surface(&mesh)?);
 
     // Filter by part if specified
-    let surfaces_to_write: Vec<_> = if let Some(part_name) = part {
+    let mut surfaces_to_write: Vec<_> = if let Some(part_name) = part {
         surfaces
             .into_iter()
             .filter(|s| s.part_name == part_name)
@@ -214,20 +891,62 @@ fn cmd_skin(
     };
 
     if surfaces_to_write.is_empty() {
-        log::warn!("No surfaces to write");
+        tracing::warn!("No surfaces to write");
         return Ok(());
     }
 
+    // Close small holes with filler surfaces
+    if let Some(max_perimeter) = fill_holes {
+        let fillers = contact_detector::mesh::fill_small_holes(&surfaces_to_write, max_perimeter)?;
+        println!(
+            "Filled {} hole(s) with a perimeter up to {}",
+            fillers.len(),
+            max_perimeter
+        );
+        surfaces_to_write.extend(fillers);
+    }
+
     // Write output
-    if surfaces_to_write.len() == 1 {
+    let extension = output.extension().and_then(|e| e.to_str());
+    if matches!(extension, Some("obj") | Some("ply")) {
+        // Mesh-processing formats hold all surfaces in one file
+        if extension == Some("obj") {
+            contact_detector::io::write_surfaces_to_obj(&surfaces_to_write, &output, triangulate)?;
+        } else {
+            contact_detector::io::write_surfaces_to_ply(
+                &surfaces_to_write,
+                &[],
+                &output,
+                triangulate,
+            )?;
+        }
+        println!(
+            "Extracted {} surfaces to: {}",
+            surfaces_to_write.len(),
+            output.display()
+        );
+    } else if merged {
+        // One file with the provenance of every face
+        write_merged_skin_to_vtu(&mesh, &surfaces_to_write, &output, vtk_version, triangulate)?;
+        println!(
+            "Extracted {} surfaces to: {}",
+            surfaces_to_write.len(),
+            output.display()
+        );
+        let mut block_names: Vec<&String> = mesh.element_blocks.keys().collect();
+        block_names.sort();
+        for (idx, name) in block_names.iter().enumerate() {
+            println!("  SourceBlock {}: {}", idx, name);
+        }
+    } else if surfaces_to_write.len() == 1 {
         // Single surface - write directly to output file
         if let Some(surface) = surfaces_to_write.first() {
-            write_surface_to_vtu(surface, &output, vtk_version)?;
+            write_surface_to_vtu(surface, &output, vtk_version, triangulate)?;
             println!("Surface extracted and written to: {}", output.display());
         }
     } else {
         // Multiple surfaces - output should be a directory
-        write_surfaces_to_vtu(&surfaces_to_write, &output, vtk_version)?;
+        write_surfaces_to_vtu(&surfaces_to_write, &output, vtk_version, triangulate)?;
         println!(
             "Extracted {} surfaces to directory: {}",
             surfaces_to_write.len(),
@@ -236,58 +955,91 @@ fn cmd_skin(
     }
 
     // Print statistics
-    for surface in &surfaces_to_write {
+    for (patch_id, surface) in surfaces_to_write.iter().enumerate() {
+        let patch = if merged { format!(" (PatchId {})", patch_id) } else { String::new() };
         println!(
-            "  - {}: {} faces, total area: {:.6}",
+            "  - {}{}: {} faces, total area: {}",
             surface.part_name,
+            patch,
             surface.num_faces(),
-            surface.total_area()
+            format.show(surface.total_area())
         );
     }
 
+    if check_closure {
+        let closures = contact_detector::mesh::check_block_closure(&surfaces_to_write)?;
+        println!();
+        println!("Skin closure:");
+        for closure in &closures {
+            if closure.holes.is_empty() {
+                println!(
+                    "  - {}: closed{}",
+                    closure.block,
+                    if closure.closed { "" } else { " (non-manifold edges)" }
+                );
+            } else {
+                println!(
+                    "  - {}: {} hole(s), {} boundary edge(s), longest perimeter {}",
+                    closure.block,
+                    closure.holes.len(),
+                    closure.num_boundary_edges(),
+                    format.show(closure.holes[0].length)
+                );
+            }
+        }
+
+        if let Some(path) = holes_output {
+            if claim_output(policy, &path)? {
+                contact_detector::io::write_holes_to_vtp(&closures, &mesh, &path, vtk_version)?;
+                println!("Holes written to: {}", path.display());
+            }
+        }
+    }
+
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all, fields(input = %input.display()))]
 fn cmd_contact(
     input: std::path::PathBuf,
     part_a: String,
-    part_b: String,
-    max_gap: f64,
-    max_penetration: f64,
-    max_angle: f64,
+    part_b: Option<String>,
+    surface_b: Option<std::path::PathBuf>,
+    criteria: contact_detector::contact::ContactCriteria,
+    spatial_index: contact_detector::contact::SpatialIndexKind,
+    algorithm: contact_detector::contact::DetectionAlgorithmKind,
+    rejection_stats: bool,
+    no_occlusion_check: bool,
     output: std::path::PathBuf,
-    vtk_version: Option<(u8, u8)>,
+    vtk_version: Option<contact_detector::io::VtkVersion>,
+    triangulate: Option<contact_detector::mesh::Triangulation>,
+    save_results: Option<std::path::PathBuf>,
+    roi: cli::RoiArgs,
+    policy: contact_detector::io::OverwritePolicy,
+    format: FloatFormat,
+    glyphs: Glyphs,
+    load: MeshLoadOptions,
 ) -> Result<()> {
-    use contact_detector::contact::{detect_contact_pairs, ContactCriteria};
+    use contact_detector::contact::VolumeIndex;
     use contact_detector::mesh::extract_surface;
 
-    log::info!("Reading mesh file: {}", input.display());
+    tracing::info!("Reading mesh file: {}", input.display());
 
     // Read mesh from file
-    let mesh = if input.extension().and_then(|s| s.to_str()) == Some("json") {
-        contact_detector::io::read_json_mesh(&input)?
-    } else {
-        #[cfg(feature = "exodus")]
-        {
-            let reader = ExodusReader::open(&input)?;
-            reader.read_mesh()?
-        }
-        #[cfg(not(feature = "exodus"))]
-        {
-            return Err(contact_detector::ContactDetectorError::ConfigError(
-                "Exodus support not compiled in. Install libhdf5-dev and libnetcdf-dev, then rebuild with --features exodus".to_string()
-            ));
-        }
-    };
+    let (mesh, _) = read_mesh(&input, &load)?;
 
-    log::info!(
+    tracing::info!(
         "Loaded mesh with {} nodes, {} elements",
         mesh.num_nodes(),
         mesh.num_elements()
     );
 
     // Extract surface
-    let surfaces = extract_surface(&mesh)?;
+    let exclusion_zones = load_exclusion_zones(&mesh, &roi.exclude_sideset)?;
+    let roi = Roi::load(roi.spec(), roi.roi_clip_output)?;
+    let (surfaces, _) = exclusion_zones.apply(extract_surface(&mesh)?);
+    let surfaces = roi.clip_surfaces(surfaces);
 
     // Find the requested surfaces
     let surface_a = surfaces
@@ -297,60 +1049,357 @@ fn cmd_contact(
             contact_detector::ContactDetectorError::ElementBlockNotFound(part_a.clone())
         })?;
 
-    let surface_b = surfaces
-        .iter()
-        .find(|s| s.part_name == part_b)
-        .ok_or_else(|| {
-            contact_detector::ContactDetectorError::ElementBlockNotFound(part_b.clone())
-        })?;
-
+    // The second surface is either a mesh part or an imported surface file
+    let imported_b;
+    let surface_b = match (&part_b, &surface_b) {
+        (_, Some(path)) => {
+            imported_b = contact_detector::io::read_surface_file(path)?;
+            println!(
+                "Imported surface '{}' with {} faces from {}",
+                imported_b.part_name,
+                imported_b.num_faces(),
+                path.display()
+            );
+            &imported_b
+        }
+        (Some(part_b), None) => surfaces
+            .iter()
+            .find(|s| &s.part_name == part_b)
+            .ok_or_else(|| {
+                contact_detector::ContactDetectorError::ElementBlockNotFound(part_b.clone())
+            })?,
+        (None, None) => unreachable!("clap requires --part-b or --surface-b"),
+    };
+
     // Set up contact detection criteria
-    let criteria = ContactCriteria::new(max_gap, max_penetration, max_angle);
+    let mut criteria = criteria;
+    criteria.spatial_index = spatial_index;
+    criteria.algorithm = algorithm;
+    criteria.record_rejections = rejection_stats;
 
     // Detect contact pairs
-    let results = detect_contact_pairs(surface_a, surface_b, &criteria)?;
+    let volume = (!no_occlusion_check).then(|| VolumeIndex::new(&mesh));
+    let results = roi.detect(surface_a, surface_b, &criteria, volume.as_ref())?;
 
     // Print summary
-    results.print_summary();
-
-    // Compute surface metrics
-    use contact_detector::contact::SurfaceMetrics;
-    use contact_detector::io::write_surface_with_contact_metadata;
+    results.print_summary(format, glyphs);
 
-    let metrics_a = SurfaceMetrics::compute(&results, surface_a, true);
-    let metrics_b = SurfaceMetrics::compute(&results, surface_b, false);
+    // Compute pair metrics
+    use contact_detector::contact::PairMetrics;
+    use contact_detector::io::{AttributeSet, FaceContext, SurfaceFormats};
 
-    metrics_a.print_summary(&surface_a.part_name);
-    metrics_b.print_summary(&surface_b.part_name);
+    let metrics = PairMetrics::compute(&results, surface_a, surface_b);
+    metrics.print_summary(&surface_a.part_name, &surface_b.part_name, format, glyphs);
 
     // Write surface A with contact metadata
-    write_surface_with_contact_metadata(surface_a, &results, &metrics_a, &output, vtk_version)?;
+    if claim_output(policy, &output)? {
+        SurfaceFormats::with_builtin_formats(vtk_version, triangulate).write_contact(
+            &AttributeSet::contact_defaults(),
+            &FaceContext::new(&results, surface_a, surface_b, true),
+            &output,
+        )?;
+
+        println!(
+            "\nWrote surface with contact metadata to: {}",
+            output.display()
+        );
+    }
+
+    if let Some(path) = save_results {
+        if claim_output(policy, &path)? {
+            let mut file = contact_detector::io::ContactResultsFile::new(vec![results]);
+            file.mesh_file = Some(input.display().to_string());
+            file.export(&path)?;
+            println!("Saved contact results to: {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all, fields(input = %input.display()))]
+fn cmd_deviation(
+    input: std::path::PathBuf,
+    points_path: std::path::PathBuf,
+    part: Option<String>,
+    max_distance: Option<f64>,
+    output: std::path::PathBuf,
+    vtk_version: Option<contact_detector::io::VtkVersion>,
+    policy: contact_detector::io::OverwritePolicy,
+    formats: FloatFormats,
+    load: MeshLoadOptions,
+) -> Result<()> {
+    use contact_detector::contact::{compute_point_deviations, DeviationSummary};
+    use contact_detector::mesh::{combine_surfaces, extract_surface};
+
+    tracing::info!("Reading mesh file: {}", input.display());
+
+    let (mesh, _) = read_mesh(&input, &load)?;
+
+    let surfaces = extract_surface(&mesh)?;
+    let selected: Vec<_> = match &part {
+        Some(part_name) => surfaces
+            .iter()
+            .filter(|s| &s.part_name == part_name)
+            .collect(),
+        None => surfaces.iter().collect(),
+    };
+    if selected.is_empty() {
+        return Err(contact_detector::ContactDetectorError::ElementBlockNotFound(
+            part.unwrap_or_default(),
+        ));
+    }
+    let surface_name = part.unwrap_or_else(|| "skin".to_string());
+    let surface = combine_surfaces(surface_name, &selected);
+
+    tracing::info!("Reading point cloud: {}", points_path.display());
+    let points = contact_detector::io::read_point_cloud(&points_path)?;
+
+    let deviations = compute_point_deviations(&points, &surface, max_distance);
+    DeviationSummary::compute(&deviations).print_summary(&surface.part_name, formats.console);
+
+    if claim_output(policy, &output)? {
+        contact_detector::io::write_point_deviations(
+            &points,
+            &deviations,
+            &output,
+            vtk_version,
+            formats.files,
+        )?;
+        println!("\nWrote point deviations to: {}", output.display());
+    }
 
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all, fields(input = %input.display()))]
+fn cmd_probe(
+    input: std::path::PathBuf,
+    path: std::path::PathBuf,
+    part_a: String,
+    part_b: String,
+    samples: usize,
+    criteria: contact_detector::contact::ContactCriteria,
+    output: std::path::PathBuf,
+    policy: contact_detector::io::OverwritePolicy,
+    formats: FloatFormats,
+    load: MeshLoadOptions,
+) -> Result<()> {
+    use contact_detector::contact::{
+        detect_contact_pairs, probe_gap_along_path, remove_occluded_pairs, VolumeIndex,
+    };
+    use contact_detector::mesh::extract_surface;
+
+    if !claim_output(policy, &output)? {
+        return Ok(());
+    }
+
+    tracing::info!("Reading path: {}", path.display());
+    let polyline = contact_detector::io::read_polyline(&path)?;
+
+    tracing::info!("Reading mesh file: {}", input.display());
+    let (mesh, _) = read_mesh(&input, &load)?;
+
+    let surfaces = extract_surface(&mesh)?;
+    let find_surface = |name: &String| {
+        surfaces.iter().find(|s| &s.part_name == name).ok_or_else(|| {
+            contact_detector::ContactDetectorError::ElementBlockNotFound(name.clone())
+        })
+    };
+    let (surface_a, surface_b) = (find_surface(&part_a)?, find_surface(&part_b)?);
+
+    // Detect the pair as the contact command does
+    let mut results = detect_contact_pairs(surface_a, surface_b, &criteria)?;
+    remove_occluded_pairs(&mut results, surface_a, &VolumeIndex::new(&mesh));
+
+    let profile = probe_gap_along_path(&polyline, samples, surface_a, surface_b, &results)?;
+    let gaps: Vec<f64> = profile.iter().filter_map(|sample| sample.gap).collect();
+    let length = profile.last().map_or(0.0, |sample| sample.arclength);
+
+    let format = formats.console;
     println!(
-        "\nWrote surface with contact metadata to: {}",
-        output.display()
+        "Gap along {} ({} samples, length {}):",
+        path.display(),
+        profile.len(),
+        format.show(length)
+    );
+    println!("  Paired samples: {} of {}", gaps.len(), profile.len());
+    if !gaps.is_empty() {
+        let min = gaps.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = gaps.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        println!("  Gap range:      {} to {}", format.show(min), format.show(max));
+    }
+    let max_offset = profile.iter().map(|sample| sample.offset).fold(0.0, f64::max);
+    println!(
+        "  Max offset of the path from {}: {}",
+        part_a,
+        format.show(max_offset)
+    );
+
+    contact_detector::io::write_gap_profile(&profile, &output, formats.files)?;
+    println!("\nWrote gap profile to: {}", output.display());
+
+    Ok(())
+}
+
+fn cmd_compare(
+    before: std::path::PathBuf,
+    after: std::path::PathBuf,
+    tolerance: Option<f64>,
+    json: bool,
+    format: FloatFormat,
+    glyphs: Glyphs,
+) -> Result<()> {
+    use contact_detector::contact::RevisionComparison;
+    use contact_detector::io::ContactResultsFile;
+
+    tracing::info!("Reading contact results: {}", before.display());
+    let old = ContactResultsFile::load(&before)?;
+    tracing::info!("Reading contact results: {}", after.display());
+    let new = ContactResultsFile::load(&after)?;
+
+    let comparison = RevisionComparison::compare(&old.results, &new.results, tolerance);
+    if json {
+        let text = serde_json::to_string_pretty(&comparison).map_err(|e| {
+            contact_detector::ContactDetectorError::ConfigError(format!(
+                "Failed to serialize comparison: {}",
+                e
+            ))
+        })?;
+        println!("{}", text);
+    } else {
+        comparison.print_summary(format, glyphs);
+    }
+
+    Ok(())
+}
+
+#[tracing::instrument(skip_all, fields(input = %input.display()))]
+fn cmd_query(
+    input: std::path::PathBuf,
+    point: [f64; 3],
+    criteria: contact_detector::contact::ContactCriteria,
+    json: bool,
+    format: FloatFormat,
+    glyphs: Glyphs,
+    load: MeshLoadOptions,
+) -> Result<()> {
+    use contact_detector::contact::query_point;
+    use contact_detector::mesh::{extract_surface, Point};
+
+    tracing::info!("Reading mesh file: {}", input.display());
+
+    let (mesh, _) = read_mesh(&input, &load)?;
+
+    let surfaces = extract_surface(&mesh)?;
+    let point = Point::new(point[0], point[1], point[2]);
+
+    let query = query_point(&surfaces, &point, &criteria)?;
+    if json {
+        let text = serde_json::to_string_pretty(&query).map_err(|e| {
+            contact_detector::ContactDetectorError::ConfigError(format!(
+                "Failed to serialize query: {}",
+                e
+            ))
+        })?;
+        println!("{}", text);
+        return Ok(());
+    }
+    let Some(query) = query else {
+        println!("No surface faces found in mesh");
+        return Ok(());
+    };
+
+    println!("{}", "=".repeat(60));
+    println!("POINT QUERY");
+    println!("{}", "=".repeat(60));
+    println!();
+    println!(
+        "  Point:        ({}, {}, {})",
+        format.show(point.x),
+        format.show(point.y),
+        format.show(point.z)
     );
+    println!();
+    println!("Nearest face:");
+    println!("  Block:        {}", query.block_name);
+    println!("  Surface:      {}", query.surface_name);
+    println!("  Face index:   {}", query.face_index);
+    println!(
+        "  Centroid:     ({}, {}, {})",
+        format.show(query.centroid.x),
+        format.show(query.centroid.y),
+        format.show(query.centroid.z)
+    );
+    println!(
+        "  Normal:       ({:.4}, {:.4}, {:.4})",
+        query.normal.x, query.normal.y, query.normal.z
+    );
+    println!("  Area:         {}", format.show(query.area));
+    println!("  Distance:     {}", format.show(query.distance));
+    println!();
+
+    if query.is_paired() {
+        println!("Contact status: PAIRED ({} partner(s))", query.partners.len());
+        for partner in &query.partners {
+            println!(
+                "  - {} face {}: gap {}, angle {:.2}{}, centroid ({}, {}, {})",
+                partner.surface_name,
+                partner.face_index,
+                format.show(partner.gap),
+                partner.normal_angle,
+                glyphs.degrees(),
+                format.show(partner.centroid.x),
+                format.show(partner.centroid.y),
+                format.show(partner.centroid.z)
+            );
+        }
+    } else {
+        println!("Contact status: UNPAIRED");
+    }
+    println!();
+    println!("{}", "=".repeat(60));
 
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all, fields(input = %input.display()))]
 fn cmd_analyze(
     input: std::path::PathBuf,
     pairs: String,
     config_file: Option<std::path::PathBuf>,
     output: std::path::PathBuf,
-    vtk_version: Option<(u8, u8)>,
+    vtk_version: Option<contact_detector::io::VtkVersion>,
+    no_cache: bool,
+    dry_run: bool,
+    naming: cli::OutputArgs,
+    roi: cli::RoiArgs,
+    mut manifest: RunManifest,
+    format: FloatFormat,
+    glyphs: Glyphs,
+    load: MeshLoadOptions,
 ) -> Result<()> {
     use contact_detector::config::AnalysisConfig;
-    use contact_detector::contact::{detect_contact_pairs, SurfaceMetrics};
-    use contact_detector::io::write_surface_with_contact_metadata;
-    use contact_detector::mesh::extract_surface;
+    use contact_detector::contact::{
+        ContactInterface, InterfaceSide, PairMetrics, ResultCache, RunSummary, VolumeIndex,
+        CACHE_DIR_NAME,
+    };
+    use contact_detector::io::{
+        AttributeSet, FaceContext, OutputNamer, OverwritePolicy, SurfaceFormats,
+    };
+    use contact_detector::mesh::{extract_surface, SurfaceBounds};
     use indicatif::{ProgressBar, ProgressStyle};
 
-    log::info!("Starting batch analysis...");
+    tracing::info!("Starting batch analysis...");
+
+    record_inputs(&mut manifest, &input, &load, &roi)?;
 
     // Load or create configuration
     let config = if let Some(config_path) = config_file {
+        manifest.add_input(&config_path, "config")?;
         AnalysisConfig::from_file(&config_path)?
     } else {
         use contact_detector::contact::ContactCriteria;
@@ -362,25 +1411,11 @@ fn cmd_analyze(
         )?
     };
 
-    log::info!("Analyzing {} contact pairs", config.contact_pairs.len());
+    tracing::info!("Analyzing {} contact pairs", config.contact_pairs.len());
 
     // Read mesh
     println!("Reading mesh file: {}", config.input_file);
-    let mesh = if input.extension().and_then(|s| s.to_str()) == Some("json") {
-        contact_detector::io::read_json_mesh(&input)?
-    } else {
-        #[cfg(feature = "exodus")]
-        {
-            let reader = ExodusReader::open(&input)?;
-            reader.read_mesh()?
-        }
-        #[cfg(not(feature = "exodus"))]
-        {
-            return Err(contact_detector::ContactDetectorError::ConfigError(
-                "Exodus support not compiled in".to_string(),
-            ));
-        }
-    };
+    let (mesh, _) = read_mesh(&input, &load)?;
 
     println!(
         "Loaded mesh: {} nodes, {} elements, {} blocks\n",
@@ -389,14 +1424,81 @@ fn cmd_analyze(
         mesh.num_blocks()
     );
 
+    let namer = OutputNamer::new(&naming.output_name)?;
+    let policy = naming.policy();
+
+    let exclusion_zones = load_exclusion_zones(&mesh, &roi.exclude_sideset)?;
+
+    // Command-line ROI takes precedence over the config file
+    let roi = match roi.spec() {
+        Some(spec) => Roi::load(Some(spec), roi.roi_clip_output)?,
+        None => Roi::load(config.roi.clone(), config.roi_clip_output || roi.roi_clip_output)?,
+    };
+
     // Extract surfaces
     println!("Extracting surfaces...");
-    let surfaces = extract_surface(&mesh)?;
+    let (surfaces, _) = exclusion_zones.apply(extract_surface(&mesh)?);
+    let surfaces = roi.clip_surfaces(surfaces);
     println!("Extracted {} surfaces\n", surfaces.len());
 
+    if dry_run {
+        println!("{}", "=".repeat(60));
+        println!("DRY RUN");
+        println!("{}", "=".repeat(60));
+        println!();
+        println!("Contact pairs that would be analyzed:");
+        for (idx, pair_config) in config.contact_pairs.iter().enumerate() {
+            let surface_a = config.resolve_surface(&pair_config.surface_a, &mesh, &surfaces)?;
+            let surface_b = config.resolve_surface(&pair_config.surface_b, &mesh, &surfaces)?;
+            let in_range = match (
+                SurfaceBounds::of_surface(&surface_a),
+                SurfaceBounds::of_surface(&surface_b),
+            ) {
+                (Some(a), Some(b)) => {
+                    let criteria = pair_config.criteria.for_surfaces(&surface_a, &surface_b);
+                    a.within(&b, criteria.search_radius())
+                }
+                _ => true,
+            };
+            println!(
+                "  [{}] {} {} {}: {} {} {} faces{}",
+                idx + 1,
+                pair_config.surface_a,
+                glyphs.between(),
+                pair_config.surface_b,
+                surface_a.num_faces(),
+                glyphs.times(),
+                surface_b.num_faces(),
+                if in_range { "" } else { " (too far apart, no pairs expected)" }
+            );
+        }
+        if !config.expected_contacts.is_empty() {
+            println!();
+            println!("Expected contacts to check: {}", config.expected_contacts.len());
+        }
+        println!();
+        println!("Files that would be written to {}:", output.display());
+        for (idx, pair_config) in config.contact_pairs.iter().enumerate() {
+            let filename = analyze_output_filename(pair_config, idx + 1, &namer);
+            let exists = output.join(&filename).exists();
+            println!("  {}{}", filename, if exists { " (already exists)" } else { "" });
+        }
+        if !no_cache {
+            println!("  {}/ (result cache)", CACHE_DIR_NAME);
+        }
+        return Ok(());
+    }
+
     // Create output directory
     std::fs::create_dir_all(&output)?;
 
+    // Stop before any detection if existing results would be clobbered
+    if policy == OverwritePolicy::Error {
+        for (idx, pair_config) in config.contact_pairs.iter().enumerate() {
+            policy.claim(&output.join(analyze_output_filename(pair_config, idx + 1, &namer)))?;
+        }
+    }
+
     // Setup progress bar
     let pb = ProgressBar::new(config.contact_pairs.len() as u64);
     pb.set_style(
@@ -406,140 +1508,361 @@ fn cmd_analyze(
             .progress_chars("=>-"),
     );
 
+    let volume = VolumeIndex::new(&mesh);
+    let cache = if no_cache {
+        None
+    } else {
+        Some(ResultCache::in_output_dir(&output)?)
+    };
+    let mut num_cached = 0;
+
+    // Per-group totals: (pairs involved, face pairs, paired area)
+    let mut group_totals: std::collections::BTreeMap<String, (usize, usize, f64)> =
+        std::collections::BTreeMap::new();
+    let mut summary = RunSummary::new(mesh.element_blocks.keys());
+
+    // Every configured pair as analyzed, with the surfaces it was resolved to
+    let mut interfaces: Vec<ContactInterface> = Vec::new();
+    let mut pair_surfaces = Vec::new();
+
+    let writers = SurfaceFormats::with_builtin_formats(vtk_version, None);
+    let attributes = AttributeSet::contact_defaults();
+
     // Process each contact pair
     for (idx, pair_config) in config.contact_pairs.iter().enumerate() {
         pb.set_message(format!(
-            "{} ↔ {}",
-            pair_config.surface_a, pair_config.surface_b
+            "{} {} {}",
+            pair_config.surface_a,
+            glyphs.between(),
+            pair_config.surface_b
         ));
 
-        // Find surfaces
-        let surface_a = surfaces
-            .iter()
-            .find(|s| s.part_name == pair_config.surface_a)
-            .ok_or_else(|| {
-                contact_detector::ContactDetectorError::ElementBlockNotFound(
-                    pair_config.surface_a.clone(),
-                )
-            })?;
-
-        let surface_b = surfaces
-            .iter()
-            .find(|s| s.part_name == pair_config.surface_b)
-            .ok_or_else(|| {
-                contact_detector::ContactDetectorError::ElementBlockNotFound(
-                    pair_config.surface_b.clone(),
-                )
-            })?;
-
-        // Detect contact pairs
-        let results = detect_contact_pairs(surface_a, surface_b, &pair_config.criteria)?;
+        // Resolve surfaces (patches, blocks, side sets or configured groups)
+        let surface_a = config.resolve_surface(&pair_config.surface_a, &mesh, &surfaces)?;
+        let surface_b = config.resolve_surface(&pair_config.surface_b, &mesh, &surfaces)?;
+
+        // Detect contact pairs, reusing cached results for unchanged inputs
+        let results = match &cache {
+            Some(cache) => {
+                let key = ResultCache::key(
+                    &surface_a,
+                    &surface_b,
+                    &pair_config.criteria,
+                    roi.cache_context(&surface_a, &surface_b),
+                );
+                match cache.load(&key, &surface_a, &surface_b) {
+                    Some(results) => {
+                        num_cached += 1;
+                        results
+                    }
+                    None => {
+                        let results =
+                            roi.detect(&surface_a, &surface_b, &pair_config.criteria, Some(&volume))?;
+                        cache.store(&key, &surface_a, &surface_b, &results)?;
+                        results
+                    }
+                }
+            }
+            None => roi.detect(&surface_a, &surface_b, &pair_config.criteria, Some(&volume))?,
+        };
 
         // Compute metrics
-        let metrics_a = SurfaceMetrics::compute(&results, surface_a, true);
+        let metrics = PairMetrics::compute(&results, &surface_a, &surface_b);
+
+        // Roll up per group
+        for (name, metrics) in [
+            (&pair_config.surface_a, &metrics.surface_a),
+            (&pair_config.surface_b, &metrics.surface_b),
+        ] {
+            if config.is_group(name) {
+                let entry = group_totals.entry(name.clone()).or_insert((0, 0, 0.0));
+                entry.0 += 1;
+                entry.1 += metrics.num_pairs;
+                entry.2 += metrics.paired_area;
+            }
+        }
+
+        // Roll up over the run; group members' parts count as in contact
+        // if there is enough of it
+        let accepted = metrics.meets_acceptance(&pair_config.criteria);
+        if results.num_pairs() > 0 && accepted {
+            summary.add_pair(idx + 1, &surface_a, &surface_b, &results, &metrics);
+            for name in [&pair_config.surface_a, &pair_config.surface_b] {
+                for member in config.groups.get(name).into_iter().flatten() {
+                    summary.mark_in_contact(member.split(':').next().unwrap_or(member));
+                }
+            }
+        }
 
         // Generate output filename
-        let output_filename = pair_config.output_file.clone().unwrap_or_else(|| {
-            format!(
-                "contact_{}_{}.vtu",
-                sanitize_filename(&pair_config.surface_a),
-                sanitize_filename(&pair_config.surface_b)
-            )
-        });
+        let output_filename = analyze_output_filename(pair_config, idx + 1, &namer);
 
         let output_path = output.join(&output_filename);
 
         // Write results
-        write_surface_with_contact_metadata(surface_a, &results, &metrics_a, &output_path, vtk_version)?;
+        let write = policy.claim(&output_path)?;
+        if write {
+            let context = FaceContext::new(&results, &surface_a, &surface_b, true);
+            writers.write_contact(&attributes, &context, &output_path)?;
+        }
 
         // Print brief summary
         println!(
-            "\n[{}/{}] {} ↔ {}:",
+            "\n[{}/{}] {} {} {}:",
             idx + 1,
             config.contact_pairs.len(),
             pair_config.surface_a,
+            glyphs.between(),
             pair_config.surface_b
         );
         println!(
-            "  Pairs: {}, Unpaired: {}, Avg Distance: {:.6}",
-            metrics_a.num_pairs, metrics_a.num_unpaired, metrics_a.avg_distance
+            "  Pairs: {}, Unpaired: {}, Avg Distance: {}",
+            metrics.num_pairs(),
+            metrics.surface_a.num_unpaired,
+            format.show(metrics.surface_a.avg_distance)
         );
-        println!("  Output: {}", output_filename);
+        if results.num_pairs() > 0 && !accepted {
+            println!("  Below the minimum paired area or coverage: not counted as contact");
+        }
+        if write {
+            println!("  Output: {}", output_filename);
+        } else {
+            println!("  Kept existing: {}", output_filename);
+        }
+
+        let mut interface = ContactInterface::new(
+            idx + 1,
+            InterfaceSide::master(pair_config.surface_a.clone(), pair_surfaces.len()),
+            InterfaceSide::slave(pair_config.surface_b.clone(), pair_surfaces.len() + 1),
+            results,
+            metrics,
+        );
+        interface.artifacts.push(output_path);
+        interfaces.push(interface);
+        pair_surfaces.extend([surface_a, surface_b]);
 
         pb.inc(1);
     }
 
     pb.finish_with_message("Complete");
 
+    // Check the expected contacts, detecting those not analyzed above with
+    // the default criteria
+    let mut failed_expectations = 0;
+    let mut expectation_report = Vec::new();
+    for expected in &config.expected_contacts {
+        let analyzed = interfaces
+            .iter()
+            .find(|interface| interface.connects(&expected.surface_a, &expected.surface_b))
+            .and_then(|interface| interface.metrics_from(&expected.surface_a));
+        let metrics = match analyzed {
+            Some(metrics) => metrics,
+            None => {
+                let surface_a = config.resolve_surface(&expected.surface_a, &mesh, &surfaces)?;
+                let surface_b = config.resolve_surface(&expected.surface_b, &mesh, &surfaces)?;
+                let results =
+                    roi.detect(&surface_a, &surface_b, &config.default_criteria, Some(&volume))?;
+                PairMetrics::compute(&results, &surface_a, &surface_b)
+            }
+        };
+        let violations = expected.violations(&metrics);
+        let label = format!(
+            "{} {} {}",
+            expected.surface_a,
+            glyphs.between(),
+            expected.surface_b
+        );
+        if violations.is_empty() {
+            expectation_report.push(format!(
+                "  PASS {} (coverage {:.1}%, gap {}..{})",
+                label,
+                metrics.coverage_a() * 100.0,
+                format.show(metrics.surface_a.min_distance),
+                format.show(metrics.surface_a.max_distance)
+            ));
+        } else {
+            failed_expectations += 1;
+            expectation_report.push(format!("  FAIL {}: {}", label, violations.join(", ")));
+        }
+    }
+
     println!("\n{}", "=".repeat(60));
     println!("BATCH ANALYSIS COMPLETE");
     println!("{}", "=".repeat(60));
     println!("Processed {} contact pairs", config.contact_pairs.len());
+    if num_cached > 0 {
+        println!(
+            "Reused cached results for {} unchanged pair(s)",
+            num_cached
+        );
+    }
+    if !group_totals.is_empty() {
+        println!();
+        println!("Group summary:");
+        for (name, (num_pair_configs, num_face_pairs, paired_area)) in &group_totals {
+            println!(
+                "  {}: {} contact pair(s), {} face pairs, paired area {}",
+                name,
+                num_pair_configs,
+                num_face_pairs,
+                format.show(*paired_area)
+            );
+        }
+        println!();
+    }
+    summary.print(format, glyphs);
+    println!();
+    if !expectation_report.is_empty() {
+        println!(
+            "Expected contacts ({} of {} met):",
+            config.expected_contacts.len() - failed_expectations,
+            config.expected_contacts.len()
+        );
+        for line in &expectation_report {
+            println!("{}", line);
+        }
+        println!();
+    }
     println!("Results written to: {}", output.display());
     println!("{}", "=".repeat(60));
 
+    manifest.set_resolved_config(&config)?;
+    manifest.finish(&output)?;
+
+    if failed_expectations > 0 {
+        return Err(contact_detector::ContactDetectorError::ContactCheckFailed(format!(
+            "{} of {} expected contact(s) violated",
+            failed_expectations,
+            config.expected_contacts.len()
+        )));
+    }
+
     Ok(())
 }
 
-fn sanitize_filename(name: &str) -> String {
-    name.chars()
-        .map(|c| {
-            if c.is_alphanumeric() || c == '_' || c == '-' {
-                c
-            } else {
-                '_'
-            }
-        })
-        .collect()
+/// Record the mesh and the files it is loaded with as inputs of a run
+fn record_inputs(
+    manifest: &mut RunManifest,
+    input: &std::path::Path,
+    load: &MeshLoadOptions,
+    roi: &cli::RoiArgs,
+) -> Result<()> {
+    manifest.add_input(input, "mesh")?;
+    if let Some(path) = &load.assembly {
+        manifest.add_input(path, "assembly")?;
+    }
+    if let Some(path) = &roi.roi_stl {
+        manifest.add_input(path, "roi")?;
+    }
+    Ok(())
+}
+
+/// Result file of a configured pair: its own name, or one from the naming
+/// template
+fn analyze_output_filename(
+    pair_config: &contact_detector::config::ContactPairConfig,
+    pair_id: usize,
+    namer: &contact_detector::io::OutputNamer,
+) -> String {
+    pair_config.output_file.clone().unwrap_or_else(|| {
+        namer.file_name(pair_id, &pair_config.surface_a, &pair_config.surface_b, "vtu")
+    })
+}
+
+/// Whether to write the output file at `path` under the overwrite policy,
+/// noting an existing file that is kept
+fn claim_output(policy: contact_detector::io::OverwritePolicy, path: &std::path::Path) -> Result<bool> {
+    let write = policy.claim(path)?;
+    if !write {
+        println!("Kept existing output: {}", path.display());
+    }
+    Ok(write)
 }
 
+/// Faces of one side of a detected pair that go into its sideset: the whole
+/// patch, or only the paired faces
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all, fields(input = %input.display()))]
 fn cmd_auto_contact(
     input: std::path::PathBuf,
-    max_gap: f64,
-    max_penetration: f64,
-    max_angle: f64,
+    criteria: contact_detector::contact::ContactCriteria,
+    spatial_index: contact_detector::contact::SpatialIndexKind,
+    algorithm: contact_detector::contact::DetectionAlgorithmKind,
+    rejection_stats: bool,
+    sdf_spacing: Option<f64>,
+    coarse_stride: Option<usize>,
+    pair_time_limit: Option<f64>,
+    pair_max_faces: Option<usize>,
+    revisit_skipped: bool,
+    preview: Option<usize>,
+    dry_run: bool,
+    no_occlusion_check: bool,
     min_pairs: usize,
+    min_paired_area: Option<f64>,
+    min_coverage_fraction: Option<f64>,
+    merge_pairs: bool,
+    allowed_contacts: Option<std::path::PathBuf>,
+    patch_filter: contact_detector::mesh::PatchFilter,
     output: std::path::PathBuf,
-    vtk_version: Option<(u8, u8)>,
+    vtk_version: Option<contact_detector::io::VtkVersion>,
     export_metadata: bool,
+    save_results: bool,
+    export_manifest: bool,
+    manifest_format: cli::ManifestFormatArg,
+    export_outlines: bool,
+    export_graph: bool,
+    export_broad_phase: bool,
+    export_nodal_status: Option<cli::NodalStatusFormatArg>,
+    vis_max_faces: Option<usize>,
+    paraview_script: bool,
+    max_size_ratio: f64,
+    refinement_report: bool,
+    conformity_tolerance: Option<f64>,
+    pressure_cone: Option<contact_detector::contact::PressureCone>,
+    fastener_clearance: Option<f64>,
+    thermal_config: Option<std::path::PathBuf>,
     export_sidesets: bool,
+    sideset_dist_factors: Option<contact_detector::contact::FaceValueSource>,
+    sideset_paired_only: bool,
+    sideset_name_template: String,
+    max_name_length: usize,
     visualize_with_skin: bool,
     multiblock: bool,
     export_nodesets: bool,
     _export_materials: bool,
     export_volume: bool,
+    triangulate: Option<contact_detector::mesh::Triangulation>,
+    naming: cli::OutputArgs,
+    roi: cli::RoiArgs,
+    scope: cli::AssemblyScopeArgs,
+    mut manifest: RunManifest,
+    formats: FloatFormats,
+    glyphs: Glyphs,
+    load: MeshLoadOptions,
 ) -> Result<()> {
-    use contact_detector::contact::{detect_contact_pairs, ContactCriteria, SurfaceMetrics};
-    use contact_detector::io::{write_surface_with_contact_metadata, ContactMetadata};
-    use contact_detector::mesh::extract_surface;
+    use contact_detector::contact::{plan_all, AutoContactOptions, BroadPhaseStatus, ContactInterface, PairBudget};
+    use contact_detector::io::{
+        write_broad_phase_to_vtp, write_conductance_table, write_decimated_contact_vtu, write_outlines_to_vtp, write_vtk,
+        AttributeSet, ContactMetadata, ContactPairMetadata, FaceContext, OutlineGroup, OutputLegend, OutputNamer,
+        OverwritePolicy, ParaViewScript, SurfaceFormats,
+    };
     use indicatif::{ProgressBar, ProgressStyle};
 
     #[cfg(feature = "exodus")]
-    use contact_detector::io::{add_contact_sidesets_to_mesh, write_contact_surfaces_with_skin, write_exodus};
+    use contact_detector::io::{
+        add_auto_contact_sidesets, write_contact_surfaces_with_skin, write_exodus_with_options,
+        ExodusWriteOptions,
+    };
 
     println!("{}", "=".repeat(60));
     println!("AUTOMATIC CONTACT DETECTION");
     println!("{}", "=".repeat(60));
     println!();
 
-    log::info!("Reading mesh file: {}", input.display());
+    tracing::info!("Reading mesh file: {}", input.display());
 
     // Read mesh from file
-    let mesh = if input.extension().and_then(|s| s.to_str()) == Some("json") {
-        contact_detector::io::read_json_mesh(&input)?
-    } else {
-        #[cfg(feature = "exodus")]
-        {
-            let reader = ExodusReader::open(&input)?;
-            reader.read_mesh()?
-        }
-        #[cfg(not(feature = "exodus"))]
-        {
-            return Err(contact_detector::ContactDetectorError::ConfigError(
-                "Exodus support not compiled in. Install libhdf5-dev and libnetcdf-dev, then rebuild with --features exodus".to_string()
-            ));
-        }
-    };
+    let (mut mesh, mut warnings) = read_mesh(&input, &load)?;
 
     println!(
         "Loaded mesh: {} nodes, {} elements, {} blocks",
@@ -549,47 +1872,302 @@ fn cmd_auto_contact(
     );
     println!();
 
-    // Extract all surfaces
-    println!("Extracting surfaces from all element blocks...");
-    let surfaces = extract_surface(&mesh)?;
-    println!("Extracted {} surfaces:", surfaces.len());
-    for surface in &surfaces {
+    // Extract all surfaces
+    println!("Extracting surfaces from all element blocks...");
+    record_inputs(&mut manifest, &input, &load, &roi)?;
+    let exclusion_zones = load_exclusion_zones(&mesh, &roi.exclude_sideset)?;
+    let roi = Roi::load(roi.spec(), roi.roi_clip_output)?;
+    if roi.region.is_some() {
+        println!("Restricting detection to the region of interest");
+    }
+    if let Some(name) = &scope.within_assembly {
+        println!("Restricting detection to assembly '{}'", name);
+    }
+    if let Some((a, b)) = &scope.between_assemblies {
+        println!(
+            "Restricting detection to contacts between assemblies '{}' and '{}'",
+            a, b
+        );
+    }
+    let scope = scope.resolve(&mesh)?;
+    if let Some(path) = &allowed_contacts {
+        manifest.add_input(path, "allowed_contacts")?;
+    }
+    let allowed_contacts = allowed_contacts
+        .map(|path| contact_detector::config::AllowedContacts::from_file(&path))
+        .transpose()?;
+    if let Some(path) = &thermal_config {
+        manifest.add_input(path, "thermal_config")?;
+    }
+    let thermal_config = thermal_config
+        .map(|path| contact_detector::contact::ThermalConfig::from_file(&path))
+        .transpose()?;
+    let namer = OutputNamer::new(&naming.output_name)?;
+    let policy = naming.policy();
+    let writers = SurfaceFormats::with_builtin_formats(vtk_version, triangulate);
+    let attributes = AttributeSet::contact_defaults();
+
+    // Set up contact detection criteria
+    let (max_gap, max_penetration, max_angle, min_facing) = (
+        criteria.max_gap_distance,
+        criteria.max_penetration,
+        criteria.max_normal_angle,
+        criteria.min_facing,
+    );
+    let relative_max_gap = criteria.relative_max_gap;
+    let mut criteria = criteria;
+    criteria.spatial_index = spatial_index;
+    criteria.algorithm = algorithm;
+    criteria.record_rejections = rejection_stats;
+    criteria.min_paired_area = min_paired_area;
+    criteria.min_coverage_fraction = min_coverage_fraction;
+    let options = AutoContactOptions {
+        criteria,
+        min_pairs,
+        patch_filter,
+        exclusion_zones,
+        occlusion_check: !no_occlusion_check,
+        coarse_stride,
+        preview,
+        region: roi.region.clone(),
+        clip_to_region: roi.clip_output,
+        sdf_spacing,
+        record_broad_phase: export_broad_phase,
+        budget: PairBudget {
+            time_limit: pair_time_limit.map(std::time::Duration::from_secs_f64),
+            max_faces: pair_max_faces,
+            revisit_skipped,
+        },
+        fastener_clearance,
+        scope,
+    };
+
+    // Surface extraction and broad phase
+    let plan = plan_all(&mesh, &options)?;
+    let format = formats.console;
+    println!("Extracted {} surfaces:", plan.surfaces.len());
+    for surface in &plan.surfaces {
+        println!(
+            "  - {}: {} faces, area: {}",
+            surface.part_name,
+            surface.num_faces(),
+            format.show(surface.total_area())
+        );
+    }
+    println!();
+
+    if let Some(target_faces) = preview {
+        println!(
+            "Preview: decimated to {} faces in total (at most about {} per surface)",
+            plan.surfaces.iter().map(|s| s.num_faces()).sum::<usize>(),
+            target_faces
+        );
+        println!();
+    }
+
+    if !plan.filtered_patches.is_empty() {
+        println!(
+            "Filtered {} small patch(es) from detection:",
+            plan.filtered_patches.len()
+        );
+        for patch in &plan.filtered_patches {
+            println!("  - {}: {}", patch.name, patch.reason);
+        }
+        println!();
+    }
+
+    println!("Contact detection criteria:");
+    match relative_max_gap {
+        Some(relative) => println!(
+            "  Max gap:         {}% of the smaller part's {}, per pair",
+            relative.percent,
+            relative.measure.description()
+        ),
+        None => println!("  Max gap:         {}", format.show(max_gap)),
+    }
+    println!("  Max penetration: {}", format.show(max_penetration));
+    println!("  Max angle:       {:.1}{}", max_angle, glyphs.degrees());
+    println!("  Min facing:      {:.3}", min_facing);
+    println!("  Min pairs:       {}", min_pairs);
+    if let Some(area) = min_paired_area {
+        println!("  Min paired area: {}", format.show(area));
+    }
+    if let Some(fraction) = min_coverage_fraction {
+        println!("  Min coverage:    {:.1}%", 100.0 * fraction);
+    }
+    if let Some(spacing) = sdf_spacing {
+        println!("  SDF spacing:     {}", format.show(spacing));
+    }
+    println!();
+
+    // Files written once per run (if any contact is found)
+    let mut run_files: Vec<String> = Vec::new();
+    if multiblock {
+        run_files.push("contact_analysis.vtm".to_string());
+    }
+    if refinement_report {
+        run_files.push("mesh_refinement_report.json".to_string());
+    }
+    if pressure_cone.is_some() {
+        run_files.push("bolt_footprint_report.json".to_string());
+    }
+    if fastener_clearance.is_some() {
+        run_files.push("fastener_fits.json".to_string());
+    }
+    if thermal_config.is_some() {
+        run_files.push("thermal_conductance.csv".to_string());
+    }
+    if let Some(format) = export_nodal_status {
+        run_files.push(format.file_name().to_string());
+    }
+    if export_outlines {
+        run_files.push("contact_outlines.vtp".to_string());
+    }
+    if export_graph {
+        run_files.push("interface_graph.dot".to_string());
+        run_files.push("interface_graph.json".to_string());
+        if !mesh.assemblies.is_empty() {
+            run_files.push("interface_graph_assemblies.dot".to_string());
+            run_files.push("interface_graph_assemblies.json".to_string());
+        }
+    }
+    run_files.push("contact_legend.json".to_string());
+    if paraview_script {
+        run_files.push("contact_state.py".to_string());
+    }
+    if export_manifest {
+        run_files.push(format!("contact_manifest.{}", manifest_format.extension()));
+    }
+    if export_sidesets && cfg!(feature = "exodus") {
+        run_files.push("mesh_with_contact_sidesets.exo".to_string());
+    }
+    if export_metadata {
+        run_files.push("contact_metadata.json".to_string());
+    }
+    if save_results {
+        run_files.push("contact_results.json".to_string());
+    }
+
+    if dry_run {
+        println!("{}", "=".repeat(60));
+        println!("DRY RUN");
+        println!("{}", "=".repeat(60));
+        println!();
+        println!(
+            "{} of {} surface pair(s) would be tested ({} too far apart{}):",
+            plan.candidates.len(),
+            plan.total_tests,
+            plan.skipped_far_apart,
+            if coarse_stride.is_some() {
+                format!(", {} without a coarse hit", plan.skipped_coarse)
+            } else {
+                String::new()
+            }
+        );
+        for (idx, &(i, j)) in plan.candidates.iter().enumerate() {
+            let (surface_a, surface_b) = (&plan.surfaces[i], &plan.surfaces[j]);
+            let estimate = match &plan.coarse_estimates {
+                Some(estimates) => format!(" (~{} face pairs)", estimates[idx].estimated_pairs()),
+                None => String::new(),
+            };
+            println!(
+                "  {} {} {}: {} {} {} faces{}",
+                surface_a.part_name,
+                glyphs.between(),
+                surface_b.part_name,
+                surface_a.num_faces(),
+                glyphs.times(),
+                surface_b.num_faces(),
+                estimate
+            );
+        }
+        println!();
+
+        if preview.is_some() {
+            println!("Preview runs write no files.");
+            return Ok(());
+        }
         println!(
-            "  - {}: {} faces, area: {:.6}",
-            surface.part_name,
-            surface.num_faces(),
-            surface.total_area()
+            "Files that would be written to {} if contact is found:",
+            output.display()
         );
+        if multiblock {
+            println!("  contact_analysis.vtm (blocks in contact_pairs/)");
+        } else {
+            println!("  {}.vtu per pair in contact", namer.template());
+        }
+        if let Some(max_faces) = vis_max_faces {
+            println!(
+                "  {}.vis.vtu per pair whose first surface has over {} faces",
+                namer.template(),
+                max_faces
+            );
+        }
+        for file in run_files.iter().filter(|file| !file.ends_with(".vtm")) {
+            let exists = output.join(file).exists();
+            println!("  {}{}", file, if exists { " (already exists)" } else { "" });
+        }
+        if export_broad_phase {
+            println!("  broad_phase.json, broad_phase.vtp (written before detection)");
+        }
+        println!("  in_progress/ with each pair as it is found (removed when the run completes)");
+        return Ok(());
     }
-    println!();
 
-    // Set up contact detection criteria
-    let criteria = ContactCriteria::new(max_gap, max_penetration, max_angle);
-
-    println!("Contact detection criteria:");
-    println!("  Max gap:         {:.6}", max_gap);
-    println!("  Max penetration: {:.6}", max_penetration);
-    println!("  Max angle:       {:.1}°", max_angle);
-    println!("  Min pairs:       {}", min_pairs);
-    println!();
+    // Create output directory (preview runs write nothing)
+    if preview.is_none() {
+        std::fs::create_dir_all(&output)?;
 
-    // Create output directory
-    std::fs::create_dir_all(&output)?;
+        // Stop before any detection if existing results would be clobbered
+        if policy == OverwritePolicy::Error {
+            for file in &run_files {
+                policy.claim(&output.join(file))?;
+            }
+        }
+    }
 
-    // Test all pairs of surfaces
-    let num_surfaces = surfaces.len();
-    let total_tests = (num_surfaces * (num_surfaces - 1)) / 2; // n choose 2
+    // The broad phase is written before detection, whether or not any
+    // contact is found
+    if let (Some(graph), None) = (&plan.broad_phase, preview) {
+        println!(
+            "Broad phase: {} candidate(s), {} with spheres apart, {} with boxes apart, {} without a coarse hit",
+            graph.count(BroadPhaseStatus::Candidate),
+            graph.count(BroadPhaseStatus::SpheresApart),
+            graph.count(BroadPhaseStatus::BoxesApart),
+            graph.count(BroadPhaseStatus::NoCoarseHit)
+        );
+        let json_path = output.join("broad_phase.json");
+        if claim_output(policy, &json_path)? {
+            graph.export(&json_path)?;
+            println!("Broad phase graph written to: {}", json_path.display());
+        }
+        let vtp_path = output.join("broad_phase.vtp");
+        if claim_output(policy, &vtp_path)? {
+            write_broad_phase_to_vtp(graph, &vtp_path, vtk_version)?;
+            println!("Broad phase bounding boxes written to: {}", vtp_path.display());
+        }
+        println!();
+    }
 
-    if total_tests == 0 {
+    if plan.total_tests == 0 {
         println!("Not enough surfaces to test for contact (need at least 2)");
         return Ok(());
     }
 
-    println!("Testing {} surface pair combinations...", total_tests);
+    println!("Testing {} surface pair combinations...", plan.total_tests);
     println!("{}", "=".repeat(60));
 
+    if let Some(stride) = coarse_stride {
+        println!(
+            "Coarse pass (every {} face(s)): {} of {} surface pair(s) likely in contact",
+            stride,
+            plan.candidates.len(),
+            plan.total_tests - plan.skipped_far_apart
+        );
+    }
+
     // Setup progress bar
-    let pb = ProgressBar::new(total_tests as u64);
+    let pb = ProgressBar::new(plan.candidates.len() as u64);
     pb.set_style(
         ProgressStyle::default_bar()
             .template("[{elapsed_precise}] {bar:40.cyan/blue} {pos}/{len} {msg}")
@@ -597,59 +2175,94 @@ fn cmd_auto_contact(
             .progress_chars("=>-"),
     );
 
-    let mut detected_pairs = Vec::new();
-
-    // Test all unique pairs (i, j) where i < j
-    for i in 0..num_surfaces {
-        for j in (i + 1)..num_surfaces {
-            let surface_a = &surfaces[i];
-            let surface_b = &surfaces[j];
-
-            pb.set_message(format!("{} ↔ {}", surface_a.part_name, surface_b.part_name));
-
-            // Detect contact pairs
-            let results = detect_contact_pairs(surface_a, surface_b, &criteria)?;
-
-            // Check if this pair has significant contact
-            if results.num_pairs() >= min_pairs {
-                let metrics_a = SurfaceMetrics::compute(&results, surface_a, true);
-                let metrics_b = SurfaceMetrics::compute(&results, surface_b, false);
-
-                detected_pairs.push((
-                    surface_a.part_name.clone(),
-                    surface_b.part_name.clone(),
-                    results,
-                    metrics_a,
-                    metrics_b,
-                    i,
-                    j,
-                ));
-
-                log::info!(
-                    "Found contact: {} ↔ {} ({} pairs)",
-                    surface_a.part_name,
-                    surface_b.part_name,
-                    detected_pairs.last().unwrap().2.num_pairs()
-                );
-            }
-
-            pb.inc(1);
-        }
+    // Write each pair (numbered in the order found) and append it to a
+    // metadata log as soon as it is detected, so a long run can be reviewed
+    // while it goes on and a crash keeps what was found. The final files
+    // below are numbered in surface order; pair files are moved into place
+    // and the directory is removed once the run completes.
+    let progress_dir = output.join("in_progress");
+    if preview.is_none() && progress_dir.exists() {
+        std::fs::remove_dir_all(&progress_dir)?;
     }
+    let mut found_files = std::collections::HashMap::new();
+
+    // Test the candidate pairs
+    let mut report = plan.run_with(
+        &mesh,
+        &options,
+        |idx, surface_a, surface_b| {
+            pb.set_position(idx as u64);
+            pb.set_message(format!(
+                "{} {} {}",
+                surface_a.part_name,
+                glyphs.between(),
+                surface_b.part_name
+            ));
+        },
+        |pair, surfaces| {
+            if preview.is_some() {
+                return Ok(());
+            }
+            std::fs::create_dir_all(&progress_dir)?;
+            let found_id = found_files.len() + 1;
+            let surface_a = pair.surface(surfaces, true);
+            let surface_b = pair.surface(surfaces, false);
+            let file_name = namer.file_name(found_id, &pair.side_a.part, &pair.side_b.part, "vtu");
+            let path = progress_dir.join(&file_name);
+            writers.write_contact(&attributes, &FaceContext::of_interface(pair, surfaces, true), &path)?;
+            ContactPairMetadata::new(found_id, surface_a, surface_b, &pair.results, &pair.metrics)
+                .append_to(progress_dir.join("contact_pairs.jsonl"))?;
+            pb.suspend(|| {
+                println!(
+                    "Found {} {} {} ({} pairs): in_progress/{}",
+                    pair.side_a.part,
+                    glyphs.between(),
+                    pair.side_b.part,
+                    pair.results.num_pairs(),
+                    file_name
+                )
+            });
+            found_files.insert((pair.side_a.surface, pair.side_b.surface), path);
+            Ok(())
+        },
+    )?;
 
     pb.finish_with_message("Complete");
     println!();
 
+    if preview.is_some() {
+        println!("{}", "=".repeat(60));
+        println!("PREVIEW RESULTS");
+        println!("{}", "=".repeat(60));
+        println!();
+        if report.pairs.is_empty() {
+            println!("No parts in contact on the decimated surfaces.");
+        } else {
+            println!("{} part pair(s) touch on the decimated surfaces:", report.pairs.len());
+            for interface in &report.pairs {
+                println!(
+                    "  - {} {} {} ({} coarse face pairs)",
+                    interface.side_a.part,
+                    glyphs.between(),
+                    interface.side_b.part,
+                    interface.results.num_pairs()
+                );
+            }
+        }
+        println!();
+        println!("Run without --preview for full-resolution detection and output files.");
+        return Ok(());
+    }
+
     // Initialize metadata if export requested
-    let mut metadata = if export_metadata {
-        Some(ContactMetadata::new(
+    let mut metadata = export_metadata.then(|| {
+        ContactMetadata::of_auto_contact(
             input.to_string_lossy().to_string(),
-            &criteria,
+            &report,
+            &mesh,
             min_pairs,
-        ))
-    } else {
-        None
-    };
+        )
+    });
 
     // Report results
     println!("{}", "=".repeat(60));
@@ -657,12 +2270,101 @@ fn cmd_auto_contact(
     println!("{}", "=".repeat(60));
     println!();
 
-    if detected_pairs.is_empty() {
+    if report.rejected_facing > 0 {
+        println!(
+            "Rejected {} candidate face pair(s) that do not face each other (--min-facing {:.3})",
+            report.rejected_facing, min_facing
+        );
+        println!();
+    }
+    if report.rejected_occluded > 0 {
+        println!(
+            "Rejected {} pair(s) passing through a part's own material",
+            report.rejected_occluded
+        );
+        println!();
+    }
+    if report.below_acceptance > 0 {
+        println!(
+            "Dropped {} surface pair(s) with too little contact (--min-pairs, \
+             --min-paired-area, --min-coverage-fraction)",
+            report.below_acceptance
+        );
+        println!();
+    }
+    if !report.skipped_budget.is_empty() {
+        println!("Skipped {} surface pair(s) (budget):", report.skipped_budget.len());
+        for skip in &report.skipped_budget {
+            println!(
+                "  - {} {} {}: {}{}",
+                skip.part_a,
+                glyphs.between(),
+                skip.part_b,
+                skip.limit,
+                if skip.revisited {
+                    ", revisited without limits"
+                } else {
+                    ""
+                }
+            );
+        }
+        if !revisit_skipped {
+            println!("  Rerun with --revisit-skipped to test them after the other pairs");
+        }
+        println!();
+    }
+    if let Some(rejections) = &report.rejections {
+        println!("Rejections over all tested surface pairs (binding criterion first):");
+        rejections.print_summary("  ");
+        println!();
+    }
+
+    if report.pairs.is_empty() {
         println!("No contact pairs detected with the specified criteria.");
         println!();
+        if let Some((reason, faces)) = report.rejections.as_ref().and_then(|r| r.binding()) {
+            println!(
+                "Binding criterion: {} (stopped {} face(s))",
+                reason.criterion(),
+                faces
+            );
+            println!();
+        }
         println!("Suggestions:");
-        println!("  - Try increasing --max-gap (current: {:.6})", max_gap);
-        println!("  - Try increasing --max-angle (current: {:.1}°)", max_angle);
+        let tolerances = contact_detector::mesh::MeshStats::compute(&mesh).recommended_tolerances();
+        match (relative_max_gap, &tolerances) {
+            (Some(relative), _) => println!(
+                "  - Try a larger percentage for --max-gap (current: {})",
+                relative
+            ),
+            (None, Some(tolerances)) if tolerances.max_gap > max_gap => println!(
+                "  - Try --max-gap {} --max-penetration {} (current: {}), {:.0}% of the \
+                 median element edge ({}) in the finest block '{}'",
+                format.show(tolerances.max_gap),
+                format.show(tolerances.max_penetration),
+                format.show(max_gap),
+                100.0 * contact_detector::mesh::GAP_FRACTION_OF_EDGE,
+                format.show(tolerances.reference_edge_length),
+                tolerances.reference_block
+            ),
+            (None, Some(tolerances)) => println!(
+                "  - --max-gap {} already exceeds {:.0}% of the median element edge ({}) in \
+                 the finest block '{}'; the parts may not touch at all",
+                format.show(max_gap),
+                100.0 * contact_detector::mesh::GAP_FRACTION_OF_EDGE,
+                format.show(tolerances.reference_edge_length),
+                tolerances.reference_block
+            ),
+            (None, None) => println!(
+                "  - Try increasing --max-gap (current: {})",
+                format.show(max_gap)
+            ),
+        }
+        println!(
+            "  - Try increasing --max-angle (current: {:.1}{})",
+            max_angle,
+            glyphs.degrees()
+        );
         println!(
             "  - Try decreasing --min-pairs (current: {})",
             min_pairs
@@ -670,104 +2372,460 @@ fn cmd_auto_contact(
     } else {
         println!(
             "Detected {} contact pair(s):",
-            detected_pairs.len()
+            report.pairs.len()
         );
         println!();
 
+        // ParaView state script loading the files written below, with
+        // distances colored over the largest one found
+        let mut paraview = paraview_script.then(|| {
+            let range = report.max_distance();
+            ParaViewScript::new(if range > 0.0 { range } else { max_gap })
+        });
+
+        // Run-level totals, printed at the end and stored in the metadata
+        let mut summary = report.run_summary(&mesh);
+
+        // Pairs between blocks that are not on the allowed list
+        let unexpected: std::collections::HashSet<usize> = match &allowed_contacts {
+            Some(allowed) => report.unexpected_contacts(allowed).map(|pair| pair.id).collect(),
+            None => Default::default(),
+        };
+
         // Write output files for each detected pair
-        for (idx, (part_a, part_b, results, metrics_a, metrics_b, i, j)) in
-            detected_pairs.iter().enumerate()
-        {
+        let surfaces = &report.surfaces;
+        let num_interfaces = report.pairs.len();
+        for interface in report.pairs.iter_mut() {
+            let [fits_a, fits_b] = interface.patch_fits(surfaces);
+            let ContactInterface { id, side_a, side_b, results, metrics, artifacts } = interface;
+            let (part_a, part_b) = (&side_a.part, &side_b.part);
+            let (i, j) = (&side_a.surface, &side_b.surface);
             println!(
-                "[{}/{}] {} ↔ {}:",
-                idx + 1,
-                detected_pairs.len(),
+                "[{}/{}] {} {} {}:",
+                id,
+                num_interfaces,
                 part_a,
+                glyphs.between(),
                 part_b
             );
             println!("  Contact pairs:   {}", results.num_pairs());
             println!("  Unpaired (A):    {}", results.unpaired_a.len());
             println!("  Unpaired (B):    {}", results.unpaired_b.len());
-            println!("  Avg distance:    {:.6}", metrics_a.avg_distance);
-            println!("  Min distance:    {:.6}", metrics_a.min_distance);
-            println!("  Max distance:    {:.6}", metrics_a.max_distance);
-
-            // Add to metadata if export requested
-            if let Some(ref mut meta) = metadata {
-                meta.add_contact_pair(
-                    idx + 1,
-                    &surfaces[*i],
-                    &surfaces[*j],
-                    results,
-                    metrics_a,
-                    metrics_b,
+            println!("  Avg distance:    {}", format.show(metrics.surface_a.avg_distance));
+            println!("  Min distance:    {}", format.show(metrics.surface_a.min_distance));
+            println!("  Max distance:    {}", format.show(metrics.surface_a.max_distance));
+            println!(
+                "  Opposition dev:  median {:.2}{degrees}, 90th pct {:.2}{degrees}, max {:.2}{degrees}",
+                metrics.surface_a.opposition_deviation.median,
+                metrics.surface_a.opposition_deviation.p90,
+                metrics.surface_a.opposition_deviation.max,
+                degrees = glyphs.degrees()
+            );
+            println!(
+                "  Coverage (A/B):  {:.1}% / {:.1}%",
+                metrics.coverage_a() * 100.0,
+                metrics.coverage_b() * 100.0
+            );
+            println!("  Size ratio:      {:.2}", metrics.size_ratio);
+            println!("  Interface type:  {}", metrics.interface_type);
+            if let Some((reason, faces)) = results.rejections.as_ref().and_then(|r| r.binding()) {
+                println!("  Binding:         {} ({} unpaired face(s) on A)", reason.criterion(), faces);
+            }
+            if unexpected.contains(id) {
+                println!(
+                    "  UNEXPECTED CONTACT: {} {} {} is not on the allowed list",
+                    surfaces[*i].block_name(),
+                    glyphs.between(),
+                    surfaces[*j].block_name()
+                );
+                warnings.push(
+                    WarningKind::UnexpectedContact,
+                    format!("Unexpected contact: {} ↔ {} (pair {})", part_a, part_b, id),
                 );
+                summary.unexpected_contacts.push(format!("{} ↔ {}", part_a, part_b));
             }
+            fits_a.print("A", format);
+            fits_b.print("B", format);
 
             // Generate output filename
-            let output_filename = format!(
-                "contact_{}_{}.vtu",
-                sanitize_filename(part_a),
-                sanitize_filename(part_b)
-            );
+            let output_filename = namer.file_name(*id, part_a, part_b, "vtu");
 
             let output_path = output.join(&output_filename);
 
+            // Decimated copy of large surfaces for interactive viewing,
+            // written next to the multi-block dataset too
+            let mut wrote_vis_copy = false;
+            if let Some(max_faces) = vis_max_faces.filter(|&n| surfaces[*i].num_faces() > n) {
+                let vis_filename = namer.file_name(*id, part_a, part_b, "vis.vtu");
+                let vis_path = output.join(&vis_filename);
+                if policy.claim(&vis_path)? {
+                    let num_faces = write_decimated_contact_vtu(
+                        &surfaces[*i],
+                        results,
+                        max_faces,
+                        &vis_path,
+                        vtk_version,
+                    )?;
+                    println!("  Vis copy:        {} ({} faces)", vis_filename, num_faces);
+                } else {
+                    println!("  Kept existing:   {}", vis_filename);
+                }
+                if let Some(script) = paraview.as_mut() {
+                    script.add_contact(vis_filename, "contact_gap", "pair_id", 0);
+                }
+                artifacts.push(vis_path);
+                wrote_vis_copy = true;
+            }
+
             // Skip individual file writing if multiblock is enabled
             if multiblock {
                 continue;
             }
 
             // Write results - use enhanced visualization if requested
-            if visualize_with_skin {
+            let context = FaceContext::new(results, &surfaces[*i], &surfaces[*j], true);
+            let write = policy.claim(&output_path)?;
+            if write && visualize_with_skin {
                 #[cfg(feature = "exodus")]
                 {
                     write_contact_surfaces_with_skin(
                         &surfaces[*i],
                         &surfaces[*j],
                         results,
-                        &surfaces,
+                        surfaces,
                         part_a,
                         part_b,
-                        idx + 1,
+                        *id,
                         &output_path,
                         vtk_version,
+                        triangulate,
                     )?;
                 }
                 #[cfg(not(feature = "exodus"))]
                 {
-                    log::warn!("--visualize-with-skin requires exodus feature, falling back to standard output");
-                    write_surface_with_contact_metadata(
-                        &surfaces[*i],
-                        results,
-                        metrics_a,
-                        &output_path,
-                        vtk_version,
-                    )?;
+                    tracing::warn!("--visualize-with-skin requires exodus feature, falling back to standard output");
+                    writers.write_contact(&attributes, &context, &output_path)?;
                 }
+            } else if write {
+                // Already written with the same settings while detecting
+                match found_files.remove(&(*i, *j)) {
+                    Some(found) => std::fs::rename(found, &output_path)?,
+                    None => writers.write_contact(&attributes, &context, &output_path)?,
+                }
+            }
+
+            if write {
+                println!("  Output:          {}", output_filename);
             } else {
-                write_surface_with_contact_metadata(
-                    &surfaces[*i],
-                    results,
-                    metrics_a,
-                    &output_path,
-                    vtk_version,
-                )?;
+                println!("  Kept existing:   {}", output_filename);
+            }
+            println!();
+            artifacts.push(output_path);
+
+            // The script loads the decimated copy in place of the full file
+            if let Some(script) = paraview.as_mut().filter(|_| !wrote_vis_copy) {
+                if visualize_with_skin && cfg!(feature = "exodus") {
+                    script.add_contact(output_filename, "distance", "is_paired", 1);
+                } else {
+                    script.add_contact(output_filename, "contact_gap", "pair_id", 0);
+                }
+            }
+        }
+
+        // Check mesh density across each interface
+        let density_report = report.refinement_report(max_size_ratio);
+        density_report.print_summary(glyphs);
+        if refinement_report {
+            let report_path = output.join("mesh_refinement_report.json");
+            if claim_output(policy, &report_path)? {
+                density_report.export(&report_path)?;
+                println!("Mesh refinement report written to: {}", report_path.display());
             }
+        }
+        println!();
+
+        // Measure the contact patches and check them against the bolt
+        // pressure cones if requested
+        if let Some(cone) = pressure_cone {
+            let footprint_report = report.footprint_report(cone);
+            footprint_report.print_summary(format, glyphs);
+            let report_path = output.join("bolt_footprint_report.json");
+            if claim_output(policy, &report_path)? {
+                footprint_report.export(&report_path)?;
+                println!("Bolt footprint report written to: {}", report_path.display());
+            }
+            println!();
+        }
+
+        // Report the clearance of each fastener found
+        if let Some(ratio) = fastener_clearance {
+            let fastener_report = report.fastener_report(ratio);
+            fastener_report.print_summary(format, glyphs);
+            let report_path = output.join("fastener_fits.json");
+            if claim_output(policy, &report_path)? {
+                fastener_report.export(&report_path)?;
+                println!("Fastener fits written to: {}", report_path.display());
+            }
+            println!();
+        }
+
+        // Estimate the thermal conductance of each interface if requested
+        if let Some(thermal) = &thermal_config {
+            let conductances = report.thermal_conductances(thermal)?;
+            println!("Thermal contact conductance:");
+            for interface in &conductances {
+                println!(
+                    "  - {} ({}) {} {} ({}): {:.6e} per unit area, {:.6e} over {}",
+                    interface.part_a,
+                    interface.material_a,
+                    glyphs.between(),
+                    interface.part_b,
+                    interface.material_b,
+                    interface.conductance,
+                    interface.total_conductance,
+                    format.show(interface.paired_area)
+                );
+            }
+            let table_path = output.join("thermal_conductance.csv");
+            if claim_output(policy, &table_path)? {
+                write_conductance_table(&conductances, &table_path, formats.files)?;
+                println!("Thermal conductance table written to: {}", table_path.display());
+            }
+            println!();
+        }
+
+        // Check node conformity of each interface if requested
+        if let Some(tolerance) = conformity_tolerance {
+            println!("{}", "=".repeat(60));
+            println!("INTERFACE NODE CONFORMITY");
+            println!("{}", "=".repeat(60));
+            println!();
+            println!("  Node matching tolerance: {}", format.show(tolerance));
+            println!();
+
+            let conformity = report.conformity(tolerance);
+            let node_sets = report.add_unmatched_node_sets(&conformity, &mut mesh);
+            for ((interface, conformity), node_set) in
+                report.pairs.iter().zip(&conformity).zip(&node_sets)
+            {
+                println!(
+                    "  {} {} {}: {:.1}% of {} interface nodes matched ({})",
+                    interface.side_a.part,
+                    glyphs.between(),
+                    interface.side_b.part,
+                    conformity.matched_percent(),
+                    conformity.num_nodes_a + conformity.num_nodes_b,
+                    if conformity.is_conforming() {
+                        "conforming, nodes can be merged"
+                    } else {
+                        "non-conforming, needs a tie or contact"
+                    }
+                );
+
+                if let Some(name) = node_set {
+                    println!(
+                        "    {} unmatched node(s) exported as node set '{}'",
+                        conformity.unmatched_nodes.len(),
+                        name
+                    );
+                }
+            }
+            if let Some(meta) = metadata.as_mut() {
+                meta.set_conformity(&conformity);
+            }
+            println!();
+            println!("{}", "=".repeat(60));
+            println!();
+        }
+
+        // Extract the outline of each contact zone if requested
+        if export_outlines {
+            println!("{}", "=".repeat(60));
+            println!("CONTACT OUTLINES");
+            println!("{}", "=".repeat(60));
+            println!();
+
+            let mut groups = Vec::new();
+            for (interface, outlines) in report.pairs.iter().zip(report.outlines()) {
+                println!(
+                    "  {} {} {}:",
+                    interface.side_a.part,
+                    glyphs.between(),
+                    interface.side_b.part
+                );
+                for ((role, is_a), loops) in [(0, true), (1, false)].into_iter().zip(outlines) {
+                    let surface = interface.surface(&report.surfaces, is_a);
+                    println!(
+                        "    {}: {} loop(s), perimeter {}",
+                        surface.part_name,
+                        loops.len(),
+                        format.show(loops.iter().map(|l| l.length).sum::<f64>())
+                    );
+                    groups.push(OutlineGroup {
+                        pair_id: interface.id,
+                        role,
+                        loops,
+                    });
+                }
+            }
+
+            let outline_path = output.join("contact_outlines.vtp");
+            let write_outlines = claim_output(policy, &outline_path)?;
+            if write_outlines {
+                write_outlines_to_vtp(&groups, &mesh, &outline_path, vtk_version)?;
+            }
+            if let Some(script) = paraview.as_mut() {
+                script.add_context("contact_outlines.vtp");
+            }
+            println!();
+            if write_outlines {
+                println!("Contact outlines written to: {}", outline_path.display());
+            }
+            println!();
+        }
+
+        // Which parts touch which: floating parts and disconnected groups of
+        // parts would leave rigid body modes in the solver
+        let graph = report.interface_graph(&mesh);
+        let connectivity = graph.check_connectivity();
+        connectivity.print();
+        println!();
+        warnings.extend(connectivity.warnings());
+        if !connectivity.is_connected() {
+            summary.disconnected_components = connectivity.components;
+        }
+
+        // The same interfaces between the top-level assemblies of the mesh
+        let assembly_graph =
+            (!mesh.assemblies.is_empty()).then(|| graph.roll_up(&mesh.block_assemblies()));
+        if let Some(assembly_graph) = &assembly_graph {
+            println!("Interfaces by assembly:");
+            if assembly_graph.edges.is_empty() {
+                println!("  (none)");
+            }
+            for edge in &assembly_graph.edges {
+                let between = if edge.part_a == edge.part_b {
+                    format!("{} (internal)", edge.part_a)
+                } else {
+                    format!("{} {} {}", edge.part_a, glyphs.between(), edge.part_b)
+                };
+                println!(
+                    "  {}: {} pair(s), area {}, min gap {}",
+                    between,
+                    edge.contact_pairs,
+                    format.show(edge.paired_area),
+                    format.show(edge.min_gap)
+                );
+            }
+            println!();
+        }
+
+        // Export which parts touch which if requested
+        if export_graph {
+            println!(
+                "Interface graph: {} part(s), {} interface(s)",
+                graph.parts.len(),
+                graph.edges.len()
+            );
+            let dot_path = output.join("interface_graph.dot");
+            if claim_output(policy, &dot_path)? {
+                graph.write_dot(&dot_path)?;
+                println!("Interface graph written to: {}", dot_path.display());
+            }
+            let json_path = output.join("interface_graph.json");
+            if claim_output(policy, &json_path)? {
+                graph.export_json(&json_path)?;
+                println!("Interface adjacency written to: {}", json_path.display());
+            }
+            if let Some(assembly_graph) = &assembly_graph {
+                let dot_path = output.join("interface_graph_assemblies.dot");
+                if claim_output(policy, &dot_path)? {
+                    assembly_graph.write_dot(&dot_path)?;
+                    println!(
+                        "Assembly interface graph written to: {}",
+                        dot_path.display()
+                    );
+                }
+                let json_path = output.join("interface_graph_assemblies.json");
+                if claim_output(policy, &json_path)? {
+                    assembly_graph.export_json(&json_path)?;
+                    println!("Assembly adjacency written to: {}", json_path.display());
+                }
+            }
+            println!();
+        }
+
+        // Mark the contact status on the volume mesh nodes if requested
+        if let Some(format) = export_nodal_status {
+            let nodal = report.nodal_contact(&mesh);
+            println!(
+                "{} of {} node(s) in contact",
+                nodal.num_in_contact(),
+                mesh.num_nodes()
+            );
 
-            println!("  Output:          {}", output_filename);
+            let status_path = output.join(format.file_name());
+            match format {
+                cli::NodalStatusFormatArg::Vtu => {
+                    if claim_output(policy, &status_path)? {
+                        let mut status_mesh = mesh.clone();
+                        nodal.add_to_mesh(&mut status_mesh);
+                        write_vtk(&status_mesh, &status_path, vtk_version)?;
+                        println!("Nodal contact status written to: {}", status_path.display());
+                    }
+                }
+                #[cfg(feature = "exodus")]
+                cli::NodalStatusFormatArg::Exodus => {
+                    if claim_output(policy, &status_path)? {
+                        let mut status_mesh = mesh.clone();
+                        nodal.add_to_mesh(&mut status_mesh);
+                        let options = ExodusWriteOptions { max_name_length };
+                        write_exodus_with_options(&status_mesh, &status_path, &options)?;
+                        println!("Nodal contact status written to: {}", status_path.display());
+                    }
+                }
+                #[cfg(not(feature = "exodus"))]
+                cli::NodalStatusFormatArg::Exodus => {
+                    println!("WARNING: --export-nodal-status exodus requires exodus feature");
+                    println!("Skipping nodal status export.");
+                }
+            }
+            println!();
+        }
+
+        // Consolidate overlapping pairs into interfaces if requested
+        if merge_pairs {
+            let merged = report.merged_interfaces();
+
+            summary.num_interfaces = merged.len();
+            println!("Consolidated into {} interface(s):", merged.len());
+            for interface in &merged {
+                let pair_list: Vec<String> =
+                    interface.pair_ids.iter().map(|id| id.to_string()).collect();
+                println!(
+                    "  Interface {}: {} ({} face pairs, from pair(s) {})",
+                    interface.interface_id,
+                    interface.blocks.join(&format!(" {} ", glyphs.between())),
+                    interface.num_face_pairs,
+                    pair_list.join(", ")
+                );
+            }
             println!();
+
+            if let Some(ref mut meta) = metadata {
+                meta.interfaces = merged;
+            }
         }
 
         // Export multi-block VTM if requested
-        if multiblock {
-            use contact_detector::io::MultiBlockBuilder;
-            use contact_detector::io::vtu::DEFAULT_VTK_VERSION;
+        if multiblock && claim_output(policy, &output.join("contact_analysis.vtm"))? {
+            use contact_detector::io::{MultiBlockBuilder, VtkFileKind, VtkVersion};
 
             println!("Generating multi-block VTM dataset...");
 
-            let vtk_ver = vtk_version.unwrap_or(DEFAULT_VTK_VERSION);
+            let vtk_ver = VtkVersion::resolve(vtk_version, VtkFileKind::XmlMultiBlock)?;
             let mut mb_builder = MultiBlockBuilder::new(&output, "contact_analysis".to_string(), vtk_ver);
+            mb_builder.set_triangulation(triangulate);
 
             // Add volume mesh if requested
             if export_volume {
@@ -789,16 +2847,16 @@ fn cmd_auto_contact(
 
             // Add contact pairs
             println!("  Adding contact pairs...");
-            let contact_pair_data: Vec<_> = detected_pairs
+            let contact_pair_data: Vec<_> = report
+                .pairs
                 .iter()
-                .enumerate()
-                .map(|(_idx, (part_a, part_b, results, _metrics_a, _metrics_b, i, j))| {
+                .map(|interface| {
                     (
-                        part_a.clone(),
-                        part_b.clone(),
-                        surfaces[*i].clone(),
-                        surfaces[*j].clone(),
-                        results.clone(),
+                        interface.side_a.part.clone(),
+                        interface.side_b.part.clone(),
+                        interface.surface(&report.surfaces, true).clone(),
+                        interface.surface(&report.surfaces, false).clone(),
+                        interface.results.clone(),
                     )
                 })
                 .collect();
@@ -807,16 +2865,78 @@ fn cmd_auto_contact(
 
             // Write the multi-block meta file
             mb_builder.write()?;
+            warnings.extend(mb_builder.warnings().clone());
 
             println!("Multi-block VTM dataset written to: {}/contact_analysis.vtm", output.display());
             println!();
+
+            if let Some(script) = paraview.as_mut() {
+                script.add_contact("contact_analysis.vtm", "Distance", "IsPaired", 1);
+            }
+        }
+
+        // Names of the integer codes of the category arrays written above
+        let mut legend = OutputLegend::new();
+        legend.add_pair_labels(
+            report.pairs.iter().map(|interface| {
+                (
+                    interface.id,
+                    format!("{} ↔ {}", interface.side_a.part, interface.side_b.part),
+                )
+            }),
+        );
+        let legend_path = output.join("contact_legend.json");
+        if claim_output(policy, &legend_path)? {
+            legend.export(&legend_path)?;
+            println!("Array legend written to: {}", legend_path.display());
+        }
+        println!();
+
+        if let Some(script) = paraview.as_mut() {
+            script.set_legend(legend);
+        }
+
+        if let Some(script) = &paraview {
+            let script_path = output.join("contact_state.py");
+            if claim_output(policy, &script_path)? {
+                script.write(&script_path)?;
+                println!("ParaView state script written to: {}", script_path.display());
+            }
+            println!("  Open with: paraview --script={}", script_path.display());
+            println!();
         }
 
-        // Export metadata if requested
-        if let Some(meta) = metadata {
-            let metadata_path = output.join("contact_metadata.json");
-            meta.export(&metadata_path)?;
-            println!("Metadata exported to: {}", metadata_path.display());
+        // Unique per-pair sideset names, shared by the Exodus export and the manifest
+        if export_sidesets || export_manifest {
+            use contact_detector::io::SidesetNamer;
+
+            let mut namer = SidesetNamer::new(&sideset_name_template, mesh.side_sets.keys())?
+                .with_max_name_length(max_name_length);
+            namer.name_interfaces(&mut report.pairs, &mut warnings);
+
+            // The metadata names the sidesets as exported
+            if let Some(meta) = metadata.as_mut() {
+                meta.set_sideset_names(&report.pairs);
+            }
+        }
+
+        // Export the neutral contact manifest if requested
+        if export_manifest {
+            use contact_detector::io::ContactManifest;
+
+            let manifest = ContactManifest::of_auto_contact(
+                input.to_string_lossy().to_string(),
+                &report,
+                &mesh,
+                sideset_paired_only,
+                &mut warnings,
+            );
+
+            let manifest_path = output.join(format!("contact_manifest.{}", manifest_format.extension()));
+            if claim_output(policy, &manifest_path)? {
+                manifest.export(&manifest_path)?;
+                println!("Contact manifest written to: {}", manifest_path.display());
+            }
             println!();
         }
 
@@ -826,43 +2946,86 @@ fn cmd_auto_contact(
             {
                 println!("Exporting contact sidesets to Exodus file...");
 
-                // Create a copy of the mesh to add sidesets
+                // Create a copy of the mesh to add sidesets, one per side of
+                // every interface with the per-face contact fields (gap,
+                // normal angle, margins, ...) as side set variables
                 let mut mesh_with_sidesets = mesh.clone();
-
-                // Collect all contact surfaces with their sideset names
-                let mut contact_surfaces = Vec::new();
-                for (part_a, part_b, _results, _metrics_a, _metrics_b, i, j) in
-                    detected_pairs.iter()
-                {
-                    let sideset_name_a = format!("auto_contact_{}", sanitize_filename(part_a));
-                    let sideset_name_b = format!("auto_contact_{}", sanitize_filename(part_b));
-
-                    contact_surfaces.push((sideset_name_a, &surfaces[*i]));
-                    contact_surfaces.push((sideset_name_b, &surfaces[*j]));
-                }
-
-                // Add sidesets to mesh
-                add_contact_sidesets_to_mesh(&mut mesh_with_sidesets, &contact_surfaces, &mesh)?;
+                let sideset_warnings = add_auto_contact_sidesets(
+                    &mut mesh_with_sidesets,
+                    &report,
+                    &attributes,
+                    sideset_dist_factors,
+                    sideset_paired_only,
+                    &mesh,
+                )?;
+                warnings.extend(sideset_warnings);
 
                 // Write mesh with sidesets
                 let exodus_output = output.join("mesh_with_contact_sidesets.exo");
-                write_exodus(&mesh_with_sidesets, &exodus_output)?;
+                if claim_output(policy, &exodus_output)? {
+                    let options = ExodusWriteOptions { max_name_length };
+                    write_exodus_with_options(&mesh_with_sidesets, &exodus_output, &options)?;
 
-                println!("Mesh with contact sidesets written to: {}", exodus_output.display());
+                    println!("Mesh with contact sidesets written to: {}", exodus_output.display());
+                }
                 println!();
             }
             #[cfg(not(feature = "exodus"))]
             {
+                let _ = sideset_dist_factors;
                 println!("WARNING: --export-sidesets requires exodus feature");
                 println!("Skipping sideset export.");
                 println!();
             }
         }
 
+        // Export metadata if requested (after all exports so it carries their warnings)
+        if let Some(mut meta) = metadata {
+            meta.warnings = warnings.clone();
+            meta.summary = Some(summary.clone());
+            let metadata_path = output.join("contact_metadata.json");
+            if claim_output(policy, &metadata_path)? {
+                meta.export_with(&metadata_path, formats.files)?;
+                println!("Metadata exported to: {}", metadata_path.display());
+            }
+            println!();
+        }
+
+        if save_results {
+            let results_path = output.join("contact_results.json");
+            if claim_output(policy, &results_path)? {
+                let mut file = contact_detector::io::ContactResultsFile::new(
+                    report.pairs.iter().map(|p| p.results.clone()).collect(),
+                );
+                file.mesh_file = Some(input.display().to_string());
+                file.export(&results_path)?;
+                println!("Contact results saved to: {}", results_path.display());
+            }
+            println!();
+        }
+
+        if !warnings.is_empty() {
+            println!("{} warning(s) raised during processing", warnings.len());
+            println!();
+        }
+
+        summary.print(format, glyphs);
+        println!();
+
         println!("{}", "=".repeat(60));
         println!("Results written to: {}", output.display());
         println!("{}", "=".repeat(60));
     }
 
+    // Every result is in its final place now
+    if progress_dir.exists() {
+        if let Err(e) = std::fs::remove_dir_all(&progress_dir) {
+            tracing::warn!("Could not remove {}: {}", progress_dir.display(), e);
+        }
+    }
+
+    manifest.set_resolved_config(&report.criteria)?;
+    manifest.finish(&output)?;
+
     Ok(())
 }
@@ -0,0 +1,105 @@
+//! Zero-copy views of the face arrays of a surface
+//!
+//! Points and vectors are stored as nalgebra types, which have the layout of
+//! `[T; 3]`. The views below reinterpret the stored arrays as plain
+//! coordinates without copying, so numerics code (numpy through bindings,
+//! optimization loops) can take a whole array at once instead of converting
+//! face by face. `<[[T; 3]]>::as_flattened` gives the interleaved `x, y, z`
+//! values as one `&[T]`.
+//!
+//! With the `ndarray` feature the same arrays are also available as
+//! `ArrayView2` (n × 3) and `ArrayView1` (n), still borrowing the surface.
+
+use crate::mesh::types::SurfaceMesh;
+use bytemuck::Pod;
+use nalgebra::{Point3, Scalar, Vector3};
+
+/// Coordinates of points, sharing their memory
+pub fn point_coords<T: Scalar + Pod>(points: &[Point3<T>]) -> &[[T; 3]] {
+    bytemuck::cast_slice(points)
+}
+
+/// Components of vectors, sharing their memory
+pub fn vector_coords<T: Scalar + Pod>(vectors: &[Vector3<T>]) -> &[[T; 3]] {
+    bytemuck::cast_slice(vectors)
+}
+
+impl<T: Scalar + Pod> SurfaceMesh<T> {
+    /// Face centroids as `[x, y, z]` rows
+    pub fn centroid_coords(&self) -> &[[T; 3]] {
+        point_coords(&self.face_centroids)
+    }
+
+    /// Face normals as `[x, y, z]` rows
+    pub fn normal_coords(&self) -> &[[T; 3]] {
+        vector_coords(&self.face_normals)
+    }
+
+    /// Face areas
+    pub fn area_values(&self) -> &[T] {
+        &self.face_areas
+    }
+
+    /// Node coordinates as `[x, y, z]` rows
+    pub fn node_coords(&self) -> &[[T; 3]] {
+        point_coords(&self.nodes)
+    }
+}
+
+#[cfg(feature = "ndarray")]
+impl<T: Scalar + Pod> SurfaceMesh<T> {
+    /// Face centroids as an n × 3 array
+    pub fn centroid_view(&self) -> ndarray::ArrayView2<'_, T> {
+        self.centroid_coords().into()
+    }
+
+    /// Face normals as an n × 3 array
+    pub fn normal_view(&self) -> ndarray::ArrayView2<'_, T> {
+        self.normal_coords().into()
+    }
+
+    /// Face areas as an array
+    pub fn area_view(&self) -> ndarray::ArrayView1<'_, T> {
+        self.face_areas.as_slice().into()
+    }
+
+    /// Node coordinates as an n × 3 array
+    pub fn node_view(&self) -> ndarray::ArrayView2<'_, T> {
+        self.node_coords().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::types::Point;
+
+    #[test]
+    fn test_face_array_views() {
+        let mut surface = SurfaceMesh::new("Plate".to_string());
+        surface.face_centroids = vec![Point::new(0.5, 0.5, 0.0), Point::new(1.5, 0.5, 0.0)];
+        surface.face_normals = vec![Vector3::z(), -Vector3::z()];
+        surface.face_areas = vec![1.0, 2.0];
+
+        let centroids = surface.centroid_coords();
+        assert_eq!(centroids, [[0.5, 0.5, 0.0], [1.5, 0.5, 0.0]]);
+        assert_eq!(centroids.as_ptr().cast(), surface.face_centroids.as_ptr());
+        assert_eq!(
+            surface.normal_coords().as_flattened(),
+            [0.0, 0.0, 1.0, 0.0, 0.0, -1.0]
+        );
+        assert_eq!(surface.area_values(), [1.0, 2.0]);
+
+        // Single precision surfaces share their memory just the same
+        let narrow = surface.to_precision::<f32>();
+        assert_eq!(narrow.centroid_coords()[1], [1.5f32, 0.5, 0.0]);
+
+        #[cfg(feature = "ndarray")]
+        {
+            let view = surface.centroid_view();
+            assert_eq!(view.shape(), [2, 3]);
+            assert_eq!(view[[1, 0]], 1.5);
+            assert_eq!(surface.area_view().sum(), 3.0);
+        }
+    }
+}
@@ -0,0 +1,440 @@
+//! Assembly definition files
+//!
+//! Meshes exported by some pre-processors lump a whole assembly into one
+//! element block and carry few or no node and side sets. An assembly
+//! definition is a small YAML file that adds blocks and sets when the mesh is
+//! loaded, selected by global element ID ranges and/or geometric regions, so
+//! parts can be told apart without re-meshing:
+//!
+//! ```yaml
+//! blocks:
+//!   - name: Bolt
+//!     region: { type: cylinder, start: [0, 0, -5], end: [0, 0, 25], radius: 3.1 }
+//!   - name: Plate
+//!     elements: [[1, 1200], 1350]
+//! node_sets:
+//!   - name: fixed
+//!     region: { type: box, min: [-50, -50, -0.01], max: [50, 50, 0.01] }
+//! side_sets:
+//!   - name: plate_top
+//!     elements: [[1, 1200]]
+//!     region: { type: box, min: [-50, -50, 9.99], max: [50, 50, 10.01] }
+//! ```
+//!
+//! Regions use the same syntax as regions of interest ([`RoiSpec`]).
+
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::roi::{RegionOfInterest, RoiSpec};
+use crate::mesh::types::{HexElement, Mesh, Point, QuadFace};
+use crate::warnings::{WarningKind, Warnings};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+/// Inclusive range of global element IDs
+///
+/// Written either as a single ID or as a `[first, last]` pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "IdRangeRepr", into = "IdRangeRepr")]
+pub struct IdRange {
+    pub first: i64,
+    pub last: i64,
+}
+
+impl IdRange {
+    /// Whether `id` lies in the range
+    pub fn contains(&self, id: i64) -> bool {
+        (self.first..=self.last).contains(&id)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum IdRangeRepr {
+    Single(i64),
+    Span([i64; 2]),
+}
+
+impl TryFrom<IdRangeRepr> for IdRange {
+    type Error = String;
+
+    fn try_from(repr: IdRangeRepr) -> std::result::Result<Self, String> {
+        let (first, last) = match repr {
+            IdRangeRepr::Single(id) => (id, id),
+            IdRangeRepr::Span([first, last]) => (first, last),
+        };
+        if first > last {
+            return Err(format!("invalid element ID range [{}, {}]", first, last));
+        }
+        Ok(Self { first, last })
+    }
+}
+
+impl From<IdRange> for IdRangeRepr {
+    fn from(range: IdRange) -> Self {
+        if range.first == range.last {
+            IdRangeRepr::Single(range.first)
+        } else {
+            IdRangeRepr::Span([range.first, range.last])
+        }
+    }
+}
+
+/// One block or set of an assembly definition
+///
+/// Elements are selected by ID (any of `elements`, if given) and by position
+/// (element centroid inside `region`, if given); at least one is required.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetDefinition {
+    /// Name of the block or set
+    pub name: String,
+
+    /// Global element ID ranges
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub elements: Vec<IdRange>,
+
+    /// Geometric region
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<RoiSpec>,
+}
+
+/// Blocks and sets to merge into a mesh at load time
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssemblyDefinition {
+    /// Element blocks; selected elements are moved out of their current block
+    #[serde(default)]
+    pub blocks: Vec<SetDefinition>,
+
+    /// Node sets: nodes of the selected elements, or all nodes, inside `region`
+    #[serde(default)]
+    pub node_sets: Vec<SetDefinition>,
+
+    /// Side sets: boundary faces of the selected elements whose centroid lies
+    /// inside `region`
+    #[serde(default)]
+    pub side_sets: Vec<SetDefinition>,
+}
+
+impl AssemblyDefinition {
+    /// Read and validate a definition from a YAML file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let definition: Self = serde_yaml::from_str(&text).map_err(|e| {
+            ContactDetectorError::ConfigError(format!(
+                "Failed to parse assembly definition '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    /// Check that every entry is named and selects something
+    pub fn validate(&self) -> Result<()> {
+        let entries = self
+            .blocks
+            .iter()
+            .chain(&self.node_sets)
+            .chain(&self.side_sets);
+        for entry in entries {
+            if entry.name.trim().is_empty() {
+                return Err(ContactDetectorError::ConfigError(
+                    "Assembly definition entry without a name".to_string(),
+                ));
+            }
+            if entry.elements.is_empty() && entry.region.is_none() {
+                return Err(ContactDetectorError::ConfigError(format!(
+                    "Assembly definition entry '{}' needs 'elements' or 'region'",
+                    entry.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Merge the blocks and sets into `mesh`
+    ///
+    /// Blocks are applied in order, so an element selected by several blocks
+    /// ends up in the last one; blocks left without elements are removed.
+    /// Sets that already exist are extended. Entries that select nothing are
+    /// skipped with a warning.
+    pub fn apply(&self, mesh: &mut Mesh) -> Result<Warnings> {
+        let mut warnings = Warnings::new();
+        let centroids: Vec<Point> = mesh
+            .elements
+            .iter()
+            .map(|element| centroid(mesh, &element.node_ids))
+            .collect();
+
+        for entry in &self.blocks {
+            let selected = select_elements(mesh, &centroids, entry, true)?;
+            if selected.is_empty() {
+                warn_empty(&mut warnings, "Block", &entry.name);
+                continue;
+            }
+
+            for elements in mesh.element_blocks.values_mut() {
+                elements.retain(|elem_idx| !selected.contains(elem_idx));
+            }
+            mesh.element_blocks
+                .retain(|_, elements| !elements.is_empty());
+            merge_set(
+                mesh.element_blocks.entry(entry.name.clone()).or_default(),
+                selected,
+            );
+        }
+
+        for entry in &self.node_sets {
+            let selected = select_nodes(mesh, &centroids, entry)?;
+            if selected.is_empty() {
+                warn_empty(&mut warnings, "Node set", &entry.name);
+                continue;
+            }
+            merge_set(
+                mesh.node_sets.entry(entry.name.clone()).or_default(),
+                selected,
+            );
+        }
+
+        let face_counts = if self.side_sets.is_empty() {
+            HashMap::new()
+        } else {
+            face_counts(mesh)
+        };
+        for entry in &self.side_sets {
+            let selected = select_sides(mesh, &centroids, &face_counts, entry)?;
+            if selected.is_empty() {
+                warn_empty(&mut warnings, "Side set", &entry.name);
+                continue;
+            }
+            merge_set(
+                mesh.side_sets.entry(entry.name.clone()).or_default(),
+                selected,
+            );
+        }
+
+        mesh.provenance.history.push(format!(
+            "assembly definition: {} blocks, {} node sets, {} side sets",
+            self.blocks.len(),
+            self.node_sets.len(),
+            self.side_sets.len()
+        ));
+        Ok(warnings)
+    }
+}
+
+fn warn_empty(warnings: &mut Warnings, what: &str, name: &str) {
+    warnings.push(
+        WarningKind::EmptyAssemblySet,
+        format!(
+            "{} '{}' of the assembly definition selects nothing; skipped",
+            what, name
+        ),
+    );
+}
+
+/// Add `selected` to an existing set, keeping it sorted and free of duplicates
+fn merge_set<T: Ord + Copy>(set: &mut Vec<T>, selected: BTreeSet<T>) {
+    let merged: BTreeSet<T> = set.iter().copied().chain(selected).collect();
+    *set = merged.into_iter().collect();
+}
+
+fn centroid(mesh: &Mesh, node_ids: &[usize]) -> Point {
+    let sum = node_ids
+        .iter()
+        .fold(Point::origin().coords, |sum, &n| sum + mesh.nodes[n].coords);
+    Point::from(sum / node_ids.len() as f64)
+}
+
+fn load_region(entry: &SetDefinition) -> Result<Option<RegionOfInterest>> {
+    entry.region.as_ref().map(RoiSpec::load).transpose()
+}
+
+/// Elements matching the ID ranges and, if `use_region`, the region
+fn select_elements(
+    mesh: &Mesh,
+    centroids: &[Point],
+    entry: &SetDefinition,
+    use_region: bool,
+) -> Result<BTreeSet<usize>> {
+    let region = if use_region {
+        load_region(entry)?
+    } else {
+        None
+    };
+    Ok((0..mesh.num_elements())
+        .filter(|&elem_idx| {
+            let id = mesh.global_elem_id(elem_idx) as i64;
+            entry.elements.is_empty() || entry.elements.iter().any(|range| range.contains(id))
+        })
+        .filter(|&elem_idx| {
+            region
+                .as_ref()
+                .is_none_or(|r| r.contains(&centroids[elem_idx]))
+        })
+        .collect())
+}
+
+fn select_nodes(
+    mesh: &Mesh,
+    centroids: &[Point],
+    entry: &SetDefinition,
+) -> Result<BTreeSet<usize>> {
+    let region = load_region(entry)?;
+    let inside = |node: &usize| {
+        region
+            .as_ref()
+            .is_none_or(|r| r.contains(&mesh.nodes[*node]))
+    };
+
+    if entry.elements.is_empty() {
+        return Ok((0..mesh.num_nodes()).filter(inside).collect());
+    }
+    Ok(select_elements(mesh, centroids, entry, false)?
+        .into_iter()
+        .flat_map(|elem_idx| mesh.elements[elem_idx].node_ids)
+        .filter(inside)
+        .collect())
+}
+
+/// Number of elements sharing each face
+fn face_counts(mesh: &Mesh) -> HashMap<QuadFace, usize> {
+    let mut counts = HashMap::with_capacity(mesh.num_elements() * 6);
+    for element in &mesh.elements {
+        for face in element.faces() {
+            *counts.entry(face.canonical()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+fn select_sides(
+    mesh: &Mesh,
+    centroids: &[Point],
+    face_counts: &HashMap<QuadFace, usize>,
+    entry: &SetDefinition,
+) -> Result<BTreeSet<(usize, u8)>> {
+    let region = load_region(entry)?;
+    let mut sides = BTreeSet::new();
+    for elem_idx in select_elements(mesh, centroids, entry, false)? {
+        for (face_idx, face) in mesh.elements[elem_idx].faces().iter().enumerate() {
+            if face_counts.get(&face.canonical()) != Some(&1) {
+                continue;
+            }
+            let inside = region
+                .as_ref()
+                .is_none_or(|r| r.contains(&centroid(mesh, face.corners())));
+            if let (true, Some(side)) = (inside, HexElement::side_of_face_index(face_idx)) {
+                sides.insert((elem_idx, side));
+            }
+        }
+    }
+    Ok(sides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Row of `n` unit hexes along x, all in block "All", element IDs 101..
+    fn make_row(n: usize) -> Mesh {
+        let mut mesh = Mesh::new();
+        for i in 0..=n {
+            for (y, z) in [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)] {
+                mesh.nodes.push(Point::new(i as f64, y, z));
+            }
+        }
+        for i in 0..n {
+            let a = 4 * i;
+            let b = a + 4;
+            mesh.elements.push(HexElement::new([
+                a,
+                b,
+                b + 1,
+                a + 1,
+                a + 3,
+                b + 3,
+                b + 2,
+                a + 2,
+            ]));
+            mesh.elem_num_map.push(101 + i as i32);
+        }
+        mesh.element_blocks
+            .insert("All".to_string(), (0..n).collect());
+        mesh
+    }
+
+    fn parse(yaml: &str) -> AssemblyDefinition {
+        let definition: AssemblyDefinition = serde_yaml::from_str(yaml).unwrap();
+        definition.validate().unwrap();
+        definition
+    }
+
+    #[test]
+    fn test_blocks_by_id_and_region() {
+        let mut mesh = make_row(4);
+        let definition = parse(
+            r#"
+blocks:
+  - name: Left
+    elements: [[101, 102]]
+  - name: Right
+    region: { type: cylinder, start: [2, 0.5, 0.5], end: [4, 0.5, 0.5], radius: 0.5 }
+"#,
+        );
+
+        let warnings = definition.apply(&mut mesh).unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!(mesh.element_blocks["Left"], vec![0, 1]);
+        assert_eq!(mesh.element_blocks["Right"], vec![2, 3]);
+        assert!(!mesh.element_blocks.contains_key("All"));
+        assert_eq!(mesh.provenance.history.len(), 1);
+    }
+
+    #[test]
+    fn test_node_and_side_sets() {
+        let mut mesh = make_row(2);
+        let definition = parse(
+            r#"
+node_sets:
+  - name: end
+    region: { type: box, min: [-0.1, -1, -1], max: [0.1, 2, 2] }
+side_sets:
+  - name: top_right
+    elements: [102]
+    region: { type: box, min: [0, 0, 0.9], max: [3, 1, 1.1] }
+  - name: nothing
+    region: { type: sphere, center: [10, 10, 10], radius: 1 }
+"#,
+        );
+
+        let warnings = definition.apply(&mut mesh).unwrap();
+        assert_eq!(mesh.node_sets["end"], vec![0, 1, 2, 3]);
+
+        // Only the top face of the second element
+        let sides = &mesh.side_sets["top_right"];
+        assert_eq!(sides.len(), 1);
+        assert_eq!(sides[0].0, 1);
+        let face = mesh.elements[1].side_face(sides[0].1).unwrap();
+        assert!(face.corners().iter().all(|&n| mesh.nodes[n].z == 1.0));
+
+        assert_eq!(warnings.len(), 1);
+        assert!(!mesh.side_sets.contains_key("nothing"));
+    }
+
+    #[test]
+    fn test_invalid_definitions() {
+        let missing: AssemblyDefinition =
+            serde_yaml::from_str("blocks:\n  - name: Empty\n").unwrap();
+        assert!(missing.validate().is_err());
+
+        assert!(serde_yaml::from_str::<AssemblyDefinition>(
+            "blocks:\n  - name: Bad\n    elements: [[5, 1]]\n"
+        )
+        .is_err());
+    }
+}
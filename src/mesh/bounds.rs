@@ -0,0 +1,271 @@
+//! Bounding volumes of surfaces and parts
+//!
+//! An oriented bounding box (from the principal directions of the node cloud)
+//! and a bounding sphere give a compact summary of where a part sits and how
+//! it is oriented, which makes mis-positioned parts easy to spot. Both are
+//! also cheap conservative tests for whether two surfaces can be in contact
+//! at all, so surface pairs that are far apart can skip detection.
+
+use crate::mesh::types::{Mesh, Point, SurfaceMesh, Vec3};
+use nalgebra::{Matrix3, SymmetricEigen};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Relative tolerance below which a cross product of box axes is ignored in
+/// the separating axis test
+const PARALLEL_AXIS_TOLERANCE: f64 = 1e-9;
+
+/// Smallest sphere found (Ritter's approximation) enclosing a set of points
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoundingSphere {
+    pub center: [f64; 3],
+    pub radius: f64,
+}
+
+impl BoundingSphere {
+    /// Enclosing sphere of `points`, or `None` if there are none
+    pub fn from_points(points: &[Point]) -> Option<Self> {
+        let first = points.first()?;
+        let farthest_from = |p: &Point| {
+            *points
+                .iter()
+                .max_by(|a, b| (*a - p).norm_squared().total_cmp(&(*b - p).norm_squared()))
+                .unwrap_or(p)
+        };
+
+        let y = farthest_from(first);
+        let z = farthest_from(&y);
+        let mut center = nalgebra::center(&y, &z);
+        let mut radius = (z - y).norm() / 2.0;
+
+        for p in points {
+            let d = (p - center).norm();
+            if d > radius {
+                // Grow just enough to include p
+                let new_radius = (radius + d) / 2.0;
+                center += (p - center) * ((new_radius - radius) / d);
+                radius = new_radius;
+            }
+        }
+
+        Some(Self {
+            center: center.into(),
+            radius,
+        })
+    }
+
+    /// Whether the spheres come within `margin` of each other
+    pub fn within(&self, other: &BoundingSphere, margin: f64) -> bool {
+        let distance = (Point::from(self.center) - Point::from(other.center)).norm();
+        distance <= self.radius + other.radius + margin
+    }
+}
+
+/// Box aligned with the principal directions of a set of points
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OrientedBoundingBox {
+    pub center: [f64; 3],
+
+    /// Principal directions (unit vectors, right-handed), longest extent first
+    pub axes: [[f64; 3]; 3],
+
+    /// Edge lengths along each axis
+    pub extents: [f64; 3],
+}
+
+impl OrientedBoundingBox {
+    /// Bounding box of `points` along their principal directions, or `None`
+    /// if there are none
+    pub fn from_points(points: &[Point]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+
+        let n = points.len() as f64;
+        let mean = points.iter().fold(Vec3::zeros(), |sum, p| sum + p.coords) / n;
+        let covariance = points.iter().fold(Matrix3::zeros(), |sum, p| {
+            let d = p.coords - mean;
+            sum + d * d.transpose()
+        }) / n;
+        let eigen = SymmetricEigen::new(covariance);
+
+        let mut spans: Vec<(Vec3, f64, f64)> = (0..3)
+            .map(|k| {
+                let axis: Vec3 = eigen.eigenvectors.column(k).normalize();
+                let (lo, hi) = points.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| {
+                    let t = p.coords.dot(&axis);
+                    (lo.min(t), hi.max(t))
+                });
+                (axis, lo, hi)
+            })
+            .collect();
+        spans.sort_by(|a, b| (b.2 - b.1).total_cmp(&(a.2 - a.1)));
+
+        // Keep the frame right-handed
+        let third = spans[0].0.cross(&spans[1].0);
+        if third.dot(&spans[2].0) < 0.0 {
+            let (axis, lo, hi) = spans[2];
+            spans[2] = (-axis, -hi, -lo);
+        }
+
+        let center = spans.iter().fold(Vec3::zeros(), |sum, (axis, lo, hi)| {
+            sum + axis * ((lo + hi) / 2.0)
+        });
+
+        Some(Self {
+            center: center.into(),
+            axes: [spans[0].0.into(), spans[1].0.into(), spans[2].0.into()],
+            extents: [
+                spans[0].2 - spans[0].1,
+                spans[1].2 - spans[1].1,
+                spans[2].2 - spans[2].1,
+            ],
+        })
+    }
+
+    /// Whether the boxes come within `margin` of each other
+    ///
+    /// Separating axis test on the boxes, one of them grown by `margin` in
+    /// every direction. Conservative: may report nearby boxes that are in
+    /// fact slightly further apart, never misses boxes that are closer.
+    pub fn within(&self, other: &OrientedBoundingBox, margin: f64) -> bool {
+        let axes_a = self.axes.map(Vec3::from);
+        let axes_b = other.axes.map(Vec3::from);
+        let half_a = self.extents.map(|e| e / 2.0 + margin);
+        let half_b = other.extents.map(|e| e / 2.0);
+        let offset = Vec3::from(other.center) - Vec3::from(self.center);
+
+        let radius = |axes: &[Vec3; 3], half: &[f64; 3], l: &Vec3| -> f64 {
+            (0..3).map(|k| half[k] * axes[k].dot(l).abs()).sum()
+        };
+        let separated = |l: Vec3| {
+            offset.dot(&l).abs() > radius(&axes_a, &half_a, &l) + radius(&axes_b, &half_b, &l)
+        };
+
+        let face_axes = axes_a.iter().chain(&axes_b).copied();
+        let edge_axes = axes_a
+            .iter()
+            .flat_map(|a| axes_b.iter().map(move |b| a.cross(b)))
+            .filter(|l| l.norm() > PARALLEL_AXIS_TOLERANCE)
+            .map(|l| l.normalize());
+
+        !face_axes.chain(edge_axes).any(separated)
+    }
+}
+
+/// Oriented box and sphere bounding a surface or part
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SurfaceBounds {
+    pub obb: OrientedBoundingBox,
+    pub sphere: BoundingSphere,
+}
+
+impl SurfaceBounds {
+    /// Bounds of a set of points, or `None` if there are none
+    pub fn from_points(points: &[Point]) -> Option<Self> {
+        Some(Self {
+            obb: OrientedBoundingBox::from_points(points)?,
+            sphere: BoundingSphere::from_points(points)?,
+        })
+    }
+
+    /// Bounds of the nodes used by a surface's faces, or `None` if it has no
+    /// faces (or no node data)
+    pub fn of_surface(surface: &SurfaceMesh) -> Option<Self> {
+        let nodes: BTreeSet<usize> = surface
+            .faces
+            .iter()
+            .flat_map(|face| face.corners().iter().copied())
+            .collect();
+        let points: Vec<Point> = nodes
+            .into_iter()
+            .filter_map(|n| surface.nodes.get(n).copied())
+            .collect();
+        Self::from_points(&points)
+    }
+
+    /// Bounds of the nodes of some elements of a mesh (e.g. a block), or
+    /// `None` if there are none
+    pub fn of_elements(mesh: &Mesh, elements: &[usize]) -> Option<Self> {
+        let nodes: BTreeSet<usize> = elements
+            .iter()
+            .flat_map(|&elem_idx| mesh.elements[elem_idx].node_ids)
+            .collect();
+        let points: Vec<Point> = nodes.into_iter().map(|n| mesh.nodes[n]).collect();
+        Self::from_points(&points)
+    }
+
+    /// Whether two bounded surfaces can come within `margin` of each other
+    ///
+    /// Used as a broad phase: surfaces for which this is false cannot have
+    /// faces within `margin`, so detection between them can be skipped.
+    pub fn within(&self, other: &SurfaceBounds, margin: f64) -> bool {
+        self.sphere.within(&other.sphere, margin) && self.obb.within(&other.obb, margin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Corners of a box of size `size` rotated by `angle` about z, moved to `at`
+    fn box_corners(size: [f64; 3], angle: f64, at: [f64; 3]) -> Vec<Point> {
+        let rotation = nalgebra::Rotation3::from_axis_angle(&Vec3::z_axis(), angle);
+        let mut points = Vec::new();
+        for i in 0..8 {
+            let local = Vec3::new(
+                if i & 1 == 0 { 0.0 } else { size[0] },
+                if i & 2 == 0 { 0.0 } else { size[1] },
+                if i & 4 == 0 { 0.0 } else { size[2] },
+            );
+            points.push(Point::from(rotation * local + Vec3::from(at)));
+        }
+        points
+    }
+
+    #[test]
+    fn test_obb_of_rotated_box() {
+        let angle = 30f64.to_radians();
+        let points = box_corners([4.0, 2.0, 1.0], angle, [1.0, 2.0, 3.0]);
+        let obb = OrientedBoundingBox::from_points(&points).unwrap();
+
+        for (extent, expected) in obb.extents.iter().zip([4.0, 2.0, 1.0]) {
+            assert!((extent - expected).abs() < 1e-9);
+        }
+        let long_axis = Vec3::from(obb.axes[0]);
+        assert!(
+            (long_axis
+                .dot(&Vec3::new(angle.cos(), angle.sin(), 0.0))
+                .abs()
+                - 1.0)
+                .abs()
+                < 1e-9
+        );
+
+        let centroid = points.iter().fold(Vec3::zeros(), |s, p| s + p.coords) / 8.0;
+        assert!((Vec3::from(obb.center) - centroid).norm() < 1e-9);
+
+        let sphere = BoundingSphere::from_points(&points).unwrap();
+        assert!(points
+            .iter()
+            .all(|p| (p - Point::from(sphere.center)).norm() <= sphere.radius + 1e-9));
+        // Ritter's sphere is close to the optimal half diagonal
+        assert!(sphere.radius < 1.1 * 21f64.sqrt() / 2.0);
+    }
+
+    #[test]
+    fn test_within() {
+        let a = SurfaceBounds::from_points(&box_corners([1.0, 1.0, 1.0], 0.0, [0.0; 3])).unwrap();
+        // Diagonal box whose sphere overlaps a's but which is 0.4 away from it
+        let b = SurfaceBounds::from_points(&box_corners(
+            [2.0, 0.2, 1.0],
+            -45f64.to_radians(),
+            [1.0 + 0.2 * 2f64.sqrt(), 1.0 + 0.2 * 2f64.sqrt(), 0.0],
+        ))
+        .unwrap();
+
+        assert!(a.sphere.within(&b.sphere, 0.0));
+        assert!(!a.within(&b, 0.1));
+        assert!(a.within(&b, 0.5));
+    }
+}
@@ -0,0 +1,165 @@
+//! Connected bodies within element blocks
+//!
+//! Many meshes put a whole assembly into a single element block, so parts
+//! that touch each other end up in the same "part" and their contact is never
+//! looked for. Elements connected through shared faces form one body;
+//! splitting each block into its bodies lets skinning and contact detection
+//! treat every body as a separate part.
+
+use crate::mesh::types::Mesh;
+use crate::mesh::union_find::UnionFind;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Group elements into bodies connected through shared faces
+///
+/// Returns the bodies ordered by their smallest element index, each with its
+/// element indices in ascending order.
+pub fn connected_components(mesh: &Mesh, elements: &[usize]) -> Vec<Vec<usize>> {
+    // Threshold for parallelization (below this, overhead isn't worth it)
+    const PARALLEL_THRESHOLD: usize = 10_000;
+
+    let face_keys = |(local, &elem_idx): (usize, &usize)| {
+        mesh.elements[elem_idx]
+            .faces()
+            .map(|face| (face.canonical().node_ids, local))
+    };
+
+    #[cfg(feature = "parallel")]
+    let keyed: Vec<([usize; 4], usize)> = if elements.len() >= PARALLEL_THRESHOLD {
+        elements
+            .par_iter()
+            .enumerate()
+            .flat_map_iter(face_keys)
+            .collect()
+    } else {
+        elements.iter().enumerate().flat_map(face_keys).collect()
+    };
+
+    #[cfg(not(feature = "parallel"))]
+    let keyed: Vec<([usize; 4], usize)> = elements.iter().enumerate().flat_map(face_keys).collect();
+
+    let mut sets = UnionFind::new(elements.len());
+    sets.union_by_key(keyed);
+
+    let mut bodies: Vec<Vec<usize>> = sets
+        .components()
+        .into_iter()
+        .map(|locals| {
+            let mut body: Vec<usize> = locals.into_iter().map(|l| elements[l]).collect();
+            body.sort_unstable();
+            body
+        })
+        .collect();
+    bodies.sort_unstable_by_key(|body| body[0]);
+    bodies
+}
+
+/// Split every block made of several disconnected bodies into one block per
+/// body, named "<block>_body_<n>" (n from 1)
+///
+/// Blocks with a single body keep their name. Returns the names of the blocks
+/// that were split.
+pub fn split_connected_components(mesh: &mut Mesh) -> Vec<String> {
+    let mut block_names: Vec<String> = mesh.element_blocks.keys().cloned().collect();
+    block_names.sort();
+
+    let mut split = Vec::new();
+    for block_name in block_names {
+        let bodies = connected_components(mesh, &mesh.element_blocks[&block_name]);
+        if bodies.len() < 2 {
+            continue;
+        }
+
+        tracing::info!(
+            "Splitting block '{}' into {} connected bodies",
+            block_name,
+            bodies.len()
+        );
+        mesh.element_blocks.remove(&block_name);
+        for (i, body) in bodies.into_iter().enumerate() {
+            mesh.element_blocks
+                .insert(format!("{}_body_{}", block_name, i + 1), body);
+        }
+        split.push(block_name);
+    }
+
+    if !split.is_empty() {
+        mesh.provenance
+            .history
+            .push(format!("split into connected bodies: {}", split.join(", ")));
+    }
+    split
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::types::{HexElement, Point};
+
+    /// Unit hex at (x, 0, 0) with its own nodes
+    fn add_hex(mesh: &mut Mesh, x: f64) -> usize {
+        let base = mesh.nodes.len();
+        for (dx, dy, dz) in [
+            (0., 0., 0.),
+            (1., 0., 0.),
+            (1., 1., 0.),
+            (0., 1., 0.),
+            (0., 0., 1.),
+            (1., 0., 1.),
+            (1., 1., 1.),
+            (0., 1., 1.),
+        ] {
+            mesh.nodes.push(Point::new(x + dx, dy, dz));
+        }
+        mesh.elements
+            .push(HexElement::new(std::array::from_fn(|i| base + i)));
+        mesh.elements.len() - 1
+    }
+
+    /// Unit hex at (x, 0, 0) sharing its x- face with the previous hex
+    fn add_connected_hex(mesh: &mut Mesh, x: f64) -> usize {
+        let prev = *mesh.elements.last().unwrap();
+        let base = mesh.nodes.len();
+        for (dy, dz) in [(0., 0.), (1., 0.), (0., 1.), (1., 1.)] {
+            mesh.nodes.push(Point::new(x + 1.0, dy, dz));
+        }
+        let ids = prev.node_ids;
+        mesh.elements.push(HexElement::new([
+            ids[1],
+            base,
+            base + 1,
+            ids[2],
+            ids[5],
+            base + 2,
+            base + 3,
+            ids[6],
+        ]));
+        mesh.elements.len() - 1
+    }
+
+    #[test]
+    fn test_split_touching_bodies() {
+        // Two bodies of two hexes each; the second touches the first at
+        // x = 2 through coincident but distinct nodes
+        let mut mesh = Mesh::new();
+        let a0 = add_hex(&mut mesh, 0.0);
+        let a1 = add_connected_hex(&mut mesh, 1.0);
+        let b0 = add_hex(&mut mesh, 2.0);
+        let b1 = add_connected_hex(&mut mesh, 3.0);
+        mesh.element_blocks
+            .insert("Assembly".to_string(), vec![b1, a0, b0, a1]);
+        mesh.element_blocks.insert("Other".to_string(), vec![]);
+
+        let bodies = connected_components(&mesh, &mesh.element_blocks["Assembly"]);
+        assert_eq!(bodies, vec![vec![a0, a1], vec![b0, b1]]);
+
+        let split = split_connected_components(&mut mesh);
+        assert_eq!(split, vec!["Assembly".to_string()]);
+        assert_eq!(mesh.element_blocks["Assembly_body_1"], vec![a0, a1]);
+        assert_eq!(mesh.element_blocks["Assembly_body_2"], vec![b0, b1]);
+        assert!(!mesh.element_blocks.contains_key("Assembly"));
+        assert!(mesh.element_blocks.contains_key("Other"));
+    }
+}
@@ -0,0 +1,360 @@
+//! Level-of-detail surfaces for preview runs
+//!
+//! A surface is decimated by vertex clustering: the nodes of its faces are
+//! grouped into cubic cells of a uniform grid and each group is replaced by
+//! its mean, so neighbouring quads merge into larger ones. Faces whose
+//! corners collapse to three clusters become triangles; faces collapsing
+//! further, duplicates of an already kept face, and faces folded against
+//! their original orientation are dropped. Nodes are also grouped by the
+//! dominant axis of their normal, so the two sides of a wall thinner than a
+//! cell are not merged into one, and nodes on the open border of the surface
+//! are only grouped with each other so its outline is kept (straight borders
+//! exactly, corners cut by up to a cell). The mean of coplanar nodes stays in
+//! their plane, so flat contact faces keep their gap; curved surfaces sag by
+//! up to the chord height of a cell.
+
+use crate::mesh::geometry::{compute_face_area, compute_face_centroid, compute_face_normal};
+use crate::mesh::types::{Point, QuadFace, SurfaceMesh, Vec3};
+use std::collections::{HashMap, HashSet};
+
+/// Attempts at growing the cell size before settling for the last result
+const MAX_PASSES: usize = 8;
+
+/// Decimated surface together with the faces it was merged from
+#[derive(Debug, Clone)]
+pub struct Decimation {
+    /// Decimated surface, with its own node list of cluster points
+    pub surface: SurfaceMesh,
+
+    /// Faces of the original surface merged into each decimated face (faces
+    /// collapsed to an edge or a point belong to none)
+    pub source_faces: Vec<Vec<usize>>,
+}
+
+/// Decimated copy of `surface` with at most about `target_faces` faces
+///
+/// See [`SurfaceMesh::decimate`].
+pub fn decimate_surface(surface: &SurfaceMesh, target_faces: usize) -> SurfaceMesh {
+    let groups = vec![0; surface.num_faces()];
+    let keep = vec![false; surface.num_faces()];
+    decimate_surface_grouped(surface, target_faces, &groups, &keep).surface
+}
+
+/// Decimate `surface` without merging faces across groups or touching the
+/// faces to keep
+///
+/// Faces only merge with faces of the same `face_groups` value. Nodes shared
+/// by faces of different groups are, like those on the surface border, only
+/// grouped with each other, so group boundaries are kept without cracks; all
+/// nodes of faces flagged in `keep_faces` stay where they are, so those faces
+/// are reproduced exactly. A surface already within the
+/// target is returned unchanged.
+pub fn decimate_surface_grouped(
+    surface: &SurfaceMesh,
+    target_faces: usize,
+    face_groups: &[u32],
+    keep_faces: &[bool],
+) -> Decimation {
+    let target_faces = target_faces.max(1);
+    let unchanged = || Decimation {
+        surface: surface.clone(),
+        source_faces: (0..surface.num_faces()).map(|f| vec![f]).collect(),
+    };
+    if surface.num_faces() <= target_faces {
+        return unchanged();
+    }
+
+    // Start from cells the size of the target faces and grow them until the
+    // target is met
+    let mut cell_size = (surface.total_area() / target_faces as f64).sqrt();
+    if cell_size.is_nan() || cell_size <= 0.0 {
+        return unchanged();
+    }
+    let nodes = cluster_nodes(surface, face_groups, keep_faces);
+    let mut decimated = cluster_surface(surface, &nodes, cell_size);
+    for _ in 1..MAX_PASSES {
+        if decimated.surface.num_faces() <= target_faces {
+            break;
+        }
+        let ratio = decimated.surface.num_faces() as f64 / target_faces as f64;
+        cell_size *= ratio.sqrt().max(1.1);
+        decimated = cluster_surface(surface, &nodes, cell_size);
+    }
+    decimated
+}
+
+/// How a node of the surface is clustered
+struct ClusterNode {
+    /// Area-weighted normal of the faces using the node
+    normal: Vec3,
+
+    /// Nodes the node may be grouped with
+    class: NodeClass,
+}
+
+/// Which nodes a node may be grouped with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum NodeClass {
+    /// Interior nodes of faces of the same group
+    Interior(u32),
+
+    /// Other nodes on the surface border or between groups
+    Boundary,
+
+    /// None: the node stays where it is
+    Pinned,
+}
+
+/// Nodes on edges used by a single face
+fn border_nodes(surface: &SurfaceMesh) -> HashSet<usize> {
+    let mut edge_uses: HashMap<(usize, usize), usize> = HashMap::new();
+    for face in &surface.faces {
+        let corners = face.corners();
+        for k in 0..corners.len() {
+            let (a, b) = (corners[k], corners[(k + 1) % corners.len()]);
+            *edge_uses.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+        }
+    }
+    edge_uses
+        .into_iter()
+        .filter(|&(_, uses)| uses == 1)
+        .flat_map(|((a, b), _)| [a, b])
+        .collect()
+}
+
+/// Clustering of each node used by the surface's faces
+fn cluster_nodes(
+    surface: &SurfaceMesh,
+    face_groups: &[u32],
+    keep_faces: &[bool],
+) -> HashMap<usize, ClusterNode> {
+    let mut nodes: HashMap<usize, ClusterNode> = HashMap::new();
+    for (face_idx, face) in surface.faces.iter().enumerate() {
+        let weighted = surface.face_normals[face_idx] * surface.face_areas[face_idx];
+        let class = if keep_faces[face_idx] {
+            NodeClass::Pinned
+        } else {
+            NodeClass::Interior(face_groups[face_idx])
+        };
+        for &node in face.corners() {
+            let entry = nodes.entry(node).or_insert(ClusterNode {
+                normal: Vec3::zeros(),
+                class,
+            });
+            entry.normal += weighted;
+            entry.class = match (entry.class, class) {
+                (NodeClass::Pinned, _) | (_, NodeClass::Pinned) => NodeClass::Pinned,
+                (a, b) if a == b => a,
+                _ => NodeClass::Boundary,
+            };
+        }
+    }
+    for node in border_nodes(surface) {
+        if let Some(entry) = nodes.get_mut(&node) {
+            if entry.class != NodeClass::Pinned {
+                entry.class = NodeClass::Boundary;
+            }
+        }
+    }
+    nodes
+}
+
+/// Dominant axis and direction of a normal (0..6)
+fn normal_bucket(normal: &Vec3) -> u8 {
+    let axis = normal.iamax();
+    axis as u8 * 2 + u8::from(normal[axis] < 0.0)
+}
+
+/// Cluster a node belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ClusterKey {
+    /// Grid cell, normal bucket and class of the node
+    Cell([i64; 3], u8, NodeClass),
+
+    /// A pinned node, alone in its cluster
+    Pinned(usize),
+}
+
+/// One pass of vertex clustering at the given cell size
+fn cluster_surface(
+    surface: &SurfaceMesh,
+    nodes: &HashMap<usize, ClusterNode>,
+    cell_size: f64,
+) -> Decimation {
+    let mut decimated = SurfaceMesh::new(surface.part_name.clone());
+
+    // Cluster of each node, and the sum of the nodes in each cluster
+    let mut cluster_of: HashMap<usize, usize> = HashMap::new();
+    let mut cluster_ids: HashMap<ClusterKey, usize> = HashMap::new();
+    let mut sums: Vec<(Vec3, usize)> = Vec::new();
+    let mut node_ids: Vec<usize> = nodes.keys().copied().collect();
+    node_ids.sort_unstable();
+    for node in node_ids {
+        let p = &surface.nodes[node];
+        let key = match nodes[&node].class {
+            NodeClass::Pinned => ClusterKey::Pinned(node),
+            class => ClusterKey::Cell(
+                [
+                    (p.x / cell_size).floor() as i64,
+                    (p.y / cell_size).floor() as i64,
+                    (p.z / cell_size).floor() as i64,
+                ],
+                normal_bucket(&nodes[&node].normal),
+                class,
+            ),
+        };
+        let cluster = *cluster_ids.entry(key).or_insert_with(|| {
+            sums.push((Vec3::zeros(), 0));
+            sums.len() - 1
+        });
+        sums[cluster].0 += p.coords;
+        sums[cluster].1 += 1;
+        cluster_of.insert(node, cluster);
+    }
+    decimated.nodes = sums
+        .iter()
+        .map(|(sum, count)| Point::from(sum / *count as f64))
+        .collect();
+
+    let mut source_faces: Vec<Vec<usize>> = Vec::new();
+    let mut kept: HashMap<QuadFace, usize> = HashMap::new();
+    let mut dropped: HashSet<QuadFace> = HashSet::new();
+    for (face_idx, face) in surface.faces.iter().enumerate() {
+        // Corners in order with consecutive repeats collapsed
+        let mut corners: Vec<usize> = Vec::with_capacity(4);
+        for node in face.corners() {
+            let cluster = cluster_of[node];
+            if corners.last() != Some(&cluster) {
+                corners.push(cluster);
+            }
+        }
+        if corners.len() > 1 && corners.first() == corners.last() {
+            corners.pop();
+        }
+
+        let merged = match corners[..] {
+            [a, b, c, d] if a != c && b != d => QuadFace::new([a, b, c, d]),
+            [a, b, c] => QuadFace::triangle([a, b, c]),
+            _ => continue,
+        };
+        let key = merged.canonical();
+        if let Some(&existing) = kept.get(&key) {
+            source_faces[existing].push(face_idx);
+            continue;
+        }
+        if dropped.contains(&key) {
+            continue;
+        }
+
+        let (Ok(normal), Ok(centroid), Ok(area)) = (
+            compute_face_normal(&merged, &decimated.nodes),
+            compute_face_centroid(&merged, &decimated.nodes),
+            compute_face_area(&merged, &decimated.nodes),
+        ) else {
+            dropped.insert(key);
+            continue;
+        };
+        if normal.dot(&surface.face_normals[face_idx]) <= 0.0 {
+            // Folded against the original orientation
+            dropped.insert(key);
+            continue;
+        }
+
+        kept.insert(key, decimated.faces.len());
+        source_faces.push(vec![face_idx]);
+        decimated.faces.push(merged);
+        decimated.face_normals.push(normal);
+        decimated.face_centroids.push(centroid);
+        decimated.face_areas.push(area);
+    }
+
+    Decimation {
+        surface: decimated,
+        source_faces,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `n` x `n` grid of unit quads at height `z`
+    fn make_plate(n: usize, z: f64) -> SurfaceMesh {
+        let mut surface = SurfaceMesh::new("plate".to_string());
+        for j in 0..=n {
+            for i in 0..=n {
+                surface.nodes.push(Point::new(i as f64, j as f64, z));
+            }
+        }
+        for j in 0..n {
+            for i in 0..n {
+                let n0 = j * (n + 1) + i;
+                surface
+                    .faces
+                    .push(QuadFace::new([n0, n0 + 1, n0 + n + 2, n0 + n + 1]));
+                surface
+                    .face_centroids
+                    .push(Point::new(i as f64 + 0.5, j as f64 + 0.5, z));
+                surface.face_normals.push(Vec3::new(0.0, 0.0, 1.0));
+                surface.face_areas.push(1.0);
+            }
+        }
+        surface
+    }
+
+    #[test]
+    fn test_decimate_plate() {
+        let plate = make_plate(40, 0.25);
+        let preview = plate.decimate(100);
+
+        assert!(preview.num_faces() <= 100, "{} faces", preview.num_faces());
+        assert!(preview.num_faces() >= 25, "{} faces", preview.num_faces());
+        assert_eq!(preview.face_normals.len(), preview.num_faces());
+        assert!(preview.nodes.iter().all(|p| (p.z - 0.25).abs() < 1e-12));
+        assert!(preview
+            .face_normals
+            .iter()
+            .all(|n| (n - Vec3::new(0.0, 0.0, 1.0)).norm() < 1e-9));
+        // Straight borders are kept, only the corners are cut
+        assert!(preview.total_area() > 0.98 * plate.total_area());
+        assert!(preview.total_area() <= plate.total_area() + 1e-9);
+
+        // Already small enough
+        assert_eq!(plate.decimate(5000).num_faces(), plate.num_faces());
+    }
+
+    #[test]
+    fn test_decimate_grouped_keeps_boundaries() {
+        let plate = make_plate(40, 0.0);
+        // Left half in group 1, one face kept as is
+        let groups: Vec<u32> = (0..plate.num_faces())
+            .map(|f| u32::from(f % 40 < 20))
+            .collect();
+        let mut keep = vec![false; plate.num_faces()];
+        keep[25 * 40 + 30] = true;
+
+        let decimation = decimate_surface_grouped(&plate, 100, &groups, &keep);
+        let preview = &decimation.surface;
+        assert!(preview.num_faces() < plate.num_faces() / 4);
+        assert_eq!(decimation.source_faces.len(), preview.num_faces());
+
+        // No face merges across groups, the kept face is alone
+        for sources in &decimation.source_faces {
+            assert!(sources.iter().all(|&f| groups[f] == groups[sources[0]]));
+            if sources.contains(&(25 * 40 + 30)) {
+                assert_eq!(sources.len(), 1);
+            }
+        }
+        // The groups meet along x = 20 without gaps or overlaps (corners of
+        // the plate and of each group are cut by up to a cell)
+        let area_of_group = |group: u32| -> f64 {
+            (0..preview.num_faces())
+                .filter(|&f| groups[decimation.source_faces[f][0]] == group)
+                .map(|f| preview.face_areas[f])
+                .sum()
+        };
+        assert!((area_of_group(1) - 800.0).abs() < 0.1 * 800.0);
+        assert!((area_of_group(0) - 800.0).abs() < 0.1 * 800.0);
+        assert!((preview.total_area() - plate.total_area()).abs() < 0.05 * 1600.0);
+    }
+}
@@ -0,0 +1,178 @@
+//! Boundary edge loops of surfaces
+//!
+//! The outline of a surface patch or of a contact zone is made of the edges
+//! that belong to exactly one of its faces. Chaining those edges gives one
+//! loop per outline (an outer loop plus one per hole), whose length is the
+//! perimeter of the zone.
+
+use crate::mesh::types::{Point, SurfaceMesh};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// A chain of boundary edges
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EdgeLoop {
+    /// Node indices along the loop; a closed loop does not repeat its first
+    /// node at the end
+    pub nodes: Vec<usize>,
+
+    /// Whether the last node connects back to the first
+    pub closed: bool,
+
+    /// Total edge length
+    pub length: f64,
+}
+
+impl EdgeLoop {
+    /// Node indices as a polyline, repeating the first node of a closed loop
+    pub fn polyline(&self) -> Vec<usize> {
+        let mut line = self.nodes.clone();
+        if self.closed {
+            line.extend(self.nodes.first());
+        }
+        line
+    }
+}
+
+/// Boundary edge loops of a surface, longest first
+///
+/// Every boundary edge ends up in exactly one loop. Where the outline touches
+/// itself at a node (e.g. two faces meeting at a corner) the split into loops
+/// is arbitrary, and edges next to non-manifold edges may form open chains.
+pub fn boundary_loops(surface: &SurfaceMesh) -> Vec<EdgeLoop> {
+    // Count how many faces use each (undirected) edge
+    let mut edge_count: HashMap<(usize, usize), usize> = HashMap::new();
+    for face in &surface.faces {
+        let corners = face.corners();
+        for k in 0..corners.len() {
+            let (a, b) = (corners[k], corners[(k + 1) % corners.len()]);
+            *edge_count.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+        }
+    }
+
+    // Boundary edges at each node; ordered so that loops come out the same
+    // on every run
+    let mut edges: Vec<(usize, usize)> = edge_count
+        .into_iter()
+        .filter(|&(_, count)| count == 1)
+        .map(|(edge, _)| edge)
+        .collect();
+    edges.sort_unstable();
+    let mut at_node: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (edge_idx, &(a, b)) in edges.iter().enumerate() {
+        at_node.entry(a).or_default().push(edge_idx);
+        at_node.entry(b).or_default().push(edge_idx);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let next_unused = |node: usize, used: &mut [bool]| -> Option<usize> {
+        let edge_idx = *at_node.get(&node)?.iter().find(|&&e| !used[e])?;
+        used[edge_idx] = true;
+        let (a, b) = edges[edge_idx];
+        Some(if a == node { b } else { a })
+    };
+
+    let mut loops = Vec::new();
+    for start_edge in 0..edges.len() {
+        if used[start_edge] {
+            continue;
+        }
+        used[start_edge] = true;
+        let (start, second) = edges[start_edge];
+
+        // Walk forward from the second node until returning to the start or
+        // running out of edges, then extend backwards from the start
+        let mut chain = vec![start, second];
+        let mut closed = false;
+        while let Some(node) = next_unused(*chain.last().unwrap(), &mut used) {
+            if node == start {
+                closed = true;
+                break;
+            }
+            chain.push(node);
+        }
+        if !closed {
+            let mut backwards = Vec::new();
+            let mut node = start;
+            while let Some(previous) = next_unused(node, &mut used) {
+                backwards.push(previous);
+                node = previous;
+            }
+            backwards.reverse();
+            backwards.extend(chain);
+            chain = backwards;
+        }
+
+        let mut edge_loop = EdgeLoop {
+            nodes: chain,
+            closed,
+            length: 0.0,
+        };
+        edge_loop.length = polyline_length(&edge_loop.polyline(), &surface.nodes);
+        loops.push(edge_loop);
+    }
+
+    loops.sort_by(|a, b| b.length.total_cmp(&a.length));
+    loops
+}
+
+/// Total length of the segments between consecutive nodes
+fn polyline_length(line: &[usize], nodes: &[Point]) -> f64 {
+    line.windows(2)
+        .map(|pair| (nodes[pair[1]] - nodes[pair[0]]).norm())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::types::QuadFace;
+
+    /// `n` x `n` grid of unit squares in the z = 0 plane, skipping the faces
+    /// listed in `holes` (as (i, j))
+    fn make_grid(n: usize, holes: &[(usize, usize)]) -> SurfaceMesh {
+        let mut surface = SurfaceMesh::new("Grid".to_string());
+        for j in 0..=n {
+            for i in 0..=n {
+                surface.nodes.push(Point::new(i as f64, j as f64, 0.0));
+            }
+        }
+        let id = |i: usize, j: usize| j * (n + 1) + i;
+        for j in 0..n {
+            for i in 0..n {
+                if !holes.contains(&(i, j)) {
+                    surface.faces.push(QuadFace::new([
+                        id(i, j),
+                        id(i + 1, j),
+                        id(i + 1, j + 1),
+                        id(i, j + 1),
+                    ]));
+                }
+            }
+        }
+        surface
+    }
+
+    #[test]
+    fn test_outer_loop_and_hole() {
+        let loops = boundary_loops(&make_grid(3, &[(1, 1)]));
+        assert_eq!(loops.len(), 2);
+
+        assert!(loops[0].closed);
+        assert_eq!(loops[0].nodes.len(), 12);
+        assert!((loops[0].length - 12.0).abs() < 1e-12);
+
+        assert!(loops[1].closed);
+        assert!((loops[1].length - 4.0).abs() < 1e-12);
+        assert_eq!(loops[1].polyline().len(), 5);
+    }
+
+    #[test]
+    fn test_faces_touching_at_a_corner() {
+        // Two squares sharing only the node at (1, 1)
+        let loops = boundary_loops(&make_grid(2, &[(1, 0), (0, 1)]));
+        let total: f64 = loops.iter().map(|l| l.length).sum();
+        assert!((total - 8.0).abs() < 1e-12);
+        assert_eq!(loops.iter().map(|l| l.nodes.len()).sum::<usize>(), 8);
+    }
+}
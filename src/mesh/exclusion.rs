@@ -0,0 +1,135 @@
+//! Exclusion zones given by side sets
+//!
+//! Some regions must never be declared as contact, however close they come
+//! to another part: weld seams, instrumentation pads, bonded labels. When the
+//! mesh tags them with side sets (e.g. `no_contact_zone`), their faces are
+//! removed from the extracted surfaces, so they are neither written as skin
+//! nor paired. Faces are matched by their nodes, so a face shared by two
+//! blocks is excluded from both.
+
+use crate::error::Result;
+use crate::mesh::surface::extract_sideset_surface;
+use crate::mesh::types::{Mesh, QuadFace, SurfaceMesh};
+use std::collections::HashSet;
+
+/// Faces of the side sets excluded from skinning and pairing
+#[derive(Debug, Clone, Default)]
+pub struct ExclusionZones {
+    side_sets: Vec<String>,
+    faces: HashSet<QuadFace>,
+}
+
+impl ExclusionZones {
+    /// Faces of the named side sets of `mesh` (an error if one is missing)
+    pub fn from_side_sets(mesh: &Mesh, names: &[String]) -> Result<Self> {
+        let mut faces = HashSet::new();
+        for name in names {
+            let surface = extract_sideset_surface(mesh, name)?;
+            faces.extend(surface.faces.iter().map(QuadFace::canonical));
+        }
+
+        Ok(Self {
+            side_sets: names.to_vec(),
+            faces,
+        })
+    }
+
+    /// Names of the excluded side sets
+    pub fn side_sets(&self) -> &[String] {
+        &self.side_sets
+    }
+
+    /// Number of distinct excluded faces
+    pub fn num_faces(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Whether nothing is excluded
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+
+    /// Whether `face` (in any node order) lies in an exclusion zone
+    pub fn contains(&self, face: &QuadFace) -> bool {
+        self.faces.contains(&face.canonical())
+    }
+
+    /// The surfaces without their excluded faces, dropping surfaces left
+    /// empty, and the number of faces removed
+    pub fn apply(&self, surfaces: Vec<SurfaceMesh>) -> (Vec<SurfaceMesh>, usize) {
+        if self.is_empty() {
+            return (surfaces, 0);
+        }
+
+        let mut num_removed = 0;
+        let kept = surfaces
+            .into_iter()
+            .filter_map(|surface| {
+                let kept: Vec<usize> = (0..surface.num_faces())
+                    .filter(|&i| !self.contains(&surface.faces[i]))
+                    .collect();
+                num_removed += surface.num_faces() - kept.len();
+                match kept.len() {
+                    0 => None,
+                    n if n == surface.num_faces() => Some(surface),
+                    _ => Some(surface.subset(&kept)),
+                }
+            })
+            .collect();
+        (kept, num_removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::surface::extract_surface;
+    use crate::mesh::types::{HexElement, Point};
+
+    #[test]
+    fn test_exclusion_zones() {
+        // Two unit cubes side by side in one block; the pad covers the top
+        // of the second cube
+        let mut mesh = Mesh::new();
+        let outline = [
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.0),
+            (2.0, 1.0),
+            (1.0, 1.0),
+            (0.0, 1.0),
+        ];
+        for z in [0.0, 1.0] {
+            for (x, y) in outline {
+                mesh.nodes.push(Point::new(x, y, z));
+            }
+        }
+        mesh.elements
+            .push(HexElement::new([0, 1, 4, 5, 6, 7, 10, 11]));
+        mesh.elements
+            .push(HexElement::new([1, 2, 3, 4, 7, 8, 9, 10]));
+        mesh.element_blocks.insert("Block".to_string(), vec![0, 1]);
+        mesh.side_sets
+            .insert("no_contact_zone".to_string(), vec![(1, 6)]);
+
+        let surfaces = extract_surface(&mesh).unwrap();
+        let total: usize = surfaces.iter().map(|s| s.num_faces()).sum();
+        let zones =
+            ExclusionZones::from_side_sets(&mesh, &["no_contact_zone".to_string()]).unwrap();
+        assert_eq!(zones.num_faces(), 1);
+        assert_eq!(zones.side_sets(), ["no_contact_zone"]);
+
+        let (kept, num_removed) = zones.apply(surfaces);
+        assert_eq!(num_removed, 1);
+        assert_eq!(kept.iter().map(|s| s.num_faces()).sum::<usize>(), total - 1);
+        assert!(kept
+            .iter()
+            .flat_map(|s| &s.face_centroids)
+            .all(|c| !(c.z > 0.99 && c.x > 1.0)));
+
+        assert!(ExclusionZones::from_side_sets(&mesh, &["missing".to_string()]).is_err());
+        let (all, num_removed) = ExclusionZones::default().apply(extract_surface(&mesh).unwrap());
+        assert_eq!(num_removed, 0);
+        assert_eq!(all.iter().map(|s| s.num_faces()).sum::<usize>(), total);
+    }
+}
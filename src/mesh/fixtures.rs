@@ -0,0 +1,34 @@
+//! Surface fixtures shared by the unit tests
+
+use crate::mesh::types::{Point, QuadFace, SurfaceMesh, Vec3};
+
+/// `nx` × `ny` grid of unit squares at height `z`, normals along +z
+///
+/// Nodes and faces are numbered along y first, then x, so a single row
+/// numbers its nodes in pairs across the row.
+pub(crate) fn grid(name: &str, nx: usize, ny: usize, z: f64) -> SurfaceMesh {
+    let mut surface = SurfaceMesh::new(name.to_string());
+    for i in 0..=nx {
+        for j in 0..=ny {
+            surface.nodes.push(Point::new(i as f64, j as f64, z));
+        }
+    }
+    for i in 0..nx {
+        for j in 0..ny {
+            let n0 = i * (ny + 1) + j;
+            let n1 = n0 + ny + 1;
+            surface.faces.push(QuadFace::new([n0, n1, n1 + 1, n0 + 1]));
+            surface.face_normals.push(Vec3::new(0.0, 0.0, 1.0));
+            surface
+                .face_centroids
+                .push(Point::new(i as f64 + 0.5, j as f64 + 0.5, z));
+            surface.face_areas.push(1.0);
+        }
+    }
+    surface
+}
+
+/// Row of `n` unit squares along x at height `z`
+pub(crate) fn strip(name: &str, z: f64, n: usize) -> SurfaceMesh {
+    grid(name, n, 1, z)
+}
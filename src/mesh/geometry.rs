@@ -1,11 +1,16 @@
 //! Geometric operations for mesh elements
+//!
+//! The kernels are generic over the scalar type ([`Real`]), so they work on
+//! geometry stored in `f32` as well as `f64`.
 
 use crate::error::{ContactDetectorError, Result};
-use crate::mesh::types::{Point, QuadFace, Vec3};
+use crate::mesh::precision::Real;
+use crate::mesh::types::QuadFace;
+use nalgebra::{Point3, Vector3};
 
 /// Compute the normal vector of a quad face
 /// Uses the cross product of diagonals to get a normal pointing outward
-pub fn compute_face_normal(face: &QuadFace, nodes: &[Point]) -> Result<Vec3> {
+pub fn compute_face_normal<T: Real>(face: &QuadFace, nodes: &[Point3<T>]) -> Result<Vector3<T>> {
     let n0 = get_node(nodes, face.node_ids[0])?;
     let n1 = get_node(nodes, face.node_ids[1])?;
     let n2 = get_node(nodes, face.node_ids[2])?;
@@ -20,7 +25,7 @@ pub fn compute_face_normal(face: &QuadFace, nodes: &[Point]) -> Result<Vec3> {
 
     // Normalize
     let norm = normal.norm();
-    if norm < 1e-12 {
+    if norm < T::cast(1e-12) {
         return Err(ContactDetectorError::GeometryError(
             "Degenerate face (zero normal)".to_string(),
         ));
@@ -29,22 +34,26 @@ pub fn compute_face_normal(face: &QuadFace, nodes: &[Point]) -> Result<Vec3> {
     Ok(normal / norm)
 }
 
-/// Compute the centroid of a quad face
-pub fn compute_face_centroid(face: &QuadFace, nodes: &[Point]) -> Result<Point> {
+/// Compute the centroid of a quad (or triangle) face
+pub fn compute_face_centroid<T: Real>(face: &QuadFace, nodes: &[Point3<T>]) -> Result<Point3<T>> {
     let n0 = get_node(nodes, face.node_ids[0])?;
     let n1 = get_node(nodes, face.node_ids[1])?;
     let n2 = get_node(nodes, face.node_ids[2])?;
     let n3 = get_node(nodes, face.node_ids[3])?;
 
-    // Average of all four nodes
-    let centroid = (n0.coords + n1.coords + n2.coords + n3.coords) / 4.0;
+    // Average of the distinct corner nodes
+    let centroid = if face.is_triangle() {
+        (n0.coords + n1.coords + n2.coords) / T::cast(3.0)
+    } else {
+        (n0.coords + n1.coords + n2.coords + n3.coords) / T::cast(4.0)
+    };
 
-    Ok(Point::from(centroid))
+    Ok(Point3::from(centroid))
 }
 
 /// Compute the area of a quad face
 /// Uses the cross product of diagonals divided by 2
-pub fn compute_face_area(face: &QuadFace, nodes: &[Point]) -> Result<f64> {
+pub fn compute_face_area<T: Real>(face: &QuadFace, nodes: &[Point3<T>]) -> Result<T> {
     let n0 = get_node(nodes, face.node_ids[0])?;
     let n1 = get_node(nodes, face.node_ids[1])?;
     let n2 = get_node(nodes, face.node_ids[2])?;
@@ -55,9 +64,9 @@ pub fn compute_face_area(face: &QuadFace, nodes: &[Point]) -> Result<f64> {
     let d2 = n3 - n1;
 
     let cross = d1.cross(&d2);
-    let area = cross.norm() / 2.0;
+    let area = cross.norm() / T::cast(2.0);
 
-    if area < 1e-12 {
+    if area < T::cast(1e-12) {
         return Err(ContactDetectorError::GeometryError(
             "Degenerate face (zero area)".to_string(),
         ));
@@ -67,38 +76,166 @@ pub fn compute_face_area(face: &QuadFace, nodes: &[Point]) -> Result<f64> {
 }
 
 /// Compute the distance between two points
-pub fn distance(p1: &Point, p2: &Point) -> f64 {
+pub fn distance<T: Real>(p1: &Point3<T>, p2: &Point3<T>) -> T {
     (p2 - p1).norm()
 }
 
 /// Compute the signed distance from a point to a plane defined by a point and normal
 /// Positive distance means the point is on the side the normal points to
-pub fn signed_distance_to_plane(point: &Point, plane_point: &Point, plane_normal: &Vec3) -> f64 {
+pub fn signed_distance_to_plane<T: Real>(
+    point: &Point3<T>,
+    plane_point: &Point3<T>,
+    plane_normal: &Vector3<T>,
+) -> T {
     let v = point - plane_point;
     v.dot(plane_normal)
 }
 
 /// Project a point onto a plane defined by a point and normal
-pub fn project_point_to_plane(point: &Point, plane_point: &Point, plane_normal: &Vec3) -> Point {
+pub fn project_point_to_plane<T: Real>(
+    point: &Point3<T>,
+    plane_point: &Point3<T>,
+    plane_normal: &Vector3<T>,
+) -> Point3<T> {
     let dist = signed_distance_to_plane(point, plane_point, plane_normal);
-    Point::from(point.coords - dist * plane_normal)
+    Point3::from(point.coords - plane_normal * dist)
 }
 
 /// Compute the angle between two vectors in degrees
-pub fn angle_between_vectors(v1: &Vec3, v2: &Vec3) -> f64 {
+pub fn angle_between_vectors<T: Real>(v1: &Vector3<T>, v2: &Vector3<T>) -> T {
     let dot = v1.dot(v2);
     let norm_product = v1.norm() * v2.norm();
 
-    if norm_product < 1e-12 {
-        return 0.0;
+    if norm_product < T::cast(1e-12) {
+        return T::zero();
     }
 
-    let cos_angle = (dot / norm_product).clamp(-1.0, 1.0);
-    cos_angle.acos().to_degrees()
+    let cos_angle = (dot / norm_product).clamp(-T::one(), T::one());
+    cos_angle.acos() * T::cast(180.0) / T::pi()
+}
+
+/// Closest point to `p` on the triangle `abc` (Ericson, Real-Time Collision
+/// Detection, 5.1.5)
+pub fn closest_point_on_triangle<T: Real>(
+    p: &Point3<T>,
+    a: &Point3<T>,
+    b: &Point3<T>,
+    c: &Point3<T>,
+) -> Point3<T> {
+    let zero = T::zero();
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab.dot(&ap);
+    let d2 = ac.dot(&ap);
+    if d1 <= zero && d2 <= zero {
+        return *a;
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(&bp);
+    let d4 = ac.dot(&bp);
+    if d3 >= zero && d4 <= d3 {
+        return *b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= zero && d1 >= zero && d3 <= zero {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(&cp);
+    let d6 = ac.dot(&cp);
+    if d6 >= zero && d5 <= d6 {
+        return *c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= zero && d2 >= zero && d6 <= zero {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= zero && (d4 - d3) >= zero && (d5 - d6) >= zero {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    // Inside the triangle
+    let denom = T::one() / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+/// Closest point to `p` on a quad (or triangle) face
+///
+/// Quads are treated as two triangles split along the 0-2 diagonal.
+pub fn closest_point_on_face<T: Real>(p: &Point3<T>, face: &QuadFace, nodes: &[Point3<T>]) -> Point3<T> {
+    face_polygons(face, nodes, Some(Triangulation::Diagonal02))
+        .iter()
+        .map(|tri| closest_point_on_triangle(p, &nodes[tri[0]], &nodes[tri[1]], &nodes[tri[2]]))
+        .min_by(|x, y| {
+            distance(p, x)
+                .partial_cmp(&distance(p, y))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .expect("a face has at least one triangle")
+}
+
+/// How quad faces are split into triangles for tri-only output formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Triangulation {
+    /// Split along the shorter diagonal (better-shaped triangles)
+    ShortestDiagonal,
+
+    /// Always split along the diagonal through nodes 0 and 2
+    Diagonal02,
+
+    /// Always split along the diagonal through nodes 1 and 3
+    Diagonal13,
+}
+
+impl Triangulation {
+    /// Split a quad into two triangles with the same winding as the quad
+    ///
+    /// Ties in [`Triangulation::ShortestDiagonal`] use the 0-2 diagonal, so
+    /// the result is deterministic.
+    pub fn split<T: Real>(&self, face: &QuadFace, nodes: &[Point3<T>]) -> [[usize; 3]; 2] {
+        let [n0, n1, n2, n3] = face.node_ids;
+        let use_02 = match self {
+            Triangulation::Diagonal02 => true,
+            Triangulation::Diagonal13 => false,
+            Triangulation::ShortestDiagonal => {
+                distance(&nodes[n0], &nodes[n2]) <= distance(&nodes[n1], &nodes[n3])
+            }
+        };
+
+        if use_02 {
+            [[n0, n1, n2], [n0, n2, n3]]
+        } else {
+            [[n0, n1, n3], [n1, n2, n3]]
+        }
+    }
+}
+
+/// Polygons to output for a face: its corners, or its two triangles when
+/// triangulating a quad (triangle faces are always output as one triangle)
+pub fn face_polygons<T: Real>(
+    face: &QuadFace,
+    nodes: &[Point3<T>],
+    triangulation: Option<Triangulation>,
+) -> Vec<Vec<usize>> {
+    match triangulation {
+        Some(triangulation) if !face.is_triangle() => triangulation
+            .split(face, nodes)
+            .iter()
+            .map(|tri| tri.to_vec())
+            .collect(),
+        _ => vec![face.corners().to_vec()],
+    }
 }
 
 /// Helper to safely get a node from the node array
-fn get_node(nodes: &[Point], index: usize) -> Result<&Point> {
+fn get_node<T: Real>(nodes: &[Point3<T>], index: usize) -> Result<&Point3<T>> {
     nodes.get(index).ok_or_else(|| {
         ContactDetectorError::InvalidMeshTopology(format!("Node index {} out of bounds", index))
     })
@@ -107,6 +244,7 @@ fn get_node(nodes: &[Point], index: usize) -> Result<&Point> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mesh::types::{Point, Vec3};
     use approx::assert_relative_eq;
 
     fn make_square_face() -> (QuadFace, Vec<Point>) {
@@ -190,4 +328,75 @@ mod tests {
         assert_relative_eq!(angle_between_vectors(&v1, &v3), 180.0, epsilon = 1e-8);
         assert_relative_eq!(angle_between_vectors(&v1, &v1), 0.0, epsilon = 1e-8);
     }
+
+    #[test]
+    fn test_triangle_face_geometry() {
+        let nodes = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(3.0, 0.0, 0.0),
+            Point::new(0.0, 3.0, 0.0),
+        ];
+        let face = QuadFace::triangle([0, 1, 2]);
+
+        let normal = compute_face_normal(&face, &nodes).unwrap();
+        assert_relative_eq!(normal.z, 1.0, epsilon = 1e-10);
+        assert_relative_eq!(
+            compute_face_area(&face, &nodes).unwrap(),
+            4.5,
+            epsilon = 1e-10
+        );
+        let centroid = compute_face_centroid(&face, &nodes).unwrap();
+        assert_relative_eq!(centroid.x, 1.0, epsilon = 1e-10);
+        assert_relative_eq!(centroid.y, 1.0, epsilon = 1e-10);
+
+        let polygons = face_polygons(&face, &nodes, Some(Triangulation::Diagonal02));
+        assert_eq!(polygons, vec![vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn test_closest_point_on_face() {
+        let nodes = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+        ];
+        let face = QuadFace::new([0, 1, 2, 3]);
+
+        // Above the interior: projects straight down
+        let p = closest_point_on_face(&Point::new(0.8, 0.3, 2.0), &face, &nodes);
+        assert_relative_eq!(p, Point::new(0.8, 0.3, 0.0), epsilon = 1e-12);
+
+        // Beyond an edge and a corner: clamps to the boundary
+        let p = closest_point_on_face(&Point::new(0.5, -1.0, 1.0), &face, &nodes);
+        assert_relative_eq!(p, Point::new(0.5, 0.0, 0.0), epsilon = 1e-12);
+        let p = closest_point_on_face(&Point::new(2.0, 2.0, 0.0), &face, &nodes);
+        assert_relative_eq!(p, Point::new(1.0, 1.0, 0.0), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_triangulation_split() {
+        // Quad stretched along x: the 1-3 diagonal is shorter
+        let nodes = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(2.0, 0.0, 0.0),
+            Point::new(3.0, 1.0, 0.0),
+            Point::new(1.0, 1.0, 0.0),
+        ];
+        let face = QuadFace::new([0, 1, 2, 3]);
+
+        let shortest = Triangulation::ShortestDiagonal.split(&face, &nodes);
+        assert_eq!(shortest, [[0, 1, 3], [1, 2, 3]]);
+        assert_eq!(
+            Triangulation::Diagonal02.split(&face, &nodes),
+            [[0, 1, 2], [0, 2, 3]]
+        );
+
+        // Both triangles keep the quad's orientation
+        let quad_normal = compute_face_normal(&face, &nodes).unwrap();
+        for tri in shortest {
+            let [a, b, c] = tri.map(|i| nodes[i]);
+            assert!((b - a).cross(&(c - a)).dot(&quad_normal) > 0.0);
+        }
+    }
 }
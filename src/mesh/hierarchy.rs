@@ -0,0 +1,198 @@
+//! Block hierarchy from assembly records
+//!
+//! Newer Exodus files group element blocks into named assemblies, which may
+//! in turn group other assemblies (an `Engine` assembly of a `Head` and a
+//! `Crankcase` assembly, say). [`Mesh::assemblies`] keeps the direct members
+//! of each; the helpers here resolve them to element blocks so detection can
+//! be scoped within one assembly or between two ([`AssemblyScope`]) and
+//! results rolled up to the top-level assemblies
+//! ([`Mesh::block_assemblies`]).
+
+use crate::error::{ContactDetectorError, Result};
+use crate::mesh::types::Mesh;
+use nalgebra::Scalar;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+/// A named group of element blocks and/or other assemblies
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockAssembly {
+    /// Element blocks directly in the assembly
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocks: Vec<String>,
+
+    /// Names of the assemblies nested in this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assemblies: Vec<String>,
+}
+
+impl<T: Scalar> Mesh<T> {
+    /// All element blocks of an assembly, including those of nested
+    /// assemblies; blocks the mesh does not have are left out
+    pub fn assembly_blocks(&self, name: &str) -> Result<BTreeSet<String>> {
+        if !self.assemblies.contains_key(name) {
+            let mut available: Vec<&str> = self.assemblies.keys().map(String::as_str).collect();
+            available.sort_unstable();
+            return Err(ContactDetectorError::ConfigError(if available.is_empty() {
+                format!("Unknown assembly '{}': the mesh has no assemblies", name)
+            } else {
+                format!(
+                    "Unknown assembly '{}' (available: {})",
+                    name,
+                    available.join(", ")
+                )
+            }));
+        }
+
+        let mut blocks = BTreeSet::new();
+        let mut visited = BTreeSet::new();
+        let mut pending = vec![name];
+        while let Some(name) = pending.pop() {
+            // A malformed file may nest an assembly in itself
+            if !visited.insert(name) {
+                continue;
+            }
+            let Some(assembly) = self.assemblies.get(name) else {
+                continue;
+            };
+            blocks.extend(
+                assembly
+                    .blocks
+                    .iter()
+                    .filter(|block| self.element_blocks.contains_key(*block))
+                    .cloned(),
+            );
+            pending.extend(assembly.assemblies.iter().map(String::as_str));
+        }
+        Ok(blocks)
+    }
+
+    /// Assemblies not nested in any other, sorted by name
+    pub fn top_level_assemblies(&self) -> Vec<&str> {
+        let nested: BTreeSet<&str> = self
+            .assemblies
+            .values()
+            .flat_map(|assembly| assembly.assemblies.iter().map(String::as_str))
+            .collect();
+        let mut top: Vec<&str> = self
+            .assemblies
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !nested.contains(name))
+            .collect();
+        top.sort_unstable();
+        top
+    }
+
+    /// The top-level assemblies each element block belongs to, directly or
+    /// through nested assemblies; blocks in no assembly are left out
+    pub fn block_assemblies(&self) -> HashMap<String, Vec<String>> {
+        let mut groups: HashMap<String, Vec<String>> = HashMap::new();
+        for name in self.top_level_assemblies() {
+            for block in self.assembly_blocks(name).unwrap_or_default() {
+                groups.entry(block).or_default().push(name.to_string());
+            }
+        }
+        groups
+    }
+}
+
+/// Which contacts to detect, by the assemblies of their parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssemblyScope {
+    /// Contacts with both parts in these blocks
+    Within(BTreeSet<String>),
+
+    /// Contacts with one part in each set of blocks
+    Between(BTreeSet<String>, BTreeSet<String>),
+}
+
+impl AssemblyScope {
+    /// Contacts inside the named assembly
+    pub fn within<T: Scalar>(mesh: &Mesh<T>, assembly: &str) -> Result<Self> {
+        Ok(Self::Within(mesh.assembly_blocks(assembly)?))
+    }
+
+    /// Contacts between the two named assemblies
+    pub fn between<T: Scalar>(mesh: &Mesh<T>, assembly_a: &str, assembly_b: &str) -> Result<Self> {
+        Ok(Self::Between(
+            mesh.assembly_blocks(assembly_a)?,
+            mesh.assembly_blocks(assembly_b)?,
+        ))
+    }
+
+    /// Whether a block can take part in any contact in scope
+    pub fn includes_block(&self, block: &str) -> bool {
+        match self {
+            Self::Within(blocks) => blocks.contains(block),
+            Self::Between(a, b) => a.contains(block) || b.contains(block),
+        }
+    }
+
+    /// Whether a contact between two blocks is in scope
+    pub fn allows(&self, block_a: &str, block_b: &str) -> bool {
+        match self {
+            Self::Within(blocks) => blocks.contains(block_a) && blocks.contains(block_b),
+            Self::Between(a, b) => {
+                (a.contains(block_a) && b.contains(block_b))
+                    || (a.contains(block_b) && b.contains(block_a))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mesh() -> Mesh {
+        let mut mesh = Mesh::new();
+        for block in ["Head", "Gasket", "Crankcase", "Bracket"] {
+            mesh.element_blocks.insert(block.to_string(), Vec::new());
+        }
+        let assembly = |blocks: &[&str], assemblies: &[&str]| BlockAssembly {
+            blocks: blocks.iter().map(|b| b.to_string()).collect(),
+            assemblies: assemblies.iter().map(|a| a.to_string()).collect(),
+        };
+        mesh.assemblies = HashMap::from([
+            ("Engine".to_string(), assembly(&[], &["Top", "Bottom"])),
+            ("Top".to_string(), assembly(&["Head", "Gasket"], &[])),
+            (
+                "Bottom".to_string(),
+                assembly(&["Crankcase", "Missing"], &["Bottom"]),
+            ),
+            ("Mount".to_string(), assembly(&["Bracket"], &[])),
+        ]);
+        mesh
+    }
+
+    #[test]
+    fn test_assembly_hierarchy() {
+        let mesh = mesh();
+        let engine: Vec<String> = mesh
+            .assembly_blocks("Engine")
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(engine, ["Crankcase", "Gasket", "Head"]);
+        assert!(mesh.assembly_blocks("Frame").is_err());
+        assert!(Mesh::new().assembly_blocks("Engine").is_err());
+
+        assert_eq!(mesh.top_level_assemblies(), ["Engine", "Mount"]);
+        let groups = mesh.block_assemblies();
+        assert_eq!(groups["Head"], ["Engine"]);
+        assert_eq!(groups["Bracket"], ["Mount"]);
+        assert_eq!(groups.len(), 4);
+
+        let within = AssemblyScope::within(&mesh, "Top").unwrap();
+        assert!(within.allows("Head", "Gasket"));
+        assert!(!within.allows("Head", "Crankcase"));
+        assert!(!within.includes_block("Bracket"));
+
+        let between = AssemblyScope::between(&mesh, "Engine", "Mount").unwrap();
+        assert!(between.allows("Bracket", "Crankcase"));
+        assert!(between.allows("Head", "Bracket"));
+        assert!(!between.allows("Head", "Gasket"));
+        assert!(between.includes_block("Gasket"));
+    }
+}
@@ -0,0 +1,130 @@
+//! Filling small holes in extracted skins
+//!
+//! Operations that need a watertight skin (SDF voxelization, enclosed volume)
+//! fail on blocks with small cracks or missing faces. Filling closes each
+//! small hole (see [`crate::mesh::check_block_closure`]) with a fan of faces
+//! over the existing hole nodes, so no nodes are added and the faces still
+//! index the volume mesh nodes. Filler faces go into surfaces of their own,
+//! named `<block>:hole_<k>`, so they stay distinguishable from the faces of
+//! the mesh.
+
+use crate::error::Result;
+use crate::mesh::geometry::compute_face_area;
+use crate::mesh::surface::{build_surface_mesh, check_block_closure};
+use crate::mesh::types::{QuadFace, SurfaceMesh};
+use std::collections::HashSet;
+
+/// Whether a surface is a hole filler made by [`fill_small_holes`]
+pub fn is_hole_filler(surface: &SurfaceMesh) -> bool {
+    surface
+        .part_name
+        .rsplit_once(':')
+        .is_some_and(|(_, patch)| patch.starts_with("hole_"))
+}
+
+/// Filler surfaces for the closed holes of at most `max_perimeter` in the
+/// skins of the blocks of `surfaces`
+///
+/// Each hole is closed by a quad (four nodes), a triangle (three) or a fan of
+/// triangles from its first node, oriented like the faces around it. Fans
+/// over strongly non-convex holes may overlap; degenerate (zero-area) faces
+/// are left out, so a hole whose nodes are collinear or coincident (a
+/// zipped-up crack) is not filled. Open chains are never filled.
+pub fn fill_small_holes(surfaces: &[SurfaceMesh], max_perimeter: f64) -> Result<Vec<SurfaceMesh>> {
+    let Some(nodes) = surfaces.first().map(|s| &s.nodes) else {
+        return Ok(Vec::new());
+    };
+
+    let mut fillers = Vec::new();
+    for closure in check_block_closure(surfaces)? {
+        // Directed edges of the block's faces, to orient the fill against them
+        let directed: HashSet<(usize, usize)> = surfaces
+            .iter()
+            .filter(|s| s.block_name() == closure.block)
+            .flat_map(|s| &s.faces)
+            .flat_map(|face| {
+                let corners = face.corners();
+                (0..corners.len()).map(move |k| (corners[k], corners[(k + 1) % corners.len()]))
+            })
+            .collect();
+
+        let mut num_filled = 0;
+        for hole in &closure.holes {
+            if !hole.closed || hole.length > max_perimeter || hole.nodes.len() < 3 {
+                continue;
+            }
+
+            // A face around the hole runs along it in one direction; the fill
+            // must run the other way
+            let mut ring = hole.nodes.clone();
+            if directed.contains(&(ring[0], ring[1])) {
+                ring.reverse();
+            }
+
+            let faces: Vec<QuadFace> = match ring.len() {
+                3 => vec![QuadFace::triangle([ring[0], ring[1], ring[2]])],
+                4 => vec![QuadFace::new([ring[0], ring[1], ring[2], ring[3]])],
+                n => (1..n - 1)
+                    .map(|i| QuadFace::triangle([ring[0], ring[i], ring[i + 1]]))
+                    .collect(),
+            };
+            let faces: Vec<QuadFace> = faces
+                .into_iter()
+                .filter(|face| compute_face_area(face, nodes).is_ok())
+                .collect();
+            if faces.is_empty() {
+                continue;
+            }
+
+            let name = format!("{}:hole_{}", closure.block, num_filled);
+            fillers.push(build_surface_mesh(name, faces, nodes)?);
+            num_filled += 1;
+        }
+    }
+
+    Ok(fillers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::surface::extract_surface;
+    use crate::mesh::types::{HexElement, Mesh, Point};
+
+    #[test]
+    fn test_fill_small_holes() {
+        let mut mesh = Mesh::new();
+        for z in [0.0, 1.0] {
+            mesh.nodes.push(Point::new(0.0, 0.0, z));
+            mesh.nodes.push(Point::new(1.0, 0.0, z));
+            mesh.nodes.push(Point::new(1.0, 1.0, z));
+            mesh.nodes.push(Point::new(0.0, 1.0, z));
+        }
+        mesh.elements
+            .push(HexElement::new([0, 1, 2, 3, 4, 5, 6, 7]));
+        mesh.element_blocks.insert("Block1".to_string(), vec![0]);
+
+        let all = extract_surface(&mesh).unwrap();
+        assert!(fill_small_holes(&all, 10.0).unwrap().is_empty());
+
+        // Remove the top face: a hole of perimeter 4
+        let (top, mut surfaces): (Vec<_>, Vec<_>) =
+            all.into_iter().partition(|s| s.face_centroids[0].z > 0.9);
+        assert!(fill_small_holes(&surfaces, 3.9).unwrap().is_empty());
+
+        let fillers = fill_small_holes(&surfaces, 4.0).unwrap();
+        assert_eq!(fillers.len(), 1);
+        assert_eq!(fillers[0].part_name, "Block1:hole_0");
+        assert!(is_hole_filler(&fillers[0]));
+        assert!(!is_hole_filler(&surfaces[0]));
+        assert_eq!(fillers[0].num_faces(), 1);
+        assert!((fillers[0].total_area() - 1.0).abs() < 1e-12);
+
+        // Same orientation as the face it replaces
+        assert!(fillers[0].face_normals[0].dot(&top[0].face_normals[0]) > 0.99);
+
+        surfaces.extend(fillers);
+        let closure = check_block_closure(&surfaces).unwrap();
+        assert!(closure[0].closed);
+    }
+}
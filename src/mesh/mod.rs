@@ -1,9 +1,41 @@
 //! Mesh data structures and operations
 
+pub mod arrays;
+pub mod assembly;
+pub mod bounds;
+pub mod components;
+pub mod decimate;
+pub mod edges;
+pub mod exclusion;
+#[cfg(test)]
+pub(crate) mod fixtures;
 pub mod geometry;
+pub mod hierarchy;
+pub mod holes;
+pub mod mass;
+pub mod precision;
+pub mod roi;
+pub mod stats;
 pub mod surface;
+pub mod tags;
 pub mod types;
+pub mod union_find;
 
+pub use arrays::*;
+pub use assembly::*;
+pub use bounds::*;
+pub use components::*;
+pub use decimate::*;
+pub use edges::*;
+pub use exclusion::*;
 pub use geometry::*;
+pub use hierarchy::*;
+pub use holes::*;
+pub use mass::*;
+pub use precision::*;
+pub use roi::*;
+pub use stats::*;
 pub use surface::*;
+pub use tags::*;
 pub use types::*;
+pub use union_find::*;
@@ -0,0 +1,148 @@
+//! Scalar precision of stored geometry
+//!
+//! Meshes and surfaces store node coordinates and per-face data in double
+//! precision by default. For huge models where single precision is accurate
+//! enough (coordinates with about seven significant digits), [`Mesh`] and
+//! [`SurfaceMesh`] can hold `f32` instead, roughly halving the memory of the
+//! geometry: convert with [`Mesh::to_precision`] and extract surfaces from the
+//! converted mesh. The geometry kernels in [`crate::mesh::geometry`] are
+//! generic over [`Real`]; contact detection itself runs in `f64`, so surfaces
+//! are widened with [`SurfaceMesh::to_precision`] right before they are
+//! compared.
+
+use crate::mesh::types::{Mesh, SurfaceMesh};
+use nalgebra::{Point3, RealField};
+
+/// Floating-point type geometry can be stored in (`f32` or `f64`)
+pub trait Real: RealField + Copy {
+    /// Convert from `f64`, rounding if `Self` is narrower
+    fn cast(value: f64) -> Self {
+        nalgebra::convert(value)
+    }
+
+    /// Convert to `f64` (exact)
+    fn into_f64(self) -> f64 {
+        self.to_subset().expect("f32 and f64 convert to f64")
+    }
+}
+
+impl Real for f32 {}
+impl Real for f64 {}
+
+fn convert_points<T: Real, U: Real>(points: &[Point3<T>]) -> Vec<Point3<U>> {
+    points
+        .iter()
+        .map(|p| {
+            Point3::new(
+                U::cast(p.x.into_f64()),
+                U::cast(p.y.into_f64()),
+                U::cast(p.z.into_f64()),
+            )
+        })
+        .collect()
+}
+
+impl<T: Real> Mesh<T> {
+    /// Copy of this mesh with node coordinates stored as `U`
+    pub fn to_precision<U: Real>(&self) -> Mesh<U> {
+        Mesh {
+            nodes: convert_points(&self.nodes),
+            elements: self.elements.clone(),
+            element_blocks: self.element_blocks.clone(),
+            material_ids: self.material_ids.clone(),
+            node_sets: self.node_sets.clone(),
+            side_sets: self.side_sets.clone(),
+            side_set_dist_factors: self.side_set_dist_factors.clone(),
+            side_set_fields: self.side_set_fields.clone(),
+            node_num_map: self.node_num_map.clone(),
+            elem_num_map: self.elem_num_map.clone(),
+            node_fields: self.node_fields.clone(),
+            element_fields: self.element_fields.clone(),
+            assemblies: self.assemblies.clone(),
+            units: self.units.clone(),
+            provenance: self.provenance.clone(),
+        }
+    }
+
+    /// Approximate heap memory of the nodes and elements in bytes
+    pub fn geometry_bytes(&self) -> usize {
+        std::mem::size_of_val(self.nodes.as_slice())
+            + std::mem::size_of_val(self.elements.as_slice())
+    }
+}
+
+impl<T: Real> SurfaceMesh<T> {
+    /// Copy of this surface with coordinates and face data stored as `U`
+    pub fn to_precision<U: Real>(&self) -> SurfaceMesh<U> {
+        SurfaceMesh {
+            part_name: self.part_name.clone(),
+            faces: self.faces.clone(),
+            face_normals: self
+                .face_normals
+                .iter()
+                .map(|n| n.map(|c| U::cast(c.into_f64())))
+                .collect(),
+            face_centroids: convert_points(&self.face_centroids),
+            face_areas: self
+                .face_areas
+                .iter()
+                .map(|a| U::cast(a.into_f64()))
+                .collect(),
+            nodes: convert_points(&self.nodes),
+            face_tags: self.face_tags.clone(),
+        }
+    }
+
+    /// Approximate heap memory of the faces, face data and nodes in bytes
+    pub fn geometry_bytes(&self) -> usize {
+        std::mem::size_of_val(self.faces.as_slice())
+            + std::mem::size_of_val(self.face_normals.as_slice())
+            + std::mem::size_of_val(self.face_centroids.as_slice())
+            + std::mem::size_of_val(self.face_areas.as_slice())
+            + std::mem::size_of_val(self.nodes.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::surface::extract_surface;
+    use crate::mesh::types::{HexElement, Point};
+
+    #[test]
+    fn test_f32_storage() {
+        let mut mesh = Mesh::new();
+        mesh.nodes = (0..8)
+            .map(|i| {
+                Point::new(
+                    (i & 1) as f64 * 2.0,
+                    ((i >> 1) & 1) as f64,
+                    (i >> 2) as f64 + 1e-9,
+                )
+            })
+            .collect();
+        mesh.elements = vec![HexElement::new([0, 1, 3, 2, 4, 5, 7, 6])];
+        mesh.element_blocks.insert("Block1".to_string(), vec![0]);
+
+        let compact: Mesh<f32> = mesh.to_precision();
+        assert_eq!(
+            std::mem::size_of_val(compact.nodes.as_slice()) * 2,
+            std::mem::size_of_val(mesh.nodes.as_slice())
+        );
+
+        let surfaces = extract_surface(&mesh).unwrap();
+        let compact_surfaces = extract_surface(&compact).unwrap();
+        assert_eq!(surfaces.len(), compact_surfaces.len());
+        for (full, compact) in surfaces.iter().zip(&compact_surfaces) {
+            assert_eq!(full.faces, compact.faces);
+            assert!(compact.geometry_bytes() < full.geometry_bytes());
+            let widened: SurfaceMesh = compact.to_precision();
+            for (a, b) in full.face_areas.iter().zip(&widened.face_areas) {
+                assert!((a - b).abs() < 1e-6);
+            }
+            for (a, b) in full.face_centroids.iter().zip(&widened.face_centroids) {
+                assert!((a - b).norm() < 1e-6);
+            }
+        }
+    }
+}
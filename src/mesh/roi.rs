@@ -0,0 +1,306 @@
+//! Region-of-interest (ROI) filtering
+//!
+//! A region of interest restricts detection (and optionally output) to faces
+//! whose centroid lies inside a box, a sphere, a cylinder, or a closed STL
+//! "tool" volume.
+//! This makes iterating on a single joint of a large assembly much cheaper.
+
+use crate::error::{ContactDetectorError, Result};
+use crate::io::stl::{read_stl_triangles, Triangle};
+use crate::mesh::types::{Point, SurfaceMesh, Vec3};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Serializable description of a region of interest
+///
+/// Used in configuration files; call [`RoiSpec::load`] to obtain a
+/// [`RegionOfInterest`] that can be queried.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RoiSpec {
+    /// Axis-aligned box
+    Box { min: [f64; 3], max: [f64; 3] },
+
+    /// Sphere
+    Sphere { center: [f64; 3], radius: f64 },
+
+    /// Finite cylinder between the centres of its two end caps
+    Cylinder {
+        start: [f64; 3],
+        end: [f64; 3],
+        radius: f64,
+    },
+
+    /// Closed triangulated volume read from an STL file
+    Stl { path: PathBuf },
+}
+
+impl RoiSpec {
+    /// Validate the specification and load any referenced geometry
+    pub fn load(&self) -> Result<RegionOfInterest> {
+        match self {
+            RoiSpec::Box { min, max } => {
+                if (0..3).any(|i| min[i] > max[i]) {
+                    return Err(ContactDetectorError::ConfigError(format!(
+                        "Invalid ROI box: min {:?} exceeds max {:?}",
+                        min, max
+                    )));
+                }
+                Ok(RegionOfInterest::Box {
+                    min: Point::from(*min),
+                    max: Point::from(*max),
+                })
+            }
+            RoiSpec::Sphere { center, radius } => {
+                if *radius <= 0.0 {
+                    return Err(ContactDetectorError::ConfigError(format!(
+                        "Invalid ROI sphere radius: {}",
+                        radius
+                    )));
+                }
+                Ok(RegionOfInterest::Sphere {
+                    center: Point::from(*center),
+                    radius: *radius,
+                })
+            }
+            RoiSpec::Cylinder { start, end, radius } => {
+                if *radius <= 0.0 || start == end {
+                    return Err(ContactDetectorError::ConfigError(format!(
+                        "Invalid ROI cylinder: radius {} from {:?} to {:?}",
+                        radius, start, end
+                    )));
+                }
+                Ok(RegionOfInterest::Cylinder {
+                    start: Point::from(*start),
+                    end: Point::from(*end),
+                    radius: *radius,
+                })
+            }
+            RoiSpec::Stl { path } => {
+                let triangles = read_stl_triangles(path)?;
+                if triangles.is_empty() {
+                    return Err(ContactDetectorError::ConfigError(format!(
+                        "ROI volume '{}' contains no triangles",
+                        path.display()
+                    )));
+                }
+                Ok(RegionOfInterest::Volume(TriangleVolume::new(triangles)))
+            }
+        }
+    }
+}
+
+/// A loaded region of interest
+#[derive(Debug, Clone)]
+pub enum RegionOfInterest {
+    /// Axis-aligned box
+    Box { min: Point, max: Point },
+
+    /// Sphere
+    Sphere { center: Point, radius: f64 },
+
+    /// Finite cylinder between the centres of its two end caps
+    Cylinder {
+        start: Point,
+        end: Point,
+        radius: f64,
+    },
+
+    /// Closed triangulated volume
+    Volume(TriangleVolume),
+}
+
+impl RegionOfInterest {
+    /// Whether a point lies inside the region (boundary counts as inside)
+    pub fn contains(&self, point: &Point) -> bool {
+        match self {
+            RegionOfInterest::Box { min, max } => {
+                (0..3).all(|i| point[i] >= min[i] && point[i] <= max[i])
+            }
+            RegionOfInterest::Sphere { center, radius } => (point - center).norm() <= *radius,
+            RegionOfInterest::Cylinder { start, end, radius } => {
+                let axis = end - start;
+                let t = (point - start).dot(&axis) / axis.norm_squared();
+                (0.0..=1.0).contains(&t) && (point - (start + axis * t)).norm() <= *radius
+            }
+            RegionOfInterest::Volume(volume) => volume.contains(point),
+        }
+    }
+}
+
+/// Closed triangle mesh used as an inside/outside test volume
+#[derive(Debug, Clone)]
+pub struct TriangleVolume {
+    triangles: Vec<Triangle>,
+    bbox_min: Point,
+    bbox_max: Point,
+}
+
+impl TriangleVolume {
+    /// Create a volume from a closed set of triangles
+    pub fn new(triangles: Vec<Triangle>) -> Self {
+        let mut bbox_min = Point::new(f64::MAX, f64::MAX, f64::MAX);
+        let mut bbox_max = Point::new(f64::MIN, f64::MIN, f64::MIN);
+        for vertex in triangles.iter().flatten() {
+            for i in 0..3 {
+                bbox_min[i] = bbox_min[i].min(vertex[i]);
+                bbox_max[i] = bbox_max[i].max(vertex[i]);
+            }
+        }
+
+        Self {
+            triangles,
+            bbox_min,
+            bbox_max,
+        }
+    }
+
+    /// Inside test by ray casting (odd number of crossings = inside)
+    pub fn contains(&self, point: &Point) -> bool {
+        if (0..3).any(|i| point[i] < self.bbox_min[i] || point[i] > self.bbox_max[i]) {
+            return false;
+        }
+
+        // Slightly skewed direction avoids rays running exactly along mesh edges
+        let dir = Vec3::new(1.0, 1e-3, 2e-3).normalize();
+        let crossings = self
+            .triangles
+            .iter()
+            .filter(|tri| ray_intersects_triangle(point, &dir, tri))
+            .count();
+
+        crossings % 2 == 1
+    }
+}
+
+/// Möller–Trumbore ray/triangle intersection (forward hits only)
+fn ray_intersects_triangle(origin: &Point, dir: &Vec3, tri: &Triangle) -> bool {
+    const EPS: f64 = 1e-12;
+
+    let edge1 = tri[1] - tri[0];
+    let edge2 = tri[2] - tri[0];
+    let h = dir.cross(&edge2);
+    let a = edge1.dot(&h);
+    if a.abs() < EPS {
+        return false;
+    }
+
+    let f = 1.0 / a;
+    let s = origin - tri[0];
+    let u = f * s.dot(&h);
+    if !(0.0..=1.0).contains(&u) {
+        return false;
+    }
+
+    let q = s.cross(&edge1);
+    let v = f * dir.dot(&q);
+    if v < 0.0 || u + v > 1.0 {
+        return false;
+    }
+
+    f * edge2.dot(&q) > EPS
+}
+
+/// Restrict a surface to the faces whose centroid lies inside `roi`
+///
+/// Returns the restricted surface and, for each of its faces, the index of the
+/// corresponding face in the original surface.
+pub fn restrict_surface(
+    surface: &SurfaceMesh,
+    roi: &RegionOfInterest,
+) -> (SurfaceMesh, Vec<usize>) {
+    let kept: Vec<usize> = (0..surface.faces.len())
+        .filter(|&i| roi.contains(&surface.face_centroids[i]))
+        .collect();
+
+    (surface.subset(&kept), kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Closed unit cube [0,1]^3 as 12 triangles
+    fn unit_cube_triangles() -> Vec<Triangle> {
+        let p = |x: f64, y: f64, z: f64| Point::new(x, y, z);
+        let quads = [
+            [p(0., 0., 0.), p(1., 0., 0.), p(1., 1., 0.), p(0., 1., 0.)],
+            [p(0., 0., 1.), p(1., 0., 1.), p(1., 1., 1.), p(0., 1., 1.)],
+            [p(0., 0., 0.), p(1., 0., 0.), p(1., 0., 1.), p(0., 0., 1.)],
+            [p(0., 1., 0.), p(1., 1., 0.), p(1., 1., 1.), p(0., 1., 1.)],
+            [p(0., 0., 0.), p(0., 1., 0.), p(0., 1., 1.), p(0., 0., 1.)],
+            [p(1., 0., 0.), p(1., 1., 0.), p(1., 1., 1.), p(1., 0., 1.)],
+        ];
+        quads
+            .iter()
+            .flat_map(|q| [[q[0], q[1], q[2]], [q[0], q[2], q[3]]])
+            .collect()
+    }
+
+    #[test]
+    fn test_box_and_sphere_contains() {
+        let roi = RoiSpec::Box {
+            min: [0.0, 0.0, 0.0],
+            max: [1.0, 1.0, 1.0],
+        }
+        .load()
+        .unwrap();
+        assert!(roi.contains(&Point::new(0.5, 0.5, 1.0)));
+        assert!(!roi.contains(&Point::new(0.5, 0.5, 1.1)));
+
+        let roi = RoiSpec::Sphere {
+            center: [0.0, 0.0, 0.0],
+            radius: 1.0,
+        }
+        .load()
+        .unwrap();
+        assert!(roi.contains(&Point::new(0.5, 0.5, 0.5)));
+        assert!(!roi.contains(&Point::new(1.0, 1.0, 0.0)));
+
+        assert!(RoiSpec::Sphere {
+            center: [0.0; 3],
+            radius: -1.0
+        }
+        .load()
+        .is_err());
+    }
+
+    #[test]
+    fn test_cylinder_contains() {
+        let roi = RoiSpec::Cylinder {
+            start: [0.0, 0.0, 0.0],
+            end: [0.0, 0.0, 2.0],
+            radius: 0.5,
+        }
+        .load()
+        .unwrap();
+        assert!(roi.contains(&Point::new(0.3, 0.3, 1.0)));
+        assert!(roi.contains(&Point::new(0.0, 0.0, 2.0)));
+        assert!(!roi.contains(&Point::new(0.4, 0.4, 1.0)));
+        assert!(!roi.contains(&Point::new(0.0, 0.0, 2.1)));
+        assert!(!roi.contains(&Point::new(0.0, 0.0, -0.1)));
+    }
+
+    #[test]
+    fn test_triangle_volume_contains() {
+        let volume = TriangleVolume::new(unit_cube_triangles());
+        assert!(volume.contains(&Point::new(0.5, 0.5, 0.5)));
+        assert!(volume.contains(&Point::new(0.1, 0.9, 0.2)));
+        assert!(!volume.contains(&Point::new(1.5, 0.5, 0.5)));
+        assert!(!volume.contains(&Point::new(-0.5, 0.5, 0.5)));
+    }
+
+    #[test]
+    fn test_spec_serde() {
+        let spec: RoiSpec =
+            serde_json::from_str(r#"{"type": "sphere", "center": [1, 2, 3], "radius": 0.5}"#)
+                .unwrap();
+        assert_eq!(
+            spec,
+            RoiSpec::Sphere {
+                center: [1.0, 2.0, 3.0],
+                radius: 0.5
+            }
+        );
+    }
+}
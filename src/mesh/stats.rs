@@ -0,0 +1,242 @@
+//! Element size statistics
+//!
+//! Contact tolerances only make sense relative to the element size: a gap of
+//! 0.01 is huge on a mesh of 0.05 elements and noise on one of 10. This
+//! computes the distributions of element edge lengths and face areas per
+//! element block and derives default detection tolerances from the finest
+//! block.
+
+use crate::mesh::geometry::{compute_face_area, distance};
+use crate::mesh::types::{HexElement, Mesh};
+use serde::{Deserialize, Serialize};
+
+/// Node pairs of the 12 edges of a hexahedron
+const HEX_EDGES: [(usize, usize); 12] = [
+    (0, 1),
+    (1, 2),
+    (2, 3),
+    (3, 0),
+    (4, 5),
+    (5, 6),
+    (6, 7),
+    (7, 4),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+/// Recommended maximum gap as a fraction of the median edge length
+pub const GAP_FRACTION_OF_EDGE: f64 = 0.1;
+
+/// Recommended maximum penetration as a fraction of the median edge length
+pub const PENETRATION_FRACTION_OF_EDGE: f64 = 0.02;
+
+/// Summary of a set of values
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Distribution {
+    pub count: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+
+    /// 5th percentile
+    pub p05: f64,
+
+    /// 95th percentile
+    pub p95: f64,
+}
+
+impl Distribution {
+    /// Summarize the finite values, `None` if there are none
+    pub fn from_values(mut values: Vec<f64>) -> Option<Self> {
+        values.retain(|v| v.is_finite());
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+
+        let percentile = |p: f64| values[((values.len() - 1) as f64 * p).round() as usize];
+        Some(Self {
+            count: values.len(),
+            min: values[0],
+            max: values[values.len() - 1],
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+            median: percentile(0.5),
+            p05: percentile(0.05),
+            p95: percentile(0.95),
+        })
+    }
+}
+
+/// Element sizes of one element block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockStats {
+    pub name: String,
+    pub num_elements: usize,
+
+    /// Lengths of the element edges (edges shared by several elements are
+    /// counted once per element)
+    pub edge_length: Option<Distribution>,
+
+    /// Areas of the element faces (degenerate faces are skipped)
+    pub face_area: Option<Distribution>,
+}
+
+/// Contact tolerances suited to the element size
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecommendedTolerances {
+    pub max_gap: f64,
+    pub max_penetration: f64,
+
+    /// Block whose element size the tolerances are based on
+    pub reference_block: String,
+
+    /// Median edge length of the reference block
+    pub reference_edge_length: f64,
+}
+
+/// Element size statistics of a mesh
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeshStats {
+    /// Per-block statistics, sorted by block name
+    pub blocks: Vec<BlockStats>,
+
+    /// Edge lengths over all blocks
+    pub edge_length: Option<Distribution>,
+
+    /// Face areas over all blocks
+    pub face_area: Option<Distribution>,
+}
+
+impl MeshStats {
+    /// Compute the element size statistics of every block
+    pub fn compute(mesh: &Mesh) -> Self {
+        let mut names: Vec<&String> = mesh.element_blocks.keys().collect();
+        names.sort();
+
+        let mut all_edges = Vec::new();
+        let mut all_areas = Vec::new();
+        let blocks = names
+            .into_iter()
+            .map(|name| {
+                let elements = &mesh.element_blocks[name];
+                let (edges, areas) = element_sizes(mesh, elements);
+                all_edges.extend_from_slice(&edges);
+                all_areas.extend_from_slice(&areas);
+                BlockStats {
+                    name: name.clone(),
+                    num_elements: elements.len(),
+                    edge_length: Distribution::from_values(edges),
+                    face_area: Distribution::from_values(areas),
+                }
+            })
+            .collect();
+
+        Self {
+            blocks,
+            edge_length: Distribution::from_values(all_edges),
+            face_area: Distribution::from_values(all_areas),
+        }
+    }
+
+    /// Tolerances based on the block with the smallest median edge length,
+    /// so that no part sees a gap tolerance beyond a fraction of its elements
+    pub fn recommended_tolerances(&self) -> Option<RecommendedTolerances> {
+        let (block, edge) = self
+            .blocks
+            .iter()
+            .filter_map(|block| Some((block, block.edge_length?.median)))
+            .filter(|&(_, median)| median > 0.0)
+            .min_by(|a, b| a.1.total_cmp(&b.1))?;
+        Some(RecommendedTolerances {
+            max_gap: GAP_FRACTION_OF_EDGE * edge,
+            max_penetration: PENETRATION_FRACTION_OF_EDGE * edge,
+            reference_block: block.name.clone(),
+            reference_edge_length: edge,
+        })
+    }
+}
+
+/// Edge lengths and face areas of the given elements
+fn element_sizes(mesh: &Mesh, elements: &[usize]) -> (Vec<f64>, Vec<f64>) {
+    let mut edges = Vec::with_capacity(elements.len() * HEX_EDGES.len());
+    let mut areas = Vec::with_capacity(elements.len() * 6);
+    for element in elements.iter().filter_map(|&idx| mesh.elements.get(idx)) {
+        let HexElement { node_ids } = element;
+        for &(a, b) in &HEX_EDGES {
+            if let (Some(pa), Some(pb)) = (mesh.nodes.get(node_ids[a]), mesh.nodes.get(node_ids[b]))
+            {
+                edges.push(distance(pa, pb));
+            }
+        }
+        areas.extend(
+            element
+                .faces()
+                .iter()
+                .filter_map(|face| compute_face_area(face, &mesh.nodes).ok()),
+        );
+    }
+    (edges, areas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::types::Point;
+
+    /// Block of unit cubes and block of one 0.5 × 0.5 × 2 brick
+    fn two_block_mesh() -> Mesh {
+        let mut mesh = Mesh::new();
+        let add_hex = |mesh: &mut Mesh, origin: [f64; 3], size: [f64; 3]| {
+            let base = mesh.nodes.len();
+            for k in 0..2 {
+                for (i, j) in [(0, 0), (1, 0), (1, 1), (0, 1)] {
+                    mesh.nodes.push(Point::new(
+                        origin[0] + i as f64 * size[0],
+                        origin[1] + j as f64 * size[1],
+                        origin[2] + k as f64 * size[2],
+                    ));
+                }
+            }
+            mesh.elements
+                .push(HexElement::new(std::array::from_fn(|n| base + n)));
+            mesh.elements.len() - 1
+        };
+        let a = add_hex(&mut mesh, [0.0; 3], [1.0; 3]);
+        let b = add_hex(&mut mesh, [1.0, 0.0, 0.0], [1.0; 3]);
+        let c = add_hex(&mut mesh, [0.0, 0.0, 1.0], [0.5, 0.5, 2.0]);
+        mesh.element_blocks.insert("Cubes".to_string(), vec![a, b]);
+        mesh.element_blocks.insert("Brick".to_string(), vec![c]);
+        mesh
+    }
+
+    #[test]
+    fn test_mesh_stats() {
+        let stats = MeshStats::compute(&two_block_mesh());
+        assert_eq!(stats.blocks.len(), 2);
+
+        let brick = &stats.blocks[0];
+        assert_eq!(brick.name, "Brick");
+        let edges = brick.edge_length.unwrap();
+        assert_eq!(edges.count, 12);
+        assert_eq!((edges.min, edges.median, edges.max), (0.5, 0.5, 2.0));
+        let areas = brick.face_area.unwrap();
+        assert_eq!((areas.min, areas.max), (0.25, 1.0));
+
+        let cubes = &stats.blocks[1];
+        assert_eq!(cubes.edge_length.unwrap().count, 24);
+        assert_eq!(cubes.edge_length.unwrap().mean, 1.0);
+        assert_eq!(stats.edge_length.unwrap().count, 36);
+
+        let tolerances = stats.recommended_tolerances().unwrap();
+        assert_eq!(tolerances.reference_block, "Brick");
+        assert!((tolerances.max_gap - 0.05).abs() < 1e-12);
+        assert!((tolerances.max_penetration - 0.01).abs() < 1e-12);
+
+        assert!(MeshStats::compute(&Mesh::new())
+            .recommended_tolerances()
+            .is_none());
+    }
+}
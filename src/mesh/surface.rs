@@ -1,19 +1,27 @@
 //! Surface extraction ("skinning") from hexahedral mesh
 
 use crate::error::{ContactDetectorError, Result};
+use crate::mesh::edges::{boundary_loops, EdgeLoop};
 use crate::mesh::geometry::{compute_face_area, compute_face_centroid, compute_face_normal};
-use crate::mesh::types::{Mesh, Point, QuadFace, SurfaceMesh};
-use std::collections::HashMap;
+use crate::mesh::precision::Real;
+use crate::mesh::tags::FaceTags;
+use crate::mesh::types::{HexElement, Mesh, QuadFace, SurfaceMesh};
+use nalgebra::Point3;
+use crate::mesh::union_find::UnionFind;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
 /// Extract surface mesh from a volume mesh
-/// Returns one SurfaceMesh per element block (part)
-pub fn extract_surface(mesh: &Mesh) -> Result<Vec<SurfaceMesh>> {
-    log::info!(
+/// Returns one SurfaceMesh per element block (part), in the precision of the
+/// mesh
+#[tracing::instrument(skip_all, fields(blocks = mesh.element_blocks.len()))]
+pub fn extract_surface<T: Real>(mesh: &Mesh<T>) -> Result<Vec<SurfaceMesh<T>>> {
+    tracing::info!(
         "Extracting surface from mesh with {} elements",
-        mesh.num_elements()
+        mesh.elements.len()
     );
 
     // Build face adjacency map
@@ -22,18 +30,18 @@ pub fn extract_surface(mesh: &Mesh) -> Result<Vec<SurfaceMesh>> {
     // Extract boundary faces (faces with exactly 1 adjacent element)
     let boundary_faces = extract_boundary_faces(&face_adjacency);
 
-    log::info!("Found {} boundary faces", boundary_faces.len());
+    tracing::info!("Found {} boundary faces", boundary_faces.len());
 
     // Group faces by element block
     let surfaces = group_by_block(mesh, &boundary_faces, &face_adjacency)?;
 
-    log::info!("Created {} surface meshes", surfaces.len());
+    tracing::info!("Created {} surface meshes", surfaces.len());
 
     Ok(surfaces)
 }
 
 /// Build a map from canonical faces to the elements that contain them
-fn build_face_adjacency(mesh: &Mesh) -> Result<HashMap<QuadFace, Vec<usize>>> {
+fn build_face_adjacency<T: Real>(mesh: &Mesh<T>) -> Result<HashMap<QuadFace, Vec<usize>>> {
     let mut adjacency: HashMap<QuadFace, Vec<usize>> = HashMap::new();
 
     for (elem_idx, element) in mesh.elements.iter().enumerate() {
@@ -69,11 +77,11 @@ fn extract_boundary_faces(
 }
 
 /// Group boundary faces by element block and create SurfaceMesh for each
-fn group_by_block(
-    mesh: &Mesh,
+fn group_by_block<T: Real>(
+    mesh: &Mesh<T>,
     boundary_faces: &HashMap<QuadFace, usize>,
     _face_adjacency: &HashMap<QuadFace, Vec<usize>>,
-) -> Result<Vec<SurfaceMesh>> {
+) -> Result<Vec<SurfaceMesh<T>>> {
     // Create a map from element index to block name
     let mut elem_to_block: HashMap<usize, String> = HashMap::new();
     for (block_name, elem_indices) in &mesh.element_blocks {
@@ -82,9 +90,13 @@ fn group_by_block(
         }
     }
 
-    // Group faces by block
-    let mut block_faces: HashMap<String, Vec<QuadFace>> = HashMap::new();
-    for (face, elem_idx) in boundary_faces {
+    // Group faces by block, visiting faces by element and blocks by name so
+    // that surfaces and their faces come out in the same order on every run
+    // (hash map iteration order differs between processes)
+    let mut sorted_faces: Vec<(&QuadFace, &usize)> = boundary_faces.iter().collect();
+    sorted_faces.sort_unstable_by_key(|&(face, &elem_idx)| (elem_idx, face.node_ids));
+    let mut block_faces: BTreeMap<String, Vec<QuadFace>> = BTreeMap::new();
+    for (face, elem_idx) in sorted_faces {
         let block_name = elem_to_block
             .get(elem_idx)
             .ok_or_else(|| {
@@ -104,7 +116,7 @@ fn group_by_block(
     // Build SurfaceMesh for each block, further subdividing by connectivity and coplanarity
     let mut surfaces = Vec::new();
     for (block_name, faces) in block_faces {
-        log::info!(
+        tracing::info!(
             "Subdividing block '{}' with {} faces into surface patches",
             block_name,
             faces.len()
@@ -113,7 +125,7 @@ fn group_by_block(
         // Subdivide faces into coplanar surface patches
         let surface_patches = subdivide_into_surface_patches(&faces, &mesh.nodes, &block_name)?;
 
-        log::info!(
+        tracing::info!(
             "Block '{}' subdivided into {} surface patches",
             block_name,
             surface_patches.len()
@@ -126,11 +138,11 @@ fn group_by_block(
 }
 
 /// Subdivide a set of boundary faces into surface patches based on connectivity and coplanarity
-fn subdivide_into_surface_patches(
+fn subdivide_into_surface_patches<T: Real>(
     faces: &[QuadFace],
-    nodes: &[Point],
+    nodes: &[Point3<T>],
     block_name: &str,
-) -> Result<Vec<SurfaceMesh>> {
+) -> Result<Vec<SurfaceMesh<T>>> {
     use std::collections::{HashSet, VecDeque};
 
     if faces.is_empty() {
@@ -141,7 +153,7 @@ fn subdivide_into_surface_patches(
     const MAX_COPLANAR_ANGLE: f64 = 10.0;
 
     // Build face adjacency graph (which faces share edges)
-    let face_adjacency = build_boundary_face_adjacency(faces);
+    let face_adjacency = FaceAdjacency::from_faces(faces);
 
     // Compute normals for all faces (needed for coplanarity check)
     let face_normals: Vec<_> = faces
@@ -171,20 +183,18 @@ fn subdivide_into_surface_patches(
             patch_faces.push(faces[current_idx]);
 
             // Check all adjacent faces
-            if let Some(adjacent_indices) = face_adjacency.get(&current_idx) {
-                for &adj_idx in adjacent_indices {
-                    if visited.contains(&adj_idx) {
-                        continue;
-                    }
-
-                    // Check if adjacent face is coplanar with seed
-                    let adj_normal = &face_normals[adj_idx];
-                    let angle = crate::mesh::geometry::angle_between_vectors(seed_normal, adj_normal);
-
-                    if angle <= MAX_COPLANAR_ANGLE {
-                        visited.insert(adj_idx);
-                        queue.push_back(adj_idx);
-                    }
+            for &adj_idx in face_adjacency.neighbors(current_idx) {
+                if visited.contains(&adj_idx) {
+                    continue;
+                }
+
+                // Check if adjacent face is coplanar with seed
+                let adj_normal = &face_normals[adj_idx];
+                let angle = crate::mesh::geometry::angle_between_vectors(seed_normal, adj_normal);
+
+                if angle <= T::cast(MAX_COPLANAR_ANGLE) {
+                    visited.insert(adj_idx);
+                    queue.push_back(adj_idx);
                 }
             }
         }
@@ -198,52 +208,92 @@ fn subdivide_into_surface_patches(
     Ok(surface_patches)
 }
 
-/// Build adjacency graph for boundary faces (which faces share edges)
-fn build_boundary_face_adjacency(faces: &[QuadFace]) -> HashMap<usize, Vec<usize>> {
-    use std::collections::HashMap;
-
-    // Map from edge (as canonical pair of node IDs) to face indices
-    let mut edge_to_faces: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+/// Edge-neighbour graph of the faces of a surface
+///
+/// Two faces are neighbours if they share an edge that belongs to no other
+/// face; edges shared by three or more faces (non-manifold) connect nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FaceAdjacency {
+    neighbors: Vec<Vec<usize>>,
+}
 
-    for (face_idx, face) in faces.iter().enumerate() {
-        // Get all 4 edges of the quad face
-        let edges = [
-            (face.node_ids[0], face.node_ids[1]),
-            (face.node_ids[1], face.node_ids[2]),
-            (face.node_ids[2], face.node_ids[3]),
-            (face.node_ids[3], face.node_ids[0]),
-        ];
+impl FaceAdjacency {
+    /// Build the graph of a list of faces
+    pub fn from_faces(faces: &[QuadFace]) -> Self {
+        // Map from edge (as canonical pair of node IDs) to face indices
+        let mut edge_to_faces: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+
+        for (face_idx, face) in faces.iter().enumerate() {
+            let corners = face.corners();
+            for k in 0..corners.len() {
+                let (n1, n2) = (corners[k], corners[(k + 1) % corners.len()]);
+                // Use canonical form (smaller node first)
+                let edge = if n1 < n2 { (n1, n2) } else { (n2, n1) };
+                edge_to_faces.entry(edge).or_default().push(face_idx);
+            }
+        }
 
-        for (n1, n2) in edges {
-            // Use canonical form (smaller node first)
-            let edge = if n1 < n2 { (n1, n2) } else { (n2, n1) };
-            edge_to_faces.entry(edge).or_default().push(face_idx);
+        let mut neighbors = vec![Vec::new(); faces.len()];
+        for face_indices in edge_to_faces.values() {
+            if let [face_a, face_b] = face_indices[..] {
+                if face_a != face_b {
+                    neighbors[face_a].push(face_b);
+                    neighbors[face_b].push(face_a);
+                }
+            }
         }
+        for list in &mut neighbors {
+            list.sort_unstable();
+            list.dedup();
+        }
+
+        Self { neighbors }
     }
 
-    // Build adjacency map: face_idx -> list of adjacent face indices
-    let mut adjacency: HashMap<usize, Vec<usize>> = HashMap::new();
+    /// Faces sharing an edge with `face_idx`, in ascending order
+    pub fn neighbors(&self, face_idx: usize) -> &[usize] {
+        &self.neighbors[face_idx]
+    }
 
-    for face_indices in edge_to_faces.values() {
-        if face_indices.len() == 2 {
-            // Two faces share this edge - they are adjacent
-            let face_a = face_indices[0];
-            let face_b = face_indices[1];
+    /// Number of faces in the graph
+    pub fn len(&self) -> usize {
+        self.neighbors.len()
+    }
 
-            adjacency.entry(face_a).or_default().push(face_b);
-            adjacency.entry(face_b).or_default().push(face_a);
-        }
+    /// Whether the graph has no faces
+    pub fn is_empty(&self) -> bool {
+        self.neighbors.is_empty()
     }
 
-    adjacency
+    /// Faces with fewer neighbours than edges, i.e. on the open border of the
+    /// surface (or next to a non-manifold edge)
+    pub fn border_faces(&self, faces: &[QuadFace]) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&i| self.neighbors[i].len() < faces[i].corners().len())
+            .collect()
+    }
+
+    /// Edge-connected groups of faces, each in ascending order, ordered by
+    /// their smallest face
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut sets = UnionFind::new(self.len());
+        for (face_idx, list) in self.neighbors.iter().enumerate() {
+            for &other in list {
+                sets.union(face_idx, other);
+            }
+        }
+        sets.components()
+    }
 }
 
 /// Build a SurfaceMesh from faces and nodes
-fn build_surface_mesh(
+///
+/// Fails on degenerate (zero-area) faces.
+pub fn build_surface_mesh<T: Real>(
     part_name: String,
     faces: Vec<QuadFace>,
-    nodes: &[Point],
-) -> Result<SurfaceMesh> {
+    nodes: &[Point3<T>],
+) -> Result<SurfaceMesh<T>> {
     // Threshold for parallelization (below this, overhead isn't worth it)
     const PARALLEL_THRESHOLD: usize = 5000;
 
@@ -306,6 +356,7 @@ fn build_surface_mesh(
         face_centroids,
         face_areas,
         nodes: surface_nodes,
+        face_tags: FaceTags::default(),
     };
 
     Ok(surface)
@@ -336,7 +387,7 @@ pub fn validate_surface_closure(surface: &SurfaceMesh) -> Result<bool> {
     let is_closed = edge_count.values().all(|&count| count == 2);
 
     if !is_closed {
-        log::warn!(
+        tracing::warn!(
             "Surface '{}' is not closed - some edges are not shared by exactly 2 faces",
             surface.part_name
         );
@@ -345,10 +396,238 @@ pub fn validate_surface_closure(surface: &SurfaceMesh) -> Result<bool> {
     Ok(is_closed)
 }
 
+/// Closure of the skin of one element block
+#[derive(Debug, Clone)]
+pub struct BlockClosure {
+    /// Element block name
+    pub block: String,
+
+    /// Number of skin faces of the block
+    pub num_faces: usize,
+
+    /// Whether every skin edge is shared by exactly two skin faces
+    pub closed: bool,
+
+    /// Loops of edges used by only one skin face, longest first: cracks,
+    /// holes and non-conforming (hanging node) interfaces
+    pub holes: Vec<EdgeLoop>,
+}
+
+impl BlockClosure {
+    /// Number of edges used by only one skin face
+    pub fn num_boundary_edges(&self) -> usize {
+        self.holes
+            .iter()
+            .map(|l| l.nodes.len() - usize::from(!l.closed))
+            .sum()
+    }
+}
+
+/// Check whether the skin of each element block is closed
+///
+/// Surfaces are grouped by [`SurfaceMesh::block_name`]; blocks come out in
+/// name order. A closed block may still have edges shared by three or more
+/// faces (non-manifold), which [`validate_surface_closure`] reports as not
+/// closed but which form no hole.
+pub fn check_block_closure(surfaces: &[SurfaceMesh]) -> Result<Vec<BlockClosure>> {
+    let mut blocks: BTreeMap<&str, Vec<&SurfaceMesh>> = BTreeMap::new();
+    for surface in surfaces {
+        blocks.entry(surface.block_name()).or_default().push(surface);
+    }
+
+    blocks
+        .into_iter()
+        .map(|(block, patches)| {
+            let skin = combine_surfaces(block.to_string(), &patches);
+            Ok(BlockClosure {
+                block: block.to_string(),
+                num_faces: skin.num_faces(),
+                closed: validate_surface_closure(&skin)?,
+                holes: boundary_loops(&skin),
+            })
+        })
+        .collect()
+}
+
+/// Build a surface mesh from a named side set
+///
+/// The surface is named after the side set and contains one face per side.
+pub fn extract_sideset_surface(mesh: &Mesh, name: &str) -> Result<SurfaceMesh> {
+    let sides = mesh.side_sets.get(name).ok_or_else(|| {
+        ContactDetectorError::ConfigError(format!("Side set not found: {}", name))
+    })?;
+
+    let faces = sides
+        .iter()
+        .map(|&(elem_idx, side)| {
+            mesh.elements
+                .get(elem_idx)
+                .and_then(|elem| elem.side_face(side))
+                .ok_or_else(|| {
+                    ContactDetectorError::InvalidMeshTopology(format!(
+                        "Side set '{}' references invalid element {} / side {}",
+                        name, elem_idx, side
+                    ))
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    build_surface_mesh(name.to_string(), faces, &mesh.nodes)
+}
+
+/// Lookup from a quad face to the element side it lies on
+///
+/// Building the index visits every face of every element, so build it once
+/// and reuse it when mapping many surfaces back to the same mesh.
+#[derive(Debug, Clone, Default)]
+pub struct ElementSideIndex {
+    sides: HashMap<QuadFace, (usize, u8)>,
+}
+
+impl ElementSideIndex {
+    /// Index all element faces of a mesh
+    pub fn new(mesh: &Mesh) -> Self {
+        let mut sides = HashMap::with_capacity(mesh.num_elements() * 6);
+        for (elem_idx, element) in mesh.elements.iter().enumerate() {
+            for (face_idx, face) in element.faces().iter().enumerate() {
+                if let Some(side) = HexElement::side_of_face_index(face_idx) {
+                    sides.insert(face.canonical(), (elem_idx, side));
+                }
+            }
+        }
+
+        Self { sides }
+    }
+
+    /// Element index and Exodus side number (1-6) of a face, in any node order
+    ///
+    /// Interior faces are shared by two elements; either one may be returned.
+    pub fn get(&self, face: &QuadFace) -> Option<(usize, u8)> {
+        self.sides.get(&face.canonical()).copied()
+    }
+
+    /// Number of distinct faces indexed
+    pub fn len(&self) -> usize {
+        self.sides.len()
+    }
+
+    /// Whether the index contains no faces
+    pub fn is_empty(&self) -> bool {
+        self.sides.is_empty()
+    }
+}
+
+/// Combine several surfaces extracted from the same mesh into one surface
+///
+/// Faces are concatenated in input order; nodes are taken from the first
+/// surface since extracted surfaces share the volume mesh node list.
+pub fn combine_surfaces(name: String, surfaces: &[&SurfaceMesh]) -> SurfaceMesh {
+    let mut combined = SurfaceMesh::new(name);
+    if let Some(first) = surfaces.first() {
+        combined.nodes = first.nodes.clone();
+    }
+
+    for surface in surfaces {
+        combined.face_tags.append(&surface.face_tags, combined.faces.len());
+        combined.faces.extend_from_slice(&surface.faces);
+        combined.face_normals.extend_from_slice(&surface.face_normals);
+        combined.face_centroids.extend_from_slice(&surface.face_centroids);
+        combined.face_areas.extend_from_slice(&surface.face_areas);
+    }
+
+    combined
+}
+
+/// Thresholds a surface patch must meet to take part in contact detection
+///
+/// Tiny sliver patches (one or two faces along a fillet or chamfer) tend to
+/// produce many spurious contact pairs; filtering them keeps reports readable.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PatchFilter {
+    /// Minimum number of faces in a patch
+    #[serde(default)]
+    pub min_faces: usize,
+
+    /// Minimum total patch area
+    #[serde(default)]
+    pub min_area: f64,
+}
+
+impl PatchFilter {
+    /// Create a new patch filter
+    pub fn new(min_faces: usize, min_area: f64) -> Self {
+        Self {
+            min_faces,
+            min_area,
+        }
+    }
+
+    /// Reason the surface fails the filter, or `None` if it passes
+    pub fn rejection_reason(&self, surface: &SurfaceMesh) -> Option<String> {
+        if surface.num_faces() < self.min_faces {
+            Some(format!(
+                "{} faces < minimum {}",
+                surface.num_faces(),
+                self.min_faces
+            ))
+        } else if surface.total_area() < self.min_area {
+            Some(format!(
+                "area {:.6} < minimum {:.6}",
+                surface.total_area(),
+                self.min_area
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// A surface patch excluded from detection by a [`PatchFilter`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilteredPatch {
+    /// Surface (patch) name
+    pub name: String,
+
+    /// Number of faces in the patch
+    pub num_faces: usize,
+
+    /// Total patch area
+    pub area: f64,
+
+    /// Why the patch was excluded
+    pub reason: String,
+}
+
+/// Split surfaces into those passing `filter` and a report of the excluded ones
+pub fn filter_patches(
+    surfaces: Vec<SurfaceMesh>,
+    filter: &PatchFilter,
+) -> (Vec<SurfaceMesh>, Vec<FilteredPatch>) {
+    let mut kept = Vec::with_capacity(surfaces.len());
+    let mut filtered = Vec::new();
+
+    for surface in surfaces {
+        match filter.rejection_reason(&surface) {
+            Some(reason) => {
+                tracing::debug!("Filtering patch '{}': {}", surface.part_name, reason);
+                filtered.push(FilteredPatch {
+                    name: surface.part_name.clone(),
+                    num_faces: surface.num_faces(),
+                    area: surface.total_area(),
+                    reason,
+                });
+            }
+            None => kept.push(surface),
+        }
+    }
+
+    (kept, filtered)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::mesh::types::HexElement;
+    use crate::mesh::types::{HexElement, Point};
 
     fn make_single_hex_mesh() -> Mesh {
         // Create a simple 1x1x1 cube
@@ -375,6 +654,15 @@ mod tests {
             material_ids: vec![1], // Single element with material ID 1
             node_sets: HashMap::new(),
             side_sets: HashMap::new(),
+            side_set_dist_factors: HashMap::new(),
+            side_set_fields: HashMap::new(),
+            node_num_map: Vec::new(),
+            elem_num_map: Vec::new(),
+            node_fields: HashMap::new(),
+            element_fields: HashMap::new(),
+            assemblies: HashMap::new(),
+            units: None,
+            provenance: Default::default(),
         }
     }
 
@@ -398,6 +686,88 @@ mod tests {
         assert_eq!(total_faces, 6);
     }
 
+    #[test]
+    fn test_filter_patches() {
+        let mesh = make_single_hex_mesh();
+        let surfaces = extract_surface(&mesh).unwrap();
+
+        // Every patch of the unit cube has 1 face with area 1.0
+        let (kept, filtered) = filter_patches(surfaces.clone(), &PatchFilter::new(1, 1.0));
+        assert_eq!(kept.len(), 6);
+        assert!(filtered.is_empty());
+
+        let (kept, filtered) = filter_patches(surfaces.clone(), &PatchFilter::new(2, 0.0));
+        assert!(kept.is_empty());
+        assert_eq!(filtered.len(), 6);
+        assert!(filtered[0].reason.contains("faces"));
+
+        let (kept, filtered) = filter_patches(surfaces, &PatchFilter::new(0, 1.5));
+        assert!(kept.is_empty());
+        assert!(filtered[0].reason.contains("area"));
+    }
+
+    #[test]
+    fn test_extract_sideset_surface() {
+        let mut mesh = make_single_hex_mesh();
+        mesh.side_sets
+            .insert("top".to_string(), vec![(0, 6)]);
+        mesh.side_sets.insert("bad".to_string(), vec![(0, 9)]);
+
+        let surface = extract_sideset_surface(&mesh, "top").unwrap();
+        assert_eq!(surface.part_name, "top");
+        assert_eq!(surface.num_faces(), 1);
+        assert!((surface.face_centroids[0].z - 1.0).abs() < 1e-12);
+
+        assert!(extract_sideset_surface(&mesh, "bad").is_err());
+        assert!(extract_sideset_surface(&mesh, "missing").is_err());
+    }
+
+    #[test]
+    fn test_element_side_index() {
+        let mesh = make_single_hex_mesh();
+        let index = ElementSideIndex::new(&mesh);
+        assert_eq!(index.len(), 6);
+
+        // Top face in reversed node order still maps to side 6
+        let top = mesh.elements[0].side_face(6).unwrap();
+        let mut reversed = top.node_ids;
+        reversed.reverse();
+        assert_eq!(index.get(&QuadFace::new(reversed)), Some((0, 6)));
+        assert_eq!(index.get(&QuadFace::new([0, 1, 6, 7])), None);
+    }
+
+    #[test]
+    fn test_combine_surfaces() {
+        let mesh = make_single_hex_mesh();
+        let surfaces = extract_surface(&mesh).unwrap();
+        let refs: Vec<&SurfaceMesh> = surfaces.iter().collect();
+
+        let combined = combine_surfaces("all".to_string(), &refs);
+        assert_eq!(combined.num_faces(), 6);
+        assert!((combined.total_area() - 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_check_block_closure() {
+        let mesh = make_single_hex_mesh();
+        let mut surfaces = extract_surface(&mesh).unwrap();
+        let closure = check_block_closure(&surfaces).unwrap();
+        assert_eq!(closure.len(), 1);
+        assert_eq!(closure[0].block, "Block1");
+        assert_eq!(closure[0].num_faces, 6);
+        assert!(closure[0].closed);
+        assert!(closure[0].holes.is_empty());
+
+        // Removing the top face leaves one square hole
+        surfaces.retain(|s| s.face_centroids[0].z < 0.9);
+        let closure = check_block_closure(&surfaces).unwrap();
+        assert!(!closure[0].closed);
+        assert_eq!(closure[0].holes.len(), 1);
+        assert!(closure[0].holes[0].closed);
+        assert_eq!(closure[0].num_boundary_edges(), 4);
+        assert!((closure[0].holes[0].length - 4.0).abs() < 1e-12);
+    }
+
     #[test]
     fn test_face_adjacency() {
         let mesh = make_single_hex_mesh();
@@ -405,11 +775,32 @@ mod tests {
 
         // Single hex has 6 unique faces, each with 1 adjacent element
         assert_eq!(adjacency.len(), 6);
-        for (_, elements) in &adjacency {
+        for elements in adjacency.values() {
             assert_eq!(elements.len(), 1);
         }
     }
 
+    #[test]
+    fn test_surface_face_adjacency() {
+        let mesh = make_single_hex_mesh();
+        let faces = mesh.elements[0].faces();
+
+        // Closed box: every face borders four others
+        let adjacency = FaceAdjacency::from_faces(&faces);
+        assert_eq!(adjacency.len(), 6);
+        assert!((0..6).all(|i| adjacency.neighbors(i).len() == 4));
+        assert!(adjacency.border_faces(&faces).is_empty());
+        assert_eq!(adjacency.components().len(), 1);
+
+        // Two opposite faces share no edge
+        let mut surface = SurfaceMesh::new("Open".to_string());
+        surface.faces = vec![faces[0], faces[1]];
+        let adjacency = surface.face_adjacency();
+        assert!(adjacency.neighbors(0).is_empty());
+        assert_eq!(adjacency.border_faces(&surface.faces), vec![0, 1]);
+        assert_eq!(adjacency.components(), vec![vec![0], vec![1]]);
+    }
+
     #[test]
     fn test_boundary_faces() {
         let mesh = make_single_hex_mesh();
@@ -453,6 +844,15 @@ mod tests {
             material_ids: vec![1, 1], // Two elements with material ID 1
             node_sets: HashMap::new(),
             side_sets: HashMap::new(),
+            side_set_dist_factors: HashMap::new(),
+            side_set_fields: HashMap::new(),
+            node_num_map: Vec::new(),
+            elem_num_map: Vec::new(),
+            node_fields: HashMap::new(),
+            element_fields: HashMap::new(),
+            assemblies: HashMap::new(),
+            units: None,
+            provenance: Default::default(),
         };
 
         let adjacency = build_face_adjacency(&mesh).unwrap();
@@ -0,0 +1,283 @@
+//! User-defined per-face tags
+//!
+//! A [`SurfaceMesh`](crate::mesh::SurfaceMesh) carries named tag layers in
+//! [`FaceTags`], each holding an optional integer or text value per face, so
+//! that regions can be annotated programmatically (`coating = "coated"`,
+//! `zone = 3`). Tags follow the faces through [`SurfaceMesh::subset`],
+//! region clipping, surface combination and precision conversion; decimated
+//! preview copies carry none. Detection never pairs a face matching one of
+//! [`ContactCriteria::excluded_tags`], and contact surface outputs write each
+//! layer as a `tag_<name>` cell array.
+//!
+//! [`SurfaceMesh::subset`]: crate::mesh::SurfaceMesh::subset
+//! [`ContactCriteria::excluded_tags`]: crate::contact::ContactCriteria::excluded_tags
+
+use crate::error::{ContactDetectorError, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+/// Value of a face tag
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TagValue {
+    Int(i64),
+    Text(String),
+}
+
+impl TagValue {
+    /// An integer if `text` is one, text otherwise
+    pub fn parse(text: &str) -> Self {
+        text.parse()
+            .map(TagValue::Int)
+            .unwrap_or_else(|_| TagValue::Text(text.to_string()))
+    }
+}
+
+impl fmt::Display for TagValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagValue::Int(value) => write!(f, "{}", value),
+            TagValue::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<i64> for TagValue {
+    fn from(value: i64) -> Self {
+        TagValue::Int(value)
+    }
+}
+
+impl From<&str> for TagValue {
+    fn from(value: &str) -> Self {
+        TagValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for TagValue {
+    fn from(value: String) -> Self {
+        TagValue::Text(value)
+    }
+}
+
+/// Named tag layers of the faces of one surface
+///
+/// Layers are stored sparsely: faces past the end of a layer are untagged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FaceTags {
+    layers: BTreeMap<String, Vec<Option<TagValue>>>,
+}
+
+impl FaceTags {
+    /// Create an empty set of tags
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether no face is tagged
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Names of the tag layers, in order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.layers.keys().map(String::as_str)
+    }
+
+    /// Tag `face` with `value` in the layer `name`
+    pub fn set(&mut self, name: &str, face: usize, value: impl Into<TagValue>) {
+        let layer = self.layers.entry(name.to_string()).or_default();
+        if layer.len() <= face {
+            layer.resize(face + 1, None);
+        }
+        layer[face] = Some(value.into());
+    }
+
+    /// Tag of `face` in the layer `name`
+    pub fn get(&self, name: &str, face: usize) -> Option<&TagValue> {
+        self.layers.get(name)?.get(face)?.as_ref()
+    }
+
+    /// Faces tagged `value` in the layer `name`
+    pub fn faces_with(&self, name: &str, value: &TagValue) -> Vec<usize> {
+        self.layers.get(name).map_or_else(Vec::new, |layer| {
+            (0..layer.len())
+                .filter(|&face| layer[face].as_ref() == Some(value))
+                .collect()
+        })
+    }
+
+    /// Tags of the given faces, renumbered in the given order
+    pub fn subset(&self, faces: &[usize]) -> Self {
+        let mut subset = Self::new();
+        for (name, layer) in &self.layers {
+            for (new_face, &face) in faces.iter().enumerate() {
+                if let Some(Some(value)) = layer.get(face) {
+                    subset.set(name, new_face, value.clone());
+                }
+            }
+        }
+        subset
+    }
+
+    /// Append the tags of another surface whose faces follow the first
+    /// `offset` faces of this one
+    pub fn append(&mut self, other: &FaceTags, offset: usize) {
+        for (name, values) in &other.layers {
+            for (face, value) in values.iter().enumerate() {
+                if let Some(value) = value {
+                    self.set(name, offset + face, value.clone());
+                }
+            }
+        }
+    }
+
+    /// Distinct text values of the layer `name`, sorted
+    pub fn labels(&self, name: &str) -> Vec<&str> {
+        let labels: BTreeSet<&str> = self
+            .layers
+            .get(name)
+            .into_iter()
+            .flatten()
+            .filter_map(|value| match value {
+                Some(TagValue::Text(text)) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        labels.into_iter().collect()
+    }
+
+    /// One number per face of the layer `name`, for outputs that only hold
+    /// numbers: integer tags as they are, text tags as their index in
+    /// [`FaceTags::labels`], and NaN on untagged faces
+    pub fn values(&self, name: &str, num_faces: usize) -> Vec<f64> {
+        let labels = self.labels(name);
+        (0..num_faces)
+            .map(|face| match self.get(name, face) {
+                Some(TagValue::Int(value)) => *value as f64,
+                Some(TagValue::Text(text)) => labels
+                    .binary_search(&text.as_str())
+                    .map_or(f64::NAN, |index| index as f64),
+                None => f64::NAN,
+            })
+            .collect()
+    }
+}
+
+/// Selects the faces with a tag: any value of the layer (`coating`) or one
+/// value (`coating=coated`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TagFilter {
+    /// Tag layer name
+    pub name: String,
+
+    /// Required value, any if none
+    pub value: Option<TagValue>,
+}
+
+impl TagFilter {
+    /// Whether `face` of the tags is selected
+    pub fn matches(&self, tags: &FaceTags, face: usize) -> bool {
+        match (tags.get(&self.name, face), &self.value) {
+            (Some(tag), Some(value)) => tag == value,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+impl fmt::Display for TagFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "{}={}", self.name, value),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+impl FromStr for TagFilter {
+    type Err = ContactDetectorError;
+
+    /// Parse `name` or `name=value`
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, value) = match s.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(TagValue::parse(value.trim()))),
+            None => (s.trim(), None),
+        };
+        if name.is_empty() {
+            return Err(ContactDetectorError::ConfigError(format!(
+                "Invalid tag filter '{}'. Expected format: 'name' or 'name=value'",
+                s
+            )));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            value,
+        })
+    }
+}
+
+impl Serialize for TagFilter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TagFilter {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_face_tags() {
+        let mut tags = FaceTags::new();
+        tags.set("coating", 1, "coated");
+        tags.set("coating", 3, "bare");
+        tags.set("zone", 0, 7);
+        assert_eq!(tags.names().collect::<Vec<_>>(), ["coating", "zone"]);
+        assert_eq!(tags.get("coating", 1), Some(&TagValue::from("coated")));
+        assert_eq!(tags.get("coating", 2), None);
+        assert_eq!(tags.get("coating", 10), None);
+        assert_eq!(tags.faces_with("zone", &TagValue::Int(7)), [0]);
+
+        // Renumbered with the faces; layers left empty are dropped
+        let subset = tags.subset(&[3, 1]);
+        assert_eq!(subset.get("coating", 0), Some(&TagValue::from("bare")));
+        assert_eq!(subset.get("coating", 1), Some(&TagValue::from("coated")));
+        assert_eq!(subset.names().count(), 1);
+
+        let mut combined = subset.clone();
+        combined.append(&tags, 2);
+        assert_eq!(combined.get("zone", 2), Some(&TagValue::Int(7)));
+        assert_eq!(combined.get("coating", 5), Some(&TagValue::from("bare")));
+
+        // Text tags are written as indices into the sorted labels
+        assert_eq!(tags.labels("coating"), ["bare", "coated"]);
+        let values = tags.values("coating", 4);
+        assert!(values[0].is_nan());
+        assert_eq!((values[1], values[3]), (1.0, 0.0));
+        assert_eq!(tags.values("zone", 1), [7.0]);
+
+        let filter: TagFilter = "coating=coated".parse().unwrap();
+        assert!(filter.matches(&tags, 1));
+        assert!(!filter.matches(&tags, 3));
+        let any: TagFilter = "coating".parse().unwrap();
+        assert!(any.matches(&tags, 3) && !any.matches(&tags, 0));
+        let zone: TagFilter = "zone = 7".parse().unwrap();
+        assert_eq!(zone.value, Some(TagValue::Int(7)));
+        assert!(zone.matches(&tags, 0));
+        assert!("=coated".parse::<TagFilter>().is_err());
+
+        let json = serde_json::to_string(&filter).unwrap();
+        assert_eq!(json, "\"coating=coated\"");
+        assert_eq!(serde_json::from_str::<TagFilter>(&json).unwrap(), filter);
+    }
+}
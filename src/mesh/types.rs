@@ -1,6 +1,9 @@
 //! Core mesh data structures
 
-use nalgebra::{Point3, Vector3};
+use crate::mesh::hierarchy::BlockAssembly;
+use crate::mesh::tags::FaceTags;
+use nalgebra::{Point3, Scalar, Vector3};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// 3D point type
@@ -19,6 +22,9 @@ pub struct HexElement {
     pub node_ids: [usize; 8],
 }
 
+/// Index into [`HexElement::faces`] for each Exodus II HEX8 side number (1-6)
+const EXODUS_SIDE_TO_FACE: [usize; 6] = [2, 3, 4, 5, 0, 1];
+
 impl HexElement {
     /// Create a new hex element
     pub fn new(node_ids: [usize; 8]) -> Self {
@@ -39,9 +45,29 @@ impl HexElement {
             QuadFace::new([n[3], n[0], n[4], n[7]]), // left (x-)
         ]
     }
+
+    /// Face for an Exodus II side number (1-6), or `None` if out of range
+    ///
+    /// Side sets in [`Mesh::side_sets`] use Exodus side numbering, which
+    /// differs from the order returned by [`HexElement::faces`].
+    pub fn side_face(&self, side: u8) -> Option<QuadFace> {
+        let face_idx = *EXODUS_SIDE_TO_FACE.get((side as usize).checked_sub(1)?)?;
+        Some(self.faces()[face_idx])
+    }
+
+    /// Exodus II side number (1-6) of the face at `face_idx` in [`HexElement::faces`]
+    pub fn side_of_face_index(face_idx: usize) -> Option<u8> {
+        EXODUS_SIDE_TO_FACE
+            .iter()
+            .position(|&f| f == face_idx)
+            .map(|side| side as u8 + 1)
+    }
 }
 
 /// Quadrilateral face with 4 nodes
+///
+/// Triangles from imported surface meshes are stored as degenerate quads
+/// whose last node repeats the third (see [`QuadFace::triangle`]).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct QuadFace {
     /// Node IDs in counter-clockwise order
@@ -54,6 +80,27 @@ impl QuadFace {
         Self { node_ids }
     }
 
+    /// Create a triangle face (a quad with the third node repeated)
+    pub fn triangle(node_ids: [usize; 3]) -> Self {
+        Self {
+            node_ids: [node_ids[0], node_ids[1], node_ids[2], node_ids[2]],
+        }
+    }
+
+    /// Whether this face is a triangle stored as a degenerate quad
+    pub fn is_triangle(&self) -> bool {
+        self.node_ids[2] == self.node_ids[3]
+    }
+
+    /// The distinct corner nodes: three for a triangle, otherwise four
+    pub fn corners(&self) -> &[usize] {
+        if self.is_triangle() {
+            &self.node_ids[..3]
+        } else {
+            &self.node_ids
+        }
+    }
+
     /// Get canonical form for hashing (smallest rotation)
     /// This ensures two faces with the same nodes in different orders hash equally
     pub fn canonical(&self) -> Self {
@@ -82,10 +129,13 @@ impl QuadFace {
 }
 
 /// Complete mesh representation
+///
+/// Node coordinates are stored as `T`: `f64` by default, `f32` to save memory
+/// (see [`crate::mesh::precision`]).
 #[derive(Debug, Clone)]
-pub struct Mesh {
+pub struct Mesh<T: Scalar = f64> {
     /// All nodes in the mesh
-    pub nodes: Vec<Point>,
+    pub nodes: Vec<Point3<T>>,
 
     /// All hexahedral elements
     pub elements: Vec<HexElement>,
@@ -103,8 +153,100 @@ pub struct Mesh {
     pub node_sets: HashMap<String, Vec<usize>>,
 
     /// Side sets (named groups of element faces)
-    /// Maps sideset name -> (element index, local face id)
+    /// Maps sideset name -> (element index, Exodus side number 1-6)
     pub side_sets: HashMap<String, Vec<(usize, u8)>>,
+
+    /// Side set distribution factors (optional)
+    /// Maps sideset name -> one factor per face node (4 per side, in side order)
+    pub side_set_dist_factors: HashMap<String, Vec<f64>>,
+
+    /// Side set variables (optional), kept by the Exodus and binary formats
+    /// Maps sideset name -> variable name -> one value per side
+    pub side_set_fields: HashMap<String, HashMap<String, Vec<f64>>>,
+
+    /// Global node IDs from the source model (optional)
+    /// If present, must have same length as nodes; otherwise IDs are index + 1
+    pub node_num_map: Vec<i32>,
+
+    /// Global element IDs from the source model (optional)
+    /// If present, must have same length as elements; otherwise IDs are index + 1
+    pub elem_num_map: Vec<i32>,
+
+    /// Nodal fields (optional)
+    /// Maps field name -> one value per node
+    pub node_fields: HashMap<String, Vec<f64>>,
+
+    /// Element fields (optional)
+    /// Maps field name -> one value per element
+    pub element_fields: HashMap<String, Vec<f64>>,
+
+    /// Named groups of blocks and nested groups (optional), e.g. from Exodus
+    /// assembly records
+    /// Maps assembly name -> direct members
+    pub assemblies: HashMap<String, BlockAssembly>,
+
+    /// Length unit of the coordinates (optional), e.g. "mm"
+    pub units: Option<String>,
+
+    /// Where the mesh came from
+    pub provenance: MeshProvenance,
+}
+
+/// Origin of a mesh, carried through intermediate file formats
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MeshProvenance {
+    /// Path of the file the mesh was originally read from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Format of the original file ("exodus", "json", ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+
+    /// Processing steps applied since reading, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<String>,
+
+    /// Element blocks of the original file and whether each was read, in
+    /// file order (empty if the reader keeps every block)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocks: Vec<BlockReadRecord>,
+}
+
+/// Whether an element block of a source file made it into the mesh
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockReadStatus {
+    Read,
+    Skipped,
+}
+
+/// Outcome of reading one element block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockReadRecord {
+    /// Block name (as in the mesh if it was read)
+    pub name: String,
+
+    /// Element type as stored in the file, e.g. `HEX8` or `TETRA4`
+    pub element_type: String,
+
+    /// Number of elements in the block
+    pub num_elements: usize,
+
+    pub status: BlockReadStatus,
+
+    /// Why the block was skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl MeshProvenance {
+    /// Element blocks that were not read, and so are invisible to detection
+    pub fn skipped_blocks(&self) -> impl Iterator<Item = &BlockReadRecord> {
+        self.blocks
+            .iter()
+            .filter(|block| block.status == BlockReadStatus::Skipped)
+    }
 }
 
 impl Mesh {
@@ -117,6 +259,15 @@ impl Mesh {
             material_ids: Vec::new(),
             node_sets: HashMap::new(),
             side_sets: HashMap::new(),
+            side_set_dist_factors: HashMap::new(),
+            side_set_fields: HashMap::new(),
+            node_num_map: Vec::new(),
+            elem_num_map: Vec::new(),
+            node_fields: HashMap::new(),
+            element_fields: HashMap::new(),
+            assemblies: HashMap::new(),
+            units: None,
+            provenance: MeshProvenance::default(),
         }
     }
 
@@ -135,6 +286,22 @@ impl Mesh {
         self.element_blocks.len()
     }
 
+    /// Global (solver) ID of a node, falling back to the 1-based index
+    pub fn global_node_id(&self, node_idx: usize) -> i32 {
+        self.node_num_map
+            .get(node_idx)
+            .copied()
+            .unwrap_or(node_idx as i32 + 1)
+    }
+
+    /// Global (solver) ID of an element, falling back to the 1-based index
+    pub fn global_elem_id(&self, elem_idx: usize) -> i32 {
+        self.elem_num_map
+            .get(elem_idx)
+            .copied()
+            .unwrap_or(elem_idx as i32 + 1)
+    }
+
     /// Get elements in a specific block
     pub fn get_block(&self, name: &str) -> Option<Vec<&HexElement>> {
         self.element_blocks
@@ -150,8 +317,11 @@ impl Default for Mesh {
 }
 
 /// Surface mesh (extracted from volume mesh)
+///
+/// Coordinates and face data are stored as `T`: `f64` by default, `f32` to
+/// save memory (see [`crate::mesh::precision`]).
 #[derive(Debug, Clone)]
-pub struct SurfaceMesh {
+pub struct SurfaceMesh<T: Scalar = f64> {
     /// Part/block name this surface belongs to
     pub part_name: String,
 
@@ -159,16 +329,19 @@ pub struct SurfaceMesh {
     pub faces: Vec<QuadFace>,
 
     /// Face normals (outward pointing)
-    pub face_normals: Vec<Vec3>,
+    pub face_normals: Vec<Vector3<T>>,
 
     /// Face centroids
-    pub face_centroids: Vec<Point>,
+    pub face_centroids: Vec<Point3<T>>,
 
     /// Face areas
-    pub face_areas: Vec<f64>,
+    pub face_areas: Vec<T>,
 
     /// Reference to original nodes (shared with volume mesh)
-    pub nodes: Vec<Point>,
+    pub nodes: Vec<Point3<T>>,
+
+    /// User-defined tags of the faces (see [`crate::mesh::tags`])
+    pub face_tags: FaceTags,
 }
 
 impl SurfaceMesh {
@@ -181,6 +354,7 @@ impl SurfaceMesh {
             face_centroids: Vec::new(),
             face_areas: Vec::new(),
             nodes: Vec::new(),
+            face_tags: FaceTags::default(),
         }
     }
 
@@ -193,6 +367,46 @@ impl SurfaceMesh {
     pub fn total_area(&self) -> f64 {
         self.face_areas.iter().sum()
     }
+
+    /// Edge-neighbour graph of the faces
+    ///
+    /// Built on each call; keep the result when it is needed more than once.
+    pub fn face_adjacency(&self) -> crate::mesh::surface::FaceAdjacency {
+        crate::mesh::surface::FaceAdjacency::from_faces(&self.faces)
+    }
+
+    /// Element block this surface was extracted from
+    ///
+    /// Surface patches are named "<block>:patch_<n>"; this returns "<block>".
+    pub fn block_name(&self) -> &str {
+        self.part_name.split(':').next().unwrap_or(&self.part_name)
+    }
+
+    /// Coarse copy of this surface with at most about `target_faces` faces
+    ///
+    /// Neighbouring faces are merged by vertex clustering (see
+    /// [`crate::mesh::decimate`]) for quick preview runs. The result has its
+    /// own node list of cluster points rather than the volume mesh's nodes.
+    /// Surfaces already within the target are returned unchanged.
+    pub fn decimate(&self, target_faces: usize) -> SurfaceMesh {
+        crate::mesh::decimate::decimate_surface(self, target_faces)
+    }
+
+    /// Copy of this surface containing only the given faces, in the given order
+    pub fn subset(&self, face_indices: &[usize]) -> SurfaceMesh {
+        SurfaceMesh {
+            part_name: self.part_name.clone(),
+            faces: face_indices.iter().map(|&i| self.faces[i]).collect(),
+            face_normals: face_indices.iter().map(|&i| self.face_normals[i]).collect(),
+            face_centroids: face_indices
+                .iter()
+                .map(|&i| self.face_centroids[i])
+                .collect(),
+            face_areas: face_indices.iter().map(|&i| self.face_areas[i]).collect(),
+            nodes: self.nodes.clone(),
+            face_tags: self.face_tags.subset(face_indices),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -209,6 +423,25 @@ mod tests {
         assert_eq!(faces[1].node_ids, [4, 5, 6, 7]); // top
     }
 
+    #[test]
+    fn test_exodus_side_numbering() {
+        let hex = HexElement::new([0, 1, 2, 3, 4, 5, 6, 7]);
+
+        // Side 1 is the y- face (nodes 1,2,6,5 in 1-based Exodus numbering)
+        assert_eq!(hex.side_face(1).unwrap().node_ids, [0, 1, 5, 4]);
+        // Sides 5 and 6 are bottom and top
+        assert_eq!(hex.side_face(5).unwrap().node_ids, [0, 3, 2, 1]);
+        assert_eq!(hex.side_face(6).unwrap().node_ids, [4, 5, 6, 7]);
+        assert!(hex.side_face(0).is_none());
+        assert!(hex.side_face(7).is_none());
+
+        for side in 1..=6u8 {
+            let face = hex.side_face(side).unwrap();
+            let face_idx = hex.faces().iter().position(|f| *f == face).unwrap();
+            assert_eq!(HexElement::side_of_face_index(face_idx), Some(side));
+        }
+    }
+
     #[test]
     fn test_quad_canonical() {
         let face1 = QuadFace::new([1, 2, 3, 4]);
@@ -219,6 +452,14 @@ mod tests {
         // Note: face3 reversed should also match after canonicalization
     }
 
+    #[test]
+    fn test_triangle_face() {
+        let tri = QuadFace::triangle([4, 5, 6]);
+        assert!(tri.is_triangle());
+        assert_eq!(tri.corners(), &[4, 5, 6]);
+        assert!(!QuadFace::new([1, 2, 3, 4]).is_triangle());
+    }
+
     #[test]
     fn test_mesh_creation() {
         let mut mesh = Mesh::new();
@@ -228,4 +469,43 @@ mod tests {
         mesh.nodes.push(Point::new(0.0, 0.0, 0.0));
         assert_eq!(mesh.num_nodes(), 1);
     }
+
+    #[test]
+    fn test_global_ids() {
+        let mut mesh = Mesh::new();
+        assert_eq!(mesh.global_node_id(0), 1);
+        assert_eq!(mesh.global_elem_id(4), 5);
+
+        mesh.node_num_map = vec![100, 200];
+        mesh.elem_num_map = vec![7];
+        assert_eq!(mesh.global_node_id(1), 200);
+        assert_eq!(mesh.global_elem_id(0), 7);
+    }
+
+    #[test]
+    fn test_skipped_blocks() {
+        let record = |name: &str, status, reason: Option<&str>| BlockReadRecord {
+            name: name.to_string(),
+            element_type: if reason.is_some() { "TETRA4" } else { "HEX8" }.to_string(),
+            num_elements: 4,
+            status,
+            reason: reason.map(String::from),
+        };
+        let mut provenance = MeshProvenance::default();
+        assert_eq!(provenance.skipped_blocks().count(), 0);
+
+        provenance.blocks = vec![
+            record("Housing", BlockReadStatus::Read, None),
+            record("Seal", BlockReadStatus::Skipped, Some("unsupported")),
+            record("Cover", BlockReadStatus::Read, None),
+        ];
+        let skipped: Vec<_> = provenance.skipped_blocks().map(|b| b.name.as_str()).collect();
+        assert_eq!(skipped, vec!["Seal"]);
+
+        // Statuses are written in lowercase and read records are kept
+        let json = serde_json::to_string(&provenance).unwrap();
+        assert!(json.contains(r#""status":"skipped""#));
+        let parsed: MeshProvenance = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, provenance);
+    }
 }
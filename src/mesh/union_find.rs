@@ -0,0 +1,141 @@
+//! Union-find (disjoint set) component labeling
+//!
+//! Splitting blocks into bodies and merging detected contact pairs both need
+//! the connected components of a graph. Union-find with path halving and
+//! union by size runs in near-linear time without building adjacency lists.
+//! Connections through shared keys (e.g. faces shared by elements) are found
+//! by sorting the keys, which runs in parallel with the `parallel` feature.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Disjoint sets over the items `0..len`
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    /// Every item in its own set
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        }
+    }
+
+    /// Number of items
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Whether there are no items
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Representative of the set containing `item`
+    pub fn find(&mut self, mut item: usize) -> usize {
+        while self.parent[item] != item {
+            self.parent[item] = self.parent[self.parent[item]];
+            item = self.parent[item];
+        }
+        item
+    }
+
+    /// Merge the sets containing `a` and `b`; returns false if they already
+    /// were the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut ra, mut rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return false;
+        }
+        if self.size[ra] < self.size[rb] {
+            std::mem::swap(&mut ra, &mut rb);
+        }
+        self.parent[rb] = ra;
+        self.size[ra] += self.size[rb];
+        true
+    }
+
+    /// Merge all items that share a key
+    ///
+    /// `keyed` holds `(key, item)` entries, any number per item.
+    pub fn union_by_key<K: Ord + Send>(&mut self, mut keyed: Vec<(K, usize)>) {
+        // Threshold for parallelization (below this, overhead isn't worth it)
+        const PARALLEL_THRESHOLD: usize = 50_000;
+
+        #[cfg(feature = "parallel")]
+        {
+            if keyed.len() >= PARALLEL_THRESHOLD {
+                keyed.par_sort_unstable_by(|a, b| a.0.cmp(&b.0));
+            } else {
+                keyed.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+            }
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        keyed.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        for run in keyed.chunk_by(|a, b| a.0 == b.0) {
+            for entry in &run[1..] {
+                self.union(run[0].1, entry.1);
+            }
+        }
+    }
+
+    /// Component label of every item, numbered from 0 in order of each
+    /// component's smallest item
+    pub fn labels(&mut self) -> Vec<usize> {
+        let mut root_label = vec![usize::MAX; self.len()];
+        let mut next = 0;
+        (0..self.len())
+            .map(|item| {
+                let root = self.find(item);
+                if root_label[root] == usize::MAX {
+                    root_label[root] = next;
+                    next += 1;
+                }
+                root_label[root]
+            })
+            .collect()
+    }
+
+    /// Items of each component in ascending order, components ordered by
+    /// their smallest item
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        for (item, label) in self.labels().into_iter().enumerate() {
+            if label == components.len() {
+                components.push(Vec::new());
+            }
+            components[label].push(item);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_and_components() {
+        let mut sets = UnionFind::new(6);
+        assert!(sets.union(4, 1));
+        assert!(sets.union(5, 3));
+        assert!(sets.union(1, 5));
+        assert!(!sets.union(3, 4));
+
+        assert_eq!(sets.labels(), vec![0, 1, 2, 1, 1, 1]);
+        assert_eq!(sets.components(), vec![vec![0], vec![1, 3, 4, 5], vec![2]]);
+    }
+
+    #[test]
+    fn test_union_by_key() {
+        let mut sets = UnionFind::new(4);
+        sets.union_by_key(vec![("x", 0), ("y", 3), ("z", 1), ("x", 2), ("w", 3)]);
+        assert_eq!(sets.components(), vec![vec![0, 2], vec![1], vec![3]]);
+    }
+}
@@ -0,0 +1,153 @@
+//! Non-fatal warning collection
+//!
+//! Readers and exporters that skip or downgrade problematic data (non-hex blocks,
+//! faces that can't be mapped back to elements, invalid side IDs, ...) record a
+//! [`Warning`] in a [`Warnings`] accumulator instead of only writing to the log.
+//! The accumulator is returned alongside the main result so that callers and
+//! scripts can react to dropped data programmatically.
+
+use serde::{Deserialize, Serialize};
+
+/// Category of a non-fatal problem encountered while processing data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningKind {
+    /// An element block was not read (e.g. non-hexahedral elements)
+    SkippedBlock,
+
+    /// A node set was not read because its data was invalid
+    SkippedNodeSet,
+
+    /// A side set was not read because its data was invalid
+    SkippedSideSet,
+
+    /// A surface face could not be mapped back to a volume element
+    UnmappedFace,
+
+    /// A side set entry referenced a face ID outside the valid range
+    InvalidSide,
+
+    /// A side set was not written because it had no faces
+    EmptySideSet,
+
+    /// Side set distribution factors were dropped because they were invalid
+    SkippedDistFactors,
+
+    /// Side set variables were dropped because they were invalid
+    SkippedSideSetVariable,
+
+    /// An exported side set name was truncated or changed to avoid a collision
+    RenamedSideSet,
+
+    /// A name read from a file was not valid UTF-8 and was sanitized
+    InvalidName,
+
+    /// An assembly record was not read because it groups entities other
+    /// than element blocks and assemblies
+    SkippedAssembly,
+
+    /// A block or set of an assembly definition selected nothing
+    EmptyAssemblySet,
+
+    /// Parts not on the allowed contact list were found in contact
+    UnexpectedContact,
+
+    /// A part has no interface to any other part
+    FloatingPart,
+
+    /// The parts split into groups that do not touch each other
+    DisconnectedAssembly,
+}
+
+/// A single non-fatal problem
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Warning {
+    /// Category of the problem
+    pub kind: WarningKind,
+
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Accumulator for non-fatal warnings
+///
+/// Every pushed warning is also forwarded to `tracing::warn!`, so console users see
+/// the same messages as before while library users can inspect them afterwards.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Warnings {
+    entries: Vec<Warning>,
+}
+
+impl Warnings {
+    /// Create an empty warning accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a warning (also emitted to the log)
+    pub fn push(&mut self, kind: WarningKind, message: impl Into<String>) {
+        let message = message.into();
+        tracing::warn!("{}", message);
+        self.entries.push(Warning { kind, message });
+    }
+
+    /// Append all warnings from another accumulator
+    pub fn extend(&mut self, other: Warnings) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Number of recorded warnings
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no warnings were recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over all recorded warnings
+    pub fn iter(&self) -> impl Iterator<Item = &Warning> {
+        self.entries.iter()
+    }
+
+    /// Iterate over warnings of a single kind
+    pub fn of_kind(&self, kind: WarningKind) -> impl Iterator<Item = &Warning> {
+        self.entries.iter().filter(move |w| w.kind == kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_filter() {
+        let mut warnings = Warnings::new();
+        assert!(warnings.is_empty());
+
+        warnings.push(WarningKind::SkippedBlock, "Skipping block 2");
+        warnings.push(WarningKind::UnmappedFace, "Face not found");
+        warnings.push(WarningKind::SkippedBlock, "Skipping block 3");
+
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(warnings.of_kind(WarningKind::SkippedBlock).count(), 2);
+        assert_eq!(warnings.of_kind(WarningKind::InvalidSide).count(), 0);
+    }
+
+    #[test]
+    fn test_serializes_as_list() {
+        let mut warnings = Warnings::new();
+        warnings.push(WarningKind::EmptySideSet, "Skipping empty sideset 'a'");
+
+        let json = serde_json::to_string(&warnings).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"kind":"empty_side_set","message":"Skipping empty sideset 'a'"}]"#
+        );
+
+        let parsed: Warnings = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, warnings);
+    }
+}
@@ -296,7 +296,7 @@ fn test_error_handling_invalid_block() {
 
 #[test]
 fn test_multiblock_export() {
-    use contact_detector::io::MultiBlockBuilder;
+    use contact_detector::io::{MultiBlockBuilder, VtkVersion};
     use std::fs;
     use tempfile::TempDir;
 
@@ -307,7 +307,7 @@ fn test_multiblock_export() {
     let output_dir = temp_dir.path();
 
     // Create multi-block builder
-    let mut mb_builder = MultiBlockBuilder::new(output_dir, "test_mesh".to_string(), (2, 2));
+    let mut mb_builder = MultiBlockBuilder::new(output_dir, "test_mesh".to_string(), VtkVersion::V2_2);
 
     // Add volume mesh
     let result = mb_builder.add_volume_mesh(&mesh);
@@ -333,7 +333,7 @@ fn test_multiblock_export() {
 
 #[test]
 fn test_multiblock_with_contact_pairs() {
-    use contact_detector::io::MultiBlockBuilder;
+    use contact_detector::io::{MultiBlockBuilder, VtkVersion};
     use tempfile::TempDir;
 
     let mesh = create_two_block_mesh();
@@ -349,7 +349,7 @@ fn test_multiblock_with_contact_pairs() {
     let output_dir = temp_dir.path();
 
     // Create multi-block builder
-    let mut mb_builder = MultiBlockBuilder::new(output_dir, "test_contact".to_string(), (2, 2));
+    let mut mb_builder = MultiBlockBuilder::new(output_dir, "test_contact".to_string(), VtkVersion::V2_2);
 
     // Add contact pairs
     let contact_data = vec![(
@@ -376,3 +376,217 @@ fn test_multiblock_with_contact_pairs() {
     let vtm_content = std::fs::read_to_string(&vtm_path).expect("Failed to read VTM file");
     assert!(vtm_content.contains("ContactPairs"), "VTM should contain ContactPairs block");
 }
+
+/// Two stacked slabs of nx × ny hexahedra with a small gap between them
+fn create_stacked_slabs(nx: usize, ny: usize) -> Mesh {
+    let mut mesh = Mesh::new();
+    for (block, z0) in [("Lower", 0.0), ("Upper", 1.001)] {
+        let base = mesh.nodes.len();
+        for k in 0..2 {
+            for j in 0..=ny {
+                for i in 0..=nx {
+                    mesh.nodes.push(Point::new(i as f64, j as f64, z0 + k as f64));
+                }
+            }
+        }
+        let node =
+            |i: usize, j: usize, k: usize| base + (k * (ny + 1) + j) * (nx + 1) + i;
+        let mut elements = Vec::new();
+        for j in 0..ny {
+            for i in 0..nx {
+                elements.push(mesh.elements.len());
+                mesh.elements.push(HexElement::new([
+                    node(i, j, 0),
+                    node(i + 1, j, 0),
+                    node(i + 1, j + 1, 0),
+                    node(i, j + 1, 0),
+                    node(i, j, 1),
+                    node(i + 1, j, 1),
+                    node(i + 1, j + 1, 1),
+                    node(i, j + 1, 1),
+                ]));
+            }
+        }
+        mesh.element_blocks.insert(block.to_string(), elements);
+    }
+    mesh
+}
+
+#[test]
+fn test_output_is_deterministic() {
+    // Large enough for parallel detection
+    let mesh = create_stacked_slabs(40, 30);
+    let criteria = ContactCriteria::new(0.01, 0.01, 180.0);
+
+    let run = || {
+        let surfaces = extract_surface(&mesh).unwrap();
+        let at_height = |z: f64| {
+            surfaces
+                .iter()
+                .find(|s| s.faces.len() == 1200 && (s.face_centroids[0].z - z).abs() < 1e-9)
+                .unwrap()
+        };
+        let (lower, upper) = (at_height(1.0), at_height(1.001));
+        let results = detect_contact_pairs(lower, upper, &criteria).unwrap();
+        let names: Vec<String> = surfaces.iter().map(|s| s.part_name.clone()).collect();
+        let faces: Vec<[usize; 4]> = surfaces
+            .iter()
+            .flat_map(|s| s.faces.iter().map(|f| f.node_ids))
+            .collect();
+        let pairs: Vec<(usize, usize, u64)> = results
+            .pairs
+            .iter()
+            .map(|p| (p.surface_a_face_id, p.surface_b_face_id, p.distance.to_bits()))
+            .collect();
+        (names, faces, pairs, results.unpaired_a, results.unpaired_b)
+    };
+
+    let first = run();
+    assert_eq!(first.2.len(), 1200);
+    assert!(first.2.windows(2).all(|w| (w[0].0, w[0].1) < (w[1].0, w[1].1)));
+    for _ in 0..3 {
+        assert_eq!(run(), first);
+    }
+
+    // Equal meshes (in maps with different hash seeds) write identical files
+    let dir = std::env::temp_dir().join("test_output_is_deterministic");
+    std::fs::create_dir_all(&dir).unwrap();
+    for name in ["a.json", "b.json"] {
+        let mut mesh = create_stacked_slabs(2, 2);
+        for set in ["top", "bottom", "left", "right"] {
+            mesh.node_sets.insert(set.to_string(), vec![0]);
+        }
+        write_json_mesh(&mesh, dir.join(name)).unwrap();
+    }
+    assert_eq!(
+        std::fs::read(dir.join("a.json")).unwrap(),
+        std::fs::read(dir.join("b.json")).unwrap()
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_merged_skin_export() {
+    use contact_detector::io::write_merged_skin_to_vtu;
+    use vtkio::model::{Attribute, DataSet, IOBuffer, Piece};
+
+    let mesh = create_two_block_mesh();
+    let surfaces = extract_surface(&mesh).expect("Surface extraction should succeed");
+    let path = std::env::temp_dir().join("test_merged_skin_export.vtu");
+    write_merged_skin_to_vtu(&mesh, &surfaces, &path, None, None).unwrap();
+
+    let vtk = vtkio::Vtk::import(&path).expect("Merged skin should be readable");
+    let _ = std::fs::remove_file(&path);
+    let DataSet::UnstructuredGrid { pieces, .. } = vtk.data else {
+        panic!("Expected an unstructured grid");
+    };
+    let Piece::Inline(piece) = &pieces[0] else {
+        panic!("Expected an inline piece");
+    };
+    let array = |name: &str| -> Vec<i32> {
+        piece
+            .data
+            .cell
+            .iter()
+            .find_map(|a| match a {
+                Attribute::DataArray(array) if array.name == name => match &array.data {
+                    IOBuffer::I32(values) => Some(values.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("Missing cell array {}", name))
+    };
+
+    // Every face of both cubes, each on a side of its own element
+    let source_block = array("SourceBlock");
+    assert_eq!(source_block.len(), 12);
+    assert_eq!(source_block.iter().filter(|&&b| b == 0).count(), 6);
+    assert_eq!(source_block.iter().filter(|&&b| b == 1).count(), 6);
+    assert_eq!(array("ElementId"), source_block.iter().map(|b| b + 1).collect::<Vec<_>>());
+    assert!(array("ElementSide").iter().all(|s| (1..=6).contains(s)));
+
+    let patch_id = array("PatchId");
+    assert_eq!(*patch_id.last().unwrap() as usize, surfaces.len() - 1);
+    assert!(patch_id.windows(2).all(|w| w[0] <= w[1]));
+}
+
+/// Run the command line tool from the crate root, returning its standard output
+fn run_cli(args: &[&str]) -> String {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_contact-detector"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("Failed to run contact-detector");
+    assert!(
+        output.status.success(),
+        "contact-detector {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("Output should be UTF-8")
+}
+
+#[test]
+fn test_cli_log_file() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let log_path = temp_dir.path().join("run.log");
+    let skin_path = temp_dir.path().join("skin.vtu");
+    run_cli(&[
+        "skin",
+        "test-data/stacked-cubes.json",
+        "-o",
+        skin_path.to_str().unwrap(),
+        "--quiet",
+        "--log-file",
+        log_path.to_str().unwrap(),
+    ]);
+    assert!(skin_path.exists());
+
+    // The file records debug events and stage timings even with --quiet
+    let log = std::fs::read_to_string(&log_path).expect("Log file should be written");
+    assert!(log.contains("DEBUG"), "No debug events in log:\n{}", log);
+    assert!(log.contains("extract_surface"), "No surface extraction span in log:\n{}", log);
+    assert!(log.contains("close time.busy="), "No stage timings in log:\n{}", log);
+    assert!(!log.contains("\u{1b}["), "Log file should not contain ANSI escapes");
+}
+
+#[test]
+fn test_cli_dry_run() {
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let output_dir = temp_dir.path().join("out");
+    let output = output_dir.to_str().unwrap();
+
+    // Planned surface pairs and output files, with nothing written
+    let listing = run_cli(&[
+        "auto-contact",
+        "test-data/stacked-cubes.json",
+        "-o",
+        output,
+        "--dry-run",
+        "--ascii",
+    ]);
+    assert!(listing.contains("DRY RUN"));
+    assert!(listing.contains("45 of 66 surface pair(s) would be tested (21 too far apart)"));
+    assert!(listing.contains("  Lower:patch_5 <-> Upper:patch_0: 1 x 1 faces\n"));
+    assert!(listing.contains("contact_analysis.vtm"));
+    assert!(!output_dir.exists());
+
+    let listing = run_cli(&[
+        "analyze",
+        "test-data/stacked-cubes.json",
+        "--pairs",
+        "Lower:Upper",
+        "-o",
+        output,
+        "--dry-run",
+        "--ascii",
+    ]);
+    assert!(listing.contains("  [1] Lower <-> Upper: 6 x 6 faces\n"));
+    assert!(listing.contains("  contact_Lower_Upper.vtu\n"));
+    assert!(!output_dir.exists());
+}